@@ -0,0 +1,49 @@
+#include <assert.h>
+#include <stdint.h>
+#include <string.h>
+
+#include "arithmetic.h"
+
+// Check the status of a call and free the error buffer if there is one
+static void check_status(RustCallStatus *status, int8_t expected_code) {
+    assert(status->code == expected_code);
+    if (status->code != UNIFFI_CALL_SUCCESS) {
+        assert(status->errorBuf.len > 0);
+        RustCallStatus free_status;
+        memset(&free_status, 0, sizeof(free_status));
+        ffi_arithmetical_rustbuffer_free(status->errorBuf, &free_status);
+        assert(free_status.code == UNIFFI_CALL_SUCCESS);
+    }
+    memset(status, 0, sizeof(*status));
+}
+
+int main(void) {
+    RustCallStatus status;
+    memset(&status, 0, sizeof(status));
+
+    assert(ffi_arithmetical_uniffi_contract_version() == UNIFFI_CONTRACT_VERSION);
+    assert(uniffi_arithmetical_checksum_func_add() != 0);
+    assert(uniffi_arithmetical_checksum_func_equal() != 0);
+
+    assert(uniffi_arithmetical_fn_func_add(2, 4, &status) == 6);
+    check_status(&status, UNIFFI_CALL_SUCCESS);
+
+    uniffi_arithmetical_fn_func_add(UINT64_MAX, 1, &status);
+    check_status(&status, UNIFFI_CALL_ERROR);
+
+    assert(uniffi_arithmetical_fn_func_sub(4, 2, &status) == 2);
+    check_status(&status, UNIFFI_CALL_SUCCESS);
+
+    assert(uniffi_arithmetical_fn_func_div(8, 4, &status) == 2);
+    check_status(&status, UNIFFI_CALL_SUCCESS);
+
+    uniffi_arithmetical_fn_func_div(8, 0, &status);
+    check_status(&status, UNIFFI_CALL_UNEXPECTED_ERROR);
+
+    assert(uniffi_arithmetical_fn_func_equal(2, 2, &status) == 1);
+    check_status(&status, UNIFFI_CALL_SUCCESS);
+    assert(uniffi_arithmetical_fn_func_equal(2, 4, &status) == 0);
+    check_status(&status, UNIFFI_CALL_SUCCESS);
+
+    return 0;
+}
@@ -3,4 +3,5 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_arithmetic.py",
     "tests/bindings/test_arithmetic.kts",
     "tests/bindings/test_arithmetic.swift",
+    "tests/bindings/test_arithmetic.c",
 );
@@ -1,5 +1,3 @@
-use url::Url;
-
 // A custom guid defined via a proc-macro (ie, not referenced in the UDL)
 // By far the easiest way to define custom types.
 pub struct ExampleCustomType(String);
@@ -33,7 +31,17 @@ impl UniffiCustomTypeConverter for Handle {
     }
 }
 
-// Use `url::Url` as a custom type, with `String` as the Builtin
+// Custom Url type which wraps a `url::Url`.  `url::Url` itself is a built-in type when the `url`
+// feature of `uniffi` is enabled, which other crates in this workspace do.
+pub struct Url(url::Url);
+
+impl Url {
+    pub fn parse(input: &str) -> Result<Self, url::ParseError> {
+        url::Url::parse(input).map(Self)
+    }
+}
+
+// Use `Url` as a custom type, with `String` as the Builtin
 impl UniffiCustomTypeConverter for Url {
     type Builtin = String;
 
@@ -42,7 +50,7 @@ impl UniffiCustomTypeConverter for Url {
     }
 
     fn from_custom(obj: Self) -> Self::Builtin {
-        obj.into()
+        obj.0.into()
     }
 }
 
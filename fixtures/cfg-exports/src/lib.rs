@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+#[uniffi::export]
+pub fn always_exported() -> u32 {
+    1
+}
+
+#[uniffi::export]
+#[cfg(feature = "gated")]
+pub fn gated_function() -> u32 {
+    2
+}
+
+#[uniffi::export]
+#[cfg(not(feature = "gated"))]
+pub fn ungated_function() -> u32 {
+    3
+}
+
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: u32,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self { value: 0 })
+    }
+
+    #[uniffi::constructor]
+    #[cfg(feature = "gated")]
+    pub fn gated_new(value: u32) -> Arc<Self> {
+        Arc::new(Self { value })
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    #[cfg(feature = "gated")]
+    pub fn gated_value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[uniffi::export]
+#[cfg(feature = "gated")]
+pub trait GatedTrait: Send + Sync {
+    fn get(&self) -> u32;
+}
+
+#[uniffi::export]
+#[cfg(feature = "gated")]
+pub const GATED_CONSTANT: u32 = 4;
+
+uniffi::setup_scaffolding!("cfg_exports");
@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The library is built without the `gated` feature, so the items it gates must not be in the
+// metadata.
+#[cfg(not(feature = "gated"))]
+#[test]
+fn test_gated_items_are_not_exported() {
+    use uniffi_bindgen::{library_mode::find_components, EmptyCrateConfigSupplier};
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let components = find_components(
+        &test_helper.cdylib_path().unwrap(),
+        &EmptyCrateConfigSupplier,
+    )
+    .unwrap();
+    let ci = &components
+        .iter()
+        .find(|c| c.ci.crate_name() == "uniffi_fixture_cfg_exports")
+        .unwrap()
+        .ci;
+
+    assert!(ci.get_function_definition("always_exported").is_some());
+    assert!(ci.get_function_definition("ungated_function").is_some());
+    assert!(ci.get_function_definition("gated_function").is_none());
+
+    let counter = ci.get_object_definition("Counter").unwrap();
+    let constructors: Vec<_> = counter.constructors().iter().map(|c| c.name()).collect();
+    assert_eq!(constructors, ["new"]);
+    let methods: Vec<_> = counter.methods().iter().map(|m| m.name()).collect();
+    assert_eq!(methods, ["value"]);
+
+    assert!(ci.get_object_definition("GatedTrait").is_none());
+    assert!(ci.constant_definitions().is_empty());
+}
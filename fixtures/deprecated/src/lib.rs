@@ -0,0 +1,105 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+fn fetch_sync(url: String) -> String {
+    format!("GET {url}")
+}
+
+pub struct LegacyRequest {
+    url: String,
+    #[allow(dead_code)]
+    user_agent: String,
+}
+
+pub struct Client;
+
+impl Client {
+    fn new() -> Self {
+        Self
+    }
+
+    fn get(&self, url: String) -> String {
+        fetch_sync(url)
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct Request {
+    url: String,
+    #[uniffi(deprecated = "timeouts are set on the session")]
+    timeout_secs: u32,
+}
+
+#[derive(uniffi::Enum)]
+pub enum Method {
+    Get,
+    #[uniffi(deprecated = "use Method.Get instead")]
+    Head,
+    Post {
+        #[uniffi(deprecated)]
+        content_type: String,
+        body: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum FetchError {
+    #[error("NotFound")]
+    NotFound,
+    #[error("TimedOut")]
+    #[uniffi(deprecated = "fetches no longer time out")]
+    TimedOut,
+}
+
+#[uniffi::export]
+fn fetch(request: Request) -> Result<String, FetchError> {
+    if request.url.is_empty() {
+        return Err(FetchError::NotFound);
+    }
+    Ok(format!("GET {}", request.url))
+}
+
+#[uniffi::export(deprecated)]
+fn fetch_legacy(request: LegacyRequest) -> String {
+    fetch_sync(request.url)
+}
+
+#[uniffi::export]
+fn method_name(method: Method) -> String {
+    match method {
+        Method::Get => "GET",
+        Method::Head => "HEAD",
+        Method::Post { .. } => "POST",
+    }
+    .to_string()
+}
+
+#[derive(uniffi::Object)]
+pub struct Session;
+
+#[uniffi::export]
+impl Session {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    #[uniffi::constructor(deprecated = "use Session.new() instead")]
+    fn open() -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    fn fetch(&self, request: Request) -> Result<String, FetchError> {
+        fetch(request)
+    }
+
+    #[uniffi::method(deprecated = "use fetch() instead")]
+    fn fetch_sync(&self, url: String) -> String {
+        fetch_sync(url)
+    }
+}
+
+uniffi::include_scaffolding!("deprecated");
@@ -0,0 +1,28 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This test ensures the generated code works as expected when items are deprecated.  The
+// deprecations are only compiler warnings, so we don't check for them here.
+
+import uniffi.fixture.deprecated.*;
+
+assert(fetchSync("a") == "GET a")
+assert(fetchLegacy(LegacyRequest("b", "uniffi")) == "GET b")
+assert(fetch(Request("c", 1u)) == "GET c")
+
+assert(Client().get("a") == "GET a")
+
+val session = Session.open()
+assert(session.fetchSync("b") == "GET b")
+assert(Session().fetch(Request("c", 1u)) == "GET c")
+
+assert(methodName(Method.Head) == "HEAD")
+assert(methodName(Method.Post("text/plain", "")) == "POST")
+
+try {
+    fetch(Request("", 1u))
+    throw RuntimeException("Should have thrown")
+} catch (e: FetchException.NotFound) {
+    // It's okay!
+}
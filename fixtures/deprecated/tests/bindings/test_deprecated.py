@@ -0,0 +1,66 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import unittest
+import warnings
+from uniffi_deprecated import *
+
+class TestDeprecated(unittest.TestCase):
+    def assert_deprecated(self, message, call):
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            result = call()
+        self.assertEqual([(w.category, str(w.message)) for w in caught], [(DeprecationWarning, message)])
+        # The warning points at the caller, rather than the generated bindings.
+        self.assertEqual(caught[0].filename, __file__)
+        return result
+
+    def assert_not_deprecated(self, call):
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            result = call()
+        self.assertEqual(caught, [])
+        return result
+
+    def test_functions(self):
+        self.assertEqual(self.assert_deprecated("use fetch() instead", lambda: fetch_sync("a")), "GET a")
+        self.assertEqual(
+            self.assert_deprecated(
+                "fetch_legacy is deprecated",
+                lambda: fetch_legacy(LegacyRequest(url="b", user_agent="uniffi")),
+            ),
+            "GET b",
+        )
+        self.assertEqual(self.assert_not_deprecated(lambda: fetch(Request(url="c", timeout_secs=1))), "GET c")
+
+    def test_methods(self):
+        client = Client()
+        self.assertEqual(self.assert_deprecated("use Session.fetch() instead", lambda: client.get("a")), "GET a")
+
+        session = self.assert_not_deprecated(lambda: Session())
+        self.assertEqual(self.assert_deprecated("use fetch() instead", lambda: session.fetch_sync("b")), "GET b")
+        self.assertEqual(self.assert_not_deprecated(lambda: session.fetch(Request(url="c", timeout_secs=1))), "GET c")
+
+    def test_constructors(self):
+        session = self.assert_deprecated("use Session.new() instead", lambda: Session.open())
+        self.assertEqual(session.fetch(Request(url="a", timeout_secs=1)), "GET a")
+
+    def test_docstrings(self):
+        self.assertEqual(fetch_sync.__doc__.strip(), "Deprecated: use fetch() instead")
+        self.assertEqual(fetch_legacy.__doc__.strip(), "Deprecated.")
+        self.assertIsNone(fetch.__doc__)
+        self.assertEqual(Session.fetch_sync.__doc__.strip(), "Deprecated: use fetch() instead")
+        self.assertEqual(Session.open.__doc__.strip(), "Deprecated: use Session.new() instead")
+        self.assertEqual(LegacyRequest.__doc__.strip(), "Deprecated: use Request instead")
+        self.assertEqual(FetchError.TimedOut.__doc__.strip(), "Deprecated: fetches no longer time out")
+
+    def test_types(self):
+        # Using deprecated types and variants doesn't warn.
+        self.assertEqual(self.assert_not_deprecated(lambda: method_name(Method.HEAD())), "HEAD")
+        self.assertEqual(method_name(Method.POST(content_type="text/plain", body="")), "POST")
+        with self.assertRaises(FetchError.NotFound):
+            fetch(Request(url="", timeout_secs=1))
+
+if __name__ == '__main__':
+    unittest.main()
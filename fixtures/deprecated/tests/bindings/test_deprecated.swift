@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This test ensures the generated code works as expected when items are deprecated.  The
+// deprecations are only compiler warnings, so we don't check for them here.
+
+import uniffi_deprecated
+
+assert(fetchSync(url: "a") == "GET a")
+assert(fetchLegacy(request: LegacyRequest(url: "b", userAgent: "uniffi")) == "GET b")
+assert(try! fetch(request: Request(url: "c", timeoutSecs: 1)) == "GET c")
+
+assert(Client().get(url: "a") == "GET a")
+
+let session = Session.open()
+assert(session.fetchSync(url: "b") == "GET b")
+assert(try! Session().fetch(request: Request(url: "c", timeoutSecs: 1)) == "GET c")
+
+assert(methodName(method: .head) == "HEAD")
+assert(methodName(method: .post(contentType: "text/plain", body: "")) == "POST")
+
+do {
+    let _ = try fetch(request: Request(url: "", timeoutSecs: 1))
+    fatalError("Should have thrown")
+} catch FetchError.NotFound {
+    // It's okay!
+} catch {
+    fatalError("Unexpected error: \(error)")
+}
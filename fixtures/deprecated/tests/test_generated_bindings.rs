@@ -0,0 +1,145 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_deprecated.kts",
+    "tests/bindings/test_deprecated.swift",
+    "tests/bindings/test_deprecated.py",
+);
+
+#[cfg(test)]
+mod tests {
+    use uniffi_bindgen::{bindings::*, BindingGenerator};
+    use uniffi_testing::UniFFITestHelper;
+
+    // The deprecation messages of the fixture, along with the items deprecated without one.
+    const MESSAGES: &[&str] = &[
+        "fetches no longer time out",
+        "timeouts are set on the session",
+        "use Method.Get instead",
+        "use Request instead",
+        "use Session.fetch() instead",
+        "use Session.new() instead",
+        "use fetch() instead",
+    ];
+
+    // Generate the bindings in library mode, so they include both the UDL and proc-macro items.
+    fn generate_bindings<T>(gen: T, file_extension: &str) -> String
+    where
+        T: BindingGenerator + Sync,
+        T::Config: Sync,
+    {
+        let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+        let out_dir = test_helper
+            .create_out_dir(
+                std::env!("CARGO_TARGET_TMPDIR"),
+                format!("test-deprecated-{file_extension}"),
+            )
+            .unwrap();
+        let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir).unwrap();
+
+        uniffi_bindgen::library_mode::generate_bindings(
+            &cdylib_path,
+            None,
+            &gen,
+            &uniffi_bindgen::cargo_metadata::CrateConfigSupplier::from(
+                test_helper.cargo_metadata(),
+            ),
+            None,
+            &out_dir,
+            false,
+        )
+        .unwrap();
+
+        let glob_pattern = out_dir.join(format!("**/*.{file_extension}"));
+        let sources = glob::glob(glob_pattern.as_str())
+            .unwrap()
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(sources.len(), 1);
+        std::fs::read_to_string(&sources[0]).unwrap()
+    }
+
+    // Check every message is in the bindings, rendered by `render`, and count the items
+    // deprecated without a message.
+    fn check_deprecations(
+        bindings: &str,
+        render: impl Fn(&str) -> String,
+        without_message: &str,
+        without_message_count: usize,
+    ) {
+        let missing = MESSAGES
+            .iter()
+            .map(|message| render(message))
+            .filter(|deprecation| !bindings.contains(deprecation))
+            .collect::<Vec<_>>();
+        assert_eq!(missing, Vec::<String>::new(), "deprecations not found");
+        assert_eq!(
+            bindings.matches(without_message).count(),
+            without_message_count
+        );
+    }
+
+    #[test]
+    fn test_deprecated_kotlin() {
+        let bindings = generate_bindings(KotlinBindingGenerator, "kt");
+        // `fetch_legacy()`, `LegacyRequest.user_agent` and `Method.Post.content_type`
+        check_deprecations(
+            &bindings,
+            |message| format!("@Deprecated(\"{message}\")"),
+            "@Deprecated(\"\")",
+            3,
+        );
+    }
+
+    #[test]
+    fn test_deprecated_swift() {
+        let bindings = generate_bindings(SwiftBindingGenerator, "swift");
+        // `fetch_legacy()` and `LegacyRequest.user_agent`.  Swift can't deprecate the
+        // associated values of an enum case.
+        check_deprecations(
+            &bindings,
+            |message| format!("@available(*, deprecated, message: \"{message}\")"),
+            "@available(*, deprecated)\n",
+            2,
+        );
+    }
+
+    #[test]
+    fn test_deprecated_python() {
+        let bindings = generate_bindings(PythonBindingGenerator, "py");
+        check_deprecations(
+            &bindings,
+            |message| format!("Deprecated: {message}"),
+            "Deprecated.",
+            3,
+        );
+        // Only the functions, methods and constructors warn when they're called.
+        assert!(bindings
+            .contains("warnings.warn(\"use fetch() instead\", DeprecationWarning, stacklevel=2)"));
+        assert!(bindings.contains(
+            "warnings.warn(\"fetch_legacy is deprecated\", DeprecationWarning, stacklevel=2)"
+        ));
+        assert!(!bindings.contains("warnings.warn(\"use Request instead\""));
+    }
+
+    #[test]
+    fn test_deprecated_ruby() {
+        let bindings = generate_bindings(RubyBindingGenerator, "rb");
+        // Ruby has no deprecation attribute, so the items get a comment.  The fields are
+        // deprecated in a comment before their `attr_reader`.
+        let missing = [
+            "# DEPRECATED: fetches no longer time out\n",
+            "# DEPRECATED `timeout_secs`: timeouts are set on the session\n",
+            "# DEPRECATED: use Method.Get instead\n",
+            "# DEPRECATED: use Request instead\n",
+            "# DEPRECATED: use Session.fetch() instead\n",
+            "# DEPRECATED: use Session.new() instead\n",
+            "# DEPRECATED: use fetch() instead\n",
+            "# DEPRECATED\n",
+            "# DEPRECATED `user_agent`\n",
+            "# DEPRECATED `content_type`\n",
+        ]
+        .into_iter()
+        .filter(|comment| !bindings.contains(comment))
+        .collect::<Vec<_>>();
+        assert_eq!(missing, Vec::<&str>::new(), "deprecations not found");
+    }
+}
@@ -42,7 +42,10 @@ pub enum ErrorTest {
 pub enum AssociatedErrorTest {
     /// <docstring-associated-error-variant>
     #[error("Test")]
-    Test { code: i16 },
+    Test {
+        /// <docstring-error-variant-field>
+        code: i16,
+    },
     /// <docstring-associated-error-variant-2>
     #[error("Test2")]
     Test2 { code: i16 },
@@ -75,6 +78,15 @@ impl ObjectTest {
 struct RecordTest {
     /// <docstring-record-field>
     test: i32,
+    /// <docstring-multiline-field>
+    ///
+    /// Markdown passes through, including `code`, paths like `src/*.rs`, regexes like `\d+` and
+    /// "quotes":
+    ///
+    /// ```text
+    /// let x = 1; /* a comment */
+    /// ```
+    multiline: i32,
 }
 
 /// <docstring-function>
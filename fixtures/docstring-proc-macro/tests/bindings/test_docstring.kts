@@ -28,7 +28,7 @@ val obj1 = ObjectTest
 val obj2 = ObjectTest.newAlternate()
 obj2.test()
 
-val rec = RecordTest(123)
+val rec = RecordTest(123, 456)
 val recField = rec.test
 
 class CallbackImpls() : CallbackTest {
@@ -28,7 +28,7 @@ var obj1 = ObjectTest()
 var obj2 = ObjectTest.newAlternate()
 obj2.test()
 
-var rec = RecordTest(test: 123)
+var rec = RecordTest(test: 123, multiline: 456)
 var recField = rec.test
 
 class CallbackImpls: CallbackTest {
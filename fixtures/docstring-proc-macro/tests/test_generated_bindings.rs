@@ -23,10 +23,12 @@ mod tests {
         "<docstring-enum-variant>",
         "<docstring-enum>",
         "<docstring-error-variant-2>",
+        "<docstring-error-variant-field>",
         "<docstring-error-variant>",
         "<docstring-error>",
         "<docstring-function>",
         "<docstring-method>",
+        "<docstring-multiline-field>",
         "<docstring-namespace>",
         "<docstring-object>",
         "<docstring-primary-constructor>",
@@ -35,7 +37,11 @@ mod tests {
         "<docstring-variant-field>",
     ];
 
-    fn test_docstring<T: BindingGenerator>(gen: T, file_extension: &str) {
+    fn test_docstring<T>(gen: T, file_extension: &str)
+    where
+        T: BindingGenerator + Sync,
+        T::Config: Sync,
+    {
         let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
 
         let out_dir = test_helper
@@ -62,4 +62,8 @@ pub trait CallbackTest {
     fn test(&self);
 }
 
+pub trait TraitTest: Send + Sync {
+    fn test(&self);
+}
+
 uniffi::include_scaffolding!("docstring");
@@ -6,7 +6,7 @@ uniffi::build_foreign_language_testcases!(
 
 #[cfg(test)]
 mod tests {
-    use camino::Utf8PathBuf;
+    use camino::{Utf8Path, Utf8PathBuf};
     use uniffi_bindgen::{bindings::*, BindingGenerator};
     use uniffi_testing::UniFFITestHelper;
 
@@ -20,10 +20,12 @@ mod tests {
         "<docstring-associated-error>",
         "<docstring-callback-method>",
         "<docstring-callback>",
+        "<docstring-constant>",
         "<docstring-enum-variant-2>",
         "<docstring-enum-variant>",
         "<docstring-enum>",
         "<docstring-error-variant-2>",
+        "<docstring-error-variant-field>",
         "<docstring-error-variant>",
         "<docstring-error>",
         "<docstring-function>",
@@ -33,39 +35,26 @@ mod tests {
         "<docstring-primary-constructor>",
         "<docstring-record-field>",
         "<docstring-record>",
+        "<docstring-trait-method>",
+        "<docstring-trait>",
+        "<docstring-variant-field>",
     ];
 
-    fn test_docstring<T: BindingGenerator>(gen: T, file_extension: &str) {
-        let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
-
-        let out_dir = test_helper
-            .create_out_dir(
-                std::env!("CARGO_TARGET_TMPDIR"),
-                format!(
-                    "test-docstring-{}",
-                    file_extension.to_string().replace('.', "")
-                ),
-            )
-            .unwrap();
-
-        uniffi_bindgen::generate_bindings(
-            &Utf8PathBuf::from("src/docstring.udl"),
-            None,
-            gen,
-            Some(&out_dir),
-            None,
-            None,
-            false,
-        )
-        .unwrap();
+    fn create_out_dir(test_helper: &UniFFITestHelper, name: &str) -> Utf8PathBuf {
+        test_helper
+            .create_out_dir(std::env!("CARGO_TARGET_TMPDIR"), name)
+            .unwrap()
+    }
 
+    // Check the bindings written to `out_dir` contain every docstring
+    fn check_docstrings(out_dir: &Utf8Path, file_extension: &str) -> Utf8PathBuf {
         let glob_pattern = out_dir.join(format!("**/*.{}", file_extension));
 
         let sources = glob::glob(glob_pattern.as_str())
             .unwrap()
             .flatten()
-            .map(|p| String::from(p.to_string_lossy()))
-            .collect::<Vec<String>>();
+            .map(|p| Utf8PathBuf::try_from(p).unwrap())
+            .collect::<Vec<_>>();
 
         assert_eq!(sources.len(), 1);
 
@@ -82,20 +71,124 @@ mod tests {
             "docstrings not found in {}",
             &sources[0]
         );
+        sources[0].clone()
+    }
+
+    fn test_docstring<T: BindingGenerator>(gen: T, file_extension: &str) {
+        let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+        let out_dir = create_out_dir(
+            &test_helper,
+            &format!("test-docstring-{}", file_extension.replace('.', "")),
+        );
+
+        let report = uniffi_bindgen::generate_bindings(
+            &Utf8PathBuf::from("src/docstring.udl"),
+            None,
+            gen,
+            Some(&out_dir),
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+
+        let source = check_docstrings(&out_dir, file_extension);
+
+        // The bindings are reported, along with any other files like Swift headers
+        let written_files = &report.written_files["uniffi_fixture_docstring"];
+        assert!(written_files.contains(&source));
+        assert!(written_files.iter().all(|f| f.is_file()));
+    }
+
+    // Library mode reads the docstrings from the UDL file, since they aren't in the library.
+    fn test_docstring_library_mode<T>(gen: T, file_extension: &str)
+    where
+        T: BindingGenerator + Sync,
+        T::Config: Sync,
+    {
+        let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+        let out_dir = create_out_dir(
+            &test_helper,
+            &format!("test-docstring-library-{}", file_extension.replace('.', "")),
+        );
+        let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir).unwrap();
+
+        uniffi_bindgen::library_mode::generate_bindings(
+            &cdylib_path,
+            None,
+            &gen,
+            &uniffi_bindgen::cargo_metadata::CrateConfigSupplier::from(
+                test_helper.cargo_metadata(),
+            ),
+            None,
+            &out_dir,
+            false,
+        )
+        .unwrap();
+
+        check_docstrings(&out_dir, file_extension);
+    }
+
+    // Reads the UDL file from a string, rather than the one the library was built from
+    struct UdlSupplier(String);
+
+    impl uniffi_bindgen::BindgenCrateConfigSupplier for UdlSupplier {
+        fn get_udl_file(
+            &self,
+            _crate_name: &str,
+            _udl_file: &uniffi_meta::UdlFile,
+        ) -> anyhow::Result<String> {
+            Ok(self.0.clone())
+        }
+    }
+
+    // `uniffi-bindgen check` should find a library which was built from a different UDL file.
+    #[test]
+    fn test_check_udl_drift() {
+        let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+        let cdylib_path = test_helper.cdylib_path().unwrap();
+        let udl = std::fs::read_to_string("src/docstring.udl").unwrap();
+
+        let report =
+            uniffi_bindgen::check::find_mismatches(&cdylib_path, &UdlSupplier(udl.clone()))
+                .unwrap();
+        assert!(report.is_empty(), "{report}");
+
+        let drifted_udl = udl.replacen(
+            "[Throws=ErrorTest] void test();",
+            "[Throws=ErrorTest] u32 test();",
+            1,
+        );
+        assert_ne!(udl, drifted_udl);
+        let report =
+            uniffi_bindgen::check::find_mismatches(&cdylib_path, &UdlSupplier(drifted_udl))
+                .unwrap();
+        let report = report.to_string();
+        assert!(
+            report.starts_with(
+                "crate `uniffi_fixture_docstring`:\n  \
+                 checksum function `uniffi_uniffi_fixture_docstring_checksum_func_test`: checksum\n"
+            ),
+            "{report}"
+        );
+        assert_eq!(report.matches("checksum function").count(), 1, "{report}");
     }
 
     #[test]
     fn test_docstring_kotlin() {
         test_docstring(KotlinBindingGenerator, "kt");
+        test_docstring_library_mode(KotlinBindingGenerator, "kt");
     }
 
     #[test]
     fn test_docstring_python() {
         test_docstring(PythonBindingGenerator, "py");
+        test_docstring_library_mode(PythonBindingGenerator, "py");
     }
 
     #[test]
     fn test_docstring_swift() {
         test_docstring(SwiftBindingGenerator, "swift");
+        test_docstring_library_mode(SwiftBindingGenerator, "swift");
     }
 }
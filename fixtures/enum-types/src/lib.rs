@@ -7,6 +7,13 @@ pub enum Animal {
     Cat,
 }
 
+// The scaffolding checks these values match the ones in the UDL.
+pub enum AnimalUdlInt {
+    Dog = 3,
+    Cat,
+    Fish = -1,
+}
+
 // Though it has the proc-macro, we drop the variant
 // literals if there is not a repr type defined
 #[derive(uniffi::Enum)]
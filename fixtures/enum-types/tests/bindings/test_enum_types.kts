@@ -14,6 +14,10 @@ assert(AnimalLargeUInt.CAT.value == 4294967299.toULong())
 // could check `value == (-3).toByte()` but that's ugly :)
 assert(AnimalSignedInt.DOG.value + 3 == 0)
 
+assert(AnimalUdlInt.DOG.value == 3L)
+assert(AnimalUdlInt.CAT.value == 4L)
+assert(AnimalUdlInt.FISH.value == -1L)
+
 // Assert that no destroy() function is created for simple Enum
 val simpleCat: Animal = Animal.CAT
 assert(simpleCat::class.functions.find { it.name == "destroy" } == null)
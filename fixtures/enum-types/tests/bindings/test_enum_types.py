@@ -28,5 +28,9 @@ class TestErrorTypes(unittest.TestCase):
         self.assertEqual(AnimalSignedInt.WALLABY.value, 0)
         self.assertEqual(AnimalSignedInt.WOMBAT.value, 1)
 
+        self.assertEqual(AnimalUdlInt.DOG.value, 3)
+        self.assertEqual(AnimalUdlInt.CAT.value, 4)
+        self.assertEqual(AnimalUdlInt.FISH.value, -1)
+
 if __name__=='__main__':
     unittest.main()
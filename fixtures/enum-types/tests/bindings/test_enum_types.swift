@@ -12,3 +12,7 @@ assert(AnimalLargeUInt.dog.rawValue == 4294967298)
 assert(AnimalLargeUInt.cat.rawValue == 4294967299)
 
 assert(AnimalSignedInt.dog.rawValue == -3)
+
+assert(AnimalUdlInt.dog.rawValue == 3)
+assert(AnimalUdlInt.cat.rawValue == 4)
+assert(AnimalUdlInt.fish.rawValue == -1)
@@ -128,6 +128,20 @@ pub struct ProcErrorInterface {
 
 #[uniffi::export]
 impl ProcErrorInterface {
+    // Secondary constructors of an object which is also an error
+    #[uniffi::constructor(default(e = "default"))]
+    fn with_message(e: String) -> Self {
+        Self { e }
+    }
+
+    #[uniffi::constructor]
+    fn non_empty(e: String) -> Result<Self, Arc<ProcErrorInterface>> {
+        if e.is_empty() {
+            return Err(Arc::new(Self { e: "empty".into() }));
+        }
+        Ok(Self { e })
+    }
+
     fn message(&self) -> String {
         self.e.clone()
     }
@@ -246,4 +260,39 @@ fn get_tuple(t: Option<TupleError>) -> TupleError {
     t.unwrap_or_else(|| TupleError::Oops("oops".to_string()))
 }
 
+// A two-level chain of errors: `LoadError` is caused by a `StorageError`, which is caused by an
+// error that can't cross the FFI, passed as a string.
+#[derive(thiserror::Error, uniffi::Error, Debug)]
+pub enum StorageError {
+    #[error("read failed")]
+    ReadFailed {
+        #[uniffi(source)]
+        reason: String,
+    },
+}
+
+#[derive(thiserror::Error, uniffi::Error, Debug)]
+pub enum LoadError {
+    #[error("can't load {path}")]
+    Storage {
+        path: String,
+        #[source]
+        #[uniffi(source)]
+        source: StorageError,
+    },
+    #[error("not found")]
+    NotFound,
+}
+
+#[uniffi::export]
+fn load(path: String) -> Result<(), LoadError> {
+    let io_error = anyhow::Error::msg("disk full").context("reading header");
+    Err(LoadError::Storage {
+        path,
+        source: StorageError::ReadFailed {
+            reason: uniffi::format_error_chain(io_error.as_ref()),
+        },
+    })
+}
+
 uniffi::include_scaffolding!("error_types");
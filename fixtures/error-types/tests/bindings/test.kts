@@ -103,6 +103,16 @@ try {
     assert(e.toString() == "uniffi.error_types.TupleException${'$'}Value: v1=1")
 }
 
+try {
+    load("data.bin")
+    throw RuntimeException("Should have failed")
+} catch (e: LoadException.Storage) {
+    val cause = e.cause as StorageException.ReadFailed
+    assert(cause === e.`source`)
+    assert(cause.cause!!.message == "reading header: disk full")
+    assert(e.toString() == "uniffi.error_types.LoadException${'$'}Storage: path=data.bin, source=uniffi.error_types.StorageException${'$'}ReadFailed: reason=reading header: disk full")
+}
+
 runBlocking {
     try {
         aoops()
@@ -111,3 +121,13 @@ runBlocking {
         assert(e.toString() == "async-oops")
     }
 }
+
+// An object used as an error, which has secondary constructors that can throw it too.
+assert(ProcErrorInterface.withMessage().message() == "default")
+assert(ProcErrorInterface.nonEmpty("eek").message() == "eek")
+try {
+    ProcErrorInterface.nonEmpty("")
+    throw RuntimeException("Should have failed")
+} catch (e: ProcErrorInterface) {
+    assert(e.message() == "empty")
+}
@@ -80,6 +80,14 @@ class TestErrorTypes(unittest.TestCase):
         self.assertEqual(cm.exception.message(), "eek")
         self.assertEqual(str(cm.exception), "ProcErrorInterface(eek)")
 
+    # ProcErrorInterface has secondary constructors, which can throw it too.
+    def test_procmacro_interface_error_constructors(self):
+        self.assertEqual(ProcErrorInterface.with_message().message(), "default")
+        self.assertEqual(ProcErrorInterface.non_empty("eek").message(), "eek")
+        with self.assertRaises(ProcErrorInterface) as cm:
+            ProcErrorInterface.non_empty("")
+        self.assertEqual(cm.exception.message(), "empty")
+
     def test_enum_error(self):
         with self.assertRaises(Error) as cm:
             oops_enum(0)
@@ -116,6 +124,15 @@ class TestErrorTypes(unittest.TestCase):
         # XXX - can't compare Python errors.
         self.assertEqual(cm.exception.error[0], "inner")
 
+    def test_error_source(self):
+        with self.assertRaises(LoadError.Storage) as cm:
+            load("data.bin")
+        e = cm.exception
+        self.assertIsInstance(e.__cause__, StorageError.ReadFailed)
+        self.assertIs(e.__cause__, e.source)
+        self.assertEqual(str(e.__cause__.__cause__), "reading header: disk full")
+        self.assertEqual(str(e), "path='data.bin', source=StorageError.ReadFailed(reason='reading header: disk full')")
+
     def test_tuple_error(self):
         r = get_tuple()
         self.assertEqual(repr(r), "TupleError.Oops('oops')")
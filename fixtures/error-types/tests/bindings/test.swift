@@ -1,3 +1,4 @@
+import Foundation
 import error_types
 do {
     try oops()
@@ -94,6 +95,20 @@ do {
     assert(error.localizedDescription == "error_types.TupleError.Value(1)")
 }
 
+do {
+    try load(path: "data.bin")
+    fatalError("Should have thrown")
+} catch let e as LoadError {
+    guard case let .ReadFailed(reason)? = e.underlyingError as? StorageError else {
+        fatalError("Unexpected underlying error \(String(describing: e.underlyingError))")
+    }
+    assert(reason == "reading header: disk full")
+    let nsError = e as NSError
+    let underlying = nsError.userInfo[NSUnderlyingErrorKey] as! NSError
+    assert((underlying.userInfo[NSUnderlyingErrorKey] as! NSError).localizedDescription == "reading header: disk full")
+    assert(String(reflecting: e) == "error_types.LoadError.Storage(path: \"data.bin\", source: error_types.StorageError.ReadFailed(reason: \"reading header: disk full\"))")
+}
+
 do {
     try oopsNowrap()
     fatalError("Should have thrown")
@@ -113,3 +128,13 @@ assert(String(describing: e) == "the error")
 assert(String(reflecting: e) == "ErrorInterface { e: the error }")
 // assert(Error.self is Swift.Error.Type) -- always true!
 assert(Error.self != Swift.Error.self)
+
+// An object used as an error, which has secondary constructors that can throw it too.
+assert(ProcErrorInterface.withMessage().message() == "default")
+assert(try! ProcErrorInterface.nonEmpty(e: "eek").message() == "eek")
+do {
+    _ = try ProcErrorInterface.nonEmpty(e: "")
+    fatalError("Should have thrown")
+} catch let e as ProcErrorInterface {
+    assert(e.message() == "empty")
+}
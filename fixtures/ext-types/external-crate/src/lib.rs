@@ -14,6 +14,23 @@ pub enum ExternalCrateNonExhaustiveEnum {
     Two,
 }
 
+// This type is referenced as an "external" type as an error with fields.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExternalCrateNonExhaustiveError {
+    Failed { code: i32, reason: String },
+}
+
+impl std::fmt::Display for ExternalCrateNonExhaustiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Failed { code, reason } => write!(f, "failed with {code}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ExternalCrateNonExhaustiveError {}
+
 // This type is referenced as an "external" type as an interface.
 impl ExternalCrateInterface {
     pub fn new(sval: String) -> Self {
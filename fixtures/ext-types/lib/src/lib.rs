@@ -1,7 +1,8 @@
-use custom_types::Handle;
+use custom_types::{Handle, Url};
 use ext_types_custom::{ANestedGuid, Guid, Ouid};
 use ext_types_external_crate::{
     ExternalCrateDictionary, ExternalCrateInterface, ExternalCrateNonExhaustiveEnum,
+    ExternalCrateNonExhaustiveError,
 };
 use std::sync::Arc;
 use uniffi_one::{
@@ -9,7 +10,6 @@ use uniffi_one::{
     UniffiOneUDLTrait,
 };
 use uniffi_sublib::SubLibType;
-use url::Url;
 
 // #1988
 uniffi::ffi_converter_forward!(
@@ -197,6 +197,13 @@ fn get_external_crate_interface(val: String) -> Arc<ExternalCrateInterface> {
     Arc::new(ExternalCrateInterface::new(val))
 }
 
+fn throw_external_crate_error(
+    code: i32,
+    reason: String,
+) -> Result<(), ExternalCrateNonExhaustiveError> {
+    Err(ExternalCrateNonExhaustiveError::Failed { code, reason })
+}
+
 fn get_uniffi_one_udl_trait(
     t: Option<Arc<dyn UniffiOneUDLTrait>>,
 ) -> Option<Arc<dyn UniffiOneUDLTrait>> {
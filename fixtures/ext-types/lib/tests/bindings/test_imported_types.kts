@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.imported_types_lib.*
-import uniffi.imported_types_sublib.*
+import uniffi.ext_types.sublib.*
 import uniffi.uniffi_one_ns.*
 import uniffi.ext_types_custom.*
 
@@ -54,3 +54,11 @@ assert(getMaybeUniffiOneEnums(listOf(uoe, null)) == listOf(uoe, null))
 
 assert(ct.ecd.sval == "ecd")
 assert(getExternalCrateInterface("foo").value() == "foo")
+
+try {
+    throwExternalCrateError(3, "oops")
+    throw RuntimeException("Should have thrown")
+} catch (e: ExternalCrateNonExhaustiveException.Failed) {
+    assert(e.code == 3)
+    assert(e.reason == "oops")
+}
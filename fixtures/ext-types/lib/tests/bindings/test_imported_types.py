@@ -68,6 +68,12 @@ class TestIt(unittest.TestCase):
         self.assertEqual(ct.ecd.sval, "ecd");
         self.assertEqual(get_external_crate_interface("foo").value(), "foo")
 
+    def test_external_crate_error(self):
+        with self.assertRaises(ExternalCrateNonExhaustiveError.Failed) as cm:
+            throw_external_crate_error(3, "oops")
+        self.assertEqual(cm.exception.code, 3)
+        self.assertEqual(cm.exception.reason, "oops")
+
     def test_procmacro_types(self):
         t1 = UniffiOneProcMacroType(sval="hello")
         self.assertEqual(t1, get_uniffi_one_proc_macro_type(t1))
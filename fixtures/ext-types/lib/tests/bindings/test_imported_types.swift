@@ -52,3 +52,11 @@ assert(getMaybeUniffiOneEnums(es: [UniffiOneEnum.one, nil]) == [UniffiOneEnum.on
 
 assert(ct.ecd.sval == "ecd")
 assert(getExternalCrateInterface(val: "foo").value() == "foo")
+
+do {
+    try throwExternalCrateError(code: 3, reason: "oops")
+    fatalError("should have thrown")
+} catch let ExternalCrateNonExhaustiveError.Failed(code, reason) {
+    assert(code == 3)
+    assert(reason == "oops")
+}
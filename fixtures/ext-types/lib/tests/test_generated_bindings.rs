@@ -3,3 +3,55 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_imported_types.py",
     "tests/bindings/test_imported_types.swift",
 );
+
+// With a crate name, only that crate's bindings are written, but the types it uses from the other
+// crates in the library are still resolved.
+#[test]
+fn test_generate_one_crate() {
+    use uniffi_bindgen::{
+        bindings::PythonBindingGenerator, cargo_metadata::CrateConfigSupplier,
+        library_mode::generate_bindings,
+    };
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let out_dir = test_helper
+        .create_out_dir(std::env!("CARGO_TARGET_TMPDIR"), "test-generate-one-crate")
+        .unwrap();
+    let cdylib_path = test_helper.cdylib_path().unwrap();
+    let config_supplier = CrateConfigSupplier::from(test_helper.cargo_metadata());
+    let generate = |crate_name: &str| {
+        generate_bindings(
+            &cdylib_path,
+            Some(crate_name.to_string()),
+            &PythonBindingGenerator,
+            &config_supplier,
+            None,
+            &out_dir,
+            false,
+        )
+    };
+
+    let components = generate("uniffi_ext_types_lib").unwrap();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].ci.crate_name(), "uniffi_ext_types_lib");
+    let mut written: Vec<_> = out_dir
+        .read_dir_utf8()
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string())
+        .filter(|name| name.ends_with(".py"))
+        .collect();
+    written.sort();
+    assert_eq!(written, ["imported_types_lib.py"]);
+    let bindings = std::fs::read_to_string(out_dir.join("imported_types_lib.py")).unwrap();
+    assert!(bindings.contains("from ext_types_custom import Guid"));
+
+    // An unknown crate name lists the crates which were found
+    assert_eq!(
+        generate("not_a_crate").unwrap_err().to_string(),
+        format!(
+            "Crate not_a_crate not found in {cdylib_path} (found: custom_types, ext_types_custom, \
+             uniffi_ext_types_lib, uniffi_one, uniffi_sublib)"
+        )
+    );
+}
@@ -1,10 +1,10 @@
-use custom_types::Handle;
+use custom_types::{Handle, Url};
 use ext_types_custom::Guid;
 use std::sync::Arc;
 use uniffi_one::{
-    UniffiOneEnum, UniffiOneInterface, UniffiOneProcMacroType, UniffiOneTrait, UniffiOneType,
+    UniffiOneCallbackInterface, UniffiOneEnum, UniffiOneInterface, UniffiOneProcMacroType,
+    UniffiOneTrait, UniffiOneType,
 };
-use url::Url;
 
 uniffi::use_udl_record!(uniffi_one, UniffiOneType);
 uniffi::use_udl_enum!(uniffi_one, UniffiOneEnum);
@@ -162,6 +162,15 @@ fn get_uniffi_one_trait(t: Option<Arc<dyn UniffiOneTrait>>) -> Option<Arc<dyn Un
     t
 }
 
+// A callback interface defined in another crate
+#[uniffi::export]
+fn invoke_uniffi_one_callback_interface(
+    cb: Box<dyn UniffiOneCallbackInterface>,
+    value: String,
+) -> String {
+    cb.on_done(value)
+}
+
 // Some custom types via macros.
 // Another guid - here we use a regular struct.
 pub struct Uuid {
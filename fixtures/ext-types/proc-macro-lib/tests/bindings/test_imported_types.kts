@@ -50,5 +50,10 @@ assert(getMaybeUniffiOneEnum(null) == null)
 assert(getUniffiOneEnums(listOf(uoe)) == listOf(uoe))
 assert(getMaybeUniffiOneEnums(listOf(uoe, null)) == listOf(uoe, null))
 
+class CallbackInterfaceImpl : UniffiOneCallbackInterface {
+    override fun onDone(value: String) = "$value done"
+}
+assert(invokeUniffiOneCallbackInterface(CallbackInterfaceImpl(), "hello") == "hello done")
+
 val g = getGuidProcmacro(null)
 assert(g == getGuidProcmacro(g))
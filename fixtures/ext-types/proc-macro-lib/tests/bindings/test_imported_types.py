@@ -63,6 +63,13 @@ class TestIt(unittest.TestCase):
         self.assertEqual([e], get_uniffi_one_enums([e]))
         self.assertEqual([e, None], get_maybe_uniffi_one_enums([e, None]))
 
+    def test_external_callback_interface(self):
+        class CallbackInterfaceImpl(UniffiOneCallbackInterface):
+            def on_done(self, value):
+                return f"{value} done"
+
+        self.assertEqual(invoke_uniffi_one_callback_interface(CallbackInterfaceImpl(), "hello"), "hello done")
+
     def test_get_guid_procmacro(self):
         g = get_guid_procmacro(None)
         self.assertEqual(g, get_guid_procmacro(g))
@@ -55,5 +55,12 @@ assert(getMaybeUniffiOneEnum(e: nil) == nil)
 assert(getUniffiOneEnums(es: [UniffiOneEnum.one]) == [UniffiOneEnum.one])
 assert(getMaybeUniffiOneEnums(es: [UniffiOneEnum.one, nil]) == [UniffiOneEnum.one, nil])
 
+class CallbackInterfaceImpl: UniffiOneCallbackInterface {
+    func onDone(value: String) -> String {
+        return "\(value) done"
+    }
+}
+assert(invokeUniffiOneCallbackInterface(cb: CallbackInterfaceImpl(), value: "hello") == "hello done")
+
 let g = getGuidProcmacro(g: nil)
 assert(g == getGuidProcmacro(g: g))
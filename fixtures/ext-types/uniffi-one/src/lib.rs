@@ -44,6 +44,12 @@ pub trait UniffiOneTrait: Send + Sync {
     fn hello(&self) -> String;
 }
 
+// A callback interface which is passed into functions in other crates.
+#[uniffi::export(callback_interface)]
+pub trait UniffiOneCallbackInterface: Send + Sync {
+    fn on_done(&self, value: String) -> String;
+}
+
 // Note `UDL` vs `Udl` is important here to test foreign binding name fixups.
 pub trait UniffiOneUDLTrait: Send + Sync {
     fn hello(&self) -> String;
@@ -383,6 +383,33 @@ pub trait SayAfterTrait: Send + Sync {
     async fn say_after(&self, ms: u16, who: String) -> String;
 }
 
+// Example of a trait with both sync and async methods
+#[uniffi::export]
+#[async_trait::async_trait]
+pub trait Store: Send + Sync {
+    fn name(&self) -> String;
+    async fn load(&self, delay_ms: u16, key: String) -> Option<String>;
+}
+
+struct MemoryStore;
+
+#[async_trait::async_trait]
+impl Store for MemoryStore {
+    fn name(&self) -> String {
+        "memory".to_string()
+    }
+
+    async fn load(&self, delay_ms: u16, key: String) -> Option<String> {
+        TimerFuture::new(Duration::from_millis(delay_ms.into())).await;
+        (key == "greeting").then(|| "Hello".to_string())
+    }
+}
+
+#[uniffi::export]
+fn get_store() -> Arc<dyn Store> {
+    Arc::new(MemoryStore)
+}
+
 // Example of async trait defined in the UDL file
 #[async_trait::async_trait]
 pub trait SayAfterUdlTrait: Send + Sync {
@@ -451,11 +478,17 @@ pub enum ParserError {
     NotAnInt,
     #[error("UnexpectedError")]
     UnexpectedError,
+    #[error("Cancelled")]
+    Cancelled,
 }
 
 impl From<uniffi::UnexpectedUniFFICallbackError> for ParserError {
-    fn from(_: uniffi::UnexpectedUniFFICallbackError) -> Self {
-        Self::UnexpectedError
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        if e.cancelled {
+            Self::Cancelled
+        } else {
+            Self::UnexpectedError
+        }
     }
 }
 
@@ -498,4 +531,19 @@ async fn cancel_delay_using_trait(obj: Arc<dyn AsyncParser>, delay_ms: i32) {
     assert_eq!(future.await, Err(Aborted));
 }
 
+// An async function that can throw, defined in the UDL file
+async fn fallible_udl(do_fail: bool) -> Result<u8, MyError> {
+    fallible_me(do_fail).await
+}
+
+// Async callback interface defined in the UDL file
+#[async_trait::async_trait]
+pub trait UdlGreeter: Send + Sync {
+    async fn greet(&self, who: String) -> String;
+}
+
+async fn greet_using_udl_callback(greeter: Box<dyn UdlGreeter>, who: String) -> String {
+    greeter.greet(who).await
+}
+
 uniffi::include_scaffolding!("futures");
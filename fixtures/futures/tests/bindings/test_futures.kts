@@ -105,8 +105,18 @@ runBlocking {
 
 // Test async constructors
 runBlocking {
-    val megaphone = Megaphone.secondary()
+    val megaphone = Megaphone.new()
     assert(megaphone.sayAfter(1U, "hi") == "HELLO, HI!")
+
+    val secondary = Megaphone.secondary()
+    assert(secondary.sayAfter(1U, "hi") == "HELLO, HI!")
+
+    try {
+        FallibleMegaphone.new()
+        throw RuntimeException("Expected an exception")
+    } catch (e: MyException.Foo) {
+        // Expected
+    }
 }
 
 // Test async method returning optional object
@@ -132,6 +142,14 @@ runBlocking {
     assertApproximateTime(time, 200, "async methods")
 }
 
+// Test trait interfaces with both sync and async methods
+runBlocking {
+    val store = getStore()
+    assert(store.name() == "memory")
+    assert(store.load(10U, "greeting") == "Hello")
+    assert(store.load(10U, "farewell") == null)
+}
+
 // Test async methods in UDL-defined trait interfaces
 runBlocking {
     val traits = getSayAfterUdlTraits()
@@ -160,6 +178,9 @@ class KotlinAsyncParser: AsyncParser {
         if (value == "force-unexpected-exception") {
             throw RuntimeException("UnexpectedException")
         }
+        if (value == "force-cancellation") {
+            throw kotlin.coroutines.cancellation.CancellationException("Parsing was cancelled")
+        }
         try {
             return value.toInt()
         } catch (e: NumberFormatException) {
@@ -199,6 +220,12 @@ runBlocking {
     } catch(e: ParserException.UnexpectedException) {
         // Expected
     }
+    try {
+        tryFromStringUsingTrait(traitObj, 1, "force-cancellation")
+        throw RuntimeException("Expected last statement to throw")
+    } catch(e: ParserException.Cancelled) {
+        // Expected
+    }
     delayUsingTrait(traitObj, 1)
     try {
         tryDelayUsingTrait(traitObj, "one")
@@ -218,6 +245,25 @@ runBlocking {
 }
 
 
+// Test async functions and callback interfaces defined in the UDL file
+class KotlinUdlGreeter: UdlGreeter {
+    override suspend fun greet(who: String): String {
+        delay(1)
+        return "Hi, $who!"
+    }
+}
+
+runBlocking {
+    assert(fallibleUdl(false) == 42.toUByte())
+    try {
+        fallibleUdl(true)
+        throw RuntimeException("Expected last statement to throw")
+    } catch(e: MyException.Foo) {
+        // Expected
+    }
+    assert(greetUsingUdlCallback(KotlinUdlGreeter(), "Alice") == "Hi, Alice!")
+}
+
 // Test with the Tokio runtime.
 runBlocking {
     val time = measureTimeMillis {
@@ -83,10 +83,17 @@ class TestFutures(unittest.TestCase):
         self.assertTrue(str(e.exception).startswith("async constructors not supported"))
 
         async def test():
+            megaphone = await Megaphone.new()
+            result_alice = await megaphone.say_after(0, 'Alice')
+            self.assertEqual(result_alice, 'HELLO, ALICE!')
+
             megaphone = await Megaphone.secondary()
             result_alice = await megaphone.say_after(0, 'Alice')
             self.assertEqual(result_alice, 'HELLO, ALICE!')
 
+            with self.assertRaises(MyError.Foo):
+                await FallibleMegaphone.new()
+
             udl_megaphone = await UdlMegaphone.secondary()
             result_udl = await udl_megaphone.say_after(0, 'udl')
             self.assertEqual(result_udl, 'HELLO, UDL!')
@@ -108,6 +115,15 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_trait_interface_with_sync_and_async_methods(self):
+        async def test():
+            store = get_store()
+            self.assertEqual(store.name(), 'memory')
+            self.assertEqual(await store.load(10, 'greeting'), 'Hello')
+            self.assertIsNone(await store.load(10, 'farewell'))
+
+        asyncio.run(test())
+
     def test_udl_async_trait_interface_methods(self):
         async def test():
             traits = get_say_after_udl_traits()
@@ -123,6 +139,25 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_udl_async_functions(self):
+        async def test():
+            self.assertEqual(await fallible_udl(False), 42)
+            with self.assertRaises(MyError.Foo):
+                await fallible_udl(True)
+
+        asyncio.run(test())
+
+    def test_udl_async_callback_interface_methods(self):
+        class PyGreeter:
+            async def greet(self, who):
+                await asyncio.sleep(0.001)
+                return f"Hi, {who}!"
+
+        async def test():
+            self.assertEqual(await greet_using_udl_callback(PyGreeter(), "Alice"), "Hi, Alice!")
+
+        asyncio.run(test())
+
     def test_foreign_async_trait_interface_methods(self):
         class PyAsyncParser:
             def __init__(self):
@@ -136,6 +171,8 @@ class TestFutures(unittest.TestCase):
                 await asyncio.sleep(delay_ms / 1000.0)
                 if value == "force-unexpected-exception":
                     raise RuntimeError("UnexpectedException")
+                if value == "force-cancellation":
+                    raise asyncio.CancelledError()
                 try:
                     return int(value)
                 except:
@@ -161,6 +198,8 @@ class TestFutures(unittest.TestCase):
                 await try_from_string_using_trait(trait_obj, 1, "fourty-two")
             with self.assertRaises(ParserError.UnexpectedError):
                 await try_from_string_using_trait(trait_obj, 1, "force-unexpected-exception")
+            with self.assertRaises(ParserError.Cancelled):
+                await try_from_string_using_trait(trait_obj, 1, "force-cancellation")
             await delay_using_trait(trait_obj, 1)
             await try_delay_using_trait(trait_obj, "1")
             with self.assertRaises(ParserError.NotAnInt):
@@ -131,6 +131,20 @@ Task {
 	counter.leave()
 }
 
+// Test trait interfaces with both sync and async methods
+counter.enter()
+
+Task {
+	let store = getStore()
+	assert(store.name() == "memory")
+	let greeting = await store.load(delayMs: 10, key: "greeting")
+	assert(greeting == "Hello")
+	let farewell = await store.load(delayMs: 10, key: "farewell")
+	assert(farewell == nil)
+
+	counter.leave()
+}
+
 // Test UDL-defined async trait interface methods
 counter.enter()
 
@@ -183,6 +197,9 @@ class SwiftAsyncParser: AsyncParser {
         if (value == "force-unexpected-exception") {
             throw UnexpectedError()
         }
+        if (value == "force-cancellation") {
+            throw CancellationError()
+        }
         guard let result = Int32(value) else {
             throw ParserError.NotAnInt
         }
@@ -233,6 +250,12 @@ Task {
     } catch ParserError.UnexpectedError {
         // Expected
     }
+    do {
+        let _ = try await tryFromStringUsingTrait(obj: traitObj, delayMs: 1, value: "force-cancellation")
+        fatalError("Expected previous statement to throw")
+    } catch ParserError.Cancelled {
+        // Expected
+    }
     await delayUsingTrait(obj: traitObj, delayMs: 1)
     try! await tryDelayUsingTrait(obj: traitObj, delayMs: "1")
     do {
@@ -255,6 +278,31 @@ Task {
     counter.leave()
 }
 
+// Test async functions and callback interfaces defined in the UDL file
+counter.enter()
+
+class SwiftUdlGreeter: UdlGreeter {
+    func greet(who: String) async -> String {
+        try! await Task.sleep(nanoseconds: 1_000_000)
+        return "Hi, \(who)!"
+    }
+}
+
+Task {
+    let result = try! await fallibleUdl(doFail: false)
+    assert(result == 42)
+    do {
+        let _ = try await fallibleUdl(doFail: true)
+        fatalError("Expected previous statement to throw")
+    } catch MyError.Foo {
+        // Expected
+    }
+    let greeting = await greetUsingUdlCallback(greeter: SwiftUdlGreeter(), who: "Alice")
+    assert(greeting == "Hi, Alice!")
+
+    counter.leave()
+}
+
 // Test async function returning an object
 counter.enter()
 
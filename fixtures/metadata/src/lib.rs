@@ -9,3 +9,11 @@
 mod tests;
 
 pub struct UniFfiTag;
+
+// `uniffi::setup_scaffolding!()` implements this for the `CatchAllError` of `catch_all` functions
+impl uniffi::TypeId<UniFfiTag> for uniffi::CatchAllError {
+    const TYPE_ID_META: uniffi::MetadataBuffer =
+        uniffi::MetadataBuffer::from_code(uniffi::metadata::codes::TYPE_ENUM)
+            .concat_str("uniffi_fixture_metadata")
+            .concat_str("CatchAllError");
+}
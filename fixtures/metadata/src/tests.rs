@@ -76,6 +76,45 @@ mod error {
     }
 }
 
+mod plan {
+    #[derive(uniffi::Record, Debug)]
+    #[uniffi(tag = "pro")]
+    pub struct ProPlan {
+        seats: u32,
+    }
+}
+
+mod legacy {
+    #[derive(uniffi::Record, Debug)]
+    #[uniffi(deprecated = "use ProPlan instead")]
+    pub struct LegacyPlan {
+        #[uniffi(deprecated)]
+        seats: u32,
+    }
+
+    #[derive(uniffi::Enum, Debug)]
+    pub enum LegacyTier {
+        #[uniffi(deprecated = "every plan is pro now")]
+        Basic,
+        Pro {
+            #[uniffi(deprecated)]
+            seats: u32,
+        },
+    }
+}
+
+mod packet {
+    #[derive(uniffi::Record, Debug)]
+    pub struct Packet {
+        #[uniffi(default = b"\xDE\xAD\xBE\xEF")]
+        magic: Vec<u8>,
+        #[uniffi(default = b"")]
+        payload: Vec<u8>,
+        #[uniffi(default = Some(b"v1"))]
+        version: Option<Vec<u8>>,
+    }
+}
+
 mod calc {
     #[derive(uniffi::Object)]
     pub struct Calculator {}
@@ -186,16 +225,105 @@ mod test_metadata {
                         name: "name".into(),
                         ty: Type::String,
                         default: Some(LiteralMetadata::String("test".to_owned())),
+                        is_source: false,
                         docstring: None,
+                        deprecated: None,
                     },
                     FieldMetadata {
                         name: "age".into(),
                         ty: Type::UInt16,
                         default: None,
+                        is_source: false,
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_tagged_record() {
+        check_metadata(
+            &plan::UNIFFI_META_UNIFFI_FIXTURE_METADATA_RECORD_PROPLAN,
+            RecordMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "ProPlan".into(),
+                fields: vec![FieldMetadata {
+                    name: "seats".into(),
+                    ty: Type::UInt32,
+                    default: None,
+                    is_source: false,
+                    docstring: None,
+                    deprecated: None,
+                }],
+                docstring: None,
+                tag: Some("pro".into()),
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_deprecated_record() {
+        check_metadata(
+            &legacy::UNIFFI_META_UNIFFI_FIXTURE_METADATA_RECORD_LEGACYPLAN,
+            RecordMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "LegacyPlan".into(),
+                fields: vec![FieldMetadata {
+                    name: "seats".into(),
+                    ty: Type::UInt32,
+                    default: None,
+                    is_source: false,
+                    docstring: None,
+                    deprecated: Some("".into()),
+                }],
+                docstring: None,
+                tag: None,
+                deprecated: Some("use ProPlan instead".into()),
+            },
+        );
+    }
+
+    #[test]
+    fn test_record_bytes_defaults() {
+        let field = |name: &str, ty, default| FieldMetadata {
+            name: name.into(),
+            ty,
+            default: Some(default),
+            is_source: false,
+            docstring: None,
+            deprecated: None,
+        };
+        check_metadata(
+            &packet::UNIFFI_META_UNIFFI_FIXTURE_METADATA_RECORD_PACKET,
+            RecordMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "Packet".into(),
+                fields: vec![
+                    field(
+                        "magic",
+                        Type::Bytes,
+                        LiteralMetadata::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+                    ),
+                    field("payload", Type::Bytes, LiteralMetadata::Bytes(vec![])),
+                    field(
+                        "version",
+                        Type::Optional {
+                            inner_type: Box::new(Type::Bytes),
+                        },
+                        LiteralMetadata::Some {
+                            inner: Box::new(LiteralMetadata::Bytes(b"v1".to_vec())),
+                        },
+                    ),
+                ],
+                docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -215,22 +343,26 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Paper".into(),
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Scissors".into(),
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -250,6 +382,7 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Initialized".into(),
@@ -258,9 +391,12 @@ mod test_metadata {
                             name: "data".into(),
                             ty: Type::String,
                             default: None,
+                            is_source: false,
                             docstring: None,
+                            deprecated: None,
                         }],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Complete".into(),
@@ -272,13 +408,56 @@ mod test_metadata {
                                 name: "Person".into(),
                             },
                             default: None,
+                            is_source: false,
+                            docstring: None,
+                            deprecated: None,
+                        }],
+                        docstring: None,
+                        deprecated: None,
+                    },
+                ],
+                non_exhaustive: false,
+                docstring: None,
+                tag: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_deprecated_enum_variants() {
+        check_metadata(
+            &legacy::UNIFFI_META_UNIFFI_FIXTURE_METADATA_ENUM_LEGACYTIER,
+            EnumMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "LegacyTier".into(),
+                shape: EnumShape::Enum,
+                discr_type: None,
+                variants: vec![
+                    VariantMetadata {
+                        name: "Basic".into(),
+                        discr: None,
+                        fields: vec![],
+                        docstring: None,
+                        deprecated: Some("every plan is pro now".into()),
+                    },
+                    VariantMetadata {
+                        name: "Pro".into(),
+                        discr: None,
+                        fields: vec![FieldMetadata {
+                            name: "seats".into(),
+                            ty: Type::UInt32,
+                            default: None,
+                            is_source: false,
                             docstring: None,
+                            deprecated: Some("".into()),
                         }],
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -298,22 +477,26 @@ mod test_metadata {
                         discr: Some(LiteralMetadata::new_uint(1)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Three".into(),
                         discr: Some(LiteralMetadata::new_uint(3)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Fifteen".into(),
                         discr: Some(LiteralMetadata::new_uint(15)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -332,9 +515,11 @@ mod test_metadata {
                     discr: Some(LiteralMetadata::new_uint(1)),
                     fields: vec![],
                     docstring: None,
+                    deprecated: None,
                 }],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -354,16 +539,19 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "DivideByZero".into(),
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -383,6 +571,7 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "PermissionDenied".into(),
@@ -391,9 +580,12 @@ mod test_metadata {
                             name: "reason".into(),
                             ty: Type::String,
                             default: None,
+                            is_source: false,
                             docstring: None,
+                            deprecated: None,
                         }],
                         docstring: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "InvalidWeapon".into(),
@@ -405,13 +597,17 @@ mod test_metadata {
                                 name: "Weapon".into(),
                             },
                             default: None,
+                            is_source: false,
                             docstring: None,
+                            deprecated: None,
                         }],
                         docstring: None,
+                        deprecated: None,
                     },
                 ],
                 non_exhaustive: false,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -425,6 +621,7 @@ mod test_metadata {
                 name: "Calculator".into(),
                 imp: ObjectImpl::Struct,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -473,6 +670,11 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export(catch_all)]
+    pub fn test_func_catch_all() -> Result<u8, Box<dyn std::error::Error + Send + Sync>> {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     #[allow(unused)]
     pub async fn test_async_func(person: Person, weapon: Weapon) -> String {
@@ -484,6 +686,36 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export(tag = "pro")]
+    pub fn test_func_tagged() {}
+
+    #[uniffi::export(deprecated = "use test_func() instead")]
+    pub fn test_func_deprecated() {}
+
+    #[uniffi::export]
+    impl Calculator {
+        #[allow(unused)]
+        #[uniffi::method(deprecated)]
+        pub fn legacy_add(&self, a: u8, b: u8) -> u8 {
+            unimplemented!()
+        }
+    }
+
+    // Methods get the tag of their impl block unless they have their own.
+    #[uniffi::export(tag = "pro")]
+    impl Calculator {
+        #[allow(unused)]
+        pub fn pro_mul(&self, a: u8, b: u8) -> u8 {
+            unimplemented!()
+        }
+
+        #[allow(unused)]
+        #[uniffi::method(tag = "beta")]
+        pub fn beta_div(&self, a: u8, b: u8) -> u8 {
+            unimplemented!()
+        }
+    }
+
     #[uniffi::export]
     pub trait CalculatorDisplay: Send + Sync {
         fn display_result(&self, val: String);
@@ -505,6 +737,23 @@ mod test_function_metadata {
         pub fn get_display(&self) -> Arc<dyn CalculatorDisplay> {
             unimplemented!()
         }
+
+        #[allow(unused)]
+        #[uniffi::method(consumes)]
+        pub fn finish(self: Arc<Self>) -> u8 {
+            unimplemented!()
+        }
+
+        #[allow(unused)]
+        pub fn max_operands() -> u32 {
+            unimplemented!()
+        }
+
+        #[allow(unused)]
+        #[uniffi::method(name = "clear")]
+        pub fn try_reset(&self) -> bool {
+            unimplemented!()
+        }
     }
 
     #[uniffi::export(with_foreign)]
@@ -542,8 +791,10 @@ mod test_function_metadata {
                 ],
                 return_type: Some(Type::String),
                 throws: None,
-                checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC.checksum()),
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_func()),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -559,10 +810,10 @@ mod test_function_metadata {
                 inputs: vec![],
                 return_type: None,
                 throws: None,
-                checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_NO_RETURN.checksum(),
-                ),
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_func_no_return()),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -585,9 +836,11 @@ mod test_function_metadata {
                     name: "FlatError".into(),
                 }),
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_THAT_THROWS.checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_func_test_func_that_throws(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -607,10 +860,57 @@ mod test_function_metadata {
                     name: "FlatError".into(),
                 }),
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_NO_RETURN_THAT_THROWS
-                        .checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_func_test_func_no_return_that_throws(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_function_catch_all() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_CATCH_ALL,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_catch_all".into(),
+                is_async: false,
+                inputs: vec![],
+                return_type: Some(Type::UInt8),
+                throws: Some(Type::Enum {
+                    module_path: "uniffi_fixture_metadata".into(),
+                    name: "CatchAllError".into(),
+                }),
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_func_catch_all()),
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+        // The function also declares the error
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CATCH_ALL_ERROR_UNIFFI_UNIFFI_FIXTURE_METADATA_FN_FUNC_TEST_FUNC_CATCH_ALL,
+            EnumMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "CatchAllError".into(),
+                shape: EnumShape::Error { flat: true },
+                variants: vec![VariantMetadata {
+                    name: "Failed".into(),
+                    discr: None,
+                    fields: vec![],
+                    docstring: None,
+                    deprecated: None,
+                }],
+                discr_type: None,
+                non_exhaustive: false,
+                docstring: Some(
+                    "The error thrown by functions which are exported with `catch_all`.\n\n\
+                     Its message describes the original error and the errors which caused it."
+                        .into(),
+                ),
+                tag: None,
             },
         );
     }
@@ -624,6 +924,116 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "add".into(),
                 is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
+                inputs: vec![
+                    FnParamMetadata::simple("a", Type::UInt8),
+                    FnParamMetadata::simple("b", Type::UInt8),
+                ],
+                return_type: Some(Type::UInt8),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_method_calculator_add()),
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_tagged_function() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_TAGGED,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_tagged".into(),
+                is_async: false,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_func_tagged()),
+                docstring: None,
+                tag: Some("pro".into()),
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_tagged_methods() {
+        fn method(name: &str, checksum: u16, tag: &str) -> MethodMetadata {
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: name.into(),
+                is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
+                inputs: vec![
+                    FnParamMetadata::simple("a", Type::UInt8),
+                    FnParamMetadata::simple("b", Type::UInt8),
+                ],
+                return_type: Some(Type::UInt8),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(checksum),
+                docstring: None,
+                tag: Some(tag.into()),
+                deprecated: None,
+            }
+        }
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_PRO_MUL,
+            method(
+                "pro_mul",
+                uniffi_uniffi_fixture_metadata_checksum_method_calculator_pro_mul(),
+                "pro",
+            ),
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_BETA_DIV,
+            method(
+                "beta_div",
+                uniffi_uniffi_fixture_metadata_checksum_method_calculator_beta_div(),
+                "beta",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_deprecated_function() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_DEPRECATED,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_deprecated".into(),
+                is_async: false,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_func_deprecated()),
+                docstring: None,
+                tag: None,
+                deprecated: Some("use test_func() instead".into()),
+            },
+        );
+    }
+
+    #[test]
+    fn test_deprecated_method() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_LEGACY_ADD,
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: "legacy_add".into(),
+                is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
                 inputs: vec![
                     FnParamMetadata::simple("a", Type::UInt8),
                     FnParamMetadata::simple("b", Type::UInt8),
@@ -632,13 +1042,34 @@ mod test_function_metadata {
                 throws: None,
                 takes_self_by_arc: false,
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ADD.checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_method_calculator_legacy_add(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: Some("".into()),
             },
         );
     }
 
+    #[test]
+    fn test_deprecation_not_in_checksum() {
+        // Replace the deprecation at the end of the metadata with the flag for an item which
+        // isn't deprecated.  The checksum, which the bindings check at runtime, stays the same.
+        let deprecated = UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_DEPRECATED;
+        let deprecation_len = 1 + 2 + "use test_func() instead".len();
+        let mut not_deprecated = deprecated[..deprecated.len() - deprecation_len].to_vec();
+        not_deprecated.push(0);
+
+        let Metadata::Func(deprecated) = read_metadata(&deprecated).unwrap() else {
+            panic!("expected function metadata");
+        };
+        let Metadata::Func(not_deprecated) = read_metadata(&not_deprecated).unwrap() else {
+            panic!("expected function metadata");
+        };
+        assert_eq!(not_deprecated.deprecated, None);
+        assert_eq!(deprecated.checksum, not_deprecated.checksum);
+    }
+
     #[test]
     fn test_async_function() {
         check_metadata(
@@ -665,10 +1096,10 @@ mod test_function_metadata {
                 ],
                 return_type: Some(Type::String),
                 throws: None,
-                checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_ASYNC_FUNC.checksum(),
-                ),
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_func_test_async_func()),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -691,10 +1122,11 @@ mod test_function_metadata {
                     name: "FlatError".into(),
                 }),
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_ASYNC_FUNC_THAT_THROWS
-                        .checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_func_test_async_func_that_throws(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -708,6 +1140,9 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "async_sub".into(),
                 is_async: true,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
                 inputs: vec![
                     FnParamMetadata::simple("a", Type::UInt8),
                     FnParamMetadata::simple("b", Type::UInt8),
@@ -716,10 +1151,86 @@ mod test_function_metadata {
                 throws: None,
                 takes_self_by_arc: false,
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ASYNC_SUB
-                        .checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_method_calculator_async_sub(),
+                ),
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_consuming_method() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_FINISH,
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: "finish".into(),
+                is_async: false,
+                consumes: true,
+                is_static: false,
+                is_getter: false,
+                inputs: vec![],
+                return_type: Some(Type::UInt8),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_method_calculator_finish()),
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_static_method() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_MAX_OPERANDS,
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: "max_operands".into(),
+                is_async: false,
+                consumes: false,
+                is_static: true,
+                is_getter: false,
+                inputs: vec![],
+                return_type: Some(Type::UInt32),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(
+                    uniffi_uniffi_fixture_metadata_checksum_method_calculator_max_operands(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_renamed_method() {
+        // The metadata, its symbol and the checksum all use the foreign name
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_CLEAR,
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: "clear".into(),
+                is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
+                inputs: vec![],
+                return_type: Some(Type::Boolean),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_method_calculator_clear()),
+                docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -733,6 +1244,7 @@ mod test_function_metadata {
                 name: "CalculatorDisplay".into(),
                 imp: ObjectImpl::Trait,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -746,6 +1258,7 @@ mod test_function_metadata {
                 name: "TraitWithForeign".into(),
                 imp: ObjectImpl::CallbackTrait,
                 docstring: None,
+                tag: None,
             },
         );
     }
@@ -766,14 +1279,18 @@ mod test_function_metadata {
                 self_name: "Calculator".into(),
                 name: "get_display".into(),
                 is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
                 inputs: vec![],
                 throws: None,
                 takes_self_by_arc: false,
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_GET_DISPLAY
-                        .checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_method_calculator_get_display(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -788,15 +1305,17 @@ mod test_function_metadata {
                 index: 0,
                 name: "display_result".into(),
                 is_async: false,
-                inputs: vec![
-                    FnParamMetadata::simple("val", Type::String),
-                ],
+                has_default: false,
+                inputs: vec![FnParamMetadata::simple("val", Type::String)],
                 return_type: None,
                 throws: None,
                 takes_self_by_arc: false,
-                checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_RESULT
-                    .checksum()),
+                checksum: Some(
+                    uniffi_uniffi_fixture_metadata_checksum_method_calculatordisplay_display_result(
+                    ),
+                ),
                 docstring: None,
+                deprecated: None,
             },
         );
     }
@@ -824,10 +1343,11 @@ mod test_function_metadata {
                 is_async: false,
                 throws: None,
                 checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_INPUT_TRAIT_WITH_FOREIGN
-                        .checksum(),
+                    uniffi_uniffi_fixture_metadata_checksum_func_input_trait_with_foreign(),
                 ),
                 docstring: None,
+                tag: None,
+                deprecated: None,
             },
         );
     }
@@ -840,6 +1360,7 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "Logger".into(),
                 docstring: None,
+                tag: None,
             },
         );
         check_metadata(
@@ -850,15 +1371,118 @@ mod test_function_metadata {
                 index: 0,
                 name: "log".into(),
                 is_async: false,
+                has_default: false,
                 inputs: vec![FnParamMetadata::simple("message", Type::String)],
                 return_type: None,
                 throws: None,
                 takes_self_by_arc: false,
-                checksum: Some(
-                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_LOG.checksum(),
-                ),
+                checksum: Some(uniffi_uniffi_fixture_metadata_checksum_method_logger_log()),
                 docstring: None,
+                deprecated: None,
+            },
+        );
+    }
+}
+
+mod test_constant_metadata {
+    use super::*;
+
+    /// The largest payload we accept
+    #[uniffi::export]
+    #[allow(unused)]
+    pub const MAX_SIZE: u32 = 65536;
+
+    #[uniffi::export]
+    #[allow(unused)]
+    pub const MIN_OFFSET: i32 = -10;
+
+    #[uniffi::export]
+    #[allow(unused)]
+    pub const SCALE: f64 = 1.5;
+
+    #[uniffi::export]
+    #[allow(unused)]
+    pub const GREETING: &str = "hello";
+
+    #[uniffi::export]
+    #[allow(unused)]
+    pub const ENABLED: bool = true;
+
+    fn constant(name: &str, ty: Type, value: LiteralMetadata) -> ConstantMetadata {
+        ConstantMetadata {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: name.into(),
+            ty,
+            value,
+            docstring: None,
+            tag: None,
+        }
+    }
+
+    #[test]
+    fn test_constants() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONSTANT_MAX_SIZE,
+            ConstantMetadata {
+                docstring: Some("The largest payload we accept".into()),
+                ..constant(
+                    "MAX_SIZE",
+                    Type::UInt32,
+                    LiteralMetadata::UInt(65536, Radix::Decimal, Type::UInt32),
+                )
+            },
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONSTANT_MIN_OFFSET,
+            constant(
+                "MIN_OFFSET",
+                Type::Int32,
+                LiteralMetadata::Int(-10, Radix::Decimal, Type::Int32),
+            ),
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONSTANT_SCALE,
+            constant(
+                "SCALE",
+                Type::Float64,
+                LiteralMetadata::Float("1.5".into(), Type::Float64),
+            ),
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONSTANT_GREETING,
+            constant(
+                "GREETING",
+                Type::String,
+                LiteralMetadata::String("hello".into()),
+            ),
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONSTANT_ENABLED,
+            constant("ENABLED", Type::Boolean, LiteralMetadata::Boolean(true)),
+        );
+    }
+
+    #[uniffi::export]
+    pub trait Versioned: Send + Sync {
+        /// The version of the format
+        const VERSION: u16 = 3;
+
+        fn name(&self) -> String;
+    }
+
+    #[test]
+    fn test_trait_constants() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_TRAIT_CONSTANT_VERSIONED_VERSION,
+            TraitConstantMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                trait_name: "Versioned".into(),
+                name: "VERSION".into(),
+                ty: Type::UInt16,
+                value: LiteralMetadata::UInt(3, Radix::Decimal, Type::UInt16),
+                docstring: Some("The version of the format".into()),
             },
         );
+        assert_eq!(<dyn Versioned>::VERSION, 3);
     }
 }
@@ -0,0 +1,75 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+// A builder whose methods take and return `Arc<Self>`, so calls can be chained in the bindings.
+#[derive(Clone, uniffi::Object)]
+pub struct ClientBuilder {
+    timeout_secs: u64,
+    retries: u32,
+}
+
+#[uniffi::export]
+impl ClientBuilder {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            timeout_secs: 30,
+            retries: 0,
+        })
+    }
+
+    fn with_timeout(self: Arc<Self>, secs: u64) -> Result<Arc<Self>, ClientBuildError> {
+        if secs == 0 {
+            return Err(ClientBuildError::ZeroTimeout);
+        }
+        Ok(Arc::new(Self {
+            timeout_secs: secs,
+            ..(*self).clone()
+        }))
+    }
+
+    fn with_retries(self: Arc<Self>, retries: u32) -> Arc<Self> {
+        Arc::new(Self {
+            retries,
+            ..(*self).clone()
+        })
+    }
+
+    fn build(self: Arc<Self>) -> Result<Arc<Client>, ClientBuildError> {
+        if self.retries > 10 {
+            return Err(ClientBuildError::TooManyRetries);
+        }
+        Ok(Arc::new(Client {
+            timeout_secs: self.timeout_secs,
+            retries: self.retries,
+        }))
+    }
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ClientBuildError {
+    #[error("the timeout can't be zero")]
+    ZeroTimeout,
+    #[error("too many retries")]
+    TooManyRetries,
+}
+
+#[derive(uniffi::Object)]
+pub struct Client {
+    timeout_secs: u64,
+    retries: u32,
+}
+
+#[uniffi::export]
+impl Client {
+    fn timeout_secs(&self) -> u64 {
+        self.timeout_secs
+    }
+
+    fn retries(&self) -> u32 {
+        self.retries
+    }
+}
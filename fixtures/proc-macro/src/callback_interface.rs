@@ -17,5 +17,16 @@ pub trait TestCallbackInterface {
 
 #[uniffi::export(callback_interface)]
 pub trait OtherCallbackInterface {
+    /// Multiplying by this leaves the other factor unchanged
+    const IDENTITY: u32 = 1;
+
     fn multiply(&self, a: u32, b: u32) -> u32;
+    // Foreign implementations use this default implementation
+    fn square(&self, a: u32) -> u32 {
+        self.multiply(a, a)
+    }
+    // A no-op by default, so foreign implementations don't have to implement it
+    fn on_debug(&self, message: String) {
+        let _ = message;
+    }
 }
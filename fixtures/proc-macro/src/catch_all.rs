@@ -0,0 +1,71 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{error::Error, sync::Arc};
+
+use anyhow::{bail, Context};
+
+// Functions returning `anyhow::Result` or boxed errors, which throw `CatchAllError`.  Most of
+// these rely on `catch-all = true` in this crate's `Cargo.toml`.
+
+#[uniffi::export]
+fn parse_port(value: String) -> anyhow::Result<u16> {
+    let port = value
+        .parse::<u16>()
+        .with_context(|| format!("invalid port `{value}`"))?;
+    if port == 0 {
+        bail!("port 0 is reserved");
+    }
+    Ok(port)
+}
+
+#[uniffi::export]
+fn parse_ratio(value: String) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    let (num, denom) = value.split_once('/').ok_or("missing `/`")?;
+    Ok(num.parse::<f64>()? / denom.parse::<f64>()?)
+}
+
+#[uniffi::export]
+async fn parse_port_async(value: String) -> anyhow::Result<u16> {
+    parse_port(value)
+}
+
+type BoxedResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+// Type aliases need the attribute, since they can't be recognized by name
+#[uniffi::export(catch_all)]
+fn parse_flag(value: String) -> BoxedResult<bool> {
+    Ok(value.parse()?)
+}
+
+#[derive(uniffi::Object)]
+pub struct Endpoint {
+    host: String,
+    port: u16,
+}
+
+#[uniffi::export]
+impl Endpoint {
+    #[uniffi::constructor]
+    fn new(address: String) -> anyhow::Result<Self> {
+        let (host, port) = address
+            .rsplit_once(':')
+            .with_context(|| format!("`{address}` has no port"))?;
+        Ok(Self {
+            host: host.to_string(),
+            port: parse_port(port.to_string())?,
+        })
+    }
+
+    fn with_port(&self, port: String) -> anyhow::Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            host: self.host.clone(),
+            port: parse_port(port).context("can't change the port")?,
+        }))
+    }
+
+    fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
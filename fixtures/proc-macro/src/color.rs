@@ -0,0 +1,61 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+// An object with static methods, which belong to the type without constructing it.
+#[derive(uniffi::Object)]
+pub struct Color {
+    rgb: [u8; 3],
+}
+
+#[uniffi::export]
+impl Color {
+    #[uniffi::constructor]
+    fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { rgb: [r, g, b] }
+    }
+
+    /// Parses a color like `#ff8000`, returning `None` if it's invalid
+    fn from_hex(hex: &str) -> Option<Arc<Self>> {
+        let digits = hex.strip_prefix('#')?;
+        if digits.len() != 6 || !digits.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+        Some(Arc::new(Self {
+            rgb: [channel(0)?, channel(2)?, channel(4)?],
+        }))
+    }
+
+    fn primaries() -> Vec<Arc<Self>> {
+        vec![
+            Arc::new(Self::new(255, 0, 0)),
+            Arc::new(Self::new(0, 255, 0)),
+            Arc::new(Self::new(0, 0, 255)),
+        ]
+    }
+
+    #[uniffi::method(default(alpha = false))]
+    fn channel_count(alpha: bool) -> u8 {
+        if alpha {
+            4
+        } else {
+            3
+        }
+    }
+
+    async fn named(name: String) -> Option<Arc<Self>> {
+        match name.as_str() {
+            "black" => Some(Arc::new(Self::new(0, 0, 0))),
+            "white" => Some(Arc::new(Self::new(255, 255, 255))),
+            _ => None,
+        }
+    }
+
+    fn hex(&self) -> String {
+        let [r, g, b] = self.rgb;
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
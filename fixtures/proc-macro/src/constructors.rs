@@ -0,0 +1,125 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Every form of constructor: primary or secondary, fallible or not, with or without defaults,
+//! and async.  Constructors given a `value` over 100 fail with `ConstructorError::TooLarge`.
+
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ConstructorError {
+    #[error("{value} is too large")]
+    TooLarge { value: u32 },
+}
+
+fn check(value: u32) -> Result<u32, ConstructorError> {
+    match value {
+        0..=100 => Ok(value),
+        _ => Err(ConstructorError::TooLarge { value }),
+    }
+}
+
+/// The constructors of these objects record their name and arguments
+macro_rules! describe_impl {
+    ($($ty:ident),*) => {$(
+        #[uniffi::export]
+        impl $ty {
+            fn describe(&self) -> String {
+                self.0.clone()
+            }
+        }
+    )*};
+}
+
+#[derive(uniffi::Object)]
+pub struct PrimaryInfallible(String);
+
+#[uniffi::export]
+impl PrimaryInfallible {
+    #[uniffi::constructor]
+    fn new(value: u32) -> Self {
+        Self(format!("new {value}"))
+    }
+
+    #[uniffi::constructor(default(value = 1, label = "default"))]
+    fn with_defaults(value: u32, label: String) -> Self {
+        Self(format!("with_defaults {value} {label}"))
+    }
+
+    #[uniffi::constructor]
+    fn fallible(value: u32) -> Result<Self, ConstructorError> {
+        Ok(Self(format!("fallible {}", check(value)?)))
+    }
+
+    #[uniffi::constructor(default(value = 2, label = "fallible"))]
+    fn fallible_with_defaults(value: u32, label: String) -> Result<Arc<Self>, ConstructorError> {
+        Ok(Arc::new(Self(format!(
+            "fallible_with_defaults {} {label}",
+            check(value)?
+        ))))
+    }
+
+    #[uniffi::constructor]
+    async fn async_infallible(value: u32) -> Self {
+        Self(format!("async_infallible {value}"))
+    }
+
+    #[uniffi::constructor]
+    async fn async_fallible(value: u32) -> Result<Self, ConstructorError> {
+        Ok(Self(format!("async_fallible {}", check(value)?)))
+    }
+
+    #[uniffi::constructor(default(value = 3))]
+    async fn async_fallible_with_defaults(value: u32) -> Result<Arc<Self>, ConstructorError> {
+        Ok(Arc::new(Self(format!(
+            "async_fallible_with_defaults {}",
+            check(value)?
+        ))))
+    }
+}
+
+#[derive(uniffi::Object)]
+pub struct PrimaryWithDefaults(String);
+
+#[uniffi::export]
+impl PrimaryWithDefaults {
+    #[uniffi::constructor(default(value = 4, label = "primary"))]
+    fn new(value: u32, label: String) -> Self {
+        Self(format!("new {value} {label}"))
+    }
+}
+
+#[derive(uniffi::Object)]
+pub struct PrimaryFallible(String);
+
+#[uniffi::export]
+impl PrimaryFallible {
+    #[uniffi::constructor]
+    fn new(value: u32) -> Result<Self, ConstructorError> {
+        Ok(Self(format!("new {}", check(value)?)))
+    }
+
+    #[uniffi::constructor(default(value = 6))]
+    fn fallible_with_defaults(value: u32) -> Result<Self, ConstructorError> {
+        Ok(Self(format!("fallible_with_defaults {}", check(value)?)))
+    }
+}
+
+#[derive(uniffi::Object)]
+pub struct PrimaryFallibleWithDefaults(String);
+
+#[uniffi::export]
+impl PrimaryFallibleWithDefaults {
+    #[uniffi::constructor(default(value = 5))]
+    fn new(value: u32) -> Result<Arc<Self>, ConstructorError> {
+        Ok(Arc::new(Self(format!("new {}", check(value)?))))
+    }
+}
+
+describe_impl!(
+    PrimaryInfallible,
+    PrimaryWithDefaults,
+    PrimaryFallible,
+    PrimaryFallibleWithDefaults
+);
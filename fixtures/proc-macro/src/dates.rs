@@ -0,0 +1,52 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+// `chrono` types, which are supported with the `chrono` feature
+#[derive(uniffi::Record)]
+pub struct Appointment {
+    day: NaiveDate,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+#[uniffi::export]
+fn appointment_on(day: NaiveDate, hour: u32) -> Appointment {
+    let starts_at = day.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+    Appointment {
+        day,
+        starts_at,
+        ends_at: Some(starts_at + Duration::hours(1)),
+    }
+}
+
+#[uniffi::export]
+fn appointment_length_seconds(appointment: Appointment) -> Option<i64> {
+    appointment
+        .ends_at
+        .map(|ends_at| (ends_at - appointment.starts_at).num_seconds())
+}
+
+#[uniffi::export]
+fn next_day(day: NaiveDate) -> NaiveDate {
+    day.succ_opt().unwrap()
+}
+
+#[uniffi::export]
+fn first_of_months(year: i32) -> Vec<NaiveDate> {
+    (1..=12)
+        .map(|month| NaiveDate::from_ymd_opt(year, month, 1).unwrap())
+        .collect()
+}
+
+#[uniffi::export]
+fn date_parts(day: NaiveDate) -> Vec<i32> {
+    vec![day.year(), day.month() as i32, day.day() as i32]
+}
+
+#[uniffi::export]
+fn unix_seconds(time: DateTime<Utc>) -> i64 {
+    time.timestamp()
+}
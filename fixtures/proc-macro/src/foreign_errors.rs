@@ -0,0 +1,42 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+/// Errors thrown by foreign `Validator` implementations
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ValidationError {
+    #[error("{input:?} is shorter than {min_length}")]
+    TooShort { input: String, min_length: u32 },
+    #[error("{input:?} contains {word:?}")]
+    Forbidden { input: String, word: String },
+    /// Anything the foreign code threw that isn't a `ValidationError`
+    #[error("unexpected {class_name:?}: {reason}")]
+    Unexpected {
+        reason: String,
+        class_name: Option<String>,
+        cancelled: bool,
+    },
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for ValidationError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::Unexpected {
+            reason: e.reason,
+            class_name: e.class_name,
+            cancelled: e.cancelled,
+        }
+    }
+}
+
+#[uniffi::export(with_foreign)]
+pub trait Validator: Send + Sync {
+    fn validate(&self, input: String) -> Result<String, ValidationError>;
+}
+
+/// Run `input` through the validator, returning its error to the caller unchanged
+#[uniffi::export]
+fn run_validator(validator: Arc<dyn Validator>, input: String) -> Result<String, ValidationError> {
+    validator.validate(input)
+}
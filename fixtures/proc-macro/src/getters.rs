@@ -0,0 +1,60 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+/// A book, whose public fields are read-only properties in the bindings
+#[derive(uniffi::Object)]
+#[uniffi(getters)]
+pub struct Book {
+    /// The title on the cover
+    pub title: String,
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+    // Private fields don't get a getter.
+    copies: u32,
+}
+
+#[uniffi::export]
+impl Book {
+    #[uniffi::constructor]
+    fn new(title: String, authors: Vec<String>, year: Option<u16>) -> Self {
+        Self {
+            title,
+            authors,
+            year,
+            copies: 1,
+        }
+    }
+
+    fn copies(&self) -> u32 {
+        self.copies
+    }
+}
+
+// Only the fields with `#[uniffi(get)]` get a getter.
+#[derive(uniffi::Object)]
+pub struct Shelf {
+    #[uniffi(get)]
+    pub label: String,
+    #[uniffi(get)]
+    pub books: Vec<Arc<Book>>,
+    pub capacity: u32,
+}
+
+#[uniffi::export]
+impl Shelf {
+    #[uniffi::constructor]
+    fn new(label: String, books: Vec<Arc<Book>>) -> Self {
+        Self {
+            label,
+            books,
+            capacity: 10,
+        }
+    }
+
+    fn has_space(&self) -> bool {
+        (self.books.len() as u32) < self.capacity
+    }
+}
@@ -0,0 +1,64 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// An object whose methods are exported from three impl blocks in two modules.  The bindings
+// have a single `Inventory` class with all of them.
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+#[derive(uniffi::Object, Default)]
+pub struct Inventory {
+    items: Mutex<BTreeMap<String, u32>>,
+}
+
+#[uniffi::export]
+impl Inventory {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn count(&self, item: String) -> u32 {
+        self.items.lock().unwrap().get(&item).copied().unwrap_or(0)
+    }
+
+    fn items(&self) -> Vec<String> {
+        self.items.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+mod writes {
+    use super::Inventory;
+
+    #[uniffi::export]
+    impl Inventory {
+        #[uniffi::constructor]
+        fn with_item(item: String, count: u32) -> Self {
+            let inventory = Self::default();
+            inventory.add(item, count);
+            inventory
+        }
+
+        fn add(&self, item: String, count: u32) {
+            *self.items.lock().unwrap().entry(item).or_default() += count;
+        }
+    }
+
+    #[uniffi::export]
+    impl Inventory {
+        /// Removes up to `count` of the item, returning how many were removed
+        fn remove(&self, item: String, count: u32) -> u32 {
+            let mut items = self.items.lock().unwrap();
+            let Some(stock) = items.get_mut(&item) else {
+                return 0;
+            };
+            let removed = count.min(*stock);
+            *stock -= removed;
+            if *stock == 0 {
+                items.remove(&item);
+            }
+            removed
+        }
+    }
+}
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use serde_json::{json, Value};
+
+// `serde_json::Value`, which is supported with the `json` feature
+#[derive(uniffi::Record)]
+pub struct Event {
+    name: String,
+    payload: Option<Value>,
+}
+
+#[uniffi::export]
+fn json_round_trip(value: Value) -> Value {
+    value
+}
+
+#[uniffi::export]
+fn json_depth(value: Value) -> u32 {
+    match value {
+        Value::Array(items) => 1 + items.into_iter().map(json_depth).max().unwrap_or(0),
+        Value::Object(fields) => {
+            1 + fields
+                .into_iter()
+                .map(|(_, v)| json_depth(v))
+                .max()
+                .unwrap_or(0)
+        }
+        _ => 0,
+    }
+}
+
+#[uniffi::export]
+fn json_document() -> Value {
+    json!({
+        "name": "h\u{e9}llo \u{1F600}",
+        "big": u64::MAX,
+        "small": i64::MIN,
+        "float": 1.5e300,
+        "nested": [[[{"empty": {}}]], null, true],
+    })
+}
+
+#[uniffi::export]
+fn event_payload_keys(event: Event) -> Vec<String> {
+    match event.payload {
+        Some(Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => vec![],
+    }
+}
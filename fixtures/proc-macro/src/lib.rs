@@ -2,9 +2,28 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    num::{NonZeroI32, NonZeroU32, NonZeroU64, NonZeroU8},
+    sync::Arc,
+};
 
+mod builder;
 mod callback_interface;
+mod catch_all;
+mod color;
+mod constructors;
+mod dates;
+mod foreign_errors;
+mod getters;
+mod inventory;
+mod json;
+mod logger;
+mod object_records;
+mod paths;
+mod store;
+mod transaction;
+mod urls;
 
 use callback_interface::TestCallbackInterface;
 
@@ -18,6 +37,22 @@ pub fn one_inner_by_ref(one: &One) -> i32 {
     one.inner
 }
 
+// Test methods on records
+#[uniffi::export]
+impl One {
+    fn doubled(&self) -> i32 {
+        self.inner * 2
+    }
+
+    fn with_inner(self, inner: i32) -> One {
+        One { inner }
+    }
+
+    async fn doubled_async(&self) -> i32 {
+        self.inner * 2
+    }
+}
+
 #[derive(uniffi::Record)]
 pub struct Two {
     a: String,
@@ -54,6 +89,9 @@ impl Unused {
 
 #[uniffi::export]
 pub trait Trait: Send + Sync {
+    /// Whether `concat_strings` puts a separator between the strings
+    const USES_SEPARATOR: bool = false;
+
     // Test the absence of `with_foreign` by inputting reference arguments, which is
     // incompatible with callback interfaces
     #[allow(clippy::ptr_arg)]
@@ -75,6 +113,24 @@ pub trait TraitWithForeign: Send + Sync {
 
 struct RustTraitImpl {}
 
+#[uniffi::export(with_foreign)]
+pub trait Greeter: Send + Sync {
+    /// The greeting to use when there's no better one
+    const DEFAULT_GREETING: &'static str = "Hello";
+
+    fn name(&self) -> String;
+
+    // Foreign implementations can use this default implementation, or override it
+    fn greet(&self, greeting: String) -> String {
+        format!("{greeting}, {}!", self.name())
+    }
+}
+
+#[uniffi::export]
+fn greet_with(greeter: Arc<dyn Greeter>, greeting: String) -> String {
+    greeter.greet(greeting)
+}
+
 impl TraitWithForeign for RustTraitImpl {
     fn name(&self) -> String {
         "RustTraitImpl".to_string()
@@ -149,6 +205,25 @@ fn return_hashmap(h: HashMap<i8, u64>) -> HashMap<i8, u64> {
     h
 }
 
+#[uniffi::export]
+fn count_maybe_bools(values: Vec<MaybeBool>) -> HashMap<MaybeBool, u32> {
+    let mut counts = HashMap::new();
+    for v in values {
+        *counts.entry(v).or_default() += 1;
+    }
+    counts
+}
+
+#[uniffi::export]
+fn return_bool_keyed_map(h: HashMap<bool, u32>) -> HashMap<bool, u32> {
+    h
+}
+
+#[uniffi::export]
+fn return_u32_keyed_map(h: HashMap<u32, String>) -> HashMap<u32, String> {
+    h
+}
+
 #[uniffi::export]
 fn take_record_with_bytes(rwb: RecordWithBytes) -> Vec<u8> {
     rwb.some_bytes
@@ -178,6 +253,9 @@ fn call_callback_interface(cb: Box<dyn TestCallbackInterface>) {
     assert_eq!(42, cb.callback_handler(Object::new()));
 
     assert_eq!(6, cb.get_other_callback_interface().multiply(2, 3));
+    assert_eq!(25, cb.get_other_callback_interface().square(5));
+    cb.get_other_callback_interface()
+        .on_debug("called the default".to_string());
 }
 
 // Type that's defined in the UDL and not wrapped with #[uniffi::export]
@@ -199,7 +277,7 @@ fn make_record_with_bytes() -> RecordWithBytes {
     }
 }
 
-#[derive(uniffi::Enum)]
+#[derive(uniffi::Enum, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MaybeBool {
     True,
     False,
@@ -215,11 +293,50 @@ pub enum MixedEnum {
     All { s: String, i: i64 },
 }
 
+// Test methods on enums
+#[uniffi::export]
+impl MaybeBool {
+    fn label(&self) -> String {
+        match self {
+            MaybeBool::True => "yes",
+            MaybeBool::False => "no",
+            MaybeBool::Uncertain => "maybe",
+        }
+        .to_string()
+    }
+
+    fn negated(self) -> MaybeBool {
+        match self {
+            MaybeBool::True => MaybeBool::False,
+            MaybeBool::False => MaybeBool::True,
+            MaybeBool::Uncertain => MaybeBool::Uncertain,
+        }
+    }
+}
+
+#[uniffi::export]
+impl MixedEnum {
+    fn describe(&self) -> String {
+        match self {
+            MixedEnum::None => "none".to_string(),
+            MixedEnum::String(s) => format!("string {s}"),
+            MixedEnum::Int(i) => format!("int {i}"),
+            MixedEnum::Both(s, i) => format!("both {s} {i}"),
+            MixedEnum::All { s, i } => format!("all {s} {i}"),
+        }
+    }
+}
+
 #[uniffi::export]
 fn get_mixed_enum(v: Option<MixedEnum>) -> MixedEnum {
     v.unwrap_or(MixedEnum::Int(1))
 }
 
+#[uniffi::export]
+fn mixed_enum_or_default(#[uniffi(default = MixedEnum::None)] v: MixedEnum) -> MixedEnum {
+    v
+}
+
 #[repr(u8)]
 #[derive(uniffi::Enum)]
 pub enum ReprU8 {
@@ -338,6 +455,104 @@ fn renamed_rename_test() -> bool {
     true
 }
 
+/// Test renamed fields and arguments
+#[derive(uniffi::Record)]
+pub struct RecordWithRenamedFields {
+    #[uniffi(name = "timestamp_ms")]
+    ts_ms: u64,
+    #[uniffi(name = "label")]
+    name: String,
+}
+
+#[derive(uniffi::Enum)]
+pub enum EnumWithRenamedFields {
+    Event {
+        #[uniffi(name = "timestamp_ms")]
+        ts_ms: u64,
+    },
+}
+
+#[uniffi::export]
+fn make_record_with_renamed_fields(
+    #[uniffi(name = "timestamp_ms")] ts_ms: u64,
+    #[uniffi(name = "label")] name: String,
+) -> RecordWithRenamedFields {
+    RecordWithRenamedFields { ts_ms, name }
+}
+
+#[uniffi::export]
+fn get_enum_with_renamed_fields_timestamp(value: EnumWithRenamedFields) -> u64 {
+    match value {
+        EnumWithRenamedFields::Event { ts_ms } => ts_ms,
+    }
+}
+
+/// Test skipped fields
+#[derive(uniffi::Record)]
+pub struct RecordWithSkippedField {
+    value: u32,
+    // Rust-side state which can't cross the FFI
+    #[uniffi(skip)]
+    cache: std::cell::OnceCell<Vec<Box<dyn Fn() -> u32>>>,
+}
+
+/// Test tuple structs
+#[derive(uniffi::Record)]
+pub struct Point(#[uniffi(name = "x")] pub f64, #[uniffi(name = "y")] pub f64);
+
+#[derive(uniffi::Record)]
+pub struct TupleWithSkippedField(
+    pub u32,
+    #[uniffi(skip)] pub Option<String>,
+    #[uniffi(default = "unlabelled")] pub String,
+);
+
+#[uniffi::export]
+fn translate_point(point: Point, dx: f64, dy: f64) -> Point {
+    Point(point.0 + dx, point.1 + dy)
+}
+
+#[uniffi::export]
+fn double_tuple_with_skipped_field(tuple: TupleWithSkippedField) -> TupleWithSkippedField {
+    assert!(tuple.1.is_none());
+    TupleWithSkippedField(tuple.0 * 2, None, tuple.2)
+}
+
+#[derive(uniffi::Enum)]
+pub enum EnumWithSkippedField {
+    Value {
+        value: u32,
+        #[uniffi(skip)]
+        cache: std::cell::OnceCell<Vec<Box<dyn Fn() -> u32>>>,
+    },
+}
+
+#[uniffi::export]
+fn double_record_with_skipped_field(record: RecordWithSkippedField) -> RecordWithSkippedField {
+    // Skipped fields are always their default value after being lifted
+    assert!(record.cache.get().is_none());
+    let cache = std::cell::OnceCell::new();
+    let value = record.value;
+    let _ = cache.set(vec![Box::new(move || value * 2) as Box<dyn Fn() -> u32>]);
+    RecordWithSkippedField {
+        value: cache.get().unwrap()[0](),
+        cache,
+    }
+}
+
+#[uniffi::export]
+fn double_enum_with_skipped_field(value: EnumWithSkippedField) -> EnumWithSkippedField {
+    match value {
+        EnumWithSkippedField::Value { value, cache } => {
+            assert!(cache.get().is_none());
+            EnumWithSkippedField::Value {
+                value: value * 2,
+                cache,
+            }
+        }
+    }
+}
+
 /// Test defaults on Records
 #[derive(uniffi::Record)]
 pub struct RecordWithDefaults {
@@ -354,6 +569,37 @@ pub struct RecordWithDefaults {
     opt_vec: Option<Vec<bool>>,
     #[uniffi(default = Some(42))]
     opt_integer: Option<i32>,
+    #[uniffi(default = b"\xDE\xAD\xBE\xEF")]
+    magic: Vec<u8>,
+    #[uniffi(default = b"")]
+    empty_bytes: Vec<u8>,
+    #[uniffi(default = Some(b"ok"))]
+    opt_bytes: Option<Vec<u8>>,
+}
+
+/// Test defaults on enum variant fields
+#[derive(uniffi::Enum)]
+pub enum EnumWithDefaults {
+    Circle {
+        radius: f64,
+        #[uniffi(default = false)]
+        filled: bool,
+    },
+    // Fields without defaults can follow fields with defaults
+    Rect {
+        #[uniffi(default = 1.0)]
+        width: f64,
+        height: f64,
+    },
+}
+
+#[uniffi::export]
+fn enum_with_defaults_area(shape: EnumWithDefaults) -> f64 {
+    match shape {
+        EnumWithDefaults::Circle { filled: false, .. } => 0.0,
+        EnumWithDefaults::Circle { radius, .. } => 3.0 * radius * radius,
+        EnumWithDefaults::Rect { width, height } => width * height,
+    }
 }
 
 /// Test defaults on top-level functions
@@ -375,10 +621,191 @@ impl ObjectWithDefaults {
         Self { num }
     }
 
+    #[uniffi::constructor]
+    fn with_offset(num: i32, #[uniffi(default = 12)] offset: i32) -> Self {
+        Self { num: num + offset }
+    }
+
     #[uniffi::method(default(other = 12))]
     fn add_to_num(&self, other: i32) -> i32 {
         self.num + other
     }
 }
 
+/// Test `#[uniffi(default = ...)]` on arguments
+#[uniffi::export]
+fn describe_with_defaults(
+    name: String,
+    #[uniffi(default = 16)] capacity: u32,
+    #[uniffi(default = MaybeBool::Uncertain)] maybe: MaybeBool,
+    #[uniffi(default = {})] counts: HashMap<String, u32>,
+) -> String {
+    let maybe = match maybe {
+        MaybeBool::True => "true",
+        MaybeBool::False => "false",
+        MaybeBool::Uncertain => "uncertain",
+    };
+    format!("{name} {capacity} {maybe} {}", counts.len())
+}
+
+/// Test byte string defaults on arguments
+#[uniffi::export]
+fn bytes_with_default(#[uniffi(default = b"\x00uniffi")] data: Vec<u8>) -> Vec<u8> {
+    data
+}
+
+#[uniffi::export]
+fn roundtrip_u128(v: u128) -> u128 {
+    v
+}
+
+#[uniffi::export]
+fn roundtrip_i128(v: i128) -> i128 {
+    v
+}
+
+#[uniffi::export]
+fn u128_max() -> u128 {
+    u128::MAX
+}
+
+#[uniffi::export]
+fn first_char(s: String) -> Option<char> {
+    s.chars().next()
+}
+
+#[uniffi::export]
+fn repeat_char(c: char, count: u32) -> String {
+    std::iter::repeat(c).take(count as usize).collect()
+}
+
+#[uniffi::export]
+fn next_id(id: NonZeroU64) -> NonZeroU64 {
+    id.checked_add(1).expect("id overflowed")
+}
+
+#[uniffi::export]
+fn optional_id(id: Option<NonZeroU64>) -> Option<NonZeroU64> {
+    id
+}
+
+#[uniffi::export]
+fn largest_ids(mut ids: Vec<NonZeroU32>, count: NonZeroU8) -> Vec<NonZeroU32> {
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids.truncate(count.get() as usize);
+    ids
+}
+
+#[uniffi::export]
+fn negate_non_zero(v: NonZeroI32) -> NonZeroI32 {
+    -v
+}
+
+/// A custom type whose conversion from its builtin can fail
+pub struct EmailAddress(String);
+
+uniffi::custom_type!(EmailAddress, String);
+
+impl UniffiCustomTypeConverter for EmailAddress {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        if val.contains('@') {
+            Ok(EmailAddress(val))
+        } else if val.is_empty() {
+            Err(BasicError::InvalidInput.into())
+        } else {
+            Err(uniffi::deps::anyhow::anyhow!(
+                "`{val}` isn't an email address"
+            ))
+        }
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.0
+    }
+}
+
+#[uniffi::export]
+fn email_domain(email: EmailAddress) -> String {
+    email.0.split_once('@').unwrap().1.to_string()
+}
+
+#[uniffi::export]
+fn try_email_domain(email: EmailAddress) -> Result<String, BasicError> {
+    Ok(email_domain(email))
+}
+
+/// A newtype over bytes
+pub struct Digest(Vec<u8>);
+
+uniffi::custom_newtype!(Digest, Vec<u8>);
+
+#[uniffi::export]
+fn digest_len(digest: Digest) -> u32 {
+    digest.0.len() as u32
+}
+
+#[uniffi::export]
+fn xor_digest(digest: Digest, mask: u8) -> Digest {
+    Digest(digest.0.into_iter().map(|b| b ^ mask).collect())
+}
+
+/// A newtype over a sequence
+pub struct Tags(Vec<String>);
+
+uniffi::custom_newtype!(Tags, Vec<String>);
+
+#[uniffi::export]
+fn join_tags(tags: Tags) -> String {
+    tags.0.join(",")
+}
+
+// Types from crates which don't use UniFFI
+mod remote {
+    use semver::{BuildMetadata, Prerelease, Version};
+    use std::cmp::Ordering;
+
+    #[uniffi::remote(Record)]
+    pub struct Version {
+        pub major: u64,
+        pub minor: u64,
+        pub patch: u64,
+        #[uniffi(skip)]
+        pub pre: Prerelease,
+        #[uniffi(skip)]
+        pub build: BuildMetadata,
+    }
+
+    #[uniffi::remote(Enum)]
+    pub enum Ordering {
+        Less,
+        Equal,
+        Greater,
+    }
+
+    #[uniffi::export]
+    fn bump_minor_version(version: Version) -> Version {
+        Version::new(version.major, version.minor + 1, 0)
+    }
+
+    #[uniffi::export]
+    fn compare_versions(a: Version, b: Version) -> Ordering {
+        a.cmp(&b)
+    }
+}
+
+/// The largest payload that can be sent
+#[uniffi::export]
+pub const MAX_PAYLOAD_SIZE: u32 = 65536;
+
+#[uniffi::export]
+pub const DEFAULT_GREETING: &str = "hello";
+
+#[uniffi::export]
+pub const MIN_TEMPERATURE: f64 = -40.5;
+
+#[uniffi::export]
+pub const VERBOSE: bool = false;
+
 uniffi::include_scaffolding!("proc-macro");
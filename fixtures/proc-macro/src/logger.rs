@@ -0,0 +1,61 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+// Trait objects inside optionals, sequences and records
+#[uniffi::export(with_foreign)]
+pub trait Logger: Send + Sync {
+    fn log(&self, message: String) -> String;
+}
+
+struct RustLogger;
+
+impl Logger for RustLogger {
+    fn log(&self, message: String) -> String {
+        format!("rust: {message}")
+    }
+}
+
+#[uniffi::export]
+fn make_rust_logger() -> Arc<dyn Logger> {
+    Arc::new(RustLogger)
+}
+
+#[derive(uniffi::Record)]
+pub struct LoggerConfig {
+    pub name: String,
+    #[uniffi(default = None)]
+    pub logger: Option<Arc<dyn Logger>>,
+}
+
+#[uniffi::export]
+fn log_with(logger: Option<Arc<dyn Logger>>, message: String) -> Option<String> {
+    logger.map(|l| l.log(message))
+}
+
+#[uniffi::export]
+fn log_with_all(loggers: Vec<Arc<dyn Logger>>, message: String) -> Vec<String> {
+    loggers.iter().map(|l| l.log(message.clone())).collect()
+}
+
+#[uniffi::export]
+fn log_with_config(config: LoggerConfig, message: String) -> Option<String> {
+    log_with(config.logger, format!("{}: {message}", config.name))
+}
+
+#[uniffi::export]
+fn roundtrip_optional_logger(logger: Option<Arc<dyn Logger>>) -> Option<Arc<dyn Logger>> {
+    logger
+}
+
+#[uniffi::export]
+fn roundtrip_loggers(loggers: Vec<Arc<dyn Logger>>) -> Vec<Arc<dyn Logger>> {
+    loggers
+}
+
+#[uniffi::export]
+fn roundtrip_logger_config(config: LoggerConfig) -> LoggerConfig {
+    config
+}
@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{collections::HashMap, sync::Arc};
+
+#[derive(uniffi::Object)]
+pub struct Member {
+    name: String,
+}
+
+#[uniffi::export]
+impl Member {
+    #[uniffi::constructor]
+    fn new(name: String) -> Arc<Self> {
+        Arc::new(Self { name })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    // Includes the reference held for this call
+    fn strong_count(self: Arc<Self>) -> u64 {
+        Arc::strong_count(&self) as u64
+    }
+}
+
+// A record holding objects inside sequences, optionals and maps
+#[derive(uniffi::Record)]
+pub struct Team {
+    pub members: Vec<Arc<Member>>,
+    #[uniffi(default = None)]
+    pub captain: Option<Arc<Member>>,
+    #[uniffi(default = {})]
+    pub by_role: HashMap<String, Arc<Member>>,
+    #[uniffi(default = [])]
+    pub substitutes: Vec<Option<Arc<Member>>>,
+}
+
+#[uniffi::export]
+fn roundtrip_team(team: Team) -> Team {
+    team
+}
+
+#[uniffi::export]
+fn team_member_names(team: Team) -> Vec<String> {
+    team.members.iter().map(|m| m.name()).collect()
+}
+
+#[uniffi::export(callback_interface)]
+pub trait TeamSource {
+    fn team(&self) -> Team;
+}
+
+// Get a team from a foreign implementation, checking the objects in it can be used from Rust.
+#[uniffi::export]
+fn team_from_source(source: Box<dyn TeamSource>) -> Team {
+    let team = source.team();
+    assert!(team.members.iter().all(|m| !m.name().is_empty()));
+    team
+}
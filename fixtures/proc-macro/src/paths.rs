@@ -0,0 +1,55 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::path::{Path, PathBuf};
+
+#[derive(uniffi::Record)]
+pub struct Download {
+    url: String,
+    destination: Option<PathBuf>,
+}
+
+#[uniffi::export]
+fn path_round_trip(path: PathBuf) -> PathBuf {
+    path
+}
+
+#[uniffi::export]
+fn path_file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+#[uniffi::export]
+fn path_join(base: &Path, parts: Vec<String>) -> PathBuf {
+    parts
+        .iter()
+        .fold(base.to_owned(), |path, part| path.join(part))
+}
+
+#[uniffi::export]
+fn download_destination(download: Download) -> PathBuf {
+    let name = download.url.rsplit('/').next().unwrap_or_default();
+    download.destination.unwrap_or_default().join(name)
+}
+
+/// A path which isn't valid UTF-8 on Unix, `/caf\xe9`, or `/café` elsewhere
+#[uniffi::export]
+fn latin1_path() -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        std::ffi::OsString::from_vec(b"/caf\xe9".to_vec()).into()
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from("/caf\u{e9}")
+    }
+}
+
+/// The length in bytes of `path`, to check the bytes the foreign code sent
+#[uniffi::export]
+fn path_byte_len(path: PathBuf) -> u32 {
+    path.into_os_string().len() as u32
+}
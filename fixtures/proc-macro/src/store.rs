@@ -0,0 +1,112 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+// Trait objects returned from factory functions and methods, since traits can't have
+// constructors
+#[uniffi::export]
+pub trait Store: Send + Sync {
+    fn get(&self, key: String) -> Option<String>;
+    /// Returns false if the store is read-only
+    fn set(&self, key: String, value: String) -> bool;
+    fn kind(&self) -> StoreKind;
+}
+
+#[derive(uniffi::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreKind {
+    Memory,
+    ReadOnly,
+}
+
+#[derive(Default)]
+struct MemoryStore(Mutex<HashMap<String, String>>);
+
+impl Store for MemoryStore {
+    fn get(&self, key: String) -> Option<String> {
+        self.0.lock().unwrap().get(&key).cloned()
+    }
+
+    fn set(&self, key: String, value: String) -> bool {
+        self.0.lock().unwrap().insert(key, value);
+        true
+    }
+
+    fn kind(&self) -> StoreKind {
+        StoreKind::Memory
+    }
+}
+
+struct ReadOnlyStore;
+
+impl Store for ReadOnlyStore {
+    fn get(&self, key: String) -> Option<String> {
+        (key == "name").then(|| "read-only".to_string())
+    }
+
+    fn set(&self, _key: String, _value: String) -> bool {
+        false
+    }
+
+    fn kind(&self) -> StoreKind {
+        StoreKind::ReadOnly
+    }
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum StoreError {
+    #[error("no store named {name}")]
+    UnknownStore { name: String },
+}
+
+#[uniffi::export]
+fn make_store(kind: StoreKind) -> Arc<dyn Store> {
+    match kind {
+        StoreKind::Memory => Arc::new(MemoryStore::default()),
+        StoreKind::ReadOnly => Arc::new(ReadOnlyStore),
+    }
+}
+
+#[uniffi::export]
+fn make_store_named(name: String) -> Result<Arc<dyn Store>, StoreError> {
+    match name.as_str() {
+        "memory" => Ok(make_store(StoreKind::Memory)),
+        "read-only" => Ok(make_store(StoreKind::ReadOnly)),
+        _ => Err(StoreError::UnknownStore { name }),
+    }
+}
+
+#[uniffi::export]
+fn store_get_or(store: Arc<dyn Store>, key: String, default: String) -> String {
+    store.get(key).unwrap_or(default)
+}
+
+#[derive(uniffi::Object, Default)]
+pub struct StoreRegistry {
+    stores: Mutex<HashMap<String, Arc<dyn Store>>>,
+}
+
+#[uniffi::export]
+impl StoreRegistry {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&self, name: String, store: Arc<dyn Store>) {
+        self.stores.lock().unwrap().insert(name, store);
+    }
+
+    fn lookup(&self, name: String) -> Result<Arc<dyn Store>, StoreError> {
+        self.stores
+            .lock()
+            .unwrap()
+            .get(&name)
+            .cloned()
+            .ok_or(StoreError::UnknownStore { name })
+    }
+}
@@ -0,0 +1,59 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+static LIVE_TRANSACTIONS: AtomicU64 = AtomicU64::new(0);
+
+// An object with methods that consume it, the bindings can't use a `Transaction` once it's been
+// committed or rolled back.
+#[derive(uniffi::Object)]
+pub struct Transaction {
+    writes: Mutex<Vec<String>>,
+}
+
+#[uniffi::export]
+impl Transaction {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        LIVE_TRANSACTIONS.fetch_add(1, Ordering::SeqCst);
+        Self {
+            writes: Mutex::new(vec![]),
+        }
+    }
+
+    fn write(&self, value: String) {
+        self.writes.lock().unwrap().push(value);
+    }
+
+    fn pending(&self) -> u32 {
+        self.writes.lock().unwrap().len() as u32
+    }
+
+    /// Returns the committed writes
+    #[uniffi::method(consumes)]
+    fn commit(self: Arc<Self>) -> Vec<String> {
+        std::mem::take(&mut *self.writes.lock().unwrap())
+    }
+
+    /// Returns the number of discarded writes
+    #[uniffi::method(consumes)]
+    fn rollback(self: Arc<Self>) -> u32 {
+        self.pending()
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        LIVE_TRANSACTIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[uniffi::export]
+fn live_transactions() -> u64 {
+    LIVE_TRANSACTIONS.load(Ordering::SeqCst)
+}
@@ -0,0 +1,32 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use url::Url;
+
+// `url::Url`, which is supported with the `url` feature
+#[derive(uniffi::Record)]
+pub struct Link {
+    title: String,
+    href: Option<Url>,
+}
+
+#[uniffi::export]
+fn url_round_trip(url: Url) -> Url {
+    url
+}
+
+#[uniffi::export]
+fn url_host(url: Url) -> Option<String> {
+    url.host_str().map(str::to_string)
+}
+
+#[uniffi::export]
+fn url_join(base: Url, path: String) -> Option<Url> {
+    base.join(&path).ok()
+}
+
+#[uniffi::export]
+fn link_hrefs(links: Vec<Link>) -> Vec<Url> {
+    links.into_iter().filter_map(|link| link.href).collect()
+}
@@ -3,10 +3,14 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.fixture.proc_macro.*;
+import kotlinx.coroutines.runBlocking
 
 val one = makeOne(123)
 assert(one.inner == 123)
 assert(oneInnerByRef(one) == 123)
+assert(one.doubled() == 246)
+assert(one.withInner(5).inner == 5)
+assert(runBlocking { one.doubledAsync() } == 246)
 
 val two = Two("a")
 assert(takeTwo(two) == "a")
@@ -22,6 +26,11 @@ assert(obj.isOtherHeavy(obj2) == MaybeBool.UNCERTAIN)
 
 assert(enumIdentity(MaybeBool.TRUE) == MaybeBool.TRUE)
 
+// map keys other than strings
+assert(countMaybeBools(listOf(MaybeBool.TRUE, MaybeBool.UNCERTAIN, MaybeBool.TRUE)) == mapOf(MaybeBool.TRUE to 2u, MaybeBool.UNCERTAIN to 1u))
+assert(returnBoolKeyedMap(mapOf(true to 1u, false to 0u)) == mapOf(true to 1u, false to 0u))
+assert(returnU32KeyedMap(mapOf(UInt.MAX_VALUE to "max")) == mapOf(UInt.MAX_VALUE to "max"))
+
 // just make sure this works / doesn't crash
 val three = Three(obj)
 
@@ -43,6 +52,20 @@ try {
 } catch (e: FlatException) {
 }
 
+// Renamed fields and arguments
+
+val recordWithRenamedFields = makeRecordWithRenamedFields(timestampMs = 1000UL, label = "start")
+assert(recordWithRenamedFields.timestampMs == 1000UL)
+assert(recordWithRenamedFields.label == "start")
+assert(getEnumWithRenamedFieldsTimestamp(EnumWithRenamedFields.Event(timestampMs = 2000UL)) == 2000UL)
+
+// Skipped fields
+
+assert(doubleRecordWithSkippedField(RecordWithSkippedField(21u)) == RecordWithSkippedField(42u))
+assert(translatePoint(Point(1.0, 2.0), 0.5, -1.0) == Point(1.5, 1.0))
+assert(doubleTupleWithSkippedField(TupleWithSkippedField(21u)) == TupleWithSkippedField(42u, "unlabelled"))
+assert(doubleEnumWithSkippedField(EnumWithSkippedField.Value(21u)) == EnumWithSkippedField.Value(42u))
+
 // Defaults
 
 val recordWithDefaults = RecordWithDefaults("Test")
@@ -53,11 +76,57 @@ assert(recordWithDefaults.floatVar == 4.2)
 assert(recordWithDefaults.vec.isEmpty())
 assert(recordWithDefaults.optVec == null)
 assert(recordWithDefaults.optInteger == 42)
+assert(recordWithDefaults.magic.contentEquals(byteArrayOf(0xDE.toByte(), 0xAD.toByte(), 0xBE.toByte(), 0xEF.toByte())))
+assert(recordWithDefaults.emptyBytes.isEmpty())
+assert(recordWithDefaults.optBytes!!.contentEquals("ok".toByteArray()))
+assert(bytesWithDefault().contentEquals(byteArrayOf(0) + "uniffi".toByteArray()))
 
 assert(doubleWithDefault() == 42)
 
+assert(enumWithDefaultsArea(EnumWithDefaults.Circle(2.0)) == 0.0)
+assert(enumWithDefaultsArea(EnumWithDefaults.Circle(2.0, filled = true)) == 12.0)
+assert(enumWithDefaultsArea(EnumWithDefaults.Rect(height = 2.0)) == 2.0)
+
 val objWithDefaults = ObjectWithDefaults()
 assert(objWithDefaults.addToNum() == 42)
+assert(ObjectWithDefaults.withOffset(18).addToNum() == 42)
+
+// Every form of constructor, which throw `ConstructorException` for values over 100
+fun assertTooLarge(value: UInt, construct: () -> Any) {
+    try {
+        construct()
+        throw RuntimeException("the constructor should have thrown")
+    } catch (e: ConstructorException.TooLarge) {
+        assert(e.value == value)
+    }
+}
+
+assert(PrimaryInfallible(7u).describe() == "new 7")
+assert(PrimaryInfallible.withDefaults().describe() == "with_defaults 1 default")
+assert(PrimaryInfallible.withDefaults(label = "given").describe() == "with_defaults 1 given")
+assert(PrimaryInfallible.fallible(8u).describe() == "fallible 8")
+assertTooLarge(101u) { PrimaryInfallible.fallible(101u) }
+assert(PrimaryInfallible.fallibleWithDefaults().describe() == "fallible_with_defaults 2 fallible")
+assert(PrimaryInfallible.fallibleWithDefaults(value = 9u).describe() == "fallible_with_defaults 9 fallible")
+assertTooLarge(102u) { PrimaryInfallible.fallibleWithDefaults(102u) }
+assert(runBlocking { PrimaryInfallible.asyncInfallible(10u) }.describe() == "async_infallible 10")
+assert(runBlocking { PrimaryInfallible.asyncFallible(11u) }.describe() == "async_fallible 11")
+assertTooLarge(103u) { runBlocking { PrimaryInfallible.asyncFallible(103u) } }
+assert(runBlocking { PrimaryInfallible.asyncFallibleWithDefaults() }.describe() == "async_fallible_with_defaults 3")
+assertTooLarge(104u) { runBlocking { PrimaryInfallible.asyncFallibleWithDefaults(104u) } }
+assert(PrimaryWithDefaults().describe() == "new 4 primary")
+assert(PrimaryWithDefaults(12u, "given").describe() == "new 12 given")
+assert(PrimaryFallible(13u).describe() == "new 13")
+assertTooLarge(105u) { PrimaryFallible(105u) }
+assert(PrimaryFallible.fallibleWithDefaults().describe() == "fallible_with_defaults 6")
+assertTooLarge(106u) { PrimaryFallible.fallibleWithDefaults(106u) }
+assert(PrimaryFallibleWithDefaults().describe() == "new 5")
+assertTooLarge(107u) { PrimaryFallibleWithDefaults(107u) }
+// Fallible constructors declare what they throw, for Java callers
+assert(PrimaryFallible::class.java.constructors.any { it.exceptionTypes.contains(ConstructorException::class.java) })
+assert(PrimaryInfallible::class.java.constructors.none { it.exceptionTypes.isNotEmpty() })
+assert(describeWithDefaults("test") == "test 16 uncertain 0")
+assert(describeWithDefaults("test", maybe = MaybeBool.TRUE, counts = mapOf("a" to 1u)) == "test 16 true 1")
 
 // Traits
 
@@ -70,6 +139,26 @@ val traitImpl2 = obj.getTraitWithForeign(null)
 assert(traitImpl2.name() == "RustTraitImpl")
 assert(obj.getTraitWithForeign(traitImpl2).name() == "RustTraitImpl")
 
+// Implementations which don't override `greet` use the Rust default implementation
+class KtGreeter : Greeter {
+    override fun name() = "Kotlin"
+}
+
+class KtCasualGreeter : Greeter {
+    override fun name() = "Kotlin"
+
+    override fun greet(greeting: String) = "Hey"
+}
+
+assert(KtGreeter().greet("Hello") == "Hello, Kotlin!")
+assert(greetWith(KtGreeter(), "Hello") == "Hello, Kotlin!")
+assert(greetWith(KtCasualGreeter(), "Hello") == "Hey")
+
+// Trait constants are in the companion object of the interface
+assert(Greeter.DEFAULT_GREETING == "Hello")
+assert(TraitInterface.USES_SEPARATOR == false)
+assert(OtherCallbackInterface.IDENTITY == 1u)
+
 
 class KtTestCallbackInterface : TestCallbackInterface {
     override fun doNothing() { }
@@ -108,8 +197,17 @@ callCallbackInterface(KtTestCallbackInterface())
 
 assert(getMixedEnum(null) == MixedEnum.Int(1))
 assert(getMixedEnum(MixedEnum.None) == MixedEnum.None)
+assert(mixedEnumOrDefault() == MixedEnum.None)
 assert(getMixedEnum(MixedEnum.String("hello")) == MixedEnum.String("hello"))
 
+// methods on enums
+assert(MaybeBool.TRUE.label() == "yes")
+assert(MaybeBool.TRUE.negated() == MaybeBool.FALSE)
+assert(MaybeBool.UNCERTAIN.negated() == MaybeBool.UNCERTAIN)
+assert(MixedEnum.None.describe() == "none")
+assert(MixedEnum.Both("hello", 1).describe() == "both hello 1")
+assert(MixedEnum.All("string", 2).describe() == "all string 2")
+
 val e = getMixedEnum(null)
 if (e is MixedEnum.Int) {
     // you can destruct the enum into its bits.
@@ -122,3 +220,382 @@ val eb = MixedEnum.Both("hi", 2)
 val (s, i) = eb
 assert(s == "hi")
 assert(i == 2L)
+
+// constants
+assert(ProcMacro.MAX_PAYLOAD_SIZE == 65536u)
+assert(ProcMacro.DEFAULT_GREETING == "hello")
+assert(ProcMacro.MIN_TEMPERATURE == -40.5)
+assert(!ProcMacro.VERBOSE)
+assert(ProcMacro.UDL_OFFSET == -12)
+
+// 128-bit integers
+val u128Max = java.math.BigInteger.ONE.shiftLeft(128).subtract(java.math.BigInteger.ONE)
+val i128Min = java.math.BigInteger.ONE.shiftLeft(127).negate()
+assert(roundtripU128(java.math.BigInteger.ZERO) == java.math.BigInteger.ZERO)
+assert(roundtripU128(u128Max) == u128Max)
+assert(u128Max() == u128Max)
+assert(roundtripI128(i128Min) == i128Min)
+assert(roundtripI128(java.math.BigInteger.valueOf(-5)) == java.math.BigInteger.valueOf(-5))
+
+try {
+    roundtripU128(u128Max.add(java.math.BigInteger.ONE))
+    throw RuntimeException("roundtripU128 should throw for values larger than u128::MAX")
+} catch (e: IllegalArgumentException) {
+}
+
+try {
+    roundtripI128(i128Min.subtract(java.math.BigInteger.ONE))
+    throw RuntimeException("roundtripI128 should throw for values smaller than i128::MIN")
+} catch (e: IllegalArgumentException) {
+}
+
+// chars
+assert(firstChar("héllo") == 'h')
+assert(firstChar("") == null)
+assert(repeatChar('é', 3u) == "ééé")
+
+try {
+    firstChar("😀")
+    throw RuntimeException("firstChar should throw for chars outside of the BMP")
+} catch (e: InternalException) {
+}
+
+try {
+    repeatChar('\uD800', 1u)
+    throw RuntimeException("repeatChar should throw for surrogates")
+} catch (e: IllegalArgumentException) {
+}
+
+// non-zero integers, which are passed as plain integers
+assert(nextId(41uL) == 42uL)
+assert(optionalId(null) == null)
+assert(optionalId(7uL) == 7uL)
+assert(largestIds(listOf(3u, 9u, 1u, 5u), 2u.toUByte()) == listOf(9u, 5u))
+assert(negateNonZero(-3) == 3)
+
+try {
+    nextId(0uL)
+    throw RuntimeException("nextId should throw for zero")
+} catch (e: InternalException) {
+    assert(e.message!!.contains("'id': value must be non-zero"))
+}
+
+try {
+    largestIds(listOf(1u, 0u), 1u.toUByte())
+    throw RuntimeException("largestIds should throw for a zero id")
+} catch (e: InternalException) {
+}
+
+// chrono dates and times
+val leapDay = java.time.LocalDate.of(2024, 2, 29)
+val appointment = appointmentOn(leapDay, 9u)
+assert(appointment.day == leapDay)
+assert(appointment.startsAt == java.time.Instant.parse("2024-02-29T09:00:00Z"))
+assert(appointmentLengthSeconds(appointment) == 3600L)
+assert(appointmentLengthSeconds(appointment.copy(endsAt = null)) == null)
+assert(nextDay(java.time.LocalDate.of(2023, 12, 31)) == java.time.LocalDate.of(2024, 1, 1))
+assert(nextDay(java.time.LocalDate.of(1969, 12, 31)) == java.time.LocalDate.of(1970, 1, 1))
+assert(firstOfMonths(-1)[1] == java.time.LocalDate.of(-1, 2, 1))
+assert(dateParts(java.time.LocalDate.of(1582, 10, 4)) == listOf(1582, 10, 4))
+assert(unixSeconds(java.time.Instant.parse("1970-01-02T00:00:00Z")) == 86400L)
+try {
+    nextDay(java.time.LocalDate.MAX)
+    throw RuntimeException("nextDay should have thrown")
+} catch (e: IllegalArgumentException) {
+    // too far from 1970 to be passed
+}
+
+// JSON values, which are their JSON text by default.  Rust writes it compactly, with sorted keys.
+assert(jsonRoundTrip("{ \"b\": [1, 2.5], \"a\": null }") == "{\"a\":null,\"b\":[1,2.5]}")
+assert(jsonRoundTrip("\"h\u00e9llo \uD83D\uDE00\"") == "\"h\u00e9llo \uD83D\uDE00\"")
+assert(jsonRoundTrip("18446744073709551615") == "18446744073709551615")
+assert(jsonRoundTrip("-9223372036854775808") == "-9223372036854775808")
+assert(jsonDepth("[[[{\"a\": []}]]]") == 5u)
+assert(eventPayloadKeys(Event("click", "{\"y\": 2, \"x\": 1}")) == listOf("x", "y"))
+assert(eventPayloadKeys(Event("none", null)) == listOf<String>())
+assert(jsonDocument().contains("\"big\":18446744073709551615"))
+try {
+    jsonRoundTrip("{")
+    throw RuntimeException("jsonRoundTrip should have thrown")
+} catch (e: InternalException) {
+    assert(e.message!!.contains("invalid JSON value"))
+}
+
+// file system paths, which are `java.io.File`s by default
+assert(pathRoundTrip(java.io.File("/tmp/h\u00e9llo \uD83D\uDE00")) == java.io.File("/tmp/h\u00e9llo \uD83D\uDE00"))
+assert(pathFileName(java.io.File("/tmp/notes.txt")) == "notes.txt")
+assert(pathFileName(java.io.File("/")) == null)
+assert(pathJoin(java.io.File("/tmp"), listOf("a", "b.txt")) == java.io.File("/tmp/a/b.txt"))
+assert(downloadDestination(Download("https://example.com/file.zip", java.io.File("/downloads"))) == java.io.File("/downloads/file.zip"))
+assert(downloadDestination(Download("https://example.com/file.zip", null)) == java.io.File("file.zip"))
+assert(pathByteLen(java.io.File("/caf\u00e9")) == 6u)
+// Bytes which aren't valid UTF-8 are replaced
+assert(latin1Path().path == "/caf\uFFFD")
+
+// URLs, which are `java.net.URI`s with this fixture's `url_type`
+assert(urlRoundTrip(java.net.URI("https://example.com")) == java.net.URI("https://example.com/"))
+assert(urlHost(java.net.URI("https://user@example.com:8080/")) == "example.com")
+assert(urlJoin(java.net.URI("https://example.com/a/b"), "../c") == java.net.URI("https://example.com/c"))
+assert(linkHrefs(listOf(Link("home", java.net.URI("https://example.com/")), Link("none", null))) == listOf(java.net.URI("https://example.com/")))
+try {
+    urlRoundTrip(java.net.URI("example.com"))
+    throw RuntimeException("urlRoundTrip should have thrown")
+} catch (e: InternalException) {
+    // The error names the argument
+    assert(e.message!!.contains("'url'"))
+}
+
+// remote types
+assert(bumpMinorVersion(Version(1uL, 2uL, 3uL)) == Version(1uL, 3uL, 0uL))
+assert(compareVersions(Version(1uL, 2uL, 3uL), Version(1uL, 10uL, 0uL)) == Ordering.LESS)
+assert(compareVersions(Version(2uL, 0uL, 0uL), Version(2uL, 0uL, 0uL)) == Ordering.EQUAL)
+
+// fallible custom types
+assert(emailDomain("me@example.com") == "example.com")
+try {
+    emailDomain("nope")
+    throw RuntimeException("emailDomain should have thrown")
+} catch (e: InternalException) {
+    assert(e.message!!.contains("`nope` isn't an email address"))
+}
+try {
+    tryEmailDomain("")
+    throw RuntimeException("tryEmailDomain should have thrown")
+} catch (e: BasicException.InvalidInput) {
+}
+
+// custom newtypes over bytes
+assert(digestLen(byteArrayOf(1, 2, 3)) == 3u)
+assert(xorDigest(byteArrayOf(0, -1), 0x0fu).contentEquals(byteArrayOf(0x0f, 0xf0.toByte())))
+assert(joinTags(listOf("a", "b")) == "a,b")
+
+// builder methods returning objects
+val client = ClientBuilder().withTimeout(5uL).withRetries(3u).build()
+assert(client.timeoutSecs() == 5uL)
+assert(client.retries() == 3u)
+assert(ClientBuilder().build().timeoutSecs() == 30uL)
+try {
+    ClientBuilder().withTimeout(0uL)
+    throw RuntimeException("withTimeout should have thrown")
+} catch (e: ClientBuildException.ZeroTimeout) {
+}
+try {
+    ClientBuilder().withRetries(11u).build()
+    throw RuntimeException("build should have thrown")
+} catch (e: ClientBuildException.TooManyRetries) {
+}
+
+// records holding objects
+val alice = Member("alice")
+val bob = Member("bob")
+assert(alice.strongCount() == 2uL)
+val team = Team(listOf(alice, bob), alice, mapOf("keeper" to bob), listOf(null, bob))
+repeat(10) {
+    // Destroying the returned record destroys the objects in its sequences and maps too
+    roundtripTeam(team).use { returned ->
+        assert(returned.members.map { it.name() } == listOf("alice", "bob"))
+        assert(returned.captain!!.name() == "alice")
+        assert(returned.byRole["keeper"]!!.name() == "bob")
+        assert(returned.substitutes[0] == null && returned.substitutes[1]!!.name() == "bob")
+    }
+}
+assert(teamMemberNames(Team(listOf(bob))) == listOf("bob"))
+assert(alice.strongCount() == 2uL)
+assert(bob.strongCount() == 2uL)
+
+class KtTeamSource : TeamSource {
+    override fun team() = Team(listOf(alice), byRole = mapOf("captain" to alice))
+}
+repeat(10) {
+    teamFromSource(KtTeamSource()).use { assert(it.byRole["captain"]!!.name() == "alice") }
+}
+assert(alice.strongCount() == 2uL)
+
+// trait objects in optionals, sequences and records
+class KtLogger : Logger {
+    override fun log(message: String) = "kotlin: $message"
+}
+
+val rustLogger = makeRustLogger()
+assert(logWith(null, "hi") == null)
+assert(logWith(rustLogger, "hi") == "rust: hi")
+assert(logWith(KtLogger(), "hi") == "kotlin: hi")
+assert(logWithAll(listOf(rustLogger, KtLogger()), "hi") == listOf("rust: hi", "kotlin: hi"))
+assert(logWithAll(listOf(), "hi") == listOf<String>())
+assert(logWithConfig(LoggerConfig("app"), "hi") == null)
+assert(logWithConfig(LoggerConfig("app", KtLogger()), "hi") == "kotlin: app: hi")
+assert(roundtripOptionalLogger(null) == null)
+assert(roundtripOptionalLogger(KtLogger())!!.log("hi") == "kotlin: hi")
+assert(roundtripLoggers(listOf(KtLogger(), rustLogger)).map { it.log("hi") } == listOf("kotlin: hi", "rust: hi"))
+assert(roundtripLoggerConfig(LoggerConfig("app")).logger == null)
+assert(roundtripLoggerConfig(LoggerConfig("app", rustLogger)).logger!!.log("hi") == "rust: hi")
+
+// trait objects returned from factory functions and methods
+val store = makeStore(StoreKind.MEMORY)
+assert(store.kind() == StoreKind.MEMORY)
+assert(store.set("name", "memory"))
+assert(store.get("name") == "memory")
+assert(storeGetOr(store, "name", "default") == "memory")
+assert(storeGetOr(store, "missing", "default") == "default")
+assert(makeStoreNamed("read-only").kind() == StoreKind.READ_ONLY)
+assert(!makeStoreNamed("read-only").set("name", "other"))
+try {
+    makeStoreNamed("disk")
+    throw RuntimeException("makeStoreNamed should have thrown")
+} catch (e: StoreException.UnknownStore) {
+    assert(e.name == "disk")
+}
+
+val registry = StoreRegistry()
+registry.register("main", store)
+assert(registry.lookup("main").get("name") == "memory")
+registry.lookup("main").set("name", "updated")
+assert(store.get("name") == "updated")
+try {
+    registry.lookup("other")
+    throw RuntimeException("lookup should have thrown")
+} catch (e: StoreException.UnknownStore) {
+}
+
+// methods which consume the object
+val live = liveTransactions()
+val tx = Transaction()
+tx.write("a")
+tx.write("b")
+assert(liveTransactions() == live + 1uL)
+assert(tx.commit() == listOf("a", "b"))
+// The Rust object is dropped once the consuming call returns.
+assert(liveTransactions() == live)
+for (call in listOf({ tx.commit() }, { tx.rollback() }, { tx.pending() }, { tx.write("c") })) {
+    try {
+        call()
+        throw RuntimeException("the transaction should have been consumed")
+    } catch (e: IllegalStateException) {
+        assert(e.message!!.contains("consumed"))
+    }
+}
+// Destroying a consumed object does nothing.
+tx.destroy()
+assert(liveTransactions() == live)
+
+// Calls racing with the consuming one either succeed or see the consumed object.
+val racingTx = Transaction()
+val writers = List(4) {
+    Thread {
+        try {
+            while (true) {
+                racingTx.write("x")
+            }
+        } catch (e: IllegalStateException) {
+        }
+    }
+}
+writers.forEach { it.start() }
+racingTx.rollback()
+writers.forEach { it.join() }
+assert(liveTransactions() == live)
+
+// static methods
+assert(Color.fromHex("#ff8000")!!.hex() == "#ff8000")
+assert(Color.fromHex("ff8000") == null)
+assert(Color.primaries().map { it.hex() } == listOf("#ff0000", "#00ff00", "#0000ff"))
+assert(Color.channelCount() == 3.toUByte())
+assert(Color.channelCount(alpha = true) == 4.toUByte())
+assert(runBlocking { Color.named("white") }!!.hex() == "#ffffff")
+assert(runBlocking { Color.named("mauve") } == null)
+
+// anyhow and boxed errors, which are thrown as `CatchAllException`
+assert(parsePort("8080") == 8080.toUShort())
+try {
+    parsePort("0")
+    throw RuntimeException("parsePort should have thrown")
+} catch (e: CatchAllException.Failed) {
+    assert(e.message == "port 0 is reserved")
+}
+assert(parseRatio("1/4") == 0.25)
+try {
+    parseRatio("1")
+    throw RuntimeException("parseRatio should have thrown")
+} catch (e: CatchAllException.Failed) {
+    assert(e.message == "missing `/`")
+}
+assert(parseFlag("true"))
+assert(runBlocking { parsePortAsync("443") } == 443.toUShort())
+val endpoint = Endpoint("localhost:8080")
+try {
+    endpoint.withPort("0")
+    throw RuntimeException("withPort should have thrown")
+} catch (e: CatchAllException.Failed) {
+    assert(e.message == "can't change the port: port 0 is reserved")
+}
+
+// Getters of public fields, which are read-only properties
+val book = Book("Dune", listOf("Frank Herbert"), 1965.toUShort())
+assert(book.title == "Dune")
+assert(book.authors == listOf("Frank Herbert"))
+assert(book.year == 1965.toUShort())
+assert(Book("Anonymous", listOf(), null).year == null)
+assert(book.copies() == 1.toUInt())
+val shelf = Shelf("sci-fi", listOf(book))
+assert(shelf.label == "sci-fi")
+assert(shelf.books.map { it.title } == listOf("Dune"))
+assert(shelf.hasSpace())
+
+// Errors thrown by foreign trait implementations
+class KtValidator : Validator {
+    override fun validate(input: String): String {
+        if (input.length < 3) {
+            throw ValidationException.TooShort(input, 3u)
+        }
+        if (input.contains("bad")) {
+            throw ValidationException.Forbidden(input, "bad")
+        }
+        if (input == "crash") {
+            throw IllegalStateException("can't validate crash")
+        }
+        if (input == "cancel") {
+            throw kotlin.coroutines.cancellation.CancellationException("validation was cancelled")
+        }
+        return input.uppercase()
+    }
+}
+
+val validator = KtValidator()
+assert(runValidator(validator, "good") == "GOOD")
+try {
+    runValidator(validator, "ok")
+    throw RuntimeException("runValidator should have thrown")
+} catch (e: ValidationException.TooShort) {
+    assert(e.input == "ok")
+    assert(e.minLength == 3u)
+}
+try {
+    runValidator(validator, "badger")
+    throw RuntimeException("runValidator should have thrown")
+} catch (e: ValidationException.Forbidden) {
+    assert(e.input == "badger")
+    assert(e.word == "bad")
+}
+try {
+    runValidator(validator, "crash")
+    throw RuntimeException("runValidator should have thrown")
+} catch (e: ValidationException.Unexpected) {
+    assert(e.reason == "java.lang.IllegalStateException: can't validate crash")
+    assert(e.className == "java.lang.IllegalStateException")
+    assert(!e.cancelled)
+}
+try {
+    runValidator(validator, "cancel")
+    throw RuntimeException("runValidator should have thrown")
+} catch (e: ValidationException.Unexpected) {
+    assert(e.className == "java.util.concurrent.CancellationException")
+    assert(e.cancelled)
+}
+
+// Methods exported from several impl blocks are all on the one class
+val inventory = Inventory.withItem("apple", 3u)
+inventory.add("pear", 2u)
+assert(inventory.count("apple") == 3u)
+assert(inventory.remove("apple", 5u) == 3u)
+assert(inventory.items() == listOf("pear"))
+assert(Inventory().items().isEmpty())
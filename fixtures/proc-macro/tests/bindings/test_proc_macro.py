@@ -2,11 +2,19 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import asyncio
+import datetime
+import os
+import pathlib
+import urllib.parse
 from proc_macro import *
 
 one = make_one(123)
 assert one.inner == 123
 assert one_inner_by_ref(one) == 123
+assert one.doubled() == 246
+assert one.with_inner(5).inner == 5
+assert asyncio.run(one.doubled_async()) == 246
 
 two = Two(a="a")
 assert take_two(two) == "a"
@@ -24,6 +32,22 @@ robj = Renamed()
 assert(robj.func())
 assert(rename_test())
 
+record_with_renamed_fields = make_record_with_renamed_fields(timestamp_ms=1000, label="start")
+assert record_with_renamed_fields.timestamp_ms == 1000
+assert record_with_renamed_fields.label == "start"
+assert get_enum_with_renamed_fields_timestamp(EnumWithRenamedFields.EVENT(timestamp_ms=2000)) == 2000
+
+record_with_skipped_field = double_record_with_skipped_field(RecordWithSkippedField(value=21))
+assert record_with_skipped_field.value == 42
+assert not hasattr(record_with_skipped_field, "cache")
+point = translate_point(Point(x=1.0, y=2.0), 0.5, -1.0)
+assert point == Point(x=1.5, y=1.0)
+tuple_with_skipped_field = double_tuple_with_skipped_field(TupleWithSkippedField(v0=21))
+assert tuple_with_skipped_field.v0 == 42
+assert tuple_with_skipped_field.v2 == "unlabelled"
+assert not hasattr(tuple_with_skipped_field, "v1")
+assert double_enum_with_skipped_field(EnumWithSkippedField.VALUE(value=21)) == EnumWithSkippedField.VALUE(value=42)
+
 trait_impl = obj.get_trait(None)
 assert trait_impl.concat_strings("foo", "bar") == "foobar"
 assert obj.get_trait(trait_impl).concat_strings("foo", "bar") == "foobar"
@@ -33,6 +57,28 @@ trait_impl2 = obj.get_trait_with_foreign(None)
 assert trait_impl2.name() == "RustTraitImpl"
 assert obj.get_trait_with_foreign(trait_impl2).name() == "RustTraitImpl"
 
+# Implementations which don't override `greet` use the Rust default implementation
+class PyGreeter(Greeter):
+    def name(self):
+        return "Python"
+
+class PyCasualGreeter(Greeter):
+    def name(self):
+        return "Python"
+
+    def greet(self, greeting):
+        return "Hey"
+
+assert PyGreeter().greet("Hello") == "Hello, Python!"
+assert greet_with(PyGreeter(), "Hello") == "Hello, Python!"
+assert greet_with(PyCasualGreeter(), "Hello") == "Hey"
+
+# Trait constants are class attributes of the protocol, and of the classes implementing it
+assert Greeter.DEFAULT_GREETING == "Hello"
+assert PyGreeter.DEFAULT_GREETING == "Hello"
+assert Trait.USES_SEPARATOR is False
+assert OtherCallbackInterface.IDENTITY == 1
+
 assert enum_identity(MaybeBool.TRUE) == MaybeBool.TRUE
 
 # just make sure this works / doesn't crash
@@ -45,6 +91,11 @@ assert(make_hashmap(1, 2) == {1: 2})
 d = {1: 2}
 assert(return_hashmap(d) == d)
 
+# map keys other than strings
+assert(count_maybe_bools([MaybeBool.TRUE, MaybeBool.UNCERTAIN, MaybeBool.TRUE]) == {MaybeBool.TRUE: 2, MaybeBool.UNCERTAIN: 1})
+assert(return_bool_keyed_map({True: 1, False: 0}) == {True: 1, False: 0})
+assert(return_u32_keyed_map({4294967295: "max"}) == {4294967295: "max"})
+
 assert(join(["a", "b", "c"], ":") == "a:b:c")
 
 try:
@@ -74,11 +125,54 @@ assert(record_with_defaults.float_var == 4.2)
 assert(record_with_defaults.vec == [])
 assert(record_with_defaults.opt_vec == None)
 assert(record_with_defaults.opt_integer == 42)
+assert(record_with_defaults.magic == b"\xde\xad\xbe\xef")
+assert(record_with_defaults.empty_bytes == b"")
+assert(record_with_defaults.opt_bytes == b"ok")
+assert(bytes_with_default() == b"\x00uniffi")
 
 assert(double_with_default() == 42)
 
+assert enum_with_defaults_area(EnumWithDefaults.CIRCLE(2.0)) == 0.0
+assert EnumWithDefaults.CIRCLE(2.0).filled == False
+assert enum_with_defaults_area(EnumWithDefaults.CIRCLE(2.0, filled=True)) == 12.0
+assert enum_with_defaults_area(EnumWithDefaults.RECT(height=2.0)) == 2.0
+assert enum_with_defaults_area(EnumWithDefaults.RECT(width=3.0, height=2.0)) == 6.0
+
 obj_with_defaults = ObjectWithDefaults()
 assert(obj_with_defaults.add_to_num() == 42)
+assert(ObjectWithDefaults.with_offset(18).add_to_num() == 42)
+assert(describe_with_defaults("test") == "test 16 uncertain 0")
+assert(describe_with_defaults("test", maybe=MaybeBool.TRUE, counts={"a": 1}) == "test 16 true 1")
+
+# Every form of constructor, which throw `ConstructorError` for values over 100
+def assert_too_large(construct, value):
+    try:
+        construct()
+        raise RuntimeError("the constructor should have raised")
+    except ConstructorError.TooLarge as e:
+        assert(e.value == value)
+
+assert(PrimaryInfallible(7).describe() == "new 7")
+assert(PrimaryInfallible.with_defaults().describe() == "with_defaults 1 default")
+assert(PrimaryInfallible.with_defaults(label="given").describe() == "with_defaults 1 given")
+assert(PrimaryInfallible.fallible(8).describe() == "fallible 8")
+assert_too_large(lambda: PrimaryInfallible.fallible(101), 101)
+assert(PrimaryInfallible.fallible_with_defaults().describe() == "fallible_with_defaults 2 fallible")
+assert(PrimaryInfallible.fallible_with_defaults(value=9).describe() == "fallible_with_defaults 9 fallible")
+assert_too_large(lambda: PrimaryInfallible.fallible_with_defaults(102), 102)
+assert(asyncio.run(PrimaryInfallible.async_infallible(10)).describe() == "async_infallible 10")
+assert(asyncio.run(PrimaryInfallible.async_fallible(11)).describe() == "async_fallible 11")
+assert_too_large(lambda: asyncio.run(PrimaryInfallible.async_fallible(103)), 103)
+assert(asyncio.run(PrimaryInfallible.async_fallible_with_defaults()).describe() == "async_fallible_with_defaults 3")
+assert_too_large(lambda: asyncio.run(PrimaryInfallible.async_fallible_with_defaults(104)), 104)
+assert(PrimaryWithDefaults().describe() == "new 4 primary")
+assert(PrimaryWithDefaults(12, "given").describe() == "new 12 given")
+assert(PrimaryFallible(13).describe() == "new 13")
+assert_too_large(lambda: PrimaryFallible(105), 105)
+assert(PrimaryFallible.fallible_with_defaults().describe() == "fallible_with_defaults 6")
+assert_too_large(lambda: PrimaryFallible.fallible_with_defaults(106), 106)
+assert(PrimaryFallibleWithDefaults().describe() == "new 5")
+assert_too_large(lambda: PrimaryFallibleWithDefaults(107), 107)
 
 # Traits
 class PyTestCallbackInterface(TestCallbackInterface):
@@ -136,6 +230,7 @@ assert(ReprU8.THREE.value == 3)
 
 assert(get_mixed_enum(None) == MixedEnum.INT(1))
 assert(get_mixed_enum(MixedEnum.NONE()) == MixedEnum.NONE())
+assert(mixed_enum_or_default() == MixedEnum.NONE())
 assert(get_mixed_enum(MixedEnum.STRING("hello")) == MixedEnum.STRING("hello"))
 assert(MixedEnum.STRING("hello")[0] == "hello")
 assert(str(MixedEnum.STRING("hello")) == "MixedEnum.STRING('hello',)")
@@ -147,3 +242,398 @@ assert(MixedEnum.BOTH("hello", 1)[-1] == 1)
 assert(str(MixedEnum.BOTH("hello", 2)) == "MixedEnum.BOTH('hello', 2)")
 
 assert(get_mixed_enum(MixedEnum.ALL("string", 2)).is_all())
+
+# methods on enums
+assert(MaybeBool.TRUE.label() == "yes")
+assert(MaybeBool.TRUE.negated() == MaybeBool.FALSE)
+assert(MaybeBool.UNCERTAIN.negated() == MaybeBool.UNCERTAIN)
+assert(MixedEnum.NONE().describe() == "none")
+assert(MixedEnum.BOTH("hello", 1).describe() == "both hello 1")
+assert(MixedEnum.ALL("string", 2).describe() == "all string 2")
+
+# constants
+assert(MAX_PAYLOAD_SIZE == 65536)
+assert(DEFAULT_GREETING == "hello")
+assert(MIN_TEMPERATURE == -40.5)
+assert(VERBOSE is False)
+assert(UDL_OFFSET == -12)
+
+# 128-bit integers
+assert(roundtrip_u128(0) == 0)
+assert(roundtrip_u128(2**128 - 1) == 2**128 - 1)
+assert(u128_max() == 2**128 - 1)
+assert(roundtrip_i128(-2**127) == -2**127)
+assert(roundtrip_i128(2**127 - 1) == 2**127 - 1)
+for (func, value) in [(roundtrip_u128, -1), (roundtrip_u128, 2**128), (roundtrip_i128, 2**127)]:
+    try:
+        func(value)
+        raise RuntimeError(f"{func.__name__}({value}) should have raised")
+    except ValueError:
+        pass
+
+# chars
+assert(first_char("héllo") == "h")
+assert(first_char("😀!") == "😀")
+assert(first_char("") is None)
+assert(repeat_char("é", 3) == "ééé")
+assert(repeat_char("😀", 2) == "😀😀")
+for value in ["", "ab"]:
+    try:
+        repeat_char(value, 1)
+        raise RuntimeError(f"repeat_char({value!r}) should have raised")
+    except ValueError:
+        pass
+
+# non-zero integers, which are passed as plain integers
+assert(next_id(41) == 42)
+assert(optional_id(None) is None)
+assert(optional_id(7) == 7)
+assert(largest_ids([3, 9, 1, 5], 2) == [9, 5])
+assert(negate_non_zero(-3) == 3)
+for call in [lambda: next_id(0), lambda: optional_id(0), lambda: largest_ids([1, 0], 1), lambda: largest_ids([1], 0)]:
+    try:
+        call()
+        raise RuntimeError("zero should have been rejected")
+    except InternalError as e:
+        assert("value must be non-zero" in str(e))
+try:
+    next_id(0)
+    raise RuntimeError("next_id should have raised")
+except InternalError as e:
+    # The error names the argument
+    assert("'id'" in str(e))
+
+# chrono dates and times
+appointment = appointment_on(datetime.date(2024, 2, 29), 9)
+assert(appointment.day == datetime.date(2024, 2, 29))
+assert(appointment.starts_at == datetime.datetime(2024, 2, 29, 9, tzinfo=datetime.timezone.utc))
+assert(appointment_length_seconds(appointment) == 3600)
+appointment.ends_at = None
+assert(appointment_length_seconds(appointment) is None)
+assert(next_day(datetime.date(2023, 12, 31)) == datetime.date(2024, 1, 1))
+assert(next_day(datetime.date(1969, 12, 31)) == datetime.date(1970, 1, 1))
+assert(first_of_months(1)[1] == datetime.date(1, 2, 1))
+assert(date_parts(datetime.date(1582, 10, 4)) == [1582, 10, 4])
+assert(unix_seconds(datetime.datetime(1970, 1, 2, tzinfo=datetime.timezone.utc)) == 86400)
+try:
+    # datetimes would lose their time
+    next_day(datetime.datetime(2024, 1, 1))
+    raise RuntimeError("next_day should have raised")
+except TypeError:
+    pass
+
+# JSON values, which are the values `json.loads()` returns
+for value in [None, True, 0, -1.5, "h\u00e9llo \U0001F600 \"quoted\"\n\u0000", [], {}, {"a": [1, {"b": None}]}]:
+    assert(json_round_trip(value) == value)
+# 64-bit integers are exact, larger ones are floats in Rust
+assert(json_round_trip(2**64 - 1) == 2**64 - 1)
+assert(json_round_trip(-2**63) == -2**63)
+assert(json_round_trip(2**64) == float(2**64))
+assert(json_round_trip(1.7976931348623157e308) == 1.7976931348623157e308)
+# serde_json only parses up to 128 levels of nesting
+deep = []
+for _ in range(60):
+    deep = [1, {"deeper": deep}]
+assert(json_depth(deep) == 121)
+assert(json_round_trip(deep) == deep)
+assert(json_document() == {
+    "name": "h\u00e9llo \U0001F600",
+    "big": 2**64 - 1,
+    "small": -2**63,
+    "float": 1.5e300,
+    "nested": [[[{"empty": {}}]], None, True],
+})
+assert(event_payload_keys(Event(name="click", payload={"y": 2, "x": 1})) == ["x", "y"])
+assert(event_payload_keys(Event(name="none", payload=None)) == [])
+for value, error in [(float("nan"), ValueError), (object(), TypeError), ({1: [1j]}, TypeError)]:
+    try:
+        json_round_trip(value)
+        raise RuntimeError("json_round_trip should have raised")
+    except error:
+        pass
+
+# file system paths, which are `pathlib.Path`s and accept any path-like value
+assert(path_round_trip(pathlib.Path("/tmp/h\u00e9llo \U0001F600")) == pathlib.Path("/tmp/h\u00e9llo \U0001F600"))
+assert(path_round_trip("relative/file.txt") == pathlib.Path("relative/file.txt"))
+assert(isinstance(path_round_trip(""), pathlib.Path))
+assert(path_file_name(pathlib.Path("/tmp/notes.txt")) == "notes.txt")
+assert(path_file_name("/") is None)
+assert(path_join(pathlib.Path("/tmp"), ["a", "b.txt"]) == pathlib.Path("/tmp/a/b.txt"))
+assert(download_destination(Download(url="https://example.com/file.zip", destination=pathlib.Path("/downloads"))) == pathlib.Path("/downloads/file.zip"))
+assert(download_destination(Download(url="https://example.com/file.zip", destination=None)) == pathlib.Path("file.zip"))
+if os.name == "posix":
+    # Paths which aren't valid UTF-8 keep their bytes
+    assert(os.fsencode(latin1_path()) == b"/caf\xe9")
+    assert(path_round_trip(latin1_path()) == latin1_path())
+    assert(path_byte_len(latin1_path()) == 5)
+    assert(path_byte_len(b"/caf\xe9") == 5)
+try:
+    path_round_trip(1)
+    raise RuntimeError("path_round_trip should have raised")
+except TypeError:
+    pass
+
+# URLs, which are their serialized text by default
+assert(url_round_trip("https://example.com") == "https://example.com/")
+assert(url_round_trip(urllib.parse.urlparse("https://example.com/a?b=c")) == "https://example.com/a?b=c")
+assert(url_host("https://user@example.com:8080/") == "example.com")
+assert(url_host("mailto:someone@example.com") is None)
+assert(url_join("https://example.com/a/b", "../c") == "https://example.com/c")
+assert(link_hrefs([Link(title="home", href="https://example.com/"), Link(title="none", href=None)]) == ["https://example.com/"])
+try:
+    url_round_trip("example.com")
+    raise RuntimeError("url_round_trip should have raised")
+except InternalError as e:
+    # The error names the argument
+    assert("'url'" in str(e))
+try:
+    url_round_trip(1)
+    raise RuntimeError("url_round_trip should have raised")
+except TypeError:
+    pass
+
+# remote types
+assert(bump_minor_version(Version(major=1, minor=2, patch=3)) == Version(major=1, minor=3, patch=0))
+assert(compare_versions(Version(major=1, minor=2, patch=3), Version(major=1, minor=10, patch=0)) == Ordering.LESS)
+assert(compare_versions(Version(major=2, minor=0, patch=0), Version(major=2, minor=0, patch=0)) == Ordering.EQUAL)
+
+# fallible custom types
+assert(email_domain("me@example.com") == "example.com")
+try:
+    email_domain("nope")
+    raise RuntimeError("email_domain should have raised")
+except InternalError as e:
+    assert "`nope` isn't an email address" in str(e)
+try:
+    try_email_domain("")
+    raise RuntimeError("try_email_domain should have raised")
+except BasicError.InvalidInput:
+    pass
+
+# custom newtypes over bytes
+assert(digest_len(b"\x01\x02\x03") == 3)
+assert(xor_digest(b"\x00\xff", 0x0f) == b"\x0f\xf0")
+assert(join_tags(["a", "b"]) == "a,b")
+
+# builder methods returning objects
+client = ClientBuilder().with_timeout(5).with_retries(3).build()
+assert(client.timeout_secs() == 5)
+assert(client.retries() == 3)
+assert(ClientBuilder().build().timeout_secs() == 30)
+try:
+    ClientBuilder().with_timeout(0)
+    raise RuntimeError("with_timeout should have raised")
+except ClientBuildError.ZeroTimeout:
+    pass
+try:
+    ClientBuilder().with_retries(11).build()
+    raise RuntimeError("build should have raised")
+except ClientBuildError.TooManyRetries:
+    pass
+
+# records holding objects
+alice = Member("alice")
+bob = Member("bob")
+assert(alice.strong_count() == 2)
+team = Team(members=[alice, bob], captain=alice, by_role={"keeper": bob}, substitutes=[None, bob])
+for _ in range(10):
+    team = roundtrip_team(team)
+assert([m.name() for m in team.members] == ["alice", "bob"])
+assert(team.captain.name() == "alice")
+assert(team.by_role["keeper"].name() == "bob")
+assert(team.substitutes[0] is None and team.substitutes[1].name() == "bob")
+assert(team_member_names(Team(members=[bob])) == ["bob"])
+# `alice` and the captain and member of `team`
+assert(alice.strong_count() == 4)
+del team
+assert(alice.strong_count() == 2)
+assert(bob.strong_count() == 2)
+
+class PythonTeamSource(TeamSource):
+    def team(self):
+        return Team(members=[alice], by_role={"captain": alice})
+
+for _ in range(10):
+    team = team_from_source(PythonTeamSource())
+    assert(team.by_role["captain"].name() == "alice")
+del team
+assert(alice.strong_count() == 2)
+
+# trait objects in optionals, sequences and records
+class PythonLogger(Logger):
+    def log(self, message):
+        return f"python: {message}"
+
+rust_logger = make_rust_logger()
+assert(log_with(None, "hi") is None)
+assert(log_with(rust_logger, "hi") == "rust: hi")
+assert(log_with(PythonLogger(), "hi") == "python: hi")
+assert(log_with_all([rust_logger, PythonLogger()], "hi") == ["rust: hi", "python: hi"])
+assert(log_with_all([], "hi") == [])
+assert(log_with_config(LoggerConfig(name="app"), "hi") is None)
+assert(log_with_config(LoggerConfig(name="app", logger=PythonLogger()), "hi") == "python: app: hi")
+assert(roundtrip_optional_logger(None) is None)
+assert(roundtrip_optional_logger(PythonLogger()).log("hi") == "python: hi")
+assert([l.log("hi") for l in roundtrip_loggers([PythonLogger(), rust_logger])] == ["python: hi", "rust: hi"])
+assert(roundtrip_logger_config(LoggerConfig(name="app")).logger is None)
+assert(roundtrip_logger_config(LoggerConfig(name="app", logger=rust_logger)).logger.log("hi") == "rust: hi")
+
+# trait objects returned from factory functions and methods
+store = make_store(StoreKind.MEMORY)
+assert(store.kind() == StoreKind.MEMORY)
+assert(store.set("name", "memory"))
+assert(store.get("name") == "memory")
+assert(store_get_or(store, "name", "default") == "memory")
+assert(store_get_or(store, "missing", "default") == "default")
+assert(make_store_named("read-only").kind() == StoreKind.READ_ONLY)
+assert(not make_store_named("read-only").set("name", "other"))
+try:
+    make_store_named("disk")
+    raise Exception("make_store_named should have thrown")
+except StoreError.UnknownStore as e:
+    assert(e.name == "disk")
+
+registry = StoreRegistry()
+registry.register("main", store)
+assert(registry.lookup("main").get("name") == "memory")
+registry.lookup("main").set("name", "updated")
+assert(store.get("name") == "updated")
+try:
+    registry.lookup("other")
+    raise Exception("lookup should have thrown")
+except StoreError.UnknownStore:
+    pass
+
+# methods which consume the object
+import threading
+live = live_transactions()
+tx = Transaction()
+tx.write("a")
+tx.write("b")
+assert(live_transactions() == live + 1)
+assert(tx.commit() == ["a", "b"])
+# The Rust object is dropped by the consuming call, not when `tx` is collected.
+assert(live_transactions() == live)
+for call in [tx.commit, tx.rollback, tx.pending, lambda: tx.write("c")]:
+    try:
+        call()
+        raise Exception("the transaction should have been consumed")
+    except ValueError:
+        pass
+del tx
+assert(live_transactions() == live)
+
+# Calls racing with the consuming one either succeed or see the consumed object.
+tx = Transaction()
+def write_until_consumed():
+    try:
+        while True:
+            tx.write("x")
+    except ValueError:
+        pass
+threads = [threading.Thread(target=write_until_consumed) for _ in range(4)]
+for t in threads:
+    t.start()
+tx.rollback()
+for t in threads:
+    t.join()
+assert(live_transactions() == live)
+
+# Each object has its own lock, so consuming one doesn't block calls on another.
+assert Transaction()._uniffi_pointer_lock is not Transaction()._uniffi_pointer_lock
+
+# static methods
+assert Color.from_hex("#ff8000").hex() == "#ff8000"
+assert Color.from_hex("ff8000") is None
+assert [c.hex() for c in Color.primaries()] == ["#ff0000", "#00ff00", "#0000ff"]
+assert Color.channel_count() == 3
+assert Color.channel_count(alpha=True) == 4
+assert asyncio.run(Color.named("white")).hex() == "#ffffff"
+assert asyncio.run(Color.named("mauve")) is None
+# They can be called on an instance too, like any Python staticmethod.
+assert Color(1, 2, 3).channel_count() == 3
+
+# anyhow and boxed errors, which are thrown as `CatchAllError`
+assert parse_port("8080") == 8080
+def catch_all_message(f, *args):
+    try:
+        f(*args)
+    except CatchAllError.Failed as e:
+        return str(e)
+    raise Exception(f"{f.__name__} should have thrown")
+assert catch_all_message(parse_port, "0") == "port 0 is reserved"
+assert catch_all_message(parse_port, "http").startswith("invalid port `http`: ")
+assert parse_ratio("1/4") == 0.25
+assert catch_all_message(parse_ratio, "1") == "missing `/`"
+assert parse_flag("true")
+assert catch_all_message(parse_flag, "yes").startswith("provided string was not")
+assert asyncio.run(parse_port_async("443")) == 443
+try:
+    asyncio.run(parse_port_async("0"))
+    raise Exception("parse_port_async should have thrown")
+except CatchAllError.Failed as e:
+    assert str(e) == "port 0 is reserved"
+endpoint = Endpoint("localhost:8080")
+assert endpoint.with_port("443").address() == "localhost:443"
+assert catch_all_message(Endpoint, "localhost") == "`localhost` has no port"
+assert catch_all_message(endpoint.with_port, "0") == "can't change the port: port 0 is reserved"
+
+# Getters of public fields, which are read-only properties
+book = Book("Dune", ["Frank Herbert"], 1965)
+assert book.title == "Dune"
+assert book.authors == ["Frank Herbert"]
+assert book.year == 1965
+assert Book("Anonymous", [], None).year is None
+assert book.copies() == 1
+assert Book.title.__doc__.strip() == "The title on the cover"
+shelf = Shelf("sci-fi", [book])
+assert shelf.label == "sci-fi"
+assert [b.title for b in shelf.books] == ["Dune"]
+assert not hasattr(shelf, "capacity")
+assert shelf.has_space()
+try:
+    book.title = "Children of Dune"
+    raise Exception("properties should be read-only")
+except AttributeError:
+    pass
+
+# Errors thrown by foreign trait implementations
+class PyValidator(Validator):
+    def validate(self, input):
+        if len(input) < 3:
+            raise ValidationError.TooShort(input=input, min_length=3)
+        if "bad" in input:
+            raise ValidationError.Forbidden(input=input, word="bad")
+        if input == "crash":
+            raise ValueError("can't validate crash")
+        return input.upper()
+
+validator = PyValidator()
+assert run_validator(validator, "good") == "GOOD"
+try:
+    run_validator(validator, "ok")
+    raise Exception("run_validator should have thrown")
+except ValidationError.TooShort as e:
+    assert e.input == "ok"
+    assert e.min_length == 3
+try:
+    run_validator(validator, "badger")
+    raise Exception("run_validator should have thrown")
+except ValidationError.Forbidden as e:
+    assert e.input == "badger"
+    assert e.word == "bad"
+try:
+    run_validator(validator, "crash")
+    raise Exception("run_validator should have thrown")
+except ValidationError.Unexpected as e:
+    assert e.reason == repr(ValueError("can't validate crash"))
+    assert e.class_name == "builtins.ValueError"
+    assert not e.cancelled
+
+# Methods exported from several impl blocks are all on the one class
+inventory = Inventory.with_item("apple", 3)
+inventory.add("pear", 2)
+assert inventory.count("apple") == 3
+assert inventory.remove("apple", 5) == 3
+assert inventory.items() == ["pear"]
+assert Inventory().items() == []
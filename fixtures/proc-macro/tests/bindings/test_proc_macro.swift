@@ -8,6 +8,8 @@ import proc_macro
 let one = makeOne(inner: 123)
 assert(one.inner == 123)
 assert(oneInnerByRef(one: one) == 123)
+assert(one.doubled() == 246)
+assert(one.withInner(inner: 5).inner == 5)
 
 let two = Two(a: "a")
 assert(takeTwo(two: two) == "a")
@@ -30,8 +32,39 @@ let traitImpl2 = obj.getTraitWithForeign(inc: nil)
 assert(traitImpl2.name() == "RustTraitImpl")
 assert(obj.getTraitWithForeign(inc: traitImpl2).name() == "RustTraitImpl")
 
+// Implementations which don't implement `greet` use the Rust default implementation
+class SwiftGreeter: Greeter {
+    func name() -> String {
+        return "Swift"
+    }
+}
+
+class SwiftCasualGreeter: Greeter {
+    func name() -> String {
+        return "Swift"
+    }
+
+    func greet(greeting: String) -> String {
+        return "Hey"
+    }
+}
+
+assert(SwiftGreeter().greet(greeting: "Hello") == "Hello, Swift!")
+assert(greetWith(greeter: SwiftGreeter(), greeting: "Hello") == "Hello, Swift!")
+assert(greetWith(greeter: SwiftCasualGreeter(), greeting: "Hello") == "Hey")
+
+// Trait constants are static members of the types conforming to the protocol
+assert(SwiftGreeter.defaultGreeting == "Hello")
+assert(Trait.usesSeparator == false)
+assert(SwiftTestCallbackInterface2.identity == 1)
+
 assert(enumIdentity(value: .true) == .true)
 
+// map keys other than strings
+assert(countMaybeBools(values: [.true, .uncertain, .true]) == [.true: 2, .uncertain: 1])
+assert(returnBoolKeyedMap(h: [true: 1, false: 0]) == [true: 1, false: 0])
+assert(returnU32KeyedMap(h: [UInt32.max: "max"]) == [UInt32.max: "max"])
+
 // just make sure this works / doesn't crash
 let three = Three(obj: obj)
 
@@ -55,6 +88,20 @@ do {
 
 struct SomeOtherError: Error { }
 
+// Renamed fields and arguments
+
+let recordWithRenamedFields = makeRecordWithRenamedFields(timestampMs: 1000, label: "start")
+assert(recordWithRenamedFields.timestampMs == 1000)
+assert(recordWithRenamedFields.label == "start")
+assert(getEnumWithRenamedFieldsTimestamp(value: .event(timestampMs: 2000)) == 2000)
+
+// Skipped fields
+
+assert(doubleRecordWithSkippedField(record: RecordWithSkippedField(value: 21)) == RecordWithSkippedField(value: 42))
+assert(translatePoint(point: Point(x: 1.0, y: 2.0), dx: 0.5, dy: -1.0) == Point(x: 1.5, y: 1.0))
+assert(doubleTupleWithSkippedField(tuple: TupleWithSkippedField(v0: 21)) == TupleWithSkippedField(v0: 42, v2: "unlabelled"))
+assert(doubleEnumWithSkippedField(value: .value(value: 21)) == .value(value: 42))
+
 // Defaults
 
 let recordWithDefaults = RecordWithDefaults(noDefaultString: "Test")
@@ -65,11 +112,67 @@ assert(recordWithDefaults.floatVar == 4.2)
 assert(recordWithDefaults.vec == [])
 assert(recordWithDefaults.optVec == nil)
 assert(recordWithDefaults.optInteger == 42)
+assert(recordWithDefaults.magic == Data([0xDE, 0xAD, 0xBE, 0xEF]))
+assert(recordWithDefaults.emptyBytes.isEmpty)
+assert(recordWithDefaults.optBytes == Data("ok".utf8))
+assert(bytesWithDefault() == Data([0]) + Data("uniffi".utf8))
 
 assert(doubleWithDefault() == 42)
 
+assert(enumWithDefaultsArea(shape: .circle(radius: 2.0)) == 0.0)
+assert(enumWithDefaultsArea(shape: .circle(radius: 2.0, filled: true)) == 12.0)
+assert(enumWithDefaultsArea(shape: .rect(height: 2.0)) == 2.0)
+
 let objWithDefaults = ObjectWithDefaults()
 assert(objWithDefaults.addToNum() == 42)
+assert(ObjectWithDefaults.withOffset(num: 18).addToNum() == 42)
+
+// Every form of constructor, which throw `ConstructorError` for values over 100
+func assertTooLarge(_ value: UInt32, _ construct: () throws -> Any) {
+    do {
+        _ = try construct()
+        fatalError("the constructor should have thrown")
+    } catch ConstructorError.TooLarge(let thrown) {
+        assert(thrown == value)
+    } catch {
+        fatalError("unexpected error \(error)")
+    }
+}
+
+assert(PrimaryInfallible(value: 7).describe() == "new 7")
+assert(PrimaryInfallible.withDefaults().describe() == "with_defaults 1 default")
+assert(PrimaryInfallible.withDefaults(label: "given").describe() == "with_defaults 1 given")
+assert(try! PrimaryInfallible.fallible(value: 8).describe() == "fallible 8")
+assertTooLarge(101) { try PrimaryInfallible.fallible(value: 101) }
+assert(try! PrimaryInfallible.fallibleWithDefaults().describe() == "fallible_with_defaults 2 fallible")
+assert(try! PrimaryInfallible.fallibleWithDefaults(value: 9).describe() == "fallible_with_defaults 9 fallible")
+assertTooLarge(102) { try PrimaryInfallible.fallibleWithDefaults(value: 102) }
+assert(PrimaryWithDefaults().describe() == "new 4 primary")
+assert(PrimaryWithDefaults(value: 12, label: "given").describe() == "new 12 given")
+assert(try! PrimaryFallible(value: 13).describe() == "new 13")
+assertTooLarge(105) { try PrimaryFallible(value: 105) }
+assert(try! PrimaryFallible.fallibleWithDefaults().describe() == "fallible_with_defaults 6")
+assertTooLarge(106) { try PrimaryFallible.fallibleWithDefaults(value: 106) }
+assert(try! PrimaryFallibleWithDefaults().describe() == "new 5")
+assertTooLarge(107) { try PrimaryFallibleWithDefaults(value: 107) }
+
+let asyncConstructorsDone = DispatchGroup()
+asyncConstructorsDone.enter()
+Task {
+    assert(await PrimaryInfallible.asyncInfallible(value: 10).describe() == "async_infallible 10")
+    assert(try! await PrimaryInfallible.asyncFallible(value: 11).describe() == "async_fallible 11")
+    assert(try! await PrimaryInfallible.asyncFallibleWithDefaults().describe() == "async_fallible_with_defaults 3")
+    do {
+        _ = try await PrimaryInfallible.asyncFallibleWithDefaults(value: 104)
+        fatalError("the constructor should have thrown")
+    } catch ConstructorError.TooLarge(let thrown) {
+        assert(thrown == 104)
+    }
+    asyncConstructorsDone.leave()
+}
+asyncConstructorsDone.wait()
+assert(describeWithDefaults(name: "test") == "test 16 uncertain 0")
+assert(describeWithDefaults(name: "test", maybe: .true, counts: ["a": 1]) == "test 16 true 1")
 
 // Traits
 
@@ -120,7 +223,16 @@ callCallbackInterface(cb: SwiftTestCallbackInterface())
 
 assert(getMixedEnum(v: nil) == .int(1))
 assert(getMixedEnum(v: MixedEnum.none) == .none)
+assert(mixedEnumOrDefault() == .none)
 assert(getMixedEnum(v: MixedEnum.string("hello")) == .string("hello"))
+
+// methods on enums
+assert(MaybeBool.true.label() == "yes")
+assert(MaybeBool.true.negated() == .false)
+assert(MaybeBool.uncertain.negated() == .uncertain)
+assert(MixedEnum.none.describe() == "none")
+assert(MixedEnum.both("hello", 1).describe() == "both hello 1")
+assert(MixedEnum.all(s: "string", i: 2).describe() == "all string 2")
 switch MixedEnum.string("hello") {
     case let .string(s):
         assert(s == "hello")
@@ -143,3 +255,298 @@ switch MixedEnum.all(s: "string", i: 2) {
     default:
         assert(false)
 }
+
+// constants
+assert(ProcMacro.maxPayloadSize == 65536)
+assert(ProcMacro.defaultGreeting == "hello")
+assert(ProcMacro.minTemperature == -40.5)
+assert(!ProcMacro.verbose)
+assert(ProcMacro.udlOffset == -12)
+
+// 128-bit integers
+assert(roundtripU128(v: RustUInt128(high: UInt64.max, low: UInt64.max)) == RustUInt128(high: UInt64.max, low: UInt64.max))
+assert(u128Max() == RustUInt128(high: UInt64.max, low: UInt64.max))
+assert(roundtripU128(v: RustUInt128(42)) == RustUInt128(42))
+assert(roundtripI128(v: RustInt128(-5)) == RustInt128(high: -1, low: UInt64.max - 4))
+assert(roundtripI128(v: RustInt128(high: Int64.min, low: 0)) == RustInt128(high: Int64.min, low: 0))
+
+// chars
+assert(firstChar(s: "héllo") == "h")
+assert(firstChar(s: "😀!") == "😀")
+assert(firstChar(s: "") == nil)
+assert(repeatChar(c: "😀", count: 2) == "😀😀")
+
+// non-zero integers, which are passed as plain integers.  Passing a zero is an internal error,
+// which is a precondition failure for functions that don't throw.
+assert(nextId(id: 41) == 42)
+assert(optionalId(id: nil) == nil)
+assert(optionalId(id: 7) == 7)
+assert(largestIds(ids: [3, 9, 1, 5], count: 2) == [9, 5])
+assert(negateNonZero(v: -3) == 3)
+
+// chrono dates and times.  Dates are converted with the proleptic Gregorian calendar, like Rust.
+let leapDay = DateComponents(year: 2024, month: 2, day: 29)
+var appointment = appointmentOn(day: leapDay, hour: 9)
+assert(appointment.day == leapDay)
+assert(appointment.startsAt == Date(timeIntervalSince1970: 1709197200))
+assert(appointmentLengthSeconds(appointment: appointment) == 3600)
+appointment.endsAt = nil
+assert(appointmentLengthSeconds(appointment: appointment) == nil)
+assert(nextDay(day: DateComponents(year: 2023, month: 12, day: 31)) == DateComponents(year: 2024, month: 1, day: 1))
+assert(nextDay(day: DateComponents(year: 1969, month: 12, day: 31)) == DateComponents(year: 1970, month: 1, day: 1))
+assert(nextDay(day: DateComponents(year: 1582, month: 10, day: 4)) == DateComponents(year: 1582, month: 10, day: 5))
+assert(firstOfMonths(year: -1)[1] == DateComponents(year: -1, month: 2, day: 1))
+assert(dateParts(day: DateComponents(year: 1582, month: 10, day: 4)) == [1582, 10, 4])
+assert(unixSeconds(time: Date(timeIntervalSince1970: 86400)) == 86400)
+
+// JSON values, which are their UTF-8 JSON text.  Rust writes it compactly, with sorted keys.
+func json(_ text: String) -> Data {
+    return text.data(using: .utf8)!
+}
+assert(jsonRoundTrip(value: json("{ \"b\": [1, 2.5], \"a\": null }")) == json("{\"a\":null,\"b\":[1,2.5]}"))
+assert(jsonRoundTrip(value: json("\"h\u{e9}llo \u{1F600}\"")) == json("\"h\u{e9}llo \u{1F600}\""))
+assert(jsonRoundTrip(value: json("18446744073709551615")) == json("18446744073709551615"))
+assert(jsonDepth(value: json("[[[{\"a\": []}]]]")) == 5)
+assert(eventPayloadKeys(event: Event(name: "click", payload: json("{\"y\": 2, \"x\": 1}"))) == ["x", "y"])
+let document = try! JSONSerialization.jsonObject(with: jsonDocument()) as! [String: Any]
+assert(document["name"] as! String == "h\u{e9}llo \u{1F600}")
+assert((document["nested"] as! [Any]).count == 3)
+
+// file system paths, which are file URLs by default
+assert(pathRoundTrip(path: URL(fileURLWithPath: "/tmp/h\u{e9}llo \u{1F600}")).path == "/tmp/h\u{e9}llo \u{1F600}")
+assert(pathFileName(path: URL(fileURLWithPath: "/tmp/notes.txt")) == "notes.txt")
+assert(pathJoin(base: URL(fileURLWithPath: "/tmp"), parts: ["a", "b.txt"]).path == "/tmp/a/b.txt")
+assert(downloadDestination(download: Download(url: "https://example.com/file.zip", destination: URL(fileURLWithPath: "/downloads"))).path == "/downloads/file.zip")
+assert(pathByteLen(path: URL(fileURLWithPath: "/caf\u{e9}")) == 6)
+// Bytes which aren't valid UTF-8 are replaced
+assert(latin1Path().path == "/caf\u{FFFD}")
+
+// URLs, which are Foundation `URL`s with this fixture's `url_type`
+assert(urlRoundTrip(url: URL(string: "https://example.com")!) == URL(string: "https://example.com/")!)
+assert(urlHost(url: URL(string: "https://user@example.com:8080/")!) == "example.com")
+assert(urlJoin(base: URL(string: "https://example.com/a/b")!, path: "../c") == URL(string: "https://example.com/c")!)
+assert(linkHrefs(links: [Link(title: "home", href: URL(string: "https://example.com/")!), Link(title: "none", href: nil)]) == [URL(string: "https://example.com/")!])
+
+// remote types
+assert(bumpMinorVersion(version: Version(major: 1, minor: 2, patch: 3)) == Version(major: 1, minor: 3, patch: 0))
+assert(compareVersions(a: Version(major: 1, minor: 2, patch: 3), b: Version(major: 1, minor: 10, patch: 0)) == .less)
+assert(compareVersions(a: Version(major: 2, minor: 0, patch: 0), b: Version(major: 2, minor: 0, patch: 0)) == .equal)
+
+// fallible custom types
+assert(emailDomain(email: "me@example.com") == "example.com")
+do {
+    _ = try tryEmailDomain(email: "")
+    fatalError("tryEmailDomain should have thrown")
+} catch BasicError.InvalidInput {
+}
+
+// custom newtypes over bytes
+assert(digestLen(digest: Data([1, 2, 3])) == 3)
+assert(xorDigest(digest: Data([0x00, 0xff]), mask: 0x0f) == Data([0x0f, 0xf0]))
+assert(joinTags(tags: ["a", "b"]) == "a,b")
+
+// builder methods returning objects
+let client = try! ClientBuilder().withTimeout(secs: 5).withRetries(retries: 3).build()
+assert(client.timeoutSecs() == 5)
+assert(client.retries() == 3)
+assert(try! ClientBuilder().build().timeoutSecs() == 30)
+do {
+    _ = try ClientBuilder().withTimeout(secs: 0)
+    fatalError("withTimeout should have thrown")
+} catch ClientBuildError.ZeroTimeout {
+}
+do {
+    _ = try ClientBuilder().withRetries(retries: 11).build()
+    fatalError("build should have thrown")
+} catch ClientBuildError.TooManyRetries {
+}
+
+// records holding objects
+let alice = Member(name: "alice")
+let bob = Member(name: "bob")
+assert(alice.strongCount() == 2)
+var team = Team(members: [alice, bob], captain: alice, byRole: ["keeper": bob], substitutes: [nil, bob])
+for _ in 0..<10 {
+    team = roundtripTeam(team: team)
+}
+assert(team.members.map { $0.name() } == ["alice", "bob"])
+assert(team.captain!.name() == "alice")
+assert(team.byRole["keeper"]!.name() == "bob")
+assert(team.substitutes[0] == nil && team.substitutes[1]!.name() == "bob")
+assert(teamMemberNames(team: Team(members: [bob])) == ["bob"])
+// `alice` and the captain and member of `team`
+assert(alice.strongCount() == 4)
+team = Team(members: [])
+assert(alice.strongCount() == 2)
+assert(bob.strongCount() == 2)
+
+class SwiftTeamSource: TeamSource {
+    func team() -> Team {
+        return Team(members: [alice], byRole: ["captain": alice])
+    }
+}
+for _ in 0..<10 {
+    assert(teamFromSource(source: SwiftTeamSource()).byRole["captain"]!.name() == "alice")
+}
+assert(alice.strongCount() == 2)
+
+// trait objects in optionals, sequences and records
+class SwiftLogger: Logger {
+    func log(message: String) -> String {
+        return "swift: \(message)"
+    }
+}
+
+let rustLogger = makeRustLogger()
+assert(logWith(logger: nil, message: "hi") == nil)
+assert(logWith(logger: rustLogger, message: "hi") == "rust: hi")
+assert(logWith(logger: SwiftLogger(), message: "hi") == "swift: hi")
+assert(logWithAll(loggers: [rustLogger, SwiftLogger()], message: "hi") == ["rust: hi", "swift: hi"])
+assert(logWithAll(loggers: [], message: "hi") == [])
+assert(logWithConfig(config: LoggerConfig(name: "app"), message: "hi") == nil)
+assert(logWithConfig(config: LoggerConfig(name: "app", logger: SwiftLogger()), message: "hi") == "swift: app: hi")
+assert(roundtripOptionalLogger(logger: nil) == nil)
+assert(roundtripOptionalLogger(logger: SwiftLogger())!.log(message: "hi") == "swift: hi")
+assert(roundtripLoggers(loggers: [SwiftLogger(), rustLogger]).map { $0.log(message: "hi") } == ["swift: hi", "rust: hi"])
+assert(roundtripLoggerConfig(config: LoggerConfig(name: "app")).logger == nil)
+assert(roundtripLoggerConfig(config: LoggerConfig(name: "app", logger: rustLogger)).logger!.log(message: "hi") == "rust: hi")
+
+// trait objects returned from factory functions and methods
+let store = makeStore(kind: .memory)
+assert(store.kind() == .memory)
+assert(store.set(key: "name", value: "memory"))
+assert(store.get(key: "name") == "memory")
+assert(storeGetOr(store: store, key: "name", default: "default") == "memory")
+assert(storeGetOr(store: store, key: "missing", default: "default") == "default")
+assert(try! makeStoreNamed(name: "read-only").kind() == .readOnly)
+assert(!(try! makeStoreNamed(name: "read-only").set(key: "name", value: "other")))
+do {
+    _ = try makeStoreNamed(name: "disk")
+    fatalError("makeStoreNamed should have thrown")
+} catch let StoreError.UnknownStore(name) {
+    assert(name == "disk")
+}
+
+let registry = StoreRegistry()
+registry.register(name: "main", store: store)
+assert(try! registry.lookup(name: "main").get(key: "name") == "memory")
+_ = try! registry.lookup(name: "main").set(key: "name", value: "updated")
+assert(store.get(key: "name") == "updated")
+do {
+    _ = try registry.lookup(name: "other")
+    fatalError("lookup should have thrown")
+} catch StoreError.UnknownStore {
+}
+
+// methods which consume the object
+let live = liveTransactions()
+let tx = Transaction()
+tx.write(value: "a")
+tx.write(value: "b")
+assert(liveTransactions() == live + 1)
+assert(tx.commit() == ["a", "b"])
+// The Rust object is dropped by the consuming call, calling any method on `tx` after this is a
+// precondition failure.
+assert(liveTransactions() == live)
+
+// Concurrent calls on an object with consuming methods share a lock on its pointer.
+let racingTx = Transaction()
+DispatchQueue.concurrentPerform(iterations: 4) { _ in
+    racingTx.write(value: "x")
+}
+assert(racingTx.rollback() == 4)
+assert(liveTransactions() == live)
+
+// static methods
+assert(Color.fromHex(hex: "#ff8000")!.hex() == "#ff8000")
+assert(Color.fromHex(hex: "ff8000") == nil)
+assert(Color.primaries().map { $0.hex() } == ["#ff0000", "#00ff00", "#0000ff"])
+assert(Color.channelCount() == 3)
+assert(Color.channelCount(alpha: true) == 4)
+
+// anyhow and boxed errors, which are thrown as `CatchAllError`
+assert(try! parsePort(value: "8080") == 8080)
+do {
+    _ = try parsePort(value: "0")
+    fatalError("parsePort should have thrown")
+} catch CatchAllError.Failed(let message) {
+    assert(message == "port 0 is reserved")
+}
+assert(try! parseRatio(value: "1/4") == 0.25)
+do {
+    _ = try parseRatio(value: "1")
+    fatalError("parseRatio should have thrown")
+} catch CatchAllError.Failed(let message) {
+    assert(message == "missing `/`")
+}
+assert(try! parseFlag(value: "true"))
+let endpoint = try! Endpoint(address: "localhost:8080")
+do {
+    _ = try endpoint.withPort(port: "0")
+    fatalError("withPort should have thrown")
+} catch CatchAllError.Failed(let message) {
+    assert(message == "can't change the port: port 0 is reserved")
+}
+
+// Getters of public fields, which are read-only properties
+let book = Book(title: "Dune", authors: ["Frank Herbert"], year: 1965)
+assert(book.title == "Dune")
+assert(book.authors == ["Frank Herbert"])
+assert(book.year == 1965)
+assert(Book(title: "Anonymous", authors: [], year: nil).year == nil)
+assert(book.copies() == 1)
+let shelf = Shelf(label: "sci-fi", books: [book])
+assert(shelf.label == "sci-fi")
+assert(shelf.books.map { $0.title } == ["Dune"])
+assert(shelf.hasSpace())
+
+// Errors thrown by foreign trait implementations
+struct ValidatorCrash: Error {}
+
+class SwiftValidator: Validator {
+    func validate(input: String) throws -> String {
+        if input.count < 3 {
+            throw ValidationError.TooShort(input: input, minLength: 3)
+        }
+        if input.contains("bad") {
+            throw ValidationError.Forbidden(input: input, word: "bad")
+        }
+        if input == "crash" {
+            throw ValidatorCrash()
+        }
+        return input.uppercased()
+    }
+}
+
+let validator = SwiftValidator()
+assert(try! runValidator(validator: validator, input: "good") == "GOOD")
+do {
+    _ = try runValidator(validator: validator, input: "ok")
+    fatalError("runValidator should have thrown")
+} catch ValidationError.TooShort(let input, let minLength) {
+    assert(input == "ok")
+    assert(minLength == 3)
+}
+do {
+    _ = try runValidator(validator: validator, input: "badger")
+    fatalError("runValidator should have thrown")
+} catch ValidationError.Forbidden(let input, let word) {
+    assert(input == "badger")
+    assert(word == "bad")
+}
+do {
+    _ = try runValidator(validator: validator, input: "crash")
+    fatalError("runValidator should have thrown")
+} catch ValidationError.Unexpected(let reason, let className, let cancelled) {
+    assert(reason == "ValidatorCrash()")
+    assert(className!.hasSuffix("ValidatorCrash"))
+    assert(!cancelled)
+}
+
+// Methods exported from several impl blocks are all on the one class
+let inventory = Inventory.withItem(item: "apple", count: 3)
+inventory.add(item: "pear", count: 2)
+assert(inventory.count(item: "apple") == 3)
+assert(inventory.remove(item: "apple", count: 5) == 3)
+assert(inventory.items() == ["pear"])
+assert(Inventory().items().isEmpty)
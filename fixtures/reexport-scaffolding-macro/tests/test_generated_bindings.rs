@@ -0,0 +1,39 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi_bindgen::{cargo_metadata::CrateConfigSupplier, library_mode::find_components};
+use uniffi_testing::UniFFITestHelper;
+
+// The wrapper's cdylib should contain the complete interface of both components.
+#[test]
+fn test_wrapped_components() {
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    // The components use UDL files, which are found using the cargo metadata.
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .exec()
+        .expect("error running cargo metadata");
+    let components = find_components(
+        &test_helper.cdylib_path().unwrap(),
+        &CrateConfigSupplier::from(metadata),
+    )
+    .unwrap();
+    // The components are listed in the order their metadata is found in the library.
+    let mut crate_names: Vec<_> = components.iter().map(|c| c.ci.crate_name()).collect();
+    crate_names.sort();
+    assert_eq!(crate_names, ["ext_types_custom", "uniffi_one"]);
+
+    let component = |crate_name| {
+        &components
+            .iter()
+            .find(|c| c.ci.crate_name() == crate_name)
+            .unwrap()
+            .ci
+    };
+    assert!(component("ext_types_custom")
+        .get_function_definition("get_guid")
+        .is_some());
+    let uniffi_one = component("uniffi_one");
+    assert_eq!(uniffi_one.namespace(), "uniffi_one_ns");
+    assert!(uniffi_one.get_record_definition("UniffiOneType").is_some());
+}
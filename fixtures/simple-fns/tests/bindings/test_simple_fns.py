@@ -3,8 +3,12 @@
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 from uniffi_simple_fns import *
+import uniffi_simple_fns
 import typing
 
+# The package version defaults to the version in Cargo.toml
+assert uniffi_simple_fns.__version__ == "0.22.0"
+
 assert get_string() == "String created by Rust"
 assert get_int() == 1289
 assert string_identity("String created by Python") == "String created by Python"
@@ -0,0 +1,69 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// Library mode on a `.wasm` module built by rustc, rather than the hand-assembled modules in the
+// `macro_metadata` unit tests.  Run with:
+//
+//     cargo test -p uniffi-fixture-simple-fns --features wasm-fixture-tests
+#![cfg(feature = "wasm-fixture-tests")]
+
+use camino::Utf8PathBuf;
+use std::process::Command;
+use uniffi_bindgen::{
+    bindings::PythonBindingGenerator, cargo_metadata::CrateConfigSupplier,
+    library_mode::generate_bindings, macro_metadata::extract_from_library,
+};
+use uniffi_testing::UniFFITestHelper;
+
+const WASM_TARGET: &str = "wasm32-wasi";
+
+// Build this fixture's cdylib for `WASM_TARGET`, returning the path of the `.wasm`
+fn build_wasm() -> Utf8PathBuf {
+    let target_dir = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("wasm");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--lib", "--target", WASM_TARGET, "--target-dir"])
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(
+        status.success(),
+        "building for {} failed, is the target installed?",
+        WASM_TARGET
+    );
+    target_dir.join(format!("{}/debug/uniffi_simple_fns.wasm", WASM_TARGET))
+}
+
+#[test]
+fn test_library_mode_on_wasm() {
+    let wasm_path = build_wasm();
+    let test_helper = UniFFITestHelper::new(env!("CARGO_PKG_NAME")).unwrap();
+    let cdylib_path = test_helper.cdylib_path().unwrap();
+
+    // The data segments of the wasm module have the same metadata as the native library
+    let mut expected = extract_from_library(&cdylib_path).unwrap();
+    expected.sort();
+    assert!(!expected.is_empty());
+    let mut items = extract_from_library(&wasm_path).unwrap();
+    items.sort();
+    assert_eq!(items, expected);
+
+    let out_dir = test_helper
+        .create_out_dir(env!("CARGO_TARGET_TMPDIR"), "test-library-mode-on-wasm")
+        .unwrap();
+    let components = generate_bindings(
+        &wasm_path,
+        None,
+        &PythonBindingGenerator,
+        // The UDL file is found using the cargo metadata
+        &CrateConfigSupplier::from(test_helper.cargo_metadata()),
+        None,
+        &out_dir,
+        false,
+    )
+    .unwrap();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].ci.crate_name(), "uniffi_simple_fns");
+    assert!(out_dir.join("uniffi_simple_fns.py").is_file());
+}
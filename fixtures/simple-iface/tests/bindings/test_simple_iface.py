@@ -2,6 +2,9 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import uniffi_simple_iface
+assert uniffi_simple_iface.__doc__.strip() == "A simple interface, defined with proc-macros only."
+
 from uniffi_simple_iface import *
 
 obj = make_object(9000)
@@ -3,3 +3,181 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_simple_iface.swift",
     "tests/bindings/test_simple_iface.py",
 );
+
+// Static archives (staticlib and rlib) should give the same metadata as the cdylib.
+#[cfg(not(windows))]
+#[test]
+fn test_metadata_from_static_archives() {
+    use uniffi_bindgen::macro_metadata::extract_from_library;
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let cdylib_path = test_helper.cdylib_path().unwrap();
+    let mut expected = extract_from_library(&cdylib_path).unwrap();
+    expected.sort();
+    assert!(!expected.is_empty());
+
+    for extension in ["a", "rlib"] {
+        let path = cdylib_path.with_extension(extension);
+        let mut items = extract_from_library(&path).unwrap();
+        items.sort();
+        assert_eq!(
+            items, expected,
+            "metadata from {path} doesn't match the cdylib"
+        );
+    }
+}
+
+// A crate can't come from more than one library when generating bindings for several libraries.
+#[test]
+fn test_crate_in_multiple_libraries() {
+    use uniffi_bindgen::{
+        bindings::PythonBindingGenerator,
+        library_mode::{generate_bindings_multi, GenerateOptions},
+        EmptyCrateConfigSupplier,
+    };
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let out_dir = test_helper
+        .create_out_dir(
+            std::env!("CARGO_TARGET_TMPDIR"),
+            "test-crate-in-multiple-libraries",
+        )
+        .unwrap();
+    let cdylib_path = test_helper.cdylib_path().unwrap();
+    let copied_cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir).unwrap();
+
+    let err = generate_bindings_multi(
+        &[&cdylib_path, &copied_cdylib_path],
+        &PythonBindingGenerator,
+        &EmptyCrateConfigSupplier,
+        &out_dir,
+        GenerateOptions::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Crate uniffi_simple_iface found in both {cdylib_path} and {copied_cdylib_path}")
+    );
+}
+
+// Generating bindings from a library built with a different contract version should fail, unless
+// the version check is disabled.
+#[test]
+fn test_contract_version_mismatch() {
+    use uniffi_bindgen::{
+        bindings::PythonBindingGenerator,
+        library_mode::{generate_bindings_multi, GenerateOptions},
+        EmptyCrateConfigSupplier,
+    };
+    use uniffi_meta::UNIFFI_CONTRACT_VERSION;
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let out_dir = test_helper
+        .create_out_dir(
+            std::env!("CARGO_TARGET_TMPDIR"),
+            "test-contract-version-mismatch",
+        )
+        .unwrap();
+    let library_path = test_helper.copy_cdylib_to_out_dir(&out_dir).unwrap();
+
+    // Patch the contract version metadata item in the copied library
+    let crate_name = b"uniffi_simple_iface";
+    let mut contract_version = vec![
+        uniffi::metadata::codes::CONTRACT_VERSION,
+        crate_name.len() as u8,
+    ];
+    contract_version.extend(crate_name);
+    let version_start = contract_version.len();
+    contract_version.extend(UNIFFI_CONTRACT_VERSION.to_le_bytes());
+    let mut data = std::fs::read(&library_path).unwrap();
+    let pos = data
+        .windows(contract_version.len())
+        .position(|w| w == contract_version)
+        .expect("contract version metadata not found");
+    let other_version = UNIFFI_CONTRACT_VERSION + 1;
+    data[pos + version_start..pos + contract_version.len()]
+        .copy_from_slice(&other_version.to_le_bytes());
+    std::fs::write(&library_path, data).unwrap();
+
+    let generate = |version_check| {
+        generate_bindings_multi(
+            &[&library_path],
+            &PythonBindingGenerator,
+            &EmptyCrateConfigSupplier,
+            &out_dir,
+            GenerateOptions {
+                version_check,
+                ..GenerateOptions::default()
+            },
+        )
+    };
+    let message = generate(true).unwrap_err().to_string();
+    assert!(
+        message.contains(&format!(
+            "uniffi-bindgen uses contract version {UNIFFI_CONTRACT_VERSION}"
+        )),
+        "{}",
+        message
+    );
+    assert!(
+        message.contains(&format!(
+            "contract version {other_version}: `uniffi_simple_iface`"
+        )),
+        "{}",
+        message
+    );
+    assert_eq!(generate(false).unwrap().len(), 1);
+}
+
+// The JSON metadata dump should be stable, and should parse back into the same metadata.
+#[test]
+fn test_metadata_json() {
+    use uniffi_bindgen::library_mode::metadata_json;
+    use uniffi_meta::MetadataGroup;
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let json = metadata_json(&test_helper.cdylib_path().unwrap()).unwrap();
+    assert_eq!(json, include_str!("metadata.json").trim_end());
+
+    let groups: Vec<MetadataGroup> = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string_pretty(&groups).unwrap(), json);
+}
+
+// The files written in library mode are recorded, even when the bindings haven't changed.
+#[test]
+fn test_written_files() {
+    use uniffi_bindgen::{
+        bindings::PythonBindingGenerator,
+        library_mode::{generate_bindings_multi, GenerateOptions},
+        EmptyCrateConfigSupplier,
+    };
+    use uniffi_testing::UniFFITestHelper;
+
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let out_dir = test_helper
+        .create_out_dir(std::env!("CARGO_TARGET_TMPDIR"), "test-written-files")
+        .unwrap();
+    let cdylib_path = test_helper.cdylib_path().unwrap();
+    // A file in the output directory which isn't written by the generator
+    std::fs::write(out_dir.join("other.py"), "").unwrap();
+
+    for _ in 0..2 {
+        let mut written_files = vec![];
+        generate_bindings_multi(
+            &[&cdylib_path],
+            &PythonBindingGenerator,
+            &EmptyCrateConfigSupplier,
+            &out_dir,
+            GenerateOptions {
+                written_files: Some(&mut written_files),
+                ..GenerateOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(written_files, [out_dir.join("uniffi_simple_iface.py")]);
+    }
+}
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{atomic::AtomicU32, atomic::Ordering, Arc};
+
+pub struct Greeting {
+    pub text: String,
+    pub language: Language,
+}
+
+pub enum Language {
+    English,
+    French,
+}
+
+fn greet(name: String) -> Greeting {
+    Greeting {
+        text: format!("Hello, {name}!"),
+        language: Language::English,
+    }
+}
+
+#[derive(Default)]
+pub struct Counter {
+    count: AtomicU32,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn increment(&self) -> u32 {
+        self.count.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+fn new_counter(start: u32) -> Arc<Counter> {
+    Arc::new(Counter {
+        count: AtomicU32::new(start),
+    })
+}
+
+#[uniffi::export]
+fn add_names(a: String, b: String) -> String {
+    format!("{a} and {b}")
+}
+
+uniffi::include_scaffolding!("core");
+uniffi::include_scaffolding!("admin");
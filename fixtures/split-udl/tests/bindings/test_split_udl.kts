@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.split_udl.*;
+
+// Items from `core.udl`
+val greeting = greet("World")
+assert(greeting.text == "Hello, World!")
+assert(greeting.language == Language.ENGLISH)
+
+// Items from `admin.udl`
+val counter = Counter()
+assert(counter.increment() == 1U)
+assert(counter.increment() == 2U)
+assert(newCounter(41U).increment() == 42U)
+
+// Items exported with proc-macros
+assert(addNames("Alice", "Bob") == "Alice and Bob")
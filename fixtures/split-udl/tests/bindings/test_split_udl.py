@@ -0,0 +1,21 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import split_udl
+from split_udl import *
+
+# Items from `core.udl`
+greeting = greet("World")
+assert greeting.text == "Hello, World!"
+assert greeting.language == Language.ENGLISH
+assert split_udl.__doc__.strip() == "Greetings, split from the admin interface."
+
+# Items from `admin.udl`
+counter = Counter()
+assert counter.increment() == 1
+assert counter.increment() == 2
+assert new_counter(41).increment() == 42
+
+# Items exported with proc-macros
+assert add_names("Alice", "Bob") == "Alice and Bob"
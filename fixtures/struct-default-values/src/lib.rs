@@ -2,12 +2,45 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
+
 struct Bookmark {
     guid: Option<String>,
     position: i32,
     last_modified: Option<i32>,
     url: String,
     title: Option<String>,
+    tags: Vec<String>,
+    metadata: HashMap<String, String>,
+}
+
+fn new_bookmark(
+    url: String,
+    title: Option<String>,
+    tags: Vec<String>,
+    metadata: HashMap<String, String>,
+) -> Bookmark {
+    Bookmark {
+        guid: None,
+        position: 0,
+        last_modified: None,
+        url,
+        title,
+        tags,
+        metadata,
+    }
+}
+
+struct Favicon {
+    magic: Vec<u8>,
+    data: Vec<u8>,
+}
+
+fn new_favicon(data: Vec<u8>) -> Favicon {
+    Favicon {
+        magic: b"\x89PNG".to_vec(),
+        data,
+    }
 }
 
 uniffi::include_scaffolding!("struct_default_values");
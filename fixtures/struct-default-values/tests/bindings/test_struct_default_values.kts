@@ -12,6 +12,8 @@ var bookmark = Bookmark(position=2, url=url)
 assert(bookmark.guid == null)
 assert(bookmark.position == 2)
 assert(bookmark.url == url)
+assert(bookmark.tags == emptyList<String>())
+assert(bookmark.metadata == emptyMap<String, String>())
 
 bookmark = Bookmark(position=3, url=url, guid="c0ffee")
 assert(bookmark.guid == "c0ffee")
@@ -33,3 +35,22 @@ assert(bookmark.position == 5)
 assert(bookmark.url == url)
 assert(bookmark.lastModified == 17)
 assert(bookmark.title == null)
+
+bookmark = newBookmark(url)
+assert(bookmark.title == null)
+assert(bookmark.tags == emptyList<String>())
+assert(bookmark.metadata == emptyMap<String, String>())
+
+bookmark = newBookmark(url, tags = listOf("rust"), metadata = mapOf("lang" to "en"))
+assert(bookmark.title == null)
+assert(bookmark.tags == listOf("rust"))
+assert(bookmark.metadata == mapOf("lang" to "en"))
+
+var favicon = Favicon()
+assert(favicon.magic.contentEquals(byteArrayOf(0x89.toByte(), 0x50, 0x4E, 0x47)))
+assert(favicon.data.isEmpty())
+
+favicon = newFavicon()
+assert(favicon.magic.contentEquals(byteArrayOf(0x89.toByte(), 0x50, 0x4E, 0x47)))
+assert(favicon.data.isEmpty())
+assert(newFavicon(byteArrayOf(0, 1)).data.contentEquals(byteArrayOf(0, 1)))
@@ -13,6 +13,8 @@ class TestStructDefaultValues(unittest.TestCase):
         assert not bookmark.guid
         assert bookmark.position == 2
         assert bookmark.url == url
+        assert bookmark.tags == []
+        assert bookmark.metadata == {}
 
     def test_bookmark_others_set(self):
         url = "https://mozilla.github.io/uniffi-rs"
@@ -22,5 +24,28 @@ class TestStructDefaultValues(unittest.TestCase):
         assert bookmark.position == 3
         assert bookmark.url == url
 
+    def test_function_defaults(self):
+        url = "https://mozilla.github.io/uniffi-rs"
+        bookmark = new_bookmark(url)
+        assert bookmark.url == url
+        assert bookmark.title is None
+        assert bookmark.tags == []
+        assert bookmark.metadata == {}
+
+        bookmark = new_bookmark(url, title="UniFFI", tags=["rust"], metadata={"lang": "en"})
+        assert bookmark.title == "UniFFI"
+        assert bookmark.tags == ["rust"]
+        assert bookmark.metadata == {"lang": "en"}
+
+    def test_bytes_defaults(self):
+        favicon = Favicon()
+        assert favicon.magic == b"\x89PNG"
+        assert favicon.data == b""
+
+        favicon = new_favicon()
+        assert favicon.magic == b"\x89PNG"
+        assert favicon.data == b""
+        assert new_favicon(b"\x00\x01").data == b"\x00\x01"
+
 if __name__=='__main__':
     unittest.main()
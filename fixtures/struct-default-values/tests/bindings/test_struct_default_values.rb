@@ -18,6 +18,8 @@ class TestStructDefaultValues < Test::Unit::TestCase
     assert_nil bookmark.guid
     assert_equal bookmark.position, 2
     assert_equal bookmark.url, url
+    assert_equal bookmark.tags, []
+    assert_equal bookmark.metadata, {}
   end
 
   def test_bookmark_others_set()
@@ -38,6 +40,16 @@ class TestStructDefaultValues < Test::Unit::TestCase
     assert_equal bookmark.url, url
   end
 
+  def test_bytes_defaults()
+    favicon = Favicon.new
+    assert_equal favicon.magic, "\x89PNG".b
+    assert_equal favicon.data, "".b
+
+    favicon = StructDefaultValues.new_favicon
+    assert_equal favicon.magic, "\x89PNG".b
+    assert_equal favicon.data, "".b
+  end
+
   def test_unnamed_unsupported()
     assert_raise ArgumentError do
       bookmark = Bookmark.new(3, "https://mozilla.github.io/uniffi-rs")
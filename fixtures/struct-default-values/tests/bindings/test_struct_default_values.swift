@@ -13,6 +13,8 @@ var bookmark = Bookmark(position: 2, url: url)
 assert(bookmark.guid == nil)
 assert(bookmark.position == 2)
 assert(bookmark.url == url)
+assert(bookmark.tags == [])
+assert(bookmark.metadata == [:])
 
 // In Swift order of named arguments still matters.
 bookmark = Bookmark(guid: "c0ffee", position: 3, url: url)
@@ -22,3 +24,22 @@ assert(bookmark.url == url)
 
 // No unnamed parameters allowed here,
 // so we cannot test that
+
+bookmark = newBookmark(url: url)
+assert(bookmark.title == nil)
+assert(bookmark.tags == [])
+assert(bookmark.metadata == [:])
+
+bookmark = newBookmark(url: url, tags: ["rust"], metadata: ["lang": "en"])
+assert(bookmark.title == nil)
+assert(bookmark.tags == ["rust"])
+assert(bookmark.metadata == ["lang": "en"])
+
+var favicon = Favicon()
+assert(favicon.magic == Data([0x89, 0x50, 0x4E, 0x47]))
+assert(favicon.data.isEmpty)
+
+favicon = newFavicon()
+assert(favicon.magic == Data([0x89, 0x50, 0x4E, 0x47]))
+assert(favicon.data.isEmpty)
+assert(newFavicon(data: Data([0, 1])).data == Data([0, 1]))
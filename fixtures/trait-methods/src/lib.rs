@@ -4,7 +4,7 @@
 
 use std::sync::Arc;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TraitMethods {
     val: String,
 }
@@ -21,8 +21,8 @@ impl std::fmt::Display for TraitMethods {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, uniffi::Object)]
-#[uniffi::export(Debug, Display, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, uniffi::Object)]
+#[uniffi::export(Debug, Display, Eq, Hash, Ord)]
 pub struct ProcTraitMethods {
     val: String,
 }
@@ -41,4 +41,15 @@ impl std::fmt::Display for ProcTraitMethods {
     }
 }
 
+fn sort_trait_methods(mut vals: Vec<Arc<TraitMethods>>) -> Vec<Arc<TraitMethods>> {
+    vals.sort();
+    vals
+}
+
+#[uniffi::export]
+fn sort_proc_trait_methods(mut vals: Vec<Arc<ProcTraitMethods>>) -> Vec<Arc<ProcTraitMethods>> {
+    vals.sort();
+    vals
+}
+
 uniffi::include_scaffolding!("trait_methods");
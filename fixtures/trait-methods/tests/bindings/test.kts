@@ -9,3 +9,13 @@ assert(m != TraitMethods("yoyo"))
 val map = mapOf(m to 1, TraitMethods("yoyo") to 2)
 assert(map[m] == 1)
 assert(map[TraitMethods("yoyo")] == 2)
+
+// ord
+assert(TraitMethods("a") < TraitMethods("b"))
+val vals = listOf("b", "c", "a").map { TraitMethods(it) }
+assert(vals.sorted() == sortTraitMethods(vals))
+assert(vals.sorted().map { it.toString() } == listOf("TraitMethods(a)", "TraitMethods(b)", "TraitMethods(c)"))
+
+assert(ProcTraitMethods("b") > ProcTraitMethods("a"))
+val procVals = listOf("b", "c", "a").map { ProcTraitMethods(it) }
+assert(procVals.sorted() == sortProcTraitMethods(procVals))
@@ -29,6 +29,17 @@ class TestTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+    def test_ord(self):
+        self.assertLess(TraitMethods("a"), TraitMethods("b"))
+        self.assertGreaterEqual(TraitMethods("b"), TraitMethods("b"))
+        vals = [TraitMethods(v) for v in ["b", "c", "a"]]
+        self.assertEqual(sorted(vals), sort_trait_methods(vals))
+        self.assertEqual([str(v) for v in sorted(vals)], ["TraitMethods(a)", "TraitMethods(b)", "TraitMethods(c)"])
+
+    def test_ord_wrong_type(self):
+        with self.assertRaises(TypeError):
+            TraitMethods("a") < 17
+
 class TestProcmacroTraitMethods(unittest.TestCase):
     def test_str(self):
         m = ProcTraitMethods("yo")
@@ -53,5 +64,11 @@ class TestProcmacroTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+    def test_ord(self):
+        self.assertGreater(ProcTraitMethods("b"), ProcTraitMethods("a"))
+        self.assertLessEqual(ProcTraitMethods("b"), ProcTraitMethods("b"))
+        vals = [ProcTraitMethods(v) for v in ["b", "c", "a"]]
+        self.assertEqual(sorted(vals), sort_proc_trait_methods(vals))
+
 if __name__=='__main__':
     unittest.main()
@@ -11,3 +11,13 @@ assert(m != TraitMethods(name: "foo"))
 // hash
 var set: Set = [TraitMethods(name: "yo")]
 assert(set.contains(TraitMethods(name: "yo")))
+
+// ord
+assert(TraitMethods(name: "a") < TraitMethods(name: "b"))
+let vals = ["b", "c", "a"].map { TraitMethods(name: $0) }
+assert(vals.sorted() == sortTraitMethods(vals: vals))
+assert(vals.sorted().map { String(describing: $0) } == ["TraitMethods(a)", "TraitMethods(b)", "TraitMethods(c)"])
+
+assert(ProcTraitMethods(val: "b") > ProcTraitMethods(val: "a"))
+let procVals = ["b", "c", "a"].map { ProcTraitMethods(val: $0) }
+assert(procVals.sorted() == sortProcTraitMethods(vals: procVals))
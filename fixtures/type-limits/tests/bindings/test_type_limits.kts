@@ -14,3 +14,10 @@ try {
 assert(takeString("") == "")
 assert(takeString("愛") == "愛")
 assert(takeString("💖") == "💖")
+
+// test_constants
+assert(UniffiTypeLimits.I8_MIN == Byte.MIN_VALUE)
+assert(UniffiTypeLimits.I8_MAX == Byte.MAX_VALUE)
+assert(UniffiTypeLimits.U8_MAX == UByte.MAX_VALUE)
+assert(UniffiTypeLimits.U16_MAX == UShort.MAX_VALUE)
+assert(UniffiTypeLimits.U64_MAX == ULong.MAX_VALUE)
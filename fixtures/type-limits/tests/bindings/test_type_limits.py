@@ -46,6 +46,14 @@ class TestTypeLimits(unittest.TestCase):
         self.assertEqual(take_u32(2**32 - 1), 2**32 - 1)
         self.assertEqual(take_u64(2**64 - 1), 2**64 - 1)
 
+    def test_constants(self):
+        self.assertEqual(I8_MIN, -2**7)
+        self.assertEqual(I8_MAX, 2**7 - 1)
+        self.assertEqual(U8_MAX, 2**8 - 1)
+        self.assertEqual(U16_MAX, 2**16 - 1)
+        self.assertEqual(U64_MAX, 2**64 - 1)
+        self.assertEqual(take_u64(U64_MAX), 2**64 - 1)
+
     def test_larger_numbers(self):
         self.assertRaises(ValueError, lambda: take_i8(10**3))
         self.assertRaises(ValueError, lambda: take_i16(10**5))
@@ -44,6 +44,14 @@ class TestTypeLimits < Test::Unit::TestCase
     assert_equal(UniffiTypeLimits.take_u32(2**32 - 1), 2**32 - 1)
     assert_equal(UniffiTypeLimits.take_u64(2**64 - 1), 2**64 - 1)
   end
+  def test_constants
+    assert_equal(UniffiTypeLimits::I8_MIN, -2**7)
+    assert_equal(UniffiTypeLimits::I8_MAX, 2**7 - 1)
+    assert_equal(UniffiTypeLimits::U8_MAX, 2**8 - 1)
+    assert_equal(UniffiTypeLimits::U16_MAX, 2**16 - 1)
+    assert_equal(UniffiTypeLimits::U64_MAX, 2**64 - 1)
+    assert_equal(UniffiTypeLimits.take_u64(UniffiTypeLimits::U64_MAX), 2**64 - 1)
+  end
   def test_larger_numbers
     assert_raise RangeError do UniffiTypeLimits.take_i8(10**3) end
     assert_raise RangeError do UniffiTypeLimits.take_i16(10**5) end
@@ -12,3 +12,10 @@ do {
     assert(takeString(v: "愛") == "愛")
     assert(takeString(v: "💖") == "💖")
 }
+
+// test_constants
+assert(UniffiTypeLimits.i8Min == Int8.min)
+assert(UniffiTypeLimits.i8Max == Int8.max)
+assert(UniffiTypeLimits.u8Max == UInt8.max)
+assert(UniffiTypeLimits.u16Max == UInt16.max)
+assert(UniffiTypeLimits.u64Max == UInt64.max)
@@ -0,0 +1,94 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+pub trait Trait: Send + Sync {
+    fn concat_strings(&self, a: &str, b: &str) -> String;
+}
+
+struct TraitImpl {}
+
+impl Trait for TraitImpl {
+    fn concat_strings(&self, a: &str, b: &str) -> String {
+        format!("{a}{b}")
+    }
+}
+
+pub trait TraitWithForeign: Send + Sync {
+    fn name(&self) -> String;
+}
+
+struct RustTraitImpl {}
+
+impl TraitWithForeign for RustTraitImpl {
+    fn name(&self) -> String {
+        "RustTraitImpl".to_string()
+    }
+}
+
+fn get_trait(inc: Option<Arc<dyn Trait>>) -> Arc<dyn Trait> {
+    inc.unwrap_or_else(|| Arc::new(TraitImpl {}))
+}
+
+fn get_trait_with_foreign(inc: Option<Arc<dyn TraitWithForeign>>) -> Arc<dyn TraitWithForeign> {
+    inc.unwrap_or_else(|| Arc::new(RustTraitImpl {}))
+}
+
+fn concat_strings_by_ref(t: &dyn Trait, a: &str, b: &str) -> String {
+    t.concat_strings(a, b)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogError {
+    #[error("The log is full")]
+    Full,
+    #[error("The logger failed unexpectedly")]
+    Unexpected,
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for LogError {
+    fn from(_: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::Unexpected
+    }
+}
+
+pub trait Logger: Send + Sync {
+    fn name(&self) -> String;
+    fn log(&self, message: String) -> Result<(), LogError>;
+}
+
+fn logger_name(logger: Arc<dyn Logger>) -> String {
+    logger.name()
+}
+
+pub struct Worker {
+    logger: Arc<dyn Logger>,
+}
+
+impl Worker {
+    fn new(logger: Arc<dyn Logger>) -> Self {
+        Self { logger }
+    }
+
+    fn run(&self, steps: u32) -> u32 {
+        let mut logged = 0;
+        for step in 1..=steps {
+            match self.logger.log(format!("step {step}")) {
+                Ok(()) => logged += 1,
+                Err(LogError::Full) => break,
+                Err(e) => panic!("unexpected error from {}: {e}", self.logger.name()),
+            }
+        }
+        logged
+    }
+}
+
+// The UDL traits can be used by items exported with proc-macros
+#[uniffi::export]
+fn shout_name(t: Arc<dyn TraitWithForeign>) -> String {
+    t.name().to_uppercase()
+}
+
+uniffi::include_scaffolding!("udl_traits");
@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.udl_traits.*;
+
+val traitImpl = getTrait(null)
+assert(traitImpl.concatStrings("foo", "bar") == "foobar")
+assert(getTrait(traitImpl).concatStrings("foo", "bar") == "foobar")
+assert(concatStringsByRef(traitImpl, "foo", "bar") == "foobar")
+
+val traitImpl2 = getTraitWithForeign(null)
+assert(traitImpl2.name() == "RustTraitImpl")
+assert(getTraitWithForeign(traitImpl2).name() == "RustTraitImpl")
+assert(shoutName(traitImpl2) == "RUSTTRAITIMPL")
+
+class KtTraitImpl : TraitWithForeign {
+    override fun name() = "KtTraitImpl"
+}
+
+val ktTraitImpl = getTraitWithForeign(KtTraitImpl())
+assert(ktTraitImpl.name() == "KtTraitImpl")
+assert(shoutName(ktTraitImpl) == "KTTRAITIMPL")
+
+// Rust keeps the foreign logger and calls it
+class KtLogger(val capacity: Int) : Logger {
+    val messages = mutableListOf<String>()
+
+    override fun name() = "KtLogger"
+
+    override fun log(message: String) {
+        if (messages.size == capacity) {
+            throw LogException.Full("the log is full")
+        }
+        messages.add(message)
+    }
+}
+
+val logger = KtLogger(3)
+assert(loggerName(logger) == "KtLogger")
+val worker = Worker(logger)
+assert(worker.run(2u) == 2u)
+assert(logger.messages == listOf("step 1", "step 2"))
+assert(worker.run(5u) == 1u)
+assert(logger.messages == listOf("step 1", "step 2", "step 1"))
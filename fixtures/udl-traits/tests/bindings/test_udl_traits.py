@@ -0,0 +1,45 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from udl_traits import *
+
+trait_impl = get_trait(None)
+assert trait_impl.concat_strings("foo", "bar") == "foobar"
+assert get_trait(trait_impl).concat_strings("foo", "bar") == "foobar"
+assert concat_strings_by_ref(trait_impl, "foo", "bar") == "foobar"
+
+trait_impl2 = get_trait_with_foreign(None)
+assert trait_impl2.name() == "RustTraitImpl"
+assert get_trait_with_foreign(trait_impl2).name() == "RustTraitImpl"
+assert shout_name(trait_impl2) == "RUSTTRAITIMPL"
+
+class PyTraitImpl(TraitWithForeign):
+    def name(self):
+        return "PyTraitImpl"
+
+py_trait_impl = get_trait_with_foreign(PyTraitImpl())
+assert py_trait_impl.name() == "PyTraitImpl"
+assert shout_name(py_trait_impl) == "PYTRAITIMPL"
+
+# Rust keeps the foreign logger and calls it
+class PyLogger(Logger):
+    def __init__(self, capacity):
+        self.capacity = capacity
+        self.messages = []
+
+    def name(self):
+        return "PyLogger"
+
+    def log(self, message):
+        if len(self.messages) == self.capacity:
+            raise LogError.Full()
+        self.messages.append(message)
+
+logger = PyLogger(3)
+assert logger_name(logger) == "PyLogger"
+worker = Worker(logger)
+assert worker.run(2) == 2
+assert logger.messages == ["step 1", "step 2"]
+assert worker.run(5) == 1
+assert logger.messages == ["step 1", "step 2", "step 1"]
@@ -0,0 +1,54 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import udl_traits
+
+let traitImpl = getTrait(inc: nil)
+assert(traitImpl.concatStrings(a: "foo", b: "bar") == "foobar")
+assert(getTrait(inc: traitImpl).concatStrings(a: "foo", b: "bar") == "foobar")
+assert(concatStringsByRef(t: traitImpl, a: "foo", b: "bar") == "foobar")
+
+let traitImpl2 = getTraitWithForeign(inc: nil)
+assert(traitImpl2.name() == "RustTraitImpl")
+assert(getTraitWithForeign(inc: traitImpl2).name() == "RustTraitImpl")
+assert(shoutName(t: traitImpl2) == "RUSTTRAITIMPL")
+
+class SwiftTraitImpl: TraitWithForeign {
+    func name() -> String {
+        return "SwiftTraitImpl"
+    }
+}
+
+let swiftTraitImpl = getTraitWithForeign(inc: SwiftTraitImpl())
+assert(swiftTraitImpl.name() == "SwiftTraitImpl")
+assert(shoutName(t: swiftTraitImpl) == "SWIFTTRAITIMPL")
+
+// Rust keeps the foreign logger and calls it
+class SwiftLogger: Logger {
+    let capacity: Int
+    var messages: [String] = []
+
+    init(capacity: Int) {
+        self.capacity = capacity
+    }
+
+    func name() -> String {
+        return "SwiftLogger"
+    }
+
+    func log(message: String) throws {
+        if messages.count == capacity {
+            throw LogError.Full(message: "the log is full")
+        }
+        messages.append(message)
+    }
+}
+
+let logger = SwiftLogger(capacity: 3)
+assert(loggerName(logger: logger) == "SwiftLogger")
+let worker = Worker(logger: logger)
+assert(worker.run(steps: 2) == 2)
+assert(logger.messages == ["step 1", "step 2"])
+assert(worker.run(steps: 5) == 1)
+assert(logger.messages == ["step 1", "step 2", "step 1"])
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_udl_traits.py",
+    "tests/bindings/test_udl_traits.kts",
+    "tests/bindings/test_udl_traits.swift",
+);
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+// Unfortunately, path is relative to a temporary build directory :-/
+uniffi_macros::generate_and_include_scaffolding!("../../../../fixtures/uitests/src/counter.udl");
+
+fn main() { /* empty main required by `trybuild` */}
+
+pub struct Counter {
+    value: std::sync::atomic::AtomicU32,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self { value: 0.into() }
+    }
+
+    pub fn increment(&self) -> u32 {
+        self.value.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1
+    }
+}
+
+// This will fail to compile, because `Counter` has a constructor in the UDL.
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    pub async fn new_async() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+}
@@ -0,0 +1,13 @@
+fn main() {} /* empty main required by `trybuild` */
+
+// `catch_all` needs a `Result` to convert
+#[uniffi::export(catch_all)]
+fn no_return() {}
+
+// `catch_all` is for `anyhow::Error` and boxed errors, other errors should be exported
+#[uniffi::export(catch_all)]
+fn io_error() -> std::io::Result<u32> {
+    Ok(0)
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(uniffi::Object)]
+pub struct Transaction;
+
+#[uniffi::export]
+impl Transaction {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    // Consuming methods need to take ownership of the object
+    #[uniffi::method(consumes)]
+    fn commit(&self) {}
+}
+
+#[uniffi::export]
+impl Transaction {
+    #[uniffi::constructor(consumes)]
+    fn from_other(other: Arc<Self>) -> Arc<Self> {
+        other
+    }
+}
+
+#[uniffi::export(consumes)]
+fn commit_transaction(transaction: Arc<Transaction>) {
+    transaction.commit()
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,50 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(uniffi::Object)]
+pub struct Inventory;
+
+#[uniffi::export]
+impl Inventory {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self
+    }
+
+    fn count(&self, item: String) -> u32 {
+        0
+    }
+}
+
+// Impl blocks in other modules can export more members, but not ones with the same foreign name
+mod writes {
+    use super::Inventory;
+
+    #[uniffi::export]
+    impl Inventory {
+        #[uniffi::constructor(name = "new")]
+        fn empty() -> Self {
+            Self
+        }
+
+        #[uniffi::method(name = "count")]
+        fn add(&self, item: String) -> u32 {
+            1
+        }
+    }
+}
+
+// Getters are methods too
+#[derive(uniffi::Object)]
+#[uniffi(getters)]
+pub struct Book {
+    pub title: String,
+}
+
+#[uniffi::export]
+impl Book {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,15 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export(tag = "pro plan")]
+pub fn spaces_in_tag() {}
+
+#[uniffi::export(tag = "")]
+pub fn empty_tag() {}
+
+#[uniffi::export(tag = "pro")]
+pub trait Billing: Send + Sync {
+    #[uniffi::method(tag = "beta")]
+    fn invoice(&self) -> u32;
+}
+
+uniffi_macros::setup_scaffolding!();
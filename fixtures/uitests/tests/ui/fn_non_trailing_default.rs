@@ -0,0 +1,8 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export]
+pub fn add(#[uniffi(default = 1)] a: u32, b: u32) -> u32 {
+    a + b
+}
+
+uniffi_macros::setup_scaffolding!();
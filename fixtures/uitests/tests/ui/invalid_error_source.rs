@@ -0,0 +1,28 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum TwoSources {
+    #[error("failed")]
+    Failed {
+        #[uniffi(source)]
+        first: String,
+        #[uniffi(source)]
+        second: String,
+    },
+}
+
+#[derive(uniffi::Enum)]
+pub enum NotAnError {
+    Value {
+        #[uniffi(source)]
+        value: String,
+    },
+}
+
+#[derive(uniffi::Record)]
+pub struct Record {
+    #[uniffi(source)]
+    value: String,
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,39 @@
+fn main() {} /* empty main required by `trybuild` */
+
+// Getters are only generated for `pub` fields
+#[derive(uniffi::Object)]
+pub struct PrivateField {
+    #[uniffi(get)]
+    id: u32,
+}
+
+// Tuple fields don't have a name for the property
+#[derive(uniffi::Object)]
+pub struct TupleField(#[uniffi(get)] pub u32);
+
+#[derive(uniffi::Object)]
+#[uniffi(getters)]
+pub struct NoPublicFields {
+    id: u32,
+}
+
+// The field types must be supported, like argument and return types
+#[derive(uniffi::Object)]
+#[uniffi(getters)]
+pub struct UnsupportedType {
+    pub ids: [u32; 4],
+}
+
+// Getters clone the field
+#[derive(uniffi::Object)]
+#[uniffi(getters)]
+pub struct NotClone {
+    pub inner: Inner,
+}
+
+#[derive(uniffi::Record)]
+pub struct Inner {
+    id: u32,
+}
+
+uniffi_macros::setup_scaffolding!();
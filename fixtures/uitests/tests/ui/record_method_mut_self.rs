@@ -0,0 +1,16 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(uniffi::Record)]
+pub struct Counter {
+    value: u32,
+}
+
+#[uniffi::export]
+impl Counter {
+    pub fn increment(&mut self) -> u32 {
+        self.value += 1;
+        self.value
+    }
+}
+
+uniffi_macros::setup_scaffolding!();
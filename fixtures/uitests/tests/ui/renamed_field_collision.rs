@@ -0,0 +1,15 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(uniffi::Record)]
+pub struct Event {
+    timestamp_ms: u64,
+    #[uniffi(name = "timestamp_ms")]
+    ts_ms: u64,
+}
+
+#[uniffi::export]
+pub fn make_event(ts_ms: u64, #[uniffi(name = "ts_ms")] timestamp_ms: u64) -> u64 {
+    ts_ms + timestamp_ms
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,35 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[derive(uniffi::Object)]
+pub struct Channel;
+
+#[uniffi::export]
+impl Channel {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self
+    }
+
+    #[uniffi::constructor(name = "new")]
+    fn with_capacity(capacity: u32) -> Self {
+        Self
+    }
+}
+
+#[uniffi::export]
+impl Channel {
+    fn send(&self, message: String) {}
+
+    #[uniffi::method(name = "send")]
+    fn try_send(&self, message: String) -> bool {
+        true
+    }
+}
+
+#[uniffi::export]
+impl Channel {
+    #[uniffi::method(name = "")]
+    fn close(&self) {}
+}
+
+uniffi_macros::setup_scaffolding!();
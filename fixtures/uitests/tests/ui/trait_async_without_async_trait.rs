@@ -0,0 +1,9 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export]
+pub trait Store: Send + Sync {
+    fn name(&self) -> String;
+    async fn load(&self, key: String) -> Option<String>;
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,17 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export]
+pub trait Limits: Send + Sync {
+    const NAMES: &'static [&'static str] = &["a", "b"];
+
+    fn count(&self) -> u32;
+}
+
+#[uniffi::export(with_foreign)]
+pub trait Version: Send + Sync {
+    const VERSION: u32;
+
+    fn name(&self) -> String;
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,13 @@
+use std::sync::Arc;
+
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export]
+pub trait Store: Send + Sync {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self>;
+
+    fn get(&self, key: String) -> Option<String>;
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,11 @@
+fn main() {} /* empty main required by `trybuild` */
+
+#[uniffi::export]
+pub trait Trait: Send + Sync {
+    fn method(&self);
+
+    #[cfg(feature = "extra")]
+    fn extra_method(&self);
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() { /* empty main required by `trybuild` */}
+
+#[derive(uniffi::Object)]
+pub struct Obj;
+
+#[derive(uniffi::Record)]
+pub struct RecordWithCow {
+    name: Cow<'static, str>,
+    // Skipped fields can have any type
+    #[uniffi(skip)]
+    cache: Rc<String>,
+}
+
+#[derive(uniffi::Record)]
+pub struct RecordWithUnsupportedFields {
+    ptr: *const u8,
+    size: usize,
+    id: std::num::NonZeroUsize,
+    items: Vec<&'static str>,
+    pair: (u32, u32),
+}
+
+#[derive(uniffi::Enum)]
+pub enum EnumWithUnsupportedFields {
+    Array { values: [u8; 4] },
+    Rc(Rc<Obj>),
+}
+
+#[uniffi::export]
+pub trait Trait: Send + Sync {}
+
+#[uniffi::export]
+pub fn takes_rc_trait(_trait: Rc<dyn Trait>) {}
+
+#[uniffi::export]
+pub fn takes_box(_obj: Box<Obj>, _offset: isize) {}
+
+// Shared references are fine for arguments, but not mutable ones
+#[uniffi::export]
+pub fn takes_refs(_name: &str, _values: &[u32], _obj: &mut Obj) {}
+
+#[uniffi::export]
+pub fn returns_ref() -> &'static str {
+    "hello"
+}
+
+#[uniffi::export]
+pub fn returns_cow_slice() -> Option<Cow<'static, [u32]>> {
+    None
+}
+
+// Only UTC times are supported, the offset would be lost
+#[uniffi::export]
+pub fn takes_fixed_offset(_time: chrono::DateTime<chrono::FixedOffset>) -> Option<chrono::DateTime<chrono::Utc>> {
+    None
+}
+
+#[uniffi::export]
+pub fn takes_callback(_callback: fn(u32) -> u32) -> Arc<Obj> {
+    Arc::new(Obj)
+}
+
+uniffi_macros::setup_scaffolding!();
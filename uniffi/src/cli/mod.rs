@@ -7,7 +7,7 @@ mod uniffi_bindgen;
 
 pub fn uniffi_bindgen_main() {
     if let Err(e) = uniffi_bindgen::run_main() {
-        eprintln!("{e}");
+        ::uniffi_bindgen::diagnostics::report_error(&e);
         std::process::exit(1);
     }
 }
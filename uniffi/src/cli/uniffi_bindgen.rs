@@ -5,19 +5,33 @@
 use anyhow::{bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::{Parser, Subcommand};
-use std::fmt;
-use uniffi_bindgen::bindings::*;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+use uniffi_bindgen::{
+    bindings::*,
+    depfile::Depfile,
+    diagnostics::MessageFormat,
+    external_generator::{available_languages_on_path, ExternalGenerator},
+};
 
 /// Enumeration of all foreign language targets currently supported by our CLI.
 ///
-#[derive(Copy, Clone, Eq, PartialEq, Hash, clap::ValueEnum)]
+/// Languages other than the builtin ones are supported by `uniffi-bindgen-<language>`
+/// executables, see [uniffi_bindgen::external_generator].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 enum TargetLanguage {
     Kotlin,
     Swift,
     Python,
     Ruby,
+    C,
+    External(ExternalGenerator),
 }
 
+const BUILTIN_LANGUAGES: [&str; 5] = ["kotlin", "swift", "python", "ruby", "c"];
+
 impl fmt::Display for TargetLanguage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -25,6 +39,8 @@ impl fmt::Display for TargetLanguage {
             Self::Swift => write!(f, "swift"),
             Self::Python => write!(f, "python"),
             Self::Ruby => write!(f, "ruby"),
+            Self::C => write!(f, "c"),
+            Self::External(generator) => write!(f, "{}", generator.language),
         }
     }
 }
@@ -37,7 +53,23 @@ impl TryFrom<&str> for TargetLanguage {
             "swift" => TargetLanguage::Swift,
             "python" | "py" => TargetLanguage::Python,
             "ruby" | "rb" => TargetLanguage::Ruby,
-            _ => bail!("Unknown or unsupported target language: \"{value}\""),
+            "c" | "h" | "headers" => TargetLanguage::C,
+            _ => match ExternalGenerator::find_on_path(value) {
+                Some(generator) => TargetLanguage::External(generator),
+                None => {
+                    let mut languages: Vec<String> =
+                        BUILTIN_LANGUAGES.iter().map(|l| l.to_string()).collect();
+                    languages.extend(
+                        available_languages_on_path()
+                            .into_iter()
+                            .filter(|l| !BUILTIN_LANGUAGES.contains(&l.as_str())),
+                    );
+                    bail!(
+                        "Unknown or unsupported target language: \"{value}\" (available: {})",
+                        languages.join(", ")
+                    )
+                }
+            },
         })
     }
 }
@@ -59,6 +91,123 @@ impl TryFrom<String> for TargetLanguage {
     }
 }
 
+fn parse_language(value: &str) -> Result<TargetLanguage> {
+    value.try_into()
+}
+
+fn parse_message_format(value: &str) -> Result<MessageFormat> {
+    match value {
+        "human" => Ok(MessageFormat::Human),
+        "json" => Ok(MessageFormat::Json),
+        _ => bail!("Unknown message format: \"{value}\" (available: human, json)"),
+    }
+}
+
+/// A `--config` or `--out-dir` argument, either `<path>` or `<language>=<path>`
+#[derive(Clone, Debug)]
+struct LanguagePathArg {
+    language: Option<String>,
+    path: Utf8PathBuf,
+}
+
+fn parse_language_path(value: &str) -> Result<LanguagePathArg> {
+    if let Some((language, path)) = value.split_once('=') {
+        let is_language_name = !language.is_empty()
+            && language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if is_language_name {
+            return Ok(LanguagePathArg {
+                language: Some(TargetLanguage::try_from(language)?.to_string()),
+                path: path.into(),
+            });
+        }
+    }
+    Ok(LanguagePathArg {
+        language: None,
+        path: value.into(),
+    })
+}
+
+// Split `<path>` and `<language>=<path>` arguments, checking each is only passed once
+fn split_language_paths(
+    args: Vec<LanguagePathArg>,
+    option: &str,
+) -> Result<(Option<Utf8PathBuf>, HashMap<String, Utf8PathBuf>)> {
+    let mut global = None;
+    let mut languages = HashMap::new();
+    for arg in args {
+        match arg.language {
+            None => {
+                if global.replace(arg.path).is_some() {
+                    bail!("{option} can only be passed once without a language");
+                }
+            }
+            Some(language) => {
+                if languages.contains_key(&language) {
+                    bail!("{option} can only be passed once for {language}");
+                }
+                languages.insert(language, arg.path);
+            }
+        }
+    }
+    Ok((global, languages))
+}
+
+/// The `--config` files, which are merged on top of each crate's `uniffi.toml`
+///
+/// For each language, the language-specific file takes precedence over the global one.
+#[derive(Default)]
+struct ConfigOverrides {
+    global: Option<Utf8PathBuf>,
+    languages: HashMap<String, Utf8PathBuf>,
+}
+
+impl ConfigOverrides {
+    fn new(args: Vec<LanguagePathArg>) -> Result<Self> {
+        let (global, languages) = split_language_paths(args, "--config")?;
+        Ok(Self { global, languages })
+    }
+
+    /// The files to merge for `language`, in order of increasing precedence
+    fn for_language(&self, language: &TargetLanguage) -> Vec<&camino::Utf8Path> {
+        self.global
+            .iter()
+            .chain(self.languages.get(&language.to_string()))
+            .map(Utf8PathBuf::as_path)
+            .collect()
+    }
+}
+
+/// The `--out-dir` directories
+///
+/// The directory for a language is its `<language>=<path>` argument if it was passed, otherwise
+/// the global directory with any `{language}` replaced by the language name.
+#[derive(Default)]
+struct OutDirs {
+    global: Option<Utf8PathBuf>,
+    languages: HashMap<String, Utf8PathBuf>,
+}
+
+impl OutDirs {
+    fn new(args: Vec<LanguagePathArg>) -> Result<Self> {
+        let (global, languages) = split_language_paths(args, "--out-dir")?;
+        Ok(Self { global, languages })
+    }
+
+    /// The directory for `language`, if one was given
+    fn for_language(&self, language: &TargetLanguage) -> Option<Utf8PathBuf> {
+        let language = language.to_string();
+        match self.languages.get(&language) {
+            Some(dir) => Some(dir.clone()),
+            None => self
+                .global
+                .as_ref()
+                .map(|dir| dir.as_str().replace("{language}", &language).into()),
+        }
+    }
+}
+
 // Structs to help our cmdline parsing. Note that docstrings below form part
 // of the "help" output.
 
@@ -76,21 +225,29 @@ struct Cli {
 enum Commands {
     /// Generate foreign language bindings
     Generate {
-        /// Foreign language(s) for which to build bindings.
-        #[clap(long, short, value_enum)]
+        /// Foreign language(s) for which to build bindings: kotlin, swift, python, ruby, c, or, when
+        /// `--library` is passed, any language with a `uniffi-bindgen-<language>` executable on
+        /// the PATH.
+        #[clap(long, short, value_parser = parse_language)]
         language: Vec<TargetLanguage>,
 
         /// Directory in which to write generated files. Default is same folder as .udl file.
-        #[clap(long, short)]
-        out_dir: Option<Utf8PathBuf>,
+        /// `{language}` in the path is replaced by the name of each language, like
+        /// `bindings/{language}`, and `<language>=<path>` gives the directory for one language.
+        /// Can be passed once for each language.
+        #[clap(long, short, value_parser = parse_language_path)]
+        out_dir: Vec<LanguagePathArg>,
 
         /// Do not try to format the generated bindings.
         #[clap(long, short)]
         no_format: bool,
 
         /// Path to optional uniffi config file. This config is merged with the `uniffi.toml` config present in each crate, with its values taking precedence.
-        #[clap(long, short)]
-        config: Option<Utf8PathBuf>,
+        /// When `--library` is passed, `<language>=<path>` gives a config file for one language,
+        /// which takes precedence over the other config files. Can be passed once for each
+        /// language.
+        #[clap(long, short, value_parser = parse_language_path)]
+        config: Vec<LanguagePathArg>,
 
         /// Extract proc-macro metadata from a native lib (cdylib or staticlib) for this crate.
         #[clap(long)]
@@ -100,6 +257,12 @@ enum Commands {
         #[clap(long = "library")]
         library_mode: bool,
 
+        /// Find the cdylib in the output of `cargo build --message-format=json`, which is read
+        /// from the source path, or from stdin if it's `-`. This implies `--library`. With
+        /// `--crate`, the cdylib for that crate is used, otherwise the most recent cdylib.
+        #[clap(long, conflicts_with = "lib_file")]
+        from_cargo_messages: bool,
+
         /// When `--library` is passed, only generate bindings for one crate.
         /// When `--library` is not passed, use this as the crate name instead of attempting to
         /// locate and parse Cargo.toml.
@@ -116,6 +279,63 @@ enum Commands {
         /// all sub-dependencies causes obscure platform specific problems.
         #[clap(long)]
         metadata_no_deps: bool,
+
+        /// When `--library` is passed, the maximum number of threads used to write the bindings.
+        /// Defaults to the number of available CPUs.
+        #[clap(long, short)]
+        jobs: Option<usize>,
+
+        /// Write a Makefile-style depfile listing the generated files and the files they were
+        /// generated from, so build systems know when to generate the bindings again.
+        #[clap(long)]
+        depfile: Option<Utf8PathBuf>,
+
+        /// When `--library` is passed, don't check that the library was built with a compatible
+        /// version of UniFFI.
+        #[clap(long)]
+        no_version_check: bool,
+
+        /// When `--library` is passed, don't generate bindings for this crate. Can be passed more
+        /// than once. Crates can also be listed in `omit_crates` in the `--config` file.
+        #[clap(long = "omit-crate")]
+        omit_crates: Vec<String>,
+
+        /// When `--library` is passed, only generate the items whose export tag is in this
+        /// comma-separated list, along with the untagged items. Tags can also be listed in
+        /// `include_tags` in the `--config` file.
+        #[clap(long, value_delimiter = ',')]
+        include_tags: Option<Vec<String>>,
+
+        /// When `--library` and `--crate` are passed, also generate bindings for the crates whose
+        /// types the crate uses, directly or through other crates.
+        #[clap(long, requires = "crate_name")]
+        with_dependencies: bool,
+
+        /// Print more details about the bindings being generated, like the crates skipped by
+        /// `--with-dependencies`.
+        #[clap(long, short)]
+        verbose: bool,
+
+        /// When `--library` is passed, keep running and generate the bindings again whenever the
+        /// library, or the UDL and `uniffi.toml` files they're generated from, change.
+        #[clap(long, short)]
+        watch: bool,
+
+        /// After generating the bindings, remove the files in the output directory which look
+        /// like bindings for the languages being generated, but weren't written by this run.
+        /// This is for bindings left behind when a crate is removed, so the output directory
+        /// should only hold generated bindings.
+        #[clap(long)]
+        clean: bool,
+
+        /// Like `--clean`, but only list the files which would be removed.
+        #[clap(long, conflicts_with = "clean")]
+        clean_dry_run: bool,
+
+        /// How errors and warnings are printed: `human`, or `json` to print each one to stdout as
+        /// a line of JSON, for tools which run `uniffi-bindgen`.
+        #[clap(long, value_parser = parse_message_format, default_value = "human")]
+        message_format: MessageFormat,
     },
 
     /// Generate Rust scaffolding code
@@ -137,138 +357,422 @@ enum Commands {
         /// Path to the library file (.so, .dll, .dylib, or .a)
         path: Utf8PathBuf,
     },
+
+    /// Compare the API of two builds of a dynamic library
+    ///
+    /// Exits with an error if any of the changes are breaking.
+    Diff {
+        /// Path to the old library file
+        old: Utf8PathBuf,
+
+        /// Path to the new library file
+        new: Utf8PathBuf,
+    },
+
+    /// Check that the UDL files of the crates in a dynamic library match their Rust code
+    ///
+    /// Items which are defined both in a UDL file and by the Rust code must have the same
+    /// signatures, fields and variants.  Exits with an error if any of them differ.
+    Check {
+        /// Whether we should exclude dependencies when running "cargo metadata".
+        #[clap(long)]
+        metadata_no_deps: bool,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        path: Utf8PathBuf,
+    },
+
+    /// Print the metadata from a dynamic library as JSON
+    PrintJson {
+        /// Write the JSON to this file rather than to stdout
+        #[clap(long, short)]
+        out_file: Option<Utf8PathBuf>,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        path: Utf8PathBuf,
+    },
 }
 
-fn gen_library_mode(
-    library_path: &camino::Utf8Path,
-    crate_name: Option<String>,
-    languages: Vec<TargetLanguage>,
-    cfo: Option<&camino::Utf8Path>,
-    out_dir: &camino::Utf8Path,
-    fmt: bool,
-    metadata_no_deps: bool,
-) -> anyhow::Result<()> {
-    use uniffi_bindgen::library_mode::generate_bindings;
+// Find the cdylib in the cargo messages in `path`, or on stdin if it's `-`
+fn library_from_cargo_messages(
+    path: &camino::Utf8Path,
+    crate_name: Option<&str>,
+) -> anyhow::Result<Utf8PathBuf> {
+    #[cfg(feature = "cargo-metadata")]
+    {
+        use std::{fs::File, io::BufReader};
+        use uniffi_bindgen::cargo_metadata::cdylib_from_messages;
+        if path == "-" {
+            cdylib_from_messages(std::io::stdin().lock(), crate_name)
+        } else {
+            let file = File::open(path).with_context(|| format!("failed to open {path}"))?;
+            cdylib_from_messages(BufReader::new(file), crate_name)
+        }
+    }
+    #[cfg(not(feature = "cargo-metadata"))]
+    {
+        let _ = (path, crate_name);
+        bail!(
+            "--from-cargo-messages is only supported when uniffi-bindgen is built with the \
+             `cargo-metadata` feature of the `uniffi` crate"
+        )
+    }
+}
 
+// The supplier of each crate's `uniffi.toml` and UDL files, for library mode
+fn config_supplier(
+    metadata_no_deps: bool,
+) -> anyhow::Result<Box<dyn uniffi_bindgen::BindgenCrateConfigSupplier>> {
     #[cfg(feature = "cargo-metadata")]
-    let config_supplier = {
+    {
         use uniffi_bindgen::cargo_metadata::CrateConfigSupplier;
         let mut cmd = cargo_metadata::MetadataCommand::new();
         if metadata_no_deps {
             cmd.no_deps();
         }
         let metadata = cmd.exec().context("error running cargo metadata")?;
-        CrateConfigSupplier::from(metadata)
-    };
+        Ok(Box::new(CrateConfigSupplier::from(metadata)))
+    }
     #[cfg(not(feature = "cargo-metadata"))]
-    let config_supplier = uniffi_bindgen::EmptyCrateConfigSupplier;
+    {
+        let _ = metadata_no_deps;
+        Ok(Box::new(uniffi_bindgen::EmptyCrateConfigSupplier))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gen_library_mode(
+    library_path: &camino::Utf8Path,
+    crate_name: Option<String>,
+    languages: Vec<TargetLanguage>,
+    config_overrides: &ConfigOverrides,
+    out_dirs: &OutDirs,
+    fmt: bool,
+    metadata_no_deps: bool,
+    jobs: Option<usize>,
+    depfile: Option<&camino::Utf8Path>,
+    version_check: bool,
+    omit_crates: Vec<String>,
+    include_tags: Option<Vec<String>>,
+    with_dependencies: bool,
+    verbose: bool,
+    clean: Option<Clean>,
+) -> anyhow::Result<Depfile> {
+    use uniffi_bindgen::{
+        clean::OutDirCleaner,
+        depfile::{DirSnapshot, RecordingConfigSupplier},
+        external_generator::ExternalGeneratorArgs,
+        library_mode::{generate_bindings_multi, GenerateOptions},
+    };
+
+    let config_supplier = config_supplier(metadata_no_deps)?;
+    let config_supplier = RecordingConfigSupplier::new(config_supplier.as_ref());
+    // The files written by the builtin generators
+    let mut written_files = vec![];
+    // External generators don't say which files they write, so those are found by comparing a
+    // snapshot of their output directory, taken before any bindings are written to it, with the
+    // files afterwards.
+    let mut snapshots: Vec<DirSnapshot> = vec![];
+    let mut metadata_json = None;
+    let mut reported_skipped_crates = false;
+    let mut cleaner = clean.map(|_| OutDirCleaner::new());
 
     for language in languages {
         // to help avoid mistakes we check the library is actually a cdylib, except
-        // for swift where static libs are often used to extract the metadata.
-        if !matches!(language, TargetLanguage::Swift) && !uniffi_bindgen::is_cdylib(library_path) {
+        // for swift and C headers where static libs are often used to extract the metadata.
+        if !matches!(language, TargetLanguage::Swift | TargetLanguage::C)
+            && !uniffi_bindgen::is_cdylib(library_path)
+        {
             anyhow::bail!(
                 "Generate bindings for {language} requires a cdylib, but {library_path} was given"
             );
         }
 
+        let out_dir = &out_dirs.for_language(&language).with_context(|| {
+            format!("--out-dir is required for {language} when using --library")
+        })?;
+        if matches!(language, TargetLanguage::External(_))
+            && !snapshots.iter().any(|s| s.dir() == out_dir)
+        {
+            snapshots.push(DirSnapshot::new(out_dir)?);
+        }
+        let cfos = config_overrides.for_language(&language);
+        let options = GenerateOptions {
+            crate_name: crate_name.clone(),
+            config_file_overrides: &cfos,
+            try_format_code: fmt,
+            jobs,
+            version_check,
+            omit_crates: &omit_crates,
+            include_tags: include_tags.as_deref(),
+            with_dependencies,
+            cleaner: cleaner.as_mut(),
+            written_files: Some(&mut written_files),
+        };
         // Type-bounds on trait implementations makes selecting between languages a bit tedious.
-        match language {
-            TargetLanguage::Kotlin => generate_bindings(
-                library_path,
-                crate_name.clone(),
+        let generated = match language {
+            TargetLanguage::Kotlin => Some(crate_names(generate_bindings_multi(
+                &[library_path],
                 &KotlinBindingGenerator,
                 &config_supplier,
-                cfo,
                 out_dir,
-                fmt,
-            )?
-            .len(),
-            TargetLanguage::Python => generate_bindings(
-                library_path,
-                crate_name.clone(),
+                options,
+            )?)),
+            TargetLanguage::Python => Some(crate_names(generate_bindings_multi(
+                &[library_path],
                 &PythonBindingGenerator,
                 &config_supplier,
-                cfo,
                 out_dir,
-                fmt,
-            )?
-            .len(),
-            TargetLanguage::Ruby => generate_bindings(
-                library_path,
-                crate_name.clone(),
+                options,
+            )?)),
+            TargetLanguage::Ruby => Some(crate_names(generate_bindings_multi(
+                &[library_path],
                 &RubyBindingGenerator,
                 &config_supplier,
-                cfo,
                 out_dir,
-                fmt,
-            )?
-            .len(),
-            TargetLanguage::Swift => generate_bindings(
-                library_path,
-                crate_name.clone(),
+                options,
+            )?)),
+            TargetLanguage::Swift => Some(crate_names(generate_bindings_multi(
+                &[library_path],
                 &SwiftBindingGenerator,
                 &config_supplier,
-                cfo,
                 out_dir,
-                fmt,
-            )?
-            .len(),
+                options,
+            )?)),
+            TargetLanguage::C => Some(crate_names(generate_bindings_multi(
+                &[library_path],
+                &CBindingGenerator,
+                &config_supplier,
+                out_dir,
+                options,
+            )?)),
+            TargetLanguage::External(generator) => {
+                let metadata_json = match &metadata_json {
+                    Some(json) => json,
+                    None => metadata_json
+                        .insert(uniffi_bindgen::library_mode::metadata_json(library_path)?),
+                };
+                std::fs::create_dir_all(out_dir)?;
+                generator.generate(&ExternalGeneratorArgs {
+                    library_path,
+                    metadata_json,
+                    out_dir,
+                    crate_name: crate_name.as_deref(),
+                    config_file_overrides: &cfos,
+                    omit_crates: &omit_crates,
+                    include_tags: include_tags.as_deref(),
+                    with_dependencies,
+                    try_format_code: fmt,
+                })?;
+                // The generator decides which crates to generate bindings for.
+                None
+            }
         };
+        // The skipped crates are the same for each language, so they're only reported once.
+        if verbose && with_dependencies && !reported_skipped_crates {
+            if let (Some(crate_name), Some(generated)) = (&crate_name, generated) {
+                report_skipped_crates(library_path, crate_name, &generated)?;
+                reported_skipped_crates = true;
+            }
+        }
+    }
+
+    if let (Some(cleaner), Some(clean)) = (&cleaner, clean) {
+        clean_out_dir(cleaner, clean)?;
+    }
+
+    let mut recorded = Depfile::new();
+    for output in written_files {
+        recorded.add_output(output);
+    }
+    for snapshot in &snapshots {
+        for output in snapshot.changed_files()? {
+            recorded.add_output(output);
+        }
+    }
+    recorded.add_input(library_path);
+    for cfo in config_overrides
+        .global
+        .iter()
+        .chain(config_overrides.languages.values())
+    {
+        recorded.add_input(cfo);
+    }
+    config_supplier.add_inputs_to(&mut recorded);
+    if let Some(path) = depfile {
+        recorded.write(path)?;
+    }
+    Ok(recorded)
+}
+
+#[cfg(feature = "watch")]
+fn watch_library_mode(
+    library_path: &camino::Utf8Path,
+    generate: impl FnMut() -> anyhow::Result<Depfile>,
+) -> anyhow::Result<()> {
+    uniffi_bindgen::watch::watch_and_generate(library_path, generate)
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch_library_mode(
+    _library_path: &camino::Utf8Path,
+    _generate: impl FnMut() -> anyhow::Result<Depfile>,
+) -> anyhow::Result<()> {
+    bail!(
+        "--watch is only supported when uniffi-bindgen is built with the `watch` feature of the \
+         `uniffi` crate"
+    )
+}
+
+fn crate_names<Config>(components: Vec<uniffi_bindgen::Component<Config>>) -> BTreeSet<String> {
+    components
+        .iter()
+        .map(|c| c.ci.crate_name().to_string())
+        .collect()
+}
+
+// Print the crates in the library which `--with-dependencies` didn't generate bindings for
+fn report_skipped_crates(
+    library_path: &camino::Utf8Path,
+    crate_name: &str,
+    generated: &BTreeSet<String>,
+) -> anyhow::Result<()> {
+    let skipped: Vec<_> = uniffi_bindgen::library_mode::metadata_groups(library_path)?
+        .into_iter()
+        .map(|group| group.namespace.crate_name)
+        .filter(|name| !generated.contains(name))
+        .collect();
+    if !skipped.is_empty() {
+        println!(
+            "Skipped crates which `{crate_name}` doesn't use: {}",
+            skipped.join(", ")
+        );
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn gen_bindings(
     udl_file: &camino::Utf8Path,
     cfo: Option<&camino::Utf8Path>,
     languages: Vec<TargetLanguage>,
-    odo: Option<&camino::Utf8Path>,
+    out_dirs: &OutDirs,
     library_file: Option<&camino::Utf8Path>,
     crate_name: Option<&str>,
     fmt: bool,
+    depfile: Option<&camino::Utf8Path>,
+    clean: Option<Clean>,
 ) -> anyhow::Result<()> {
-    use uniffi_bindgen::generate_bindings;
+    use uniffi_bindgen::{clean::OutDirCleaner, generate_external_bindings};
+    let mut recorded = depfile.map(|_| Depfile::new());
+    let mut cleaner = clean.map(|_| OutDirCleaner::new());
     for language in languages {
+        let odo = out_dirs.for_language(&language);
+        let odo = odo.as_deref();
         match language {
-            TargetLanguage::Kotlin => generate_bindings(
-                udl_file,
-                cfo,
-                KotlinBindingGenerator,
-                odo,
-                library_file,
-                crate_name,
-                fmt,
-            )?,
-            TargetLanguage::Python => generate_bindings(
-                udl_file,
-                cfo,
-                PythonBindingGenerator,
-                odo,
-                library_file,
-                crate_name,
-                fmt,
-            )?,
-            TargetLanguage::Ruby => generate_bindings(
-                udl_file,
-                cfo,
-                RubyBindingGenerator,
-                odo,
-                library_file,
-                crate_name,
-                fmt,
-            )?,
-            TargetLanguage::Swift => generate_bindings(
-                udl_file,
-                cfo,
-                SwiftBindingGenerator,
-                odo,
-                library_file,
-                crate_name,
-                fmt,
-            )?,
+            TargetLanguage::Kotlin => {
+                generate_external_bindings(
+                    &KotlinBindingGenerator,
+                    udl_file,
+                    cfo,
+                    odo,
+                    library_file,
+                    crate_name,
+                    fmt,
+                    recorded.as_mut(),
+                    cleaner.as_mut(),
+                )?;
+            }
+            TargetLanguage::Python => {
+                generate_external_bindings(
+                    &PythonBindingGenerator,
+                    udl_file,
+                    cfo,
+                    odo,
+                    library_file,
+                    crate_name,
+                    fmt,
+                    recorded.as_mut(),
+                    cleaner.as_mut(),
+                )?;
+            }
+            TargetLanguage::Ruby => {
+                generate_external_bindings(
+                    &RubyBindingGenerator,
+                    udl_file,
+                    cfo,
+                    odo,
+                    library_file,
+                    crate_name,
+                    fmt,
+                    recorded.as_mut(),
+                    cleaner.as_mut(),
+                )?;
+            }
+            TargetLanguage::Swift => {
+                generate_external_bindings(
+                    &SwiftBindingGenerator,
+                    udl_file,
+                    cfo,
+                    odo,
+                    library_file,
+                    crate_name,
+                    fmt,
+                    recorded.as_mut(),
+                    cleaner.as_mut(),
+                )?;
+            }
+            TargetLanguage::C => {
+                generate_external_bindings(
+                    &CBindingGenerator,
+                    udl_file,
+                    cfo,
+                    odo,
+                    library_file,
+                    crate_name,
+                    fmt,
+                    recorded.as_mut(),
+                    cleaner.as_mut(),
+                )?;
+            }
+            TargetLanguage::External(_) => {
+                bail!("{language} bindings can only be generated with `--library`")
+            }
         };
     }
+    if let (Some(cleaner), Some(clean)) = (&cleaner, clean) {
+        clean_out_dir(cleaner, clean)?;
+    }
+    if let (Some(path), Some(recorded)) = (depfile, recorded) {
+        recorded.write(path)?;
+    }
+    Ok(())
+}
+
+/// What `--clean` and `--clean-dry-run` do with stale bindings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Clean {
+    Remove,
+    DryRun,
+}
+
+// Remove the stale bindings found by `cleaner`, or just list them for a dry run
+fn clean_out_dir(
+    cleaner: &uniffi_bindgen::clean::OutDirCleaner,
+    clean: Clean,
+) -> anyhow::Result<()> {
+    match clean {
+        Clean::Remove => {
+            for path in cleaner.remove_stale_files()? {
+                println!("Removed stale file {path}");
+            }
+        }
+        Clean::DryRun => {
+            for path in cleaner.stale_files()? {
+                println!("Would remove stale file {path}");
+            }
+        }
+    }
     Ok(())
 }
 
@@ -284,37 +788,103 @@ pub fn run_main() -> anyhow::Result<()> {
             source,
             crate_name,
             library_mode,
+            from_cargo_messages,
             metadata_no_deps,
+            jobs,
+            depfile,
+            no_version_check,
+            omit_crates,
+            include_tags,
+            with_dependencies,
+            verbose,
+            watch,
+            clean,
+            clean_dry_run,
+            message_format,
         } => {
+            uniffi_bindgen::diagnostics::set_message_format(message_format);
+            let clean = match (clean, clean_dry_run) {
+                (true, _) => Some(Clean::Remove),
+                (false, true) => Some(Clean::DryRun),
+                (false, false) => None,
+            };
+            let (library_mode, source) = if from_cargo_messages {
+                (
+                    true,
+                    library_from_cargo_messages(&source, crate_name.as_deref())?,
+                )
+            } else {
+                (library_mode, source)
+            };
             if library_mode {
                 if lib_file.is_some() {
                     panic!("--lib-file is not compatible with --library.")
                 }
-                let out_dir = out_dir.expect("--out-dir is required when using --library");
                 if language.is_empty() {
                     panic!("please specify at least one language with --language")
                 }
-                gen_library_mode(
-                    &source,
-                    crate_name,
-                    language,
-                    config.as_deref(),
-                    &out_dir,
-                    !no_format,
-                    metadata_no_deps,
-                )?;
+                let config_overrides = ConfigOverrides::new(config)?;
+                let out_dirs = OutDirs::new(out_dir)?;
+                let generate = || {
+                    gen_library_mode(
+                        &source,
+                        crate_name.clone(),
+                        language.clone(),
+                        &config_overrides,
+                        &out_dirs,
+                        !no_format,
+                        metadata_no_deps,
+                        jobs,
+                        depfile.as_deref(),
+                        !no_version_check,
+                        omit_crates.clone(),
+                        include_tags.clone(),
+                        with_dependencies,
+                        verbose,
+                        clean,
+                    )
+                };
+                if watch {
+                    watch_library_mode(&source, generate)?;
+                } else {
+                    generate()?;
+                }
             } else {
                 if metadata_no_deps {
                     panic!("--metadata-no-deps makes no sense when not in library mode")
                 }
+                if jobs.is_some() {
+                    panic!("--jobs makes no sense when not in library mode")
+                }
+                if no_version_check {
+                    panic!("--no-version-check makes no sense when not in library mode")
+                }
+                if !omit_crates.is_empty() {
+                    panic!("--omit-crate makes no sense when not in library mode")
+                }
+                if include_tags.is_some() {
+                    panic!("--include-tags makes no sense when not in library mode")
+                }
+                if with_dependencies {
+                    panic!("--with-dependencies makes no sense when not in library mode")
+                }
+                if watch {
+                    panic!("--watch is only supported with --library")
+                }
+                let config = ConfigOverrides::new(config)?;
+                if !config.languages.is_empty() {
+                    bail!("--config <language>=<path> is only supported with --library");
+                }
                 gen_bindings(
                     &source,
-                    config.as_deref(),
+                    config.global.as_deref(),
                     language,
-                    out_dir.as_deref(),
+                    &OutDirs::new(out_dir)?,
                     lib_file.as_deref(),
                     crate_name.as_deref(),
                     !no_format,
+                    depfile.as_deref(),
+                    clean,
                 )?;
             }
         }
@@ -332,6 +902,119 @@ pub fn run_main() -> anyhow::Result<()> {
         Commands::PrintRepr { path } => {
             uniffi_bindgen::print_repr(&path)?;
         }
+        Commands::Diff { old, new } => {
+            let diff = uniffi_bindgen::api_diff::diff_libraries(&old, &new)?;
+            print!("{diff}");
+            if diff.is_breaking() {
+                bail!("The API changes are breaking");
+            }
+        }
+        Commands::Check {
+            metadata_no_deps,
+            path,
+        } => {
+            let config_supplier = config_supplier(metadata_no_deps)?;
+            let report = uniffi_bindgen::check::find_mismatches(&path, config_supplier.as_ref())?;
+            print!("{report}");
+            if !report.is_empty() {
+                bail!("The UDL files don't match the Rust code");
+            }
+        }
+        Commands::PrintJson { out_file, path } => {
+            uniffi_bindgen::print_json(&path, out_file.as_deref())?;
+        }
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_args(values: &[&str]) -> Vec<LanguagePathArg> {
+        values
+            .iter()
+            .map(|value| parse_language_path(value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_language_path() {
+        let arg = parse_language_path("out").unwrap();
+        assert_eq!(arg.language, None);
+        assert_eq!(arg.path, "out");
+        // Aliases are normalized to the language name
+        let arg = parse_language_path("py=out/python").unwrap();
+        assert_eq!(arg.language.as_deref(), Some("python"));
+        assert_eq!(arg.path, "out/python");
+        // An `=` after something which can't be a language name is part of the path
+        let arg = parse_language_path("out/a=b").unwrap();
+        assert_eq!(arg.language, None);
+        assert_eq!(arg.path, "out/a=b");
+        let err = parse_language_path("not-a-real-language=out").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Unknown or unsupported target language: \"not-a-real-language\""));
+    }
+
+    #[test]
+    fn test_split_language_paths() {
+        let (global, languages) =
+            split_language_paths(parse_args(&["out", "kotlin=kt", "swift=sw"]), "--out-dir")
+                .unwrap();
+        assert_eq!(global.as_deref(), Some("out".into()));
+        assert_eq!(languages.len(), 2);
+        assert_eq!(languages["kotlin"], "kt");
+        assert_eq!(languages["swift"], "sw");
+
+        let err = split_language_paths(parse_args(&["a", "b"]), "--out-dir").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--out-dir can only be passed once without a language"
+        );
+        // Aliases count as the same language
+        let err = split_language_paths(parse_args(&["kt=a", "kotlin=b"]), "--config").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--config can only be passed once for kotlin"
+        );
+    }
+
+    #[test]
+    fn test_out_dirs() {
+        let out_dirs = OutDirs::new(parse_args(&["bindings/{language}", "swift=ios"])).unwrap();
+        assert_eq!(
+            out_dirs.for_language(&TargetLanguage::Kotlin).as_deref(),
+            Some("bindings/kotlin".into())
+        );
+        // The language's own directory takes precedence over the global one
+        assert_eq!(
+            out_dirs.for_language(&TargetLanguage::Swift).as_deref(),
+            Some("ios".into())
+        );
+
+        let out_dirs = OutDirs::new(parse_args(&["python=py"])).unwrap();
+        assert_eq!(
+            out_dirs.for_language(&TargetLanguage::Python).as_deref(),
+            Some("py".into())
+        );
+        assert_eq!(out_dirs.for_language(&TargetLanguage::Ruby), None);
+
+        assert!(OutDirs::new(parse_args(&["a", "b"])).is_err());
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let overrides =
+            ConfigOverrides::new(parse_args(&["uniffi.toml", "kotlin=kt.toml"])).unwrap();
+        assert_eq!(
+            overrides.for_language(&TargetLanguage::Kotlin),
+            ["uniffi.toml", "kt.toml"]
+        );
+        assert_eq!(
+            overrides.for_language(&TargetLanguage::Python),
+            ["uniffi.toml"]
+        );
+        assert!(ConfigOverrides::new(parse_args(&["c=a", "h=b"])).is_err());
+    }
+}
@@ -0,0 +1,956 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Compare the foreign API of two builds of a library.
+//!
+//! This extracts the metadata from an old and a new build of a library, then reports the items
+//! which were added, removed or changed.  Each change is classified as either breaking, meaning
+//! that code using the old bindings may no longer work, or additive.
+//!
+//! Items are matched by name, so the order of items, fields and variants is ignored, as are
+//! docstrings and checksums.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use camino::Utf8Path;
+use uniffi_meta::{
+    describe_item, literal_name, same_type, type_name, FieldMetadata, FnParamMetadata,
+    LiteralMetadata, Metadata, MetadataGroup, ObjectImpl, Type,
+};
+
+use crate::{library_mode, Result};
+
+/// The differences between the APIs of two libraries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiDiff {
+    /// The crates with changes, sorted by crate name
+    pub crates: Vec<CrateDiff>,
+}
+
+/// The changes to a single crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateDiff {
+    pub crate_name: String,
+    pub changes: Vec<ApiChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    /// The item which changed, for example "function `get_foo`"
+    pub item: String,
+    /// What happened to it, for example "was removed"
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    /// Code using the old bindings may not work with the new ones
+    Breaking,
+    /// Code using the old bindings will still work
+    Additive,
+}
+
+impl ApiDiff {
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty()
+    }
+
+    /// Are any of the changes breaking?
+    pub fn is_breaking(&self) -> bool {
+        self.changes()
+            .any(|change| change.kind == ChangeKind::Breaking)
+    }
+
+    /// Iterate over all changes, across all crates
+    pub fn changes(&self) -> impl Iterator<Item = &ApiChange> {
+        self.crates.iter().flat_map(|c| c.changes.iter())
+    }
+}
+
+impl fmt::Display for ApiDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No API changes");
+        }
+        for crate_diff in &self.crates {
+            writeln!(f, "crate `{}`:", crate_diff.crate_name)?;
+            for change in &crate_diff.changes {
+                writeln!(f, "  {change}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ChangeKind::Breaking => "breaking",
+            ChangeKind::Additive => "additive",
+        };
+        write!(f, "{kind}: {} {}", self.item, self.description)
+    }
+}
+
+/// Compare the APIs of two builds of a shared library
+pub fn diff_libraries(old_library: &Utf8Path, new_library: &Utf8Path) -> Result<ApiDiff> {
+    Ok(diff_metadata_groups(
+        &library_mode::metadata_groups(old_library)?,
+        &library_mode::metadata_groups(new_library)?,
+    ))
+}
+
+/// Compare two sets of metadata groups, matching them by crate name
+pub fn diff_metadata_groups(old: &[MetadataGroup], new: &[MetadataGroup]) -> ApiDiff {
+    let old: BTreeMap<_, _> = old
+        .iter()
+        .map(|g| (g.namespace.crate_name.as_str(), g))
+        .collect();
+    let new: BTreeMap<_, _> = new
+        .iter()
+        .map(|g| (g.namespace.crate_name.as_str(), g))
+        .collect();
+    let crate_names: BTreeSet<_> = old.keys().chain(new.keys()).copied().collect();
+
+    let crates = crate_names
+        .into_iter()
+        .filter_map(|crate_name| {
+            let mut changes = Changes::default();
+            let item = format!("crate `{crate_name}`");
+            match (old.get(crate_name), new.get(crate_name)) {
+                (Some(old), Some(new)) => diff_group(&mut changes, old, new),
+                (Some(_), None) => changes.breaking(item, "was removed"),
+                (None, Some(_)) => changes.additive(item, "was added"),
+                (None, None) => unreachable!(),
+            }
+            (!changes.0.is_empty()).then(|| CrateDiff {
+                crate_name: crate_name.to_string(),
+                changes: changes.0,
+            })
+        })
+        .collect();
+    ApiDiff { crates }
+}
+
+#[derive(Default)]
+struct Changes(Vec<ApiChange>);
+
+impl Changes {
+    fn push(&mut self, kind: ChangeKind, item: impl Into<String>, description: impl Into<String>) {
+        self.0.push(ApiChange {
+            kind,
+            item: item.into(),
+            description: description.into(),
+        })
+    }
+
+    fn breaking(&mut self, item: impl Into<String>, description: impl Into<String>) {
+        self.push(ChangeKind::Breaking, item, description)
+    }
+
+    fn additive(&mut self, item: impl Into<String>, description: impl Into<String>) {
+        self.push(ChangeKind::Additive, item, description)
+    }
+}
+
+fn diff_group(changes: &mut Changes, old: &MetadataGroup, new: &MetadataGroup) {
+    if old.namespace.name != new.namespace.name {
+        changes.breaking(
+            format!("namespace `{}`", old.namespace.name),
+            format!("was renamed to `{}`", new.namespace.name),
+        );
+    }
+
+    let old_items = api_items(old);
+    let new_items = api_items(new);
+    let descriptions: BTreeSet<_> = old_items.keys().chain(new_items.keys()).collect();
+    for description in descriptions {
+        match (old_items.get(description), new_items.get(description)) {
+            (Some(old_item), Some(new_item)) => {
+                diff_item(changes, description, old_item, new_item);
+                if has_default(old_item) && is_required_trait_method(new, new_item) {
+                    changes.breaking(
+                        description,
+                        "lost its default implementation, so foreign implementations of the \
+                         trait must implement it",
+                    );
+                }
+            }
+            (Some(_), None) => changes.breaking(description, "was removed"),
+            (None, Some(new_item)) if is_required_trait_method(new, new_item) => changes.breaking(
+                description,
+                "was added, so foreign implementations of the trait must implement it",
+            ),
+            (None, Some(_)) => changes.additive(description, "was added"),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+// The items in a group which are part of the foreign API, keyed by their description
+fn api_items(group: &MetadataGroup) -> BTreeMap<String, &Metadata> {
+    group
+        .items
+        .iter()
+        .filter(|item| {
+            !matches!(
+                item,
+                Metadata::Namespace(_)
+                    | Metadata::NamespaceDocstring(_)
+                    | Metadata::ContractVersion(_)
+                    | Metadata::UdlFile(_)
+            )
+        })
+        .map(|item| (describe_item(item), item))
+        .collect()
+}
+
+// Is this a method on a trait which can be implemented by foreign code, which foreign
+// implementations must implement?
+fn is_required_trait_method(group: &MetadataGroup, item: &Metadata) -> bool {
+    let Metadata::TraitMethod(meta) = item else {
+        return false;
+    };
+    if meta.has_default {
+        return false;
+    }
+    group.items.iter().any(|other| match other {
+        Metadata::CallbackInterface(cbi) => cbi.name == meta.trait_name,
+        Metadata::Object(obj) => obj.name == meta.trait_name && obj.imp.has_callback_interface(),
+        _ => false,
+    })
+}
+
+fn has_default(item: &Metadata) -> bool {
+    matches!(item, Metadata::TraitMethod(meta) if meta.has_default)
+}
+
+fn diff_item(changes: &mut Changes, description: &str, old: &Metadata, new: &Metadata) {
+    match (old, new) {
+        (Metadata::Func(old), Metadata::Func(new)) => diff_callable(
+            changes,
+            description,
+            Callable::new(
+                old.is_async,
+                &old.inputs,
+                old.return_type.as_ref(),
+                old.throws.as_ref(),
+            ),
+            Callable::new(
+                new.is_async,
+                &new.inputs,
+                new.return_type.as_ref(),
+                new.throws.as_ref(),
+            ),
+        ),
+        (Metadata::Constructor(old), Metadata::Constructor(new)) => diff_callable(
+            changes,
+            description,
+            Callable::new(old.is_async, &old.inputs, None, old.throws.as_ref()),
+            Callable::new(new.is_async, &new.inputs, None, new.throws.as_ref()),
+        ),
+        // Static methods are called on the class rather than on an object.
+        (Metadata::Method(old), Metadata::Method(new)) if old.is_static != new.is_static => changes
+            .breaking(
+                description,
+                if new.is_static {
+                    "became a static method"
+                } else {
+                    "is no longer a static method"
+                },
+            ),
+        // Getters are properties in the bindings, rather than functions.
+        (Metadata::Method(old), Metadata::Method(new)) if old.is_getter != new.is_getter => changes
+            .breaking(
+                description,
+                if new.is_getter {
+                    "became a field getter"
+                } else {
+                    "is no longer a field getter"
+                },
+            ),
+        (Metadata::Method(old), Metadata::Method(new)) => diff_callable(
+            changes,
+            description,
+            Callable::new(
+                old.is_async,
+                &old.inputs,
+                old.return_type.as_ref(),
+                old.throws.as_ref(),
+            ),
+            Callable::new(
+                new.is_async,
+                &new.inputs,
+                new.return_type.as_ref(),
+                new.throws.as_ref(),
+            ),
+        ),
+        (Metadata::TraitMethod(old), Metadata::TraitMethod(new)) => diff_callable(
+            changes,
+            description,
+            Callable::new(
+                old.is_async,
+                &old.inputs,
+                old.return_type.as_ref(),
+                old.throws.as_ref(),
+            ),
+            Callable::new(
+                new.is_async,
+                &new.inputs,
+                new.return_type.as_ref(),
+                new.throws.as_ref(),
+            ),
+        ),
+        (Metadata::Record(old), Metadata::Record(new)) => {
+            diff_fields(changes, description, "", &old.fields, &new.fields)
+        }
+        (Metadata::Enum(old), Metadata::Enum(new)) => {
+            if old.shape != new.shape {
+                changes.breaking(
+                    description,
+                    format!("changed from {:?} to {:?}", old.shape, new.shape),
+                );
+            }
+            if old.discr_type != new.discr_type {
+                changes.breaking(description, "changed its discriminant type");
+            }
+            for old_variant in &old.variants {
+                let Some(new_variant) = new.variants.iter().find(|v| v.name == old_variant.name)
+                else {
+                    changes.breaking(description, format!("lost variant `{}`", old_variant.name));
+                    continue;
+                };
+                if old_variant.discr != new_variant.discr {
+                    changes.breaking(
+                        description,
+                        format!("changed the discriminant of variant `{}`", old_variant.name),
+                    );
+                }
+                diff_fields(
+                    changes,
+                    description,
+                    &format!("variant `{}` ", old_variant.name),
+                    &old_variant.fields,
+                    &new_variant.fields,
+                );
+            }
+            for new_variant in &new.variants {
+                if old.variants.iter().all(|v| v.name != new_variant.name) {
+                    let description_of_change = format!("gained variant `{}`", new_variant.name);
+                    // Foreign code which exhaustively matches on the enum will break, unless it
+                    // was already required to handle unknown variants.
+                    if new.non_exhaustive {
+                        changes.additive(description, description_of_change);
+                    } else {
+                        changes.breaking(description, description_of_change);
+                    }
+                }
+            }
+        }
+        (Metadata::Object(old), Metadata::Object(new)) if old.imp != new.imp => changes.breaking(
+            description,
+            format!(
+                "changed from {} to {}",
+                describe_object_impl(old.imp),
+                describe_object_impl(new.imp)
+            ),
+        ),
+        (Metadata::CustomType(old), Metadata::CustomType(new))
+            if !same_type(&old.builtin, &new.builtin) =>
+        {
+            changes.breaking(
+                description,
+                format!(
+                    "changed its builtin type from `{}` to `{}`",
+                    type_name(&old.builtin),
+                    type_name(&new.builtin)
+                ),
+            )
+        }
+        (Metadata::Constant(old), Metadata::Constant(new)) => diff_constant(
+            changes,
+            description,
+            (&old.ty, &old.value),
+            (&new.ty, &new.value),
+        ),
+        (Metadata::TraitConstant(old), Metadata::TraitConstant(new)) => diff_constant(
+            changes,
+            description,
+            (&old.ty, &old.value),
+            (&new.ty, &new.value),
+        ),
+        // Callback interfaces and uniffi traits don't have anything to compare apart from their
+        // names, their methods are separate items.  Unchanged objects and custom types also end
+        // up here.
+        _ => (),
+    }
+}
+
+fn diff_constant(
+    changes: &mut Changes,
+    description: &str,
+    (old_ty, old_value): (&Type, &LiteralMetadata),
+    (new_ty, new_value): (&Type, &LiteralMetadata),
+) {
+    if !same_type(old_ty, new_ty) {
+        changes.breaking(
+            description,
+            format!(
+                "changed its type from `{}` to `{}`",
+                type_name(old_ty),
+                type_name(new_ty)
+            ),
+        )
+    } else if old_value != new_value {
+        // Foreign compilers can inline constants, so code built against the old value keeps
+        // using it.
+        changes.breaking(
+            description,
+            format!(
+                "changed its value from `{}` to `{}`",
+                literal_name(old_value),
+                literal_name(new_value)
+            ),
+        )
+    }
+}
+
+// The parts of a function, method or constructor which affect how it's called
+struct Callable<'a> {
+    is_async: bool,
+    inputs: &'a [FnParamMetadata],
+    return_type: Option<&'a Type>,
+    throws: Option<&'a Type>,
+}
+
+impl<'a> Callable<'a> {
+    fn new(
+        is_async: bool,
+        inputs: &'a [FnParamMetadata],
+        return_type: Option<&'a Type>,
+        throws: Option<&'a Type>,
+    ) -> Self {
+        Self {
+            is_async,
+            inputs,
+            return_type,
+            throws,
+        }
+    }
+
+    fn signature(&self) -> String {
+        let params = self
+            .inputs
+            .iter()
+            .map(|p| format!("{}: {}", p.name, type_name(&p.ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let return_type = self.return_type.map(type_name);
+        let return_type = match (return_type, self.throws) {
+            (None, None) => String::new(),
+            (Some(ty), None) => format!(" -> {ty}"),
+            (ty, Some(throws)) => format!(
+                " -> Result<{}, {}>",
+                ty.unwrap_or_else(|| "()".to_string()),
+                type_name(throws)
+            ),
+        };
+        let prefix = if self.is_async { "async fn" } else { "fn" };
+        format!("{prefix}({params}){return_type}")
+    }
+}
+
+fn diff_callable(changes: &mut Changes, description: &str, old: Callable<'_>, new: Callable<'_>) {
+    let same_optional_type = |old: Option<&Type>, new: Option<&Type>| match (old, new) {
+        (Some(old), Some(new)) => same_type(old, new),
+        (old, new) => old.is_none() && new.is_none(),
+    };
+    let same_params =
+        |a: &FnParamMetadata, b: &FnParamMetadata| a.name == b.name && same_type(&a.ty, &b.ty);
+    let compatible = old.is_async == new.is_async
+        && same_optional_type(old.return_type, new.return_type)
+        && same_optional_type(old.throws, new.throws)
+        && old.inputs.len() <= new.inputs.len()
+        && old
+            .inputs
+            .iter()
+            .zip(new.inputs)
+            .all(|(a, b)| same_params(a, b))
+        // Callers can only leave out parameters which have defaults.
+        && new.inputs[old.inputs.len()..]
+            .iter()
+            .all(|p| p.default.is_some());
+    if !compatible {
+        changes.breaking(
+            description,
+            format!(
+                "changed its signature from `{}` to `{}`",
+                old.signature(),
+                new.signature()
+            ),
+        );
+        return;
+    }
+
+    for (old_param, new_param) in old.inputs.iter().zip(new.inputs) {
+        match (&old_param.default, &new_param.default) {
+            (Some(_), None) => changes.breaking(
+                description,
+                format!("parameter `{}` no longer has a default", new_param.name),
+            ),
+            (None, Some(_)) => changes.additive(
+                description,
+                format!("parameter `{}` now has a default", new_param.name),
+            ),
+            _ => (),
+        }
+    }
+    for new_param in &new.inputs[old.inputs.len()..] {
+        changes.additive(
+            description,
+            format!("gained parameter `{}` with a default", new_param.name),
+        );
+    }
+}
+
+// Compare the fields of a record or enum variant
+//
+// `prefix` describes where the fields are, for example "variant `Foo` ".
+fn diff_fields(
+    changes: &mut Changes,
+    description: &str,
+    prefix: &str,
+    old: &[FieldMetadata],
+    new: &[FieldMetadata],
+) {
+    for old_field in old {
+        let name = &old_field.name;
+        let Some(new_field) = new.iter().find(|f| &f.name == name) else {
+            changes.breaking(description, format!("{prefix}lost field `{name}`"));
+            continue;
+        };
+        if !same_type(&old_field.ty, &new_field.ty) {
+            changes.breaking(
+                description,
+                format!(
+                    "{prefix}field `{name}` changed type from `{}` to `{}`",
+                    type_name(&old_field.ty),
+                    type_name(&new_field.ty)
+                ),
+            );
+        }
+        match (&old_field.default, &new_field.default) {
+            (Some(_), None) => changes.breaking(
+                description,
+                format!("{prefix}field `{name}` no longer has a default"),
+            ),
+            (None, Some(_)) => changes.additive(
+                description,
+                format!("{prefix}field `{name}` now has a default"),
+            ),
+            _ => (),
+        }
+    }
+    for new_field in new {
+        let name = &new_field.name;
+        if old.iter().all(|f| &f.name != name) {
+            if new_field.default.is_some() {
+                changes.additive(
+                    description,
+                    format!("{prefix}gained field `{name}` with a default"),
+                );
+            } else {
+                changes.breaking(
+                    description,
+                    format!("{prefix}gained field `{name}` without a default"),
+                );
+            }
+        }
+    }
+}
+
+fn describe_object_impl(imp: ObjectImpl) -> &'static str {
+    match imp {
+        ObjectImpl::Struct => "a struct",
+        ObjectImpl::Trait => "a trait",
+        ObjectImpl::CallbackTrait => "a foreign-implementable trait",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{
+        EnumMetadata, EnumShape, FnMetadata, LiteralMetadata, MethodMetadata, NamespaceMetadata,
+        RecordMetadata, TraitMethodMetadata, VariantMetadata,
+    };
+
+    fn group(crate_name: &str, items: Vec<Metadata>) -> MetadataGroup {
+        MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: crate_name.to_string(),
+                name: crate_name.to_string(),
+            },
+            namespace_docstring: None,
+            items: items.into_iter().collect(),
+        }
+    }
+
+    fn func(name: &str, inputs: Vec<FnParamMetadata>, return_type: Option<Type>) -> Metadata {
+        FnMetadata {
+            module_path: "crate_a".to_string(),
+            name: name.to_string(),
+            is_async: false,
+            inputs,
+            return_type,
+            throws: None,
+            checksum: Some(1),
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        }
+        .into()
+    }
+
+    fn field(name: &str, ty: Type, default: Option<LiteralMetadata>) -> FieldMetadata {
+        FieldMetadata {
+            name: name.to_string(),
+            ty,
+            default,
+            is_source: false,
+            docstring: None,
+            deprecated: None,
+        }
+    }
+
+    fn record(fields: Vec<FieldMetadata>) -> Metadata {
+        RecordMetadata {
+            module_path: "crate_a".to_string(),
+            name: "Rec".to_string(),
+            fields,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        }
+        .into()
+    }
+
+    fn error_enum(variants: &[&str], non_exhaustive: bool) -> Metadata {
+        EnumMetadata {
+            module_path: "crate_a".to_string(),
+            name: "MyError".to_string(),
+            shape: EnumShape::Error { flat: true },
+            variants: variants
+                .iter()
+                .map(|name| VariantMetadata {
+                    name: name.to_string(),
+                    discr: None,
+                    fields: vec![],
+                    docstring: None,
+                    deprecated: None,
+                })
+                .collect(),
+            discr_type: None,
+            non_exhaustive,
+            docstring: None,
+            tag: None,
+        }
+        .into()
+    }
+
+    fn descriptions(diff: &ApiDiff) -> Vec<String> {
+        diff.changes().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let mut documented = func("get", vec![], Some(Type::UInt32));
+        if let Metadata::Func(meta) = &mut documented {
+            meta.docstring = Some("Docs".to_string());
+            meta.checksum = Some(2);
+        }
+        let old = [group(
+            "crate_a",
+            vec![func("get", vec![], Some(Type::UInt32))],
+        )];
+        let new = [group("crate_a", vec![documented])];
+        let diff = diff_metadata_groups(&old, &new);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No API changes\n");
+    }
+
+    #[test]
+    fn test_added_and_removed_items() {
+        let old = [
+            group("crate_a", vec![func("old_fn", vec![], None)]),
+            group("crate_b", vec![]),
+        ];
+        let new = [
+            group("crate_a", vec![func("new_fn", vec![], None)]),
+            group("crate_c", vec![]),
+        ];
+        let diff = diff_metadata_groups(&old, &new);
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            "crate `crate_a`:\n  \
+               additive: function `new_fn` was added\n  \
+               breaking: function `old_fn` was removed\n\
+             crate `crate_b`:\n  \
+               breaking: crate `crate_b` was removed\n\
+             crate `crate_c`:\n  \
+               additive: crate `crate_c` was added\n"
+        );
+    }
+
+    #[test]
+    fn test_function_signatures() {
+        let param = |name: &str, default| FnParamMetadata {
+            default,
+            ..FnParamMetadata::simple(name, Type::UInt32)
+        };
+        let old = [group(
+            "crate_a",
+            vec![
+                func("changed", vec![param("a", None)], None),
+                func("extended", vec![param("a", None)], None),
+            ],
+        )];
+        let new = [group(
+            "crate_a",
+            vec![
+                func("changed", vec![param("a", None)], Some(Type::String)),
+                func(
+                    "extended",
+                    vec![
+                        param("a", Some(LiteralMetadata::new_uint(0))),
+                        param("b", Some(LiteralMetadata::new_uint(1))),
+                    ],
+                    None,
+                ),
+            ],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "breaking: function `changed` changed its signature from `fn(a: u32)` to \
+                 `fn(a: u32) -> String`",
+                "additive: function `extended` parameter `a` now has a default",
+                "additive: function `extended` gained parameter `b` with a default",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_fields() {
+        let old = [group(
+            "crate_a",
+            vec![record(vec![
+                field("a", Type::UInt32, None),
+                field("b", Type::UInt32, None),
+                field("c", Type::UInt32, None),
+            ])],
+        )];
+        // Reordering fields isn't a change.
+        let new = [group(
+            "crate_a",
+            vec![record(vec![
+                field("c", Type::UInt32, None),
+                field("a", Type::String, None),
+                field("d", Type::UInt32, None),
+                field("e", Type::UInt32, Some(LiteralMetadata::new_uint(0))),
+            ])],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "breaking: record `Rec` field `a` changed type from `u32` to `String`",
+                "breaking: record `Rec` lost field `b`",
+                "breaking: record `Rec` gained field `d` without a default",
+                "additive: record `Rec` gained field `e` with a default",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        let old = [group("crate_a", vec![error_enum(&["A", "B"], false)])];
+        let new = [group("crate_a", vec![error_enum(&["A", "C"], false)])];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "breaking: enum `MyError` lost variant `B`",
+                "breaking: enum `MyError` gained variant `C`",
+            ]
+        );
+
+        let old = [group("crate_a", vec![error_enum(&["A"], true)])];
+        let new = [group("crate_a", vec![error_enum(&["A", "B"], true)])];
+        let diff = diff_metadata_groups(&old, &new);
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            descriptions(&diff),
+            vec!["additive: enum `MyError` gained variant `B`"]
+        );
+    }
+
+    #[test]
+    fn test_static_methods() {
+        let method = |name: &str, is_static| -> Metadata {
+            MethodMetadata {
+                module_path: "crate_a".to_string(),
+                self_name: "Obj".to_string(),
+                name: name.to_string(),
+                is_async: false,
+                consumes: false,
+                is_static,
+                is_getter: false,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: None,
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }
+            .into()
+        };
+        let old = [group(
+            "crate_a",
+            vec![method("made_static", false), method("unchanged", true)],
+        )];
+        let new = [group(
+            "crate_a",
+            vec![method("made_static", true), method("unchanged", true)],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec!["breaking: method `Obj.made_static` became a static method"]
+        );
+    }
+
+    #[test]
+    fn test_getters() {
+        let method = |name: &str, is_getter| -> Metadata {
+            MethodMetadata {
+                module_path: "crate_a".to_string(),
+                self_name: "Obj".to_string(),
+                name: name.to_string(),
+                is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter,
+                inputs: vec![],
+                return_type: Some(Type::UInt32),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: None,
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }
+            .into()
+        };
+        let old = [group(
+            "crate_a",
+            vec![method("id", true), method("len", false)],
+        )];
+        let new = [group(
+            "crate_a",
+            vec![method("id", false), method("len", true)],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "breaking: method `Obj.id` is no longer a field getter",
+                "breaking: method `Obj.len` became a field getter",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trait_methods() {
+        let trait_method = |trait_name: &str, name: &str, has_default| -> Metadata {
+            TraitMethodMetadata {
+                module_path: "crate_a".to_string(),
+                trait_name: trait_name.to_string(),
+                index: 0,
+                name: name.to_string(),
+                is_async: false,
+                has_default,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: None,
+                docstring: None,
+                deprecated: None,
+            }
+            .into()
+        };
+        let object = |name: &str, imp| -> Metadata {
+            uniffi_meta::ObjectMetadata {
+                module_path: "crate_a".to_string(),
+                name: name.to_string(),
+                imp,
+                docstring: None,
+                tag: None,
+            }
+            .into()
+        };
+        let old = [group(
+            "crate_a",
+            vec![
+                object("RustTrait", ObjectImpl::Trait),
+                object("ForeignTrait", ObjectImpl::CallbackTrait),
+            ],
+        )];
+        let new = [group(
+            "crate_a",
+            vec![
+                object("RustTrait", ObjectImpl::Trait),
+                object("ForeignTrait", ObjectImpl::CallbackTrait),
+                trait_method("RustTrait", "method", false),
+                trait_method("ForeignTrait", "method", false),
+                trait_method("ForeignTrait", "defaulted", true),
+            ],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "additive: trait method `ForeignTrait.defaulted` was added",
+                "breaking: trait method `ForeignTrait.method` was added, so foreign \
+                 implementations of the trait must implement it",
+                "additive: trait method `RustTrait.method` was added",
+            ]
+        );
+
+        let old = [group(
+            "crate_a",
+            vec![
+                object("ForeignTrait", ObjectImpl::CallbackTrait),
+                trait_method("ForeignTrait", "method", true),
+            ],
+        )];
+        let new = [group(
+            "crate_a",
+            vec![
+                object("ForeignTrait", ObjectImpl::CallbackTrait),
+                trait_method("ForeignTrait", "method", false),
+            ],
+        )];
+        assert_eq!(
+            descriptions(&diff_metadata_groups(&old, &new)),
+            vec![
+                "breaking: trait method `ForeignTrait.method` lost its default implementation, \
+                 so foreign implementations of the trait must implement it",
+            ]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::Result;
+use askama::Template;
+
+use heck::{ToShoutySnakeCase, ToUpperCamelCase};
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+
+use crate::bindings::NameTemplate;
+use crate::interface::*;
+
+// C and C++ keywords, since the header is also used from C++.  These can't be used as the names
+// of arguments or struct fields.
+const RESERVED_WORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "asm",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "nullptr",
+    "operator",
+    "or",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+    "xor",
+];
+
+fn is_reserved_word(word: &str) -> bool {
+    RESERVED_WORDS.contains(&word)
+}
+
+// Some config options for the caller to customize the generated header.
+// Note that this can only be used to control details of the header *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    header_filename: Option<NameTemplate>,
+}
+
+impl Config {
+    /// The filename of the header, `<namespace>.h` by default
+    pub fn header_filename(&self, ci: &ComponentInterface) -> Result<String> {
+        match &self.header_filename {
+            Some(template) => template.render(ci),
+            None => Ok(format!("{}.h", ci.namespace())),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "CHeaderTemplate.h")]
+pub struct CHeader<'ci> {
+    ci: &'ci ComponentInterface,
+}
+
+impl<'ci> CHeader<'ci> {
+    pub fn new(ci: &'ci ComponentInterface) -> Self {
+        Self { ci }
+    }
+}
+
+mod filters {
+    use super::*;
+
+    pub fn ffi_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "int8_t".into(),
+            FfiType::UInt8 => "uint8_t".into(),
+            FfiType::Int16 => "int16_t".into(),
+            FfiType::UInt16 => "uint16_t".into(),
+            FfiType::Int32 => "int32_t".into(),
+            FfiType::UInt32 => "uint32_t".into(),
+            FfiType::Int64 => "int64_t".into(),
+            FfiType::UInt64 => "uint64_t".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::Handle => "uint64_t".into(),
+            FfiType::RustArcPtr(_) => "void*".into(),
+            FfiType::RustBuffer(_) => "RustBuffer".into(),
+            FfiType::RustCallStatus => "RustCallStatus".into(),
+            FfiType::ForeignBytes => "ForeignBytes".into(),
+            FfiType::Callback(name) => ffi_callback_name(name)?,
+            FfiType::Struct(name) => ffi_struct_name(name)?,
+            FfiType::Reference(inner) => format!("{}*", ffi_type_name(inner)?),
+            FfiType::VoidPointer => "void*".into(),
+        })
+    }
+
+    /// The name of an FFI callback typedef, which matches the Swift bridging header
+    pub fn ffi_callback_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("Uniffi{}", nm.to_upper_camel_case()))
+    }
+
+    /// The name of an FFI struct typedef, which matches the Swift bridging header
+    pub fn ffi_struct_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("Uniffi{}", nm.to_upper_camel_case()))
+    }
+
+    /// The name of the guard for an FFI definition, which matches the Swift bridging header
+    pub fn if_guard_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(format!("UNIFFI_FFIDEF_{}", nm.to_shouty_snake_case()))
+    }
+
+    /// The name of an argument or struct field, with a trailing `_` if it's a reserved word
+    pub fn var_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(if is_reserved_word(nm) {
+            format!("{nm}_")
+        } else {
+            nm.to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filters::*;
+    use super::*;
+
+    #[test]
+    fn test_ffi_type_name() {
+        assert_eq!(ffi_type_name(&FfiType::UInt64).unwrap(), "uint64_t");
+        assert_eq!(
+            ffi_type_name(&FfiType::Reference(Box::new(FfiType::Struct(
+                "VTableCallbackInterfaceFoo".into()
+            ))))
+            .unwrap(),
+            "UniffiVTableCallbackInterfaceFoo*"
+        );
+        assert_eq!(
+            ffi_type_name(&FfiType::Callback("CallbackInterfaceFree".into())).unwrap(),
+            "UniffiCallbackInterfaceFree"
+        );
+    }
+
+    #[test]
+    fn test_var_name() {
+        assert_eq!(var_name("value").unwrap(), "value");
+        assert_eq!(var_name("default").unwrap(), "default_");
+        assert_eq!(var_name("class").unwrap(), "class_");
+    }
+
+    #[test]
+    fn test_contract_version() {
+        let ci = ComponentInterface::from_webidl("namespace example {};", "example").unwrap();
+        let header = CHeader::new(&ci).render().unwrap();
+        assert!(header.contains(&format!(
+            "#define UNIFFI_CONTRACT_VERSION {}\n",
+            ci.uniffi_contract_version()
+        )));
+    }
+}
@@ -0,0 +1,67 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a C header for the scaffolding functions of a component.
+//!
+//! This doesn't generate any bindings, just the declarations of the `extern "C"` functions
+//! exported by the Rust library along with the structs they use.  It's for calling the
+//! scaffolding directly from C or C++, or as a starting point for bindings in a language which
+//! can import C headers.
+
+use crate::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use fs_err as fs;
+
+mod gen_c;
+#[cfg(feature = "bindgen-tests")]
+pub mod test;
+use gen_c::CHeader;
+pub use gen_c::Config;
+
+pub struct CBindingGenerator;
+impl BindingGenerator for CBindingGenerator {
+    type Config = Config;
+
+    fn new_config(&self, root_toml: &toml::Value) -> Result<Self::Config> {
+        crate::config::bindings_config(root_toml, "c")
+    }
+
+    fn update_component_configs(
+        &self,
+        _settings: &GenerationSettings,
+        _components: &mut Vec<Component<Self::Config>>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_bindings(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<Vec<Utf8PathBuf>> {
+        let mut written_files = vec![];
+        for Component { ci, config, .. } in components {
+            let header_file = settings.out_dir.join(config.header_filename(ci)?);
+            fs::write(&header_file, generate_source(ci, config)?)?;
+            written_files.push(header_file);
+        }
+        Ok(written_files)
+    }
+
+    fn owned_file_patterns(&self) -> Vec<String> {
+        vec!["*.h".to_string()]
+    }
+}
+
+/// Generate the C header for a component, as a string.
+///
+/// This is what [CBindingGenerator] writes to the config's header filename.  The config doesn't
+/// change the contents of the header.
+pub fn generate_source(ci: &ComponentInterface, _config: &Config) -> Result<String> {
+    use askama::Template;
+    CHeader::new(ci)
+        .render()
+        .context("failed to render C header")
+}
@@ -0,0 +1,116 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// The scaffolding functions exported by the `{{ ci.crate_name() }}` crate.
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The following structs are used to implement the lowest level
+// of the FFI, and thus useful to multiple uniffied crates.
+// We ensure they are declared exactly once, with a header guard, UNIFFI_SHARED_H.
+// The definitions match the ones in the Swift bridging header, so the headers can be combined.
+#ifdef UNIFFI_SHARED_H
+    // We also try to prevent mixing versions of shared uniffi header structs.
+    // If you add anything to the #else block, you must increment the version suffix in UNIFFI_SHARED_HEADER_V4
+    #ifndef UNIFFI_SHARED_HEADER_V4
+        #error Combining helper code from multiple versions of uniffi is not supported
+    #endif // ndef UNIFFI_SHARED_HEADER_V4
+#else
+#define UNIFFI_SHARED_H
+#define UNIFFI_SHARED_HEADER_V4
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+
+typedef struct RustBuffer
+{
+    uint64_t capacity;
+    uint64_t len;
+    uint8_t *data;
+} RustBuffer;
+
+typedef struct ForeignBytes
+{
+    int32_t len;
+    const uint8_t *data;
+} ForeignBytes;
+
+// Error definitions
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
+
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+#endif // def UNIFFI_SHARED_H
+
+// The values of `RustCallStatus.code`
+#ifndef UNIFFI_CALL_STATUS_CODES
+#define UNIFFI_CALL_STATUS_CODES
+enum {
+    // Successful call.
+    UNIFFI_CALL_SUCCESS = 0,
+    // Expected error, `errorBuf` contains the serialized error.
+    UNIFFI_CALL_ERROR = 1,
+    // Unexpected error, `errorBuf` contains a serialized message string.
+    UNIFFI_CALL_UNEXPECTED_ERROR = 2,
+    // Async function cancelled, `errorBuf` is empty.
+    UNIFFI_CALL_CANCELLED = 3,
+};
+#endif // ndef UNIFFI_CALL_STATUS_CODES
+
+// The version of the contract between the scaffolding and the bindings, which the
+// `uniffi_contract_version` function of a compatible library returns.
+#ifndef UNIFFI_CONTRACT_VERSION
+#define UNIFFI_CONTRACT_VERSION {{ ci.uniffi_contract_version() }}
+#endif // ndef UNIFFI_CONTRACT_VERSION
+
+{%- for def in ci.ffi_definitions() %}
+#ifndef {{ def.name()|if_guard_name }}
+#define {{ def.name()|if_guard_name }}
+{%- match def %}
+{% when FfiDefinition::CallbackFunction(callback) %}
+typedef
+    {%- match callback.return_type() %}{% when Some(return_type) %} {{ return_type|ffi_type_name }} {% when None %} void {% endmatch -%}
+    (*{{ callback.name()|ffi_callback_name }})(
+        {%- for arg in callback.arguments() -%}
+        {{ arg.type_().borrow()|ffi_type_name }}
+        {%- if !loop.last || callback.has_rust_call_status_arg() %}, {% endif %}
+        {%- endfor -%}
+        {%- if callback.has_rust_call_status_arg() %}
+        RustCallStatus *uniffi_call_status
+        {%- endif %}
+        {%- if callback.arguments().is_empty() && !callback.has_rust_call_status_arg() %}void{% endif %}
+    );
+{% when FfiDefinition::Struct(struct) %}
+typedef struct {{ struct.name()|ffi_struct_name }} {
+    {%- for field in struct.fields() %}
+    {{ field.type_().borrow()|ffi_type_name }} {{ field.name()|var_name }};
+    {%- endfor %}
+} {{ struct.name()|ffi_struct_name }};
+{% when FfiDefinition::Function(func) %}
+{% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+    {%- if func.arguments().len() > 0 %}
+        {%- for arg in func.arguments() %}
+            {{- arg.type_().borrow()|ffi_type_name }} {{ arg.name()|var_name -}}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}RustCallStatus *out_status{% endif %}
+    {%- else %}
+        {%- if func.has_rust_call_status_arg() %}RustCallStatus *out_status{%- else %}void{% endif %}
+    {% endif %}
+);
+{%- endmatch %}
+#endif
+{%- endfor %}
+
+#ifdef __cplusplus
+} // extern "C"
+#endif
@@ -0,0 +1,100 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::bindings::RunScriptOptions;
+use crate::cargo_metadata::CrateConfigSupplier;
+use crate::library_mode::generate_bindings;
+use anyhow::{bail, Context, Result};
+use camino::Utf8Path;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX, EXE_SUFFIX};
+use std::process::{Command, Stdio};
+use uniffi_testing::UniFFITestHelper;
+
+/// Run C tests for a UniFFI test fixture
+pub fn run_test(tmp_dir: &str, package_name: &str, script_file: &str) -> Result<()> {
+    run_script(
+        tmp_dir,
+        package_name,
+        script_file,
+        vec![],
+        &RunScriptOptions::default(),
+    )
+}
+
+/// Compile and run a C program
+///
+/// The program can include the generated headers for a crate and is linked against its cdylib.
+/// It's compiled with `cc`, or the compiler in the `CC` environment variable.
+pub fn run_script(
+    tmp_dir: &str,
+    package_name: &str,
+    script_file: &str,
+    args: Vec<String>,
+    options: &RunScriptOptions,
+) -> Result<()> {
+    let script_path = Utf8Path::new(script_file).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(package_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &script_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &super::CBindingGenerator,
+        &CrateConfigSupplier::from(test_helper.cargo_metadata()),
+        None,
+        &out_dir,
+        false,
+    )?;
+
+    let Some(library_name) = cdylib_path
+        .file_name()
+        .and_then(|name| name.strip_prefix(DLL_PREFIX))
+        .and_then(|name| name.strip_suffix(DLL_SUFFIX))
+    else {
+        bail!("Unexpected cdylib filename: {cdylib_path}");
+    };
+    let executable = out_dir.join(format!(
+        "{}{EXE_SUFFIX}",
+        script_path.file_stem().unwrap_or("test")
+    ));
+    let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let mut command = Command::new(&compiler);
+    command
+        .current_dir(&out_dir)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(&out_dir)
+        .arg(&script_path)
+        .arg("-L")
+        .arg(&out_dir)
+        .arg(format!("-l{library_name}"))
+        .arg(format!("-Wl,-rpath,{out_dir}"))
+        .arg("-o")
+        .arg(&executable);
+    if !options.show_compiler_messages {
+        command.stderr(Stdio::null());
+    }
+    let status = command
+        .spawn()
+        .with_context(|| format!("Failed to spawn `{compiler}` when compiling test program"))?
+        .wait()
+        .with_context(|| format!("Failed to wait for `{compiler}` when compiling test program"))?;
+    if !status.success() {
+        bail!("running `{compiler}` to compile the test program failed ({command:?})");
+    }
+
+    let mut command = Command::new(&executable);
+    command.current_dir(&out_dir).args(args);
+    let status = command
+        .spawn()
+        .context("Failed to spawn the test program")?
+        .wait()
+        .context("Failed to wait for the test program")?;
+    if !status.success() {
+        bail!("running the test program failed ({command:?})");
+    }
+    Ok(())
+}
@@ -28,11 +28,12 @@ impl CodeType for EnumCodeType {
 
     fn literal(&self, literal: &Literal, ci: &ComponentInterface) -> String {
         if let Literal::Enum(v, _) = literal {
-            format!(
-                "{}.{}",
-                self.type_label(ci),
-                super::KotlinCodeOracle.enum_variant_name(v)
-            )
+            // Enums with fields are sealed classes, with an object for each variant without fields
+            let variant = match ci.get_enum_definition(&self.id) {
+                Some(e) if !e.is_flat() => super::KotlinCodeOracle.class_name(ci, v),
+                _ => super::KotlinCodeOracle.enum_variant_name(v),
+            };
+            format!("{}.{variant}", self.type_label(ci))
         } else {
             unreachable!();
         }
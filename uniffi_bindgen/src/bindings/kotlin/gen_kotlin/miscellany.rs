@@ -27,4 +27,19 @@ macro_rules! impl_code_type_for_miscellany {
 
 impl_code_type_for_miscellany!(TimestampCodeType, "java.time.Instant", "Timestamp");
 
+impl_code_type_for_miscellany!(DateCodeType, "java.time.LocalDate", "Date");
+
+// A typealias for either `String` or `JsonElement`, depending on the config
+impl_code_type_for_miscellany!(JsonCodeType, "JsonValue", "Json");
+
+// A typealias for either `java.io.File` or `String`, depending on the config
+impl_code_type_for_miscellany!(PathCodeType, "FilePath", "Path");
+
+// A typealias for the class set by the `url_type` config
+impl_code_type_for_miscellany!(UrlCodeType, "UrlValue", "Url");
+
 impl_code_type_for_miscellany!(DurationCodeType, "java.time.Duration", "Duration");
+
+impl_code_type_for_miscellany!(UInt128CodeType, "java.math.BigInteger", "UInt128");
+
+impl_code_type_for_miscellany!(Int128CodeType, "java.math.BigInteger", "Int128");
@@ -9,10 +9,12 @@ use std::fmt::Debug;
 
 use anyhow::{anyhow, Context, Result};
 use askama::Template;
+use camino::Utf8Path;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 
 use crate::backend::TemplateExpression;
+use crate::bindings::{NameTemplate, TemplateOverride, TemplateOverrides};
 
 use crate::interface::*;
 
@@ -69,9 +71,11 @@ trait CodeType: Debug {
 
 // config options to customize the generated Kotlin.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     pub(super) package_name: Option<String>,
     pub(super) cdylib_name: Option<String>,
+    module_name: Option<NameTemplate>,
     generate_immutable_records: Option<bool>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
@@ -82,10 +86,45 @@ pub struct Config {
     #[serde(default)]
     android_cleaner: Option<bool>,
     #[serde(default)]
+    kotlinx_serialization_json: bool,
+    #[serde(default)]
+    paths_as_strings: bool,
+    #[serde(default)]
+    url_type: UrlType,
+    #[serde(default)]
     kotlin_target_version: Option<String>,
+    template_overrides_dir: Option<String>,
+    #[serde(skip)]
+    pub(super) template_overrides: TemplateOverrides,
 }
 
 impl Config {
+    /// The name of the Kotlin file for a component, without the `.kt`, `<namespace>` by default
+    ///
+    /// Other components import the component's types by its package, so this doesn't affect them.
+    pub fn module_name(&self, ci: &ComponentInterface) -> Result<String> {
+        match &self.module_name {
+            Some(template) => template.render(ci),
+            None => Ok(ci.namespace().to_string()),
+        }
+    }
+
+    /// Load the templates from `template_overrides_dir`, see [TemplateOverrides]
+    pub(super) fn load_template_overrides(&mut self) -> Result<()> {
+        if let Some(dir) = &self.template_overrides_dir {
+            self.template_overrides = TemplateOverrides::load(Utf8Path::new(dir), "kt")?;
+        }
+        Ok(())
+    }
+
+    /// The override for the record `name`, whose type is `type_name` in the bindings
+    pub(crate) fn record_override(&self, name: &str, type_name: &str) -> TemplateOverride<'_> {
+        self.template_overrides.get(
+            "RecordTemplate.kt",
+            &[("name", name), ("type_name", type_name)],
+        )
+    }
+
     pub(crate) fn android_cleaner(&self) -> bool {
         self.android_cleaner.unwrap_or(self.android)
     }
@@ -138,6 +177,7 @@ impl KotlinVersion {
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CustomTypeConfig {
     imports: Option<Vec<String>>,
     type_name: Option<String>,
@@ -165,13 +205,73 @@ impl Config {
     pub fn generate_immutable_records(&self) -> bool {
         self.generate_immutable_records.unwrap_or(false)
     }
+
+    /// Whether JSON values are a `JsonElement` from kotlinx.serialization, rather than a `String`
+    pub fn kotlinx_serialization_json(&self) -> bool {
+        self.kotlinx_serialization_json
+    }
+
+    /// Whether paths are a `String`, rather than a `java.io.File`
+    pub fn paths_as_strings(&self) -> bool {
+        self.paths_as_strings
+    }
+
+    /// The Kotlin class which `url::Url` values are
+    pub fn url_type(&self) -> UrlType {
+        self.url_type
+    }
+}
+
+/// The Kotlin classes `url::Url` can be, set with `url_type`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UrlType {
+    #[default]
+    String,
+    #[serde(rename = "java.net.URI")]
+    JavaUri,
+    #[serde(rename = "java.net.URL")]
+    JavaUrl,
+    #[serde(rename = "android.net.Uri")]
+    AndroidUri,
+}
+
+impl UrlType {
+    /// The fully qualified name of the class
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            Self::String => "String",
+            Self::JavaUri => "java.net.URI",
+            Self::JavaUrl => "java.net.URL",
+            Self::AndroidUri => "android.net.Uri",
+        }
+    }
+
+    /// An expression which parses the `String` `text` to the class
+    pub fn parse(&self, text: &str) -> String {
+        match self {
+            Self::String => text.to_string(),
+            Self::JavaUri => format!("java.net.URI({text})"),
+            // `URL(String)` is deprecated, in favor of going via a `URI`
+            Self::JavaUrl => format!("java.net.URI({text}).toURL()"),
+            Self::AndroidUri => format!("android.net.Uri.parse({text})"),
+        }
+    }
 }
 
 // Generate kotlin bindings for the given ComponentInterface, as a string.
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
-    KotlinWrapper::new(config.clone(), ci)
-        .render()
-        .context("failed to render kotlin bindings")
+    config.template_overrides.render(
+        "wrapper.kt",
+        &[
+            ("namespace", ci.namespace()),
+            ("crate_name", ci.crate_name()),
+        ],
+        || {
+            KotlinWrapper::new(config.clone(), ci)
+                .render()
+                .context("failed to render kotlin bindings")
+        },
+    )
 }
 
 /// A struct to record a Kotlin import statement.
@@ -347,6 +447,11 @@ impl KotlinCodeOracle {
         nm.to_string().to_shouty_snake_case()
     }
 
+    /// Get the idiomatic Kotlin rendering of a constant name.
+    fn const_name(&self, nm: &str) -> String {
+        nm.to_string().to_shouty_snake_case()
+    }
+
     /// Get the idiomatic Kotlin rendering of an FFI callback function name
     fn ffi_callback_name(&self, nm: &str) -> String {
         format!("Uniffi{}", nm.to_upper_camel_case())
@@ -485,13 +590,20 @@ impl<T: AsType> AsCodeType for T {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(miscellany::UInt128CodeType),
+            Type::Int128 => Box::new(miscellany::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::Date => Box::new(miscellany::DateCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
@@ -646,6 +758,11 @@ mod filters {
         Ok(KotlinCodeOracle.convert_error_suffix(&name))
     }
 
+    /// Get the idiomatic Kotlin rendering of a constant name.
+    pub fn const_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(KotlinCodeOracle.const_name(nm))
+    }
+
     /// Get the idiomatic Kotlin rendering of an FFI callback function name
     pub fn ffi_callback_name(nm: &str) -> Result<String, askama::Error> {
         Ok(KotlinCodeOracle.ffi_callback_name(nm))
@@ -715,12 +832,26 @@ mod filters {
 
     /// Get the idiomatic Kotlin rendering of docstring
     pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
-        let middle = textwrap::indent(&textwrap::dedent(docstring), " * ");
+        // `*/` would end the comment early and, since block comments nest, `/*` would leave it
+        // unterminated.  The backslashes keep the rendered markdown the same.
+        let docstring = docstring.replace("*/", "*\\/").replace("/*", "/\\*");
+        let middle = textwrap::indent(&textwrap::dedent(&docstring), " * ");
         let wrapped = format!("/**\n{middle}\n */");
 
         let spaces = usize::try_from(*spaces).unwrap_or_default();
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
+
+    /// Get the `@Deprecated` annotation for a deprecation message
+    pub fn deprecated(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('\n', "\\n");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(format!("{}@Deprecated(\"{message}\")", " ".repeat(spaces)))
+    }
 }
 
 #[cfg(test)]
@@ -748,4 +879,41 @@ mod test {
         assert!(KotlinVersion::new(1, 2, 3) > KotlinVersion::new(0, 100, 0));
         assert!(KotlinVersion::new(10, 0, 0) > KotlinVersion::new(1, 10, 0));
     }
+
+    // Unknown keys are reported where they are, not where a key with the same name is valid
+    #[test]
+    fn test_unknown_custom_type_key() {
+        let root_toml: toml::Value = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+            [bindings.kotlin.custom_types.Url]
+            type_name = "URL"
+            package_name = "java.net"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            crate::config::bindings_config::<Config>(&root_toml, "kotlin")
+                .unwrap_err()
+                .to_string(),
+            "unknown config key `bindings.kotlin.custom_types.Url.package_name`"
+        );
+    }
+
+    #[test]
+    fn test_docstring_escape() {
+        assert_eq!(
+            filters::docstring("Matches `src/*.rs`.\n/* not a comment */", &4).unwrap(),
+            "    /**\n     * Matches `src/\\*.rs`.\n     * /\\* not a comment *\\/\n     */"
+        );
+    }
+
+    #[test]
+    fn test_deprecated_escape() {
+        assert_eq!(
+            filters::deprecated("use \"fetch\" for ${url}\\n", &4).unwrap(),
+            "    @Deprecated(\"use \\\"fetch\\\" for \\${url}\\\\n\")"
+        );
+    }
 }
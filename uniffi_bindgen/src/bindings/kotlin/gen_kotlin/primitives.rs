@@ -47,6 +47,14 @@ fn render_literal(literal: &Literal, _ci: &ComponentInterface) -> String {
             },
         ),
         Literal::Float(string, type_) => typed_number(type_, string.clone()),
+        Literal::Bytes(bytes) => format!(
+            "byteArrayOf({})",
+            bytes
+                .iter()
+                .map(|b| format!("{b:#04x}.toByte()"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
 
         _ => unreachable!("Literal"),
     }
@@ -76,6 +84,7 @@ macro_rules! impl_code_type_for_primitive {
 }
 
 impl_code_type_for_primitive!(BooleanCodeType, "Boolean");
+impl_code_type_for_primitive!(CharCodeType, "Char");
 impl_code_type_for_primitive!(StringCodeType, "String");
 impl_code_type_for_primitive!(BytesCodeType, "ByteArray");
 impl_code_type_for_primitive!(Int8CodeType, "Byte");
@@ -2,7 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::{BindingGenerator, Component, GenerationSettings};
+use crate::diagnostics::{Code, Diagnostic};
+use crate::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
 use anyhow::Result;
 use camino::{Utf8Path, Utf8PathBuf};
 use fs_err as fs;
@@ -10,7 +11,8 @@ use std::collections::HashMap;
 use std::process::Command;
 
 mod gen_kotlin;
-use gen_kotlin::{generate_bindings, Config};
+use gen_kotlin::generate_bindings;
+pub use gen_kotlin::Config;
 #[cfg(feature = "bindgen-tests")]
 pub mod test;
 
@@ -19,12 +21,9 @@ impl BindingGenerator for KotlinBindingGenerator {
     type Config = Config;
 
     fn new_config(&self, root_toml: &toml::Value) -> Result<Self::Config> {
-        Ok(
-            match root_toml.get("bindings").and_then(|b| b.get("kotlin")) {
-                Some(v) => v.clone().try_into()?,
-                None => Default::default(),
-            },
-        )
+        let mut config: Config = crate::config::bindings_config(root_toml, "kotlin")?;
+        config.load_template_overrides()?;
+        Ok(config)
     }
 
     fn update_component_configs(
@@ -67,26 +66,114 @@ impl BindingGenerator for KotlinBindingGenerator {
         &self,
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
-    ) -> Result<()> {
+    ) -> Result<Vec<Utf8PathBuf>> {
+        let mut written_files = vec![];
         for Component { ci, config, .. } in components {
             let mut kt_file = full_bindings_path(config, &settings.out_dir);
             fs::create_dir_all(&kt_file)?;
-            kt_file.push(format!("{}.kt", ci.namespace()));
-            fs::write(&kt_file, generate_bindings(config, ci)?)?;
-            if settings.try_format_code {
-                if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
-                    println!(
-                        "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                        kt_file.file_name().unwrap(),
-                    );
-                }
+            kt_file.push(format!("{}.kt", config.module_name(ci)?));
+            fs::write(&kt_file, generate_source(ci, config)?)?;
+            written_files.push(kt_file);
+        }
+        Ok(written_files)
+    }
+
+    /// Format all the generated files with a single `ktlint` run.
+    fn finalize(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        if settings.try_format_code && !components.is_empty() {
+            let kt_files = components
+                .iter()
+                .map(|Component { ci, config, .. }| {
+                    Ok(full_bindings_path(config, &settings.out_dir)
+                        .join(format!("{}.kt", config.module_name(ci)?)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if let Err(e) = Command::new("ktlint").arg("-F").args(kt_files).output() {
+                Diagnostic::warning(
+                    Code::Format,
+                    format!("Unable to auto-format the Kotlin bindings using ktlint: {e:?}"),
+                )
+                .emit();
             }
         }
         Ok(())
     }
+
+    /// The Kotlin files in the package directories.
+    fn owned_file_patterns(&self) -> Vec<String> {
+        vec!["**/*.kt".to_string()]
+    }
+}
+
+/// Generate the Kotlin source for a component, as a string.
+///
+/// This is what [KotlinBindingGenerator] writes to `<module_name>.kt`, `<namespace>.kt` by
+/// default, in the directory for the config's package name.  The config should have been updated by
+/// [BindingGenerator::update_component_configs] first, like it is when generating bindings.
+pub fn generate_source(ci: &ComponentInterface, config: &Config) -> Result<String> {
+    generate_bindings(config, ci)
 }
 
 fn full_bindings_path(config: &Config, out_dir: &Utf8Path) -> Utf8PathBuf {
     let package_path: Utf8PathBuf = config.package_name().split('.').collect();
     Utf8PathBuf::from(out_dir).join(package_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_overrides() {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-kotlin-overrides-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("wrapper.kt"),
+            "// Copyright Example Corp. ({{ crate_name }})\n{{ builtin }}",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("RecordTemplate.kt"),
+            "@Keep // {{ name }}\n{{ builtin }}",
+        )
+        .unwrap();
+
+        let root_toml: toml::Value = toml::de::from_str(&format!(
+            "[bindings.kotlin]\ntemplate_overrides_dir = \"{dir}\""
+        ))
+        .unwrap();
+        let mut components = vec![Component {
+            ci: ComponentInterface::from_webidl(
+                "namespace geometry { }; dictionary Point { u32 x; };",
+                "geometry_rs",
+            )
+            .unwrap(),
+            config: KotlinBindingGenerator.new_config(&root_toml).unwrap(),
+            package: None,
+        }];
+        KotlinBindingGenerator
+            .update_component_configs(&GenerationSettings::default(), &mut components)
+            .unwrap();
+        let source = generate_source(&components[0].ci, &components[0].config).unwrap();
+        assert!(source.starts_with(
+            "// Copyright Example Corp. (geometry_rs)\n// This file was autogenerated"
+        ));
+        // The annotation is followed by the builtin record class
+        let record = &source[source.find("@Keep // Point\n").unwrap()..];
+        assert!(record
+            .trim_start_matches("@Keep // Point\n")
+            .trim_start()
+            .starts_with("data class Point ("));
+
+        // Other files with the `.kt` extension aren't override points
+        fs::write(dir.join("Types.kt"), "").unwrap();
+        assert!(KotlinBindingGenerator.new_config(&root_toml).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
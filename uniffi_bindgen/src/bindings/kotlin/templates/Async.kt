@@ -57,8 +57,8 @@ internal inline fun<T> uniffiTraitInterfaceCallAsync(
         } catch(e: Exception) {
             handleError(
                 UniffiRustCallStatus.create(
-                    UNIFFI_CALL_UNEXPECTED_ERROR,
-                    {{ Type::String.borrow()|lower_fn }}(e.toString()),
+                    uniffiUnexpectedErrorCode(e),
+                    uniffiLowerUnexpectedError(e),
                 )
             )
         }
@@ -89,8 +89,8 @@ internal inline fun<T, reified E: Throwable> uniffiTraitInterfaceCallAsyncWithEr
             } else {
                 handleError(
                     UniffiRustCallStatus.create(
-                        UNIFFI_CALL_UNEXPECTED_ERROR,
-                        {{ Type::String.borrow()|lower_fn }}(e.toString()),
+                        uniffiUnexpectedErrorCode(e),
+                        uniffiLowerUnexpectedError(e),
                     )
                 )
             }
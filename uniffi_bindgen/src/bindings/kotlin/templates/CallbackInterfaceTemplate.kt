@@ -3,6 +3,7 @@
 {%- let interface_name = cbi|type_name(ci) %}
 {%- let interface_docstring = cbi.docstring() %}
 {%- let methods = cbi.methods() %}
+{%- let constants = cbi.constants() %}
 {%- let vtable = cbi.vtable() %}
 {%- let vtable_methods = cbi.vtable_methods() %}
 
@@ -14,4 +15,10 @@
  *
  * @suppress
  */
-public object {{ ffi_converter_name }}: FfiConverterCallbackInterface<{{ interface_name }}>()
+public object {{ ffi_converter_name }}: FfiConverterCallbackInterface<{{ interface_name }}>() {
+    init {
+        // Loading the library registers the vtable, which Rust needs before it can use any handles.
+        // Other crates can pass this interface across the FFI without ever loading it themselves.
+        UniffiLib.INSTANCE
+    }
+}
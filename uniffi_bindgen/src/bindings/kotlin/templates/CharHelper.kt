@@ -0,0 +1,31 @@
+/**
+ * Kotlin's `Char` is a UTF-16 code unit, so only chars in the Basic Multilingual Plane can be
+ * passed. Lifting a char outside of it, or lowering a surrogate, throws.
+ *
+ * @suppress
+ */
+public object FfiConverterChar: FfiConverter<Char, Int> {
+    override fun lift(value: Int): Char {
+        if (value > 0xFFFF) {
+            throw InternalException("char U+${Integer.toHexString(value).uppercase()} can't be represented as a Kotlin Char")
+        }
+        return value.toChar()
+    }
+
+    override fun read(buf: ByteBuffer): Char {
+        return lift(buf.getInt())
+    }
+
+    override fun lower(value: Char): Int {
+        if (value.isSurrogate()) {
+            throw IllegalArgumentException("Surrogate U+${Integer.toHexString(value.code).uppercase()} isn't a valid char")
+        }
+        return value.code
+    }
+
+    override fun allocationSize(value: Char) = 4UL
+
+    override fun write(value: Char, buf: ByteBuffer) {
+        buf.putInt(lower(value))
+    }
+}
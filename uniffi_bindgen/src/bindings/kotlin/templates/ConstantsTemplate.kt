@@ -0,0 +1,9 @@
+{%- if !ci.constant_definitions().is_empty() %}
+
+object {{ ci.namespace()|class_name(ci) }} {
+    {%- for constant in ci.constant_definitions() %}
+    {%- call kt::docstring(constant, 4) %}
+    const val {{ constant.name()|const_name }}: {{ constant|type_name(ci) }} = {{ constant.value()|render_literal(constant, ci) }}
+    {%- endfor %}
+}
+{%- endif %}
@@ -0,0 +1,29 @@
+/**
+ * Dates are passed as the number of days since 1970-01-01. Lowering a date that's too far from
+ * that to fit in an `Int` throws.
+ *
+ * @suppress
+ */
+public object FfiConverterDate: FfiConverter<java.time.LocalDate, Int> {
+    override fun lift(value: Int): java.time.LocalDate {
+        return java.time.LocalDate.ofEpochDay(value.toLong())
+    }
+
+    override fun read(buf: ByteBuffer): java.time.LocalDate {
+        return lift(buf.getInt())
+    }
+
+    override fun lower(value: java.time.LocalDate): Int {
+        val days = value.toEpochDay()
+        if (days < Int.MIN_VALUE || days > Int.MAX_VALUE) {
+            throw IllegalArgumentException("Date $value is too far from 1970-01-01")
+        }
+        return days.toInt()
+    }
+
+    override fun allocationSize(value: java.time.LocalDate) = 4UL
+
+    override fun write(value: java.time.LocalDate, buf: ByteBuffer) {
+        buf.putInt(lower(value))
+    }
+}
@@ -13,16 +13,24 @@
 enum class {{ type_name }} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {{ variant|variant_name }}{% if loop.last %};{% else %},{% endif %}
     {%- endfor %}
+    {% for meth in e.methods() -%}
+    {%- call kt::func_decl("", meth, 4) %}
+    {% endfor %}
     companion object
 }
 {% when Some with (variant_discr_type) %}
 enum class {{ type_name }}(val value: {{ variant_discr_type|type_name(ci) }}) {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {{ variant|variant_name }}({{ e|variant_discr_literal(loop.index0) }}){% if loop.last %};{% else %},{% endif %}
     {%- endfor %}
+    {% for meth in e.methods() -%}
+    {%- call kt::func_decl("", meth, 4) %}
+    {% endfor %}
     companion object
 }
 {% endmatch %}
@@ -54,13 +62,20 @@ public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}
 sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {% if !variant.has_fields() -%}
     object {{ variant|type_name(ci) }} : {{ type_name }}()
     {% else -%}
     data class {{ variant|type_name(ci) }}(
         {%- for field in variant.fields() -%}
         {%- call kt::docstring(field, 8) %}
-        val {% call kt::field_name(field, loop.index) %}: {{ field|type_name(ci) }}{% if loop.last %}{% else %}, {% endif %}
+        {%- call kt::deprecated(field, 8) %}
+        val {% call kt::field_name(field, loop.index) %}: {{ field|type_name(ci) }}
+        {%- match field.default_value() %}
+            {%- when Some with(literal) %} = {{ literal|render_literal(field, ci) }}
+            {%- else %}
+        {%- endmatch -%}
+        {% if loop.last %}{% else %}, {% endif %}
         {%- endfor -%}
     ) : {{ type_name }}() {
         companion object
@@ -84,6 +99,9 @@ sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% e
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
     {% endif %}
+    {% for meth in e.methods() -%}
+    {%- call kt::func_decl("", meth, 4) %}
+    {% endfor %}
     companion object
 }
 
@@ -7,6 +7,7 @@
 sealed class {{ type_name }}(message: String): kotlin.Exception(message){% if contains_object_references %}, Disposable {% endif %} {
         {% for variant in e.variants() -%}
         {%- call kt::docstring(variant, 4) %}
+        {%- call kt::deprecated(variant, 4) %}
         class {{ variant|error_variant_name }}(message: String) : {{ type_name }}(message)
         {% endfor %}
 
@@ -19,15 +20,27 @@ sealed class {{ type_name }}(message: String): kotlin.Exception(message){% if co
 sealed class {{ type_name }}: kotlin.Exception(){% if contains_object_references %}, Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {%- let variant_name = variant|error_variant_name %}
     class {{ variant_name }}(
         {% for field in variant.fields() -%}
         {%- call kt::docstring(field, 8) %}
+        {%- call kt::deprecated(field, 8) %}
         val {% call kt::field_name(field, loop.index) %}: {{ field|type_name(ci) }}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
     ) : {{ type_name }}() {
         override val message
             get() = "{%- for field in variant.fields() %}{% call kt::field_name_unquoted(field, loop.index) %}=${ {% call kt::field_name(field, loop.index) %} }{% if !loop.last %}, {% endif %}{% endfor %}"
+        {%- for field in variant.fields() %}
+        {%- if field.is_source() %}
+
+        override val cause: Throwable? =
+        {%- match field.as_type() %}
+        {%- when Type::String %} kotlin.Exception({% call kt::field_name(field, loop.index) %})
+        {%- else %} {% call kt::field_name(field, loop.index) %}
+        {%- endmatch %}
+        {%- endif %}
+        {%- endfor %}
     }
     {% endfor %}
 
@@ -7,3 +7,12 @@
 {{- self.add_import(fully_qualified_type_name) }}
 {{- self.add_import(fully_qualified_ffi_converter_name) }}
 {{ self.add_import_as(fully_qualified_rustbuffer_name, local_rustbuffer_name) }}
+{%- match ci.external_type_docstring(module_path, name) %}
+{%- when Some(docstring) %}
+
+// External type {{ name|class_name(ci) }} is in package {{ package_name }}
+{%- for line in docstring.lines() %}
+//{% if !line.is_empty() %} {{ line }}{% endif %}
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
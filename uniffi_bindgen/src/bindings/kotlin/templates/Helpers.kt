@@ -4,6 +4,7 @@
 internal const val UNIFFI_CALL_SUCCESS = 0.toByte()
 internal const val UNIFFI_CALL_ERROR = 1.toByte()
 internal const val UNIFFI_CALL_UNEXPECTED_ERROR = 2.toByte()
+internal const val UNIFFI_CALL_CANCELLED = 3.toByte()
 
 @Structure.FieldOrder("code", "error_buf")
 internal open class UniffiRustCallStatus : Structure() {
@@ -94,6 +95,39 @@ private inline fun <U> uniffiRustCall(callback: (UniffiRustCallStatus) -> U): U
     return uniffiRustCallWithError(UniffiNullRustCallStatusErrorHandler, callback)
 }
 
+// Status code to return for an exception that a trait interface method didn't declare.
+// Cancellation gets its own code, so that Rust can tell it apart from a failure.
+internal fun uniffiUnexpectedErrorCode(e: Throwable): Byte {
+    return if (e is kotlin.coroutines.cancellation.CancellationException) {
+        UNIFFI_CALL_CANCELLED
+    } else {
+        UNIFFI_CALL_UNEXPECTED_ERROR
+    }
+}
+
+// Lower an exception that a trait interface method didn't declare.
+// The buffer holds the exception's message followed by its class name.
+internal fun uniffiLowerUnexpectedError(e: Throwable): RustBuffer.ByValue {
+    val message = e.toString()
+    val className = e.javaClass.name
+    val rbuf = RustBuffer.alloc(
+        {{ Type::String.borrow()|ffi_converter_name }}.allocationSize(message) +
+        {{ Type::String.borrow()|ffi_converter_name }}.allocationSize(className)
+    )
+    try {
+        val bbuf = rbuf.data!!.getByteBuffer(0, rbuf.capacity).also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+        {{ Type::String.borrow()|ffi_converter_name }}.write(message, bbuf)
+        {{ Type::String.borrow()|ffi_converter_name }}.write(className, bbuf)
+        rbuf.writeField("len", bbuf.position().toLong())
+        return rbuf
+    } catch (e: Throwable) {
+        RustBuffer.free(rbuf)
+        throw e
+    }
+}
+
 internal inline fun<T> uniffiTraitInterfaceCall(
     callStatus: UniffiRustCallStatus,
     makeCall: () -> T,
@@ -102,8 +136,8 @@ internal inline fun<T> uniffiTraitInterfaceCall(
     try {
         writeReturn(makeCall())
     } catch(e: kotlin.Exception) {
-        callStatus.code = UNIFFI_CALL_UNEXPECTED_ERROR
-        callStatus.error_buf = {{ Type::String.borrow()|lower_fn }}(e.toString())
+        callStatus.code = uniffiUnexpectedErrorCode(e)
+        callStatus.error_buf = uniffiLowerUnexpectedError(e)
     }
 }
 
@@ -120,8 +154,8 @@ internal inline fun<T, reified E: Throwable> uniffiTraitInterfaceCallWithError(
             callStatus.code = UNIFFI_CALL_ERROR
             callStatus.error_buf = lowerError(e)
         } else {
-            callStatus.code = UNIFFI_CALL_UNEXPECTED_ERROR
-            callStatus.error_buf = {{ Type::String.borrow()|lower_fn }}(e.toString())
+            callStatus.code = uniffiUnexpectedErrorCode(e)
+            callStatus.error_buf = uniffiLowerUnexpectedError(e)
         }
     }
 }
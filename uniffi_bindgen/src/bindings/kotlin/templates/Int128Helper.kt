@@ -0,0 +1,26 @@
+/**
+ * @suppress
+ */
+public object FfiConverterInt128: FfiConverterRustBuffer<java.math.BigInteger> {
+    override fun read(buf: ByteBuffer): java.math.BigInteger {
+        val bytes = ByteArray(16)
+        buf.get(bytes)
+        return java.math.BigInteger(bytes)
+    }
+
+    // 16 bytes, big-endian two's complement
+    override fun allocationSize(value: java.math.BigInteger) = 16UL
+
+    override fun write(value: java.math.BigInteger, buf: ByteBuffer) {
+        if (value.bitLength() > 127) {
+            throw IllegalArgumentException("Value out of range for i128: $value")
+        }
+        // `toByteArray()` is as short as possible, so sign-extend it to 16 bytes
+        val bytes = value.toByteArray()
+        val padding = if (value.signum() < 0) (-1).toByte() else 0.toByte()
+        for (i in 15 downTo 0) {
+            val index = bytes.size - 1 - i
+            buf.put(if (index >= 0) bytes[index] else padding)
+        }
+    }
+}
@@ -2,13 +2,42 @@
 public interface {{ interface_name }} {
     {% for meth in methods.iter() -%}
     {%- call kt::docstring(meth, 4) %}
+    {%- call kt::deprecated(meth, 4) %}
+    {%- if meth.is_getter() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    val {{ meth.name()|var_name }}: {{ return_type|type_name(ci) }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- else %}
     {% if meth.is_async() -%}suspend {% endif -%}
     fun {{ meth.name()|fn_name }}({% call kt::arg_list(meth, true) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_name(ci) -}}
     {%- else -%}
     {%- endmatch %}
+    {%- match meth.default_ffi_func() %}
+    {%- when Some with (default_ffi_func) %} {
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        return {{ return_type|lift_fn }}({% call kt::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %})
+        {%- when None %}
+        {% call kt::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %}
+        {%- endmatch %}
+    }
+    {%- else %}
+    {%- endmatch %}
+    {%- endif %}
     {% endfor %}
+    {%- if constants.is_empty() %}
     companion object
+    {%- else %}
+    companion object {
+        {%- for constant in constants.iter() %}
+        {%- call kt::docstring(constant, 8) %}
+        const val {{ constant.name()|const_name }}: {{ constant|type_name(ci) }} = {{ constant.value()|render_literal(constant, ci) }}
+        {%- endfor %}
+    }
+    {%- endif %}
 }
 
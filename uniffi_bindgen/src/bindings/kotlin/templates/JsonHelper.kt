@@ -0,0 +1,41 @@
+{%- if config.kotlinx_serialization_json() %}
+/**
+ * A JSON value, parsed with kotlinx.serialization.
+ */
+public typealias JsonValue = kotlinx.serialization.json.JsonElement
+{%- else %}
+/**
+ * A JSON value, as its JSON text. Set `kotlinx_serialization_json` in `uniffi.toml` to use a
+ * `JsonElement` instead.
+ */
+public typealias JsonValue = String
+{%- endif %}
+
+/**
+ * JSON values are passed as their JSON text. Rust rejects text which isn't valid JSON.
+ *
+ * @suppress
+ */
+public object FfiConverterJson: FfiConverterRustBuffer<JsonValue> {
+    override fun read(buf: ByteBuffer): JsonValue {
+        val byteArr = ByteArray(buf.getInt())
+        buf.get(byteArr)
+        val text = byteArr.toString(Charsets.UTF_8)
+        {%- if config.kotlinx_serialization_json() %}
+        return kotlinx.serialization.json.Json.parseToJsonElement(text)
+        {%- else %}
+        return text
+        {%- endif %}
+    }
+
+    // `JsonElement.toString()` is its JSON text
+    private fun toUtf8(value: JsonValue) = value.toString().toByteArray(Charsets.UTF_8)
+
+    override fun allocationSize(value: JsonValue) = 4UL + toUtf8(value).size.toULong()
+
+    override fun write(value: JsonValue, buf: ByteBuffer) {
+        val bytes = toUtf8(value)
+        buf.putInt(bytes.size)
+        buf.put(bytes)
+    }
+}
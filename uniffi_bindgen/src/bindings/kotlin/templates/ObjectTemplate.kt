@@ -103,6 +103,7 @@
 {%- let obj = ci|get_object_definition(name) %}
 {%- let (interface_name, impl_class_name) = obj|object_names(ci) %}
 {%- let methods = obj.methods() %}
+{%- let constants = obj.constants() %}
 {%- let interface_docstring = obj.docstring() %}
 {%- let is_error = ci.is_name_used_as_error(name) %}
 {%- let ffi_converter_name = obj|ffi_converter_name %}
@@ -111,10 +112,16 @@
 
 {%- call kt::docstring(obj, 0) %}
 {% if (is_error) %}
-open class {{ impl_class_name }} : kotlin.Exception, Disposable, AutoCloseable, {{ interface_name }} {
-{% else -%}
-open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }} {
+open class {{ impl_class_name }} : kotlin.Exception, Disposable, AutoCloseable, {{ interface_name }}
+{%- else -%}
+open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }}
 {%- endif %}
+{%- for tm in obj.uniffi_traits() %}
+{%-     match tm %}
+{%-         when UniffiTrait::Ord { cmp } %}, Comparable<{{ impl_class_name }}>
+{%-         else %}
+{%-     endmatch %}
+{%- endfor %} {
 
     constructor(pointer: Pointer) {
         this.pointer = pointer
@@ -135,9 +142,15 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     {%- match obj.primary_constructor() %}
     {%- when Some(cons) %}
     {%-     if cons.is_async() %}
-    // Note no constructor generated for this object as it is async.
+    // Note no constructor generated for this object as it is async, see the companion object.
     {%-     else %}
     {%- call kt::docstring(cons, 4) %}
+    {%- call kt::deprecated(cons, 4) %}
+    {%- match cons.throws_type() %}
+    {%- when Some(throwable) %}
+    @Throws({{ throwable|type_name(ci) }}::class)
+    {%- else %}
+    {%- endmatch %}
     constructor({% call kt::arg_list(cons, true) -%}) :
         this({% call kt::to_ffi_call(cons) %})
     {%-     endif %}
@@ -149,6 +162,9 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
 
     private val wasDestroyed = AtomicBoolean(false)
     private val callCounter = AtomicLong(1)
+    {%- if obj.has_consuming_method() %}
+    private val wasConsumed = AtomicBoolean(false)
+    {%- endif %}
 
     override fun destroy() {
         // Only allow a single call to this method.
@@ -167,6 +183,11 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     }
 
     internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        {%- if obj.has_consuming_method() %}
+        if (this.wasConsumed.get()) {
+            throw IllegalStateException("${this.javaClass.simpleName} object has already been consumed")
+        }
+        {%- endif %}
         // Check and increment the call counter, to keep the object alive.
         // This needs a compare-and-set retry loop in case of concurrent updates.
         do {
@@ -189,6 +210,27 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
         }
     }
 
+    {%- if obj.has_consuming_method() %}
+
+    // Call a method which consumes the object.  Only one call can consume it, and it takes over
+    // the count of 1 that `destroy()` would have released, so the object is freed once the call
+    // and any calls already in progress have finished.
+    internal inline fun <R> consumeWithPointer(block: (ptr: Pointer) -> R): R {
+        if (!this.wasDestroyed.compareAndSet(false, true)) {
+            val state = if (this.wasConsumed.get()) "consumed" else "destroyed"
+            throw IllegalStateException("${this.javaClass.simpleName} object has already been $state")
+        }
+        this.wasConsumed.set(true)
+        try {
+            return block(this.uniffiClonePointer())
+        } finally {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                cleanable.clean()
+            }
+        }
+    }
+    {%- endif %}
+
     // Use a static inner class instead of a closure so as not to accidentally
     // capture `this` as part of the cleanable's action.
     private class UniffiCleanAction(private val pointer: Pointer?) : Runnable {
@@ -208,7 +250,11 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     }
 
     {% for meth in obj.methods() -%}
+    {%- if meth.is_getter() %}
+    {%- call kt::getter_decl("override", meth, 4) %}
+    {%- else %}
     {%- call kt::func_decl("override", meth, 4) %}
+    {%- endif %}
     {% endfor %}
 
     {%- for tm in obj.uniffi_traits() %}
@@ -228,18 +274,17 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
     override fun hashCode(): Int {
         return {{ hash.return_type().unwrap()|lift_fn }}({%- call kt::to_ffi_call(hash) %}).toInt()
     }
+    {%         when UniffiTrait::Ord { cmp } %}
+    override fun compareTo(other: {{ impl_class_name }}): Int {
+        return {{ cmp.return_type().unwrap()|lift_fn }}({% call kt::to_ffi_call(cmp) %}).toInt()
+    }
     {%-         else %}
     {%-     endmatch %}
     {%- endfor %}
 
-    {# XXX - "companion object" confusion? How to have alternate constructors *and* be an error? #}
-    {% if !obj.alternate_constructors().is_empty() -%}
-    companion object {
-        {% for cons in obj.alternate_constructors() -%}
-        {% call kt::func_decl("", cons, 4) %}
-        {% endfor %}
-    }
-    {% else if is_error %}
+    {#- Objects used as errors need their companion to be the error handler, which also holds any
+        factory constructors and static methods. #}
+    {% if is_error %}
     companion object ErrorHandler : UniffiRustCallStatusErrorHandler<{{ impl_class_name }}> {
         override fun lift(error_buf: RustBuffer.ByValue): {{ impl_class_name }} {
             // Due to some mismatches in the ffi converter mechanisms, errors are a RustBuffer.
@@ -249,6 +294,21 @@ open class {{ impl_class_name }}: Disposable, AutoCloseable, {{ interface_name }
             }
             return {{ ffi_converter_name }}.read(bb)
         }
+        {% for cons in obj.factory_constructors() -%}
+        {% call kt::func_decl("", cons, 4) %}
+        {% endfor %}
+        {% for meth in obj.static_methods() -%}
+        {% call kt::func_decl("", meth, 4) %}
+        {% endfor %}
+    }
+    {% else if !obj.factory_constructors().is_empty() || !obj.static_methods().is_empty() -%}
+    companion object {
+        {% for cons in obj.factory_constructors() -%}
+        {% call kt::func_decl("", cons, 4) %}
+        {% endfor %}
+        {% for meth in obj.static_methods() -%}
+        {% call kt::func_decl("", meth, 4) %}
+        {% endfor %}
     }
     {% else %}
     companion object
@@ -0,0 +1,45 @@
+{%- if config.paths_as_strings() %}
+/**
+ * A file system path. Set `paths_as_strings = false` in `uniffi.toml` to use a `java.io.File`
+ * instead.
+ */
+public typealias FilePath = String
+{%- else %}
+/**
+ * A file system path. Set `paths_as_strings` in `uniffi.toml` to use a `String` instead.
+ */
+public typealias FilePath = java.io.File
+{%- endif %}
+
+/**
+ * Paths are passed as their bytes, which are UTF-8 for paths that are valid Unicode. Rust paths
+ * which aren't have their invalid bytes replaced by U+FFFD.
+ *
+ * @suppress
+ */
+public object FfiConverterPath: FfiConverterRustBuffer<FilePath> {
+    override fun read(buf: ByteBuffer): FilePath {
+        val byteArr = ByteArray(buf.getInt())
+        buf.get(byteArr)
+        val path = byteArr.toString(Charsets.UTF_8)
+        {%- if config.paths_as_strings() %}
+        return path
+        {%- else %}
+        return java.io.File(path)
+        {%- endif %}
+    }
+
+    {%- if config.paths_as_strings() %}
+    private fun toUtf8(value: FilePath) = value.toByteArray(Charsets.UTF_8)
+    {%- else %}
+    private fun toUtf8(value: FilePath) = value.path.toByteArray(Charsets.UTF_8)
+    {%- endif %}
+
+    override fun allocationSize(value: FilePath) = 4UL + toUtf8(value).size.toULong()
+
+    override fun write(value: FilePath, buf: ByteBuffer) {
+        val bytes = toUtf8(value)
+        buf.putInt(bytes.size)
+        buf.put(bytes)
+    }
+}
@@ -2,9 +2,11 @@
 
 {%- if rec.has_fields() %}
 {%- call kt::docstring(rec, 0) %}
+{%- call kt::deprecated(rec, 0) %}
 data class {{ type_name }} (
     {%- for field in rec.fields() %}
     {%- call kt::docstring(field, 4) %}
+    {%- call kt::deprecated(field, 4) %}
     {% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name(ci) -}}
     {%- match field.default_value() %}
         {%- when Some with(literal) %} = {{ literal|render_literal(field, ci) }}
@@ -19,10 +21,14 @@ data class {{ type_name }} (
         {% call kt::destroy_fields(rec) %}
     }
     {% endif %}
+    {% for meth in rec.methods() -%}
+    {%- call kt::func_decl("", meth, 4) %}
+    {% endfor %}
     companion object
 }
 {%- else -%}
 {%- call kt::docstring(rec, 0) %}
+{%- call kt::deprecated(rec, 0) %}
 class {{ type_name }} {
     override fun equals(other: Any?): Boolean {
         return other is {{ type_name }}
@@ -31,6 +37,9 @@ class {{ type_name }} {
     override fun hashCode(): Int {
         return javaClass.hashCode()
     }
+    {% for meth in rec.methods() -%}
+    {%- call kt::func_decl("", meth, 4) %}
+    {% endfor %}
 
     companion object
 }
@@ -11,9 +11,15 @@
 interface Disposable {
     fun destroy()
     companion object {
+        // Objects can also be inside the sequences and maps of records and enums
         fun destroy(vararg args: Any?) {
-            args.filterIsInstance<Disposable>()
-                .forEach(Disposable::destroy)
+            for (arg in args) {
+                when (arg) {
+                    is Disposable -> arg.destroy()
+                    is Iterable<*> -> arg.forEach { destroy(it) }
+                    is Map<*, *> -> arg.values.forEach { destroy(it) }
+                }
+            }
         }
     }
 }
@@ -60,6 +66,9 @@ object NoPointer
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.kt" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.kt" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.kt" %}
 
@@ -84,6 +93,12 @@ object NoPointer
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.kt" %}
 
+{%- when Type::Int128 %}
+{%- include "Int128Helper.kt" %}
+
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.kt" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.kt" %}
 
@@ -108,7 +123,12 @@ object NoPointer
 {% include "ObjectTemplate.kt" %}
 
 {%- when Type::Record { name, module_path } %}
+{%- let record_override = config.record_override(name, type_name) %}
+{{- record_override.before() }}
+{%- if record_override.includes_builtin() %}
 {% include "RecordTemplate.kt" %}
+{%- endif %}
+{{- record_override.after() }}
 
 {%- when Type::Optional { inner_type } %}
 {% include "OptionalTemplate.kt" %}
@@ -125,6 +145,18 @@ object NoPointer
 {%- when Type::Timestamp %}
 {% include "TimestampHelper.kt" %}
 
+{%- when Type::Date %}
+{% include "DateHelper.kt" %}
+
+{%- when Type::Json %}
+{% include "JsonHelper.kt" %}
+
+{%- when Type::Path %}
+{% include "PathHelper.kt" %}
+
+{%- when Type::Url %}
+{% include "UrlHelper.kt" %}
+
 {%- when Type::Duration %}
 {% include "DurationHelper.kt" %}
 
@@ -0,0 +1,25 @@
+/**
+ * @suppress
+ */
+public object FfiConverterUInt128: FfiConverterRustBuffer<java.math.BigInteger> {
+    override fun read(buf: ByteBuffer): java.math.BigInteger {
+        val bytes = ByteArray(16)
+        buf.get(bytes)
+        return java.math.BigInteger(1, bytes)
+    }
+
+    // 16 bytes, big-endian
+    override fun allocationSize(value: java.math.BigInteger) = 16UL
+
+    override fun write(value: java.math.BigInteger, buf: ByteBuffer) {
+        if (value.signum() < 0 || value.bitLength() > 128) {
+            throw IllegalArgumentException("Value out of range for u128: $value")
+        }
+        // `toByteArray()` may add a leading zero byte for the sign, or be shorter than 16 bytes
+        val bytes = value.toByteArray()
+        for (i in 15 downTo 0) {
+            val index = bytes.size - 1 - i
+            buf.put(if (index >= 0) bytes[index] else 0.toByte())
+        }
+    }
+}
@@ -0,0 +1,29 @@
+/**
+ * A URL. Set `url_type` in `uniffi.toml` to use a `java.net.URI`, `java.net.URL` or
+ * `android.net.Uri` instead of a `String`.
+ */
+public typealias UrlValue = {{ config.url_type().class_name() }}
+
+/**
+ * URLs are passed as their serialized text. Rust rejects text which isn't a valid URL.
+ *
+ * @suppress
+ */
+public object FfiConverterUrl: FfiConverterRustBuffer<UrlValue> {
+    override fun read(buf: ByteBuffer): UrlValue {
+        val byteArr = ByteArray(buf.getInt())
+        buf.get(byteArr)
+        val text = byteArr.toString(Charsets.UTF_8)
+        return {{ config.url_type().parse("text") }}
+    }
+
+    private fun toUtf8(value: UrlValue) = value.toString().toByteArray(Charsets.UTF_8)
+
+    override fun allocationSize(value: UrlValue) = 4UL + toUtf8(value).size.toULong()
+
+    override fun write(value: UrlValue, buf: ByteBuffer) {
+        val bytes = toUtf8(value)
+        buf.putInt(bytes.size)
+        buf.put(bytes)
+    }
+}
@@ -5,7 +5,11 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
-    {%- if func.takes_self() %}
+    {%- if func.consumes_self() %}
+    consumeWithPointer {
+        {%- call to_raw_ffi_call(func) %}
+    }
+    {% else if func.takes_self() && func.value_self_type().is_none() %}
     callWithPointer {
         {%- call to_raw_ffi_call(func) %}
     }
@@ -22,14 +26,33 @@
     uniffiRustCall()
     {%- endmatch %} { _status ->
     UniffiLib.INSTANCE.{{ func.ffi_func().name() }}(
-        {% if func.takes_self() %}it, {% endif -%}
+        {% call self_arg_lowered(func) -%}
         {% call arg_list_lowered(func) -%}
         _status)
 }
 {%- endmacro -%}
 
+{#-
+// Call the Rust default implementation of a trait method, for a foreign implementation which
+// doesn't override it.
+-#}
+{%- macro to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) -%}
+    {%- match meth.throws_type() %}
+    {%- when Some with (e) %}
+    uniffiRustCallWithError({{ e|type_name(ci) }})
+    {%- else %}
+    uniffiRustCall()
+    {%- endmatch %} { _status ->
+    UniffiLib.INSTANCE.{{ default_ffi_func.name() }}(
+        {{ ffi_converter_name }}.lower(this),
+        {% call arg_list_lowered(meth) -%}
+        _status)
+}
+{%- endmacro -%}
+
 {%- macro func_decl(func_decl, callable, indent) %}
     {%- call docstring(callable, indent) %}
+    {%- call deprecated(callable, indent) %}
     {%- match callable.throws_type() -%}
     {%-     when Some(throwable) %}
     @Throws({{ throwable|type_name(ci) }}::class)
@@ -38,13 +61,13 @@
     {%- if callable.is_async() %}
     @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
     {{ func_decl }} suspend fun {{ callable.name()|fn_name }}(
-        {%- call arg_list(callable, !callable.takes_self()) -%}
+        {%- call arg_list(callable, !callable.takes_self() || callable.value_self_type().is_some()) -%}
     ){% match callable.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
         return {% call call_async(callable) %}
     }
     {%- else -%}
     {{ func_decl }} fun {{ callable.name()|fn_name }}(
-        {%- call arg_list(callable, !callable.takes_self()) -%}
+        {%- call arg_list(callable, !callable.takes_self() || callable.value_self_type().is_some()) -%}
     ){%- match callable.return_type() -%}
     {%-         when Some with (return_type) -%}
         : {{ return_type|type_name(ci) }} {
@@ -56,17 +79,41 @@
     {% endif %}
 {% endmacro %}
 
+{#-
+// A field getter, which is a read-only property rather than a function.
+-#}
+{%- macro getter_decl(getter_decl, callable, indent) %}
+    {%- call docstring(callable, indent) %}
+    {%- call deprecated(callable, indent) %}
+    {%- match callable.return_type() %}
+    {%- when Some with (return_type) %}
+    {{ getter_decl }} val {{ callable.name()|var_name }}: {{ return_type|type_name(ci) }}
+        get() = {{ return_type|lift_fn }}({% call to_ffi_call(callable) %})
+    {%- when None %}
+    {%- endmatch %}
+{% endmacro %}
+
+{#-
+// The lowered `this` for methods: the object pointer, which `callWithPointer` passes as `it`, or
+// the lowered value for methods of records and enums.
+-#}
+{%- macro self_arg_lowered(func) -%}
+    {%- match func.value_self_type() %}
+    {%- when Some(self_type) %}{{ self_type|lower_fn }}(this), {% when None %}{% if func.takes_self() %}it, {% endif %}
+    {%- endmatch %}
+{%- endmacro -%}
+
 {%- macro call_async(callable) -%}
     uniffiRustCallAsync(
-{%- if callable.takes_self() %}
-        callWithPointer { thisPtr ->
+{%- if callable.takes_self() && callable.value_self_type().is_none() %}
+        {% if callable.consumes_self() %}consumeWithPointer{% else %}callWithPointer{% endif %} { thisPtr ->
             UniffiLib.INSTANCE.{{ callable.ffi_func().name() }}(
                 thisPtr,
                 {% call arg_list_lowered(callable) %}
             )
         },
 {%- else %}
-        UniffiLib.INSTANCE.{{ callable.ffi_func().name() }}({% call arg_list_lowered(callable) %}),
+        UniffiLib.INSTANCE.{{ callable.ffi_func().name() }}({% call self_arg_lowered(callable) %}{% call arg_list_lowered(callable) %}),
 {%- endif %}
         {{ callable|async_poll(ci) }},
         {{ callable|async_complete(ci) }},
@@ -158,3 +205,11 @@ v{{- field_num -}}
 {%- macro docstring(defn, indent_spaces) %}
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
+
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecated(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
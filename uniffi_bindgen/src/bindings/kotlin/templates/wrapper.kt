@@ -3,7 +3,7 @@
 
 {%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
 
-@file:Suppress("NAME_SHADOWING")
+@file:Suppress("NAME_SHADOWING", "DEPRECATION")
 
 package {{ config.package_name() }}
 
@@ -58,4 +58,6 @@ import java.util.concurrent.ConcurrentHashMap
 {%- include "TopLevelFunctionTemplate.kt" %}
 {%- endfor %}
 
+{%- include "ConstantsTemplate.kt" %}
+
 {% import "macros.kt" as kt %}
@@ -7,19 +7,25 @@
 //! This module contains all the code for generating foreign language bindings,
 //! along with some helpers for executing foreign language scripts or tests.
 
-mod kotlin;
+pub mod c;
+pub use c::CBindingGenerator;
+pub mod kotlin;
 pub use kotlin::KotlinBindingGenerator;
-mod python;
+pub mod name_template;
+pub use name_template::NameTemplate;
+pub mod python;
 pub use python::PythonBindingGenerator;
-mod ruby;
+pub mod ruby;
 pub use ruby::RubyBindingGenerator;
-mod swift;
+pub mod swift;
 pub use swift::{generate_swift_bindings, SwiftBindingGenerator, SwiftBindingsOptions};
+pub mod template_overrides;
+pub use template_overrides::{TemplateOverride, TemplateOverrides};
 
 #[cfg(feature = "bindgen-tests")]
 pub use self::{
-    kotlin::test as kotlin_test, python::test as python_test, ruby::test as ruby_test,
-    swift::test as swift_test,
+    c::test as c_test, kotlin::test as kotlin_test, python::test as python_test,
+    ruby::test as ruby_test, swift::test as swift_test,
 };
 
 #[cfg(feature = "bindgen-tests")]
@@ -0,0 +1,181 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Templates for the names of generated modules and files.
+//!
+//! The `module_name` of the Kotlin, Python, Ruby and Swift bindings is a template which can refer to
+//! the component with `{namespace}` and `{crate_name}`, for example `module_name = "{namespace}_ffi"`.
+//! It's the name of the module without the file extension, which the bindings add.  The C
+//! `header_filename` is a template too, but it's the whole file name, including the `.h`.
+//!
+//! The template is checked when the config is deserialized, so a typo in a placeholder fails when
+//! the config is loaded rather than producing oddly named files.  Names are file names in the
+//! output directory, so neither the template nor the rendered name can contain path separators or
+//! `..`, or start with a `.`.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ComponentInterface;
+
+/// A name containing `{namespace}` and `{crate_name}` placeholders
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct NameTemplate(String);
+
+impl NameTemplate {
+    /// Check a template, failing for unknown placeholders, an empty name or a name which isn't a
+    /// plain file name
+    pub fn new(template: impl Into<String>) -> Result<Self> {
+        let template = template.into();
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                bail!("unmatched `}}` in name template `{template}`");
+            }
+            let Some(len) = rest[start..].find('}') else {
+                bail!("unmatched `{{` in name template `{template}`");
+            };
+            let placeholder = &rest[start + 1..start + len];
+            if !matches!(placeholder, "namespace" | "crate_name") {
+                bail!(
+                    "unknown placeholder `{{{placeholder}}}` in name template `{template}`, \
+                    expected `{{namespace}}` or `{{crate_name}}`"
+                );
+            }
+            rest = &rest[start + len + 1..];
+        }
+        // The namespace and crate name are never empty, so the name is only empty if the
+        // template is just whitespace.
+        if template.trim().is_empty() {
+            bail!("name template `{template}` is empty");
+        }
+        check_file_name(&template).map_err(|e| anyhow!("name template `{template}` {e}"))?;
+        Ok(Self(template))
+    }
+
+    /// The name for a component
+    pub fn render(&self, ci: &ComponentInterface) -> Result<String> {
+        self.render_with(ci.namespace(), ci.crate_name())
+    }
+
+    /// The name for the component with `namespace`, defined in `crate_name`
+    ///
+    /// This fails if the name isn't a plain file name, like when the crate name starts with a `.`
+    pub fn render_with(&self, namespace: &str, crate_name: &str) -> Result<String> {
+        let name = self
+            .0
+            .replace("{namespace}", namespace)
+            .replace("{crate_name}", crate_name);
+        check_file_name(&name)
+            .map_err(|e| anyhow!("`{name}`, rendered from name template `{}`, {e}", self.0))?;
+        Ok(name)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Check that a name can only refer to a file in the output directory
+fn check_file_name(name: &str) -> Result<()> {
+    if name.contains(['/', '\\']) {
+        bail!("contains a path separator");
+    }
+    if name.contains("..") {
+        bail!("contains `..`");
+    }
+    if name.starts_with('.') {
+        bail!("starts with `.`");
+    }
+    Ok(())
+}
+
+impl TryFrom<String> for NameTemplate {
+    type Error = anyhow::Error;
+
+    fn try_from(template: String) -> Result<Self> {
+        Self::new(template)
+    }
+}
+
+impl From<NameTemplate> for String {
+    fn from(template: NameTemplate) -> String {
+        template.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = NameTemplate::new("{namespace}_ffi").unwrap();
+        assert_eq!(
+            template.render_with("geometry", "geometry_rs").unwrap(),
+            "geometry_ffi"
+        );
+        let template = NameTemplate::new("{crate_name}_{namespace}").unwrap();
+        assert_eq!(
+            template.render_with("geometry", "geometry_rs").unwrap(),
+            "geometry_rs_geometry"
+        );
+        let template = NameTemplate::new("fixed").unwrap();
+        assert_eq!(
+            template.render_with("geometry", "geometry_rs").unwrap(),
+            "fixed"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rendered_names() {
+        let template = NameTemplate::new("{crate_name}").unwrap();
+        for crate_name in [".hidden", "a/b", "a\\b", "a..b"] {
+            assert!(
+                template.render_with("geometry", crate_name).is_err(),
+                "{crate_name}"
+            );
+        }
+        let err = template
+            .render_with("geometry", ".hidden")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "`.hidden`, rendered from name template `{crate_name}`, starts with `.`"
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in [
+            "",
+            "  ",
+            "{name}_ffi",
+            "{}",
+            "{namespace",
+            "namespace}",
+            "../{namespace}",
+            "out/{namespace}",
+            "out\\{namespace}",
+            "{namespace}..ffi",
+            ".{namespace}",
+        ] {
+            assert!(NameTemplate::new(template).is_err(), "{template}");
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            module_name: NameTemplate,
+        }
+        let config: Config = toml::from_str(r#"module_name = "{namespace}_ffi""#).unwrap();
+        assert_eq!(config.module_name.as_str(), "{namespace}_ffi");
+        let err = toml::from_str::<Config>(r#"module_name = "{namespce}_ffi""#).unwrap_err();
+        assert!(err.to_string().contains("unknown placeholder `{namespce}`"));
+    }
+}
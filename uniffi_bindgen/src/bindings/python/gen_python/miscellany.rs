@@ -32,3 +32,72 @@ macro_rules! impl_code_type_for_miscellany {
 impl_code_type_for_miscellany!(TimestampCodeType, "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "Duration");
+
+#[derive(Debug)]
+pub struct DateCodeType;
+
+impl CodeType for DateCodeType {
+    fn type_label(&self) -> String {
+        "datetime.date".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Date".into()
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonCodeType;
+
+impl CodeType for JsonCodeType {
+    fn type_label(&self) -> String {
+        "typing.Any".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Json".into()
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct PathCodeType;
+
+impl CodeType for PathCodeType {
+    fn type_label(&self) -> String {
+        "pathlib.Path".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Path".into()
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+// `_UniffiUrl` is an alias for either `str` or `urllib.parse.ParseResult`, depending on the config
+#[derive(Debug)]
+pub struct UrlCodeType;
+
+impl CodeType for UrlCodeType {
+    fn type_label(&self) -> String {
+        "_UniffiUrl".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Url".into()
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
@@ -4,6 +4,7 @@
 
 use anyhow::{Context, Result};
 use askama::Template;
+use camino::Utf8Path;
 
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use once_cell::sync::Lazy;
@@ -14,6 +15,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 
 use crate::backend::TemplateExpression;
+use crate::bindings::{NameTemplate, TemplateOverride, TemplateOverrides};
 
 use crate::interface::*;
 use crate::VisitMut;
@@ -111,15 +113,28 @@ static KEYWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
 
 // Config options to customize the generated python.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     pub(super) cdylib_name: Option<String>,
+    pub(super) package_version: Option<String>,
+    module_name: Option<NameTemplate>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    // The module names of the other components being generated, keyed by namespace.  Filled in
+    // by `update_component_configs`.
+    #[serde(skip)]
+    pub(super) external_module_names: HashMap<String, String>,
+    #[serde(default)]
+    url_type: UrlType,
+    template_overrides_dir: Option<String>,
+    #[serde(skip)]
+    pub(super) template_overrides: TemplateOverrides,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CustomTypeConfig {
     // This `CustomTypeConfig` doesn't have a `type_name` like the others -- which is why we have
     // separate structs rather than a shared one.
@@ -129,6 +144,22 @@ pub struct CustomTypeConfig {
 }
 
 impl Config {
+    /// Load the templates from `template_overrides_dir`, see [TemplateOverrides]
+    pub(super) fn load_template_overrides(&mut self) -> Result<()> {
+        if let Some(dir) = &self.template_overrides_dir {
+            self.template_overrides = TemplateOverrides::load(Utf8Path::new(dir), "py")?;
+        }
+        Ok(())
+    }
+
+    /// The override for the record `name`, whose type is `type_name` in the bindings
+    pub(crate) fn record_override(&self, name: &str, type_name: &str) -> TemplateOverride<'_> {
+        self.template_overrides.get(
+            "RecordTemplate.py",
+            &[("name", name), ("type_name", type_name)],
+        )
+    }
+
     pub fn cdylib_name(&self) -> String {
         if let Some(cdylib_name) = &self.cdylib_name {
             cdylib_name.clone()
@@ -137,22 +168,54 @@ impl Config {
         }
     }
 
+    /// The name of the Python module for a component, `<namespace>` by default
+    pub fn module_name(&self, ci: &ComponentInterface) -> Result<String> {
+        match &self.module_name {
+            Some(template) => template.render(ci),
+            None => Ok(ci.namespace().to_string()),
+        }
+    }
+
     /// Get the package name for a given external namespace.
     pub fn module_for_namespace(&self, ns: &str) -> String {
         let ns = ns.to_string().to_snake_case();
+        let module = self.external_module_names.get(&ns).unwrap_or(&ns);
         match self.external_packages.get(&ns) {
-            None => format!(".{ns}"),
-            Some(value) if value.is_empty() => ns,
-            Some(value) => format!("{value}.{ns}"),
+            None => format!(".{module}"),
+            Some(value) if value.is_empty() => module.clone(),
+            Some(value) => format!("{value}.{module}"),
         }
     }
+
+    /// Whether `url::Url` values are a `urllib.parse.ParseResult`, rather than a `str`
+    pub fn urls_as_parse_results(&self) -> bool {
+        self.url_type == UrlType::ParseResult
+    }
+}
+
+/// The Python types `url::Url` can be, set with `url_type`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UrlType {
+    #[default]
+    #[serde(rename = "str")]
+    Str,
+    #[serde(rename = "urllib.parse.ParseResult")]
+    ParseResult,
 }
 
 // Generate python bindings for the given ComponentInterface, as a string.
 pub fn generate_python_bindings(config: &Config, ci: &mut ComponentInterface) -> Result<String> {
-    PythonWrapper::new(config.clone(), ci)
-        .render()
-        .context("failed to render python bindings")
+    // `PythonWrapper::new` needs `ci` mutably, so the variables can't borrow it
+    let (namespace, crate_name) = (ci.namespace().to_string(), ci.crate_name().to_string());
+    config.template_overrides.render(
+        "wrapper.py",
+        &[("namespace", &namespace), ("crate_name", &crate_name)],
+        || {
+            PythonWrapper::new(config.clone(), ci)
+                .render()
+                .context("failed to render python bindings")
+        },
+    )
 }
 
 /// A struct to record a Python import statement.
@@ -355,6 +418,11 @@ impl PythonCodeOracle {
         fixup_keyword(nm.to_string().to_shouty_snake_case())
     }
 
+    /// Get the idiomatic Python rendering of a constant name.
+    fn const_name(&self, nm: &str) -> String {
+        fixup_keyword(nm.to_string().to_shouty_snake_case())
+    }
+
     /// Get the idiomatic Python rendering of an FFI callback function name
     fn ffi_callback_name(&self, nm: &str) -> String {
         format!("_UNIFFI_{}", nm.to_shouty_snake_case())
@@ -547,13 +615,20 @@ impl<T: AsType> AsCodeType for T {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(primitives::UInt128CodeType),
+            Type::Int128 => Box::new(primitives::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::Date => Box::new(miscellany::DateCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
@@ -593,6 +668,11 @@ pub mod filters {
         Ok(PythonCodeOracle.var_name(nm))
     }
 
+    /// Get the idiomatic Python rendering of a constant name.
+    pub fn const_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(PythonCodeOracle.const_name(nm))
+    }
+
     //TODO: Remove. Currently just being used by wrapper.py to display the
     // callback_interface function names.
     /// Get the idiomatic Python rendering of a class name (for enums, records, errors, etc).
@@ -620,6 +700,18 @@ pub mod filters {
         Ok(format!("{}.lower", ffi_converter_name(as_ct)?))
     }
 
+    /// The lowered `self` argument of a method.
+    ///
+    /// This is a pointer for object methods, or the lowered value for record and enum methods.
+    /// Consuming methods take the object's pointer rather than cloning it.
+    pub(super) fn lower_self(callable: &impl Callable) -> Result<String, askama::Error> {
+        Ok(match callable.value_self_type() {
+            Some(self_type) => format!("{}(self)", lower_fn(&self_type)?),
+            None if callable.consumes_self() => "self._uniffi_take_pointer()".to_string(),
+            None => "self._uniffi_clone_pointer()".to_string(),
+        })
+    }
+
     pub(super) fn read_fn(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(format!("{}.read", ffi_converter_name(as_ct)?))
     }
@@ -630,9 +722,17 @@ pub mod filters {
 
     pub(super) fn literal_py(
         literal: &Literal,
-        as_ct: &impl AsCodeType,
+        as_type: &impl AsType,
+        ci: &ComponentInterface,
     ) -> Result<String, askama::Error> {
-        Ok(as_ct.as_codetype().literal(literal))
+        let rendered = as_type.as_codetype().literal(literal);
+        // The variants of enums with fields are classes, which need to be instantiated
+        if let (Literal::Enum(..), Type::Enum { name, .. }) = (literal, as_type.as_type()) {
+            if ci.get_enum_definition(&name).is_some_and(|e| !e.is_flat()) {
+                return Ok(format!("{rendered}()"));
+            }
+        }
+        Ok(rendered)
     }
 
     // Get the idiomatic Python rendering of an individual enum variant's discriminant
@@ -641,6 +741,16 @@ pub mod filters {
         Ok(Type::UInt64.as_codetype().literal(&literal))
     }
 
+    // The index of the first field of an enum variant with a default value.  Its constructor's
+    // arguments are keyword-only from there, so fields without defaults can follow it.
+    pub fn keyword_only_from(variant: &Variant) -> Result<usize, askama::Error> {
+        Ok(variant
+            .fields()
+            .iter()
+            .position(|f| f.default_value().is_some())
+            .unwrap_or(usize::MAX))
+    }
+
     pub fn ffi_type_name(type_: &FfiType) -> Result<String, askama::Error> {
         Ok(PythonCodeOracle.ffi_type_label(type_))
     }
@@ -667,14 +777,57 @@ pub mod filters {
     /// Get the idiomatic Python rendering of docstring
     pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
         let docstring = textwrap::dedent(docstring);
-        // Escape triple quotes to avoid syntax error
-        let escaped = docstring.replace(r#"""""#, r#"\"\"\""#);
+        // Escape backslashes so they're not read as escape sequences, then triple quotes to avoid
+        // syntax error
+        let escaped = docstring
+            .replace('\\', r"\\")
+            .replace(r#"""""#, r#"\"\"\""#);
 
         let wrapped = format!("\"\"\"\n{escaped}\n\"\"\"");
 
         let spaces = usize::try_from(*spaces).unwrap_or_default();
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
+
+    /// Get the Python rendering of a docstring with a note about a deprecation appended
+    pub fn deprecated_docstring(
+        docstring: Option<&str>,
+        message: &str,
+        spaces: &i32,
+    ) -> Result<String, askama::Error> {
+        let note = if message.is_empty() {
+            "Deprecated.".to_string()
+        } else {
+            format!("Deprecated: {message}")
+        };
+        let text = match docstring {
+            Some(docstring) => format!("{}\n\n{note}", textwrap::dedent(docstring)),
+            None => note,
+        };
+        self::docstring(&text, spaces)
+    }
+
+    /// Get the `warnings.warn()` call for a deprecated function, method or constructor
+    pub fn deprecation_warning(
+        message: &str,
+        name: &str,
+        spaces: &i32,
+    ) -> Result<String, askama::Error> {
+        let message = if message.is_empty() {
+            format!("{name} is deprecated")
+        } else {
+            message.to_string()
+        };
+        let escaped = message
+            .replace('\\', r"\\")
+            .replace('"', r#"\""#)
+            .replace('\n', r"\n");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(format!(
+            "{}warnings.warn(\"{escaped}\", DeprecationWarning, stacklevel=2)",
+            " ".repeat(spaces)
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -695,4 +848,38 @@ And a even longer quote: \"\"\"""
 
         assert_eq!(super::filters::docstring(docstring, &0).unwrap(), expected);
     }
+
+    #[test]
+    fn test_docstring_escape_backslashes() {
+        assert_eq!(
+            super::filters::docstring(r#"Matches `\d+`, like "\"1\""."#, &0).unwrap(),
+            r#""""
+Matches `\\d+`, like "\\"1\\"".
+""""#
+        );
+    }
+
+    #[test]
+    fn test_deprecated_docstring() {
+        assert_eq!(
+            super::filters::deprecated_docstring(Some("  Fetch it."), "use fetch()", &0).unwrap(),
+            "\"\"\"\nFetch it.\n\nDeprecated: use fetch()\n\"\"\""
+        );
+        assert_eq!(
+            super::filters::deprecated_docstring(None, "", &4).unwrap(),
+            "    \"\"\"\n    Deprecated.\n    \"\"\""
+        );
+    }
+
+    #[test]
+    fn test_deprecation_warning_escape() {
+        assert_eq!(
+            super::filters::deprecation_warning(r#"use "fetch()"\n"#, "fetch_sync", &4).unwrap(),
+            r#"    warnings.warn("use \"fetch()\"\\n", DeprecationWarning, stacklevel=2)"#
+        );
+        assert_eq!(
+            super::filters::deprecation_warning("", "fetch_sync", &0).unwrap(),
+            r#"warnings.warn("fetch_sync is deprecated", DeprecationWarning, stacklevel=2)"#
+        );
+    }
 }
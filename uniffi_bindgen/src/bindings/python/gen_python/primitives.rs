@@ -29,6 +29,8 @@ fn render_literal(literal: &Literal) -> String {
             Radix::Hexadecimal => format!("{i:#x}"),
         },
         Literal::Float(string, _type_) => string.clone(),
+        // Rust's escapes for bytes are all valid in Python bytes literals.
+        Literal::Bytes(bytes) => format!("b\"{}\"", bytes.escape_ascii()),
 
         _ => unreachable!("Literal"),
     }
@@ -57,6 +59,7 @@ macro_rules! impl_code_type_for_primitive {
 }
 
 impl_code_type_for_primitive!(BooleanCodeType, "bool", "Bool");
+impl_code_type_for_primitive!(CharCodeType, "str", "Char");
 impl_code_type_for_primitive!(StringCodeType, "str", "String");
 impl_code_type_for_primitive!(BytesCodeType, "bytes", "Bytes");
 impl_code_type_for_primitive!(Int8CodeType, "int", "Int8");
@@ -67,5 +70,7 @@ impl_code_type_for_primitive!(UInt8CodeType, "int", "UInt8");
 impl_code_type_for_primitive!(UInt16CodeType, "int", "UInt16");
 impl_code_type_for_primitive!(UInt32CodeType, "int", "UInt32");
 impl_code_type_for_primitive!(UInt64CodeType, "int", "UInt64");
+impl_code_type_for_primitive!(Int128CodeType, "int", "Int128");
+impl_code_type_for_primitive!(UInt128CodeType, "int", "UInt128");
 impl_code_type_for_primitive!(Float32CodeType, "float", "Float");
 impl_code_type_for_primitive!(Float64CodeType, "float", "Double");
@@ -5,14 +5,19 @@
 use std::process::Command;
 
 use anyhow::Result;
+use camino::Utf8PathBuf;
 use fs_err as fs;
+use heck::ToSnakeCase;
+use std::collections::HashMap;
 
 mod gen_python;
 #[cfg(feature = "bindgen-tests")]
 pub mod test;
-use crate::{BindingGenerator, Component, GenerationSettings};
+use crate::diagnostics::{Code, Diagnostic};
+use crate::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
 
-use gen_python::{generate_python_bindings, Config};
+use gen_python::generate_python_bindings;
+pub use gen_python::Config;
 
 pub struct PythonBindingGenerator;
 
@@ -20,12 +25,9 @@ impl BindingGenerator for PythonBindingGenerator {
     type Config = Config;
 
     fn new_config(&self, root_toml: &toml::Value) -> Result<Self::Config> {
-        Ok(
-            match root_toml.get("bindings").and_then(|b| b.get("python")) {
-                Some(v) => v.clone().try_into()?,
-                None => Default::default(),
-            },
-        )
+        let mut config: Config = crate::config::bindings_config(root_toml, "python")?;
+        config.load_template_overrides()?;
+        Ok(config)
     }
 
     fn update_component_configs(
@@ -40,6 +42,24 @@ impl BindingGenerator for PythonBindingGenerator {
                     .clone()
                     .unwrap_or_else(|| format!("uniffi_{}", c.ci.namespace()))
             });
+            if let Some(package) = &c.package {
+                c.config
+                    .package_version
+                    .get_or_insert_with(|| package.version.clone());
+            }
+        }
+        // Imports of external types need the module names of the other components
+        let module_names = components
+            .iter()
+            .map(|c| {
+                Ok((
+                    c.ci.namespace().to_snake_case(),
+                    c.config.module_name(&c.ci)?,
+                ))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        for c in components {
+            c.config.external_module_names = module_names.clone();
         }
         Ok(())
     }
@@ -48,21 +68,108 @@ impl BindingGenerator for PythonBindingGenerator {
         &self,
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
-    ) -> Result<()> {
+    ) -> Result<Vec<Utf8PathBuf>> {
+        let mut written_files = vec![];
         for Component { ci, config, .. } in components {
-            let py_file = settings.out_dir.join(format!("{}.py", ci.namespace()));
-            fs::write(&py_file, generate_python_bindings(config, &mut ci.clone())?)?;
-
-            if settings.try_format_code {
-                if let Err(e) = Command::new("yapf").arg(&py_file).output() {
-                    println!(
-                        "Warning: Unable to auto-format {} using yapf: {e:?}",
-                        py_file.file_name().unwrap(),
-                    )
-                }
-            }
+            let py_file = settings
+                .out_dir
+                .join(format!("{}.py", config.module_name(ci)?));
+            fs::write(&py_file, generate_source(ci, config)?)?;
+            written_files.push(py_file);
         }
 
+        Ok(written_files)
+    }
+
+    /// Format all the generated files with a single `yapf` run.
+    fn finalize(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        if settings.try_format_code && !components.is_empty() {
+            let py_files = components
+                .iter()
+                .map(|c| {
+                    Ok(settings
+                        .out_dir
+                        .join(format!("{}.py", c.config.module_name(&c.ci)?)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if let Err(e) = Command::new("yapf").args(py_files).output() {
+                Diagnostic::warning(
+                    Code::Format,
+                    format!("Unable to auto-format the Python bindings using yapf: {e:?}"),
+                )
+                .emit();
+            }
+        }
         Ok(())
     }
+
+    fn owned_file_patterns(&self) -> Vec<String> {
+        vec!["*.py".to_string()]
+    }
+}
+
+/// Generate the Python module for a component, as a string.
+///
+/// This is what [PythonBindingGenerator] writes to `<module_name>.py`.  The config should have been
+/// updated by [BindingGenerator::update_component_configs] first, like it is when generating
+/// bindings.
+pub fn generate_source(ci: &ComponentInterface, config: &Config) -> Result<String> {
+    generate_python_bindings(config, &mut ci.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_name_template() {
+        let root_toml: toml::Value = toml::de::from_str(
+            r#"
+            [bindings.python]
+            module_name = "{namespace}_ffi"
+        "#,
+        )
+        .unwrap();
+        let config = PythonBindingGenerator.new_config(&root_toml).unwrap();
+        let mut components = vec![
+            Component {
+                ci: ComponentInterface::from_webidl(
+                    "namespace shared { }; dictionary Point { u32 x; };",
+                    "shared",
+                )
+                .unwrap(),
+                config: config.clone(),
+                package: None,
+            },
+            Component {
+                ci: ComponentInterface::from_webidl(
+                    "namespace example { }; dictionary Line { u32 length; };",
+                    "example",
+                )
+                .unwrap(),
+                config,
+                package: None,
+            },
+        ];
+        PythonBindingGenerator
+            .update_component_configs(&GenerationSettings::default(), &mut components)
+            .unwrap();
+        let Component { ci, config, .. } = &components[1];
+        assert_eq!(config.module_name(ci).unwrap(), "example_ffi");
+        // Imports of the other component's types use its module name
+        assert_eq!(config.module_for_namespace("shared"), ".shared_ffi");
+
+        let root_toml: toml::Value = toml::de::from_str(
+            r#"
+            [bindings.python]
+            module_name = "{namespace"
+        "#,
+        )
+        .unwrap();
+        assert!(PythonBindingGenerator.new_config(&root_toml).is_err());
+    }
 }
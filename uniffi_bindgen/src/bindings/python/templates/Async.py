@@ -67,12 +67,17 @@ def _uniffi_trait_interface_call_async(make_call, handle_success, handle_error):
     async def make_call_and_call_callback():
         try:
             handle_success(await make_call())
+        except asyncio.CancelledError as e:
+            handle_error(
+                _UniffiRustCallStatus.CALL_CANCELLED,
+                _uniffi_lower_unexpected_error(e),
+            )
         except Exception as e:
             print("UniFFI: Unhandled exception in trait interface call", file=sys.stderr)
             traceback.print_exc(file=sys.stderr)
             handle_error(
                 _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR,
-                {{ Type::String.borrow()|lower_fn }}(repr(e)),
+                _uniffi_lower_unexpected_error(e),
             )
     eventloop = _uniffi_get_event_loop()
     task = asyncio.run_coroutine_threadsafe(make_call_and_call_callback(), eventloop)
@@ -89,12 +94,17 @@ def _uniffi_trait_interface_call_async_with_error(make_call, handle_success, han
                     _UniffiRustCallStatus.CALL_ERROR,
                     lower_error(e),
                 )
+        except asyncio.CancelledError as e:
+            handle_error(
+                _UniffiRustCallStatus.CALL_CANCELLED,
+                _uniffi_lower_unexpected_error(e),
+            )
         except Exception as e:
             print("UniFFI: Unhandled exception in trait interface call", file=sys.stderr)
             traceback.print_exc(file=sys.stderr)
             handle_error(
                 _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR,
-                {{ Type::String.borrow()|lower_fn }}(repr(e)),
+                _uniffi_lower_unexpected_error(e),
             )
     eventloop = _uniffi_get_event_loop()
     task = asyncio.run_coroutine_threadsafe(make_call_and_call_callback(), eventloop)
@@ -4,6 +4,7 @@
 {%- let protocol_docstring = cbi.docstring() %}
 {%- let vtable = cbi.vtable() %}
 {%- let methods = cbi.methods() %}
+{%- let constants = cbi.constants() %}
 {%- let vtable_methods = cbi.vtable_methods() %}
 
 {% include "Protocol.py" %}
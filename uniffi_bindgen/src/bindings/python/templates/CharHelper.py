@@ -0,0 +1,26 @@
+# Chars are passed as their Unicode scalar value, and are a `str` of length 1 in Python.
+class _UniffiConverterChar:
+    @classmethod
+    def check_lower(cls, value):
+        if not isinstance(value, str):
+            raise TypeError("char requires a str, not {}".format(type(value).__name__))
+        if len(value) != 1:
+            raise ValueError("char requires a str of length 1, not {}".format(len(value)))
+        if 0xD800 <= ord(value) <= 0xDFFF:
+            raise ValueError("char can't be a surrogate ({!r})".format(value))
+
+    @staticmethod
+    def lower(value):
+        return ord(value)
+
+    @staticmethod
+    def lift(value):
+        return chr(value)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_u32())
+
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_u32(cls.lower(value))
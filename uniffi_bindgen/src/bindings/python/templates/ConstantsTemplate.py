@@ -0,0 +1,5 @@
+{%- for constant in ci.constant_definitions() %}
+
+{{ constant.name()|const_name }}: "{{ constant|type_name }}" = {{ constant.value()|literal_py(constant, ci) }}
+{%- call py::docstring(constant, 0) %}
+{%- endfor %}
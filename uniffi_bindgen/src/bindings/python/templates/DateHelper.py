@@ -0,0 +1,26 @@
+# Dates are passed as the number of days since 1970-01-01, and are a `datetime.date` in Python.
+class _UniffiConverterDate:
+    # `toordinal()` counts 0001-01-01 as day 1
+    _EPOCH_ORDINAL = datetime.date(1970, 1, 1).toordinal()
+
+    @staticmethod
+    def check_lower(value):
+        # `datetime.datetime` is a subclass of `datetime.date`, but would lose its time here
+        if not isinstance(value, datetime.date) or isinstance(value, datetime.datetime):
+            raise TypeError("date requires a datetime.date, not {}".format(type(value).__name__))
+
+    @classmethod
+    def lower(cls, value):
+        return value.toordinal() - cls._EPOCH_ORDINAL
+
+    @classmethod
+    def lift(cls, value):
+        return datetime.date.fromordinal(value + cls._EPOCH_ORDINAL)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_i32())
+
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_i32(cls.lower(value))
@@ -10,8 +10,11 @@ class {{ type_name }}(enum.Enum):
     {%- call py::docstring(e, 4) %}
     {%- for variant in e.variants() %}
     {{ variant.name() }} = {{ e|variant_discr_literal(loop.index0) }}
-    {%- call py::docstring(variant, 4) %}
+    {%- call py::deprecated_docstring(variant, 4) %}
     {% endfor %}
+    {%- for meth in e.methods() %}
+    {%- call py::method_decl(meth.name(), meth) %}
+    {%- endfor %}
 {% else %}
 
 class {{ type_name }}:
@@ -22,7 +25,7 @@ class {{ type_name }}:
     # Each enum variant is a nested class of the enum itself.
     {% for variant in e.variants() -%}
     class {{ variant.name() }}:
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::deprecated_docstring(variant, 8) %}
 
     {%-  if variant.has_nameless_fields() %}
         def __init__(self, *values):
@@ -48,13 +51,23 @@ class {{ type_name }}:
     {%-  else -%}
         {%- for field in variant.fields() %}
         {{ field.name() }}: "{{ field|type_name }}"
-        {%- call py::docstring(field, 8) %}
+        {%- call py::deprecated_docstring(field, 8) %}
         {%- endfor %}
 
-        def __init__(self,{% for field in variant.fields() %}{{ field.name() }}: "{{- field|type_name }}"{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
+        {%- let keyword_only_from = variant|keyword_only_from %}
+        def __init__(self,{% for field in variant.fields() %}{% if loop.index0 == keyword_only_from %}*, {% endif %}{{ field.name() }}: "{{- field|type_name }}"{% if field.default_value().is_some() %} = _DEFAULT{% endif %}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
             {%- if variant.has_fields() %}
             {%- for field in variant.fields() %}
-            self.{{ field.name() }} = {{ field.name() }}
+            {%- let field_name = field.name() %}
+            {%- match field.default_value() %}
+            {%- when None %}
+            self.{{ field_name }} = {{ field_name }}
+            {%- when Some with(literal) %}
+            if {{ field_name }} is _DEFAULT:
+                self.{{ field_name }} = {{ literal|literal_py(field, ci) }}
+            else:
+                self.{{ field_name }} = {{ field_name }}
+            {%- endmatch %}
             {%- endfor %}
             {%- else %}
             pass
@@ -80,6 +93,9 @@ class {{ type_name }}:
     def is_{{ variant.name()|var_name }}(self) -> bool:
         return isinstance(self, {{ type_name }}.{{ variant.name() }})
     {% endfor %}
+    {%- for meth in e.methods() %}
+    {%- call py::method_decl(meth.name(), meth) %}
+    {%- endfor %}
 
 # Now, a little trick - we make each nested variant class be a subclass of the main
 # enum class, so that method calls and instance checks etc will work intuitively.
@@ -102,7 +118,11 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
             {%- else %}
             return {{ type_name }}.{{variant.name()}}(
                 {%- for field in variant.fields() %}
+                {%- if variant|keyword_only_from <= loop.index0 %}
+                {{ field.name() }}={{ field|read_fn }}(buf),
+                {%- else %}
                 {{ field|read_fn }}(buf),
+                {%- endif %}
                 {%- endfor %}
             )
             {%- endif %}
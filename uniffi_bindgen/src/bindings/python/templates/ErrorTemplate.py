@@ -16,13 +16,13 @@ class {{ type_name }}:  # type: ignore
     {%- let variant_type_name = variant.name() -%}
     {%- if e.is_flat() %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::deprecated_docstring(variant, 8) %}
 
         def __repr__(self):
             return "{{ type_name }}.{{ variant_type_name }}({})".format(repr(str(self)))
     {%- else %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::deprecated_docstring(variant, 8) %}
 
     {%-     if variant.has_nameless_fields() %}
         def __init__(self, *values):
@@ -34,11 +34,21 @@ class {{ type_name }}:  # type: ignore
         {%- endfor %}
             super().__init__(", ".join(map(repr, values)))
             self._values = values
+        {%- for field in variant.fields() %}
+        {%- if field.is_source() %}
+            self.__cause__ = {% call py::error_cause(field, "values[{}]"|format(loop.index0)) %}
+        {%- endif %}
+        {%- endfor %}
 
         def __getitem__(self, index):
             return self._values[index]
 
     {%-     else %}
+        {%- for field in variant.fields() %}
+        {{ field.name() }}: "{{ field|type_name }}"
+        {%- call py::deprecated_docstring(field, 8) %}
+        {%- endfor %}
+
         def __init__(self{% for field in variant.fields() %}, {{ field.name() }}{% endfor %}):
             {%- if variant.has_fields() %}
             super().__init__(", ".join([
@@ -49,6 +59,11 @@ class {{ type_name }}:  # type: ignore
             {%- for field in variant.fields() %}
             self.{{ field.name() }} = {{ field.name() }}
             {%- endfor %}
+            {%- for field in variant.fields() %}
+            {%- if field.is_source() %}
+            self.__cause__ = {% call py::error_cause(field, field.name()) %}
+            {%- endif %}
+            {%- endfor %}
             {%- else %}
             pass
             {%- endif %}
@@ -1,6 +1,13 @@
 {%- let module = python_config.module_for_namespace(namespace) -%}
 
 # External type {{name}} is in namespace "{{namespace}}", crate {{module_path}}
+{%- match ci.external_type_docstring(module_path, name) %}
+{%- when Some(docstring) %}
+{%- for line in docstring.lines() %}
+#{% if !line.is_empty() %} {{ line }}{% endif %}
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
 {%- let ffi_converter_name = "_UniffiConverterType{}"|format(name) %}
 {{ self.add_import_of(module, ffi_converter_name) }}
 {{ self.add_import_of(module, name) }} {#- import the type alias itself -#}
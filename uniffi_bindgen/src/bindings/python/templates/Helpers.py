@@ -17,6 +17,7 @@ class _UniffiRustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_UNEXPECTED_ERROR = 2
+    CALL_CANCELLED = 3
 
     @staticmethod
     def default():
@@ -29,6 +30,8 @@ class _UniffiRustCallStatus(ctypes.Structure):
             return "_UniffiRustCallStatus(CALL_ERROR)"
         elif self.code == _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR:
             return "_UniffiRustCallStatus(CALL_UNEXPECTED_ERROR)"
+        elif self.code == _UniffiRustCallStatus.CALL_CANCELLED:
+            return "_UniffiRustCallStatus(CALL_CANCELLED)"
         else:
             return "_UniffiRustCallStatus(<invalid code>)"
 
@@ -70,12 +73,20 @@ def _uniffi_check_call_status(error_ffi_converter, call_status):
         raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(
             call_status.code))
 
+def _uniffi_lower_unexpected_error(e):
+    # Lower an exception that a trait interface method didn't declare.
+    # The buffer holds the exception's repr followed by its class name.
+    with _UniffiRustBuffer.alloc_with_builder() as builder:
+        {{ Type::String.borrow()|ffi_converter_name }}.write(repr(e), builder)
+        {{ Type::String.borrow()|ffi_converter_name }}.write(f"{type(e).__module__}.{type(e).__qualname__}", builder)
+        return builder.finalize()
+
 def _uniffi_trait_interface_call(call_status, make_call, write_return_value):
     try:
         return write_return_value(make_call())
     except Exception as e:
         call_status.code = _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR
-        call_status.error_buf = {{ Type::String.borrow()|lower_fn }}(repr(e))
+        call_status.error_buf = _uniffi_lower_unexpected_error(e)
 
 def _uniffi_trait_interface_call_with_error(call_status, make_call, write_return_value, error_type, lower_error):
     try:
@@ -86,4 +97,4 @@ def _uniffi_trait_interface_call_with_error(call_status, make_call, write_return
             call_status.error_buf = lower_error(e)
     except Exception as e:
         call_status.code = _UniffiRustCallStatus.CALL_UNEXPECTED_ERROR
-        call_status.error_buf = {{ Type::String.borrow()|lower_fn }}(repr(e))
+        call_status.error_buf = _uniffi_lower_unexpected_error(e)
@@ -0,0 +1,25 @@
+# There's no 128-bit integer in the C ABI, so these are passed as 16 big-endian bytes in a buffer.
+class _UniffiConverterInt128(_UniffiConverterRustBuffer):
+    CLASS_NAME = "i128"
+    VALUE_MIN = -2**127
+    VALUE_MAX = 2**127
+
+    # Same checks as `_UniffiConverterPrimitiveInt`, since this isn't a primitive on the FFI.
+    @classmethod
+    def check_lower(cls, value):
+        try:
+            value = value.__index__()
+        except Exception:
+            raise TypeError("'{}' object cannot be interpreted as an integer".format(type(value).__name__))
+        if not isinstance(value, int):
+            raise TypeError("__index__ returned non-int (type {})".format(type(value).__name__))
+        if not cls.VALUE_MIN <= value < cls.VALUE_MAX:
+            raise ValueError("{} requires {} <= value < {}".format(cls.CLASS_NAME, cls.VALUE_MIN, cls.VALUE_MAX))
+
+    @staticmethod
+    def read(buf):
+        return int.from_bytes(buf.read(16), "big", signed=True)
+
+    @staticmethod
+    def write(value, buf):
+        buf.write(value.__index__().to_bytes(16, "big", signed=True))
@@ -0,0 +1,25 @@
+{{ self.add_import("json") }}
+# JSON values are passed as their JSON text, and are the values `json.loads()` returns in Python:
+# `dict`, `list`, `str`, `int`, `float`, `bool` or `None`.
+class _UniffiConverterJson(_UniffiConverterRustBuffer):
+    @staticmethod
+    def _dumps(value):
+        return json.dumps(value, ensure_ascii=False, allow_nan=False, separators=(",", ":"))
+
+    @classmethod
+    def check_lower(cls, value):
+        # Raises a `TypeError` or `ValueError` for values that can't be written as JSON
+        cls._dumps(value)
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative JSON length")
+        return json.loads(buf.read(size).decode("utf-8"))
+
+    @classmethod
+    def write(cls, value, buf):
+        utf8_bytes = cls._dumps(value).encode("utf-8")
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
@@ -1,6 +1,7 @@
 {%- let obj = ci|get_object_definition(name) %}
 {%- let (protocol_name, impl_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
+{%- let constants = obj.constants() %}
 {%- let protocol_docstring = obj.docstring() %}
 
 {% include "Protocol.py" %}
@@ -12,16 +13,24 @@ class {{ impl_name }}:
 {%- endif %}
     {%- call py::docstring(obj, 4) %}
     _pointer: ctypes.c_void_p
+    {%- for constant in constants.iter() %}
+    {{ constant.name()|const_name }}: "{{ constant|type_name }}" = {{ constant.value()|literal_py(constant, ci) }}
+    {%- call py::docstring(constant, 4) %}
+    {%- endfor %}
 
 {%- match obj.primary_constructor() %}
 {%-     when Some with (cons) %}
 {%-         if cons.is_async() %}
     def __init__(self, *args, **kw):
-        raise ValueError("async constructors not supported.")
+        raise ValueError("async constructors not supported, use `await {{ impl_name }}.{{ cons.name() }}()` instead.")
 {%-         else %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
-        {%- call py::docstring(cons, 8) %}
+        {%- call py::callable_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
+{%-             if obj.has_consuming_method() %}
+        # Guards `_pointer`, which consuming methods take from the object.
+        self._uniffi_pointer_lock = threading.Lock()
+{%-             endif %}
         self._pointer = {% call py::to_ffi_call(cons) %}
 {%-         endif %}
 {%-     when None %}
@@ -37,7 +46,23 @@ class {{ impl_name }}:
             _uniffi_rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
 
     def _uniffi_clone_pointer(self):
+{%- if obj.has_consuming_method() %}
+        # Hold the lock while cloning, so a consuming method can't free the pointer under us.
+        with self._uniffi_pointer_lock:
+            if self._pointer is None:
+                raise ValueError("{{ impl_name }} object has already been consumed")
+            return _uniffi_rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self._pointer)
+
+    def _uniffi_take_pointer(self):
+        # Consuming methods pass the object's own pointer to Rust, leaving the object unusable.
+        with self._uniffi_pointer_lock:
+            if self._pointer is None:
+                raise ValueError("{{ impl_name }} object has already been consumed")
+            pointer, self._pointer = self._pointer, None
+            return pointer
+{%- else %}
         return _uniffi_rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self._pointer)
+{%- endif %}
 
     # Used by alternative constructors or any methods which return this type.
     @classmethod
@@ -45,14 +70,17 @@ class {{ impl_name }}:
         # Lightly yucky way to bypass the usual __init__ logic
         # and just create a new instance with the required pointer.
         inst = cls.__new__(cls)
+{%- if obj.has_consuming_method() %}
+        inst._uniffi_pointer_lock = threading.Lock()
+{%- endif %}
         inst._pointer = pointer
         return inst
 
-{%- for cons in obj.alternate_constructors() %}
+{%- for cons in obj.factory_constructors() %}
     @classmethod
 {%-  if cons.is_async() %}
     async def {{ cons.name() }}(cls, {% call py::arg_list_decl(cons) %}):
-        {%- call py::docstring(cons, 8) %}
+        {%- call py::callable_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
 
         return await _uniffi_rust_call_async(
@@ -65,7 +93,7 @@ class {{ impl_name }}:
         )
 {%-  else %}
     def {{ cons.name() }}(cls, {% call py::arg_list_decl(cons) %}):
-        {%- call py::docstring(cons, 8) %}
+        {%- call py::callable_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
         # Call the (fallible) function before creating any half-baked object instances.
         pointer = {% call py::to_ffi_call(cons) %}
@@ -73,8 +101,15 @@ class {{ impl_name }}:
 {%-  endif %}
 {% endfor %}
 
+{%- for meth in obj.static_methods() -%}
+    {%- call py::static_method_decl(meth) %}
+{%- endfor %}
 {%- for meth in obj.methods() -%}
+{%-     if meth.is_getter() %}
+    {%- call py::getter_decl(meth) %}
+{%-     else %}
     {%- call py::method_decl(meth.name(), meth) %}
+{%-     endif %}
 {%- endfor %}
 {%- for tm in obj.uniffi_traits() -%}
 {%-     match tm %}
@@ -96,6 +131,33 @@ class {{ impl_name }}:
         return {{ ne.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._uniffi_clone_pointer()", ne) %})
 {%-         when UniffiTrait::Hash { hash } %}
             {%- call py::method_decl("__hash__", hash) %}
+{%-         when UniffiTrait::Ord { cmp } %}
+    def _uniffi_cmp(self, other: {{ type_name }}) -> int:
+        return {{ cmp.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._uniffi_clone_pointer()", cmp) %})
+
+    def __lt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+
+        return self._uniffi_cmp(other) < 0
+
+    def __le__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+
+        return self._uniffi_cmp(other) <= 0
+
+    def __gt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+
+        return self._uniffi_cmp(other) > 0
+
+    def __ge__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+
+        return self._uniffi_cmp(other) >= 0
 {%-      endmatch %}
 {%- endfor %}
 
@@ -0,0 +1,22 @@
+{{ self.add_import("pathlib") }}
+# Paths are passed as their bytes, using the file system encoding. Rust paths which aren't valid
+# UTF-8 are returned with the same bytes `os.fsencode()` gives back. Any path-like value, such as
+# a `str` or a `pathlib.Path`, can be passed in.
+class _UniffiConverterPath(_UniffiConverterRustBuffer):
+    @staticmethod
+    def check_lower(value):
+        # Raises a `TypeError` for values which aren't path-like
+        os.fspath(value)
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative path length")
+        return pathlib.Path(os.fsdecode(buf.read(size)))
+
+    @staticmethod
+    def write(value, buf):
+        path_bytes = os.fsencode(value)
+        buf.write_i32(len(path_bytes))
+        buf.write(path_bytes)
@@ -1,9 +1,33 @@
 class {{ protocol_name }}(typing.Protocol):
     {%- call py::docstring_value(protocol_docstring, 4) %}
+    {%- for constant in constants.iter() %}
+    {{ constant.name()|const_name }}: "{{ constant|type_name }}" = {{ constant.value()|literal_py(constant, ci) }}
+    {%- call py::docstring(constant, 4) %}
+    {%- endfor %}
     {%- for meth in methods.iter() %}
+    {%- if meth.is_getter() %}
+    @property
+    def {{ meth.name() }}(self):
+        {%- call py::deprecated_docstring(meth, 8) %}
+        raise NotImplementedError
+    {%- else %}
     def {{ meth.name() }}(self, {% call py::arg_list_decl(meth) %}):
-        {%- call py::docstring(meth, 8) %}
+        {%- call py::deprecated_docstring(meth, 8) %}
+        {%- match meth.default_ffi_func() %}
+        {%- when Some with (default_ffi_func) %}
+        {%- call py::setup_args_extra_indent(meth) %}
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        return {{ return_type|lift_fn }}(
+            {% call py::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %}
+        )
+        {%- when None %}
+        {% call py::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %}
+        {%- endmatch %}
+        {%- else %}
         raise NotImplementedError
+        {%- endmatch %}
+    {%- endif %}
     {%- else %}
     pass
     {%- endfor %}
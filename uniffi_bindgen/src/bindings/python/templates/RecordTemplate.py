@@ -1,9 +1,9 @@
 {%- let rec = ci|get_record_definition(name) %}
 class {{ type_name }}:
-    {%- call py::docstring(rec, 4) %}
+    {%- call py::deprecated_docstring(rec, 4) %}
     {%- for field in rec.fields() %}
     {{ field.name() }}: "{{ field|type_name }}"
-    {%- call py::docstring(field, 4) %}
+    {%- call py::deprecated_docstring(field, 4) %}
     {%- endfor %}
 
     {%- if rec.has_fields() %}
@@ -19,7 +19,7 @@ class {{ type_name }}:
         self.{{ field_name }} = {{ field_name }}
         {%- when Some with(literal) %}
         if {{ field_name }} is _DEFAULT:
-            self.{{ field_name }} = {{ literal|literal_py(field) }}
+            self.{{ field_name }} = {{ literal|literal_py(field, ci) }}
         else:
             self.{{ field_name }} = {{ field_name }}
         {%- endmatch %}
@@ -35,6 +35,9 @@ class {{ type_name }}:
             return False
         {%- endfor %}
         return True
+    {%- for meth in rec.methods() %}
+    {%- call py::method_decl(meth.name(), meth) %}
+    {%- endfor %}
 
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
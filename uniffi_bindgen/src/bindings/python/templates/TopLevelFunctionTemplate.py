@@ -7,7 +7,7 @@ async def {{ func.name() }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_
 async def {{ func.name() }}({%- call py::arg_list_decl(func) -%}) -> None:
 {% endmatch %}
 
-    {%- call py::docstring(func, 4) %}
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
     return await _uniffi_rust_call_async(
         _UniffiLib.{{ func.ffi_func().name() }}({% call py::arg_list_lowered(func) %}),
@@ -29,13 +29,13 @@ async def {{ func.name() }}({%- call py::arg_list_decl(func) -%}) -> None:
 {%- when Some with (return_type) %}
 
 def {{ func.name() }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
-    {%- call py::docstring(func, 4) %}
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
     return {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
 {% when None %}
 
 def {{ func.name() }}({%- call py::arg_list_decl(func) -%}) -> None:
-    {%- call py::docstring(func, 4) %}
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
     {% call py::to_ffi_call(func) %}
 {% endmatch %}
@@ -19,6 +19,9 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.py" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.py" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.py" %}
 
@@ -43,6 +46,12 @@
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.py" %}
 
+{%- when Type::Int128 %}
+{%- include "Int128Helper.py" %}
+
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.py" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.py" %}
 
@@ -65,7 +74,12 @@
 {% endif %}
 
 {%- when Type::Record { name, module_path } %}
+{%- let record_override = python_config.record_override(name, type_name) %}
+{{- record_override.before() }}
+{%- if record_override.includes_builtin() %}
 {%- include "RecordTemplate.py" %}
+{%- endif %}
+{{- record_override.after() }}
 
 {%- when Type::Object { name, module_path, imp } %}
 {%- include "ObjectTemplate.py" %}
@@ -73,6 +87,18 @@
 {%- when Type::Timestamp %}
 {%- include "TimestampHelper.py" %}
 
+{%- when Type::Date %}
+{%- include "DateHelper.py" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.py" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.py" %}
+
+{%- when Type::Url %}
+{%- include "UrlHelper.py" %}
+
 {%- when Type::Duration %}
 {%- include "DurationHelper.py" %}
 
@@ -0,0 +1,40 @@
+{%- if python_config.urls_as_parse_results() %}
+{{ self.add_import("urllib.parse") }}
+_UniffiUrl = urllib.parse.ParseResult
+{%- else %}
+_UniffiUrl = str
+{%- endif %}
+
+# URLs are passed as their serialized text. Rust rejects text which isn't a valid URL. Both `str`s
+# and the results of `urllib.parse.urlparse()` can be passed in.
+class _UniffiConverterUrl(_UniffiConverterRustBuffer):
+    @staticmethod
+    def _text(value):
+        if isinstance(value, str):
+            return value
+        geturl = getattr(value, "geturl", None)
+        if geturl is None:
+            raise TypeError("'{}' is not a URL".format(type(value).__name__))
+        return geturl()
+
+    @classmethod
+    def check_lower(cls, value):
+        cls._text(value)
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative URL length")
+        text = buf.read(size).decode("utf-8")
+        {%- if python_config.urls_as_parse_results() %}
+        return urllib.parse.urlparse(text)
+        {%- else %}
+        return text
+        {%- endif %}
+
+    @classmethod
+    def write(cls, value, buf):
+        utf8_bytes = cls._text(value).encode("utf-8")
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
@@ -32,6 +32,30 @@ _uniffi_rust_call(
 )
 {%- endmacro -%}
 
+{#-
+// Call the Rust default implementation of a trait method, for a foreign implementation which
+// doesn't override it.
+-#}
+{%- macro to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) -%}
+{%- match meth.throws_type() -%}
+{%-     when Some with (e) -%}
+{%-         match e -%}
+{%-             when Type::Enum { name, module_path } -%}
+_uniffi_rust_call_with_error({{ e|ffi_converter_name }},
+{%-             when Type::Object { name, module_path, imp } -%}
+_uniffi_rust_call_with_error({{ e|ffi_converter_name }}__as_error,
+{%-             else %}
+# unsupported error type!
+{%-         endmatch %}
+{%- else -%}
+_uniffi_rust_call(
+{%- endmatch -%}
+    _UniffiLib.{{ default_ffi_func.name() }},
+    {{ ffi_converter_name }}.lower(self),
+    {%- call arg_list_lowered(meth) -%}
+)
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
         {{ arg|lower_fn }}({{ arg.name() }})
@@ -52,6 +76,32 @@ _uniffi_rust_call(
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
 
+{#-
+// The docstring of an item which can be deprecated, with a note about the deprecation.
+-#}
+{%- macro deprecated_docstring(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ defn.docstring()|deprecated_docstring(message, indent_spaces) }}
+{{ "" }}
+{%- else %}
+{%- call docstring(defn, indent_spaces) %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#-
+// The docstring of a deprecated function, method or constructor, followed by the
+// `DeprecationWarning` it emits when called.
+-#}
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- call deprecated_docstring(callable, indent_spaces) %}
+{%- match callable.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecation_warning(callable.name(), indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
 {#-
 // Arglist as used in Python declarations of methods, functions and constructors.
 // Note the type_name filters.
@@ -89,7 +139,7 @@ _uniffi_rust_call(
     {%- when None %}
     {%- when Some with(literal) %}
     if {{ arg.name() }} is _DEFAULT:
-        {{ arg.name() }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+        {{ arg.name() }} = {{ literal|literal_py(arg.as_type().borrow(), ci) }}
     {%- endmatch %}
     {{ arg|check_lower_fn }}({{ arg.name() }})
     {% endfor -%}
@@ -105,7 +155,7 @@ _uniffi_rust_call(
         {%- when None %}
         {%- when Some with(literal) %}
         if {{ arg.name() }} is _DEFAULT:
-            {{ arg.name() }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+            {{ arg.name() }} = {{ literal|literal_py(arg.as_type().borrow(), ci) }}
         {%- endmatch %}
         {{ arg|check_lower_fn }}({{ arg.name() }})
         {% endfor -%}
@@ -124,11 +174,11 @@ _uniffi_rust_call(
     async def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> None:
 {%      endmatch %}
 
-        {%- call docstring(meth, 8) %}
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
         return await _uniffi_rust_call_async(
             _UniffiLib.{{ meth.ffi_func().name() }}(
-                self._uniffi_clone_pointer(), {% call arg_list_lowered(meth) %}
+                {{ meth|lower_self }}, {% call arg_list_lowered(meth) %}
             ),
             _UniffiLib.{{ meth.ffi_rust_future_poll(ci) }},
             _UniffiLib.{{ meth.ffi_rust_future_complete(ci) }},
@@ -149,23 +199,87 @@ _uniffi_rust_call(
 {%-         when Some with (return_type) %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
-        {%- call docstring(meth, 8) %}
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
         return {{ return_type|lift_fn }}(
-            {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+            {% call to_ffi_call_with_prefix(meth|lower_self, meth) %}
         )
 
 {%-         when None %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> None:
-        {%- call docstring(meth, 8) %}
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+        {% call to_ffi_call_with_prefix(meth|lower_self, meth) %}
 {%      endmatch %}
 {%  endif %}
 
 {% endmacro %}
 
+{#
+ # Macro for field getters, which are read-only properties
+ #}
+{%- macro getter_decl(meth) %}
+{%- match meth.return_type() %}
+{%- when Some with (return_type) %}
+
+    @property
+    def {{ meth.name() }}(self) -> "{{ return_type|type_name }}":
+        {%- call callable_docstring(meth, 8) %}
+        return {{ return_type|lift_fn }}(
+            {% call to_ffi_call_with_prefix(meth|lower_self, meth) %}
+        )
+{%- when None %}
+{%- endmatch %}
+{% endmacro %}
+
+{#
+ # Macro to call static methods, which are called like functions
+ #}
+{%- macro static_method_decl(meth) %}
+    @staticmethod
+{%- if meth.is_async() %}
+{%-     match meth.return_type() %}
+{%-         when Some with (return_type) %}
+    async def {{ meth.name() }}({% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+{%-         when None %}
+    async def {{ meth.name() }}({% call arg_list_decl(meth) %}) -> None:
+{%-     endmatch %}
+        {%- call callable_docstring(meth, 8) %}
+        {%- call setup_args_extra_indent(meth) %}
+        return await _uniffi_rust_call_async(
+            _UniffiLib.{{ meth.ffi_func().name() }}({% call arg_list_lowered(meth) %}),
+            _UniffiLib.{{ meth.ffi_rust_future_poll(ci) }},
+            _UniffiLib.{{ meth.ffi_rust_future_complete(ci) }},
+            _UniffiLib.{{ meth.ffi_rust_future_free(ci) }},
+            # lift function
+            {%- match meth.return_type() %}
+            {%- when Some(return_type) %}
+            {{ return_type|lift_fn }},
+            {%- when None %}
+            lambda val: None,
+            {% endmatch %}
+            {% call error_ffi_converter(meth) %}
+        )
+{%- else %}
+{%-     match meth.return_type() %}
+{%-         when Some with (return_type) %}
+    def {{ meth.name() }}({% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+        {%- call callable_docstring(meth, 8) %}
+        {%- call setup_args_extra_indent(meth) %}
+        return {{ return_type|lift_fn }}(
+            {% call to_ffi_call(meth) %}
+        )
+{%-         when None %}
+    def {{ meth.name() }}({% call arg_list_decl(meth) %}) -> None:
+        {%- call callable_docstring(meth, 8) %}
+        {%- call setup_args_extra_indent(meth) %}
+        {% call to_ffi_call(meth) %}
+{%-     endmatch %}
+{%- endif %}
+
+{% endmacro %}
+
 {%- macro error_ffi_converter(func) %}
     # Error FFI converter
 {%  match func.throws_type() %}
@@ -182,3 +296,11 @@ _uniffi_rust_call(
     None,
 {%-  endmatch %}
 {% endmacro %}
+
+{#- The cause of an error, from its source field.  Strings are wrapped in an `Exception`. #}
+{%- macro error_cause(field, value) %}
+{%- match field.as_type() %}
+{%- when Type::String %}Exception({{ value }})
+{%- else %}{{ value }}
+{%- endmatch %}
+{%- endmacro %}
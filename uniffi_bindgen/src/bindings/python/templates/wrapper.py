@@ -27,6 +27,7 @@ import threading
 import itertools
 import traceback
 import typing
+import warnings
 {%- if ci.has_async_fns() %}
 import asyncio
 {%- endif %}
@@ -35,6 +36,13 @@ import platform
 {{ req.render() }}
 {%- endfor %}
 
+{%- match config.package_version %}
+{%- when Some(version) %}
+
+__version__ = "{{ version }}"
+{%- when None %}
+{%- endmatch %}
+
 # Used for default argument values
 _DEFAULT = object() # type: typing.Any
 
@@ -58,6 +66,8 @@ _DEFAULT = object() # type: typing.Any
 {%- include "TopLevelFunctionTemplate.py" %}
 {%- endfor %}
 
+{%- include "ConstantsTemplate.py" %}
+
 __all__ = [
     "InternalError",
     {%- for e in ci.enum_definitions() %}
@@ -69,6 +79,9 @@ __all__ = [
     {%- for func in ci.function_definitions() %}
     "{{ func.name() }}",
     {%- endfor %}
+    {%- for constant in ci.constant_definitions() %}
+    "{{ constant.name()|const_name }}",
+    {%- endfor %}
     {%- for obj in ci.object_definitions() %}
     "{{ obj|type_name }}",
     {%- endfor %}
@@ -4,11 +4,13 @@
 
 use anyhow::Result;
 use askama::Template;
+use camino::Utf8Path;
 
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 
+use crate::bindings::{NameTemplate, TemplateOverride, TemplateOverrides};
 use crate::interface::*;
 
 const RESERVED_WORDS: &[&str] = &[
@@ -39,11 +41,14 @@ pub fn canonical_name(t: &Type) -> String {
         Type::UInt32 => "u32".into(),
         Type::Int64 => "i64".into(),
         Type::UInt64 => "u64".into(),
+        Type::Int128 => "i128".into(),
+        Type::UInt128 => "u128".into(),
         Type::Float32 => "f32".into(),
         Type::Float64 => "f64".into(),
         Type::String => "string".into(),
         Type::Bytes => "bytes".into(),
         Type::Boolean => "bool".into(),
+        Type::Char => "char".into(),
         // API defined types.
         // Note that these all get unique names, and the parser ensures that the names do not
         // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -55,6 +60,10 @@ pub fn canonical_name(t: &Type) -> String {
         Type::Record { name, .. } => format!("Type{name}"),
         Type::CallbackInterface { name, .. } => format!("CallbackInterface{name}"),
         Type::Timestamp => "Timestamp".into(),
+        Type::Date => "Date".into(),
+        Type::Json => "Json".into(),
+        Type::Path => "Path".into(),
+        Type::Url => "Url".into(),
         Type::Duration => "Duration".into(),
         // Recursive types.
         // These add a prefix to the name of the underlying type.
@@ -80,12 +89,41 @@ pub fn canonical_name(t: &Type) -> String {
 // Note that this can only be used to control details of the ruby *that do not affect the underlying component*,
 // since the details of the underlying component are entirely determined by the `ComponentInterface`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     pub(super) cdylib_name: Option<String>,
     cdylib_path: Option<String>,
+    module_name: Option<NameTemplate>,
+    template_overrides_dir: Option<String>,
+    #[serde(skip)]
+    pub(super) template_overrides: TemplateOverrides,
 }
 
 impl Config {
+    /// Load the templates from `template_overrides_dir`, see [TemplateOverrides]
+    pub(super) fn load_template_overrides(&mut self) -> Result<()> {
+        if let Some(dir) = &self.template_overrides_dir {
+            self.template_overrides = TemplateOverrides::load(Utf8Path::new(dir), "rb")?;
+        }
+        Ok(())
+    }
+
+    /// The override for the record `name`, whose type is `type_name` in the bindings
+    pub(crate) fn record_override(&self, name: &str, type_name: &str) -> TemplateOverride<'_> {
+        self.template_overrides.get(
+            "RecordTemplate.rb",
+            &[("name", name), ("type_name", type_name)],
+        )
+    }
+
+    /// The name of the Ruby file for a component, without the `.rb`, `<namespace>` by default
+    pub fn module_name(&self, ci: &ComponentInterface) -> Result<String> {
+        match &self.module_name {
+            Some(template) => template.render(ci),
+            None => Ok(ci.namespace().to_string()),
+        }
+    }
+
     pub fn cdylib_name(&self) -> String {
         self.cdylib_name
             .clone()
@@ -185,6 +223,15 @@ mod filters {
                 Radix::Hexadecimal => format!("{i:#x}"),
             },
             Literal::Float(string, _type_) => string.clone(),
+            // Every byte is escaped, since `#{` would be interpolated.
+            Literal::Bytes(bytes) => format!(
+                "\"{}\".b",
+                bytes
+                    .iter()
+                    .map(|b| format!("\\x{b:02X}"))
+                    .collect::<Vec<_>>()
+                    .concat()
+            ),
         })
     }
 
@@ -207,6 +254,35 @@ mod filters {
         Ok(nm.to_string().to_shouty_snake_case())
     }
 
+    pub fn const_name_rb(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
+    /// The comment put before a deprecated item, followed by the indent for the item itself.
+    pub fn deprecated_rb(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        Ok(deprecation_comment("DEPRECATED", message, spaces))
+    }
+
+    /// The comment put before the `attr_reader` of a deprecated field.
+    pub fn deprecated_field_rb(
+        message: &str,
+        nm: &str,
+        spaces: &i32,
+    ) -> Result<String, askama::Error> {
+        let heading = format!("DEPRECATED `{}`", var_name_rb(nm)?);
+        Ok(deprecation_comment(&heading, message, spaces))
+    }
+
+    fn deprecation_comment(heading: &str, message: &str, spaces: &i32) -> String {
+        let indent = " ".repeat(usize::try_from(*spaces).unwrap_or_default());
+        if message.is_empty() {
+            format!("# {heading}\n{indent}")
+        } else {
+            let message = message.replace('\n', &format!("\n{indent}# "));
+            format!("# {heading}: {message}\n{indent}")
+        }
+    }
+
     pub fn coerce_rb(nm: &str, ns: &str, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8 => format!("{ns}::uniffi_in_range({nm}, \"i8\", -2**7, 2**7)"),
@@ -217,6 +293,14 @@ mod filters {
             Type::UInt16 => format!("{ns}::uniffi_in_range({nm}, \"u16\", 0, 2**16)"),
             Type::UInt32 => format!("{ns}::uniffi_in_range({nm}, \"u32\", 0, 2**32)"),
             Type::UInt64 => format!("{ns}::uniffi_in_range({nm}, \"u64\", 0, 2**64)"),
+            Type::UInt128 | Type::Int128 => {
+                panic!("No support for coercing 128-bit integers, yet")
+            }
+            Type::Char => panic!("No support for coercing chars, yet"),
+            Type::Date => panic!("No support for coercing dates, yet"),
+            Type::Json => panic!("No support for coercing JSON values, yet"),
+            Type::Path => panic!("No support for coercing paths, yet"),
+            Type::Url => panic!("No support for coercing URLs, yet"),
             Type::Float32 | Type::Float64 => nm.to_string(),
             Type::Boolean => format!("{nm} ? true : false"),
             Type::Object { .. } | Type::Enum { .. } | Type::Record { .. } => nm.to_string(),
@@ -300,6 +384,14 @@ mod filters {
                 class_name_rb(&canonical_name(type_))?,
                 nm
             ),
+            Type::UInt128 | Type::Int128 => {
+                panic!("No support for lowering 128-bit integers, yet")
+            }
+            Type::Char => panic!("No support for lowering chars, yet"),
+            Type::Date => panic!("No support for lowering dates, yet"),
+            Type::Json => panic!("No support for lowering JSON values, yet"),
+            Type::Path => panic!("No support for lowering paths, yet"),
+            Type::Url => panic!("No support for lowering URLs, yet"),
             Type::External { .. } => panic!("No support for lowering external types, yet"),
             Type::Custom { .. } => panic!("No support for lowering custom types, yet"),
         })
@@ -340,6 +432,14 @@ mod filters {
                 nm,
                 class_name_rb(&canonical_name(type_))?
             ),
+            Type::UInt128 | Type::Int128 => {
+                panic!("No support for lifting 128-bit integers, yet")
+            }
+            Type::Char => panic!("No support for lifting chars, yet"),
+            Type::Date => panic!("No support for lifting dates, yet"),
+            Type::Json => panic!("No support for lifting JSON values, yet"),
+            Type::Path => panic!("No support for lifting paths, yet"),
+            Type::Url => panic!("No support for lifting URLs, yet"),
             Type::External { .. } => panic!("No support for lifting external types, yet"),
             Type::Custom { .. } => panic!("No support for lifting custom types, yet"),
         })
@@ -1,4 +1,4 @@
-use super::{is_reserved_word, Config};
+use super::{filters, is_reserved_word, Config};
 
 #[test]
 fn when_reserved_word() {
@@ -15,6 +15,8 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        module_name: None,
+        ..Default::default()
     };
 
     assert_eq!("uniffi", config.cdylib_name());
@@ -22,6 +24,8 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: Some("todolist".to_string()),
         cdylib_path: None,
+        module_name: None,
+        ..Default::default()
     };
 
     assert_eq!("todolist", config.cdylib_name());
@@ -32,6 +36,8 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        module_name: None,
+        ..Default::default()
     };
 
     assert_eq!("", config.cdylib_path());
@@ -40,8 +46,23 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: Some("/foo/bar".to_string()),
+        module_name: None,
+        ..Default::default()
     };
 
     assert_eq!("/foo/bar", config.cdylib_path());
     assert!(config.custom_cdylib_path());
 }
+
+#[test]
+fn deprecation_comments() {
+    assert_eq!(
+        filters::deprecated_rb("use fetch()\nor poll()", &2).unwrap(),
+        "# DEPRECATED: use fetch()\n  # or poll()\n  "
+    );
+    assert_eq!(filters::deprecated_rb("", &0).unwrap(), "# DEPRECATED\n");
+    assert_eq!(
+        filters::deprecated_field_rb("", "fooBar", &2).unwrap(),
+        "# DEPRECATED `foo_bar`\n  "
+    );
+}
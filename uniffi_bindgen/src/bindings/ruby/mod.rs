@@ -4,26 +4,26 @@
 
 use std::process::Command;
 
+use crate::diagnostics::{Code, Diagnostic};
 use crate::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
 use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
 use fs_err as fs;
 
 mod gen_ruby;
 #[cfg(feature = "bindgen-tests")]
 pub mod test;
-use gen_ruby::{Config, RubyWrapper};
+pub use gen_ruby::Config;
+use gen_ruby::RubyWrapper;
 
 pub struct RubyBindingGenerator;
 impl BindingGenerator for RubyBindingGenerator {
     type Config = Config;
 
     fn new_config(&self, root_toml: &toml::Value) -> Result<Self::Config> {
-        Ok(
-            match root_toml.get("bindings").and_then(|b| b.get("ruby")) {
-                Some(v) => v.clone().try_into()?,
-                None => Default::default(),
-            },
-        )
+        let mut config: Config = crate::config::bindings_config(root_toml, "ruby")?;
+        config.load_template_overrides()?;
+        Ok(config)
     }
 
     fn update_component_configs(
@@ -46,28 +46,66 @@ impl BindingGenerator for RubyBindingGenerator {
         &self,
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
-    ) -> Result<()> {
+    ) -> Result<Vec<Utf8PathBuf>> {
+        let mut written_files = vec![];
         for Component { ci, config, .. } in components {
-            let rb_file = settings.out_dir.join(format!("{}.rb", ci.namespace()));
-            fs::write(&rb_file, generate_ruby_bindings(config, ci)?)?;
+            let rb_file = settings
+                .out_dir
+                .join(format!("{}.rb", config.module_name(ci)?));
+            fs::write(&rb_file, generate_source(ci, config)?)?;
+            written_files.push(rb_file);
+        }
+        Ok(written_files)
+    }
 
-            if settings.try_format_code {
-                if let Err(e) = Command::new("rubocop").arg("-A").arg(&rb_file).output() {
-                    println!(
-                        "Warning: Unable to auto-format {} using rubocop: {e:?}",
-                        rb_file.file_name().unwrap(),
-                    )
-                }
+    /// Format all the generated files with a single `rubocop` run.
+    fn finalize(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        if settings.try_format_code && !components.is_empty() {
+            let rb_files = components
+                .iter()
+                .map(|c| {
+                    Ok(settings
+                        .out_dir
+                        .join(format!("{}.rb", c.config.module_name(&c.ci)?)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if let Err(e) = Command::new("rubocop").arg("-A").args(rb_files).output() {
+                Diagnostic::warning(
+                    Code::Format,
+                    format!("Unable to auto-format the Ruby bindings using rubocop: {e:?}"),
+                )
+                .emit();
             }
         }
         Ok(())
     }
+
+    fn owned_file_patterns(&self) -> Vec<String> {
+        vec!["*.rb".to_string()]
+    }
 }
 
-// Generate ruby bindings for the given ComponentInterface, as a string.
-pub fn generate_ruby_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+/// Generate the Ruby module for a component, as a string.
+///
+/// This is what [RubyBindingGenerator] writes to `<module_name>.rb`.  The config should have been
+/// updated by [BindingGenerator::update_component_configs] first, like it is when generating
+/// bindings.
+pub fn generate_source(ci: &ComponentInterface, config: &Config) -> Result<String> {
     use askama::Template;
-    RubyWrapper::new(config.clone(), ci)
-        .render()
-        .context("failed to render ruby bindings")
+    config.template_overrides.render(
+        "wrapper.rb",
+        &[
+            ("namespace", ci.namespace()),
+            ("crate_name", ci.crate_name()),
+        ],
+        || {
+            RubyWrapper::new(config.clone(), ci)
+                .render()
+                .context("failed to render ruby bindings")
+        },
+    )
 }
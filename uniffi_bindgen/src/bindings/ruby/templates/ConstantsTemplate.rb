@@ -0,0 +1,3 @@
+{%- for constant in ci.constant_definitions() %}
+{{ constant.name()|const_name_rb }} = {{ constant.value()|literal_rb }}
+{%- endfor %}
@@ -2,7 +2,7 @@
 
 class {{ e.name()|class_name_rb }}
   {% for variant in e.variants() -%}
-  {{ variant.name()|enum_name_rb }} = {{ loop.index }}
+  {% call rb::deprecated(variant, 2) %}{{ variant.name()|enum_name_rb }} = {{ loop.index }}
   {% endfor %}
 end
 
@@ -15,9 +15,9 @@ class {{ e.name()|class_name_rb }}
 
   # Each enum variant is a nested class of the enum itself.
   {% for variant in e.variants() -%}
-  class {{ variant.name()|enum_name_rb }}
+  {% call rb::deprecated(variant, 2) %}class {{ variant.name()|enum_name_rb }}
     {% if variant.has_fields() %}
-    attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
+    {% call rb::deprecated_fields(variant, 4) %}attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
     {% endif %}
     def initialize({% for field in variant.fields() %}{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
       {% if variant.has_fields() %}
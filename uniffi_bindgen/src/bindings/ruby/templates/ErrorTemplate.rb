@@ -24,12 +24,12 @@ CALL_PANIC = 2
 {% if e.is_flat() %}
 class {{ e.name()|class_name_rb }}
     {%- for variant in e.variants() %}
-    {{ variant.name()|class_name_rb }} = Class.new StandardError
+    {% call rb::deprecated(variant, 4) %}{{ variant.name()|class_name_rb }} = Class.new StandardError
     {%- endfor %}
 {% else %}
 module {{ e.name()|class_name_rb }}
   {%- for variant in e.variants() %}
-  class {{ variant.name()|class_name_rb }} < StandardError
+  {% call rb::deprecated(variant, 2) %}class {{ variant.name()|class_name_rb }} < StandardError
     def initialize({% for field in variant.fields() %}{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %})
         {%- for field in variant.fields() %}
         @{{ field.name()|var_name_rb }} = {{ field.name()|var_name_rb }}
@@ -38,7 +38,7 @@ module {{ e.name()|class_name_rb }}
       end
     {%- if variant.has_fields() %}
 
-    attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %}
+    {% call rb::deprecated_fields(variant, 4) %}attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %}
     {% endif %}
 
     def to_s
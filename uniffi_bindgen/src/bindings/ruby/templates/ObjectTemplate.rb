@@ -44,7 +44,7 @@ class {{ obj.name()|class_name_rb }}
 
   {%- match obj.primary_constructor() %}
   {%- when Some with (cons) %}
-  def initialize({% call rb::arg_list_decl(cons) -%})
+  {% call rb::deprecated(cons, 2) %}def initialize({% call rb::arg_list_decl(cons) -%})
     {%- call rb::setup_args_extra_indent(cons) %}
     pointer = {% call rb::to_ffi_call(cons) %}
     @pointer = pointer
@@ -54,7 +54,7 @@ class {{ obj.name()|class_name_rb }}
   {%- endmatch %}
 
   {% for cons in obj.alternate_constructors() -%}
-  def self.{{ cons.name()|fn_name_rb }}({% call rb::arg_list_decl(cons) %})
+  {% call rb::deprecated(cons, 2) %}def self.{{ cons.name()|fn_name_rb }}({% call rb::arg_list_decl(cons) %})
     {%- call rb::setup_args_extra_indent(cons) %}
     # Call the (fallible) function before creating any half-baked object instances.
     # Lightly yucky way to bypass the usual "initialize" logic
@@ -63,18 +63,36 @@ class {{ obj.name()|class_name_rb }}
   end
   {% endfor %}
 
+  {% for meth in obj.static_methods() -%}
+  {%- match meth.return_type() -%}
+
+  {%- when Some with (return_type) -%}
+  {% call rb::deprecated(meth, 2) %}def self.{{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
+    {%- call rb::setup_args_extra_indent(meth) %}
+    result = {% call rb::to_ffi_call(meth) %}
+    return {{ "result"|lift_rb(return_type) }}
+  end
+
+  {%- when None -%}
+  {% call rb::deprecated(meth, 2) %}def self.{{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
+      {%- call rb::setup_args_extra_indent(meth) %}
+      {% call rb::to_ffi_call(meth) %}
+  end
+  {% endmatch %}
+  {% endfor %}
+
   {% for meth in obj.methods() -%}
   {%- match meth.return_type() -%}
 
   {%- when Some with (return_type) -%}
-  def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
+  {% call rb::deprecated(meth, 2) %}def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::setup_args_extra_indent(meth) %}
     result = {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
     return {{ "result"|lift_rb(return_type) }}
   end
 
   {%- when None -%}
-  def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
+  {% call rb::deprecated(meth, 2) %}def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::setup_args_extra_indent(meth) %}
       {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
   end
@@ -1,6 +1,6 @@
 # Record type {{ rec.name() }}
-class {{ rec.name()|class_name_rb }}
-  attr_reader {% for field in rec.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
+{% call rb::deprecated(rec, 0) %}class {{ rec.name()|class_name_rb }}
+  {% call rb::deprecated_fields(rec, 2) %}attr_reader {% for field in rec.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
 
   def initialize({% for field in rec.fields() %}{{ field.name()|var_name_rb -}}:
         {%- match field.default_value() %}
@@ -73,3 +73,26 @@
         {{ arg.name()|check_lower_rb(arg.as_type().borrow()) }}
         {%- endfor %}
 {%- endmacro -%}
+
+{#-
+// A comment before a deprecated item.  The item must directly follow the call, since the
+// comment ends with a newline and the indent for the item.
+-#}
+{%- macro deprecated(defn, indent_spaces) -%}
+{%- match defn.deprecated() -%}
+{%- when Some(message) -%}
+{{ message|deprecated_rb(indent_spaces) }}
+{%- else -%}
+{%- endmatch -%}
+{%- endmacro -%}
+
+{#- Comments for the deprecated fields of a record or variant, before their `attr_reader`. -#}
+{%- macro deprecated_fields(item, indent_spaces) -%}
+{%- for field in item.fields() -%}
+{%- match field.deprecated() -%}
+{%- when Some(message) -%}
+{{ message|deprecated_field_rb(field.name(), indent_spaces) }}
+{%- else -%}
+{%- endmatch -%}
+{%- endfor -%}
+{%- endmacro -%}
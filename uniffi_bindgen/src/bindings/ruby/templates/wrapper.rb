@@ -30,6 +30,8 @@ module {{ ci.namespace()|class_name_rb }}
 
   # Public interface members begin here.
 
+  {% include "ConstantsTemplate.rb" %}
+
   {% for e in ci.enum_definitions() %}
   {% if !ci.is_name_used_as_error(e.name()) %}
   {% include "EnumTemplate.rb" %}
@@ -37,7 +39,12 @@ module {{ ci.namespace()|class_name_rb }}
   {%- endfor -%}
 
   {%- for rec in ci.record_definitions() %}
+  {%- let record_override = config.record_override(rec.name(), rec.name()|class_name_rb) %}
+  {{- record_override.before() }}
+  {%- if record_override.includes_builtin() %}
   {% include "RecordTemplate.rb" %}
+  {%- endif %}
+  {{- record_override.after() }}
   {% endfor %}
 
   {% for func in ci.function_definitions() %}
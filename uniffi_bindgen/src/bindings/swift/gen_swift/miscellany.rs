@@ -17,6 +17,60 @@ impl CodeType for TimestampCodeType {
     }
 }
 
+#[derive(Debug)]
+pub struct DateCodeType;
+
+impl CodeType for DateCodeType {
+    fn type_label(&self) -> String {
+        "DateComponents".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Date".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonCodeType;
+
+impl CodeType for JsonCodeType {
+    fn type_label(&self) -> String {
+        "Data".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Json".into()
+    }
+}
+
+/// A typealias for either a file `URL` or `String`, depending on the config
+#[derive(Debug)]
+pub struct PathCodeType;
+
+impl CodeType for PathCodeType {
+    fn type_label(&self) -> String {
+        "FilePath".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Path".into()
+    }
+}
+
+/// A typealias for either `URL` or `String`, depending on the config
+#[derive(Debug)]
+pub struct UrlCodeType;
+
+impl CodeType for UrlCodeType {
+    fn type_label(&self) -> String {
+        "UrlValue".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Url".into()
+    }
+}
+
 #[derive(Debug)]
 pub struct DurationCodeType;
 
@@ -29,3 +83,42 @@ impl CodeType for DurationCodeType {
         "Duration".into()
     }
 }
+
+#[derive(Debug)]
+pub struct UInt128CodeType;
+
+impl CodeType for UInt128CodeType {
+    fn type_label(&self) -> String {
+        "RustUInt128".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "UInt128".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct Int128CodeType;
+
+impl CodeType for Int128CodeType {
+    fn type_label(&self) -> String {
+        "RustInt128".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Int128".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct CharCodeType;
+
+impl CodeType for CharCodeType {
+    fn type_label(&self) -> String {
+        "Unicode.Scalar".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Char".into()
+    }
+}
@@ -10,12 +10,14 @@ use std::fmt::Debug;
 
 use anyhow::{Context, Result};
 use askama::Template;
+use camino::Utf8Path;
 
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 
 use super::Bindings;
 use crate::backend::TemplateExpression;
+use crate::bindings::{TemplateOverride, TemplateOverrides};
 
 use crate::interface::*;
 
@@ -189,7 +191,11 @@ pub fn quote_arg_keyword(nm: String) -> String {
 /// Note that this can only be used to control details of the Swift *that do not affect the underlying component*,
 /// since the details of the underlying component are entirely determined by the `ComponentInterface`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
+    // Swift finds the library through the FFI module, so this isn't used, but it's accepted since
+    // it's documented and set by existing configs.
+    cdylib_name: Option<String>,
     pub(super) module_name: Option<String>,
     ffi_module_name: Option<String>,
     ffi_module_filename: Option<String>,
@@ -197,11 +203,18 @@ pub struct Config {
     omit_argument_labels: Option<bool>,
     generate_immutable_records: Option<bool>,
     experimental_sendable_value_types: Option<bool>,
+    paths_as_strings: Option<bool>,
+    #[serde(default)]
+    url_type: UrlType,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
+    template_overrides_dir: Option<String>,
+    #[serde(skip)]
+    pub(super) template_overrides: TemplateOverrides,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CustomTypeConfig {
     imports: Option<Vec<String>>,
     type_name: Option<String>,
@@ -210,6 +223,22 @@ pub struct CustomTypeConfig {
 }
 
 impl Config {
+    /// Load the templates from `template_overrides_dir`, see [TemplateOverrides]
+    pub(super) fn load_template_overrides(&mut self) -> Result<()> {
+        if let Some(dir) = &self.template_overrides_dir {
+            self.template_overrides = TemplateOverrides::load(Utf8Path::new(dir), "swift")?;
+        }
+        Ok(())
+    }
+
+    /// The override for the record `name`, whose type is `type_name` in the bindings
+    pub(crate) fn record_override(&self, name: &str, type_name: &str) -> TemplateOverride<'_> {
+        self.template_overrides.get(
+            "RecordTemplate.swift",
+            &[("name", name), ("type_name", type_name)],
+        )
+    }
+
     /// The name of the Swift module containing the high-level foreign-language bindings.
     /// Panics if the module name hasn't been configured.
     pub fn module_name(&self) -> String {
@@ -264,6 +293,25 @@ impl Config {
     pub fn experimental_sendable_value_types(&self) -> bool {
         self.experimental_sendable_value_types.unwrap_or(false)
     }
+
+    /// Whether paths are a `String`, rather than a file `URL`
+    pub fn paths_as_strings(&self) -> bool {
+        self.paths_as_strings.unwrap_or(false)
+    }
+
+    /// Whether `url::Url` values are a Foundation `URL`, rather than a `String`
+    pub fn urls_as_foundation_urls(&self) -> bool {
+        self.url_type == UrlType::Url
+    }
+}
+
+/// The Swift types `url::Url` can be, set with `url_type`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UrlType {
+    #[default]
+    String,
+    #[serde(rename = "URL")]
+    Url,
 }
 
 /// Generate UniFFI component bindings for Swift, as strings in memory.
@@ -271,9 +319,18 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bin
     let header = BridgingHeader::new(config, ci)
         .render()
         .context("failed to render Swift bridging header")?;
-    let library = SwiftWrapper::new(config.clone(), ci)
-        .render()
-        .context("failed to render Swift library")?;
+    let library = config.template_overrides.render(
+        "wrapper.swift",
+        &[
+            ("namespace", ci.namespace()),
+            ("crate_name", ci.crate_name()),
+        ],
+        || {
+            SwiftWrapper::new(config.clone(), ci)
+                .render()
+                .context("failed to render Swift library")
+        },
+    )?;
     let modulemap = if config.generate_module_map() {
         Some(
             ModuleMap::new_for_single_component(config, ci)
@@ -468,13 +525,20 @@ impl SwiftCodeOracle {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(miscellany::UInt128CodeType),
+            Type::Int128 => Box::new(miscellany::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(miscellany::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::Date => Box::new(miscellany::DateCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
@@ -764,13 +828,31 @@ pub mod filters {
 
     /// Get the idiomatic Swift rendering of docstring
     pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
-        let middle = textwrap::indent(&textwrap::dedent(docstring), " * ");
+        // `*/` would end the comment early and, since block comments nest, `/*` would leave it
+        // unterminated.  The backslashes keep the rendered markdown the same.
+        let docstring = docstring.replace("*/", "*\\/").replace("/*", "/\\*");
+        let middle = textwrap::indent(&textwrap::dedent(&docstring), " * ");
         let wrapped = format!("/**\n{middle}\n */");
 
         let spaces = usize::try_from(*spaces).unwrap_or_default();
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
 
+    /// Get the `@available` attribute for a deprecation message
+    pub fn deprecated(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let spaces = " ".repeat(usize::try_from(*spaces).unwrap_or_default());
+        if message.is_empty() {
+            return Ok(format!("{spaces}@available(*, deprecated)"));
+        }
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        Ok(format!(
+            "{spaces}@available(*, deprecated, message: \"{message}\")"
+        ))
+    }
+
     pub fn object_names(obj: &Object) -> Result<(String, String), askama::Error> {
         Ok(SwiftCodeOracle.object_names(obj))
     }
@@ -57,6 +57,15 @@ fn render_literal(literal: &Literal) -> String {
             },
         ),
         Literal::Float(string, type_) => typed_number(type_, string.clone()),
+        Literal::Bytes(bytes) if bytes.is_empty() => "Data()".into(),
+        Literal::Bytes(bytes) => format!(
+            "Data([{}])",
+            bytes
+                .iter()
+                .map(|b| format!("{b:#04x}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
         _ => unreachable!("Literal"),
     }
 }
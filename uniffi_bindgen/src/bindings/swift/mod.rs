@@ -29,14 +29,17 @@
 //!  * How to read from and write into a byte buffer.
 //!
 
-use crate::{BindingGenerator, Component, GenerationSettings};
+use super::NameTemplate;
+use crate::diagnostics::{Code, Diagnostic};
+use crate::{BindingGenerator, Component, ComponentInterface, GenerationSettings};
 use anyhow::{Context, Result};
 use camino::Utf8PathBuf;
 use fs_err as fs;
-use std::process::Command;
+use std::{collections::HashMap, process::Command};
 
 mod gen_swift;
-use gen_swift::{generate_bindings, generate_header, generate_modulemap, generate_swift, Config};
+pub use gen_swift::Config;
+use gen_swift::{generate_bindings, generate_header, generate_modulemap, generate_swift};
 
 #[cfg(feature = "bindgen-tests")]
 pub mod test;
@@ -57,12 +60,13 @@ impl BindingGenerator for SwiftBindingGenerator {
     type Config = Config;
 
     fn new_config(&self, root_toml: &toml::Value) -> Result<Self::Config> {
-        Ok(
-            match root_toml.get("bindings").and_then(|b| b.get("swift")) {
-                Some(v) => v.clone().try_into()?,
-                None => Default::default(),
-            },
-        )
+        let mut config: Self::Config = crate::config::bindings_config(root_toml, "swift")?;
+        config.load_template_overrides()?;
+        // The module name is a template, which is rendered by `update_component_configs`.
+        if let Some(module_name) = &config.module_name {
+            NameTemplate::new(module_name.as_str())?;
+        }
+        Ok(config)
     }
 
     fn update_component_configs(
@@ -71,9 +75,11 @@ impl BindingGenerator for SwiftBindingGenerator {
         components: &mut Vec<Component<Self::Config>>,
     ) -> Result<()> {
         for c in &mut *components {
-            c.config
-                .module_name
-                .get_or_insert_with(|| c.ci.namespace().into());
+            let module_name = match &c.config.module_name {
+                Some(template) => NameTemplate::new(template.as_str())?.render(&c.ci)?,
+                None => c.ci.namespace().into(),
+            };
+            c.config.module_name = Some(module_name);
         }
         Ok(())
     }
@@ -85,42 +91,72 @@ impl BindingGenerator for SwiftBindingGenerator {
         &self,
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
-    ) -> Result<()> {
+    ) -> Result<Vec<Utf8PathBuf>> {
+        let mut written_files = vec![];
         for Component { ci, config, .. } in components {
-            let Bindings {
-                header,
-                library,
-                modulemap,
-            } = generate_bindings(config, ci)?;
-
-            let source_file = settings
-                .out_dir
-                .join(format!("{}.swift", config.module_name()));
-            fs::write(&source_file, library)?;
-
-            let header_file = settings.out_dir.join(config.header_filename());
-            fs::write(header_file, header)?;
-
-            if let Some(modulemap) = modulemap {
-                let modulemap_file = settings.out_dir.join(config.modulemap_filename());
-                fs::write(modulemap_file, modulemap)?;
+            let mut sources: Vec<_> = generate_sources(ci, config)?.into_iter().collect();
+            sources.sort();
+            for (path, contents) in sources {
+                let path = settings.out_dir.join(path);
+                fs::write(&path, contents)?;
+                written_files.push(path);
             }
+        }
 
-            if settings.try_format_code {
-                if let Err(e) = Command::new("swiftformat")
-                    .arg(source_file.as_str())
-                    .output()
-                {
-                    println!(
-                        "Warning: Unable to auto-format {} using swiftformat: {e:?}",
-                        source_file.file_name().unwrap(),
-                    );
-                }
+        Ok(written_files)
+    }
+
+    /// Format all the generated files with a single `swiftformat` run.
+    fn finalize(
+        &self,
+        settings: &GenerationSettings,
+        components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        if settings.try_format_code && !components.is_empty() {
+            let source_files = components.iter().map(|c| {
+                settings
+                    .out_dir
+                    .join(format!("{}.swift", c.config.module_name()))
+            });
+            if let Err(e) = Command::new("swiftformat").args(source_files).output() {
+                Diagnostic::warning(
+                    Code::Format,
+                    format!("Unable to auto-format the Swift bindings using swiftformat: {e:?}"),
+                )
+                .emit();
             }
         }
-
         Ok(())
     }
+
+    fn owned_file_patterns(&self) -> Vec<String> {
+        ["*.swift", "*.h", "*.modulemap"].map(String::from).to_vec()
+    }
+}
+
+/// Generate the Swift bindings for a component, as strings.
+///
+/// This returns the `.swift` source, the `.h` header and, unless it's disabled in the config, the
+/// `.modulemap` file, keyed by their paths relative to the output directory.  These are the files
+/// [SwiftBindingGenerator] writes.  The config should have been updated by
+/// [BindingGenerator::update_component_configs] first, like it is when generating bindings.
+pub fn generate_sources(
+    ci: &ComponentInterface,
+    config: &Config,
+) -> Result<HashMap<Utf8PathBuf, String>> {
+    let Bindings {
+        header,
+        library,
+        modulemap,
+    } = generate_bindings(config, ci)?;
+    let mut sources = HashMap::from([
+        (format!("{}.swift", config.module_name()).into(), library),
+        (config.header_filename().into(), header),
+    ]);
+    if let Some(modulemap) = modulemap {
+        sources.insert(config.modulemap_filename().into(), modulemap);
+    }
+    Ok(sources)
 }
 
 /// Generate Swift bindings
@@ -149,15 +185,18 @@ pub fn generate_swift_bindings(options: SwiftBindingsOptions) -> Result<()> {
         crate::library_mode::find_components(&options.library_path, &config_supplier)?
             // map the TOML configs into a our Config struct
             .into_iter()
-            .map(|Component { ci, config }| {
-                let config = SwiftBindingGenerator.new_config(&config.into())?;
-                Ok(Component { ci, config })
+            .map(|component| {
+                Ok(Component {
+                    ci: component.ci,
+                    config: SwiftBindingGenerator.new_config(&component.config.into())?,
+                    package: component.package,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
     SwiftBindingGenerator
         .update_component_configs(&GenerationSettings::default(), &mut components)?;
 
-    for Component { ci, config } in &components {
+    for Component { ci, config, .. } in &components {
         if options.generate_swift_sources {
             let source_file = options
                 .out_dir
@@ -217,3 +256,85 @@ pub struct SwiftBindingsOptions {
     pub modulemap_filename: Option<String>,
     pub metadata_no_deps: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_sources() {
+        let ci = ComponentInterface::from_webidl(
+            "namespace example { u32 get_value(); };",
+            "crate_name",
+        )
+        .unwrap();
+        let mut components = vec![Component {
+            ci,
+            config: Config::default(),
+            package: None,
+        }];
+        SwiftBindingGenerator
+            .update_component_configs(&GenerationSettings::default(), &mut components)
+            .unwrap();
+        let Component { ci, config, .. } = &components[0];
+        let sources = generate_sources(ci, config).unwrap();
+        let mut paths: Vec<_> = sources.keys().map(|p| p.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            ["example.swift", "exampleFFI.h", "exampleFFI.modulemap"]
+        );
+        assert!(sources[&Utf8PathBuf::from("example.swift")].contains("func getValue()"));
+        assert!(sources[&Utf8PathBuf::from("exampleFFI.h")]
+            .contains("uniffi_crate_name_fn_func_get_value"));
+    }
+
+    #[test]
+    fn test_module_name_template() {
+        let ci = ComponentInterface::from_webidl(
+            "namespace example { u32 get_value(); };",
+            "crate_name",
+        )
+        .unwrap();
+        let root_toml: toml::Value = toml::de::from_str(
+            r#"
+            [bindings.swift]
+            module_name = "{namespace}Bindings"
+        "#,
+        )
+        .unwrap();
+        let config = SwiftBindingGenerator.new_config(&root_toml).unwrap();
+        let mut components = vec![Component {
+            ci,
+            config,
+            package: None,
+        }];
+        SwiftBindingGenerator
+            .update_component_configs(&GenerationSettings::default(), &mut components)
+            .unwrap();
+        let Component { ci, config, .. } = &components[0];
+        let mut paths: Vec<_> = generate_sources(ci, config)
+            .unwrap()
+            .into_keys()
+            .map(|p| p.to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "exampleBindings.swift",
+                "exampleBindingsFFI.h",
+                "exampleBindingsFFI.modulemap"
+            ]
+        );
+
+        let root_toml: toml::Value = toml::de::from_str(
+            r#"
+            [bindings.swift]
+            module_name = "{module}Bindings"
+        "#,
+        )
+        .unwrap();
+        assert!(SwiftBindingGenerator.new_config(&root_toml).is_err());
+    }
+}
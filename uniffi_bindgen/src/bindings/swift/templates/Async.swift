@@ -46,6 +46,12 @@ fileprivate func uniffiFutureContinuationCallback(handle: UInt64, pollResult: In
 }
 
 {%- if ci.has_async_callback_interface_definition() %}
+// Status code to return for an error that an async trait interface method didn't declare.
+// Cancellation gets its own code, so that Rust can tell it apart from a failure.
+private func uniffiUnexpectedErrorCode(_ error: Error) -> Int8 {
+    return error is CancellationError ? CALL_CANCELLED : CALL_UNEXPECTED_ERROR
+}
+
 private func uniffiTraitInterfaceCallAsync<T>(
     makeCall: @escaping () async throws -> T,
     handleSuccess: @escaping (T) -> (),
@@ -55,7 +61,7 @@ private func uniffiTraitInterfaceCallAsync<T>(
         do {
             handleSuccess(try await makeCall())
         } catch {
-            handleError(CALL_UNEXPECTED_ERROR, {{ Type::String.borrow()|lower_fn }}(String(describing: error)))
+            handleError(uniffiUnexpectedErrorCode(error), uniffiLowerUnexpectedError(error))
         }
     }
     let handle = UNIFFI_FOREIGN_FUTURE_HANDLE_MAP.insert(obj: task)
@@ -75,7 +81,7 @@ private func uniffiTraitInterfaceCallAsyncWithError<T, E>(
         } catch let error as E {
             handleError(CALL_ERROR, lowerError(error))
         } catch {
-            handleError(CALL_UNEXPECTED_ERROR, {{ Type::String.borrow()|lower_fn }}(String(describing: error)))
+            handleError(uniffiUnexpectedErrorCode(error), uniffiLowerUnexpectedError(error))
         }
     }
     let handle = UNIFFI_FOREIGN_FUTURE_HANDLE_MAP.insert(obj: task)
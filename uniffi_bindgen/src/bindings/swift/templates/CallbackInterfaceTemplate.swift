@@ -2,6 +2,7 @@
 {%- let callback_handler = format!("uniffiCallbackHandler{}", name) %}
 {%- let callback_init = format!("uniffiCallbackInit{}", name) %}
 {%- let methods = cbi.methods() %}
+{%- let constants = cbi.constants() %}
 {%- let protocol_name = type_name.clone() %}
 {%- let protocol_docstring = cbi.docstring() %}
 {%- let vtable = cbi.vtable() %}
@@ -55,3 +56,24 @@ extension {{ ffi_converter_name }} : FfiConverter {
         writeInt(&buf, lower(v))
     }
 }
+
+{#
+We always write these public functions just in case the callback interface is used as
+an external type by another crate.
+#}
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterType{{ name }}_lift(_ handle: UInt64) throws -> {{ type_name }} {
+    return try {{ ffi_converter_name }}.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterType{{ name }}_lower(_ v: {{ type_name }}) -> UInt64 {
+    // The vtable must be registered before Rust can call back into this handle, which isn't
+    // guaranteed when another crate's bindings are passing it across the FFI.
+    uniffiEnsureInitialized()
+    return {{ ffi_converter_name }}.lower(v)
+}
@@ -0,0 +1,9 @@
+{%- if !ci.constant_definitions().is_empty() %}
+
+public enum {{ ci.namespace()|class_name }} {
+    {%- for constant in ci.constant_definitions() %}
+    {%- call swift::docstring(constant, 4) %}
+    public static let {{ constant.name()|var_name }}: {{ constant|type_name }} = {{ constant.value()|literal_swift(constant) }}
+    {%- endfor %}
+}
+{%- endif %}
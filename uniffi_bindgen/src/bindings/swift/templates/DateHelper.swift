@@ -0,0 +1,43 @@
+// Dates are passed as the number of days since 1970-01-01. The conversion uses the proleptic
+// Gregorian calendar, like Rust does, rather than `Calendar`, which switches to the Julian calendar
+// for dates before 1582. See https://howardhinnant.github.io/date_algorithms.html
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterDate: FfiConverter {
+    typealias FfiType = Int32
+    typealias SwiftType = DateComponents
+
+    public static func lift(_ value: Int32) throws -> DateComponents {
+        let z = Int(value) + 719468
+        let era = (z >= 0 ? z : z - 146096) / 146097
+        let dayOfEra = z - era * 146097
+        let yearOfEra = (dayOfEra - dayOfEra / 1460 + dayOfEra / 36524 - dayOfEra / 146096) / 365
+        let dayOfYear = dayOfEra - (365 * yearOfEra + yearOfEra / 4 - yearOfEra / 100)
+        let shiftedMonth = (5 * dayOfYear + 2) / 153
+        let day = dayOfYear - (153 * shiftedMonth + 2) / 5 + 1
+        let month = shiftedMonth < 10 ? shiftedMonth + 3 : shiftedMonth - 9
+        let year = yearOfEra + era * 400 + (month <= 2 ? 1 : 0)
+        return DateComponents(year: year, month: month, day: day)
+    }
+
+    public static func lower(_ value: DateComponents) -> Int32 {
+        guard let year = value.year, let month = value.month, let day = value.day else {
+            fatalError("DateComponents passed as a date must have a year, month and day")
+        }
+        let y = month <= 2 ? year - 1 : year
+        let era = (y >= 0 ? y : y - 399) / 400
+        let yearOfEra = y - era * 400
+        let dayOfYear = (153 * (month > 2 ? month - 3 : month + 9) + 2) / 5 + day - 1
+        let dayOfEra = yearOfEra * 365 + yearOfEra / 4 - yearOfEra / 100 + dayOfYear
+        return Int32(era * 146097 + dayOfEra - 719468)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> DateComponents {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: DateComponents, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
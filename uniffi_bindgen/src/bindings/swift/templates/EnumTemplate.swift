@@ -6,19 +6,29 @@
 public enum {{ type_name }} {
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}(
         {%- call swift::field_list_decl(variant, variant.has_nameless_fields()) %}
     ){% endif -%}
     {% endfor %}
+    {%- for meth in e.methods() %}
+
+    {% call swift::func_decl("public func", meth, 4) %}
+    {%- endfor %}
 }
 {% when Some with (variant_discr_type) %}
 public enum {{ type_name }} : {{ variant_discr_type|type_name }} {
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|enum_variant_swift_quoted }} = {{ e|variant_discr_literal(loop.index0) }}{% if variant.fields().len() > 0 %}(
         {%- call swift::field_list_decl(variant, variant.has_nameless_fields()) %}
     ){% endif -%}
     {% endfor %}
+    {%- for meth in e.methods() %}
+
+    {% call swift::func_decl("public func", meth, 4) %}
+    {%- endfor %}
 }
 {% endmatch %}
 
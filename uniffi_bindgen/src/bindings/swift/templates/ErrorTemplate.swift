@@ -4,12 +4,14 @@ public enum {{ type_name }} {
     {% if e.is_flat() %}
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|class_name }}(message: String)
     {% endfor %}
 
     {%- else %}
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|class_name }}{% if variant.fields().len() > 0 %}(
         {%- call swift::field_list_decl(variant, variant.has_nameless_fields()) %}
     ){% endif -%}
@@ -95,3 +97,33 @@ extension {{ type_name }}: Foundation.LocalizedError {
         String(reflecting: self)
     }
 }
+{% if e.has_source_fields() %}
+extension {{ type_name }}: Foundation.CustomNSError {
+    /// The error which caused this one, from the variant's source field
+    public var underlyingError: Error? {
+        switch self {
+        {%- for variant in e.variants() %}
+        {%- match variant.source_field() %}
+        {%- when Some(source) %}
+        case let .{{ variant.name()|error_variant_swift_quoted }}({% for field in variant.fields() %}{% if field.is_source() %}source{% else %}_{% endif %}{% if !loop.last %}, {% endif %}{% endfor %}):
+            {%- match source.as_type() %}
+            {%- when Type::String %}
+            return Foundation.NSError(domain: "{{ type_name }}", code: 0, userInfo: [Foundation.NSLocalizedDescriptionKey: source])
+            {%- else %}
+            return source
+            {%- endmatch %}
+        {%- when None %}
+        case .{{ variant.name()|error_variant_swift_quoted }}:
+            return nil
+        {%- endmatch %}
+        {%- endfor %}
+        }
+    }
+
+    public var errorUserInfo: [String: Any] {
+        var userInfo = [String: Any]()
+        userInfo[Foundation.NSUnderlyingErrorKey] = underlyingError
+        return userInfo
+    }
+}
+{% endif %}
@@ -9,6 +9,8 @@ fileprivate enum UniffiInternalError: LocalizedError {
     case unexpectedRustCallStatusCode
     case unexpectedRustCallError
     case unexpectedStaleHandle
+    case unexpectedUnicodeScalar
+    case unexpectedUrl
     case rustPanic(_ message: String)
 
     public var errorDescription: String? {
@@ -21,6 +23,8 @@ fileprivate enum UniffiInternalError: LocalizedError {
         case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
         case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
         case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
+        case .unexpectedUnicodeScalar: return "Raw char value isn't a Unicode scalar value"
+        case .unexpectedUrl: return "URL value can't be parsed by Foundation"
         case let .rustPanic(message): return message
         }
     }
@@ -109,6 +113,15 @@ private func uniffiCheckCallStatus<E: Swift.Error>(
     }
 }
 
+// Lower an error that a trait interface method didn't declare.
+// The buffer holds the error's description followed by its type name.
+fileprivate func uniffiLowerUnexpectedError(_ error: Error) -> RustBuffer {
+    var writer = createWriter()
+    {{ Type::String.borrow()|ffi_converter_name }}.write(String(describing: error), into: &writer)
+    {{ Type::String.borrow()|ffi_converter_name }}.write(String(reflecting: type(of: error)), into: &writer)
+    return RustBuffer(bytes: writer)
+}
+
 private func uniffiTraitInterfaceCall<T>(
     callStatus: UnsafeMutablePointer<RustCallStatus>,
     makeCall: () throws -> T,
@@ -118,7 +131,7 @@ private func uniffiTraitInterfaceCall<T>(
         try writeReturn(makeCall())
     } catch let error {
         callStatus.pointee.code = CALL_UNEXPECTED_ERROR
-        callStatus.pointee.errorBuf = {{ Type::String.borrow()|lower_fn }}(String(describing: error))
+        callStatus.pointee.errorBuf = uniffiLowerUnexpectedError(error)
     }
 }
 
@@ -135,6 +148,6 @@ private func uniffiTraitInterfaceCallWithError<T, E>(
         callStatus.pointee.errorBuf = lowerError(error)
     } catch {
         callStatus.pointee.code = CALL_UNEXPECTED_ERROR
-        callStatus.pointee.errorBuf = {{ Type::String.borrow()|lower_fn }}(String(describing: error))
+        callStatus.pointee.errorBuf = uniffiLowerUnexpectedError(error)
     }
 }
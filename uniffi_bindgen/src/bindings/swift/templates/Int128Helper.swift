@@ -0,0 +1,37 @@
+/**
+ * A signed 128-bit integer, in two's complement and split into its high and low 64 bits.
+ *
+ * Swift doesn't have a 128-bit integer type on all the platforms we support, so `i128` values
+ * are represented by this struct.
+ */
+public struct RustInt128: Equatable, Hashable {
+    public var high: Int64
+    public var low: UInt64
+
+    public init(high: Int64, low: UInt64) {
+        self.high = high
+        self.low = low
+    }
+
+    public init(_ value: Int64) {
+        self.init(high: value < 0 ? -1 : 0, low: UInt64(bitPattern: value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterInt128: FfiConverterRustBuffer {
+    typealias SwiftType = RustInt128
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RustInt128 {
+        let high: Int64 = try readInt(&buf)
+        let low: UInt64 = try readInt(&buf)
+        return RustInt128(high: high, low: low)
+    }
+
+    public static func write(_ value: RustInt128, into buf: inout [UInt8]) {
+        writeInt(&buf, value.high)
+        writeInt(&buf, value.low)
+    }
+}
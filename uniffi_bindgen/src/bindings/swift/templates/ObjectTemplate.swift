@@ -1,6 +1,7 @@
 {%- let obj = ci|get_object_definition(name) %}
 {%- let (protocol_name, impl_class_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
+{%- let constants = obj.constants() %}
 {%- let protocol_docstring = obj.docstring() %}
 
 {%- let is_error = ci.is_name_used_as_error(name) %}
@@ -19,6 +20,8 @@ open class {{ impl_class_name }}:
     Equatable,
     {%-         when UniffiTrait::Hash { hash } %}
     Hashable,
+    {%-         when UniffiTrait::Ord { cmp } %}
+    Comparable,
     {%-         else %}
     {%-    endmatch %}
     {%- endfor %}
@@ -26,7 +29,13 @@ open class {{ impl_class_name }}:
     Swift.Error,
     {% endif %}
     {{ protocol_name }} {
+    {%- if obj.has_consuming_method() %}
+    fileprivate var pointer: UnsafeMutableRawPointer!
+    // Guards `pointer`, which consuming methods take from the object.
+    private let uniffiPointerLock = NSLock()
+    {%- else %}
     fileprivate let pointer: UnsafeMutableRawPointer!
+    {%- endif %}
 
     /// Used to instantiate a [FFIObject] without an actual pointer, for fakes in tests, mostly.
 #if swift(>=5.8)
@@ -59,8 +68,31 @@ open class {{ impl_class_name }}:
     @_documentation(visibility: private)
 #endif
     public func uniffiClonePointer() -> UnsafeMutableRawPointer {
+        {%- if obj.has_consuming_method() %}
+        // Hold the lock while cloning, so a consuming method can't free the pointer under us.
+        uniffiPointerLock.lock()
+        defer { uniffiPointerLock.unlock() }
+        guard let pointer = self.pointer else {
+            fatalError("{{ impl_class_name }} object has already been consumed")
+        }
+        return try! rustCall { {{ obj.ffi_object_clone().name() }}(pointer, $0) }
+        {%- else %}
         return try! rustCall { {{ obj.ffi_object_clone().name() }}(self.pointer, $0) }
+        {%- endif %}
     }
+    {%- if obj.has_consuming_method() %}
+
+    // Consuming methods pass the object's own pointer to Rust, leaving the object unusable.
+    private func uniffiTakePointer() -> UnsafeMutableRawPointer {
+        uniffiPointerLock.lock()
+        defer { uniffiPointerLock.unlock() }
+        guard let pointer = self.pointer else {
+            fatalError("{{ impl_class_name }} object has already been consumed")
+        }
+        self.pointer = nil
+        return pointer
+    }
+    {%- endif %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
@@ -81,8 +113,16 @@ open class {{ impl_class_name }}:
     {%- call swift::func_decl("public static func", cons, 4) %}
     {% endfor %}
 
+    {% for meth in obj.static_methods() -%}
+    {%- call swift::func_decl("public static func", meth, 4) %}
+    {% endfor %}
+
     {% for meth in obj.methods() -%}
+    {%- if meth.is_getter() %}
+    {%- call swift::getter_decl("open var", meth, 4) %}
+    {%- else %}
     {%- call swift::func_decl("open func", meth, 4) %}
+    {%- endif %}
     {% endfor %}
 
     {%- for tm in obj.uniffi_traits() %}
@@ -112,6 +152,12 @@ open class {{ impl_class_name }}:
         )
         hasher.combine(val)
     }
+    {%-         when UniffiTrait::Ord { cmp } %}
+    public static func < (self: {{ impl_class_name }}, other: {{ impl_class_name }}) -> Bool {
+        return {% call swift::try(cmp) %} {{ cmp.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call(cmp) %}
+        ) < 0
+    }
     {%-         else %}
     {%-    endmatch %}
     {%- endfor %}
@@ -0,0 +1,36 @@
+{%- if config.paths_as_strings() %}
+/// A file system path. Set `paths_as_strings = false` in `uniffi.toml` to use a file `URL` instead.
+public typealias FilePath = String
+{%- else %}
+/// A file system path. Set `paths_as_strings` in `uniffi.toml` to use a `String` instead.
+public typealias FilePath = URL
+{%- endif %}
+
+// Paths are passed as their bytes, which are UTF-8 for paths that are valid Unicode. Rust paths
+// which aren't have their invalid bytes replaced by U+FFFD.
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterPath: FfiConverterRustBuffer {
+    typealias SwiftType = FilePath
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FilePath {
+        let len: Int32 = try readInt(&buf)
+        let path = String(decoding: try readBytes(&buf, count: Int(len)), as: UTF8.self)
+        {%- if config.paths_as_strings() %}
+        return path
+        {%- else %}
+        return URL(fileURLWithPath: path)
+        {%- endif %}
+    }
+
+    public static func write(_ value: FilePath, into buf: inout [UInt8]) {
+        {%- if config.paths_as_strings() %}
+        let bytes = Array(value.utf8)
+        {%- else %}
+        let bytes = Array(value.relativePath.utf8)
+        {%- endif %}
+        writeInt(&buf, Int32(bytes.count))
+        writeBytes(&buf, bytes)
+    }
+}
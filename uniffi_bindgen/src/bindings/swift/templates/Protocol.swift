@@ -2,11 +2,52 @@
 public protocol {{ protocol_name }} : AnyObject {
     {% for meth in methods.iter() -%}
     {%- call swift::docstring(meth, 4) %}
+    {%- call swift::deprecated(meth, 4) %}
+    {%- if meth.is_getter() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    var {{ meth.name()|var_name }}: {{ return_type|type_name }} { get }
+    {%- when None %}
+    {%- endmatch %}
+    {%- else %}
     func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::async(meth) -%}{% call swift::throws(meth) -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
     {%- else -%}
     {%- endmatch %}
+    {%- endif %}
     {% endfor %}
 }
+{%- if !constants.is_empty() %}
+
+// Constants mirrored from the Rust trait.
+extension {{ protocol_name }} {
+    {%- for constant in constants.iter() %}
+    {%- call swift::docstring(constant, 4) %}
+    public static var {{ constant.name()|var_name }}: {{ constant|type_name }} { {{ constant.value()|literal_swift(constant) }} }
+    {%- endfor %}
+}
+{%- endif %}
+{%- for meth in methods.iter() %}
+{%- match meth.default_ffi_func() %}
+{%- when Some with (default_ffi_func) %}
+
+// Implementations which don't implement `{{ meth.name()|fn_name }}` use the Rust default implementation.
+extension {{ protocol_name }} {
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
+    {%- else -%}
+    {%- endmatch %} {
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        return {% call swift::try(meth) %} {{ return_type|lift_fn }}({% call swift::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %})
+        {%- when None %}
+        {% call swift::to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) %}
+        {%- endmatch %}
+    }
+}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
 
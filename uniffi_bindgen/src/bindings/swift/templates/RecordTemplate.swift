@@ -1,8 +1,10 @@
 {%- let rec = ci|get_record_definition(name) %}
 {%- call swift::docstring(rec, 0) %}
+{%- call swift::deprecated(rec, 0) %}
 public struct {{ type_name }} {
     {%- for field in rec.fields() %}
     {%- call swift::docstring(field, 4) %}
+    {%- call swift::deprecated(field, 4) %}
     public {% if config.generate_immutable_records() %}let{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name }}
     {%- endfor %}
 
@@ -13,6 +15,10 @@ public struct {{ type_name }} {
         self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
         {%- endfor %}
     }
+    {%- for meth in rec.methods() %}
+
+    {% call swift::func_decl("public func", meth, 4) %}
+    {%- endfor %}
 }
 
 {% if !contains_object_references %}
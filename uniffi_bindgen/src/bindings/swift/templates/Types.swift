@@ -19,6 +19,9 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.swift" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.swift" %}
+
 {%- when Type::String %}
 {%- include "StringHelper.swift" %}
 
@@ -49,6 +52,12 @@
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.swift" %}
 
+{%- when Type::Int128 %}
+{%- include "Int128Helper.swift" %}
+
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.swift" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.swift" %}
 
@@ -58,6 +67,18 @@
 {%- when Type::Timestamp %}
 {%- include "TimestampHelper.swift" %}
 
+{%- when Type::Date %}
+{%- include "DateHelper.swift" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.swift" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.swift" %}
+
+{%- when Type::Url %}
+{%- include "UrlHelper.swift" %}
+
 {%- when Type::Duration %}
 {%- include "DurationHelper.swift" %}
 
@@ -79,7 +100,12 @@
 {%- include "ObjectTemplate.swift" %}
 
 {%- when Type::Record { name, module_path } %}
+{%- let record_override = config.record_override(name, type_name) %}
+{{- record_override.before() }}
+{%- if record_override.includes_builtin() %}
 {%- include "RecordTemplate.swift" %}
+{%- endif %}
+{{- record_override.after() }}
 
 {%- when Type::Optional { inner_type } %}
 {%- include "OptionalTemplate.swift" %}
@@ -0,0 +1,37 @@
+/**
+ * An unsigned 128-bit integer, split into its high and low 64 bits.
+ *
+ * Swift doesn't have a 128-bit integer type on all the platforms we support, so `u128` values
+ * are represented by this struct.
+ */
+public struct RustUInt128: Equatable, Hashable {
+    public var high: UInt64
+    public var low: UInt64
+
+    public init(high: UInt64, low: UInt64) {
+        self.high = high
+        self.low = low
+    }
+
+    public init(_ value: UInt64) {
+        self.init(high: 0, low: value)
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterUInt128: FfiConverterRustBuffer {
+    typealias SwiftType = RustUInt128
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RustUInt128 {
+        let high: UInt64 = try readInt(&buf)
+        let low: UInt64 = try readInt(&buf)
+        return RustUInt128(high: high, low: low)
+    }
+
+    public static func write(_ value: RustUInt128, into buf: inout [UInt8]) {
+        writeInt(&buf, value.high)
+        writeInt(&buf, value.low)
+    }
+}
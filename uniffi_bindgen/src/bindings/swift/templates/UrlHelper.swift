@@ -0,0 +1,38 @@
+{%- if config.urls_as_foundation_urls() %}
+/// A URL. Set `url_type = "String"` in `uniffi.toml` to use a `String` instead.
+public typealias UrlValue = URL
+{%- else %}
+/// A URL, as its serialized text. Set `url_type = "URL"` in `uniffi.toml` to use a `URL` instead.
+public typealias UrlValue = String
+{%- endif %}
+
+// URLs are passed as their serialized text. Rust rejects text which isn't a valid URL.
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterUrl: FfiConverterRustBuffer {
+    typealias SwiftType = UrlValue
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> UrlValue {
+        let len: Int32 = try readInt(&buf)
+        let text = String(bytes: try readBytes(&buf, count: Int(len)), encoding: String.Encoding.utf8)!
+        {%- if config.urls_as_foundation_urls() %}
+        guard let url = URL(string: text) else {
+            throw UniffiInternalError.unexpectedUrl
+        }
+        return url
+        {%- else %}
+        return text
+        {%- endif %}
+    }
+
+    public static func write(_ value: UrlValue, into buf: inout [UInt8]) {
+        {%- if config.urls_as_foundation_urls() %}
+        let bytes = Array(value.absoluteString.utf8)
+        {%- else %}
+        let bytes = Array(value.utf8)
+        {%- endif %}
+        writeInt(&buf, Int32(bytes.count))
+        writeBytes(&buf, bytes)
+    }
+}
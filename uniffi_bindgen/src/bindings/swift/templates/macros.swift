@@ -13,15 +13,35 @@
         rustCall() {
     {%- endmatch %}
     {{ func.ffi_func().name() }}(
-        {%- if func.takes_self() %}self.uniffiClonePointer(),{% endif %}
+        {%- if func.takes_self() %}{% call lower_self(func) %},{% endif %}
         {%- call arg_list_lowered(func) -%} $0
     )
 }
 {%- endmacro -%}
 
+{#-
+// Call the Rust default implementation of a trait method, for a foreign implementation which
+// doesn't override it.
+-#}
+{%- macro to_default_ffi_call(meth, default_ffi_func, ffi_converter_name) -%}
+    {%- call try(meth) -%}
+    {%- match meth.throws_type() -%}
+    {%- when Some with (e) -%}
+        rustCallWithError({{ e|ffi_error_converter_name }}.lift) {
+    {%- else -%}
+        rustCall() {
+    {%- endmatch %}
+    {{ default_ffi_func.name() }}(
+        {{ ffi_converter_name }}.lower(self),
+        {%- call arg_list_lowered(meth) -%} $0
+    )
+}
+{%- endmacro -%}
+
 // eg, `public func foo_bar() { body }`
 {%- macro func_decl(func_decl, callable, indent) %}
 {%- call docstring(callable, indent) %}
+{%- call deprecated(callable, indent) %}
 {{ func_decl }} {{ callable.name()|fn_name }}(
     {%- call arg_list_decl(callable) -%})
     {%- call async(callable) %}
@@ -34,9 +54,23 @@
 }
 {%- endmacro %}
 
+// A field getter, which is a read-only computed property rather than a function.
+{%- macro getter_decl(getter_decl, callable, indent) %}
+{%- call docstring(callable, indent) %}
+{%- call deprecated(callable, indent) %}
+{%- match callable.return_type() %}
+{%- when Some with (return_type) %}
+{{ getter_decl }} {{ callable.name()|var_name }}: {{ return_type|type_name }} {
+    {%- call call_body(callable) %}
+}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro %}
+
 // primary ctor - no name, no return-type.
 {%- macro ctor_decl(callable, indent) %}
 {%- call docstring(callable, indent) %}
+{%- call deprecated(callable, indent) %}
 public convenience init(
     {%- call arg_list_decl(callable) -%}) {%- call async(callable) %} {%- call throws(callable) %} {
     {%- if callable.is_async() %}
@@ -74,7 +108,7 @@ public convenience init(
             rustFutureFunc: {
                 {{ callable.ffi_func().name() }}(
                     {%- if callable.takes_self() %}
-                    self.uniffiClonePointer(){% if !callable.arguments().is_empty() %},{% endif %}
+                    {% call lower_self(callable) %}{% if !callable.arguments().is_empty() %},{% endif %}
                     {% endif %}
                     {%- for arg in callable.arguments() -%}
                     {{ arg|lower_fn }}({{ arg.name()|var_name }}){% if !loop.last %},{% endif %}
@@ -99,6 +133,23 @@ public convenience init(
         )
 {%- endmacro %}
 
+{#-
+// The lowered `self` of a method: the object pointer, or the lowered value for methods of
+// records and enums.  Consuming methods take the object's pointer rather than cloning it.
+-#}
+{%- macro lower_self(func) -%}
+    {%- match func.value_self_type() -%}
+    {%- when Some(self_type) -%}
+    {{ self_type|lower_fn }}(self)
+    {%- when None -%}
+    {%- if func.consumes_self() -%}
+    self.uniffiTakePointer()
+    {%- else -%}
+    self.uniffiClonePointer()
+    {%- endif -%}
+    {%- endmatch -%}
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
         {{ arg|lower_fn }}({{ arg.name()|var_name }}),
@@ -180,3 +231,11 @@ v{{- field_num -}}
 {%- macro docstring(defn, indent_spaces) %}
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
+
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecated(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
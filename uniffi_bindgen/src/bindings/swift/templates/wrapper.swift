@@ -33,6 +33,8 @@ import {{ config.ffi_module_name() }}
 {%- include "TopLevelFunctionTemplate.swift" %}
 {%- endfor %}
 
+{%- include "ConstantsTemplate.swift" %}
+
 private enum InitializationResult {
     case ok
     case contractVersionMismatch
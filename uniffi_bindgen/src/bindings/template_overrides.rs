@@ -0,0 +1,269 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Replace parts of the generated bindings with user-provided templates.
+//!
+//! The builtin templates are compiled into `uniffi-bindgen`, so they can't be edited.  Instead,
+//! some of them are override points: when the `template_overrides_dir` config contains a file with
+//! the same name as one of these templates, it's used instead of the builtin template.  The
+//! override points for each language are:
+//!
+//! - `wrapper.<ext>`, the whole file, with the `namespace` and `crate_name` variables.
+//! - `RecordTemplate.<ext>`, each record, with the `name` of the record and the `type_name` it has
+//!   in the bindings.
+//!
+//! Override templates aren't Askama templates: `{{ variable }}` is replaced with the value of the
+//! variable and everything else is copied as it is.  `{{ builtin }}` is replaced with the output of
+//! the builtin template, so an override can add to the builtin output rather than replace it:
+//!
+//! ```text
+//! // Copyright Example Corp.
+//! {{ builtin }}
+//! ```
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+
+/// The variables each override point can use, besides `builtin`
+const OVERRIDE_POINTS: &[(&str, &[&str])] = &[
+    ("wrapper", &["namespace", "crate_name"]),
+    ("RecordTemplate", &["name", "type_name"]),
+];
+
+/// The override templates for one language
+#[derive(Debug, Clone, Default)]
+pub struct TemplateOverrides {
+    templates: HashMap<String, OverrideTemplate>,
+}
+
+impl TemplateOverrides {
+    /// Load the override templates for the language whose templates have `extension` from `dir`
+    ///
+    /// Files with other extensions are ignored, so one directory can hold the overrides for several
+    /// languages.  It fails for files which don't match an override point, and for templates using
+    /// unknown variables.
+    pub fn load(dir: &Utf8Path, extension: &str) -> Result<Self> {
+        let mut templates = HashMap::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(extension) {
+                continue;
+            }
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let stem = file_name.trim_end_matches(&format!(".{extension}"));
+            let Some((_, variables)) = OVERRIDE_POINTS.iter().find(|(name, _)| *name == stem)
+            else {
+                bail!(
+                    "`{file_name}` in {dir} isn't a template which can be overridden, expected one of: {}",
+                    OVERRIDE_POINTS
+                        .iter()
+                        .map(|(name, _)| format!("`{name}.{extension}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            let source = fs::read_to_string(&path)?;
+            let template = OverrideTemplate::parse(&source, variables)
+                .with_context(|| format!("invalid override template {}", path.display()))?;
+            templates.insert(file_name.to_string(), template);
+        }
+        Ok(Self { templates })
+    }
+
+    /// The override for the template `name`, like `RecordTemplate.kt`, with the values of its
+    /// variables
+    pub fn get<'a>(&'a self, name: &str, variables: &[(&str, &str)]) -> TemplateOverride<'a> {
+        TemplateOverride {
+            template: self.templates.get(name),
+            variables: variables
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Render the template `name`, using `builtin` to render the builtin template if needed
+    pub fn render(
+        &self,
+        name: &str,
+        variables: &[(&str, &str)],
+        builtin: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let template_override = self.get(name, variables);
+        let mut output = template_override.before();
+        if template_override.includes_builtin() {
+            output.push_str(&builtin()?);
+        }
+        output.push_str(&template_override.after());
+        Ok(output)
+    }
+}
+
+/// A template override point, with the values of its variables
+///
+/// Templates render [Self::before], then the builtin template if [Self::includes_builtin], then
+/// [Self::after].  Without an override, only the builtin template is rendered.
+pub struct TemplateOverride<'a> {
+    template: Option<&'a OverrideTemplate>,
+    variables: HashMap<String, String>,
+}
+
+impl TemplateOverride<'_> {
+    /// The output before the builtin template, or the whole output if it isn't included
+    pub fn before(&self) -> String {
+        match self.template {
+            Some(template) => self.render(&template.before),
+            None => String::new(),
+        }
+    }
+
+    pub fn includes_builtin(&self) -> bool {
+        !matches!(self.template, Some(OverrideTemplate { after: None, .. }))
+    }
+
+    /// The output after the builtin template
+    pub fn after(&self) -> String {
+        match self.template.and_then(|t| t.after.as_ref()) {
+            Some(after) => self.render(after),
+            None => String::new(),
+        }
+    }
+
+    fn render(&self, parts: &[Part]) -> String {
+        parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.as_str(),
+                Part::Variable(name) => self.variables.get(name).map_or("", String::as_str),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OverrideTemplate {
+    before: Vec<Part>,
+    // Everything after `{{ builtin }}`, if the template includes the builtin output
+    after: Option<Vec<Part>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Variable(String),
+}
+
+impl OverrideTemplate {
+    fn parse(source: &str, variables: &[&str]) -> Result<Self> {
+        let mut before = vec![];
+        let mut after = None;
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start..].find("}}") else {
+                bail!("unmatched `{{{{`");
+            };
+            let name = rest[start + 2..start + len].trim();
+            let parts = after.as_mut().unwrap_or(&mut before);
+            parts.push(Part::Text(rest[..start].to_string()));
+            if variables.contains(&name) {
+                parts.push(Part::Variable(name.to_string()));
+            } else if name == "builtin" {
+                if after.is_some() {
+                    bail!("`{{{{ builtin }}}}` can only be used once");
+                }
+                after = Some(vec![]);
+            } else {
+                bail!(
+                    "unknown variable `{name}`, expected `builtin` or one of: {}",
+                    variables.join(", ")
+                );
+            }
+            rest = &rest[start + len + 2..];
+        }
+        after
+            .as_mut()
+            .unwrap_or(&mut before)
+            .push(Part::Text(rest.to_string()));
+        Ok(Self { before, after })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(source: &str, builtin: &str) -> Result<String> {
+        let overrides = TemplateOverrides {
+            templates: HashMap::from([(
+                "RecordTemplate.kt".to_string(),
+                OverrideTemplate::parse(source, &["name", "type_name"])?,
+            )]),
+        };
+        overrides.render(
+            "RecordTemplate.kt",
+            &[("name", "point"), ("type_name", "Point")],
+            || Ok(builtin.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(
+                "@Serializable // {{name}}\n{{ builtin }}\n// end of {{ type_name }}",
+                "class Point"
+            )
+            .unwrap(),
+            "@Serializable // point\nclass Point\n// end of Point"
+        );
+        assert_eq!(
+            render("class {{ type_name }}", "data class Point").unwrap(),
+            "class Point"
+        );
+        // Templates which aren't overridden render the builtin template
+        let overrides = TemplateOverrides::default();
+        assert_eq!(
+            overrides
+                .render("RecordTemplate.kt", &[], || Ok("builtin".to_string()))
+                .unwrap(),
+            "builtin"
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for source in [
+            "{{ namespace }}",
+            "{{ builtin }}{{ builtin }}",
+            "{{ type_name",
+        ] {
+            assert!(render(source, "").is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = camino::Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-template-overrides-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("wrapper.kt"), "// Header\n{{ builtin }}").unwrap();
+        fs::write(dir.join("wrapper.py"), "# Header\n{{ builtin }}").unwrap();
+        let overrides = TemplateOverrides::load(&dir, "kt").unwrap();
+        assert_eq!(
+            overrides
+                .render("wrapper.kt", &[], || Ok("package foo".to_string()))
+                .unwrap(),
+            "// Header\npackage foo"
+        );
+
+        fs::write(dir.join("Types.kt"), "").unwrap();
+        let err = TemplateOverrides::load(&dir, "kt").unwrap_err();
+        assert!(err.to_string().contains("`Types.kt` in"), "{err}");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -5,61 +5,349 @@
 //! Helpers for data returned by cargo_metadata. Note that this doesn't
 //! execute cargo_metadata, just parses its output.
 
-use anyhow::{bail, Context};
+use anyhow::{anyhow, bail, Context};
 use camino::Utf8PathBuf;
-use cargo_metadata::Metadata;
-use std::{collections::HashMap, fs};
+use cargo_metadata::{Message, Metadata};
+use std::{collections::HashMap, fs, io::BufRead};
 
-use crate::BindgenCrateConfigSupplier;
+use uniffi_meta::UdlFile;
+
+use crate::{BindgenCrateConfigSupplier, PackageInfo};
 
 #[derive(Debug, Clone, Default)]
 pub struct CrateConfigSupplier {
     paths: HashMap<String, Utf8PathBuf>,
+    packages: HashMap<String, PackageInfo>,
 }
 
 impl BindgenCrateConfigSupplier for CrateConfigSupplier {
     fn get_toml(&self, crate_name: &str) -> anyhow::Result<Option<toml::value::Table>> {
-        let toml = self.paths.get(crate_name).map(|p| p.join("uniffi.toml"));
-        crate::load_toml_file(toml.as_deref())
+        match self.paths.get(crate_name) {
+            Some(crate_root) => Ok(Some(crate::load_crate_config(crate_root)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_toml_path(&self, crate_name: &str) -> Option<Utf8PathBuf> {
+        self.paths.get(crate_name).map(|p| p.join("uniffi.toml"))
+    }
+
+    fn get_workspace_toml_path(&self, crate_name: &str) -> Option<Utf8PathBuf> {
+        crate::workspace_config_path(self.paths.get(crate_name)?)
+            .ok()
+            .flatten()
     }
 
     fn get_udl(&self, crate_name: &str, udl_name: &str) -> anyhow::Result<String> {
-        let path = self
+        self.read_udl(crate_name, udl_name, None)
+    }
+
+    fn get_udl_file(&self, crate_name: &str, udl_file: &UdlFile) -> anyhow::Result<String> {
+        self.read_udl(
+            crate_name,
+            &udl_file.file_stub,
+            udl_file.file_path.as_deref(),
+        )
+    }
+
+    fn get_udl_path(&self, crate_name: &str, udl_file: &UdlFile) -> Option<Utf8PathBuf> {
+        self.udl_candidates(
+            crate_name,
+            &udl_file.file_stub,
+            udl_file.file_path.as_deref(),
+        )
+        .ok()?
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    fn get_package_info(&self, crate_name: &str) -> Option<PackageInfo> {
+        self.packages.get(crate_name).cloned()
+    }
+}
+
+impl CrateConfigSupplier {
+    fn read_udl(
+        &self,
+        crate_name: &str,
+        udl_name: &str,
+        recorded_path: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let candidates = self.udl_candidates(crate_name, udl_name, recorded_path)?;
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(fs::read_to_string(path)?),
+            None => bail!(
+                "No UDL file found for '{crate_name}', tried: {}",
+                candidates
+                    .iter()
+                    .map(|path| format!("'{path}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    // The places the UDL file might be, in order of preference:
+    //   - the `udl_path` from `uniffi.toml`, which is either the file itself or a directory
+    //     containing it
+    //   - the path recorded when the scaffolding was generated
+    //   - `src/{udl_name}.udl`
+    // Relative paths are resolved against the crate root.
+    fn udl_candidates(
+        &self,
+        crate_name: &str,
+        udl_name: &str,
+        recorded_path: Option<&str>,
+    ) -> anyhow::Result<Vec<Utf8PathBuf>> {
+        let crate_root = self
             .paths
             .get(crate_name)
-            .context(format!("No path known to UDL files for '{crate_name}'"))?
-            .join("src")
-            .join(format!("{udl_name}.udl"));
-        if path.exists() {
-            Ok(fs::read_to_string(path)?)
-        } else {
-            bail!(format!("No UDL file found at '{path}'"));
+            .context(format!("No path known to UDL files for '{crate_name}'"))?;
+        let file_name = format!("{udl_name}.udl");
+        let mut candidates = vec![];
+        let toml = match self.get_toml(crate_name)? {
+            Some(mut toml) => {
+                crate::config::interpolate_env(&mut toml)?;
+                Some(toml)
+            }
+            None => None,
+        };
+        if let Some(udl_path) = toml.and_then(|toml| toml.get("udl_path").cloned()) {
+            let udl_path = crate_root.join(
+                udl_path
+                    .as_str()
+                    .context(format!("`udl_path` for '{crate_name}' must be a string"))?,
+            );
+            if udl_path.is_dir() {
+                candidates.push(udl_path.join(&file_name));
+            } else {
+                candidates.push(udl_path);
+            }
+        }
+        if let Some(recorded_path) = recorded_path {
+            candidates.push(crate_root.join(recorded_path));
         }
+        candidates.push(crate_root.join("src").join(file_name));
+        candidates.dedup();
+        Ok(candidates)
     }
 }
 
 impl From<Metadata> for CrateConfigSupplier {
     fn from(metadata: Metadata) -> Self {
-        let paths: HashMap<String, Utf8PathBuf> = metadata
-            .packages
-            .iter()
-            .flat_map(|p| {
-                p.targets
-                    .iter()
-                    .filter(|t| {
-                        !t.is_bin()
-                            && !t.is_example()
-                            && !t.is_test()
-                            && !t.is_bench()
-                            && !t.is_custom_build()
-                    })
-                    .filter_map(|t| {
-                        p.manifest_path
-                            .parent()
-                            .map(|p| (t.name.replace('-', "_"), p.to_owned()))
-                    })
-            })
-            .collect();
-        Self { paths }
+        let mut paths = HashMap::new();
+        let mut packages = HashMap::new();
+        for p in &metadata.packages {
+            let Some(crate_root) = p.manifest_path.parent() else {
+                continue;
+            };
+            let package_info = PackageInfo {
+                name: p.name.clone(),
+                version: p.version.to_string(),
+                authors: p.authors.clone(),
+                license: p.license.clone(),
+            };
+            for t in p.targets.iter().filter(|t| {
+                !t.is_bin()
+                    && !t.is_example()
+                    && !t.is_test()
+                    && !t.is_bench()
+                    && !t.is_custom_build()
+            }) {
+                let crate_name = t.name.replace('-', "_");
+                paths.insert(crate_name.clone(), crate_root.to_owned());
+                packages.insert(crate_name, package_info.clone());
+            }
+        }
+        Self { paths, packages }
+    }
+}
+
+/// Find the cdylib for a crate in the messages from `cargo build --message-format=json`
+///
+/// This looks for the `compiler-artifact` messages for `cdylib` targets, so build scripts don't
+/// have to guess where cargo put the library.  With no `crate_name`, the cdylib of any crate is
+/// used.  If there are several, like when the messages are from both a debug and a release build,
+/// the most recent one is used.
+pub fn cdylib_from_messages(
+    messages: impl BufRead,
+    crate_name: Option<&str>,
+) -> anyhow::Result<Utf8PathBuf> {
+    let crate_name = crate_name.map(|name| name.replace('-', "_"));
+    let mut cdylib = None;
+    for message in Message::parse_stream(messages) {
+        let Message::CompilerArtifact(artifact) =
+            message.context("failed to read cargo messages")?
+        else {
+            continue;
+        };
+        if !artifact.target.kind.iter().any(|kind| kind == "cdylib") {
+            continue;
+        }
+        if let Some(crate_name) = &crate_name {
+            if artifact.target.name.replace('-', "_") != *crate_name {
+                continue;
+            }
+        }
+        if let Some(path) = artifact
+            .filenames
+            .into_iter()
+            .find(|path| crate::is_cdylib(path))
+        {
+            cdylib = Some(path);
+        }
+    }
+    cdylib.ok_or_else(|| match crate_name {
+        Some(crate_name) => {
+            anyhow!("No `cdylib` target for the crate '{crate_name}' found in the cargo messages")
+        }
+        None => anyhow!("No `cdylib` target found in the cargo messages"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn udl_file(file_path: Option<&str>) -> UdlFile {
+        UdlFile {
+            module_path: "crate_name".into(),
+            namespace: "test".into(),
+            file_stub: "test".into(),
+            file_path: file_path.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_udl_search_path() {
+        let crate_root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-udl-path-test-{}", std::process::id()));
+        fs::create_dir_all(crate_root.join("src")).unwrap();
+        fs::create_dir_all(crate_root.join("gen")).unwrap();
+        let supplier = CrateConfigSupplier {
+            paths: HashMap::from([("crate_name".to_string(), crate_root.clone())]),
+            ..Default::default()
+        };
+
+        let err = supplier.get_udl_file("crate_name", &udl_file(Some("gen/test.udl")));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            format!(
+                "No UDL file found for 'crate_name', tried: '{}', '{}'",
+                crate_root.join("gen/test.udl"),
+                crate_root.join("src").join("test.udl"),
+            )
+        );
+
+        fs::write(crate_root.join("src/test.udl"), "src").unwrap();
+        fs::write(crate_root.join("gen/test.udl"), "gen").unwrap();
+        fs::write(crate_root.join("other.udl"), "other").unwrap();
+        assert_eq!(supplier.get_udl("crate_name", "test").unwrap(), "src");
+        assert_eq!(
+            supplier
+                .get_udl_file("crate_name", &udl_file(Some("gen/test.udl")))
+                .unwrap(),
+            "gen"
+        );
+
+        // `udl_path` in `uniffi.toml` takes precedence, and can be a file or a directory.
+        fs::write(crate_root.join("uniffi.toml"), "udl_path = \"other.udl\"").unwrap();
+        assert_eq!(
+            supplier
+                .get_udl_file("crate_name", &udl_file(Some("gen/test.udl")))
+                .unwrap(),
+            "other"
+        );
+        fs::write(crate_root.join("uniffi.toml"), "udl_path = \"gen\"").unwrap();
+        assert_eq!(supplier.get_udl("crate_name", "test").unwrap(), "gen");
+        assert_eq!(
+            supplier.get_udl_path("crate_name", &udl_file(None)),
+            Some(crate_root.join("gen/test.udl"))
+        );
+        fs::remove_dir_all(&crate_root).unwrap();
+    }
+
+    // A `compiler-artifact` message, as printed by `cargo build --message-format=json`
+    fn artifact_message(name: &str, kind: &[&str], filenames: &[&str]) -> String {
+        serde_json::json!({
+            "reason": "compiler-artifact",
+            "package_id": format!("{name} 0.1.0 (path+file:///src/{name})"),
+            "manifest_path": format!("/src/{name}/Cargo.toml"),
+            "target": {
+                "name": name,
+                "kind": kind,
+                "crate_types": kind,
+                "required-features": [],
+                "src_path": format!("/src/{name}/src/lib.rs"),
+                "edition": "2021",
+                "doctest": true,
+                "test": true,
+            },
+            "profile": {
+                "opt_level": "0",
+                "debuginfo": 2,
+                "debug_assertions": true,
+                "overflow_checks": true,
+                "test": false,
+            },
+            "features": [],
+            "filenames": filenames,
+            "executable": null,
+            "fresh": false,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_cdylib_from_messages() {
+        let messages = [
+            artifact_message(
+                "uniffi-dep",
+                &["lib"],
+                &["/target/debug/libuniffi_dep.rlib"],
+            ),
+            artifact_message(
+                "my-crate",
+                &["cdylib", "lib"],
+                &[
+                    "/target/debug/libmy_crate.so",
+                    "/target/debug/libmy_crate.rlib",
+                ],
+            ),
+            "warning: not a JSON message".to_string(),
+            artifact_message(
+                "my-crate",
+                &["cdylib", "lib"],
+                &[
+                    "/target/release/libmy_crate.so",
+                    "/target/release/libmy_crate.rlib",
+                ],
+            ),
+            artifact_message("other", &["cdylib"], &["/target/release/libother.so"]),
+        ]
+        .join("\n");
+
+        // The most recent artifact is used.
+        assert_eq!(
+            cdylib_from_messages(messages.as_bytes(), Some("my_crate")).unwrap(),
+            "/target/release/libmy_crate.so"
+        );
+        assert_eq!(
+            cdylib_from_messages(messages.as_bytes(), None).unwrap(),
+            "/target/release/libother.so"
+        );
+        assert_eq!(
+            cdylib_from_messages(messages.as_bytes(), Some("uniffi_dep"))
+                .unwrap_err()
+                .to_string(),
+            "No `cdylib` target for the crate 'uniffi_dep' found in the cargo messages"
+        );
+        assert_eq!(
+            cdylib_from_messages("".as_bytes(), None)
+                .unwrap_err()
+                .to_string(),
+            "No `cdylib` target found in the cargo messages"
+        );
     }
 }
@@ -0,0 +1,368 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Check that the UDL files of a library's crates match their Rust code.
+//!
+//! A crate can define items in a UDL file and also export items from its Rust code with
+//! proc-macros.  If an item ends up defined in both places and the definitions drift apart, the
+//! crate still builds but the bindings fail their checksum checks when they're loaded.  This
+//! finds the items which are defined in both places and reports any differences between them,
+//! in the same way as the bindings are generated in library mode.
+//!
+//! Items which are only defined in a UDL file don't have any metadata in the library, so they're
+//! checked with the API checksum functions instead.  The checksums of the UDL file are compared
+//! with the checksum functions exported by the library, which catches a library which was built
+//! from a different version of the UDL file.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+use anyhow::bail;
+use camino::Utf8Path;
+use uniffi_meta::{
+    create_metadata_groups, describe_item, group_metadata, item_mismatches, MetadataGroup, Mismatch,
+};
+
+use crate::{library_mode, macro_metadata, BindgenCrateConfigSupplier, ComponentInterface, Result};
+
+/// The differences between the UDL files and the Rust code of a library's crates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckReport {
+    /// The crates with mismatches, sorted by crate name
+    pub crates: Vec<CrateMismatches>,
+}
+
+/// The mismatches for a single crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateMismatches {
+    pub crate_name: String,
+    pub mismatches: Vec<ItemMismatch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemMismatch {
+    /// The item, for example "function `get_foo`"
+    pub item: String,
+    /// How it differs, with [Mismatch::left] from the UDL file and [Mismatch::right] from the
+    /// Rust code
+    pub mismatch: Mismatch,
+}
+
+impl CheckReport {
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty()
+    }
+
+    // Add the mismatches from another report, keeping the crates sorted
+    fn merge(&mut self, other: CheckReport) {
+        for other_crate in other.crates {
+            match self
+                .crates
+                .iter_mut()
+                .find(|c| c.crate_name == other_crate.crate_name)
+            {
+                Some(crate_mismatches) => {
+                    crate_mismatches.mismatches.extend(other_crate.mismatches)
+                }
+                None => self.crates.push(other_crate),
+            }
+        }
+        self.crates.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "The UDL files match the Rust code");
+        }
+        for crate_mismatches in &self.crates {
+            writeln!(f, "crate `{}`:", crate_mismatches.crate_name)?;
+            for item_mismatch in &crate_mismatches.mismatches {
+                writeln!(f, "  {item_mismatch}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ItemMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.item, self.mismatch.part)?;
+        writeln!(f, "    UDL:  {}", self.mismatch.left)?;
+        write!(f, "    Rust: {}", self.mismatch.right)
+    }
+}
+
+/// Check that the UDL files of the crates in a library match their Rust code
+///
+/// Fails with the mismatches if there are any.  Use [find_mismatches] to get them as a
+/// [CheckReport].
+pub fn check_library(
+    library_path: &Utf8Path,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<()> {
+    let report = find_mismatches(library_path, config_supplier)?;
+    if !report.is_empty() {
+        bail!("The UDL files don't match the Rust code:\n{report}");
+    }
+    Ok(())
+}
+
+/// Find the differences between the UDL files of the crates in a library and their Rust code
+///
+/// The UDL files are found using `config_supplier`, in the same way as when generating bindings
+/// in library mode.  Crates without UDL files are skipped.
+///
+/// The checksum values are read by loading the library.  If it can't be loaded, for example
+/// because it's a static library, only the names of the checksum functions are compared.
+pub fn find_mismatches(
+    library_path: &Utf8Path,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<CheckReport> {
+    let (items, _) = library_mode::extract_from_libraries(&[library_path], true)?;
+    let checksum_symbols = macro_metadata::extract_library_metadata(library_path)?.checksum_symbols;
+    let mut metadata_groups = create_metadata_groups(&items);
+    group_metadata(&mut metadata_groups, items)?;
+
+    let mut udl_checksums = HashMap::new();
+    for group in metadata_groups.values() {
+        let crate_name = &group.namespace.crate_name;
+        // The checksums are computed from the UDL items as they were parsed, like the scaffolding
+        // does, rather than after their external types were fixed up.
+        if let Some(udl_group) =
+            library_mode::load_udl_metadata(group, crate_name, config_supplier)?
+        {
+            let ci = ComponentInterface::from_metadata(udl_group)?;
+            udl_checksums.insert(crate_name.clone(), ci.iter_checksums().collect());
+        }
+    }
+    let library_checksums = read_checksums(library_path, &checksum_symbols);
+
+    let udl_groups = library_mode::udl_metadata_groups(&metadata_groups, config_supplier)?;
+    let mut report = compare_udl_groups(&udl_groups, &metadata_groups);
+    report.merge(compare_udl_checksums(&udl_checksums, &library_checksums));
+    Ok(report)
+}
+
+// The checksum functions exported by a library, with their values if it could be loaded
+fn read_checksums(
+    library_path: &Utf8Path,
+    checksum_symbols: &BTreeSet<String>,
+) -> HashMap<String, Option<u16>> {
+    // SAFETY: loading the library runs its initialization code, which is the code the bindings
+    // would run when they load it.
+    let library = unsafe { libloading::Library::new(library_path) }.ok();
+    checksum_symbols
+        .iter()
+        .map(|symbol| {
+            let value = library.as_ref().and_then(|library| {
+                // SAFETY: the checksum functions take no arguments and return a `u16`.
+                unsafe {
+                    let checksum_fn = library
+                        .get::<extern "C" fn() -> u16>(symbol.as_bytes())
+                        .ok()?;
+                    Some(checksum_fn())
+                }
+            });
+            (symbol.clone(), value)
+        })
+        .collect()
+}
+
+/// Compare the UDL metadata of each crate with the metadata exported from its Rust code
+///
+/// Both maps are keyed by crate name.  Only items which are in both groups are compared, since
+/// it's normal for a crate to define some items in its UDL file and others with proc-macros.
+pub fn compare_udl_groups(
+    udl_groups: &HashMap<String, MetadataGroup>,
+    library_groups: &HashMap<String, MetadataGroup>,
+) -> CheckReport {
+    let mut crates: Vec<_> = udl_groups
+        .iter()
+        .filter_map(|(crate_name, udl_group)| {
+            let library_group = library_groups.get(crate_name)?;
+            let library_items: HashMap<_, _> = library_group
+                .items
+                .iter()
+                .map(|item| (describe_item(item), item))
+                .collect();
+            let mismatches: Vec<_> = udl_group
+                .items
+                .iter()
+                .filter_map(|udl_item| {
+                    let item = describe_item(udl_item);
+                    let library_item = library_items.get(&item)?;
+                    Some((item, item_mismatches(udl_item, library_item)))
+                })
+                .flat_map(|(item, mismatches)| {
+                    mismatches.into_iter().map(move |mismatch| ItemMismatch {
+                        item: item.clone(),
+                        mismatch,
+                    })
+                })
+                .collect();
+            (!mismatches.is_empty()).then(|| CrateMismatches {
+                crate_name: crate_name.clone(),
+                mismatches,
+            })
+        })
+        .collect();
+    crates.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+    CheckReport { crates }
+}
+
+/// Compare the API checksums of each crate's UDL file with the checksum functions of its library
+///
+/// `udl_checksums` is keyed by crate name and lists the checksum function names and values, like
+/// [ComponentInterface::iter_checksums].  `library_checksums` maps the checksum functions
+/// exported by the library to their values, or to `None` if the values couldn't be read.  Checksum
+/// functions which are only in the library are ignored, since they're for items exported by
+/// proc-macros.
+pub fn compare_udl_checksums(
+    udl_checksums: &HashMap<String, Vec<(String, u16)>>,
+    library_checksums: &HashMap<String, Option<u16>>,
+) -> CheckReport {
+    let mut crates: Vec<_> = udl_checksums
+        .iter()
+        .filter_map(|(crate_name, checksums)| {
+            let mismatches: Vec<_> = checksums
+                .iter()
+                .filter_map(|(symbol, udl_checksum)| {
+                    let library_checksum = match library_checksums.get(symbol) {
+                        None => "missing".to_string(),
+                        Some(Some(checksum)) if checksum != udl_checksum => checksum.to_string(),
+                        Some(_) => return None,
+                    };
+                    Some(ItemMismatch {
+                        item: format!("checksum function `{symbol}`"),
+                        mismatch: Mismatch {
+                            part: "checksum".to_string(),
+                            left: udl_checksum.to_string(),
+                            right: library_checksum,
+                        },
+                    })
+                })
+                .collect();
+            (!mismatches.is_empty()).then(|| CrateMismatches {
+                crate_name: crate_name.clone(),
+                mismatches,
+            })
+        })
+        .collect();
+    crates.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+    CheckReport { crates }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{FnMetadata, FnParamMetadata, Metadata, NamespaceMetadata, Type};
+
+    fn group(crate_name: &str, items: Vec<Metadata>) -> (String, MetadataGroup) {
+        let group = MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: crate_name.to_string(),
+                name: crate_name.to_string(),
+            },
+            namespace_docstring: None,
+            items: items.into_iter().collect(),
+        };
+        (crate_name.to_string(), group)
+    }
+
+    fn func(crate_name: &str, name: &str, arg_type: Type) -> Metadata {
+        FnMetadata {
+            module_path: crate_name.to_string(),
+            name: name.to_string(),
+            is_async: false,
+            inputs: vec![FnParamMetadata::simple("value", arg_type)],
+            return_type: None,
+            throws: None,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_compare_udl_groups() {
+        let udl_groups = HashMap::from([
+            group(
+                "crate_a",
+                vec![
+                    func("crate_a", "same", Type::UInt32),
+                    func("crate_a", "changed", Type::UInt32),
+                    func("crate_a", "udl_only", Type::UInt32),
+                ],
+            ),
+            group("crate_b", vec![func("crate_b", "same", Type::String)]),
+        ]);
+        let library_groups = HashMap::from([
+            group(
+                "crate_a",
+                vec![
+                    func("crate_a", "same", Type::UInt32),
+                    func("crate_a", "changed", Type::Int64),
+                    func("crate_a", "rust_only", Type::UInt32),
+                ],
+            ),
+            group("crate_b", vec![func("crate_b", "same", Type::String)]),
+            group("crate_c", vec![func("crate_c", "same", Type::Int8)]),
+        ]);
+        let report = compare_udl_groups(&udl_groups, &library_groups);
+        assert_eq!(
+            report.to_string(),
+            "crate `crate_a`:\n  \
+             function `changed`: signature\n    \
+             UDL:  fn(value: u32)\n    \
+             Rust: fn(value: i64)\n"
+        );
+        assert!(compare_udl_groups(&udl_groups, &udl_groups).is_empty());
+    }
+
+    #[test]
+    fn test_compare_udl_checksums() {
+        let udl_checksums = HashMap::from([
+            (
+                "crate_a".to_string(),
+                vec![
+                    ("uniffi_crate_a_checksum_func_same".to_string(), 1),
+                    ("uniffi_crate_a_checksum_func_changed".to_string(), 2),
+                    ("uniffi_crate_a_checksum_func_udl_only".to_string(), 3),
+                    ("uniffi_crate_a_checksum_func_unknown".to_string(), 4),
+                ],
+            ),
+            (
+                "crate_b".to_string(),
+                vec![("uniffi_crate_b_checksum_func_same".to_string(), 5)],
+            ),
+        ]);
+        let library_checksums = HashMap::from([
+            ("uniffi_crate_a_checksum_func_same".to_string(), Some(1)),
+            ("uniffi_crate_a_checksum_func_changed".to_string(), Some(20)),
+            ("uniffi_crate_a_checksum_func_unknown".to_string(), None),
+            (
+                "uniffi_crate_a_checksum_func_rust_only".to_string(),
+                Some(6),
+            ),
+            ("uniffi_crate_b_checksum_func_same".to_string(), Some(5)),
+        ]);
+        let report = compare_udl_checksums(&udl_checksums, &library_checksums);
+        assert_eq!(
+            report.to_string(),
+            "crate `crate_a`:\n  \
+             checksum function `uniffi_crate_a_checksum_func_changed`: checksum\n    \
+             UDL:  2\n    \
+             Rust: 20\n  \
+             checksum function `uniffi_crate_a_checksum_func_udl_only`: checksum\n    \
+             UDL:  3\n    \
+             Rust: missing\n"
+        );
+    }
+}
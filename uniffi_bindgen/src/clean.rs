@@ -0,0 +1,311 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Remove stale bindings from the output directory.
+//!
+//! When a crate is removed from a library, or a namespace renamed, the bindings generated for it
+//! previously are left in the output directory and the foreign build keeps compiling them.  After
+//! the bindings are written, the files in the output directory which match the generator's
+//! [BindingGenerator::owned_file_patterns] but weren't written by this run are candidates for
+//! removal.  Since the output directory may also hold hand-written sources, like `src/main/kotlin`
+//! or a Python package, a candidate is only stale if UniFFI generated it: either it's listed in the
+//! [MANIFEST_FILE] written by an earlier clean, or it starts with the header of generated files.
+//! Other files are never touched.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+
+use crate::{BindingGenerator, Result};
+
+/// The file in each cleaned output directory which lists the files written to it
+///
+/// Files listed in it are removed by the next clean if they weren't written again, even if they
+/// don't have the [GENERATED_HEADER], like Swift module maps.
+pub const MANIFEST_FILE: &str = ".uniffi-generated";
+
+/// The header which the builtin generators put at the top of the files they generate
+pub const GENERATED_HEADER: &str =
+    "This file was autogenerated by some hot garbage in the `uniffi` crate.";
+
+// How far into a file to look for the header, since it can follow a license header and the
+// Python module docstring
+const HEADER_SEARCH_LEN: u64 = 16 * 1024;
+
+/// Finds and removes stale bindings
+///
+/// Pass this to [crate::generate_external_bindings] or
+/// [crate::library_mode::generate_bindings_multi], which record the files they write in it.  Then
+/// call [Self::remove_stale_files], or [Self::stale_files] for a dry run.
+///
+/// Runs for several languages can be recorded before cleaning, and a file written by any of them
+/// isn't stale, so generators whose patterns overlap don't remove each other's files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutDirCleaner {
+    // The glob patterns for the owned files in each output directory, relative to it
+    patterns: BTreeMap<Utf8PathBuf, BTreeSet<String>>,
+    written_files: BTreeSet<Utf8PathBuf>,
+}
+
+impl OutDirCleaner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `binding_generator` wrote `written_files` to `out_dir`
+    pub fn add_run<T: BindingGenerator>(
+        &mut self,
+        binding_generator: &T,
+        out_dir: &Utf8Path,
+        written_files: &[Utf8PathBuf],
+    ) {
+        self.patterns
+            .entry(normalize(out_dir))
+            .or_default()
+            .extend(binding_generator.owned_file_patterns());
+        self.written_files
+            .extend(written_files.iter().map(|p| normalize(p)));
+    }
+
+    /// The stale files, which match the patterns of a generator and were generated by UniFFI, but
+    /// weren't written by any run
+    pub fn stale_files(&self) -> Result<BTreeSet<Utf8PathBuf>> {
+        let mut stale = BTreeSet::new();
+        for (out_dir, patterns) in &self.patterns {
+            let manifest = read_manifest(out_dir)?;
+            let escaped_out_dir = glob::Pattern::escape(out_dir.as_str());
+            for pattern in patterns {
+                for path in glob::glob(&format!("{escaped_out_dir}/{pattern}"))? {
+                    let path = Utf8PathBuf::try_from(path?)?;
+                    let normalized = normalize(&path);
+                    if path.is_file()
+                        && !self.written_files.contains(&normalized)
+                        && (manifest.contains(&normalized) || has_generated_header(&path)?)
+                    {
+                        stale.insert(path);
+                    }
+                }
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Remove the stale files, returning their paths
+    ///
+    /// The files written to each output directory are then listed in its [MANIFEST_FILE].
+    pub fn remove_stale_files(&self) -> Result<BTreeSet<Utf8PathBuf>> {
+        let stale_files = self.stale_files()?;
+        for path in &stale_files {
+            fs::remove_file(path)?;
+        }
+        for out_dir in self.patterns.keys() {
+            let mut manifest = String::new();
+            for path in &self.written_files {
+                if let Ok(path) = path.strip_prefix(out_dir) {
+                    manifest.push_str(path.as_str());
+                    manifest.push('\n');
+                }
+            }
+            fs::write(out_dir.join(MANIFEST_FILE), manifest)?;
+        }
+        Ok(stale_files)
+    }
+}
+
+// The files listed in the manifest of `out_dir`, if it has one
+fn read_manifest(out_dir: &Utf8Path) -> Result<BTreeSet<Utf8PathBuf>> {
+    let path = out_dir.join(MANIFEST_FILE);
+    if !path.is_file() {
+        return Ok(BTreeSet::new());
+    }
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| normalize(&out_dir.join(line)))
+        .collect())
+}
+
+// Does the file start with the header of generated files?
+fn has_generated_header(path: &Utf8Path) -> Result<bool> {
+    let mut start = vec![];
+    fs::File::open(path)?
+        .take(HEADER_SEARCH_LEN)
+        .read_to_end(&mut start)?;
+    Ok(start
+        .windows(GENERATED_HEADER.len())
+        .any(|window| window == GENERATED_HEADER.as_bytes()))
+}
+
+// Drop `.` components, so that `out/./foo.py` and `out/foo.py` compare equal
+fn normalize(path: &Utf8Path) -> Utf8PathBuf {
+    path.components().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Component, GenerationSettings};
+
+    // A generator which owns the files matching `patterns`
+    struct PatternGenerator {
+        patterns: &'static [&'static str],
+    }
+
+    impl BindingGenerator for PatternGenerator {
+        type Config = ();
+
+        fn new_config(&self, _root_toml: &toml::Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn update_component_configs(
+            &self,
+            _settings: &GenerationSettings,
+            _components: &mut Vec<Component<()>>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn write_bindings(
+            &self,
+            _settings: &GenerationSettings,
+            _components: &[Component<()>],
+        ) -> Result<Vec<Utf8PathBuf>> {
+            Ok(vec![])
+        }
+
+        fn owned_file_patterns(&self) -> Vec<String> {
+            self.patterns.iter().map(|p| p.to_string()).collect()
+        }
+    }
+
+    // A temporary output directory holding `files`, as (path, contents) pairs
+    fn out_dir_with(name: &str, files: &[(&str, &str)]) -> Utf8PathBuf {
+        let out_dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-clean-test-{name}-{}", std::process::id()));
+        for (path, contents) in files {
+            let path = out_dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        out_dir
+    }
+
+    fn generated(comment: &str) -> String {
+        format!("{comment} {GENERATED_HEADER}\n")
+    }
+
+    #[test]
+    fn test_stale_files() {
+        let out_dir = out_dir_with(
+            "stale",
+            &[
+                ("current.py", &generated("#")),
+                (
+                    "removed.py",
+                    &format!("\"\"\"Docs\"\"\"\n\n{}", generated("#")),
+                ),
+                ("current.h", &generated("//")),
+                ("notes.txt", &generated("//")),
+                ("uniffi/current/current.kt", &generated("//")),
+                ("uniffi/removed/removed.kt", &generated("//")),
+            ],
+        );
+
+        let mut cleaner = OutDirCleaner::new();
+        cleaner.add_run(
+            &PatternGenerator {
+                patterns: &["*.py", "**/*.kt", "*.h"],
+            },
+            &out_dir,
+            &[
+                out_dir.join("current.py"),
+                out_dir.join("./uniffi/current/current.kt"),
+            ],
+        );
+        // Another generator owning the same files wrote `current.h`
+        cleaner.add_run(
+            &PatternGenerator { patterns: &["*.h"] },
+            &out_dir,
+            &[out_dir.join("current.h")],
+        );
+        let stale = BTreeSet::from([
+            out_dir.join("removed.py"),
+            out_dir.join("uniffi/removed/removed.kt"),
+        ]);
+        assert_eq!(cleaner.stale_files().unwrap(), stale);
+        assert_eq!(cleaner.remove_stale_files().unwrap(), stale);
+        assert!(!out_dir.join("removed.py").exists());
+        assert!(out_dir.join("notes.txt").exists());
+        assert!(cleaner.stale_files().unwrap().is_empty());
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn test_user_files_survive() {
+        let out_dir = out_dir_with(
+            "user",
+            &[
+                ("generated.py", &generated("#")),
+                ("app.py", "import generated\n"),
+                ("com/example/MainActivity.kt", "package com.example\n"),
+                ("uniffi/generated/generated.kt", &generated("//")),
+                ("uniffi/removed/removed.kt", &generated("//")),
+            ],
+        );
+        let mut cleaner = OutDirCleaner::new();
+        cleaner.add_run(
+            &PatternGenerator {
+                patterns: &["*.py", "**/*.kt"],
+            },
+            &out_dir,
+            &[
+                out_dir.join("generated.py"),
+                out_dir.join("uniffi/generated/generated.kt"),
+            ],
+        );
+        assert_eq!(
+            cleaner.remove_stale_files().unwrap(),
+            BTreeSet::from([out_dir.join("uniffi/removed/removed.kt")])
+        );
+        assert!(out_dir.join("app.py").exists());
+        assert!(out_dir.join("com/example/MainActivity.kt").exists());
+        assert!(out_dir.join("generated.py").exists());
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn test_manifest() {
+        // Module maps don't have the header, so they're only removed once they're in a manifest
+        let out_dir = out_dir_with(
+            "manifest",
+            &[
+                ("one.modulemap", "module one {}\n"),
+                ("two.modulemap", "module two {}\n"),
+            ],
+        );
+        let generator = PatternGenerator {
+            patterns: &["*.modulemap"],
+        };
+        let mut cleaner = OutDirCleaner::new();
+        cleaner.add_run(&generator, &out_dir, &[out_dir.join("one.modulemap")]);
+        assert!(cleaner.remove_stale_files().unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(out_dir.join(MANIFEST_FILE)).unwrap(),
+            "one.modulemap\n"
+        );
+
+        let mut cleaner = OutDirCleaner::new();
+        cleaner.add_run(&generator, &out_dir, &[out_dir.join("two.modulemap")]);
+        assert_eq!(
+            cleaner.remove_stale_files().unwrap(),
+            BTreeSet::from([out_dir.join("one.modulemap")])
+        );
+        assert!(out_dir.join("two.modulemap").exists());
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+}
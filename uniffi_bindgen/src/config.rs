@@ -0,0 +1,506 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Strict checking of `uniffi.toml` configs.
+//!
+//! Misspelt keys would otherwise be silently ignored, so unknown keys are errors which name the
+//! key and suggest the nearest known one.  `uniffi-bindgen` checks the top-level keys with
+//! [check_top_level_keys], and binding generators read their `[bindings.<language>]` table with
+//! [bindings_config], which checks every level of the table against the config struct.
+//!
+//! Other tables under `[bindings]` aren't checked, since they're read by other generators.  A
+//! generator which reads top-level tables of its own declares them with
+//! [crate::BindingGenerator::owned_tables].
+//!
+//! With `interpolate_env = true` at the top level, `${VAR}` and `${VAR:-default}` in string values
+//! are replaced with environment variables, see [interpolate_env].
+//!
+//! Relative paths in the [PATH_KEYS] of the `[bindings.<language>]` tables are relative to the
+//! directory of the config file which sets them, see [resolve_relative_paths].
+//!
+//! `max_default_bytes` limits the length of the byte string defaults of `bytes` fields and
+//! arguments, see [max_default_bytes].
+
+use anyhow::{bail, Result};
+use camino::Utf8Path;
+use serde::de::DeserializeOwned;
+
+use crate::diagnostics::{Code, Diagnostic};
+
+/// The top-level keys which `uniffi-bindgen` reads itself
+pub const TOP_LEVEL_KEYS: &[&str] = &[
+    "bindings",
+    "include_tags",
+    "interpolate_env",
+    "max_default_bytes",
+    "omit_crates",
+    "udl_path",
+];
+
+/// Check that the top-level keys of a config are known
+///
+/// The known keys are [TOP_LEVEL_KEYS] and the `owned_tables` of the binding generator.
+pub fn check_top_level_keys(root_toml: &toml::Value, owned_tables: &[String]) -> Result<()> {
+    let Some(table) = root_toml.as_table() else {
+        return Ok(());
+    };
+    let known: Vec<&str> = TOP_LEVEL_KEYS
+        .iter()
+        .copied()
+        .chain(owned_tables.iter().map(String::as_str))
+        .collect();
+    for key in table.keys() {
+        if !known.contains(&key.as_str()) {
+            return Err(unknown_key_error(key, key, &known).into());
+        }
+    }
+    Ok(())
+}
+
+/// Deserialize the `[bindings.<language>]` table of a config, or the default if it's missing
+///
+/// The config struct, and any structs inside it, should use `#[serde(deny_unknown_fields)]`, so
+/// that unknown keys are errors.  The error names the full path of the key and suggests the
+/// nearest known key.
+pub fn bindings_config<T: DeserializeOwned + Default>(
+    root_toml: &toml::Value,
+    language: &str,
+) -> Result<T> {
+    let Some(table) = root_toml.get("bindings").and_then(|b| b.get(language)) else {
+        return Ok(T::default());
+    };
+    // Deserializing from text, rather than from the `toml::Value`, makes toml's errors include
+    // the path of the table they're about.
+    let text = toml::to_string(table)?;
+    toml::from_str(&text).map_err(|err: toml::de::Error| {
+        let message = err.to_string();
+        let (message, table_path) = split_toml_error(&message);
+        let mut path = vec!["bindings", language];
+        path.extend(table_path.into_iter().flat_map(|p| p.split('.')));
+        match parse_unknown_field(message) {
+            Some((key, expected)) => {
+                path.push(key);
+                unknown_key_error(&path.join("."), key, &expected).into()
+            }
+            None => Diagnostic::error(
+                Code::InvalidConfig,
+                format!("invalid config in [{}]: {message}", path.join(".")),
+            )
+            .into(),
+        }
+    })
+}
+
+/// The longest byte string default allowed when the config doesn't set `max_default_bytes`
+pub const DEFAULT_MAX_DEFAULT_BYTES: usize = 1024;
+
+/// The longest byte string default allowed, from the `max_default_bytes` key of a config
+///
+/// Byte string defaults are written out in full wherever they're used, so long ones bloat the
+/// generated code.
+pub fn max_default_bytes(root_toml: &toml::Value) -> Result<usize> {
+    match root_toml.get("max_default_bytes") {
+        None => Ok(DEFAULT_MAX_DEFAULT_BYTES),
+        Some(toml::Value::Integer(max)) if *max >= 0 => Ok(*max as usize),
+        Some(_) => bail!(Diagnostic::error(
+            Code::InvalidConfig,
+            "`max_default_bytes` must be a non-negative integer"
+        )),
+    }
+}
+
+/// Replace environment variable references in the string values of a config
+///
+/// This only happens if the config has `interpolate_env = true`, so configs with a literal `${`
+/// in their values keep working.  `${VAR}` is replaced with the value of `VAR`, and fails if it
+/// isn't set, naming the key which referenced it.  `${VAR:-default}` is replaced with `default`
+/// if `VAR` isn't set or is empty.
+pub fn interpolate_env(config: &mut toml::value::Table) -> Result<()> {
+    interpolate_env_with(config, |name| std::env::var(name).ok())
+}
+
+fn interpolate_env_with(
+    config: &mut toml::value::Table,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match config.get("interpolate_env") {
+        None => return Ok(()),
+        Some(toml::Value::Boolean(enabled)) => {
+            if !enabled {
+                return Ok(());
+            }
+        }
+        Some(_) => bail!(Diagnostic::error(
+            Code::InvalidConfig,
+            "`interpolate_env` must be `true` or `false`"
+        )),
+    }
+    for (key, value) in config.iter_mut() {
+        interpolate_value(value, key, &lookup)?;
+    }
+    Ok(())
+}
+
+fn interpolate_value(
+    value: &mut toml::Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match value {
+        toml::Value::String(s) => *s = interpolate_string(s, path, lookup)?,
+        toml::Value::Array(values) => {
+            for (i, value) in values.iter_mut().enumerate() {
+                interpolate_value(value, &format!("{path}[{i}]"), lookup)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                interpolate_value(value, &format!("{path}.{key}"), lookup)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+fn interpolate_string(
+    s: &str,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String> {
+    let error = |message: String| Diagnostic::error(Code::InvalidConfig, message);
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            bail!(error(format!("unterminated `${{` in `{path}`")));
+        };
+        let reference = &rest[start + 2..start + len];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        if !is_env_var_name(name) {
+            bail!(error(format!(
+                "invalid environment variable name `{name}` in `{path}`"
+            )));
+        }
+        match (lookup(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => result.push_str(default),
+            (Some(value), _) => result.push_str(&value),
+            (None, Some(default)) => result.push_str(default),
+            (None, None) => bail!(error(format!(
+                "environment variable `{name}` referenced by `{path}` isn't set"
+            ))),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// The keys of the `[bindings.<language>]` tables which are paths
+pub const PATH_KEYS: &[&str] = &["template_overrides_dir"];
+
+/// Make the relative paths in a config file relative to `dir`, the directory it was read from
+///
+/// This is done as each file is loaded, before the files are merged, so a path is relative to the
+/// file which set it rather than to the directory `uniffi-bindgen` is run from.  Paths starting
+/// with `${` are left alone, since they're usually absolute once [interpolate_env] replaces the
+/// variable.
+pub fn resolve_relative_paths(config: &mut toml::value::Table, dir: &Utf8Path) {
+    let Some(toml::Value::Table(bindings)) = config.get_mut("bindings") else {
+        return;
+    };
+    for (_, language_config) in bindings.iter_mut() {
+        let toml::Value::Table(language_config) = language_config else {
+            continue;
+        };
+        for key in PATH_KEYS {
+            if let Some(toml::Value::String(path)) = language_config.get_mut(*key) {
+                if !path.starts_with("${") && Utf8Path::new(path).is_relative() {
+                    *path = dir.join(&*path).into_string();
+                }
+            }
+        }
+    }
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unknown_key_error(path: &str, key: &str, known: &[&str]) -> Diagnostic {
+    let mut message = format!("unknown config key `{path}`");
+    if let Some(nearest) = nearest_key(key, known) {
+        message.push_str(&format!(", did you mean `{nearest}`?"));
+    }
+    Diagnostic::error(Code::InvalidConfig, message)
+}
+
+// Split toml's error message, "{message} for key `{path}` at line {line} column {column}", into
+// the message and the path of the table or key it's about, if it isn't the top level.  The line
+// and column are dropped, since they're positions in the re-serialized table rather than in the
+// config file.
+fn split_toml_error(message: &str) -> (&str, Option<&str>) {
+    let message = match message.rfind(" at line ") {
+        Some(end) => &message[..end],
+        None => message,
+    };
+    match message
+        .strip_suffix('`')
+        .and_then(|rest| rest.rsplit_once(" for key `"))
+    {
+        Some((message, path)) => (message, Some(path)),
+        None => (message, None),
+    }
+}
+
+// Parse serde's error for an unknown field, "unknown field `key`, expected one of `a`, `b`", into
+// the field and the expected ones
+fn parse_unknown_field(message: &str) -> Option<(&str, Vec<&str>)> {
+    let rest = message.strip_prefix("unknown field `")?;
+    let (key, rest) = rest.split_once('`')?;
+    // The expected fields are between backticks
+    let expected = rest.split('`').skip(1).step_by(2).collect();
+    Some((key, expected))
+}
+
+// The known key closest to `key`, if any are close enough to be a likely typo
+fn nearest_key<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Config {
+        #[allow(dead_code)]
+        cdylib_name: Option<String>,
+        #[allow(dead_code)]
+        package_name: Option<String>,
+        #[serde(default)]
+        #[allow(dead_code)]
+        custom_types: HashMap<String, CustomTypeConfig>,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct CustomTypeConfig {
+        #[allow(dead_code)]
+        imports: Option<Vec<String>>,
+        #[allow(dead_code)]
+        type_name: Option<String>,
+    }
+
+    fn error_message(toml: &str) -> String {
+        let root_toml: toml::Value = toml::de::from_str(toml).unwrap();
+        bindings_config::<Config>(&root_toml, "test")
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        assert_eq!(
+            error_message("[bindings.test]\ncydlib_name = \"foo\""),
+            "unknown config key `bindings.test.cydlib_name`, did you mean `cdylib_name`?"
+        );
+        assert_eq!(
+            error_message("[bindings.test.custom_types.Url]\nimprts = []"),
+            "unknown config key `bindings.test.custom_types.Url.imprts`, did you mean `imports`?"
+        );
+        assert_eq!(
+            error_message("[bindings.test]\nsomething_else = true"),
+            "unknown config key `bindings.test.something_else`"
+        );
+
+        let root_toml: toml::Value =
+            toml::de::from_str("[bindings.other]\nanything = true").unwrap();
+        assert!(bindings_config::<Config>(&root_toml, "test").is_ok());
+    }
+
+    // The path is where the key was found, even if a key with the same name is valid elsewhere
+    #[test]
+    fn test_unknown_key_path() {
+        assert_eq!(
+            error_message(
+                "[bindings.test]\n\
+                 package_name = \"org.example\"\n\
+                 [bindings.test.custom_types.Url]\n\
+                 type_name = \"URL\"\n\
+                 package_name = \"java.net\""
+            ),
+            "unknown config key `bindings.test.custom_types.Url.package_name`"
+        );
+        assert_eq!(
+            error_message("[bindings.test.custom_types.Url]\ntype_name = 1"),
+            "invalid config in [bindings.test.custom_types.Url.type_name]: \
+             invalid type: integer `1`, expected a string"
+        );
+    }
+
+    #[test]
+    fn test_top_level_keys() {
+        let root_toml: toml::Value =
+            toml::de::from_str("udl_pth = \"src\"\n[generator]\nkey = 1").unwrap();
+        assert_eq!(
+            check_top_level_keys(&root_toml, &["generator".to_string()])
+                .unwrap_err()
+                .to_string(),
+            "unknown config key `udl_pth`, did you mean `udl_path`?"
+        );
+        let root_toml: toml::Value = toml::de::from_str("[generator]\nkey = 1").unwrap();
+        assert!(check_top_level_keys(&root_toml, &["generator".to_string()]).is_ok());
+        assert!(check_top_level_keys(&root_toml, &[]).is_err());
+    }
+
+    #[test]
+    fn test_max_default_bytes() {
+        let max = |toml: &str| max_default_bytes(&toml::de::from_str(toml).unwrap());
+        assert_eq!(max("").unwrap(), DEFAULT_MAX_DEFAULT_BYTES);
+        assert_eq!(max("max_default_bytes = 4096").unwrap(), 4096);
+        assert_eq!(max("max_default_bytes = 0").unwrap(), 0);
+        for toml in ["max_default_bytes = -1", "max_default_bytes = \"1KiB\""] {
+            assert_eq!(
+                max(toml).unwrap_err().to_string(),
+                "`max_default_bytes` must be a non-negative integer"
+            );
+        }
+    }
+
+    fn interpolate(toml: &str) -> Result<toml::Value> {
+        let mut config: toml::value::Table = toml::de::from_str(toml).unwrap();
+        interpolate_env_with(&mut config, |name| match name {
+            "PACKAGE" => Some("org.example".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        })?;
+        Ok(config.into())
+    }
+
+    #[test]
+    fn test_interpolate_env() {
+        let config = interpolate(
+            r#"
+            interpolate_env = true
+            [bindings.kotlin]
+            package_name = "${PACKAGE}.ffi"
+            cdylib_name = "${LIB_NAME:-example}"
+            [bindings.kotlin.custom_types.Url]
+            imports = ["${EMPTY:-java.net.URL}", "$PACKAGE{}"]
+            "#,
+        )
+        .unwrap();
+        let kotlin = &config["bindings"]["kotlin"];
+        assert_eq!(kotlin["package_name"].as_str(), Some("org.example.ffi"));
+        assert_eq!(kotlin["cdylib_name"].as_str(), Some("example"));
+        assert_eq!(
+            kotlin["custom_types"]["Url"]["imports"],
+            toml::Value::Array(vec!["java.net.URL".into(), "$PACKAGE{}".into()])
+        );
+
+        // Without `interpolate_env`, values are left alone
+        let config = interpolate("[bindings.kotlin]\npackage_name = \"${PACKAGE}\"").unwrap();
+        assert_eq!(
+            config["bindings"]["kotlin"]["package_name"].as_str(),
+            Some("${PACKAGE}")
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_errors() {
+        let message = |toml: &str| interpolate(toml).unwrap_err().to_string();
+        assert_eq!(
+            message("interpolate_env = true\n[bindings.kotlin.custom_types.Url]\nimports = [\"${MISSING}\"]"),
+            "environment variable `MISSING` referenced by `bindings.kotlin.custom_types.Url.imports[0]` isn't set"
+        );
+        assert_eq!(
+            message("interpolate_env = true\nudl_path = \"${PACKAGE\""),
+            "unterminated `${` in `udl_path`"
+        );
+        assert_eq!(
+            message("interpolate_env = true\nudl_path = \"${}\""),
+            "invalid environment variable name `` in `udl_path`"
+        );
+        assert_eq!(
+            message("interpolate_env = \"yes\""),
+            "`interpolate_env` must be `true` or `false`"
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("cdylib_name", "cdylib_name"), 0);
+        assert_eq!(edit_distance("cydlib_name", "cdylib_name"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(
+            nearest_key("pakage_name", &["package_name", "cdylib_name"]),
+            Some("package_name")
+        );
+        assert_eq!(nearest_key("xyz", &["package_name"]), None);
+    }
+
+    #[test]
+    fn test_resolve_relative_paths() {
+        let mut config: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            template_overrides_dir = "templates/kotlin"
+            package_name = "org.example"
+
+            [bindings.python]
+            template_overrides_dir = "/abs/templates"
+
+            [bindings.swift]
+            template_overrides_dir = "${TEMPLATES}/swift"
+        "#,
+        )
+        .unwrap();
+        resolve_relative_paths(&mut config, Utf8Path::new("/crate"));
+        let dir = |language: &str| config["bindings"][language]["template_overrides_dir"].clone();
+        assert_eq!(
+            dir("kotlin"),
+            toml::Value::from(Utf8Path::new("/crate").join("templates/kotlin").as_str())
+        );
+        assert_eq!(dir("python"), toml::Value::from("/abs/templates"));
+        assert_eq!(dir("swift"), toml::Value::from("${TEMPLATES}/swift"));
+        // Other keys are left alone
+        assert_eq!(
+            config["bindings"]["kotlin"]["package_name"],
+            toml::Value::from("org.example")
+        );
+    }
+}
@@ -0,0 +1,336 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Makefile-style depfiles for bindings generation.
+//!
+//! Build systems like Make, Ninja, Gradle and Bazel can use a depfile to decide whether the
+//! bindings need to be generated again.  The depfile lists the files which were written, keyed by
+//! the primary output, and everything they were generated from: the library, UDL files and
+//! `uniffi.toml` files.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use uniffi_meta::UdlFile;
+
+use crate::{BindgenCrateConfigSupplier, PackageInfo, Result};
+
+/// The inputs and outputs of a bindings generation run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Depfile {
+    inputs: BTreeSet<Utf8PathBuf>,
+    outputs: BTreeSet<Utf8PathBuf>,
+}
+
+impl Depfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_input(&mut self, path: impl Into<Utf8PathBuf>) {
+        self.inputs.insert(path.into());
+    }
+
+    pub fn add_output(&mut self, path: impl Into<Utf8PathBuf>) {
+        self.outputs.insert(path.into());
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &Utf8Path> {
+        self.inputs.iter().map(Utf8PathBuf::as_path)
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = &Utf8Path> {
+        self.outputs.iter().map(Utf8PathBuf::as_path)
+    }
+
+    /// Render the depfile, with paths relative to `base_dir`
+    ///
+    /// The outputs are sorted, and the first one is used as the primary output.  All outputs are
+    /// listed as targets of a single rule, which depends on all of the inputs.
+    pub fn render(&self, base_dir: &Utf8Path) -> Result<String> {
+        let base_dir = absolute(base_dir)?;
+        let escaped = |paths: &BTreeSet<Utf8PathBuf>| -> Result<Vec<String>> {
+            paths
+                .iter()
+                .map(|p| Ok(escape(relative_to(&absolute(p)?, &base_dir).as_str())))
+                .collect()
+        };
+        let outputs = escaped(&self.outputs)?;
+        if outputs.is_empty() {
+            anyhow::bail!(
+                "No output files were generated, so there's nothing to key the depfile on"
+            );
+        }
+        let mut rendered = outputs.join(" ");
+        rendered.push(':');
+        for input in escaped(&self.inputs)? {
+            rendered.push_str(" \\\n  ");
+            rendered.push_str(&input);
+        }
+        rendered.push('\n');
+        Ok(rendered)
+    }
+
+    /// Write the depfile, with paths relative to the directory it's written to
+    pub fn write(&self, path: &Utf8Path) -> Result<()> {
+        let base_dir = match path.parent() {
+            Some(parent) if !parent.as_str().is_empty() => parent,
+            _ => Utf8Path::new("."),
+        };
+        fs::create_dir_all(base_dir)?;
+        fs::write(path, self.render(base_dir)?)?;
+        Ok(())
+    }
+}
+
+/// The modification times of the files in a directory, used to find which files are written by a
+/// bindings generator which doesn't report them, like an external generator.
+#[derive(Debug)]
+pub struct DirSnapshot {
+    dir: Utf8PathBuf,
+    modified: HashMap<Utf8PathBuf, SystemTime>,
+}
+
+impl DirSnapshot {
+    pub fn new(dir: &Utf8Path) -> Result<Self> {
+        Ok(Self {
+            dir: dir.to_owned(),
+            modified: files_in(dir)?,
+        })
+    }
+
+    /// The directory the snapshot is of
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// Files which were created or modified since the snapshot was taken
+    pub fn changed_files(&self) -> Result<Vec<Utf8PathBuf>> {
+        let mut changed: Vec<_> = files_in(&self.dir)?
+            .into_iter()
+            .filter(|(path, modified)| self.modified.get(path) != Some(modified))
+            .map(|(path, _)| path)
+            .collect();
+        changed.sort();
+        Ok(changed)
+    }
+}
+
+// All files under `dir`, with their modification times
+fn files_in(dir: &Utf8Path) -> Result<HashMap<Utf8PathBuf, SystemTime>> {
+    let mut files = HashMap::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        for entry in dir.read_dir_utf8()? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(entry.path().to_owned());
+            } else if file_type.is_file() {
+                files.insert(entry.path().to_owned(), entry.metadata()?.modified()?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// A [BindgenCrateConfigSupplier] which records the paths of the UDL and `uniffi.toml` files it
+/// reads, so they can be added to a [Depfile].
+pub struct RecordingConfigSupplier<'a> {
+    inner: &'a dyn BindgenCrateConfigSupplier,
+    paths: Mutex<BTreeSet<Utf8PathBuf>>,
+}
+
+impl<'a> RecordingConfigSupplier<'a> {
+    pub fn new(inner: &'a dyn BindgenCrateConfigSupplier) -> Self {
+        Self {
+            inner,
+            paths: Mutex::default(),
+        }
+    }
+
+    /// Add the files which were read to `depfile` as inputs
+    pub fn add_inputs_to(&self, depfile: &mut Depfile) {
+        for path in self.paths.lock().unwrap().iter() {
+            depfile.add_input(path.clone());
+        }
+    }
+
+    fn record(&self, path: Option<Utf8PathBuf>) {
+        // Files which don't exist can't be tracked by the build system.
+        if let Some(path) = path.filter(|p| p.is_file()) {
+            self.paths.lock().unwrap().insert(path);
+        }
+    }
+}
+
+impl BindgenCrateConfigSupplier for RecordingConfigSupplier<'_> {
+    fn get_toml(&self, crate_name: &str) -> Result<Option<toml::value::Table>> {
+        self.record(self.inner.get_toml_path(crate_name));
+        self.record(self.inner.get_workspace_toml_path(crate_name));
+        self.inner.get_toml(crate_name)
+    }
+
+    fn get_toml_path(&self, crate_name: &str) -> Option<Utf8PathBuf> {
+        self.inner.get_toml_path(crate_name)
+    }
+
+    fn get_workspace_toml_path(&self, crate_name: &str) -> Option<Utf8PathBuf> {
+        self.inner.get_workspace_toml_path(crate_name)
+    }
+
+    fn get_udl(&self, crate_name: &str, udl_name: &str) -> Result<String> {
+        self.inner.get_udl(crate_name, udl_name)
+    }
+
+    fn get_udl_file(&self, crate_name: &str, udl_file: &UdlFile) -> Result<String> {
+        self.record(self.inner.get_udl_path(crate_name, udl_file));
+        self.inner.get_udl_file(crate_name, udl_file)
+    }
+
+    fn get_udl_path(&self, crate_name: &str, udl_file: &UdlFile) -> Option<Utf8PathBuf> {
+        self.inner.get_udl_path(crate_name, udl_file)
+    }
+
+    fn get_udl_include(&self, crate_name: &str, path: &Utf8Path) -> Result<String> {
+        self.record(Some(path.to_owned()));
+        self.inner.get_udl_include(crate_name, path)
+    }
+
+    fn get_package_info(&self, crate_name: &str) -> Option<PackageInfo> {
+        self.inner.get_package_info(crate_name)
+    }
+}
+
+// Make a path absolute and resolve symlinks, so paths can be compared with each other.
+pub(crate) fn absolute(path: &Utf8Path) -> Result<Utf8PathBuf> {
+    if let Ok(path) = path.canonicalize_utf8() {
+        return Ok(path);
+    }
+    // The file doesn't exist, but its directory might.
+    if let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) {
+        if let Ok(parent) = parent.canonicalize_utf8() {
+            return Ok(parent.join(file_name));
+        }
+    }
+    if path.is_absolute() {
+        return Ok(path.to_owned());
+    }
+    let cwd = std::env::current_dir().context("Failed to get the current directory")?;
+    let cwd = Utf8PathBuf::try_from(cwd).context("The current directory isn't UTF-8")?;
+    Ok(cwd.join(path))
+}
+
+// Express an absolute path relative to an absolute base directory.
+pub(crate) fn relative_to(path: &Utf8Path, base_dir: &Utf8Path) -> Utf8PathBuf {
+    let path_components: Vec<_> = path.components().collect();
+    let base_components: Vec<_> = base_dir.components().collect();
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    // Paths on different Windows drives don't have a relative path.
+    if common == 0 {
+        return path.to_owned();
+    }
+    let mut relative = Utf8PathBuf::new();
+    for _ in common..base_components.len() {
+        relative.push(Utf8Component::ParentDir);
+    }
+    for component in &path_components[common..] {
+        relative.push(component);
+    }
+    if relative.as_str().is_empty() {
+        relative.push(Utf8Component::CurDir);
+    }
+    relative
+}
+
+// Escape a path for a Makefile rule.
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '$' => escaped.push_str("$$"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_relative_to() {
+        let base = Utf8Path::new("/build/out");
+        assert_eq!(
+            relative_to(Utf8Path::new("/build/out/foo.py"), base),
+            "foo.py"
+        );
+        assert_eq!(
+            relative_to(Utf8Path::new("/build/lib/libfoo.so"), base),
+            "../lib/libfoo.so"
+        );
+        assert_eq!(
+            relative_to(Utf8Path::new("/src/foo/uniffi.toml"), base),
+            "../../src/foo/uniffi.toml"
+        );
+        assert_eq!(relative_to(Utf8Path::new("/build/out"), base), ".");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("my dir/foo#1.py"), "my\\ dir/foo\\#1.py");
+        assert_eq!(escape("$HOME/foo"), "$$HOME/foo");
+    }
+
+    #[test]
+    fn test_write() {
+        let tmp_dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-depfile-test-{}", std::process::id()));
+        let out_dir = tmp_dir.join("out dir");
+        fs::create_dir_all(&out_dir).unwrap();
+        let snapshot = DirSnapshot::new(&out_dir).unwrap();
+        fs::write(out_dir.join("foo.py"), "").unwrap();
+        fs::write(out_dir.join("bar.py"), "").unwrap();
+        fs::write(tmp_dir.join("foo.udl"), "").unwrap();
+
+        let mut depfile = Depfile::new();
+        for output in snapshot.changed_files().unwrap() {
+            depfile.add_output(output);
+        }
+        depfile.add_input(tmp_dir.join("foo.udl"));
+        depfile.add_input(tmp_dir.join("libfoo.so"));
+        depfile.write(&out_dir.join("foo.d")).unwrap();
+        assert_eq!(
+            fs::read_to_string(out_dir.join("foo.d")).unwrap(),
+            "bar.py foo.py: \\\n  ../foo.udl \\\n  ../libfoo.so\n"
+        );
+
+        // Nothing changed since this snapshot, so no outputs.
+        assert!(DirSnapshot::new(&out_dir)
+            .unwrap()
+            .changed_files()
+            .unwrap()
+            .is_empty());
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
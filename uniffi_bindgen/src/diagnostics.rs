@@ -0,0 +1,251 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Structured errors and warnings, for tools which run `uniffi-bindgen`.
+//!
+//! A [Diagnostic] has a [Code] which tools can match on, along with the file, position and crate
+//! it's about when they're known.  Errors are returned as `anyhow` errors wrapping a
+//! [Diagnostic], which [Diagnostic::from_error] recovers.  Warnings are printed with
+//! [Diagnostic::emit].
+//!
+//! By default diagnostics are printed for people to read.  With [MessageFormat::Json], which the
+//! CLI uses for `--message-format json`, each one is printed to stdout as a line of JSON instead:
+//!
+//! ```json
+//! {"level":"error","code":"udl-parse","message":"parse error at line 2, column 5: `u32 get_value()`","file":"src/example.udl","line":2,"column":5,"crate":"example"}
+//! ```
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use camino::Utf8PathBuf;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// The kind of problem a [Diagnostic] reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Code {
+    /// The UDL couldn't be parsed
+    UdlParse,
+    /// The namespace is missing, or doesn't match the other definitions of it
+    UnknownNamespace,
+    /// An item is defined more than once, with different definitions
+    DuplicateItem,
+    /// A config file couldn't be read, or has invalid values
+    InvalidConfig,
+    /// The generated bindings couldn't be formatted
+    Format,
+    /// Anything else
+    Other,
+}
+
+/// How diagnostics are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    /// Newline-delimited JSON on stdout
+    Json,
+}
+
+static JSON_MESSAGES: AtomicBool = AtomicBool::new(false);
+
+/// Set how diagnostics are printed by this process
+pub fn set_message_format(format: MessageFormat) {
+    JSON_MESSAGES.store(format == MessageFormat::Json, Ordering::Relaxed);
+}
+
+pub fn message_format() -> MessageFormat {
+    if JSON_MESSAGES.load(Ordering::Relaxed) {
+        MessageFormat::Json
+    } else {
+        MessageFormat::Human
+    }
+}
+
+/// Print an error which ended the run, in the current [MessageFormat]
+pub fn report_error(err: &anyhow::Error) {
+    match message_format() {
+        MessageFormat::Human => eprintln!("{err}"),
+        MessageFormat::Json => Diagnostic::from_error(err, Code::Other).emit(),
+    }
+}
+
+/// An error or warning
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub code: Code,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<Utf8PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(rename = "crate", skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: Code, message: impl Into<String>) -> Self {
+        Self::new(Level::Error, code, message.into())
+    }
+
+    pub fn warning(code: Code, message: impl Into<String>) -> Self {
+        Self::new(Level::Warning, code, message.into())
+    }
+
+    fn new(level: Level, code: Code, message: String) -> Self {
+        Self {
+            level,
+            code,
+            message,
+            file: None,
+            line: None,
+            column: None,
+            crate_name: None,
+        }
+    }
+
+    /// Convert an error into a diagnostic
+    ///
+    /// If there's a [Diagnostic] in the error's chain, its code and location are used, otherwise
+    /// the error has `code`.  UDL parse and validation errors from `uniffi_udl` have their
+    /// position, and the file they're in if it was included by the UDL.  The message
+    /// is the whole chain, since the context added to the error is usually useful.
+    pub fn from_error(err: &anyhow::Error, code: Code) -> Self {
+        let message = format!("{err:#}");
+        for cause in err.chain() {
+            if let Some(diagnostic) = cause.downcast_ref::<Diagnostic>() {
+                return Self {
+                    message,
+                    ..diagnostic.clone()
+                };
+            }
+            if let Some(parse_error) = cause.downcast_ref::<uniffi_udl::ParseError>() {
+                return Self {
+                    file: utf8_path(parse_error.file.as_ref()),
+                    line: Some(parse_error.line),
+                    column: Some(parse_error.column),
+                    ..Self::error(Code::UdlParse, message)
+                };
+            }
+            if let Some(validation_error) = cause.downcast_ref::<uniffi_udl::ValidationError>() {
+                return Self {
+                    file: utf8_path(validation_error.file.as_ref()),
+                    line: Some(validation_error.line),
+                    column: Some(validation_error.column),
+                    ..Self::error(Code::UdlParse, message)
+                };
+            }
+        }
+        Self::error(code, message)
+    }
+
+    /// Print the diagnostic in the current [MessageFormat]
+    pub fn emit(&self) {
+        match message_format() {
+            MessageFormat::Human => match self.level {
+                Level::Error => eprintln!("Error: {}", self.message),
+                Level::Warning => println!("Warning: {}", self.message),
+            },
+            MessageFormat::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    /// The diagnostic as a single line of JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostics should serialize")
+    }
+}
+
+// The file a UDL error is in, when it's in a file included by the UDL.
+fn utf8_path(path: Option<&std::path::PathBuf>) -> Option<Utf8PathBuf> {
+    Utf8PathBuf::from_path_buf(path?.clone()).ok()
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+// Convert the error from `result` into a [Diagnostic] with `code`, unless it already has one,
+// filling in the location with `locate` if it isn't known.
+pub(crate) fn diagnose<T>(
+    result: anyhow::Result<T>,
+    code: Code,
+    locate: impl FnOnce(&mut Diagnostic),
+) -> anyhow::Result<T> {
+    result.map_err(|err| {
+        let mut diagnostic = Diagnostic::from_error(&err, code);
+        locate(&mut diagnostic);
+        diagnostic.into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn test_from_error() {
+        let err = anyhow!("something failed");
+        assert_eq!(
+            Diagnostic::from_error(&err, Code::InvalidConfig),
+            Diagnostic::error(Code::InvalidConfig, "something failed")
+        );
+
+        // The code from a diagnostic in the chain is kept, and the context is in the message.
+        let err = anyhow::Error::from(Diagnostic::error(Code::DuplicateItem, "duplicate `Foo`"))
+            .context("failed to add metadata");
+        assert_eq!(
+            Diagnostic::from_error(&err, Code::Other),
+            Diagnostic::error(
+                Code::DuplicateItem,
+                "failed to add metadata: duplicate `Foo`"
+            )
+        );
+
+        let err = uniffi_udl::parse_udl("namespace test {};\nfoo", "crate_name").unwrap_err();
+        let diagnostic = Diagnostic::from_error(&err, Code::Other);
+        assert_eq!(diagnostic.code, Code::UdlParse);
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(2), Some(1)));
+
+        let err = uniffi_udl::parse_udl("namespace test {\n  const u8 X = 300;\n};", "crate_name")
+            .unwrap_err();
+        let diagnostic = Diagnostic::from_error(&err, Code::Other);
+        assert_eq!(diagnostic.code, Code::UdlParse);
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(2), Some(12)));
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let result: anyhow::Result<()> = Err(anyhow!("missing key")).context("bad config");
+        let err = diagnose(result, Code::InvalidConfig, |d| {
+            d.file.get_or_insert_with(|| "uniffi.toml".into());
+            d.crate_name.get_or_insert_with(|| "example".into());
+        })
+        .unwrap_err();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+        assert_eq!(diagnostic.code, Code::InvalidConfig);
+        assert_eq!(
+            diagnostic.to_json(),
+            r#"{"level":"error","code":"invalid-config","message":"bad config: missing key","file":"uniffi.toml","crate":"example"}"#
+        );
+    }
+}
@@ -0,0 +1,490 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Binding generators which are separate executables.
+//!
+//! When `uniffi-bindgen generate --library` is asked for a language it doesn't support itself, it
+//! looks for an executable named `uniffi-bindgen-<language>` on `PATH`, in the same way that cargo
+//! finds its subcommands.  The executable is run with:
+//!
+//! ```text
+//! uniffi-bindgen-<language> generate
+//!     --library <library-path>
+//!     --metadata <metadata-path>
+//!     --out-dir <out-dir>
+//!     [--crate <crate-name> [--with-dependencies]]
+//!     [--config <config-path>]...
+//!     [--omit-crate <crate-name>]...
+//!     [--no-format]
+//! ```
+//!
+//! - `--library`: the library the bindings are for.
+//! - `--metadata`: a JSON file with the library's metadata, in the format written by
+//!   [crate::library_mode::metadata_json].  It can be parsed into a
+//!   `Vec<uniffi_meta::MetadataGroup>`, or generators can extract the metadata from the library
+//!   themselves.  The file is deleted once the generator exits.
+//! - `--out-dir`: the directory to write the bindings to, which already exists.
+//! - `--crate`: only generate the bindings for this crate.
+//! - `--with-dependencies`: also generate the bindings for the crates whose types the `--crate`
+//!   crate uses, directly or indirectly.  [uniffi_meta::crate_dependencies] finds them.
+//! - `--config`: config files which should be merged with each crate's `uniffi.toml`, with their
+//!   values taking precedence.  If there's more than one, they're passed in order of increasing
+//!   precedence.
+//! - `--omit-crate`: don't generate bindings for this crate.  Crates can also be listed in the
+//!   `omit_crates` key of the config files.
+//! - `--include-tags`: a comma-separated list of export tags.  Items with a tag that isn't listed
+//!   are left out of the bindings, see [uniffi_meta::retain_included_tags].  Without it, the
+//!   `include_tags` key of the config files lists the tags.
+//! - `--no-format`: don't format the generated bindings.
+//!
+//! Language names are made of ASCII letters, digits, `_` and `-`, so generators with other
+//! characters in their names aren't found.
+//!
+//! The generator must exit with a non-zero status if it fails.  New arguments will only be added
+//! as options which generators can ignore.
+
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    ffi::{OsStr, OsString},
+    hash::{BuildHasher, Hasher},
+    io::{self, Write},
+    process::{self, Command},
+};
+
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+
+/// The prefix of the names of external binding generator executables
+pub const EXECUTABLE_PREFIX: &str = "uniffi-bindgen-";
+
+/// An external binding generator executable
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalGenerator {
+    pub language: String,
+    pub path: Utf8PathBuf,
+}
+
+/// The arguments passed to an external binding generator
+#[derive(Debug, Clone)]
+pub struct ExternalGeneratorArgs<'a> {
+    pub library_path: &'a Utf8Path,
+    /// The library's metadata, as returned by [crate::library_mode::metadata_json]
+    pub metadata_json: &'a str,
+    pub out_dir: &'a Utf8Path,
+    pub crate_name: Option<&'a str>,
+    /// Also generate the bindings for the crates which `crate_name` depends on
+    pub with_dependencies: bool,
+    /// Config files to merge with each crate's config, in order of increasing precedence
+    pub config_file_overrides: &'a [&'a Utf8Path],
+    /// Crates to leave out of the bindings
+    pub omit_crates: &'a [String],
+    /// The export tags of the items to generate, or `None` for every item
+    pub include_tags: Option<&'a [String]>,
+    pub try_format_code: bool,
+}
+
+impl ExternalGenerator {
+    /// Find the generator for `language` in the `PATH` environment variable
+    pub fn find_on_path(language: &str) -> Option<Self> {
+        Self::find(language, &env::var_os("PATH").unwrap_or_default())
+    }
+
+    /// Find the generator for `language` in a list of directories, in the format of `PATH`
+    ///
+    /// The first matching executable is used.
+    pub fn find(language: &str, search_path: &OsStr) -> Option<Self> {
+        if !is_valid_language(language) {
+            return None;
+        }
+        let file_name = format!("{EXECUTABLE_PREFIX}{language}{}", env::consts::EXE_SUFFIX);
+        search_dirs(search_path)
+            .map(|dir| dir.join(&file_name))
+            .find(|path| is_executable(path))
+            .map(|path| Self {
+                language: language.to_string(),
+                path,
+            })
+    }
+
+    /// Run the generator
+    ///
+    /// The metadata is written to a new temporary file, which is removed after the generator exits.
+    pub fn generate(&self, args: &ExternalGeneratorArgs<'_>) -> Result<()> {
+        if !is_valid_language(&self.language) {
+            bail!("Invalid language name: {:?}", self.language);
+        }
+        let metadata_path = write_temp_file(
+            &format!("uniffi-metadata-{}", self.language),
+            args.metadata_json,
+        )?;
+        let result = self.run(args, &metadata_path);
+        // The generator has finished with the file, so failing to remove it isn't an error.
+        let _ = fs::remove_file(&metadata_path);
+        result
+    }
+
+    fn run(&self, args: &ExternalGeneratorArgs<'_>, metadata_path: &Utf8Path) -> Result<()> {
+        let mut command = Command::new(&self.path);
+        command.args(protocol_args(args, metadata_path));
+        let status = command
+            .status()
+            .with_context(|| format!("Failed to run {}", self.path))?;
+        if !status.success() {
+            bail!(
+                "{} bindings generator `{}` failed ({status})",
+                self.language,
+                self.path
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The languages of all the external binding generators in a list of directories, in the format
+/// of `PATH`
+///
+/// The languages are sorted and each one is only listed once.
+pub fn available_languages(search_path: &OsStr) -> Vec<String> {
+    let mut languages: Vec<String> = search_dirs(search_path)
+        .filter_map(|dir| dir.read_dir_utf8().ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| is_executable(entry.path()))
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(name);
+            name.strip_prefix(EXECUTABLE_PREFIX)
+                .filter(|language| is_valid_language(language))
+                .map(str::to_string)
+        })
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// [available_languages] for the `PATH` environment variable
+pub fn available_languages_on_path() -> Vec<String> {
+    available_languages(&env::var_os("PATH").unwrap_or_default())
+}
+
+/// Whether `language` can be the language of an external generator
+///
+/// The name is used in the names of the executable and of temporary files, so it can only contain
+/// ASCII letters, digits, `_` and `-`.
+pub fn is_valid_language(language: &str) -> bool {
+    !language.is_empty()
+        && language
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+// Write `contents` to a new file in the temporary directory, named `{prefix}-{random}.json`
+//
+// The file is created exclusively, so an existing file or link with the same name is never
+// written through, and on Unix only the current user can read it.
+fn write_temp_file(prefix: &str, contents: &str) -> Result<Utf8PathBuf> {
+    let temp_dir = Utf8PathBuf::try_from(env::temp_dir())?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    for _ in 0..100 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(process::id());
+        let path = temp_dir.join(format!("{prefix}-{:016x}.json", hasher.finish()));
+        let mut file = match options.open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create {path}")),
+        };
+        let written = file.write_all(contents.as_bytes());
+        if let Err(e) = written {
+            let _ = fs::remove_file(&path);
+            return Err(e).with_context(|| format!("Failed to write {path}"));
+        }
+        return Ok(path);
+    }
+    bail!("Failed to create a temporary file in {temp_dir}")
+}
+
+fn protocol_args(args: &ExternalGeneratorArgs<'_>, metadata_path: &Utf8Path) -> Vec<OsString> {
+    let mut result: Vec<OsString> = vec![
+        "generate".into(),
+        "--library".into(),
+        args.library_path.into(),
+        "--metadata".into(),
+        metadata_path.into(),
+        "--out-dir".into(),
+        args.out_dir.into(),
+    ];
+    if let Some(crate_name) = args.crate_name {
+        result.extend(["--crate".into(), crate_name.into()]);
+        if args.with_dependencies {
+            result.push("--with-dependencies".into());
+        }
+    }
+    for config in args.config_file_overrides {
+        result.extend(["--config".into(), config.into()]);
+    }
+    for crate_name in args.omit_crates {
+        result.extend(["--omit-crate".into(), crate_name.into()]);
+    }
+    if let Some(tags) = args.include_tags {
+        result.extend(["--include-tags".into(), tags.join(",").into()]);
+    }
+    if !args.try_format_code {
+        result.push("--no-format".into());
+    }
+    result
+}
+
+// The directories in a `PATH`-style list, skipping any which aren't UTF-8
+fn search_dirs(search_path: &OsStr) -> impl Iterator<Item = Utf8PathBuf> + '_ {
+    env::split_paths(search_path).filter_map(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Utf8Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Utf8Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_dir(name: &str) -> Utf8PathBuf {
+        let dir = Utf8PathBuf::try_from(env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "uniffi-external-generator-{name}-{}",
+                process::id()
+            ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    fn write_executable(path: &Utf8Path, contents: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::write(path, contents).unwrap();
+        fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_valid_languages() {
+        for language in ["go", "csharp", "kotlin-multiplatform", "cpp_2"] {
+            assert!(is_valid_language(language), "{language}");
+        }
+        for language in ["", "../go", "go/x", "go\\x", ".go", "go.exe", "go x"] {
+            assert!(!is_valid_language(language), "{language}");
+            assert_eq!(ExternalGenerator::find(language, OsStr::new("")), None);
+        }
+        let generator = ExternalGenerator {
+            language: "../go".into(),
+            path: "uniffi-bindgen-go".into(),
+        };
+        let args = ExternalGeneratorArgs {
+            library_path: "libfoo.so".into(),
+            metadata_json: "[]",
+            out_dir: "out".into(),
+            crate_name: None,
+            with_dependencies: false,
+            config_file_overrides: &[],
+            omit_crates: &[],
+            include_tags: None,
+            try_format_code: false,
+        };
+        assert_eq!(
+            generator.generate(&args).unwrap_err().to_string(),
+            "Invalid language name: \"../go\""
+        );
+    }
+
+    #[test]
+    fn test_write_temp_file() {
+        let first = write_temp_file("uniffi-test", "first").unwrap();
+        let second = write_temp_file("uniffi-test", "second").unwrap();
+        assert_ne!(first, second);
+        assert!(first.file_name().unwrap().starts_with("uniffi-test-"));
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "second");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+    }
+
+    #[test]
+    fn test_protocol_args() {
+        let include_tags = ["pro".to_string(), "beta".to_string()];
+        let args = ExternalGeneratorArgs {
+            library_path: "libfoo.so".into(),
+            metadata_json: "[]",
+            out_dir: "out".into(),
+            crate_name: Some("foo"),
+            with_dependencies: true,
+            config_file_overrides: &["uniffi.toml".into(), "kotlin.toml".into()],
+            omit_crates: &["vendored".to_string()],
+            include_tags: Some(&include_tags),
+            try_format_code: false,
+        };
+        assert_eq!(
+            protocol_args(&args, "metadata.json".into()),
+            [
+                "generate",
+                "--library",
+                "libfoo.so",
+                "--metadata",
+                "metadata.json",
+                "--out-dir",
+                "out",
+                "--crate",
+                "foo",
+                "--with-dependencies",
+                "--config",
+                "uniffi.toml",
+                "--config",
+                "kotlin.toml",
+                "--omit-crate",
+                "vendored",
+                "--include-tags",
+                "pro,beta",
+                "--no-format"
+            ]
+        );
+
+        let args = ExternalGeneratorArgs {
+            crate_name: None,
+            config_file_overrides: &[],
+            omit_crates: &[],
+            include_tags: None,
+            try_format_code: true,
+            ..args
+        };
+        assert_eq!(
+            protocol_args(&args, "metadata.json".into()),
+            [
+                "generate",
+                "--library",
+                "libfoo.so",
+                "--metadata",
+                "metadata.json",
+                "--out-dir",
+                "out"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_generators() {
+        let dir = test_dir("find");
+        let first = dir.join("first");
+        let second = dir.join("second");
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+        write_executable(&first.join("uniffi-bindgen-go"), "");
+        write_executable(&second.join("uniffi-bindgen-go"), "");
+        write_executable(&second.join("uniffi-bindgen-csharp"), "");
+        // Not executable, so not a generator
+        fs::write(second.join("uniffi-bindgen-notes"), "").unwrap();
+        let search_path = env::join_paths([&first, &second, &dir.join("missing")]).unwrap();
+
+        assert_eq!(
+            ExternalGenerator::find("go", &search_path),
+            Some(ExternalGenerator {
+                language: "go".into(),
+                path: first.join("uniffi-bindgen-go"),
+            })
+        );
+        assert_eq!(
+            ExternalGenerator::find("csharp", &search_path)
+                .unwrap()
+                .path,
+            second.join("uniffi-bindgen-csharp")
+        );
+        assert_eq!(ExternalGenerator::find("notes", &search_path), None);
+        assert_eq!(ExternalGenerator::find("dart", &search_path), None);
+        assert_eq!(available_languages(&search_path), ["csharp", "go"]);
+    }
+
+    // A mock generator which copies its arguments and the metadata into the out dir
+    #[cfg(unix)]
+    #[test]
+    fn test_generator_handshake() {
+        let dir = test_dir("handshake");
+        let out_dir = dir.join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        write_executable(
+            &dir.join("uniffi-bindgen-mock"),
+            r#"#!/bin/sh
+echo "$@" > "$7/args"
+cp "$5" "$7/metadata.json"
+[ "$9" != "fail" ]
+"#,
+        );
+        let search_path = env::join_paths([&dir]).unwrap();
+        let generator = ExternalGenerator::find("mock", &search_path).unwrap();
+
+        let args = ExternalGeneratorArgs {
+            library_path: "libfoo.so".into(),
+            metadata_json: r#"[{"namespace": "foo"}]"#,
+            out_dir: &out_dir,
+            crate_name: Some("foo"),
+            with_dependencies: false,
+            config_file_overrides: &[],
+            omit_crates: &[],
+            include_tags: None,
+            try_format_code: true,
+        };
+        generator.generate(&args).unwrap();
+        let recorded_args = fs::read_to_string(out_dir.join("args")).unwrap();
+        let metadata_path = recorded_args.split(' ').nth(4).unwrap();
+        assert_eq!(
+            recorded_args,
+            format!(
+                "generate --library libfoo.so --metadata {metadata_path} \
+                 --out-dir {out_dir} --crate foo\n"
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(out_dir.join("metadata.json")).unwrap(),
+            args.metadata_json
+        );
+        // The metadata file is removed after the generator runs
+        assert!(!Utf8Path::new(metadata_path).exists());
+
+        let args = ExternalGeneratorArgs {
+            crate_name: Some("fail"),
+            ..args
+        };
+        let err = generator.generate(&args).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "mock bindings generator `{}` failed (exit status: 1)",
+                generator.path
+            )
+        );
+    }
+}
@@ -40,6 +40,7 @@ use uniffi_meta::Checksum;
 
 use super::ffi::{FfiArgument, FfiCallbackFunction, FfiField, FfiFunction, FfiStruct, FfiType};
 use super::object::Method;
+use super::Constant;
 use super::{AsType, Type, TypeIterator};
 
 #[derive(Debug, Clone, Checksum)]
@@ -47,6 +48,10 @@ pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) methods: Vec<Method>,
+    // Associated constants of the trait.  These don't have FFI functions, the bindings declare
+    // them with their value, so they don't affect the checksums.
+    #[checksum_ignore]
+    pub(super) constants: Vec<Constant>,
     // We don't include the FFIFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -68,6 +73,10 @@ impl CallbackInterface {
         self.methods.iter().collect()
     }
 
+    pub fn constants(&self) -> Vec<&Constant> {
+        self.constants.iter().collect()
+    }
+
     pub fn ffi_init_callback(&self) -> &FfiFunction {
         &self.ffi_init_callback
     }
@@ -75,6 +84,14 @@ impl CallbackInterface {
     pub(super) fn derive_ffi_funcs(&mut self) {
         self.ffi_init_callback =
             FfiFunction::callback_init(&self.module_path, &self.name, vtable_name(&self.name));
+        for meth in self.methods.iter_mut() {
+            meth.derive_default_ffi_func(FfiType::UInt64);
+        }
+    }
+
+    pub fn iter_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> {
+        iter::once(&self.ffi_init_callback)
+            .chain(self.methods.iter().filter_map(Method::default_ffi_func))
     }
 
     /// FfiCallbacks to define for our methods.
@@ -131,6 +148,7 @@ impl TryFrom<uniffi_meta::CallbackInterfaceMetadata> for CallbackInterface {
             name: meta.name,
             module_path: meta.module_path,
             methods: Default::default(),
+            constants: Default::default(),
             ffi_init_callback: Default::default(),
             docstring: meta.docstring.clone(),
         })
@@ -303,6 +321,23 @@ mod test {
         assert_eq!(callbacks_two.methods()[1].name(), "too");
     }
 
+    #[test]
+    fn test_async_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            callback interface Greeter {
+                [Async]
+                string greet(string who);
+                void reset();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let callbacks = ci.get_callback_interface_definition("Greeter").unwrap();
+        assert!(callbacks.methods()[0].is_async());
+        assert!(!callbacks.methods()[1].is_async());
+        assert!(callbacks.has_async_method());
+    }
+
     #[test]
     fn test_docstring_callback_interface() {
         const UDL: &str = r#"
@@ -0,0 +1,88 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Constant definitions for a `ComponentInterface`.
+//!
+//! This module converts constant definitions from UDL into structures that
+//! can be added to a `ComponentInterface`. A declaration in the UDL like this:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! namespace example {
+//!     const u32 MAX_PAYLOAD_SIZE = 65536;
+//! };
+//! # "##, "crate_name")?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Will result in a [`Constant`] member being added to the resulting [`crate::ComponentInterface`]:
+//!
+//! ```
+//! # use uniffi_bindgen::interface::{AsType, Literal, Radix, Type};
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! # namespace example {
+//! #     const u32 MAX_PAYLOAD_SIZE = 65536;
+//! # };
+//! # "##, "crate_name")?;
+//! let constant = &ci.constant_definitions()[0];
+//! assert_eq!(constant.name(), "MAX_PAYLOAD_SIZE");
+//! assert_eq!(constant.as_type(), Type::UInt32);
+//! assert_eq!(constant.value(), &Literal::UInt(65536, Radix::Decimal, Type::UInt32));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Constants don't have any FFI functions: the bindings declare them with their value.
+
+use super::{AsType, Literal, Type};
+
+/// Represents a constant, which is a number, boolean or string with a fixed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+    pub(super) docstring: Option<String>,
+}
+
+impl Constant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Literal {
+        &self.value
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl AsType for Constant {
+    fn as_type(&self) -> Type {
+        self.type_.clone()
+    }
+}
+
+impl From<uniffi_meta::ConstantMetadata> for Constant {
+    fn from(meta: uniffi_meta::ConstantMetadata) -> Self {
+        Self {
+            name: meta.name,
+            type_: meta.ty,
+            value: meta.value,
+            docstring: meta.docstring,
+        }
+    }
+}
+
+impl From<uniffi_meta::TraitConstantMetadata> for Constant {
+    fn from(meta: uniffi_meta::TraitConstantMetadata) -> Self {
+        Self {
+            name: meta.name,
+            type_: meta.ty,
+            value: meta.value,
+            docstring: meta.docstring,
+        }
+    }
+}
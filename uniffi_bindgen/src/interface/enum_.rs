@@ -163,7 +163,7 @@ use anyhow::Result;
 use uniffi_meta::{Checksum, EnumShape};
 
 use super::record::Field;
-use super::{AsType, Literal, Type, TypeIterator};
+use super::{AsType, Literal, Method, Type, TypeIterator};
 
 /// Represents an enum with named variants, each of which may have named
 /// and typed fields.
@@ -178,6 +178,9 @@ pub struct Enum {
     pub(super) variants: Vec<Variant>,
     pub(super) shape: EnumShape,
     pub(super) non_exhaustive: bool,
+    // Methods exported with `#[uniffi::export]`, these are added after the enum itself.
+    #[checksum_ignore]
+    pub(super) methods: Vec<Method>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
 }
@@ -245,10 +248,19 @@ impl Enum {
         self.non_exhaustive
     }
 
+    /// Do any of this error's variants have a field which is the cause of the error?
+    pub fn has_source_fields(&self) -> bool {
+        self.variants.iter().any(|v| v.source_field().is_some())
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.variants.iter().flat_map(Variant::iter_types))
     }
 
+    pub fn methods(&self) -> Vec<&Method> {
+        self.methods.iter().collect()
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
@@ -269,6 +281,7 @@ impl TryFrom<uniffi_meta::EnumMetadata> for Enum {
                 .collect::<Result<_>>()?,
             shape: meta.shape,
             non_exhaustive: meta.non_exhaustive,
+            methods: vec![],
             docstring: meta.docstring.clone(),
         })
     }
@@ -293,6 +306,8 @@ pub struct Variant {
     pub(super) fields: Vec<Field>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
 }
 
 impl Variant {
@@ -316,10 +331,20 @@ impl Variant {
         self.fields.iter().any(|f| f.name.is_empty())
     }
 
+    /// The field which is the cause of this error variant, if it has one
+    pub fn source_field(&self) -> Option<&Field> {
+        self.fields.iter().find(|f| f.is_source())
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, which is empty if the variant was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -338,6 +363,7 @@ impl TryFrom<uniffi_meta::VariantMetadata> for Variant {
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
         })
     }
 }
@@ -643,6 +669,7 @@ mod test {
             discr: val.map(Literal::new_uint),
             fields: vec![],
             docstring: None,
+            deprecated: None,
         }
     }
 
@@ -666,6 +693,7 @@ mod test {
             variants: vec![],
             shape: EnumShape::Enum,
             non_exhaustive: false,
+            methods: vec![],
             docstring: None,
         };
 
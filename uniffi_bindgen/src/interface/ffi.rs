@@ -113,6 +113,10 @@ impl From<&Type> for FfiType {
             Type::Float64 => FfiType::Float64,
             // Booleans lower into an Int8, to work around a bug in JNA.
             Type::Boolean => FfiType::Int8,
+            // Chars lower into their Unicode scalar value.
+            Type::Char => FfiType::UInt32,
+            // Dates lower into the number of days since 1970-01-01.
+            Type::Date => FfiType::Int32,
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FfiType::RustBuffer(None),
@@ -130,7 +134,12 @@ impl From<&Type> for FfiType {
             | Type::Sequence { .. }
             | Type::Map { .. }
             | Type::Timestamp
-            | Type::Duration => FfiType::RustBuffer(None),
+            | Type::Duration
+            | Type::Json
+            | Type::Path
+            | Type::Url => FfiType::RustBuffer(None),
+            // There's no 128-bit integer in the C ABI, so these are serialized too.
+            Type::UInt128 | Type::Int128 => FfiType::RustBuffer(None),
             Type::External {
                 name,
                 kind: ExternalKind::Interface,
@@ -141,6 +150,11 @@ impl From<&Type> for FfiType {
                 kind: ExternalKind::Trait,
                 ..
             } => FfiType::RustArcPtr(name.clone()),
+            // External callback interfaces are also handles, owned by the crate that defines them.
+            Type::External {
+                kind: ExternalKind::CallbackInterface,
+                ..
+            } => FfiType::UInt64,
             Type::External {
                 name,
                 kind: ExternalKind::DataClass,
@@ -201,7 +215,7 @@ impl FfiDefinition {
 /// from the high-level interface. Each callable thing in the component API will have a
 /// corresponding `FfiFunction` through which it can be invoked, and UniFFI also provides
 /// some built-in `FfiFunction` helpers for use in the foreign language bindings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiFunction {
     pub(super) name: String,
     pub(super) is_async: bool,
@@ -292,7 +306,7 @@ impl Default for FfiFunction {
 /// Represents an argument to an FFI function.
 ///
 /// Each argument has a name and a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiArgument {
     pub(super) name: String,
     pub(super) type_: FfiType,
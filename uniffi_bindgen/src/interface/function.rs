@@ -61,6 +61,9 @@ pub struct Function {
     pub(super) ffi_func: FfiFunction,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    // The deprecation message doesn't change the FFI, so it isn't part of the checksum
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
@@ -140,6 +143,11 @@ impl Function {
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
+
+    /// The deprecation message, which is empty if the function was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 impl From<uniffi_meta::FnParamMetadata> for Argument {
@@ -176,6 +184,7 @@ impl From<uniffi_meta::FnMetadata> for Function {
             return_type,
             ffi_func,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
             throws: meta.throws,
             checksum_fn_name,
             checksum: meta.checksum,
@@ -186,7 +195,7 @@ impl From<uniffi_meta::FnMetadata> for Function {
 /// Represents an argument to a function/constructor/method call.
 ///
 /// Each argument has a name and a type, along with some optional metadata.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Argument {
     pub(super) name: String,
     pub(super) type_: Type,
@@ -262,6 +271,16 @@ pub trait Callable {
     fn takes_self(&self) -> bool {
         false
     }
+    /// For object methods, does the call consume the object?
+    fn consumes_self(&self) -> bool {
+        false
+    }
+    /// For methods on records and enums, the type of the value the method is called on.
+    ///
+    /// These methods take `self` as a lowered value, rather than as an object pointer.
+    fn value_self_type(&self) -> Option<Type> {
+        None
+    }
     fn result_type(&self) -> ResultType {
         ResultType {
             return_type: self.return_type(),
@@ -335,6 +354,14 @@ impl<T: Callable> Callable for &T {
     fn takes_self(&self) -> bool {
         (*self).takes_self()
     }
+
+    fn consumes_self(&self) -> bool {
+        (*self).consumes_self()
+    }
+
+    fn value_self_type(&self) -> Option<Type> {
+        (*self).value_self_type()
+    }
 }
 
 #[cfg(test)]
@@ -389,6 +416,35 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_async_function() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {
+                [Async]
+                void ready();
+                [Async, Throws=TestError]
+                u32 fallible();
+            };
+            [Error]
+            enum TestError { "err" };
+        "#,
+            "crate_name",
+        )?;
+
+        let func1 = ci.get_function_definition("ready").unwrap();
+        assert!(func1.is_async());
+        assert!(func1.throws_type().is_none());
+
+        let func2 = ci.get_function_definition("fallible").unwrap();
+        assert!(func2.is_async());
+        assert_eq!(func2.return_type(), Some(&Type::UInt32));
+        assert!(
+            matches!(func2.throws_type(), Some(Type::Enum { name, .. }) if name == "TestError")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_docstring_function() {
         const UDL: &str = r#"
@@ -406,4 +462,26 @@ mod test {
             "informative docstring"
         );
     }
+
+    #[test]
+    fn test_deprecated_function() -> Result<()> {
+        let udl = |attrs: &str| {
+            format!("namespace test {{ {attrs} u32 fetch_sync(string url); void fetch(); }};")
+        };
+        let ci =
+            ComponentInterface::from_webidl(&udl("[Deprecated=\"use fetch()\"]"), "crate_name")?;
+        let func = ci.get_function_definition("fetch_sync").unwrap();
+        assert_eq!(func.deprecated(), Some("use fetch()"));
+        assert_eq!(
+            ci.get_function_definition("fetch").unwrap().deprecated(),
+            None
+        );
+
+        // Deprecating a function doesn't change its checksum
+        let undeprecated = ComponentInterface::from_webidl(&udl(""), "crate_name")?;
+        let undeprecated_func = undeprecated.get_function_definition("fetch_sync").unwrap();
+        assert_eq!(undeprecated_func.deprecated(), None);
+        assert_eq!(func.checksum(), undeprecated_func.checksum());
+        Ok(())
+    }
 }
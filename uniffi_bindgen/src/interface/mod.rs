@@ -50,6 +50,9 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, ensure, Result};
+use heck::ToShoutySnakeCase;
+
+use crate::diagnostics::{Code, Diagnostic};
 
 pub mod universe;
 pub use uniffi_meta::{AsType, EnumShape, ExternalKind, ObjectImpl, Type};
@@ -57,6 +60,8 @@ use universe::{TypeIterator, TypeUniverse};
 
 mod callbacks;
 pub use callbacks::CallbackInterface;
+mod constant;
+pub use constant::Constant;
 mod enum_;
 pub use enum_::{Enum, Variant};
 mod function;
@@ -73,8 +78,8 @@ pub use ffi::{
 };
 pub use uniffi_meta::Radix;
 use uniffi_meta::{
-    ConstructorMetadata, LiteralMetadata, NamespaceMetadata, ObjectMetadata, TraitMethodMetadata,
-    UniffiTraitMetadata, UNIFFI_CONTRACT_VERSION,
+    ConstructorMetadata, LiteralMetadata, NamespaceMetadata, ObjectMetadata, TraitConstantMetadata,
+    TraitMethodMetadata, TypeDocstringMap, UniffiTraitMetadata, UNIFFI_CONTRACT_VERSION,
 };
 pub type Literal = LiteralMetadata;
 
@@ -92,10 +97,14 @@ pub struct ComponentInterface {
     functions: Vec<Function>,
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
+    constants: Vec<Constant>,
     // Type names which were seen used as an error.
     errors: HashSet<String>,
     // Types which were seen used as callback interface error.
     callback_interface_throws_types: BTreeSet<Type>,
+    // The docstrings of external types, keyed by the crate which defines them and their name.
+    // These are kept out of `Type::External` so they don't affect the identity of the types.
+    external_type_docstrings: TypeDocstringMap,
 }
 
 impl ComponentInterface {
@@ -136,11 +145,13 @@ impl ComponentInterface {
         if self.types.namespace.name.is_empty() {
             self.types.namespace = group.namespace.clone();
         } else if self.types.namespace != group.namespace {
-            bail!(
-                "Namespace mismatch: {:?} - {:?}",
-                group.namespace,
-                self.types.namespace
-            );
+            bail!(Diagnostic::error(
+                Code::UnknownNamespace,
+                format!(
+                    "Namespace mismatch: {:?} - {:?}",
+                    group.namespace, self.types.namespace
+                ),
+            ));
         }
 
         if group.namespace_docstring.is_some() {
@@ -210,6 +221,11 @@ impl ComponentInterface {
         self.functions.iter().find(|f| f.name == name)
     }
 
+    /// Get the definitions for every Constant in the interface.
+    pub fn constant_definitions(&self) -> &[Constant] {
+        &self.constants
+    }
+
     /// Get the definitions for every Object type in the interface.
     pub fn object_definitions(&self) -> &[Object] {
         &self.objects
@@ -279,7 +295,17 @@ impl ComponentInterface {
                     .into_iter()
                     .map(|c| c as &dyn Callable)
                     .chain(o.methods().into_iter().map(|m| m as &dyn Callable))
+                    .chain(o.static_methods().into_iter().map(|m| m as &dyn Callable))
             }))
+            .chain(self.iter_value_methods().map(|m| m as &dyn Callable))
+    }
+
+    /// Iterate over the methods of records and enums.
+    fn iter_value_methods(&self) -> impl Iterator<Item = &Method> {
+        self.records
+            .values()
+            .flat_map(|r| r.methods.iter())
+            .chain(self.enums.values().flat_map(|e| e.methods.iter()))
     }
 
     /// Should we generate read (and lift) functions for errors?
@@ -329,6 +355,29 @@ impl ComponentInterface {
         })
     }
 
+    /// Set the docstrings of the external types
+    ///
+    /// `docstrings` is keyed by crate name and type name, like [uniffi_meta::type_docstrings]
+    /// returns.  Only the docstrings of this interface's external types are kept.
+    pub fn set_external_type_docstrings(&mut self, docstrings: &TypeDocstringMap) {
+        self.external_type_docstrings = self
+            .iter_external_types()
+            .filter_map(|(name, crate_name, ..)| {
+                let key = (crate_name, name.clone());
+                let docstring = docstrings.get(&key)?.clone();
+                Some((key, docstring))
+            })
+            .collect();
+    }
+
+    /// The docstring of an external type in the crate which defines it, if it has one
+    pub fn external_type_docstring(&self, module_path: &str, name: &str) -> Option<&str> {
+        let crate_name = module_path.split("::").next().unwrap();
+        self.external_type_docstrings
+            .get(&(crate_name.to_string(), name.to_string()))
+            .map(String::as_str)
+    }
+
     /// Get details about all `Type::Custom` types
     pub fn iter_custom_types(&self) -> impl Iterator<Item = (&String, &Type)> {
         self.types.iter_known_types().filter_map(|t| match t {
@@ -360,6 +409,9 @@ impl ComponentInterface {
     ///
     /// This is important to know in language bindings that cannot integrate object types
     /// tightly with the host GC, and hence need to perform manual destruction of objects.
+    ///
+    /// Each user-defined type is only visited once, so recursive types are fine: a cycle of
+    /// records/enums that never reaches an object is `false`.
     pub fn item_contains_object_references(&self, item: &Type) -> bool {
         // this is surely broken for external records with object refs?
         self.iter_types_in_item(item).any(|t| {
@@ -714,9 +766,10 @@ impl ComponentInterface {
             .chain(
                 self.callback_interfaces
                     .iter()
-                    .map(|cb| cb.ffi_init_callback()),
+                    .flat_map(|cb| cb.iter_ffi_function_definitions()),
             )
             .chain(self.functions.iter().map(|f| &f.ffi_func))
+            .chain(self.iter_value_methods().map(|m| &m.ffi_func))
     }
 
     /// List all FFI functions definitions for RustBuffer functionality.
@@ -775,8 +828,12 @@ impl ComponentInterface {
         let method_checksums = self.objects.iter().flat_map(|o| {
             o.methods()
                 .into_iter()
+                .chain(o.static_methods())
                 .map(|m| (m.checksum_fn_name(), m.checksum()))
         });
+        let value_method_checksums = self
+            .iter_value_methods()
+            .map(|m| (m.checksum_fn_name(), m.checksum()));
         let constructor_checksums = self.objects.iter().flat_map(|o| {
             o.constructors()
                 .into_iter()
@@ -794,6 +851,7 @@ impl ComponentInterface {
         });
         func_checksums
             .chain(method_checksums)
+            .chain(value_method_checksums)
             .chain(constructor_checksums)
             .chain(callback_method_checksums)
             .map(|(fn_name, checksum)| (fn_name.to_string(), checksum))
@@ -825,12 +883,15 @@ impl ComponentInterface {
             Entry::Occupied(o) => {
                 let existing_def = o.get();
                 if defn != *existing_def {
-                    bail!(
-                        "Mismatching definition for enum `{}`!\n\
-                        existing definition: {existing_def:#?},\n\
-                        new definition: {defn:#?}",
-                        defn.name(),
-                    );
+                    bail!(Diagnostic::error(
+                        Code::DuplicateItem,
+                        format!(
+                            "Mismatching definition for enum `{}`!\n\
+                            existing definition: {existing_def:#?},\n\
+                            new definition: {defn:#?}",
+                            defn.name(),
+                        ),
+                    ));
                 }
             }
         }
@@ -848,12 +909,15 @@ impl ComponentInterface {
             Entry::Occupied(o) => {
                 let existing_def = o.get();
                 if defn != *existing_def {
-                    bail!(
-                        "Mismatching definition for record `{}`!\n\
-                         existing definition: {existing_def:#?},\n\
-                         new definition: {defn:#?}",
-                        defn.name(),
-                    );
+                    bail!(Diagnostic::error(
+                        Code::DuplicateItem,
+                        format!(
+                            "Mismatching definition for record `{}`!\n\
+                             existing definition: {existing_def:#?},\n\
+                             new definition: {defn:#?}",
+                            defn.name(),
+                        ),
+                    ));
                 }
             }
         }
@@ -866,10 +930,16 @@ impl ComponentInterface {
         // Since functions are not a first-class type, we have to check for duplicates here
         // rather than relying on the type-finding pass to catch them.
         if self.functions.iter().any(|f| f.name == defn.name) {
-            bail!("duplicate function definition: \"{}\"", defn.name);
+            bail!(Diagnostic::error(
+                Code::DuplicateItem,
+                format!("duplicate function definition: \"{}\"", defn.name),
+            ));
         }
         if self.types.get_type_definition(defn.name()).is_some() {
-            bail!("Conflicting type definition for \"{}\"", defn.name());
+            bail!(Diagnostic::error(
+                Code::DuplicateItem,
+                format!("Conflicting type definition for \"{}\"", defn.name()),
+            ));
         }
         self.types.add_known_types(defn.iter_types())?;
         defn.throws_name()
@@ -879,6 +949,18 @@ impl ComponentInterface {
         Ok(())
     }
 
+    pub(super) fn add_constant_definition(&mut self, defn: Constant) -> Result<()> {
+        if self.constants.iter().any(|c| c.name == defn.name) {
+            bail!(Diagnostic::error(
+                Code::DuplicateItem,
+                format!("duplicate constant definition: \"{}\"", defn.name),
+            ));
+        }
+        self.types.add_known_type(&defn.as_type())?;
+        self.constants.push(defn);
+        Ok(())
+    }
+
     pub(super) fn add_constructor_meta(&mut self, meta: ConstructorMetadata) -> Result<()> {
         let object = get_object(&mut self.objects, &meta.self_name)
             .ok_or_else(|| anyhow!("add_constructor_meta: object {} not found", &meta.self_name))?;
@@ -894,15 +976,48 @@ impl ComponentInterface {
 
     pub(super) fn add_method_meta(&mut self, meta: impl Into<Method>) -> Result<()> {
         let mut method: Method = meta.into();
-        let object = get_object(&mut self.objects, &method.object_name)
-            .ok_or_else(|| anyhow!("add_method_meta: object {} not found", &method.object_name))?;
-
         self.types.add_known_types(method.iter_types())?;
         method
             .throws_name()
             .map(|n| self.errors.insert(n.to_string()));
+
+        // Records and enums can have methods too, which take `self` by value.
+        if method.is_static
+            && (self.records.contains_key(&method.object_name)
+                || self.enums.contains_key(&method.object_name))
+        {
+            bail!(
+                "static method `{}` can't be exported for `{}`, only objects can have static methods",
+                method.name,
+                method.object_name
+            );
+        }
+        if let Some(record) = self.records.get_mut(&method.object_name) {
+            method.value_self_type = Some(record.as_type());
+            record.methods.push(method);
+            return Ok(());
+        }
+        if let Some(enum_) = self.enums.get_mut(&method.object_name) {
+            if matches!(enum_.shape, EnumShape::Error { .. }) {
+                bail!(
+                    "method `{}` can't be exported for `{}`, errors can't have methods",
+                    method.name,
+                    enum_.name
+                );
+            }
+            method.value_self_type = Some(enum_.as_type());
+            enum_.methods.push(method);
+            return Ok(());
+        }
+
+        let object = get_object(&mut self.objects, &method.object_name)
+            .ok_or_else(|| anyhow!("add_method_meta: object {} not found", &method.object_name))?;
         method.object_impl = object.imp;
-        object.methods.push(method);
+        if method.is_static {
+            object.static_methods.push(method);
+        } else {
+            object.methods.push(method);
+        }
         Ok(())
     }
 
@@ -963,6 +1078,23 @@ impl ComponentInterface {
         Ok(())
     }
 
+    pub(super) fn add_trait_constant_meta(&mut self, meta: TraitConstantMetadata) -> Result<()> {
+        let constants = if let Some(cbi) =
+            get_callback_interface(&mut self.callback_interfaces, &meta.trait_name)
+        {
+            &mut cbi.constants
+        } else if let Some(obj) = get_object(&mut self.objects, &meta.trait_name) {
+            &mut obj.constants
+        } else {
+            bail!(
+                "add_trait_constant_meta: trait {} not found",
+                &meta.trait_name
+            );
+        };
+        constants.push(meta.into());
+        Ok(())
+    }
+
     /// Perform global consistency checks on the declared interface.
     ///
     /// This method checks for consistency problems in the declared interface
@@ -970,19 +1102,226 @@ impl ComponentInterface {
     /// the entire interface.
     pub fn check_consistency(&self) -> Result<()> {
         if self.namespace().is_empty() {
-            bail!("missing namespace definition");
+            bail!(Diagnostic::error(
+                Code::UnknownNamespace,
+                "missing namespace definition"
+            ));
         }
 
         // Because functions aren't first class types, we need to check here that
         // a function name hasn't already been used as a type name.
         for f in self.functions.iter() {
             if self.types.get_type_definition(f.name()).is_some() {
-                bail!("Conflicting type definition for \"{}\"", f.name());
+                bail!(Diagnostic::error(
+                    Code::DuplicateItem,
+                    format!("Conflicting type definition for \"{}\"", f.name()),
+                ));
+            }
+        }
+
+        for (name, default) in self.iter_default_values() {
+            self.check_enum_default(name, default)?;
+        }
+
+        // An object's members can be exported from several impl blocks, which mustn't export the
+        // same name twice.
+        for obj in self.object_definitions() {
+            let members = obj
+                .constructors()
+                .into_iter()
+                .map(|c| ("constructor", c.name()))
+                .chain(obj.methods().into_iter().map(|m| ("method", m.name())))
+                .chain(
+                    obj.static_methods()
+                        .into_iter()
+                        .map(|m| ("static method", m.name())),
+                );
+            let mut seen = HashSet::new();
+            for (kind, name) in members {
+                if !seen.insert((kind, name)) {
+                    bail!(Diagnostic::error(
+                        Code::DuplicateItem,
+                        format!("{kind} `{}::{name}` is exported more than once", obj.name()),
+                    ));
+                }
+            }
+        }
+
+        // Static methods are class members alongside the constructors and methods, so their names
+        // must not clash with either.
+        for obj in self.object_definitions() {
+            for meth in obj.static_methods() {
+                let clashes = obj.constructors().iter().any(|c| c.name() == meth.name())
+                    || obj.methods().iter().any(|m| m.name() == meth.name());
+                if clashes {
+                    bail!(Diagnostic::error(
+                        Code::DuplicateItem,
+                        format!(
+                            "static method `{}::{}` has the same name as a constructor or method",
+                            obj.name(),
+                            meth.name()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // The bindings' equality must agree with the ordering, and Swift's `Comparable` requires
+        // `Equatable`, so `Ord` is only supported along with `Eq`.
+        for obj in self.object_definitions() {
+            let has_trait = |f: fn(&UniffiTrait) -> bool| obj.uniffi_traits().into_iter().any(f);
+            if has_trait(|t| matches!(t, UniffiTrait::Ord { .. }))
+                && !has_trait(|t| matches!(t, UniffiTrait::Eq { .. }))
+            {
+                bail!(
+                    "`{}` exports the `Ord` trait, which requires exporting the `Eq` trait too",
+                    obj.name()
+                );
+            }
+        }
+
+        // Bindings use source fields as the cause of the exceptions they throw, so they must be
+        // errors themselves, or strings.  Errors from other crates can't be checked here.
+        for e in self.enum_definitions() {
+            for v in e.variants() {
+                let Some(field) = v.source_field() else {
+                    continue;
+                };
+                let valid = match field.as_type() {
+                    Type::String => true,
+                    Type::Enum { name, .. } => {
+                        self.is_name_used_as_error(&name)
+                            || self.get_enum_definition(&name).is_none()
+                    }
+                    _ => false,
+                };
+                if !valid {
+                    bail!(
+                        "the source field of `{}::{}` must be an error or a string",
+                        e.name(),
+                        v.name()
+                    );
+                }
+            }
+        }
+
+        for t in self.iter_types() {
+            if let Type::Map { key_type, .. } = t {
+                if !self.is_valid_map_key(key_type) {
+                    bail!(
+                        "`{}` can't be used as a map key: keys must be integers, booleans, strings \
+                         or enums without fields",
+                        uniffi_meta::udl_type_name(key_type)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Map keys must be hashable in every language, and compared by value.  Enums from other crates
+    // can't be checked here.
+    fn is_valid_map_key(&self, key_type: &Type) -> bool {
+        match key_type {
+            Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Boolean
+            | Type::String => true,
+            Type::Enum { name, .. } => match self.get_enum_definition(name) {
+                Some(e) => {
+                    !self.is_name_used_as_error(name)
+                        && e.variants().iter().all(|v| !v.has_fields())
+                }
+                None => true,
+            },
+            Type::Custom { builtin, .. } => self.is_valid_map_key(builtin),
+            _ => false,
+        }
+    }
+
+    /// Check that the byte string defaults are no longer than `max_len` bytes.
+    ///
+    /// The limit comes from the `max_default_bytes` config key, see
+    /// [crate::config::max_default_bytes].
+    pub fn check_default_bytes(&self, max_len: usize) -> Result<()> {
+        for (name, default) in self.iter_default_values() {
+            let mut default = default;
+            while let Literal::Some { inner } = default {
+                default = inner;
+            }
+            if let Literal::Bytes(bytes) = default {
+                if bytes.len() > max_len {
+                    bail!(
+                        "the default value of `{name}` is {} bytes long, but defaults can't be longer than {max_len} bytes; set `max_default_bytes` in uniffi.toml to allow it",
+                        bytes.len()
+                    );
+                }
             }
         }
         Ok(())
     }
 
+    // The names and default values of all the arguments and fields which have one
+    fn iter_default_values(&self) -> impl Iterator<Item = (&str, &Literal)> {
+        let arg_defaults = self
+            .iter_callables()
+            .flat_map(|c| c.arguments())
+            .map(|a| (a.name(), a.default_value()));
+        let field_defaults = self
+            .record_definitions()
+            .flat_map(|r| r.fields())
+            .chain(
+                self.enum_definitions()
+                    .flat_map(|e| e.variants())
+                    .flat_map(|v| v.fields()),
+            )
+            .map(|f| (f.name(), f.default_value()));
+        arg_defaults
+            .chain(field_defaults)
+            .filter_map(|(name, default)| Some((name, default?)))
+    }
+
+    // Check that an enum variant used as the default value of `name` exists and has no fields,
+    // since the default can't give values for them.
+    fn check_enum_default(&self, name: &str, default: &Literal) -> Result<()> {
+        match default {
+            Literal::Some { inner } => self.check_enum_default(name, inner),
+            Literal::Enum(
+                variant,
+                Type::Enum {
+                    name: enum_name, ..
+                },
+            ) => {
+                // Enums from other crates can't be checked, since they're defined there.
+                let Some(enum_) = self.get_enum_definition(enum_name) else {
+                    return Ok(());
+                };
+                // The bindings convert variant names to their own style, so `"DEUX"` is `Deux`
+                let variant_key = variant.to_shouty_snake_case();
+                match enum_
+                    .variants()
+                    .iter()
+                    .find(|v| v.name().to_shouty_snake_case() == variant_key)
+                {
+                    None => bail!(
+                        "the default value of `{name}` is `{enum_name}::{variant}`, but `{enum_name}` has no variant named `{variant}`"
+                    ),
+                    Some(v) if v.has_fields() => bail!(
+                        "the default value of `{name}` is `{enum_name}::{variant}`, which can't be a default value because it has fields"
+                    ),
+                    Some(_) => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Automatically derive the low-level FFI functions from the high-level types in the interface.
     ///
     /// This should only be called after the high-level types have been completed defined, otherwise
@@ -994,6 +1333,14 @@ impl ComponentInterface {
         for obj in self.objects.iter_mut() {
             obj.derive_ffi_funcs()?;
         }
+        for meth in self
+            .records
+            .values_mut()
+            .flat_map(|r| r.methods.iter_mut())
+            .chain(self.enums.values_mut().flat_map(|e| e.methods.iter_mut()))
+        {
+            meth.derive_ffi_func()?;
+        }
         for callback in self.callback_interfaces.iter_mut() {
             callback.derive_ffi_funcs();
         }
@@ -1175,16 +1522,19 @@ existing definition: Enum {
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
         },
         Variant {
             name: \"two\",
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
         },
     ],
     shape: Enum,
     non_exhaustive: false,
+    methods: [],
     docstring: None,
 },
 new definition: Enum {
@@ -1197,18 +1547,21 @@ new definition: Enum {
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
         },
         Variant {
             name: \"four\",
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
         },
     ],
     shape: Error {
         flat: true,
     },
     non_exhaustive: false,
+    methods: [],
     docstring: None,
 }",
         );
@@ -1225,6 +1578,298 @@ new definition: Enum {
         assert!(format!("{err:#}").contains("Conflicting type definition for \"Testing\""));
     }
 
+    #[test]
+    fn test_enum_default_values() {
+        const UDL: &str = r#"
+            namespace test{
+                void log(string message, optional LogLevel level = "LogLevel.Info");
+            };
+            enum LogLevel { "Debug", "Info" };
+            dictionary Options {
+                LogLevel level = "Debug";
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let func = ci.get_function_definition("log").unwrap();
+        assert!(matches!(
+            func.arguments()[1].default_value(),
+            Some(Literal::Enum(variant, _)) if variant == "Info"
+        ));
+
+        let err = ComponentInterface::from_webidl(
+            &UDL.replace("\"LogLevel.Info\"", "\"Verbose\""),
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "the default value of `level` is `LogLevel::Verbose`, but `LogLevel` has no variant named `Verbose`"
+        );
+
+        const UDL_WITH_FIELDS: &str = r#"
+            namespace test{};
+            [Enum]
+            interface Shape {
+                Circle(f64 radius);
+                Point();
+            };
+            dictionary Options {
+                Shape shape = "Circle";
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_WITH_FIELDS, "crate_name").unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "the default value of `shape` is `Shape::Circle`, which can't be a default value because it has fields"
+        );
+        let udl = UDL_WITH_FIELDS.replace("\"Circle\"", "\"Point\"");
+        assert!(ComponentInterface::from_webidl(&udl, "crate_name").is_ok());
+    }
+
+    #[test]
+    fn test_check_default_bytes() {
+        const UDL: &str = r#"
+            namespace test{
+                void send(optional bytes? prefix = h"CAFE");
+            };
+            dictionary Frame {
+                bytes magic = h"DEADBEEF";
+                bytes payload = h"";
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let frame = ci.get_record_definition("Frame").unwrap();
+        assert_eq!(
+            frame.fields()[0].default_value(),
+            Some(&Literal::Bytes(vec![0xde, 0xad, 0xbe, 0xef]))
+        );
+        // Empty defaults are allowed, even when no bytes are.
+        assert_eq!(
+            frame.fields()[1].default_value(),
+            Some(&Literal::Bytes(vec![]))
+        );
+        ci.check_default_bytes(crate::config::DEFAULT_MAX_DEFAULT_BYTES)
+            .unwrap();
+        ci.check_default_bytes(4).unwrap();
+        assert_eq!(
+            ci.check_default_bytes(3).unwrap_err().to_string(),
+            "the default value of `magic` is 4 bytes long, but defaults can't be longer than 3 bytes; set `max_default_bytes` in uniffi.toml to allow it"
+        );
+        // Optional defaults are checked too.
+        assert_eq!(
+            ci.check_default_bytes(1).unwrap_err().to_string(),
+            "the default value of `prefix` is 2 bytes long, but defaults can't be longer than 1 bytes; set `max_default_bytes` in uniffi.toml to allow it"
+        );
+        ci.check_default_bytes(0).unwrap_err();
+    }
+
+    #[test]
+    fn test_map_key_types() {
+        const UDL: &str = r#"
+            namespace test{
+                record<KEY, string> get_map();
+            };
+            enum Level { "Debug", "Info" };
+            [Enum]
+            interface Shape {
+                Circle(f64 radius);
+                Point();
+            };
+            dictionary Point {
+                i32 x;
+                i32 y;
+            };
+        "#;
+        for key in ["string", "u32", "i64", "boolean", "Level"] {
+            let udl = UDL.replace("KEY", key);
+            assert!(
+                ComponentInterface::from_webidl(&udl, "crate_name").is_ok(),
+                "{key}"
+            );
+        }
+        for key in ["f32", "bytes", "Shape", "Point"] {
+            let err = ComponentInterface::from_webidl(&UDL.replace("KEY", key), "crate_name")
+                .unwrap_err();
+            assert!(
+                err.root_cause()
+                    .to_string()
+                    .contains("can't be used as a map key"),
+                "{key}: {err:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_methods_on_records_and_enums() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Point { i32 x; i32 y; };
+            enum Direction { "North", "South" };
+            [Error]
+            enum Oops { "Bad" };
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let method = |self_name: &str| uniffi_meta::MethodMetadata {
+            module_path: "crate_name".to_string(),
+            self_name: self_name.to_string(),
+            name: "flip".to_string(),
+            is_async: false,
+            consumes: false,
+            is_static: false,
+            is_getter: false,
+            inputs: vec![],
+            return_type: None,
+            throws: None,
+            takes_self_by_arc: false,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        };
+        ci.add_method_meta(method("Point")).unwrap();
+        ci.add_method_meta(method("Direction")).unwrap();
+        ci.derive_ffi_funcs().unwrap();
+
+        let meth = ci.get_record_definition("Point").unwrap().methods()[0];
+        assert_eq!(
+            meth.value_self_type(),
+            Some(Type::Record {
+                name: "Point".to_string(),
+                module_path: "crate_name".to_string(),
+            })
+        );
+        // The value is passed as a `RustBuffer`, rather than as an object pointer.
+        assert_eq!(
+            meth.ffi_func().arguments()[0].type_(),
+            FfiType::RustBuffer(None)
+        );
+        assert_eq!(
+            ci.get_enum_definition("Direction").unwrap().methods().len(),
+            1
+        );
+        assert_eq!(ci.iter_callables().count(), 2);
+
+        let err = ci.add_method_meta(method("Oops")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "method `flip` can't be exported for `Oops`, errors can't have methods"
+        );
+    }
+
+    #[test]
+    fn test_static_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Point { i32 x; i32 y; };
+            interface Counter {
+                constructor();
+                [Name=starting_at]
+                constructor(u32 start);
+                u32 get();
+            };
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let static_method = |self_name: &str, name: &str| uniffi_meta::MethodMetadata {
+            module_path: "crate_name".to_string(),
+            self_name: self_name.to_string(),
+            name: name.to_string(),
+            is_async: false,
+            consumes: false,
+            is_static: true,
+            is_getter: false,
+            inputs: vec![uniffi_meta::FnParamMetadata::simple("step", Type::UInt32)],
+            return_type: Some(Type::UInt32),
+            throws: None,
+            takes_self_by_arc: false,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        };
+        ci.add_method_meta(static_method("Counter", "max_step"))
+            .unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        ci.check_consistency().unwrap();
+
+        let obj = ci.get_object_definition("Counter").unwrap();
+        assert_eq!(obj.methods().len(), 1);
+        let meth = obj.static_methods()[0];
+        assert!(!meth.takes_self());
+        // There's no self argument, only the method's own.
+        assert_eq!(meth.ffi_func().arguments().len(), 1);
+        assert_eq!(meth.ffi_func().arguments()[0].type_(), FfiType::UInt32);
+        assert!(ci
+            .iter_checksums()
+            .any(|(name, _)| name == meth.checksum_fn_name()));
+
+        let err = ci
+            .add_method_meta(static_method("Point", "origin"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "static method `origin` can't be exported for `Point`, only objects can have static methods"
+        );
+
+        for name in ["starting_at", "get"] {
+            let mut ci = ci.clone();
+            ci.add_method_meta(static_method("Counter", name)).unwrap();
+            let err = ci.check_consistency().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "static method `Counter::{name}` has the same name as a constructor or method"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_members_from_several_impl_blocks() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Inventory {
+                constructor();
+                u32 count(string item);
+            };
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let method = |module_path: &str, name: &str| uniffi_meta::MethodMetadata {
+            module_path: module_path.to_string(),
+            self_name: "Inventory".to_string(),
+            name: name.to_string(),
+            is_async: false,
+            consumes: false,
+            is_static: false,
+            is_getter: false,
+            inputs: vec![uniffi_meta::FnParamMetadata::simple("item", Type::String)],
+            return_type: None,
+            throws: None,
+            takes_self_by_arc: false,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        };
+        ci.add_method_meta(method("crate_name::writes", "add"))
+            .unwrap();
+        ci.add_method_meta(method("crate_name::writes", "remove"))
+            .unwrap();
+        ci.check_consistency().unwrap();
+        let obj = ci.get_object_definition("Inventory").unwrap();
+        assert_eq!(
+            obj.methods().iter().map(|m| m.name()).collect::<Vec<_>>(),
+            ["count", "add", "remove"]
+        );
+
+        ci.add_method_meta(method("crate_name::reads", "count"))
+            .unwrap();
+        let err = ci.check_consistency().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "method `Inventory::count` is exported more than once"
+        );
+    }
+
     #[test]
     fn test_contains_optional_types() {
         let mut ci = ComponentInterface {
@@ -1324,6 +1969,81 @@ new definition: Enum {
         }));
     }
 
+    #[test]
+    fn test_recursive_records_without_objects() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Node {
+                sequence<Node> children;
+            };
+            dictionary A {
+                B? b;
+            };
+            dictionary B {
+                sequence<A> a;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        for name in ["Node", "A", "B"] {
+            assert!(!ci.item_contains_object_references(&Type::Record {
+                name: name.into(),
+                module_path: "".into(),
+            }));
+        }
+    }
+
+    #[test]
+    fn test_recursive_records_with_objects() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface TestObj {};
+            dictionary A {
+                B? b;
+            };
+            dictionary B {
+                sequence<A> a;
+                TestObj obj;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        for name in ["A", "B"] {
+            assert!(ci.item_contains_object_references(&Type::Record {
+                name: name.into(),
+                module_path: "".into(),
+            }));
+        }
+    }
+
+    #[test]
+    fn test_custom_types_when_walking_types() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface TestObj {};
+            dictionary Inner {
+                TestObj obj;
+            };
+            [Custom]
+            typedef string Guid;
+            [Custom]
+            typedef Inner Wrapper;
+            dictionary HasGuid {
+                Guid guid;
+            };
+            dictionary HasWrapper {
+                Wrapper wrapper;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(!ci.item_contains_object_references(&Type::Record {
+            name: "HasGuid".into(),
+            module_path: "".into(),
+        }));
+        assert!(ci.item_contains_object_references(&Type::Record {
+            name: "HasWrapper".into(),
+            module_path: "".into(),
+        }));
+    }
+
     #[test]
     fn test_docstring_namespace() {
         const UDL: &str = r#"
@@ -1344,4 +2064,39 @@ new definition: Enum {
         let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
         assert_eq!(ci.namespace_docstring().unwrap(), "informative\ndocstring");
     }
+
+    #[test]
+    fn test_external_type_docstrings() {
+        const UDL: &str = r#"
+            namespace test{
+                Documented get_documented();
+                Undocumented get_undocumented();
+            };
+            [External="other_crate"]
+            typedef extern Documented;
+            [External="other_crate"]
+            typedef extern Undocumented;
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let docstrings = TypeDocstringMap::from([
+            (
+                ("other_crate".to_string(), "Documented".to_string()),
+                "A documented type".to_string(),
+            ),
+            (
+                ("other_crate".to_string(), "Unused".to_string()),
+                "A type which isn't used".to_string(),
+            ),
+        ]);
+        ci.set_external_type_docstrings(&docstrings);
+        assert_eq!(
+            ci.external_type_docstring("other_crate::submodule", "Documented"),
+            Some("A documented type")
+        );
+        assert_eq!(
+            ci.external_type_docstring("other_crate", "Undocumented"),
+            None
+        );
+        assert_eq!(ci.external_type_docstring("other_crate", "Unused"), None);
+    }
 }
@@ -57,13 +57,15 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::iter;
+
 use anyhow::Result;
 use uniffi_meta::Checksum;
 
 use super::callbacks;
 use super::ffi::{FfiArgument, FfiCallbackFunction, FfiFunction, FfiStruct, FfiType};
 use super::function::{Argument, Callable};
-use super::{AsType, ObjectImpl, Type, TypeIterator};
+use super::{AsType, Constant, ObjectImpl, Type, TypeIterator};
 
 /// An "object" is an opaque type that is passed around by reference, can
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
@@ -87,10 +89,16 @@ pub struct Object {
     pub(super) module_path: String,
     pub(super) constructors: Vec<Constructor>,
     pub(super) methods: Vec<Method>,
+    // Methods which don't take `self`, these are static members of the foreign class.
+    pub(super) static_methods: Vec<Method>,
     // The "trait" methods - they have a (presumably "well known") name, and
     // a regular method (albeit with a generated name)
     // XXX - this should really be a HashSet, but not enough transient types support hash to make it worthwhile now.
     pub(super) uniffi_traits: Vec<UniffiTrait>,
+    // Associated constants of trait interfaces.  These don't have FFI functions, the bindings
+    // declare them with their value, so they don't affect the checksums.
+    #[checksum_ignore]
+    pub(super) constants: Vec<Constant>,
     // We don't include the FfiFuncs in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -143,6 +151,14 @@ impl Object {
         self.methods.iter().any(Method::is_async)
     }
 
+    /// Does the object have a method which consumes it?
+    ///
+    /// The bindings for these objects need to guard their handle, since a consuming method can
+    /// invalidate it while other calls are in progress.
+    pub fn has_consuming_method(&self) -> bool {
+        self.methods.iter().any(Method::consumes)
+    }
+
     pub fn constructors(&self) -> Vec<&Constructor> {
         self.constructors.iter().collect()
     }
@@ -160,10 +176,23 @@ impl Object {
             .collect()
     }
 
+    /// The constructors which bindings without async constructors generate as static factory
+    /// methods: the alternate constructors, along with the primary constructor if it's async.
+    pub fn factory_constructors(&self) -> Vec<&Constructor> {
+        self.constructors
+            .iter()
+            .filter(|cons| !cons.is_primary_constructor() || cons.is_async())
+            .collect()
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
 
+    pub fn static_methods(&self) -> Vec<&Method> {
+        self.static_methods.iter().collect()
+    }
+
     pub fn get_method(&self, name: &str) -> Method {
         let matches: Vec<_> = self.methods.iter().filter(|m| m.name() == name).collect();
         match matches.len() {
@@ -172,6 +201,10 @@ impl Object {
         }
     }
 
+    pub fn constants(&self) -> Vec<&Constant> {
+        self.constants.iter().collect()
+    }
+
     pub fn uniffi_traits(&self) -> Vec<&UniffiTrait> {
         self.uniffi_traits.iter().collect()
     }
@@ -200,13 +233,16 @@ impl Object {
             .chain(&self.ffi_init_callback)
             .chain(self.constructors.iter().map(|f| &f.ffi_func))
             .chain(self.methods.iter().map(|f| &f.ffi_func))
+            .chain(self.methods.iter().filter_map(Method::default_ffi_func))
+            .chain(self.static_methods.iter().map(|f| &f.ffi_func))
             .chain(
                 self.uniffi_traits
                     .iter()
                     .flat_map(|ut| match ut {
                         UniffiTrait::Display { fmt: m }
                         | UniffiTrait::Debug { fmt: m }
-                        | UniffiTrait::Hash { hash: m } => vec![m],
+                        | UniffiTrait::Hash { hash: m }
+                        | UniffiTrait::Ord { cmp: m } => vec![m],
                         UniffiTrait::Eq { eq, ne } => vec![eq, ne],
                     })
                     .map(|m| &m.ffi_func),
@@ -240,6 +276,15 @@ impl Object {
         }
         for meth in self.methods.iter_mut() {
             meth.derive_ffi_func()?;
+            // Only traits which can be implemented by foreign code have default FFI functions
+            if self.imp.has_callback_interface() {
+                meth.derive_default_ffi_func(FfiType::RustArcPtr(self.name.to_string()));
+            } else {
+                meth.default_ffi_func = None;
+            }
+        }
+        for meth in self.static_methods.iter_mut() {
+            meth.derive_ffi_func()?;
         }
         for ut in self.uniffi_traits.iter_mut() {
             ut.derive_ffi_func()?;
@@ -288,6 +333,7 @@ impl Object {
             self.methods
                 .iter()
                 .map(Method::iter_types)
+                .chain(self.static_methods.iter().map(Method::iter_types))
                 .chain(self.uniffi_traits.iter().map(UniffiTrait::iter_types))
                 .chain(self.constructors.iter().map(Constructor::iter_types))
                 .flatten(),
@@ -315,7 +361,9 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
             imp: meta.imp,
             constructors: Default::default(),
             methods: Default::default(),
+            static_methods: Default::default(),
             uniffi_traits: Default::default(),
+            constants: Default::default(),
             ffi_func_clone: FfiFunction {
                 name: ffi_clone_name,
                 ..Default::default()
@@ -346,6 +394,7 @@ impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
             uniffi_meta::UniffiTraitMetadata::Hash { hash } => {
                 UniffiTrait::Hash { hash: hash.into() }
             }
+            uniffi_meta::UniffiTraitMetadata::Ord { cmp } => UniffiTrait::Ord { cmp: cmp.into() },
         }
     }
 }
@@ -371,6 +420,9 @@ pub struct Constructor {
     pub(super) ffi_func: FfiFunction,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    // The deprecation message doesn't change the FFI, so it isn't part of the checksum
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
@@ -423,6 +475,11 @@ impl Constructor {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, which is empty if the constructor was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn is_primary_constructor(&self) -> bool {
         self.name == "new"
     }
@@ -459,6 +516,7 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
             arguments,
             ffi_func,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
             throws: meta.throws.map(Into::into),
             checksum_fn_name,
             checksum: meta.checksum,
@@ -470,13 +528,27 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
 //
 // The FFI will represent this as a function whose first/self argument is a
 // `FfiType::RustArcPtr` to the instance.
-#[derive(Debug, Clone, Checksum)]
+//
+// Records and enums can have methods too, in which case the first argument is
+// the lowered value itself.
+//
+// Static methods don't take `self` at all, so their FFI function is like a top-level function.
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Method {
     pub(super) name: String,
     pub(super) object_name: String,
     pub(super) object_module_path: String,
     pub(super) is_async: bool,
+    // Does the method consume the object?  Only object methods taking `self: Arc<Self>` can.
+    pub(super) consumes: bool,
+    // Is this a static method, which doesn't take `self`?  Only object methods can be.
+    pub(super) is_static: bool,
+    // Is this the getter of a public field?  The bindings make these read-only properties.
+    pub(super) is_getter: bool,
     pub(super) object_impl: ObjectImpl,
+    // The record or enum type for methods on those, `None` for object methods.
+    #[checksum_ignore]
+    pub(super) value_self_type: Option<Type>,
     pub(super) arguments: Vec<Argument>,
     pub(super) return_type: Option<Type>,
     // We don't include the FFIFunc in the hash calculation, because:
@@ -487,8 +559,15 @@ pub struct Method {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
+    // For trait methods with a default implementation, the FFI function which foreign
+    // implementations call to run it.  This is ignored for the same reasons as `ffi_func`.
+    #[checksum_ignore]
+    pub(super) default_ffi_func: Option<FfiFunction>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    // The deprecation message doesn't change the FFI, so it isn't part of the checksum
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) takes_self_by_arc: bool,
     pub(super) checksum_fn_name: String,
@@ -517,22 +596,34 @@ impl Method {
     // Methods have a special implicit first argument for the object instance,
     // hence `arguments` and `full_arguments` are different.
     pub fn full_arguments(&self) -> Vec<Argument> {
-        vec![Argument {
-            name: "ptr".to_string(),
-            // TODO: ideally we'd get this via `ci.resolve_type_expression` so that it
-            // is contained in the proper `TypeUniverse`, but this works for now.
-            type_: Type::Object {
-                name: self.object_name.clone(),
-                module_path: self.object_module_path.clone(),
-                imp: self.object_impl,
+        if self.is_static {
+            return self.arguments.clone();
+        }
+        let self_arg = match &self.value_self_type {
+            Some(type_) => Argument {
+                name: "uniffi_self".to_string(),
+                type_: type_.clone(),
+                by_ref: false,
+                optional: false,
+                default: None,
+            },
+            None => Argument {
+                name: "ptr".to_string(),
+                // TODO: ideally we'd get this via `ci.resolve_type_expression` so that it
+                // is contained in the proper `TypeUniverse`, but this works for now.
+                type_: Type::Object {
+                    name: self.object_name.clone(),
+                    module_path: self.object_module_path.clone(),
+                    imp: self.object_impl,
+                },
+                by_ref: !self.takes_self_by_arc,
+                optional: false,
+                default: None,
             },
-            by_ref: !self.takes_self_by_arc,
-            optional: false,
-            default: None,
-        }]
-        .into_iter()
-        .chain(self.arguments.iter().cloned())
-        .collect()
+        };
+        iter::once(self_arg)
+            .chain(self.arguments.iter().cloned())
+            .collect()
     }
 
     pub fn return_type(&self) -> Option<&Type> {
@@ -543,6 +634,20 @@ impl Method {
         &self.ffi_func
     }
 
+    /// Does the trait method have a default implementation, which foreign implementations of the
+    /// trait can use instead of implementing it?
+    pub fn has_default(&self) -> bool {
+        self.default_ffi_func.is_some()
+    }
+
+    /// The FFI function which runs the default implementation of the trait method
+    ///
+    /// Its first argument is the foreign implementation of the trait, lowered like any other value
+    /// of the trait's type, followed by the method's arguments.
+    pub fn default_ffi_func(&self) -> Option<&FfiFunction> {
+        self.default_ffi_func.as_ref()
+    }
+
     pub fn checksum_fn_name(&self) -> &str {
         &self.checksum_fn_name
     }
@@ -567,10 +672,32 @@ impl Method {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, which is empty if the method was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn takes_self_by_arc(&self) -> bool {
         self.takes_self_by_arc
     }
 
+    /// Does calling the method consume the object?
+    ///
+    /// The foreign handle to the object can't be used after a consuming method has been called.
+    pub fn consumes(&self) -> bool {
+        self.consumes
+    }
+
+    /// Is this a static method, which is called without an object?
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Is this the getter of a public field, which the bindings expose as a read-only property?
+    pub fn is_getter(&self) -> bool {
+        self.is_getter
+    }
+
     pub fn derive_ffi_func(&mut self) -> Result<()> {
         assert!(!self.ffi_func.name().is_empty());
         self.ffi_func.init(
@@ -580,6 +707,20 @@ impl Method {
         Ok(())
     }
 
+    // Derive the default FFI function, where `self_type` is how the trait's foreign
+    // implementations are lowered.
+    pub(super) fn derive_default_ffi_func(&mut self, self_type: FfiType) {
+        let return_type = self.return_type.as_ref().map(Into::into);
+        let arguments = iter::once(FfiArgument {
+            name: "uniffi_self".to_string(),
+            type_: self_type,
+        })
+        .chain(self.arguments.iter().map(Into::into));
+        if let Some(ffi_func) = &mut self.default_ffi_func {
+            ffi_func.init(return_type, arguments);
+        }
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(
             self.arguments
@@ -614,11 +755,17 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             object_name: meta.self_name,
             object_module_path: meta.module_path,
             is_async,
+            consumes: meta.consumes,
+            is_static: meta.is_static,
+            is_getter: meta.is_getter,
             object_impl: ObjectImpl::Struct, // will be filled in later
+            value_self_type: None,
             arguments,
             return_type,
             ffi_func,
+            default_ffi_func: None,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
             checksum_fn_name,
@@ -631,6 +778,10 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
     fn from(meta: uniffi_meta::TraitMethodMetadata) -> Self {
         let ffi_name = meta.ffi_symbol_name();
         let checksum_fn_name = meta.checksum_symbol_name();
+        let default_ffi_func = meta.has_default.then(|| FfiFunction {
+            name: meta.default_ffi_symbol_name(),
+            ..FfiFunction::default()
+        });
         let is_async = meta.is_async;
         let return_type = meta.return_type.map(Into::into);
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
@@ -644,15 +795,21 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
             object_name: meta.trait_name,
             object_module_path: meta.module_path,
             is_async,
+            consumes: false,
+            is_static: false,
+            is_getter: false,
             arguments,
             return_type,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
             checksum_fn_name,
             checksum: meta.checksum,
             ffi_func,
+            default_ffi_func,
             object_impl: ObjectImpl::Struct,
+            value_self_type: None,
         }
     }
 }
@@ -664,6 +821,7 @@ pub enum UniffiTrait {
     Display { fmt: Method },
     Eq { eq: Method, ne: Method },
     Hash { hash: Method },
+    Ord { cmp: Method },
 }
 
 impl UniffiTrait {
@@ -672,7 +830,8 @@ impl UniffiTrait {
             match self {
                 UniffiTrait::Display { fmt: m }
                 | UniffiTrait::Debug { fmt: m }
-                | UniffiTrait::Hash { hash: m } => vec![m.iter_types()],
+                | UniffiTrait::Hash { hash: m }
+                | UniffiTrait::Ord { cmp: m } => vec![m.iter_types()],
                 UniffiTrait::Eq { eq, ne } => vec![eq.iter_types(), ne.iter_types()],
             }
             .into_iter()
@@ -684,7 +843,8 @@ impl UniffiTrait {
         match self {
             UniffiTrait::Display { fmt: m }
             | UniffiTrait::Debug { fmt: m }
-            | UniffiTrait::Hash { hash: m } => {
+            | UniffiTrait::Hash { hash: m }
+            | UniffiTrait::Ord { cmp: m } => {
                 m.derive_ffi_func()?;
             }
             UniffiTrait::Eq { eq, ne } => {
@@ -736,7 +896,15 @@ impl Callable for Method {
     }
 
     fn takes_self(&self) -> bool {
-        true
+        !self.is_static
+    }
+
+    fn consumes_self(&self) -> bool {
+        self.consumes
+    }
+
+    fn value_self_type(&self) -> Option<Type> {
+        self.value_self_type.clone()
     }
 }
 
@@ -804,6 +972,36 @@ mod test {
         assert_eq!(cons.ffi_func.arguments.len(), 1);
     }
 
+    #[test]
+    fn test_factory_constructors() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                constructor();
+                [Name=new_with_u32]
+                constructor(u32 v);
+            };
+            interface AsyncTesting {
+                [Async]
+                constructor();
+                [Name=new_with_u32]
+                constructor(u32 v);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let names = |obj: &Object| {
+            obj.factory_constructors()
+                .into_iter()
+                .map(|cons| cons.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert_eq!(names(obj), ["new_with_u32"]);
+        // An async primary constructor is a factory method too.
+        let obj = ci.get_object_definition("AsyncTesting").unwrap();
+        assert_eq!(names(obj), ["new", "new_with_u32"]);
+    }
+
     #[test]
     fn test_the_name_new_identifies_the_primary_constructor() {
         const UDL: &str = r#"
@@ -904,6 +1102,19 @@ mod test {
             err.to_string(),
             "Trait interfaces can not have constructors: \"new\""
         );
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Trait, WithForeign]
+            interface Testing {
+                constructor();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trait interfaces can not have constructors: \"new\""
+        );
     }
 
     #[test]
@@ -48,7 +48,7 @@ use anyhow::Result;
 use uniffi_meta::Checksum;
 
 use super::Literal;
-use super::{AsType, Type, TypeIterator};
+use super::{AsType, Method, Type, TypeIterator};
 
 /// Represents a "data class" style object, for passing around complex values.
 ///
@@ -60,8 +60,13 @@ pub struct Record {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) fields: Vec<Field>,
+    // Methods exported with `#[uniffi::export]`, these are added after the record itself.
+    #[checksum_ignore]
+    pub(super) methods: Vec<Method>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
 }
 
 impl Record {
@@ -77,10 +82,19 @@ impl Record {
         &self.fields
     }
 
+    pub fn methods(&self) -> Vec<&Method> {
+        self.methods.iter().collect()
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, which is empty if the record was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -111,7 +125,9 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
+            methods: vec![],
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
         })
     }
 }
@@ -123,7 +139,11 @@ pub struct Field {
     pub(super) type_: Type,
     pub(super) default: Option<Literal>,
     #[checksum_ignore]
+    pub(super) is_source: bool,
+    #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
 }
 
 impl Field {
@@ -139,10 +159,23 @@ impl Field {
         self.default.as_ref()
     }
 
+    /// Is this error variant field the cause of the error?
+    ///
+    /// Its type is another error or a string, and bindings should use it as the cause of the
+    /// exception they throw.
+    pub fn is_source(&self) -> bool {
+        self.is_source
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, which is empty if the field was deprecated without one
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
     }
@@ -165,7 +198,9 @@ impl TryFrom<uniffi_meta::FieldMetadata> for Field {
             name,
             type_,
             default,
+            is_source: meta.is_source,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
         })
     }
 }
@@ -77,12 +77,19 @@ impl TypeUniverse {
             Type::Int32 => self.add_type_definition("u32", type_)?,
             Type::UInt64 => self.add_type_definition("u64", type_)?,
             Type::Int64 => self.add_type_definition("i64", type_)?,
+            Type::UInt128 => self.add_type_definition("u128", type_)?,
+            Type::Int128 => self.add_type_definition("i128", type_)?,
             Type::Float32 => self.add_type_definition("f32", type_)?,
             Type::Float64 => self.add_type_definition("f64", type_)?,
             Type::Boolean => self.add_type_definition("bool", type_)?,
+            Type::Char => self.add_type_definition("char", type_)?,
             Type::String => self.add_type_definition("string", type_)?,
             Type::Bytes => self.add_type_definition("bytes", type_)?,
             Type::Timestamp => self.add_type_definition("timestamp", type_)?,
+            Type::Date => self.add_type_definition("date", type_)?,
+            Type::Json => self.add_type_definition("json", type_)?,
+            Type::Path => self.add_type_definition("path", type_)?,
+            Type::Url => self.add_type_definition("url", type_)?,
             Type::Duration => self.add_type_definition("duration", type_)?,
             Type::Object { name, .. }
             | Type::Record { name, .. }
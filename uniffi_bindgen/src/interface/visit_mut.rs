@@ -1,4 +1,4 @@
-use crate::interface::{Enum, FfiDefinition, Record};
+use crate::interface::{Enum, FfiDefinition, Method, Record};
 use crate::{ComponentInterface, VisitMut};
 use std::collections::{BTreeMap, BTreeSet};
 use uniffi_meta::Type;
@@ -42,6 +42,10 @@ impl ComponentInterface {
                     visitor.visit_type(&mut field.type_);
                 }
             }
+
+            for method in enum_item.methods.iter_mut() {
+                visit_value_method(visitor, method);
+            }
             updated_enums.insert(updated_key, enum_item.clone());
         }
         self.enums = updated_enums;
@@ -53,6 +57,10 @@ impl ComponentInterface {
                 visitor.visit_field(field);
                 visitor.visit_type(&mut field.type_);
             }
+
+            for method in record_item.methods.iter_mut() {
+                visit_value_method(visitor, method);
+            }
         }
         self.fix_record_keys_after_rename();
 
@@ -74,7 +82,11 @@ impl ComponentInterface {
         for object in self.objects.iter_mut() {
             visitor.visit_object(object);
 
-            for method in object.methods.iter_mut() {
+            for method in object
+                .methods
+                .iter_mut()
+                .chain(object.static_methods.iter_mut())
+            {
                 visitor.visit_method(method);
 
                 for argument in method.arguments.iter_mut() {
@@ -152,3 +164,21 @@ impl ComponentInterface {
         self.records = new_records;
     }
 }
+
+// Methods of records and enums, which also have the value they're called on as a type.
+fn visit_value_method<V: VisitMut>(visitor: &V, method: &mut Method) {
+    visitor.visit_method(method);
+
+    for argument in method.arguments.iter_mut() {
+        visitor.visit_argument(argument);
+        visitor.visit_type(&mut argument.type_);
+    }
+
+    if let Some(return_type) = &mut method.return_type {
+        visitor.visit_type(return_type);
+    }
+
+    if let Some(self_type) = &mut method.value_self_type {
+        visitor.visit_type(self_type);
+    }
+}
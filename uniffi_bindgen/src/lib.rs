@@ -96,12 +96,21 @@ use anyhow::{anyhow, bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use fs_err::{self as fs, File};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::io::prelude::*;
 use std::io::ErrorKind;
 use std::process::Command;
+use std::slice;
 
+pub mod api_diff;
 pub mod backend;
 pub mod bindings;
+pub mod check;
+pub mod clean;
+pub mod config;
+pub mod depfile;
+pub mod diagnostics;
+pub mod external_generator;
 pub mod interface;
 pub mod library_mode;
 pub mod macro_metadata;
@@ -110,6 +119,10 @@ pub mod scaffolding;
 #[cfg(feature = "cargo-metadata")]
 pub mod cargo_metadata;
 
+#[cfg(feature = "watch")]
+pub mod watch;
+
+use crate::diagnostics::{diagnose, Code};
 use crate::interface::{
     Argument, Constructor, Enum, FfiArgument, FfiField, Field, Function, Method, Object, Record,
     Variant,
@@ -156,6 +169,12 @@ pub trait BindingGenerator: Sized {
 
     /// Writes the bindings to the output directory
     ///
+    /// In library mode, this may be called several times in parallel, each time with a different
+    /// subset of the components.
+    ///
+    /// Returns the paths of all the files which were written, including secondary files like
+    /// headers.
+    ///
     /// # Arguments
     /// - `components`: An array of [`Component`]s representing the items to be generated.
     /// - `out_dir`: The path to where the binding generator should write the output bindings
@@ -163,7 +182,49 @@ pub trait BindingGenerator: Sized {
         &self,
         settings: &GenerationSettings,
         components: &[Component<Self::Config>],
-    ) -> Result<()>;
+    ) -> Result<Vec<Utf8PathBuf>>;
+
+    /// Called once after the bindings for all the components have been written
+    ///
+    /// This can write files which cover all the components, like a package manifest, or run a
+    /// formatter over all the generated files at once.  The default does nothing.
+    ///
+    /// # Arguments
+    /// - `components`: All the [`Component`]s which were passed to [Self::write_bindings].
+    fn finalize(
+        &self,
+        _settings: &GenerationSettings,
+        _components: &[Component<Self::Config>],
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Glob patterns, relative to the output directory, for the files this generator writes
+    ///
+    /// These are used to find stale bindings, see [clean].  Files which match the patterns but
+    /// weren't written by a run are removed when cleaning, so the patterns should be as narrow as
+    /// the generator's naming allows.  The default is no patterns, so nothing is ever removed.
+    fn owned_file_patterns(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Top-level tables of `uniffi.toml` which this generator reads
+    ///
+    /// Unknown top-level keys are errors, see [config].  A generator which keeps its settings in
+    /// its own table, rather than under `[bindings.<language>]`, returns the table's name here so
+    /// it isn't rejected.  The default is no tables.
+    fn owned_tables(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// The result of [generate_external_bindings]
+#[derive(Debug)]
+pub struct GenerationReport<Config> {
+    /// The components the bindings were generated for
+    pub components: Vec<Component<Config>>,
+    /// The files written for each component, keyed by crate name
+    pub written_files: BTreeMap<String, Vec<Utf8PathBuf>>,
 }
 
 /// A trait to alter language specific type representations.
@@ -237,6 +298,20 @@ pub trait VisitMut {
 pub struct Component<Config> {
     pub ci: ComponentInterface,
     pub config: Config,
+    /// The Cargo package the component's crate is in, if it's known.
+    pub package: Option<PackageInfo>,
+}
+
+/// Information about a Cargo package, from its `Cargo.toml`.
+///
+/// Binding generators can use this to default config values, for example to stamp the package
+/// version into the generated code.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: String,
+    pub authors: Vec<String>,
+    pub license: Option<String>,
 }
 
 /// A trait used by the bindgen to obtain config information about a source crate
@@ -246,7 +321,8 @@ pub struct Component<Config> {
 /// In most cases `cargo_metadata` can be used, but this should be able to work in
 /// more environments.
 pub trait BindgenCrateConfigSupplier {
-    /// Get the toml for the crate. Probably came from uniffi.toml in the root of the crate source.
+    /// Get the toml for the crate. Probably came from uniffi.toml in the root of the crate source,
+    /// merged on top of the workspace's uniffi.toml, see [load_crate_config].
     fn get_toml(&self, _crate_name: &str) -> Result<Option<toml::value::Table>> {
         Ok(None)
     }
@@ -254,6 +330,43 @@ pub trait BindgenCrateConfigSupplier {
     fn get_udl(&self, crate_name: &str, udl_name: &str) -> Result<String> {
         bail!("Crate {crate_name} has no UDL {udl_name}")
     }
+    /// Obtains the contents of a UDL file from its metadata, which may include the path it was
+    /// read from when the scaffolding was generated. Defaults to [Self::get_udl].
+    fn get_udl_file(&self, crate_name: &str, udl_file: &uniffi_meta::UdlFile) -> Result<String> {
+        self.get_udl(crate_name, &udl_file.file_stub)
+    }
+    /// Obtains the contents of a UDL file included by another one with `// #include`.
+    ///
+    /// `path` is relative to the file which includes it, so it's only relative to the current
+    /// directory if [Self::get_udl_path] is.  Defaults to reading the file.
+    fn get_udl_include(&self, _crate_name: &str, path: &Utf8Path) -> Result<String> {
+        fs::read_to_string(path).with_context(|| format!("Failed to read UDL from {path}"))
+    }
+    /// The path of the toml file read by [Self::get_toml], if it comes from a file.
+    ///
+    /// This is used to list the inputs in a [depfile::Depfile].
+    fn get_toml_path(&self, _crate_name: &str) -> Option<Utf8PathBuf> {
+        None
+    }
+    /// The path of the workspace toml file merged into the config by [Self::get_toml], if any.
+    ///
+    /// Like [Self::get_toml_path], this is used to list the inputs in a [depfile::Depfile].
+    fn get_workspace_toml_path(&self, _crate_name: &str) -> Option<Utf8PathBuf> {
+        None
+    }
+    /// The path of the UDL file read by [Self::get_udl_file], if it comes from a file.
+    fn get_udl_path(
+        &self,
+        _crate_name: &str,
+        _udl_file: &uniffi_meta::UdlFile,
+    ) -> Option<Utf8PathBuf> {
+        None
+    }
+    /// Information about the Cargo package the crate is in, which is stored in
+    /// [Component::package].
+    fn get_package_info(&self, _crate_name: &str) -> Option<PackageInfo> {
+        None
+    }
 }
 
 pub struct EmptyCrateConfigSupplier;
@@ -280,6 +393,10 @@ pub fn is_cdylib(library_file: impl AsRef<Utf8Path>) -> bool {
 /// - `out_dir_override`: The path to write the bindings to. If [`None`], it will be the path to the parent directory of the `udl_file`
 /// - `library_file`: The path to a dynamic library to attempt to extract the definitions from and extend the component interface with. No extensions to component interface occur if it's [`None`]
 /// - `crate_name`: Override the default crate name that is guessed from UDL file path.
+/// - `depfile`: If specified, the files read and written are added to this [`depfile::Depfile`].
+/// - `cleaner`: If specified, the files written are recorded in it so stale bindings can be removed
+///   from the output directory afterwards, see [`clean`].
+#[allow(clippy::too_many_arguments)]
 pub fn generate_external_bindings<T: BindingGenerator>(
     binding_generator: &T,
     udl_file: impl AsRef<Utf8Path>,
@@ -288,53 +405,115 @@ pub fn generate_external_bindings<T: BindingGenerator>(
     library_file: Option<impl AsRef<Utf8Path>>,
     crate_name: Option<&str>,
     try_format_code: bool,
-) -> Result<()> {
+    depfile: Option<&mut depfile::Depfile>,
+    cleaner: Option<&mut clean::OutDirCleaner>,
+) -> Result<GenerationReport<T::Config>> {
     let crate_name = crate_name
         .map(|c| Ok(c.to_string()))
         .unwrap_or_else(|| crate_name_from_cargo_toml(udl_file.as_ref()))?;
-    let mut ci = parse_udl(udl_file.as_ref(), &crate_name)?;
-    if let Some(ref library_file) = library_file {
-        macro_metadata::add_to_ci_from_library(&mut ci, library_file.as_ref())?;
-    }
-    let crate_root = &guess_crate_root(udl_file.as_ref()).context("Failed to guess crate root")?;
-
-    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+    // All errors are converted into diagnostics for the crate
+    let generate = || -> Result<GenerationReport<T::Config>> {
+        let (mut ci, included_files) = parse_udl(udl_file.as_ref(), &crate_name)?;
+        if let Some(ref library_file) = library_file {
+            macro_metadata::add_to_ci_from_library(&mut ci, library_file.as_ref())?;
+        }
+        let crate_root =
+            &guess_crate_root(udl_file.as_ref()).context("Failed to guess crate root")?;
+
+        let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+
+        let crate_config_file = crate_root.join("uniffi.toml");
+        let workspace_config_file = workspace_config_path(crate_root)?;
+        let config = {
+            let crate_config = load_crate_config(crate_root)
+                .with_context(|| format!("failed to load the config for {crate_root}"))?;
+            let toml_value =
+                overridden_config_value(crate_config, config_file_override.as_slice())?;
+            config::check_top_level_keys(&toml_value, &binding_generator.owned_tables())?;
+            ci.check_default_bytes(config::max_default_bytes(&toml_value)?)?;
+            diagnose(
+                binding_generator.new_config(&toml_value),
+                Code::InvalidConfig,
+                |_| (),
+            )?
+        };
 
-    let config = {
-        let crate_config = load_toml_file(Some(&crate_root.join("uniffi.toml")))
-            .context("failed to load {crate_root}/uniffi.toml")?;
-        let toml_value =
-            overridden_config_value(crate_config.unwrap_or_default(), config_file_override)?;
-        binding_generator.new_config(&toml_value)?
-    };
+        let settings = GenerationSettings {
+            cdylib: match library_file {
+                Some(ref library_file) => {
+                    crate::library_mode::calc_cdylib_name(library_file.as_ref())
+                        .map(ToOwned::to_owned)
+                }
+                None => None,
+            },
+            out_dir: get_out_dir(
+                udl_file.as_ref(),
+                out_dir_override.as_ref().map(|p| p.as_ref()),
+            )?,
+            try_format_code,
+        };
 
-    let settings = GenerationSettings {
-        cdylib: match library_file {
-            Some(ref library_file) => {
-                crate::library_mode::calc_cdylib_name(library_file.as_ref()).map(ToOwned::to_owned)
-            }
-            None => None,
-        },
-        out_dir: get_out_dir(
-            udl_file.as_ref(),
-            out_dir_override.as_ref().map(|p| p.as_ref()),
-        )?,
-        try_format_code,
+        let mut components = vec![Component {
+            ci,
+            config,
+            package: None,
+        }];
+        binding_generator.update_component_configs(&settings, &mut components)?;
+        let written_files: BTreeMap<_, _> = components
+            .iter()
+            .map(|component| {
+                let files =
+                    binding_generator.write_bindings(&settings, slice::from_ref(component))?;
+                Ok((component.ci.crate_name().to_string(), files))
+            })
+            .collect::<Result<_>>()?;
+        binding_generator
+            .finalize(&settings, &components)
+            .context("Failed to finalize the bindings")?;
+        if let Some(cleaner) = cleaner {
+            let written: Vec<_> = written_files.values().flatten().cloned().collect();
+            cleaner.add_run(binding_generator, &settings.out_dir, &written);
+        }
+        let report = GenerationReport {
+            components,
+            written_files,
+        };
+        let Some(depfile) = depfile else {
+            return Ok(report);
+        };
+        for output in report.written_files.values().flatten() {
+            depfile.add_output(output.clone());
+        }
+        depfile.add_input(udl_file.as_ref());
+        for included_file in included_files {
+            depfile.add_input(included_file);
+        }
+        let inputs = [
+            workspace_config_file.as_deref(),
+            Some(crate_config_file.as_path()),
+            config_file_override,
+            library_file.as_ref().map(|p| p.as_ref()),
+        ];
+        for input in inputs.into_iter().flatten().filter(|p| p.is_file()) {
+            depfile.add_input(input);
+        }
+        Ok(report)
     };
-
-    let mut components = vec![Component { ci, config }];
-    binding_generator.update_component_configs(&settings, &mut components)?;
-    binding_generator.write_bindings(&settings, &components)
+    diagnose(generate(), Code::Other, |d| {
+        d.crate_name.get_or_insert_with(|| crate_name.clone());
+    })
 }
 
 // Generate the infrastructural Rust code for implementing the UDL interface,
 // such as the `extern "C"` function definitions and record data types.
 // Locates and parses Cargo.toml to determine the name of the crate.
+//
+// Returns the files included by the UDL file, which build scripts should tell cargo about.
 pub fn generate_component_scaffolding(
     udl_file: &Utf8Path,
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
-) -> Result<()> {
+) -> Result<Vec<Utf8PathBuf>> {
     let component = parse_udl(udl_file, &crate_name_from_cargo_toml(udl_file)?)?;
     generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
 }
@@ -342,32 +521,41 @@ pub fn generate_component_scaffolding(
 // Generate the infrastructural Rust code for implementing the UDL interface,
 // such as the `extern "C"` function definitions and record data types, using
 // the specified crate name.
+//
+// Returns the files included by the UDL file, which build scripts should tell cargo about.
 pub fn generate_component_scaffolding_for_crate(
     udl_file: &Utf8Path,
     crate_name: &str,
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
-) -> Result<()> {
+) -> Result<Vec<Utf8PathBuf>> {
     let component = parse_udl(udl_file, crate_name)?;
     generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
 }
 
 fn generate_component_scaffolding_inner(
-    component: ComponentInterface,
+    (component, included_files): (ComponentInterface, Vec<Utf8PathBuf>),
     udl_file: &Utf8Path,
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
-) -> Result<()> {
+) -> Result<Vec<Utf8PathBuf>> {
     let file_stem = udl_file.file_stem().context("not a file")?;
     let filename = format!("{file_stem}.uniffi.rs");
     let out_path = get_out_dir(udl_file, out_dir_override)?.join(filename);
+    let partial_namespace = uniffi_udl::is_partial_namespace(&fs::read_to_string(udl_file)?)?;
     let mut f = File::create(&out_path)?;
-    write!(f, "{}", RustScaffolding::new(&component, file_stem))
-        .context("Failed to write output file")?;
+    write!(
+        f,
+        "{}",
+        RustScaffolding::new(&component, file_stem)
+            .partial_namespace(partial_namespace)
+            .udl_path(udl_path_from_crate_root(udl_file))
+    )
+    .context("Failed to write output file")?;
     if format_code {
         format_code_with_rustfmt(&out_path)?;
     }
-    Ok(())
+    Ok(included_files)
 }
 
 // Generate the bindings in the target languages that call the scaffolding
@@ -380,7 +568,7 @@ pub fn generate_bindings<T: BindingGenerator>(
     library_file: Option<&Utf8Path>,
     crate_name: Option<&str>,
     try_format_code: bool,
-) -> Result<()> {
+) -> Result<GenerationReport<T::Config>> {
     generate_external_bindings(
         &binding_generator,
         udl_file,
@@ -389,6 +577,8 @@ pub fn generate_bindings<T: BindingGenerator>(
         library_file,
         crate_name,
         try_format_code,
+        None,
+        None,
     )
 }
 
@@ -398,6 +588,18 @@ pub fn print_repr(library_path: &Utf8Path) -> Result<()> {
     Ok(())
 }
 
+/// Print the metadata from a library as JSON, see [library_mode::metadata_json]
+///
+/// The JSON is written to `out_file` if it's specified, otherwise to stdout.
+pub fn print_json(library_path: &Utf8Path, out_file: Option<&Utf8Path>) -> Result<()> {
+    let json = library_mode::metadata_json(library_path)?;
+    match out_file {
+        Some(out_file) => fs::write(out_file, json + "\n")?,
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
 // Given the path to a UDL file, locate and parse the corresponding Cargo.toml to determine
 // the library crate name.
 // Note that this is largely a copy of code in uniffi_macros/src/util.rs, but sharing it
@@ -465,11 +667,64 @@ fn get_out_dir(udl_file: &Utf8Path, out_dir_override: Option<&Utf8Path>) -> Resu
     })
 }
 
-fn parse_udl(udl_file: &Utf8Path, crate_name: &str) -> Result<ComponentInterface> {
+// The path of the UDL file relative to the crate root, which is recorded in the metadata so library
+// mode can find UDL files outside of `src/`.  This is only known when we're run from a build
+// script, since otherwise `CARGO_MANIFEST_DIR` is for some other crate, or not set at all.
+fn udl_path_from_crate_root(udl_file: &Utf8Path) -> Option<String> {
+    std::env::var_os("OUT_DIR")?;
+    let crate_root = depfile::absolute(std::env::var("CARGO_MANIFEST_DIR").ok()?.as_ref()).ok()?;
+    let udl_file = depfile::absolute(udl_file).ok()?;
+    let relative = depfile::relative_to(&udl_file, &crate_root);
+    if relative.is_absolute() {
+        return Some(relative.into_string());
+    }
+    // Use `/` as the separator, so the path can be used on any host.
+    let components: Vec<_> = relative.components().map(|c| c.as_str()).collect();
+    Some(components.join("/"))
+}
+
+// Parse a UDL file, returning the paths of the files it includes along with the interface.
+fn parse_udl(
+    udl_file: &Utf8Path,
+    crate_name: &str,
+) -> Result<(ComponentInterface, Vec<Utf8PathBuf>)> {
     let udl = fs::read_to_string(udl_file)
         .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
-    let group = uniffi_udl::parse_udl(&udl, crate_name)?;
-    ComponentInterface::from_metadata(group)
+    let (group, included_files) = diagnose(
+        parse_udl_with_includes(&udl, udl_file, crate_name, |path| {
+            Ok(fs::read_to_string(path)?)
+        }),
+        Code::UdlParse,
+        |d| {
+            d.file.get_or_insert_with(|| udl_file.to_owned());
+        },
+    )?;
+    let ci = diagnose(ComponentInterface::from_metadata(group), Code::Other, |d| {
+        d.file.get_or_insert_with(|| udl_file.to_owned());
+    })?;
+    Ok((ci, included_files))
+}
+
+// Parse UDL read from `udl_file` with [uniffi_udl::parse_udl_file], reading the files it includes
+// with `read_include`.
+pub(crate) fn parse_udl_with_includes(
+    udl: &str,
+    udl_file: &Utf8Path,
+    crate_name: &str,
+    read_include: impl Fn(&Utf8Path) -> Result<String>,
+) -> Result<(uniffi_meta::MetadataGroup, Vec<Utf8PathBuf>)> {
+    fn utf8_path(path: &std::path::Path) -> Result<&Utf8Path> {
+        Utf8Path::from_path(path).with_context(|| format!("{} isn't UTF-8", path.display()))
+    }
+    let (group, included_files) =
+        uniffi_udl::parse_udl_file(udl, udl_file.as_std_path(), crate_name, |path| {
+            read_include(utf8_path(path)?)
+        })?;
+    let included_files = included_files
+        .iter()
+        .map(|path| Ok(utf8_path(path)?.to_owned()))
+        .collect::<Result<_>>()?;
+    Ok((group, included_files))
 }
 
 fn format_code_with_rustfmt(path: &Utf8Path) -> Result<()> {
@@ -492,29 +747,104 @@ fn load_toml_file(source: Option<&Utf8Path>) -> Result<Option<toml::value::Table
         if source.exists() {
             let contents =
                 fs::read_to_string(source).with_context(|| format!("read file: {:?}", source))?;
-            return Ok(Some(
-                toml::de::from_str(&contents)
-                    .with_context(|| format!("parse toml: {:?}", source))?,
-            ));
+            let table = toml::de::from_str::<toml::value::Table>(&contents);
+            let line_col = table.as_ref().err().and_then(|e| e.line_col());
+            let mut table = diagnose(
+                table.with_context(|| format!("parse toml: {:?}", source)),
+                Code::InvalidConfig,
+                |d| {
+                    d.file = Some(source.to_owned());
+                    // toml's positions start from 0
+                    d.line = line_col.map(|(line, _)| line + 1);
+                    d.column = line_col.map(|(_, col)| col + 1);
+                },
+            )?;
+            if let Some(dir) = source.parent() {
+                config::resolve_relative_paths(&mut table, dir);
+            }
+            return Ok(Some(table));
         }
     }
 
     Ok(None)
 }
 
-/// Load the default `uniffi.toml` config, merge TOML trees with `config_file_override` if specified.
+/// Load a crate's config, which is its `uniffi.toml` merged on top of the workspace's
+///
+/// The workspace config is the nearest `uniffi.toml` above the crate root, up to the workspace
+/// root, see [workspace_config_path].  The files are merged with [merge_toml], so settings for
+/// the whole workspace can be overridden per crate.  Files which don't exist are skipped.
+pub fn load_crate_config(crate_root: &Utf8Path) -> Result<toml::value::Table> {
+    let mut config = match workspace_config_path(crate_root)? {
+        Some(path) => load_toml_file(Some(&path))?.unwrap_or_default(),
+        None => toml::value::Table::new(),
+    };
+    if let Some(crate_config) = load_toml_file(Some(&crate_root.join("uniffi.toml")))? {
+        merge_toml(&mut config, crate_config);
+    }
+    Ok(config)
+}
+
+/// Find the workspace's `uniffi.toml` for a crate
+///
+/// This is the nearest `uniffi.toml` in a directory above `crate_root`, searching up to the
+/// workspace root, which is the first directory with a `Cargo.toml` containing a `[workspace]`
+/// table.  There's no workspace config if the crate isn't in a workspace, or is the workspace
+/// root itself.
+pub fn workspace_config_path(crate_root: &Utf8Path) -> Result<Option<Utf8PathBuf>> {
+    if is_workspace_root(crate_root)? {
+        return Ok(None);
+    }
+    let mut config_path = None;
+    for dir in crate_root.ancestors().skip(1) {
+        let path = dir.join("uniffi.toml");
+        if config_path.is_none() && path.is_file() {
+            config_path = Some(path);
+        }
+        if is_workspace_root(dir)? {
+            return Ok(config_path);
+        }
+    }
+    Ok(None)
+}
+
+fn is_workspace_root(dir: &Utf8Path) -> Result<bool> {
+    Ok(load_toml_file(Some(&dir.join("Cargo.toml")))?
+        .is_some_and(|manifest| manifest.contains_key("workspace")))
+}
+
+/// Merge the override config files into a crate's `uniffi.toml` config.
+///
+/// The files are merged in order, so later files take precedence over earlier ones.  The CLI
+/// passes the global `--config` file followed by the language-specific one, so the precedence is:
+///
+/// 1. the language-specific override
+/// 2. the global override
+/// 3. the crate's `uniffi.toml`
+/// 4. the workspace's `uniffi.toml`
+/// 5. the binding generator's defaults, for values which aren't set in any file
+///
+/// Files which don't exist are skipped.
 fn overridden_config_value(
     mut config: toml::value::Table,
-    config_file_override: Option<&Utf8Path>,
+    config_file_overrides: &[&Utf8Path],
 ) -> Result<toml::Value> {
-    let override_config = load_toml_file(config_file_override).context("override config")?;
-    if let Some(override_config) = override_config {
-        merge_toml(&mut config, override_config);
+    for config_file_override in config_file_overrides {
+        let override_config =
+            load_toml_file(Some(config_file_override)).context("override config")?;
+        if let Some(override_config) = override_config {
+            merge_toml(&mut config, override_config);
+        }
     }
+    config::interpolate_env(&mut config)?;
     Ok(toml::Value::from(config))
 }
 
-fn merge_toml(a: &mut toml::value::Table, b: toml::value::Table) {
+/// Merge `b` into `a`, with the values from `b` taking precedence
+///
+/// Tables are merged key by key, recursively, so `b` only needs the keys it changes.  Any other
+/// values, including arrays, are replaced rather than combined.
+pub fn merge_toml(a: &mut toml::value::Table, b: toml::value::Table) {
     for (key, value) in b.into_iter() {
         match a.get_mut(&key) {
             Some(existing_value) => match (existing_value, value) {
@@ -614,4 +944,324 @@ mod test {
 
         assert_eq!(&expected, &default);
     }
+
+    #[test]
+    fn test_overridden_config_value_missing_files() {
+        let crate_config: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+        "#,
+        )
+        .unwrap();
+
+        // No override file means the crate config is used as-is.
+        assert_eq!(
+            overridden_config_value(crate_config.clone(), &[]).unwrap(),
+            toml::Value::from(crate_config.clone())
+        );
+        // An override file that doesn't exist is treated the same way.
+        assert_eq!(
+            overridden_config_value(
+                crate_config.clone(),
+                &[Utf8Path::new("/does/not/exist/uniffi.toml")]
+            )
+            .unwrap(),
+            toml::Value::from(crate_config)
+        );
+        // A crate without its own config falls back to the defaults.
+        assert_eq!(
+            overridden_config_value(Default::default(), &[]).unwrap(),
+            toml::Value::from(toml::value::Table::new())
+        );
+    }
+
+    #[test]
+    fn test_overridden_config_value_precedence() {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-config-precedence-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let global = dir.join("global.toml");
+        fs::write(
+            &global,
+            r#"
+            [bindings.kotlin]
+            package_name = "org.global"
+            cdylib_name = "global"
+
+            [bindings.python]
+            cdylib_name = "global"
+        "#,
+        )
+        .unwrap();
+        let kotlin = dir.join("kotlin.toml");
+        fs::write(
+            &kotlin,
+            r#"
+            [bindings.kotlin]
+            package_name = "org.kotlin"
+        "#,
+        )
+        .unwrap();
+        let crate_config: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+            android = true
+        "#,
+        )
+        .unwrap();
+
+        // The language-specific file takes precedence over the global one, which takes precedence
+        // over the crate config.
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.kotlin"
+            cdylib_name = "global"
+            android = true
+
+            [bindings.python]
+            cdylib_name = "global"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            overridden_config_value(crate_config.clone(), &[&global, &kotlin]).unwrap(),
+            toml::Value::from(expected)
+        );
+
+        // Without a language-specific file, the global one is used.
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.global"
+            cdylib_name = "global"
+            android = true
+
+            [bindings.python]
+            cdylib_name = "global"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            overridden_config_value(crate_config.clone(), &[&global]).unwrap(),
+            toml::Value::from(expected)
+        );
+
+        // Only a language-specific file.
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.kotlin"
+            android = true
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            overridden_config_value(crate_config, &[&kotlin]).unwrap(),
+            toml::Value::from(expected)
+        );
+    }
+
+    #[test]
+    fn test_merge_toml_nested_tables_and_arrays() {
+        let mut workspace: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.workspace"
+            cdylib_name = "workspace"
+            custom_types = ["Url", "Guid"]
+
+            [bindings.kotlin.external_packages]
+            shared = "org.workspace.shared"
+            logging = "org.workspace.logging"
+        "#,
+        )
+        .unwrap();
+        let crate_config: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+            custom_types = ["Url"]
+
+            [bindings.kotlin.external_packages]
+            logging = "org.example.logging"
+            other = "org.example.other"
+        "#,
+        )
+        .unwrap();
+
+        // Conflicting scalars and arrays are replaced, tables are merged key by key.
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+            cdylib_name = "workspace"
+            custom_types = ["Url"]
+
+            [bindings.kotlin.external_packages]
+            shared = "org.workspace.shared"
+            logging = "org.example.logging"
+            other = "org.example.other"
+        "#,
+        )
+        .unwrap();
+        merge_toml(&mut workspace, crate_config);
+        assert_eq!(workspace, expected);
+    }
+
+    #[test]
+    fn test_load_crate_config() {
+        let workspace_root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-crate-config-{}", std::process::id()));
+        let crate_root = workspace_root.join("components/example");
+        fs::create_dir_all(&crate_root).unwrap();
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"components/example\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_root.join("Cargo.toml"),
+            "[package]\nname = \"example\"\n",
+        )
+        .unwrap();
+
+        // No config files at all.
+        assert_eq!(workspace_config_path(&crate_root).unwrap(), None);
+        assert_eq!(load_crate_config(&crate_root).unwrap(), Default::default());
+
+        fs::write(
+            workspace_root.join("uniffi.toml"),
+            r#"
+            [bindings.kotlin]
+            package_name = "org.workspace"
+            cdylib_name = "workspace"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            workspace_config_path(&crate_root).unwrap(),
+            Some(workspace_root.join("uniffi.toml"))
+        );
+        // The workspace root has no workspace config of its own.
+        assert_eq!(workspace_config_path(&workspace_root).unwrap(), None);
+
+        // Only the workspace config.
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.workspace"
+            cdylib_name = "workspace"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(load_crate_config(&crate_root).unwrap(), expected);
+
+        // The crate config takes precedence over the workspace config.
+        fs::write(
+            crate_root.join("uniffi.toml"),
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+        "#,
+        )
+        .unwrap();
+        let expected: toml::value::Table = toml::de::from_str(
+            r#"
+            [bindings.kotlin]
+            package_name = "org.example"
+            cdylib_name = "workspace"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(load_crate_config(&crate_root).unwrap(), expected);
+
+        // The nearest config above the crate is used.
+        fs::write(
+            workspace_root.join("components/uniffi.toml"),
+            r#"
+            [bindings.kotlin]
+            cdylib_name = "components"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            workspace_config_path(&crate_root).unwrap(),
+            Some(workspace_root.join("components/uniffi.toml"))
+        );
+        fs::remove_dir_all(workspace_root).unwrap();
+    }
+
+    #[test]
+    fn test_template_overrides_dir_relative_to_config() {
+        let workspace_root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-template-overrides-{}", std::process::id()));
+        let crate_root = workspace_root.join("components/example");
+        fs::create_dir_all(&crate_root).unwrap();
+        fs::write(
+            workspace_root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"components/example\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_root.join("Cargo.toml"),
+            "[package]\nname = \"example\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(workspace_root.join("templates")).unwrap();
+        fs::write(
+            workspace_root.join("templates/wrapper.py"),
+            "# {{ crate_name }}\n{{ builtin }}",
+        )
+        .unwrap();
+        fs::write(
+            workspace_root.join("uniffi.toml"),
+            r#"
+            [bindings.python]
+            template_overrides_dir = "templates"
+        "#,
+        )
+        .unwrap();
+
+        // The directory is relative to the workspace config, not to the crate or the current
+        // directory.
+        let config = overridden_config_value(load_crate_config(&crate_root).unwrap(), &[]).unwrap();
+        assert_eq!(
+            config["bindings"]["python"]["template_overrides_dir"].as_str(),
+            Some(workspace_root.join("templates").as_str())
+        );
+        bindings::PythonBindingGenerator
+            .new_config(&config)
+            .unwrap();
+
+        // A directory set by an override file is relative to that file.
+        let override_dir = workspace_root.join("overrides");
+        fs::create_dir_all(override_dir.join("python")).unwrap();
+        fs::write(
+            override_dir.join("uniffi.toml"),
+            r#"
+            [bindings.python]
+            template_overrides_dir = "python"
+        "#,
+        )
+        .unwrap();
+        let config = overridden_config_value(
+            load_crate_config(&crate_root).unwrap(),
+            &[&override_dir.join("uniffi.toml")],
+        )
+        .unwrap();
+        assert_eq!(
+            config["bindings"]["python"]["template_overrides_dir"].as_str(),
+            Some(override_dir.join("python").as_str())
+        );
+        bindings::PythonBindingGenerator
+            .new_config(&config)
+            .unwrap();
+        fs::remove_dir_all(workspace_root).unwrap();
+    }
 }
@@ -16,15 +16,24 @@
 ///   - UniFFI can figure out the package/module names for each crate, eliminating the external
 ///     package maps.
 use crate::{
+    clean::OutDirCleaner,
+    config,
+    diagnostics::{diagnose, Code},
     macro_metadata, overridden_config_value, BindgenCrateConfigSupplier, BindingGenerator,
     Component, ComponentInterface, GenerationSettings, Result,
 };
-use anyhow::bail;
-use camino::Utf8Path;
-use std::{collections::HashMap, fs};
+use anyhow::{bail, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    num::NonZeroUsize,
+    thread,
+};
 use toml::value::Table as TomlTable;
 use uniffi_meta::{
-    create_metadata_groups, fixup_external_type, group_metadata, Metadata, MetadataGroup,
+    create_metadata_groups, describe_item, fixup_external_type, group_metadata, reachable_crates,
+    retain_included_tags, type_docstrings, Metadata, MetadataGroup, Type,
 };
 
 /// Generate foreign bindings
@@ -33,8 +42,21 @@ use uniffi_meta::{
 /// External bindings authors should consider using [find_components], which provides a simpler
 /// interface and allows for more flexibility in how the external bindings are generated.
 ///
+/// Each crate's config is obtained from `config_supplier` (usually the `uniffi.toml` in the
+/// crate root), then `config_file_override` is merged on top of it with its values taking
+/// precedence.  See [generate_bindings_multi] to use more than one override file.
+///
+/// If `crate_name` is specified, only the bindings for that crate are written and returned.  The
+/// other crates in the library are still used to resolve external types.
+///
+/// The bindings are written using one thread per available CPU, see [generate_bindings_multi] to
+/// control this.
+///
+/// The library's UniFFI contract version and API checksum functions are checked against this
+/// version of `uniffi-bindgen`, see [generate_bindings_multi] to skip this.
+///
 /// Returns the list of sources used to generate the bindings, in no particular order.
-pub fn generate_bindings<T: BindingGenerator + ?Sized>(
+pub fn generate_bindings<T>(
     library_path: &Utf8Path,
     crate_name: Option<String>,
     binding_generator: &T,
@@ -42,54 +64,491 @@ pub fn generate_bindings<T: BindingGenerator + ?Sized>(
     config_file_override: Option<&Utf8Path>,
     out_dir: &Utf8Path,
     try_format_code: bool,
-) -> Result<Vec<Component<T::Config>>> {
-    let mut components = find_components(library_path, config_supplier)?
-        .into_iter()
-        .map(|Component { ci, config }| {
-            let toml_value = overridden_config_value(config, config_file_override)?;
-            let config = binding_generator.new_config(&toml_value)?;
-            Ok(Component { ci, config })
+) -> Result<Vec<Component<T::Config>>>
+where
+    T: BindingGenerator + Sync + ?Sized,
+    T::Config: Sync,
+{
+    generate_bindings_multi(
+        &[library_path],
+        binding_generator,
+        config_supplier,
+        out_dir,
+        GenerateOptions {
+            crate_name,
+            config_file_overrides: config_file_override.as_slice(),
+            try_format_code,
+            ..GenerateOptions::default()
+        },
+    )
+}
+
+/// Options for [generate_bindings_multi]
+///
+/// The defaults generate the bindings for every crate and item, check the library's version and
+/// don't format the code, like [generate_bindings] without a config override file.
+pub struct GenerateOptions<'a> {
+    /// If specified, only the bindings for this crate are written and returned.  The other crates
+    /// in the libraries are still used to resolve external types.
+    pub crate_name: Option<String>,
+    /// Config files merged on top of each crate's config in order, so the values from later files
+    /// take precedence.  For example, the CLI passes the global `--config` file followed by the
+    /// one for the language being generated.
+    pub config_file_overrides: &'a [&'a Utf8Path],
+    /// Whether to run the formatter over the generated bindings.
+    pub try_format_code: bool,
+    /// Limits the number of threads used to write the bindings.  If it's `None`, one thread per
+    /// available CPU is used.
+    pub jobs: Option<usize>,
+    /// If set, generation fails when a library was built with a different UniFFI contract version
+    /// or is missing API checksum functions.  Bindings generated from such a library would fail
+    /// when they're loaded.
+    pub version_check: bool,
+    /// No bindings are generated for these crates, or the crates listed in the `omit_crates` key
+    /// of the config override files.  See [omit_components].
+    pub omit_crates: &'a [String],
+    /// If specified, items with an export tag which isn't in it are left out of the bindings.
+    /// Otherwise the `include_tags` key of each crate's config lists the tags, and every item is
+    /// generated if there's no such key.  See [uniffi_meta::retain_included_tags].
+    pub include_tags: Option<&'a [String]>,
+    /// If set along with `crate_name`, the bindings for the crates which that crate's bindings
+    /// use, directly or indirectly, are also generated.  See [component_dependencies].
+    pub with_dependencies: bool,
+    /// If specified, the files written are recorded in it so stale bindings can be removed from
+    /// the output directory afterwards, see [crate::clean].
+    pub cleaner: Option<&'a mut OutDirCleaner>,
+    /// If specified, the paths of the files written are appended to it, for example to list them
+    /// as the outputs in a depfile.
+    pub written_files: Option<&'a mut Vec<Utf8PathBuf>>,
+}
+
+impl Default for GenerateOptions<'_> {
+    fn default() -> Self {
+        Self {
+            crate_name: None,
+            config_file_overrides: &[],
+            try_format_code: false,
+            jobs: None,
+            version_check: true,
+            omit_crates: &[],
+            include_tags: None,
+            with_dependencies: false,
+            cleaner: None,
+            written_files: None,
+        }
+    }
+}
+
+/// Generate foreign bindings for several libraries at once
+///
+/// This works like [generate_bindings], but types from any of the libraries can be used as
+/// external types by the others.  Each crate must only be in one of the libraries and the
+/// bindings for a crate will load the library it was found in.  See [GenerateOptions] for the
+/// options.
+pub fn generate_bindings_multi<T>(
+    library_paths: &[&Utf8Path],
+    binding_generator: &T,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+    out_dir: &Utf8Path,
+    options: GenerateOptions<'_>,
+) -> Result<Vec<Component<T::Config>>>
+where
+    T: BindingGenerator + Sync + ?Sized,
+    T::Config: Sync,
+{
+    let GenerateOptions {
+        crate_name,
+        config_file_overrides,
+        try_format_code,
+        jobs,
+        version_check,
+        omit_crates,
+        include_tags,
+        with_dependencies,
+        cleaner,
+        written_files: recorded_files,
+    } = options;
+    let (items, crate_libraries) = extract_from_libraries(library_paths, version_check)?;
+    let omit_crates = omitted_crates(omit_crates, config_file_overrides)?;
+    let components = omit_components(
+        components_from_metadata(items, config_supplier, include_tags, config_file_overrides)?,
+        &omit_crates,
+    )?
+    .into_iter()
+    .map(|component| {
+        let toml_value = overridden_config_value(component.config, config_file_overrides)?;
+        let config = diagnose(
+            config::check_top_level_keys(&toml_value, &binding_generator.owned_tables())
+                .and_then(|_| binding_generator.new_config(&toml_value)),
+            Code::InvalidConfig,
+            |d| {
+                d.crate_name
+                    .get_or_insert_with(|| component.ci.crate_name().to_string());
+            },
+        )?;
+        diagnose(
+            config::max_default_bytes(&toml_value)
+                .and_then(|max_len| component.ci.check_default_bytes(max_len)),
+            Code::Other,
+            |d| {
+                d.crate_name
+                    .get_or_insert_with(|| component.ci.crate_name().to_string());
+            },
+        )?;
+        Ok(Component {
+            ci: component.ci,
+            config,
+            package: component.package,
         })
-        .collect::<Result<Vec<_>>>()?;
+    })
+    .collect::<Result<Vec<_>>>()?;
 
+    // Update the configs for each library separately, so that each one gets the right cdylib.
+    let mut components_by_library: Vec<Vec<_>> = library_paths.iter().map(|_| vec![]).collect();
+    for c in components {
+        components_by_library[crate_libraries[c.ci.crate_name()]].push(c);
+    }
+    let mut components = vec![];
+    for (library_path, mut library_components) in library_paths.iter().zip(components_by_library) {
+        let settings = GenerationSettings {
+            out_dir: out_dir.to_owned(),
+            try_format_code,
+            cdylib: calc_cdylib_name(library_path).map(ToOwned::to_owned),
+        };
+        binding_generator.update_component_configs(&settings, &mut library_components)?;
+        components.extend(library_components);
+    }
     let settings = GenerationSettings {
         out_dir: out_dir.to_owned(),
         try_format_code,
-        cdylib: calc_cdylib_name(library_path).map(ToOwned::to_owned),
+        cdylib: match library_paths {
+            [library_path] => calc_cdylib_name(library_path).map(ToOwned::to_owned),
+            _ => None,
+        },
     };
-    binding_generator.update_component_configs(&settings, &mut components)?;
+    if library_paths.len() > 1 {
+        // Update again with all components, so the crates in each library know about the crates
+        // in the others.  The cdylib names were set above, so aren't changed by this.
+        binding_generator.update_component_configs(&settings, &mut components)?;
+    }
 
     fs::create_dir_all(out_dir)?;
     if let Some(crate_name) = &crate_name {
-        let old_elements = components.drain(..);
-        let mut matches: Vec<_> = old_elements
-            .filter(|s| s.ci.crate_name() == crate_name)
+        let mut found_crate_names: Vec<_> = components
+            .iter()
+            .map(|c| c.ci.crate_name().to_string())
             .collect();
+        found_crate_names.sort();
+        let selected = if with_dependencies {
+            component_dependencies(&components, crate_name)
+        } else {
+            BTreeSet::from([crate_name.clone()])
+        };
+        let (mut matches, dependencies): (Vec<_>, Vec<_>) = components
+            .drain(..)
+            .filter(|c| selected.contains(c.ci.crate_name()))
+            .partition(|c| c.ci.crate_name() == crate_name);
+        let libraries = library_paths
+            .iter()
+            .map(|p| p.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
         match matches.len() {
-            0 => bail!("Crate {crate_name} not found in {library_path}"),
+            0 => bail!(
+                "Crate {crate_name} not found in {libraries} (found: {})",
+                found_crate_names.join(", ")
+            ),
             1 => components.push(matches.pop().unwrap()),
-            n => bail!("{n} crates named {crate_name} found in {library_path}"),
+            n => bail!("{n} crates named {crate_name} found in {libraries}"),
         }
+        components.extend(dependencies);
     }
 
-    binding_generator.write_bindings(&settings, &components)?;
+    let written_files = write_bindings(binding_generator, &settings, &components, jobs)?;
+    if let Some(cleaner) = cleaner {
+        cleaner.add_run(binding_generator, out_dir, &written_files);
+    }
+    if let Some(recorded_files) = recorded_files {
+        recorded_files.extend(written_files);
+    }
 
     Ok(components)
 }
 
+/// Remove the components for the crates in `omit_crates`
+///
+/// This is for crates which are linked into the library but whose bindings aren't wanted, for
+/// example because they're only used from Rust.  It fails if a crate in `omit_crates` isn't one
+/// of the components, or if any of the remaining components use a type from an omitted crate,
+/// since their bindings would import bindings which don't exist.
+pub fn omit_components<Config>(
+    components: Vec<Component<Config>>,
+    omit_crates: &BTreeSet<String>,
+) -> Result<Vec<Component<Config>>> {
+    let crate_names: BTreeSet<_> = components.iter().map(|c| c.ci.crate_name()).collect();
+    let unknown: Vec<_> = omit_crates
+        .iter()
+        .filter(|c| !crate_names.contains(c.as_str()))
+        .map(|c| format!("`{c}`"))
+        .collect();
+    if !unknown.is_empty() {
+        bail!(
+            "Can't omit {} since they're not in the library (found: {})",
+            unknown.join(", "),
+            crate_names.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    let components: Vec<_> = components
+        .into_iter()
+        .filter(|c| !omit_crates.contains(c.ci.crate_name()))
+        .collect();
+    let references: Vec<_> = components
+        .iter()
+        .flat_map(|c| {
+            external_references(&c.ci)
+                .into_iter()
+                .filter_map(|(item, ty)| {
+                    let Type::External {
+                        module_path, name, ..
+                    } = ty
+                    else {
+                        return None;
+                    };
+                    let crate_name = module_path.split("::").next().unwrap_or(module_path);
+                    omit_crates.contains(crate_name).then(|| {
+                        format!(
+                            "\n  crate `{}`: {item} uses `{name}` from `{crate_name}`",
+                            c.ci.crate_name()
+                        )
+                    })
+                })
+                .collect::<BTreeSet<_>>()
+        })
+        .collect();
+    if !references.is_empty() {
+        bail!(
+            "Types from omitted crates are used by the bindings for other crates:{}\n\
+             Either stop omitting the crates or stop using their types.",
+            references.concat()
+        );
+    }
+    Ok(components)
+}
+
+/// The crates whose bindings are needed by the bindings for `crate_name`
+///
+/// This is `crate_name` plus the crates of the external types its component uses, and so on.
+/// It works like [uniffi_meta::crate_dependencies], but also follows the external types used in
+/// UDL files, which aren't in the library's metadata.
+pub fn component_dependencies<Config>(
+    components: &[Component<Config>],
+    crate_name: &str,
+) -> BTreeSet<String> {
+    reachable_crates(crate_name, |crate_name| {
+        components
+            .iter()
+            .filter(|c| c.ci.crate_name() == crate_name)
+            .flat_map(|c| c.ci.iter_external_types())
+            .map(|(_, crate_name, ..)| crate_name)
+            .collect::<Vec<_>>()
+    })
+}
+
+// The items in a component interface which use external types, along with those types
+fn external_references(ci: &ComponentInterface) -> Vec<(String, &Type)> {
+    let items = ci
+        .function_definitions()
+        .iter()
+        .map(|f| (format!("function `{}`", f.name()), f.iter_types()))
+        .chain(
+            ci.object_definitions()
+                .iter()
+                .map(|o| (format!("object `{}`", o.name()), o.iter_types())),
+        )
+        .chain(
+            ci.record_definitions()
+                .map(|r| (format!("record `{}`", r.name()), r.iter_types())),
+        )
+        .chain(
+            ci.enum_definitions()
+                .map(|e| (format!("enum `{}`", e.name()), e.iter_types())),
+        )
+        .chain(ci.callback_interface_definitions().iter().map(|cbi| {
+            (
+                format!("callback interface `{}`", cbi.name()),
+                cbi.iter_types(),
+            )
+        }));
+    items
+        .flat_map(|(description, types)| {
+            types
+                .filter(|ty| matches!(ty, Type::External { .. }))
+                .map(move |ty| (description.clone(), ty))
+        })
+        .collect()
+}
+
+// The crates to omit, from the `omit_crates` argument and the config override files
+fn omitted_crates(
+    omit_crates: &[String],
+    config_file_overrides: &[&Utf8Path],
+) -> Result<BTreeSet<String>> {
+    let config = overridden_config_value(TomlTable::new(), config_file_overrides)?;
+    let from_config = match config.get("omit_crates") {
+        Some(value) => value
+            .clone()
+            .try_into::<Vec<String>>()
+            .context("`omit_crates` must be a list of crate names")?,
+        None => vec![],
+    };
+    Ok(omit_crates.iter().cloned().chain(from_config).collect())
+}
+
+// The tags of the items to generate bindings for, from the `include_tags` argument or else the
+// `include_tags` key of the crate's config.  `None` means that every item is included.
+fn included_tags(
+    include_tags: Option<&[String]>,
+    config: &toml::Value,
+) -> Result<Option<BTreeSet<String>>> {
+    let tags = match (include_tags, config.get("include_tags")) {
+        (Some(tags), _) => tags.to_vec(),
+        (None, Some(value)) => value
+            .clone()
+            .try_into::<Vec<String>>()
+            .context("`include_tags` must be a list of tags")?,
+        (None, None) => return Ok(None),
+    };
+    Ok(Some(
+        tags.into_iter().filter(|tag| !tag.is_empty()).collect(),
+    ))
+}
+
+// Write the bindings for `components`, using up to `jobs` threads
+//
+// The components are split into a batch for each thread and `write_bindings` is called once per
+// batch.  All errors are reported, rather than just the first one.  Once every batch is written,
+// `finalize` is called with all the components.  Returns the files which were written.
+fn write_bindings<T>(
+    binding_generator: &T,
+    settings: &GenerationSettings,
+    components: &[Component<T::Config>],
+    jobs: Option<usize>,
+) -> Result<Vec<Utf8PathBuf>>
+where
+    T: BindingGenerator + Sync + ?Sized,
+    T::Config: Sync,
+{
+    let jobs = jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .clamp(1, components.len().max(1));
+    if jobs == 1 {
+        let written_files = binding_generator.write_bindings(settings, components)?;
+        finalize_bindings(binding_generator, settings, components)?;
+        return Ok(written_files);
+    }
+
+    let batch_size = components.len().div_ceil(jobs);
+    let mut written_files = vec![];
+    let errors: Vec<_> = thread::scope(|scope| {
+        components
+            .chunks(batch_size)
+            .map(|batch| scope.spawn(|| binding_generator.write_bindings(settings, batch)))
+            // Start all the threads before joining any of them
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|handle| match handle.join() {
+                Ok(Ok(files)) => {
+                    written_files.extend(files);
+                    None
+                }
+                Ok(Err(e)) => Some(e),
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect()
+    });
+    match errors.len() {
+        0 => {
+            finalize_bindings(binding_generator, settings, components)?;
+            Ok(written_files)
+        }
+        1 => Err(errors.into_iter().next().unwrap()),
+        n => bail!(
+            "{n} errors writing bindings:\n{}",
+            errors
+                .iter()
+                .map(|e| format!("{e:#}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }
+}
+
+fn finalize_bindings<T>(
+    binding_generator: &T,
+    settings: &GenerationSettings,
+    components: &[Component<T::Config>],
+) -> Result<()>
+where
+    T: BindingGenerator + ?Sized,
+{
+    binding_generator
+        .finalize(settings, components)
+        .context("Failed to finalize the bindings")
+}
+
 // If `library_path` is a C dynamic library, return its name
+//
+// On Linux/macOS the `lib` prefix is added by the toolchain, so we strip it.  Windows DLLs and
+// wasm modules don't get a prefix, so any `lib` there is part of the library name and must be kept.
 pub fn calc_cdylib_name(library_path: &Utf8Path) -> Option<&str> {
-    let cdylib_extensions = [".so", ".dll", ".dylib"];
+    let cdylib_extensions = [
+        (".so", true),
+        (".dylib", true),
+        (".dll", false),
+        (".wasm", false),
+    ];
     let filename = library_path.file_name()?;
-    let filename = filename.strip_prefix("lib").unwrap_or(filename);
-    for ext in cdylib_extensions {
-        if let Some(f) = filename.strip_suffix(ext) {
+    for (ext, has_lib_prefix) in cdylib_extensions {
+        if let Some(f) = strip_library_extension(filename, ext) {
+            if has_lib_prefix {
+                return match f.strip_prefix("lib") {
+                    Some(name) if !name.is_empty() => Some(name),
+                    _ => Some(f),
+                };
+            }
             return Some(f);
         }
     }
     None
 }
 
+// Strip `ext` from a library file name, along with any version number.  Linux puts the version
+// after the extension (`libfoo.so.1.2.3`) and macOS puts it before (`libfoo.1.2.3.dylib`).
+fn strip_library_extension<'a>(filename: &'a str, ext: &str) -> Option<&'a str> {
+    let is_version = |s: &str| {
+        s.split('.')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+    };
+    match ext {
+        ".so" => match filename.split_once(".so.") {
+            Some((name, version)) if is_version(version) => Some(name),
+            _ => filename.strip_suffix(ext),
+        },
+        ".dylib" => filename.strip_suffix(ext).map(|name| {
+            let mut name = name;
+            while let Some((rest, version)) = name.rsplit_once('.') {
+                if !is_version(version) {
+                    break;
+                }
+                name = rest;
+            }
+            name
+        }),
+        _ => filename.strip_suffix(ext),
+    }
+}
+
 /// Find UniFFI components from a shared library file
 ///
 /// This method inspects the library file and creates [ComponentInterface] instances for each
@@ -101,10 +560,137 @@ pub fn find_components(
     library_path: &Utf8Path,
     config_supplier: &dyn BindgenCrateConfigSupplier,
 ) -> Result<Vec<Component<TomlTable>>> {
+    find_components_multi(&[library_path], config_supplier)
+}
+
+/// Find UniFFI components from several shared library files
+///
+/// This works like [find_components], but types from any of the libraries can be used as
+/// external types by the others.  It's an error for a crate to be in more than one library.
+pub fn find_components_multi(
+    library_paths: &[&Utf8Path],
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<Vec<Component<TomlTable>>> {
+    let (items, _) = extract_from_libraries(library_paths, true)?;
+    components_from_metadata(items, config_supplier, None, &[])
+}
+
+/// Dump the metadata from a shared library file as JSON
+///
+/// The metadata is grouped by crate, in the same way as when generating bindings, and the groups
+/// are sorted by crate name.  The JSON can be parsed back into a `Vec<MetadataGroup>`.
+pub fn metadata_json(library_path: &Utf8Path) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&metadata_groups(
+        library_path,
+    )?)?)
+}
+
+/// Extract the metadata from a shared library file and group it by crate
+///
+/// The groups are sorted by crate name.
+pub fn metadata_groups(library_path: &Utf8Path) -> Result<Vec<MetadataGroup>> {
     let items = macro_metadata::extract_from_library(library_path)?;
     let mut metadata_groups = create_metadata_groups(&items);
     group_metadata(&mut metadata_groups, items)?;
+    let mut metadata_groups: Vec<MetadataGroup> = metadata_groups.into_values().collect();
+    metadata_groups.sort_by(|a, b| a.namespace.crate_name.cmp(&b.namespace.crate_name));
+    Ok(metadata_groups)
+}
+
+// Extract the metadata from all libraries
+//
+// Also returns a map of crate names to the index of the library they were found in.
+pub(crate) fn extract_from_libraries(
+    library_paths: &[&Utf8Path],
+    version_check: bool,
+) -> Result<(Vec<Metadata>, HashMap<String, usize>)> {
+    let mut items = vec![];
+    let mut crate_libraries: HashMap<String, usize> = HashMap::new();
+    for (index, library_path) in library_paths.iter().enumerate() {
+        let library =
+            macro_metadata::extract_library_metadata(library_path).with_context(|| {
+                format!(
+                    "Failed to read the UniFFI metadata from {library_path}, \
+                 it may have been built with a different version of UniFFI"
+                )
+            })?;
+        if version_check {
+            macro_metadata::verify_library(library_path, &library)?;
+        }
+        macro_metadata::verify_reexported_crates(library_path, &library)?;
+        let library_items = library.items;
+        for crate_name in create_metadata_groups(&library_items).into_keys() {
+            if let Some(other_index) = crate_libraries.insert(crate_name.clone(), index) {
+                bail!(
+                    "Crate {crate_name} found in both {} and {library_path}",
+                    library_paths[other_index]
+                );
+            }
+        }
+        items.extend(library_items);
+    }
+    Ok((items, crate_libraries))
+}
+
+fn components_from_metadata(
+    items: Vec<Metadata>,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+    include_tags: Option<&[String]>,
+    config_file_overrides: &[&Utf8Path],
+) -> Result<Vec<Component<TomlTable>>> {
+    let mut metadata_groups = create_metadata_groups(&items);
+    group_metadata(&mut metadata_groups, items)?;
+    let mut included = HashMap::new();
+    for crate_name in metadata_groups.keys() {
+        let config = overridden_config_value(
+            config_supplier.get_toml(crate_name)?.unwrap_or_default(),
+            config_file_overrides,
+        )?;
+        if let Some(tags) = included_tags(include_tags, &config)? {
+            included.insert(crate_name.clone(), tags);
+        }
+    }
+    retain_included_tags(&mut metadata_groups, &included)?;
+    let mut udl_items = udl_metadata_groups(&metadata_groups, config_supplier)?;
+    // Types defined in UDL files don't have their docstrings in the library, so they're only
+    // taken from the library's items.
+    let docstrings = type_docstrings(metadata_groups.values().flat_map(|group| &group.items));
+
+    metadata_groups
+        .into_values()
+        .map(|mut group| {
+            let crate_name = &group.namespace.crate_name;
+            let mut ci = ComponentInterface::new(crate_name);
+            if let Some(metadata) = udl_items.remove(crate_name) {
+                // A namespace docstring from the UDL takes precedence over the crate docs.
+                if metadata.namespace_docstring.is_some() {
+                    group.namespace_docstring = None;
+                }
+                ci.add_metadata(metadata)?;
+            };
+            ci.add_metadata(group)?;
+            ci.set_external_type_docstrings(&docstrings);
+            let config = config_supplier
+                .get_toml(ci.crate_name())?
+                .unwrap_or_default();
+            let package = config_supplier.get_package_info(ci.crate_name());
+            Ok(Component {
+                ci,
+                config,
+                package,
+            })
+        })
+        .collect()
+}
 
+// The metadata from the UDL files of the crates in `metadata_groups`, keyed by crate name
+//
+// The external types in the UDL items are fixed up like the library's items were when they were
+// grouped, so the two can be combined or compared.
+pub(crate) fn udl_metadata_groups(
+    metadata_groups: &HashMap<String, MetadataGroup>,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<HashMap<String, MetadataGroup>> {
     // Collect and process all UDL from all groups at the start - the fixups
     // of external types makes this tricky to do as we finalize the group.
     let mut udl_items: HashMap<String, MetadataGroup> = HashMap::new();
@@ -116,34 +702,20 @@ pub fn find_components(
             metadata_group.items = metadata_group
                 .items
                 .into_iter()
-                .map(|item| fixup_external_type(item, &metadata_groups))
                 // some items are both in UDL and library metadata. For many that's fine but
                 // uniffi-traits aren't trivial to compare meaning we end up with dupes.
                 // We filter out such problematic items here.
                 .filter(|item| !matches!(item, Metadata::UniffiTrait { .. }))
-                .collect();
+                .map(|item| fixup_external_type(item, metadata_groups))
+                .collect::<Result<_>>()?;
             udl_items.insert(crate_name, metadata_group);
         };
     }
-
-    metadata_groups
-        .into_values()
-        .map(|group| {
-            let crate_name = &group.namespace.crate_name;
-            let mut ci = ComponentInterface::new(crate_name);
-            if let Some(metadata) = udl_items.remove(crate_name) {
-                ci.add_metadata(metadata)?;
-            };
-            ci.add_metadata(group)?;
-            let config = config_supplier
-                .get_toml(ci.crate_name())?
-                .unwrap_or_default();
-            Ok(Component { ci, config })
-        })
-        .collect()
+    Ok(udl_items)
 }
 
-fn load_udl_metadata(
+// The metadata from the UDL files of a crate, merged into one group, or `None` if it has none
+pub(crate) fn load_udl_metadata(
     group: &MetadataGroup,
     crate_name: &str,
     config_supplier: &dyn BindgenCrateConfigSupplier,
@@ -152,32 +724,322 @@ fn load_udl_metadata(
         .items
         .iter()
         .filter_map(|i| match i {
-            uniffi_meta::Metadata::UdlFile(meta) => Some(meta),
+            Metadata::UdlFile(meta) => Some(meta),
             _ => None,
         })
         .collect::<Vec<_>>();
-    // We only support 1 UDL file per crate, for no good reason!
-    match udl_items.len() {
-        0 => Ok(None),
-        1 => {
-            if udl_items[0].module_path != crate_name {
+    // A crate can split its interface across several UDL files, which all declare the same
+    // namespace.  Merge them into a single group.
+    let mut merged: Option<MetadataGroup> = None;
+    let mut item_sources: HashMap<String, &str> = HashMap::new();
+    for udl_item in udl_items {
+        if udl_item.module_path != crate_name {
+            bail!(
+                "UDL is for crate '{}' but this crate name is '{}'",
+                udl_item.module_path,
+                crate_name
+            );
+        }
+        let udl = config_supplier.get_udl_file(crate_name, udl_item)?;
+        // Included files are found relative to the UDL file, so need its path.
+        let udl_group = match config_supplier.get_udl_path(crate_name, udl_item) {
+            Some(udl_path) => {
+                crate::parse_udl_with_includes(&udl, &udl_path, crate_name, |path| {
+                    config_supplier.get_udl_include(crate_name, path)
+                })?
+                .0
+            }
+            None => uniffi_udl::parse_udl(&udl, crate_name)?,
+        };
+        for item in &udl_group.items {
+            let other = item_sources.insert(describe_item(item), &udl_item.file_stub);
+            if let Some(other) = other.filter(|other| *other != udl_item.file_stub) {
                 bail!(
-                    "UDL is for crate '{}' but this crate name is '{}'",
-                    udl_items[0].module_path,
-                    crate_name
+                    "{} is defined in both {other}.udl and {}.udl",
+                    describe_item(item),
+                    udl_item.file_stub
                 );
             }
-            let udl = config_supplier.get_udl(crate_name, &udl_items[0].file_stub)?;
-            let udl_group = uniffi_udl::parse_udl(&udl, crate_name)?;
-            Ok(Some(udl_group))
         }
-        n => bail!("{n} UDL files found for {crate_name}"),
+        match &mut merged {
+            None => merged = Some(udl_group),
+            Some(merged) => {
+                if merged.namespace != udl_group.namespace {
+                    bail!(
+                        "UDL files for crate '{crate_name}' use different namespaces: '{}' and '{}'",
+                        merged.namespace.name,
+                        udl_group.namespace.name
+                    );
+                }
+                if udl_group.namespace_docstring.is_some() {
+                    merged.namespace_docstring = udl_group.namespace_docstring;
+                }
+                merged.items.extend(udl_group.items);
+            }
+        }
     }
+    Ok(merged)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::sync::Mutex;
+
+    // Records the batches of crates it's asked to write, failing for crates named `bad_*`, and
+    // the crates it's asked to finalize, failing if any are named `unfinished_*`
+    #[derive(Default)]
+    struct RecordingGenerator {
+        batches: Mutex<Vec<Vec<String>>>,
+        finalized: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl BindingGenerator for RecordingGenerator {
+        type Config = ();
+
+        fn new_config(&self, _root_toml: &toml::Value) -> Result<()> {
+            Ok(())
+        }
+
+        fn update_component_configs(
+            &self,
+            _settings: &GenerationSettings,
+            _components: &mut Vec<Component<()>>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn write_bindings(
+            &self,
+            _settings: &GenerationSettings,
+            components: &[Component<()>],
+        ) -> Result<Vec<Utf8PathBuf>> {
+            let crate_names: Vec<_> = components
+                .iter()
+                .map(|c| c.ci.crate_name().to_string())
+                .collect();
+            self.batches.lock().unwrap().push(crate_names.clone());
+            match crate_names.iter().find(|name| name.starts_with("bad_")) {
+                Some(name) => bail!("can't write {name}"),
+                None => Ok(crate_names
+                    .iter()
+                    .map(|name| format!("/tmp/{name}.txt").into())
+                    .collect()),
+            }
+        }
+
+        fn finalize(
+            &self,
+            _settings: &GenerationSettings,
+            components: &[Component<()>],
+        ) -> Result<()> {
+            let crate_names: Vec<_> = components
+                .iter()
+                .map(|c| c.ci.crate_name().to_string())
+                .collect();
+            self.finalized.lock().unwrap().push(crate_names.clone());
+            match crate_names
+                .iter()
+                .find(|name| name.starts_with("unfinished_"))
+            {
+                Some(name) => bail!("can't finalize {name}"),
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn components(crate_names: &[&str]) -> Vec<Component<()>> {
+        crate_names
+            .iter()
+            .map(|name| Component {
+                ci: ComponentInterface::new(name),
+                config: (),
+                package: None,
+            })
+            .collect()
+    }
+
+    fn settings() -> GenerationSettings {
+        GenerationSettings {
+            out_dir: "/tmp".into(),
+            try_format_code: false,
+            cdylib: None,
+        }
+    }
+
+    #[test]
+    fn write_bindings_in_batches() {
+        let generator = RecordingGenerator::default();
+        let components = components(&["a", "b", "c", "d", "e"]);
+        let mut written_files =
+            write_bindings(&generator, &settings(), &components, Some(2)).unwrap();
+        written_files.sort();
+        assert_eq!(
+            written_files,
+            ["a", "b", "c", "d", "e"].map(|name| Utf8PathBuf::from(format!("/tmp/{name}.txt")))
+        );
+        let mut batches = generator.batches.into_inner().unwrap();
+        batches.sort();
+        assert_eq!(batches, vec![vec!["a", "b", "c"], vec!["d", "e"]]);
+        assert_eq!(
+            generator.finalized.into_inner().unwrap(),
+            vec![vec!["a", "b", "c", "d", "e"]]
+        );
+    }
+
+    #[test]
+    fn write_bindings_with_one_job() {
+        let generator = RecordingGenerator::default();
+        let components = components(&["a", "b", "c"]);
+        write_bindings(&generator, &settings(), &components, Some(1)).unwrap();
+        assert_eq!(
+            generator.batches.into_inner().unwrap(),
+            vec![vec!["a", "b", "c"]]
+        );
+    }
+
+    #[test]
+    fn write_bindings_reports_all_errors() {
+        let generator = RecordingGenerator::default();
+        let components = components(&["bad_a", "b", "bad_c"]);
+        let err = write_bindings(&generator, &settings(), &components, Some(3)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 errors writing bindings:\ncan't write bad_a\ncan't write bad_c"
+        );
+        // Nothing is finalized if the bindings couldn't be written
+        assert!(generator.finalized.into_inner().unwrap().is_empty());
+    }
+
+    #[test]
+    fn finalize_errors_are_reported_separately() {
+        let generator = RecordingGenerator::default();
+        let components = components(&["a", "unfinished_b"]);
+        let err = write_bindings(&generator, &settings(), &components, Some(2)).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "Failed to finalize the bindings: can't finalize unfinished_b"
+        );
+    }
+
+    // A component for `crate_name` with a function which takes `arg_type`
+    fn component_with_function(crate_name: &str, arg_type: Type) -> Component<()> {
+        let func = uniffi_meta::FnMetadata {
+            module_path: crate_name.into(),
+            name: "get_value".into(),
+            is_async: false,
+            inputs: vec![uniffi_meta::FnParamMetadata::simple("value", arg_type)],
+            return_type: None,
+            throws: None,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        };
+        let group = MetadataGroup {
+            namespace: uniffi_meta::NamespaceMetadata {
+                crate_name: crate_name.into(),
+                name: crate_name.into(),
+            },
+            namespace_docstring: None,
+            items: [func.into()].into(),
+        };
+        Component {
+            ci: ComponentInterface::from_metadata(group).unwrap(),
+            config: (),
+            package: None,
+        }
+    }
+
+    fn crate_set(crate_names: &[&str]) -> BTreeSet<String> {
+        crate_names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn omit_components_removes_crates() {
+        let components = omit_components(components(&["a", "b", "c"]), &crate_set(&["b"])).unwrap();
+        let crate_names: Vec<_> = components.iter().map(|c| c.ci.crate_name()).collect();
+        assert_eq!(crate_names, ["a", "c"]);
+    }
+
+    #[test]
+    fn omit_components_rejects_unknown_crates() {
+        let err =
+            omit_components(components(&["a", "b"]), &crate_set(&["a", "x", "y"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Can't omit `x`, `y` since they're not in the library (found: a, b)"
+        );
+    }
+
+    #[test]
+    fn omit_components_rejects_used_crates() {
+        let external = Type::External {
+            module_path: "b::types".into(),
+            name: "Value".into(),
+            namespace: "b".into(),
+            kind: uniffi_meta::ExternalKind::DataClass,
+            tagged: false,
+        };
+        let components = vec![
+            component_with_function("a", external),
+            component_with_function("b", Type::UInt32),
+        ];
+        let err = omit_components(components, &crate_set(&["b"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Types from omitted crates are used by the bindings for other crates:\n  \
+             crate `a`: function `get_value` uses `Value` from `b`\n\
+             Either stop omitting the crates or stop using their types."
+        );
+    }
+
+    #[test]
+    fn included_tags_prefers_the_argument() {
+        let config: toml::Value = toml::from_str(r#"include_tags = ["beta"]"#).unwrap();
+        let tags = |tags: &[&str]| Some(tags.iter().map(|tag| tag.to_string()).collect());
+        assert_eq!(included_tags(None, &config).unwrap(), tags(&["beta"]));
+        assert_eq!(
+            included_tags(Some(&["pro".into(), "".into()]), &config).unwrap(),
+            tags(&["pro"])
+        );
+        assert_eq!(
+            included_tags(None, &toml::Value::Table(Default::default())).unwrap(),
+            None
+        );
+        let config: toml::Value = toml::from_str(r#"include_tags = "pro""#).unwrap();
+        assert_eq!(
+            included_tags(None, &config).unwrap_err().to_string(),
+            "`include_tags` must be a list of tags"
+        );
+    }
+
+    #[test]
+    fn component_dependencies_are_transitive() {
+        let external = |crate_name: &str| Type::External {
+            module_path: format!("{crate_name}::types"),
+            name: "Value".into(),
+            namespace: crate_name.into(),
+            kind: uniffi_meta::ExternalKind::DataClass,
+            tagged: false,
+        };
+        // `c` uses `a`, which uses `b`.  Nothing uses `d`.
+        let components = vec![
+            component_with_function("a", external("b")),
+            component_with_function("b", Type::UInt32),
+            component_with_function("c", external("a")),
+            component_with_function("d", Type::UInt32),
+        ];
+        assert_eq!(
+            component_dependencies(&components, "a"),
+            crate_set(&["a", "b"])
+        );
+        assert_eq!(
+            component_dependencies(&components, "c"),
+            crate_set(&["a", "b", "c"])
+        );
+        assert_eq!(component_dependencies(&components, "d"), crate_set(&["d"]));
+    }
 
     #[test]
     fn calc_cdylib_name_is_correct() {
@@ -193,19 +1055,185 @@ mod test {
             "uniffi",
             calc_cdylib_name("/path/to/uniffi.dll".into()).unwrap()
         );
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/uniffi.wasm".into()).unwrap()
+        );
     }
 
-    /// Right now we unconditionally strip the `lib` prefix.
-    ///
-    /// Technically Windows DLLs do not start with a `lib` prefix,
-    /// but a library name could start with a `lib` prefix.
+    /// Windows DLLs don't get a `lib` prefix, so a library name could genuinely start with `lib`.
     /// On Linux/macOS this would result in a `liblibuniffi.{so,dylib}` file.
     #[test]
-    #[ignore] // Currently fails.
     fn calc_cdylib_name_is_correct_on_windows() {
         assert_eq!(
             "libuniffi",
             calc_cdylib_name("/path/to/libuniffi.dll".into()).unwrap()
         );
+        assert_eq!(
+            "libuniffi",
+            calc_cdylib_name("/path/to/liblibuniffi.so".into()).unwrap()
+        );
+        assert_eq!(
+            "libuniffi",
+            calc_cdylib_name("/path/to/liblibuniffi.dylib".into()).unwrap()
+        );
+    }
+
+    /// A crate named `lib` produces `liblib.so`
+    #[test]
+    fn calc_cdylib_name_for_crate_named_lib() {
+        assert_eq!(
+            "lib",
+            calc_cdylib_name("/path/to/liblib.so".into()).unwrap()
+        );
+        assert_eq!(
+            "lib",
+            calc_cdylib_name("/path/to/liblib.dylib".into()).unwrap()
+        );
+        assert_eq!("lib", calc_cdylib_name("/path/to/lib.dll".into()).unwrap());
+    }
+
+    /// Versioned libraries, as produced by packaging tools
+    #[test]
+    fn calc_cdylib_name_versioned() {
+        // Linux puts the version after the extension
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/libuniffi.so.1".into()).unwrap()
+        );
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/libuniffi.so.1.2.3".into()).unwrap()
+        );
+        // macOS puts it before the extension
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/libuniffi.1.dylib".into()).unwrap()
+        );
+        assert_eq!(
+            "uniffi",
+            calc_cdylib_name("/path/to/libuniffi.1.2.3.dylib".into()).unwrap()
+        );
+        // Windows DLLs don't have a version in the file name, and digits in the name are kept
+        assert_eq!(
+            "uniffi2",
+            calc_cdylib_name("/path/to/uniffi2.dll".into()).unwrap()
+        );
+        assert_eq!(
+            "uniffi2",
+            calc_cdylib_name("/path/to/libuniffi2.so".into()).unwrap()
+        );
+    }
+
+    #[test]
+    fn calc_cdylib_name_not_a_cdylib() {
+        assert_eq!(None, calc_cdylib_name("/path/to/libuniffi.a".into()));
+        assert_eq!(None, calc_cdylib_name("/path/to/uniffi.lib".into()));
+        assert_eq!(None, calc_cdylib_name("/path/to/libuniffi.rlib".into()));
+        assert_eq!(None, calc_cdylib_name("/path/to/uniffi.json".into()));
+        assert_eq!(None, calc_cdylib_name("/path/to/libuniffi.so.debug".into()));
+        assert_eq!(None, calc_cdylib_name("/path/to/libuniffi.so.1.a".into()));
+    }
+
+    // Supplies UDL from memory, as if the files were in `src/`
+    struct UdlSupplier(HashMap<&'static str, &'static str>);
+
+    impl BindgenCrateConfigSupplier for UdlSupplier {
+        fn get_udl(&self, _crate_name: &str, udl_name: &str) -> Result<String> {
+            Ok(self.0[udl_name].to_string())
+        }
+
+        fn get_udl_path(
+            &self,
+            _crate_name: &str,
+            udl_file: &uniffi_meta::UdlFile,
+        ) -> Option<Utf8PathBuf> {
+            Some(Utf8Path::new("src").join(format!("{}.udl", udl_file.file_stub)))
+        }
+
+        fn get_udl_include(&self, crate_name: &str, path: &Utf8Path) -> Result<String> {
+            assert_eq!(path.parent(), Some(Utf8Path::new("src")));
+            self.get_udl(crate_name, path.file_stem().unwrap())
+        }
+    }
+
+    fn udl_group(file_stubs: &[&str]) -> MetadataGroup {
+        MetadataGroup {
+            namespace: uniffi_meta::NamespaceMetadata {
+                crate_name: "crate_name".into(),
+                name: "test".into(),
+            },
+            namespace_docstring: None,
+            items: file_stubs
+                .iter()
+                .map(|file_stub| {
+                    Metadata::UdlFile(uniffi_meta::UdlFile {
+                        module_path: "crate_name".into(),
+                        namespace: "test".into(),
+                        file_stub: file_stub.to_string(),
+                        file_path: None,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn load_udl_metadata_merges_files() {
+        let supplier = UdlSupplier(HashMap::from([
+            ("core", "/// docs\nnamespace test { u32 get_a(); };"),
+            ("extra", "partial namespace test { u32 get_b(); };"),
+        ]));
+        let merged = load_udl_metadata(&udl_group(&["core", "extra"]), "crate_name", &supplier)
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.namespace.name, "test");
+        assert_eq!(merged.namespace_docstring.as_deref(), Some("docs"));
+        let names: Vec<_> = merged.items.iter().map(describe_item).collect();
+        assert_eq!(names, vec!["function `get_a`", "function `get_b`"]);
+    }
+
+    #[test]
+    fn load_udl_metadata_resolves_includes() {
+        let supplier = UdlSupplier(HashMap::from([
+            (
+                "core",
+                "namespace test { u32 get_a(); };\n// #include \"errors.udl\"",
+            ),
+            ("errors", "[Error]\nenum ApiError { \"Failed\" };"),
+            ("broken", "namespace test {};\n// #include \"core.udl\""),
+        ]));
+        let group = load_udl_metadata(&udl_group(&["core"]), "crate_name", &supplier)
+            .unwrap()
+            .unwrap();
+        let names: Vec<_> = group.items.iter().map(describe_item).collect();
+        assert_eq!(names, vec!["enum `ApiError`", "function `get_a`"]);
+
+        let err = load_udl_metadata(&udl_group(&["broken"]), "crate_name", &supplier).unwrap_err();
+        assert!(
+            err.to_string().ends_with("declares a namespace, but included UDL files use the namespace of the file which includes them"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_udl_metadata_rejects_conflicts() {
+        let supplier = UdlSupplier(HashMap::from([
+            ("core", "namespace test { u32 get_a(); };"),
+            ("extra", "partial namespace test { u32 get_a(); };"),
+            ("other", "partial namespace other { };"),
+        ]));
+        let err =
+            load_udl_metadata(&udl_group(&["core", "extra"]), "crate_name", &supplier).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "function `get_a` is defined in both core.udl and extra.udl"
+        );
+        let err =
+            load_udl_metadata(&udl_group(&["core", "other"]), "crate_name", &supplier).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "UDL files for crate 'crate_name' use different namespaces: 'test' and 'other'"
+        );
     }
 }
@@ -69,7 +69,9 @@ fn add_enum_to_ci(iface: &mut ComponentInterface, meta: EnumMetadata) -> anyhow:
 
 fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Result<()> {
     match item {
-        Metadata::Namespace(_) => unreachable!(),
+        Metadata::Namespace(_) | Metadata::NamespaceDocstring(_) | Metadata::ContractVersion(_) => {
+            unreachable!()
+        }
         Metadata::UdlFile(_) => (),
         Metadata::Func(meta) => {
             iface.add_function_definition(meta.into())?;
@@ -113,6 +115,12 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
         Metadata::TraitMethod(meta) => {
             iface.add_trait_method_meta(meta)?;
         }
+        Metadata::Constant(meta) => {
+            iface.add_constant_definition(meta.into())?;
+        }
+        Metadata::TraitConstant(meta) => {
+            iface.add_trait_constant_meta(meta)?;
+        }
         Metadata::CustomType(meta) => {
             iface.types.add_known_type(&Type::Custom {
                 module_path: meta.module_path.clone(),
@@ -12,19 +12,40 @@ use goblin::{
     pe::PE,
     Object,
 };
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use uniffi_meta::Metadata;
 
+/// Metadata extracted from a library file
+#[derive(Debug, Default)]
+pub struct LibraryMetadata {
+    pub items: Vec<Metadata>,
+    /// The API checksum functions exported by the library, which the foreign bindings call to
+    /// check that they match the library.
+    pub checksum_symbols: BTreeSet<String>,
+    /// Crates whose scaffolding was re-exported by `uniffi::reexport_scaffolding!()`.  The library
+    /// should contain the metadata for each of these.
+    pub reexported_crates: BTreeSet<String>,
+}
+
 /// Extract metadata written by the `uniffi::export` macro from a library file
 ///
 /// In addition to generating the scaffolding, that macro and also encodes the
 /// `uniffi_meta::Metadata` for the components which can be used to generate the bindings side of
 /// the interface.
 pub fn extract_from_library(path: &Utf8Path) -> anyhow::Result<Vec<Metadata>> {
+    Ok(extract_library_metadata(path)?.items)
+}
+
+/// Extract the metadata and the names of the checksum functions from a library file
+pub fn extract_library_metadata(path: &Utf8Path) -> anyhow::Result<LibraryMetadata> {
     extract_from_bytes(&fs::read(path)?)
 }
 
-fn extract_from_bytes(file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+fn extract_from_bytes(file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
+    // goblin doesn't understand WebAssembly, so we handle it ourselves.
+    if file_data.starts_with(WASM_MAGIC) {
+        return extract_from_wasm(file_data);
+    }
     match Object::parse(file_data)? {
         Object::Elf(elf) => extract_from_elf(elf, file_data),
         Object::PE(pe) => extract_from_pe(pe, file_data),
@@ -34,7 +55,7 @@ fn extract_from_bytes(file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
     }
 }
 
-pub fn extract_from_elf(elf: Elf<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+pub fn extract_from_elf(elf: Elf<'_>, file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
     let mut extracted = ExtractedItems::new();
     let iter = elf
         .syms
@@ -46,6 +67,9 @@ pub fn extract_from_elf(elf: Elf<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Me
             .strtab
             .get_at(sym.st_name)
             .context("Error getting symbol name")?;
+        if sym.st_shndx != 0 {
+            extracted.add_symbol(name);
+        }
         if is_metadata_symbol(name) {
             // Offset relative to the start of the section.
             let section_offset = sym.st_value - sh.sh_addr;
@@ -56,10 +80,11 @@ pub fn extract_from_elf(elf: Elf<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Me
     Ok(extracted.into_metadata())
 }
 
-pub fn extract_from_pe(pe: PE<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+pub fn extract_from_pe(pe: PE<'_>, file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
     let mut extracted = ExtractedItems::new();
     for export in pe.exports {
         if let Some(name) = export.name {
+            extracted.add_symbol(name);
             if is_metadata_symbol(name) {
                 extracted.extract_item(
                     name,
@@ -72,7 +97,7 @@ pub fn extract_from_pe(pe: PE<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metad
     Ok(extracted.into_metadata())
 }
 
-pub fn extract_from_mach(mach: Mach<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+pub fn extract_from_mach(mach: Mach<'_>, file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
     match mach {
         Mach::Binary(macho) => extract_from_macho(macho, file_data),
         // Multi-binary library, just extract the first one
@@ -83,7 +108,7 @@ pub fn extract_from_mach(mach: Mach<'_>, file_data: &[u8]) -> anyhow::Result<Vec
     }
 }
 
-pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
     let mut sections: Vec<Section> = Vec::new();
     for sects in macho.segments.sections() {
         sections.extend(sects.map(|r| r.expect("section").0));
@@ -94,6 +119,9 @@ pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<
     // Iterate through the symbols.  This picks up symbols from the .o files embedded in a Darwin
     // archive.
     for (name, nlist) in macho.symbols().flatten() {
+        if nlist.is_global() && nlist.get_type() == symbols::N_SECT {
+            extracted.add_symbol(name);
+        }
         // Check that the symbol:
         //   - Is global (exported)
         //   - Has type=N_SECT (it's regular data as opposed to something like
@@ -116,6 +144,7 @@ pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<
     // Iterate through the exports.  This picks up symbols from .dylib files.
     for export in macho.exports()? {
         let name = &export.name;
+        extracted.add_symbol(name);
         if is_metadata_symbol(name) {
             extracted.extract_item(name, file_data, export.offset as usize)?;
         }
@@ -126,31 +155,267 @@ pub fn extract_from_macho(macho: MachO<'_>, file_data: &[u8]) -> anyhow::Result<
 pub fn extract_from_archive(
     archive: Archive<'_>,
     file_data: &[u8],
-) -> anyhow::Result<Vec<Metadata>> {
+) -> anyhow::Result<LibraryMetadata> {
     // Store the names of archive members that have metadata symbols in them
     let mut members_to_check: HashSet<&str> = HashSet::new();
+    let mut extracted = ExtractedItems::new();
     for (member_name, _, symbols) in archive.summarize() {
         for name in symbols {
+            extracted.add_symbol(name);
             if is_metadata_symbol(name) {
                 members_to_check.insert(member_name);
             }
         }
     }
 
-    let mut items = vec![];
+    let mut library = extracted.into_metadata();
     for member_name in members_to_check {
-        items.append(
+        library.items.append(
             &mut extract_from_bytes(
                 archive
                     .extract(member_name, file_data)
                     .with_context(|| format!("Failed to extract archive member `{member_name}`"))?,
             )
-            .with_context(|| {
-                format!("Failed to extract data from archive member `{member_name}`")
-            })?,
+            .with_context(|| format!("Failed to extract data from archive member `{member_name}`"))?
+            .items,
         );
     }
-    Ok(items)
+    // The same item can be found in more than one member, for example when an archive bundles
+    // several copies of an object file.
+    library.items.sort();
+    library.items.dedup();
+    Ok(library)
+}
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+const WASM_IMPORT_SECTION: u8 = 2;
+const WASM_GLOBAL_SECTION: u8 = 6;
+const WASM_EXPORT_SECTION: u8 = 7;
+const WASM_DATA_SECTION: u8 = 11;
+const WASM_FUNCTION_KIND: u8 = 0;
+const WASM_GLOBAL_KIND: u8 = 3;
+
+/// Extract metadata from a WebAssembly module
+///
+/// `#[no_mangle]` statics are exported as globals which hold the address of the static in linear
+/// memory.  We find the exported metadata globals, then read the metadata from the data segment
+/// which initializes that address.
+pub fn extract_from_wasm(file_data: &[u8]) -> anyhow::Result<LibraryMetadata> {
+    let mut imported_global_count = 0;
+    // Initial values of the globals defined in the module, if they're constants
+    let mut globals: Vec<Option<u64>> = vec![];
+    // Exported metadata globals and their index
+    let mut metadata_exports: Vec<(&str, usize)> = vec![];
+    // Exported functions
+    let mut function_exports: Vec<&str> = vec![];
+    // Active data segments and the address they're loaded at
+    let mut data_segments: Vec<(u64, &[u8])> = vec![];
+
+    // Skip the magic number and version
+    let mut reader = WasmReader::new(file_data.get(8..).context("Truncated wasm header")?);
+    while !reader.is_empty() {
+        let section_id = reader.read_u8()?;
+        let size = reader.read_leb()? as usize;
+        let mut section = WasmReader::new(reader.read_bytes(size)?);
+        match section_id {
+            WASM_IMPORT_SECTION => {
+                for _ in 0..section.read_leb()? {
+                    section.read_name()?;
+                    section.read_name()?;
+                    match section.read_u8()? {
+                        // function: type index
+                        WASM_FUNCTION_KIND => {
+                            section.read_leb()?;
+                        }
+                        // table: reftype and limits
+                        1 => {
+                            section.read_u8()?;
+                            section.read_limits()?;
+                        }
+                        // memory: limits
+                        2 => section.read_limits()?,
+                        // global: valtype and mutability
+                        WASM_GLOBAL_KIND => {
+                            section.read_bytes(2)?;
+                            imported_global_count += 1;
+                        }
+                        // tag: attribute and type index
+                        4 => {
+                            section.read_u8()?;
+                            section.read_leb()?;
+                        }
+                        kind => bail!("Unknown wasm import kind: {kind}"),
+                    }
+                }
+            }
+            WASM_GLOBAL_SECTION => {
+                for _ in 0..section.read_leb()? {
+                    // valtype and mutability
+                    section.read_bytes(2)?;
+                    globals.push(section.read_const_expr()?);
+                }
+            }
+            WASM_EXPORT_SECTION => {
+                for _ in 0..section.read_leb()? {
+                    let name = section.read_name()?;
+                    let kind = section.read_u8()?;
+                    let index = section.read_leb()? as usize;
+                    if kind == WASM_GLOBAL_KIND && is_metadata_symbol(name) {
+                        metadata_exports.push((name, index));
+                    } else if kind == WASM_FUNCTION_KIND {
+                        function_exports.push(name);
+                    }
+                }
+            }
+            WASM_DATA_SECTION => {
+                for _ in 0..section.read_leb()? {
+                    let offset = match section.read_leb()? {
+                        // active segment for memory 0
+                        0 => section.read_const_expr()?,
+                        // passive segment, not loaded at any address
+                        1 => None,
+                        // active segment with an explicit memory index
+                        2 => {
+                            section.read_leb()?;
+                            section.read_const_expr()?
+                        }
+                        flags => bail!("Unknown wasm data segment flags: {flags}"),
+                    };
+                    let size = section.read_leb()? as usize;
+                    let data = section.read_bytes(size)?;
+                    if let Some(offset) = offset {
+                        data_segments.push((offset, data));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut extracted = ExtractedItems::new();
+    for name in function_exports {
+        extracted.add_symbol(name);
+    }
+    for (name, index) in metadata_exports {
+        let address = index
+            .checked_sub(imported_global_count)
+            .and_then(|i| globals.get(i).copied().flatten())
+            .with_context(|| format!("Error getting the address of `{name}`"))?;
+        let (segment_address, data) = data_segments
+            .iter()
+            .find(|(start, data)| (*start..*start + data.len() as u64).contains(&address))
+            .with_context(|| format!("Error finding the data for `{name}`"))?;
+        extracted.extract_item(name, data, (address - segment_address) as usize)?;
+    }
+    Ok(extracted.into_metadata())
+}
+
+/// Minimal reader for the parts of the wasm binary format that we care about
+struct WasmReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> WasmReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    fn read_bytes(&mut self, size: usize) -> anyhow::Result<&'a [u8]> {
+        if self.buf.len() < size {
+            bail!("Unexpected end of wasm data");
+        }
+        let bytes;
+        (bytes, self.buf) = self.buf.split_at(size);
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    // Read an unsigned LEB128 integer
+    fn read_leb(&mut self) -> anyhow::Result<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        bail!("Invalid LEB128 integer in wasm data")
+    }
+
+    // Read a signed LEB128 integer
+    fn read_sleb(&mut self) -> anyhow::Result<i64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as i64) << shift;
+            if byte & 0x80 == 0 {
+                if shift + 7 < 64 && byte & 0x40 != 0 {
+                    // sign extend
+                    value |= -1 << (shift + 7);
+                }
+                return Ok(value);
+            }
+        }
+        bail!("Invalid LEB128 integer in wasm data")
+    }
+
+    fn read_name(&mut self) -> anyhow::Result<&'a str> {
+        let size = self.read_leb()? as usize;
+        std::str::from_utf8(self.read_bytes(size)?).context("Invalid name in wasm data")
+    }
+
+    fn read_limits(&mut self) -> anyhow::Result<()> {
+        let flags = self.read_u8()?;
+        self.read_leb()?;
+        if flags & 0x01 != 0 {
+            self.read_leb()?;
+        }
+        Ok(())
+    }
+
+    // Read a constant expression, returning its value if it's a single integer constant
+    fn read_const_expr(&mut self) -> anyhow::Result<Option<u64>> {
+        let mut value = None;
+        let mut instruction_count = 0;
+        loop {
+            match self.read_u8()? {
+                // end
+                0x0b => break,
+                // i32.const
+                0x41 => value = Some(self.read_sleb()? as i32 as u32 as u64),
+                // i64.const
+                0x42 => value = Some(self.read_sleb()? as u64),
+                // f32.const / f64.const
+                0x43 => {
+                    self.read_bytes(4)?;
+                }
+                0x44 => {
+                    self.read_bytes(8)?;
+                }
+                // global.get / ref.func
+                0x23 | 0xd2 => {
+                    self.read_leb()?;
+                }
+                // ref.null
+                0xd0 => {
+                    self.read_u8()?;
+                }
+                // extended constant expressions: integer add/sub/mul
+                0x6a..=0x6c | 0x7c..=0x7e => (),
+                opcode => bail!("Unsupported instruction in wasm constant expression: {opcode:#x}"),
+            }
+            instruction_count += 1;
+        }
+        Ok(value.filter(|_| instruction_count == 1))
+    }
 }
 
 /// Container for extracted metadata items
@@ -160,6 +425,8 @@ struct ExtractedItems {
     /// symbol names for the extracted items, we use this to ensure that we don't extract the same
     /// symbol twice
     names: HashSet<String>,
+    checksum_symbols: BTreeSet<String>,
+    reexported_crates: BTreeSet<String>,
 }
 
 impl ExtractedItems {
@@ -185,8 +452,24 @@ impl ExtractedItems {
         Ok(())
     }
 
-    fn into_metadata(self) -> Vec<Metadata> {
-        self.items
+    // Record a symbol defined by the library, if it's a checksum function or a re-export hack
+    // function
+    fn add_symbol(&mut self, name: &str) {
+        // Skip the "_" char that Darwin prepends, if present
+        let name = name.strip_prefix('_').unwrap_or(name);
+        if name.starts_with("uniffi_") && name.contains("_checksum_") {
+            self.checksum_symbols.insert(name.to_string());
+        } else if let Some(crate_name) = name.strip_suffix("_uniffi_reexport_hack") {
+            self.reexported_crates.insert(crate_name.to_string());
+        }
+    }
+
+    fn into_metadata(self) -> LibraryMetadata {
+        LibraryMetadata {
+            items: self.items,
+            checksum_symbols: self.checksum_symbols,
+            reexported_crates: self.reexported_crates,
+        }
     }
 }
 
@@ -195,3 +478,119 @@ fn is_metadata_symbol(name: &str) -> bool {
     let name = name.strip_prefix('_').unwrap_or(name);
     name.starts_with("UNIFFI_META")
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::NamespaceMetadata;
+
+    fn leb(mut value: u64) -> Vec<u8> {
+        let mut bytes = vec![];
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn section(id: u8, contents: Vec<u8>) -> Vec<u8> {
+        [vec![id], leb(contents.len() as u64), contents].concat()
+    }
+
+    fn name(name: &str) -> Vec<u8> {
+        [leb(name.len() as u64), name.as_bytes().to_vec()].concat()
+    }
+
+    // `i32.const <value>; end`.  Values are kept below 64 so the signed/unsigned LEB128
+    // encodings match.
+    fn i32_const(value: u64) -> Vec<u8> {
+        [vec![0x41], leb(value), vec![0x0b]].concat()
+    }
+
+    // Build a wasm module with a single `UNIFFI_META_NAMESPACE_NS` static at address 40
+    fn wasm_module(imported_globals: u64) -> Vec<u8> {
+        let metadata = [
+            // `codes::NAMESPACE`
+            vec![6],
+            vec![5],
+            b"crate".to_vec(),
+            vec![2],
+            b"ns".to_vec(),
+        ]
+        .concat();
+        let data = [vec![0xff; 8], metadata].concat();
+
+        let mut imports = leb(imported_globals);
+        for i in 0..imported_globals {
+            imports.extend(name("env"));
+            imports.extend(name(&format!("global{i}")));
+            imports.extend([WASM_GLOBAL_KIND, 0x7f, 0x00]);
+        }
+        let globals = [
+            leb(2),
+            vec![0x7f, 0x00],
+            i32_const(8),
+            vec![0x7f, 0x00],
+            i32_const(40),
+        ]
+        .concat();
+        let exports = [
+            leb(2),
+            name("memory"),
+            vec![2, 0],
+            name("UNIFFI_META_NAMESPACE_NS"),
+            vec![WASM_GLOBAL_KIND],
+            leb(imported_globals + 1),
+        ]
+        .concat();
+        let data_segments = [
+            leb(2),
+            // a passive segment, which should be ignored
+            leb(1),
+            leb(4),
+            vec![0; 4],
+            leb(0),
+            i32_const(32),
+            leb(data.len() as u64),
+            data,
+        ]
+        .concat();
+
+        [
+            WASM_MAGIC.to_vec(),
+            vec![1, 0, 0, 0],
+            section(WASM_IMPORT_SECTION, imports),
+            section(WASM_GLOBAL_SECTION, globals),
+            section(WASM_EXPORT_SECTION, exports),
+            // a custom section, which should be skipped
+            section(0, name("producers")),
+            section(WASM_DATA_SECTION, data_segments),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_extract_from_wasm() {
+        for imported_globals in [0, 2] {
+            assert_eq!(
+                extract_from_bytes(&wasm_module(imported_globals))
+                    .unwrap()
+                    .items,
+                vec![Metadata::Namespace(NamespaceMetadata {
+                    crate_name: "crate".to_string(),
+                    name: "ns".to_string(),
+                })]
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_from_truncated_wasm() {
+        let module = wasm_module(0);
+        assert!(extract_from_bytes(&module[..module.len() - 4]).is_err());
+    }
+}
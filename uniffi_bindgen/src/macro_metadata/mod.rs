@@ -8,9 +8,11 @@ use camino::Utf8Path;
 
 mod ci;
 mod extract;
+mod verify;
 
 pub use ci::{add_group_to_ci, add_to_ci};
-pub use extract::extract_from_library;
+pub use extract::{extract_from_library, extract_library_metadata, LibraryMetadata};
+pub use verify::{verify_library, verify_reexported_crates};
 
 pub fn add_to_ci_from_library(
     iface: &mut ComponentInterface,
@@ -0,0 +1,299 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Check that a library matches this version of `uniffi-bindgen`
+//!
+//! The foreign bindings check the contract version and the API checksums when they're loaded,
+//! but failing there means the mismatch is only found when the app is run.  These checks catch
+//! the same problems when the bindings are generated.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Result};
+use camino::Utf8Path;
+use uniffi_meta::{Metadata, UNIFFI_CONTRACT_VERSION};
+
+use super::LibraryMetadata;
+
+/// Check the contract version and API checksum functions of an extracted library
+pub fn verify_library(library_path: &Utf8Path, library: &LibraryMetadata) -> Result<()> {
+    verify_contract_versions(library_path, &library.items)?;
+    verify_checksum_symbols(library_path, library)
+}
+
+fn verify_contract_versions(library_path: &Utf8Path, items: &[Metadata]) -> Result<()> {
+    // Crates that were built with a different contract version, grouped by that version
+    let mut mismatches: BTreeMap<u32, BTreeSet<&str>> = BTreeMap::new();
+    // Crates without a contract version.  Every crate with a namespace should have one, so these
+    // were built with a version of UniFFI from before it was exported as metadata.
+    let mut missing: BTreeSet<&str> = items
+        .iter()
+        .filter_map(|item| match item {
+            Metadata::Namespace(meta) => Some(meta.crate_name.as_str()),
+            _ => None,
+        })
+        .collect();
+    for item in items {
+        if let Metadata::ContractVersion(meta) = item {
+            missing.remove(crate_name(&meta.module_path));
+            if meta.version != UNIFFI_CONTRACT_VERSION {
+                mismatches
+                    .entry(meta.version)
+                    .or_default()
+                    .insert(crate_name(&meta.module_path));
+            }
+        }
+    }
+    if mismatches.is_empty() && missing.is_empty() {
+        return Ok(());
+    }
+    let crate_list = |crates: BTreeSet<&str>| {
+        crates
+            .into_iter()
+            .map(|c| format!("`{c}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut details = mismatches
+        .into_iter()
+        .map(|(version, crates)| format!("  contract version {version}: {}", crate_list(crates)))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        details.push(format!(
+            "  an unknown contract version, from an older version of UniFFI: {}",
+            crate_list(missing)
+        ));
+    }
+    let details = details.join("\n");
+    bail!(
+        "UniFFI contract version mismatch: uniffi-bindgen uses contract version \
+         {UNIFFI_CONTRACT_VERSION}, but {library_path} was built with:\n{details}\n\
+         Use the same version of UniFFI to build the library and to generate the bindings, \
+         or pass `--no-version-check` to skip this check."
+    )
+}
+
+fn verify_checksum_symbols(library_path: &Utf8Path, library: &LibraryMetadata) -> Result<()> {
+    // Missing checksum functions, grouped by crate
+    let mut missing: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for item in &library.items {
+        let (module_path, symbol_name) = match item {
+            Metadata::Func(meta) if meta.checksum.is_some() => {
+                (&meta.module_path, meta.checksum_symbol_name())
+            }
+            Metadata::Constructor(meta) if meta.checksum.is_some() => {
+                (&meta.module_path, meta.checksum_symbol_name())
+            }
+            Metadata::Method(meta) if meta.checksum.is_some() => {
+                (&meta.module_path, meta.checksum_symbol_name())
+            }
+            Metadata::TraitMethod(meta) if meta.checksum.is_some() => {
+                (&meta.module_path, meta.checksum_symbol_name())
+            }
+            _ => continue,
+        };
+        if !library.checksum_symbols.contains(&symbol_name) {
+            missing
+                .entry(crate_name(module_path))
+                .or_default()
+                .push(symbol_name);
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let details = missing
+        .into_iter()
+        .map(|(crate_name, symbols)| format!("  crate `{crate_name}`: {}", symbols.join(", ")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    bail!(
+        "UniFFI API checksum functions are missing from {library_path}:\n{details}\n\
+         Make sure the library was built with the same version of UniFFI as uniffi-bindgen, \
+         or pass `--no-version-check` to skip this check."
+    )
+}
+
+/// Check that the library contains the metadata for each crate it re-exports the scaffolding for
+///
+/// This runs regardless of `--no-version-check`, since a missing crate means its bindings would
+/// silently be left out.
+pub fn verify_reexported_crates(library_path: &Utf8Path, library: &LibraryMetadata) -> Result<()> {
+    let namespaces: BTreeSet<&str> = library
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Metadata::Namespace(meta) => Some(meta.crate_name.as_str()),
+            _ => None,
+        })
+        .collect();
+    let missing = library
+        .reexported_crates
+        .iter()
+        .filter(|crate_name| !namespaces.contains(crate_name.as_str()))
+        .map(|crate_name| format!("`{crate_name}`"))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "{library_path} re-exports the scaffolding of {}, but the UniFFI metadata for them is \
+         missing from the library.\n\
+         Make sure the library is built from the crate that calls \
+         `uniffi::reexport_scaffolding!()`, and that it calls the macro once for each dependency.",
+        missing.join(", ")
+    )
+}
+
+fn crate_name(module_path: &str) -> &str {
+    module_path.split("::").next().unwrap_or(module_path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{ContractVersionMetadata, FnMetadata, NamespaceMetadata, Type};
+
+    fn contract_version(module_path: &str, version: u32) -> Metadata {
+        ContractVersionMetadata {
+            module_path: module_path.into(),
+            version,
+        }
+        .into()
+    }
+
+    fn func(module_path: &str, name: &str) -> Metadata {
+        FnMetadata {
+            module_path: module_path.into(),
+            name: name.into(),
+            is_async: false,
+            inputs: vec![],
+            return_type: Some(Type::UInt32),
+            throws: None,
+            checksum: Some(0),
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        }
+        .into()
+    }
+
+    fn library(items: Vec<Metadata>, checksum_symbols: &[&str]) -> LibraryMetadata {
+        LibraryMetadata {
+            items,
+            checksum_symbols: checksum_symbols.iter().map(|s| s.to_string()).collect(),
+            reexported_crates: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn test_matching_library() {
+        let lib = library(
+            vec![
+                contract_version("crate_a", UNIFFI_CONTRACT_VERSION),
+                func("crate_a", "get_value"),
+            ],
+            &["uniffi_crate_a_checksum_func_get_value"],
+        );
+        verify_library("libtest.so".into(), &lib).unwrap();
+    }
+
+    #[test]
+    fn test_contract_version_mismatch() {
+        let lib = library(
+            vec![
+                contract_version("crate_a", UNIFFI_CONTRACT_VERSION),
+                contract_version("crate_b", 1),
+                contract_version("crate_c::submodule", 1),
+            ],
+            &[],
+        );
+        let message = verify_library("libtest.so".into(), &lib)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains(&format!(
+            "uniffi-bindgen uses contract version {UNIFFI_CONTRACT_VERSION}, \
+             but libtest.so was built with:\n"
+        )));
+        assert!(message.contains("  contract version 1: `crate_b`, `crate_c`\n"));
+        assert!(!message.contains("crate_a"));
+        assert!(message.contains("--no-version-check"));
+    }
+
+    #[test]
+    fn test_missing_contract_version() {
+        let namespace = |crate_name: &str| -> Metadata {
+            NamespaceMetadata {
+                crate_name: crate_name.into(),
+                name: crate_name.into(),
+            }
+            .into()
+        };
+        let lib = library(
+            vec![
+                namespace("crate_a"),
+                contract_version("crate_a", UNIFFI_CONTRACT_VERSION),
+                namespace("crate_b"),
+                namespace("crate_c"),
+                contract_version("crate_c", 1),
+            ],
+            &[],
+        );
+        let message = verify_library("libtest.so".into(), &lib)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("  contract version 1: `crate_c`\n"));
+        assert!(message.contains(
+            "  an unknown contract version, from an older version of UniFFI: `crate_b`\n"
+        ));
+        assert!(!message.contains("crate_a"));
+    }
+
+    #[test]
+    fn test_missing_checksum_symbols() {
+        let mut no_checksum = func("crate_b", "no_checksum");
+        if let Metadata::Func(meta) = &mut no_checksum {
+            meta.checksum = None;
+        }
+        let lib = library(
+            vec![
+                func("crate_a", "get_value"),
+                func("crate_b", "get_value"),
+                func("crate_b", "set_value"),
+                no_checksum,
+            ],
+            &["uniffi_crate_a_checksum_func_get_value"],
+        );
+        let message = verify_library("libtest.so".into(), &lib)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains(
+            "  crate `crate_b`: uniffi_crate_b_checksum_func_get_value, \
+             uniffi_crate_b_checksum_func_set_value\n"
+        ));
+        assert!(!message.contains("crate_a"));
+        assert!(!message.contains("no_checksum"));
+    }
+
+    #[test]
+    fn test_missing_reexported_crates() {
+        let namespace = |crate_name: &str| -> Metadata {
+            NamespaceMetadata {
+                crate_name: crate_name.into(),
+                name: crate_name.into(),
+            }
+            .into()
+        };
+        let mut lib = library(vec![namespace("wrapper"), namespace("crate_a")], &[]);
+        lib.reexported_crates = ["crate_a".to_string()].into();
+        verify_reexported_crates("libtest.so".into(), &lib).unwrap();
+
+        lib.reexported_crates = ["crate_a", "crate_b", "crate_c"].map(String::from).into();
+        let message = verify_reexported_crates("libtest.so".into(), &lib)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("re-exports the scaffolding of `crate_b`, `crate_c`,"));
+        assert!(message.contains("uniffi::reexport_scaffolding!()"));
+    }
+}
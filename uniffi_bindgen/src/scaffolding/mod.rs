@@ -14,10 +14,34 @@ use heck::{ToShoutySnakeCase, ToSnakeCase};
 pub struct RustScaffolding<'a> {
     ci: &'a ComponentInterface,
     udl_base_name: &'a str,
+    // The UDL extends a namespace declared in another UDL file, whose scaffolding sets up the
+    // crate-level items.
+    partial_namespace: bool,
+    // The path of the UDL file relative to the crate root, or empty if it's not known.
+    udl_path: String,
 }
 impl<'a> RustScaffolding<'a> {
     pub fn new(ci: &'a ComponentInterface, udl_base_name: &'a str) -> Self {
-        Self { ci, udl_base_name }
+        Self {
+            ci,
+            udl_base_name,
+            partial_namespace: false,
+            udl_path: String::new(),
+        }
+    }
+
+    pub fn udl_path(self, udl_path: Option<String>) -> Self {
+        Self {
+            udl_path: udl_path.unwrap_or_default(),
+            ..self
+        }
+    }
+
+    pub fn partial_namespace(self, partial_namespace: bool) -> Self {
+        Self {
+            partial_namespace,
+            ..self
+        }
     }
 }
 mod filters {
@@ -33,12 +57,19 @@ mod filters {
             Type::UInt32 => "u32".into(),
             Type::Int64 => "i64".into(),
             Type::UInt64 => "u64".into(),
+            Type::Int128 => "i128".into(),
+            Type::UInt128 => "u128".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             Type::String => "::std::string::String".into(),
             Type::Bytes => "::std::vec::Vec<u8>".into(),
             Type::Timestamp => "::std::time::SystemTime".into(),
+            Type::Date => "::uniffi::deps::chrono::NaiveDate".into(),
+            Type::Json => "::uniffi::deps::serde_json::Value".into(),
+            Type::Path => "::std::path::PathBuf".into(),
+            Type::Url => "::uniffi::deps::url::Url".into(),
             Type::Duration => "::std::time::Duration".into(),
             Type::Enum { name, .. } | Type::Record { name, .. } => format!("r#{name}"),
             Type::Object { name, imp, .. } => {
@@ -68,10 +99,34 @@ mod filters {
                 kind: ExternalKind::Trait,
                 ..
             } => format!("::std::sync::Arc<dyn r#{name}>"),
+            Type::External {
+                name,
+                kind: ExternalKind::CallbackInterface,
+                ..
+            } => format!("Box<dyn r#{name}>"),
             Type::External { name, .. } => format!("r#{name}"),
         })
     }
 
+    // The type of a `[ByRef]` argument, without the `&`.  Like the arguments of functions exported
+    // with proc-macros, references to trait interfaces are `&dyn Trait` rather than a reference to
+    // the `Arc`.
+    pub fn by_ref_type_rs(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Object { name, imp, .. } if imp.is_trait_interface() => imp.rust_name_for(name),
+            _ => type_rs(type_)?,
+        })
+    }
+
+    // The value of the variant at `index`, as an `i128` literal to compare it with the Rust enum.
+    pub fn variant_discr_rs(e: &Enum, index: &usize) -> Result<String, askama::Error> {
+        Ok(match e.variant_discr(*index).expect("invalid index") {
+            Literal::UInt(v, ..) => format!("{v}_i128"),
+            Literal::Int(v, ..) => format!("{v}_i128"),
+            _ => unreachable!("discriminants are always integers"),
+        })
+    }
+
     // Turns a `crate-name` into the `crate_name` the .rs code needs to specify.
     pub fn crate_name_rs(nm: &str) -> Result<String, askama::Error> {
         Ok(format!("r#{}", nm.to_string().to_snake_case()))
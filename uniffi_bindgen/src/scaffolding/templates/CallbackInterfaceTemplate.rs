@@ -1,10 +1,10 @@
 #[::uniffi::export_for_udl(callback_interface)]
 pub trait r#{{ cbi.name() }} {
     {%- for meth in cbi.methods() %}
-    fn r#{{ meth.name() }}(
+    {% if meth.is_async() %}async {% endif %}fn r#{{ meth.name() }}(
         {% if meth.takes_self_by_arc()%}self: Arc<Self>{% else %}&self{% endif %},
         {%- for arg in meth.arguments() %}
-        r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
+        r#{{ arg.name() }}: {% if arg.by_ref() %}&{{ arg.as_type().borrow()|by_ref_type_rs }}{% else %}{{ arg.as_type().borrow()|type_rs }}{% endif %},
         {%- endfor %}
     )
     {%- match (meth.return_type(), meth.throws_type()) %}
@@ -15,3 +15,13 @@ enum r#{{ e.name() }} {
     },
     {%- endfor %}
 }
+{%- if e.variant_discr_type().is_some() %}
+
+// The values given to the variants in the UDL must be the values of the Rust enum.
+{%- for variant in e.variants() %}
+const _: () = ::std::assert!(
+    r#{{ e.name() }}::r#{{ variant.name() }} as i128 == {{ e|variant_discr_rs(loop.index0) }},
+    "the value of `{{ e.name() }}::{{ variant.name() }}` in the UDL doesn't match the Rust enum",
+);
+{%- endfor %}
+{%- endif %}
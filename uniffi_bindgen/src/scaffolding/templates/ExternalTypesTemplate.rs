@@ -12,6 +12,8 @@
 ::uniffi::ffi_converter_arc_forward!(r#{{ name }}, ::{{ crate_name|crate_name_rs }}::UniFfiTag, crate::UniFfiTag);
 {%- when ExternalKind::Trait %}
 ::uniffi::ffi_converter_arc_forward!(dyn r#{{ name }}, ::{{ crate_name|crate_name_rs }}::UniFfiTag, crate::UniFfiTag);
+{%- when ExternalKind::CallbackInterface %}
+{#- Callback interfaces can only be used as external types via proc-macros, which don't tag their FfiConverters #}
 {%- endmatch %}
 {% endif %}
 {%- endfor %}
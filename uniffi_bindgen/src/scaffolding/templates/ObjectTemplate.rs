@@ -9,7 +9,7 @@ pub trait r#{{ obj.name() }} {
     {% if meth.is_async() %}async {% endif %}fn r#{{ meth.name() }}(
         {% if meth.takes_self_by_arc()%}self: Arc<Self>{% else %}&self{% endif %},
         {%- for arg in meth.arguments() %}
-        r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
+        r#{{ arg.name() }}: {% if arg.by_ref() %}&{{ arg.as_type().borrow()|by_ref_type_rs }}{% else %}{{ arg.as_type().borrow()|type_rs }}{% endif %},
         {%- endfor %}
     )
     {%- match (meth.return_type(), meth.throws_type()) %}
@@ -31,18 +31,29 @@ pub trait r#{{ obj.name() }} {
 #[uniffi::export(Hash)]
 {%          when UniffiTrait::Eq { eq, ne }%}
 #[uniffi::export(Eq)]
+{%          when UniffiTrait::Ord { cmp }%}
+#[uniffi::export(Ord)]
 {%      endmatch %}
 {% endfor %}
 #[::uniffi::udl_derive(Object)]
 struct {{ obj.rust_name() }} { }
 
+{%- if !obj.constructors().is_empty() %}
+// Lets `#[uniffi::constructor]` reject async constructors for objects with UDL constructors.
+#[doc(hidden)]
+impl {{ obj.rust_name() }} {
+    #[allow(dead_code)]
+    pub(crate) const UNIFFI_HAS_UDL_CONSTRUCTORS: bool = true;
+}
+{%- endif %}
+
 {%- for cons in obj.constructors() %}
 #[::uniffi::export_for_udl]
 impl {{ obj.rust_name() }} {
     #[uniffi::constructor]
     pub {% if cons.is_async() %}async {% endif %}fn r#{{ cons.name() }}(
         {%- for arg in cons.arguments() %}
-        r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
+        r#{{ arg.name() }}: {% if arg.by_ref() %}&{{ arg.as_type().borrow()|by_ref_type_rs }}{% else %}{{ arg.as_type().borrow()|type_rs }}{% endif %},
         {%- endfor %}
     )
     {%- match (cons.return_type(), cons.throws_type()) %}
@@ -63,7 +74,7 @@ impl {{ obj.rust_name() }} {
     pub {% if meth.is_async() %}async {% endif %}fn r#{{ meth.name() }}(
         {% if meth.takes_self_by_arc()%}self: Arc<Self>{% else %}&self{% endif %},
         {%- for arg in meth.arguments() %}
-        r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
+        r#{{ arg.name() }}: {% if arg.by_ref() %}&{{ arg.as_type().borrow()|by_ref_type_rs }}{% else %}{{ arg.as_type().borrow()|type_rs }}{% endif %},
         {%- endfor %}
     )
     {%- match (meth.return_type(), meth.throws_type()) %}
@@ -3,13 +3,15 @@
 /// See `uniffi_bindgen::macro_metadata` for how this is used.
 
 // ditto for info about the UDL which spawned us.
-{%- let const_udl_var = "UNIFFI_META_CONST_UDL_{}"|format(ci.namespace().to_shouty_snake_case()) %}
-{%- let static_udl_var = "UNIFFI_META_UDL_{}"|format(ci.namespace().to_shouty_snake_case()) %}
+{%- let udl_var_suffix = "{}_{}"|format(ci.namespace().to_shouty_snake_case(), udl_base_name.to_shouty_snake_case()) %}
+{%- let const_udl_var = "UNIFFI_META_CONST_UDL_{}"|format(udl_var_suffix) %}
+{%- let static_udl_var = "UNIFFI_META_UDL_{}"|format(udl_var_suffix) %}
 
 const {{ const_udl_var }}: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::UDL_FILE)
     .concat_str("{{ ci.types.namespace.crate_name }}")
     .concat_str("{{ ci.namespace() }}")
-    .concat_str("{{ udl_base_name }}");
+    .concat_str("{{ udl_base_name }}")
+    .concat_long_str({{ "{:?}"|format(udl_path) }});
 
 #[doc(hidden)]
 #[no_mangle]
@@ -2,7 +2,9 @@
 // Trust me, you don't want to mess with it!
 {% import "macros.rs" as rs %}
 
+{%- if !partial_namespace %}
 ::uniffi::setup_scaffolding!("{{ ci.namespace() }}");
+{%- endif %}
 
 {% include "UdlMetadata.rs" %}
 
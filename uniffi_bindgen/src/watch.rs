@@ -0,0 +1,195 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate bindings again whenever the files they're generated from change.
+//!
+//! This is for iterating on a library: rebuild it and the bindings are regenerated without
+//! running `uniffi-bindgen` again.  The files to watch are found in the same way as for a
+//! [Depfile], so they include the UDL and `uniffi.toml` files which were read, and are updated
+//! after each run, for example when a crate is added to the library.
+
+use std::{collections::BTreeSet, env, path::PathBuf, sync::mpsc, time::Duration};
+
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+use crate::{depfile::Depfile, Result};
+
+/// How long to wait for changes to settle before generating the bindings
+///
+/// Building a library writes it more than once, so this avoids generating the bindings from a
+/// partly written file.
+pub const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Generate bindings, then generate them again whenever their inputs change
+///
+/// `generate` is called to generate the bindings and returns a [Depfile] listing the files which
+/// were read and written, usually found with [crate::depfile::RecordingConfigSupplier] and the
+/// files returned by the binding generator.  `library_path` is always watched, even if generating the
+/// bindings fails before the inputs are known.
+///
+/// Errors from `generate` are printed and the inputs from the last successful run are watched
+/// until they change again.  This only returns if watching the files fails.
+pub fn watch_and_generate(
+    library_path: &Utf8Path,
+    mut generate: impl FnMut() -> Result<Depfile>,
+) -> Result<()> {
+    let library_path = normalize(library_path)?;
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, sender)?;
+    let mut inputs = BTreeSet::from([library_path.clone()]);
+    let mut watched_dirs = BTreeSet::new();
+    let mut run = |inputs: &mut BTreeSet<Utf8PathBuf>| -> Result<()> {
+        match generate() {
+            Ok(depfile) => {
+                for output in depfile.outputs() {
+                    println!("Wrote {output}");
+                }
+                let mut new_inputs = BTreeSet::from([library_path.clone()]);
+                for input in depfile.inputs() {
+                    new_inputs.insert(normalize(input)?);
+                }
+                *inputs = new_inputs;
+            }
+            Err(e) => eprintln!("Failed to generate the bindings: {e}"),
+        }
+        Ok(())
+    };
+
+    run(&mut inputs)?;
+    loop {
+        // Files are watched through their directories, so they're still watched when a build
+        // replaces them rather than writing to them.
+        let dirs = watched_dirs_for(&inputs);
+        for dir in watched_dirs.difference(&dirs) {
+            // The directory may have been removed, which removes the watch anyway.
+            let _ = debouncer.watcher().unwatch(dir.as_std_path());
+        }
+        for dir in dirs.difference(&watched_dirs) {
+            debouncer
+                .watcher()
+                .watch(dir.as_std_path(), RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {dir}"))?;
+        }
+        watched_dirs = dirs;
+        println!(
+            "Watching {} files for changes, press Ctrl-C to stop",
+            inputs.len()
+        );
+
+        let changed = loop {
+            let events = receiver
+                .recv()
+                .context("The file watcher stopped unexpectedly")?;
+            match events {
+                Ok(events) => {
+                    let changed = changed_inputs(events.into_iter().map(|e| e.path), &inputs);
+                    if !changed.is_empty() {
+                        break changed;
+                    }
+                }
+                Err(e) => eprintln!("Error watching files: {e}"),
+            }
+        };
+        for path in changed {
+            println!("Changed: {path}");
+        }
+        run(&mut inputs)?;
+    }
+}
+
+// The directories to watch for changes to `inputs`
+fn watched_dirs_for(inputs: &BTreeSet<Utf8PathBuf>) -> BTreeSet<Utf8PathBuf> {
+    inputs
+        .iter()
+        .filter_map(|path| path.parent())
+        .map(Utf8Path::to_owned)
+        .collect()
+}
+
+// The inputs which are in `paths`, which are the paths of the changes reported by the watcher
+fn changed_inputs(
+    paths: impl IntoIterator<Item = PathBuf>,
+    inputs: &BTreeSet<Utf8PathBuf>,
+) -> BTreeSet<Utf8PathBuf> {
+    paths
+        .into_iter()
+        .filter_map(|path| Utf8PathBuf::from_path_buf(path).ok())
+        .filter_map(|path| normalize(&path).ok())
+        .filter(|path| inputs.contains(path))
+        .collect()
+}
+
+// The watcher reports absolute paths with their directories resolved, for example `/var` is
+// `/private/var` on macOS, so the inputs and the reported paths are both made absolute and their
+// directories canonicalized to compare them, which also removes any `..`.  The file itself isn't
+// resolved, since it's the symlink which is replaced when the file changes.
+fn normalize(path: &Utf8Path) -> Result<Utf8PathBuf> {
+    let path = if path.is_absolute() {
+        path.to_owned()
+    } else {
+        Utf8PathBuf::try_from(env::current_dir()?)?.join(path)
+    };
+    // The directory may have been removed, in which case the path is compared as it is.
+    if let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) {
+        if let Ok(parent) = parent.canonicalize_utf8() {
+            return Ok(parent.join(file_name));
+        }
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> BTreeSet<Utf8PathBuf> {
+        paths.iter().map(Utf8PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_watched_dirs() {
+        let inputs = paths(&[
+            "/work/target/debug/libfoo.so",
+            "/work/foo/uniffi.toml",
+            "/work/foo/src/foo.udl",
+        ]);
+        assert_eq!(
+            watched_dirs_for(&inputs),
+            paths(&["/work/target/debug", "/work/foo", "/work/foo/src"])
+        );
+    }
+
+    #[test]
+    fn test_changed_inputs() {
+        let inputs = paths(&["/work/target/debug/libfoo.so", "/work/foo/uniffi.toml"]);
+        let events = [
+            "/work/target/debug/libfoo.so",
+            "/work/target/debug/libfoo.so",
+            "/work/target/debug/libbar.so",
+            "/work/foo/Cargo.toml",
+        ]
+        .map(PathBuf::from);
+        assert_eq!(
+            changed_inputs(events, &inputs),
+            paths(&["/work/target/debug/libfoo.so"])
+        );
+        assert!(changed_inputs([], &inputs).is_empty());
+    }
+
+    #[test]
+    fn test_changed_inputs_normalized() {
+        let dir = env::temp_dir().join(format!("uniffi-watch-{}", std::process::id()));
+        let dir = Utf8PathBuf::try_from(dir).unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let input = normalize(&dir.join("src/../uniffi.toml")).unwrap();
+        let inputs = BTreeSet::from([input.clone()]);
+        let events = [dir.join("uniffi.toml"), dir.join("src/lib.rs")].map(PathBuf::from);
+        let changed = changed_inputs(events, &inputs);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(changed, BTreeSet::from([input]));
+        assert!(!changed.iter().any(|path| path.as_str().contains("..")));
+    }
+}
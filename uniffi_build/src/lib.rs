@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use anyhow::{Context, Result};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use std::env;
 
 /// Generate the rust "scaffolding" required to build a uniffi component.
@@ -24,7 +24,10 @@ pub fn generate_scaffolding(udl_file: impl AsRef<Utf8Path>) -> Result<()> {
     println!("cargo:rerun-if-changed={udl_file}");
     println!("cargo:rerun-if-env-changed=UNIFFI_TESTS_DISABLE_EXTENSIONS");
     let out_dir = env::var("OUT_DIR").context("$OUT_DIR missing?!")?;
-    uniffi_bindgen::generate_component_scaffolding(udl_file, Some(out_dir.as_ref()), false)
+    let included_files =
+        uniffi_bindgen::generate_component_scaffolding(udl_file, Some(out_dir.as_ref()), false)?;
+    rerun_if_changed(&included_files);
+    Ok(())
 }
 
 /// Like generate_scaffolding, but uses the specified crate_name instead of locating and parsing
@@ -43,10 +46,19 @@ pub fn generate_scaffolding_for_crate(
     // Calling the command line helps making sure that the generated swift/Kotlin/whatever
     // bindings were generated with the same version of uniffi as the Rust scaffolding code.
     let out_dir = env::var("OUT_DIR").context("$OUT_DIR missing?!")?;
-    uniffi_bindgen::generate_component_scaffolding_for_crate(
+    let included_files = uniffi_bindgen::generate_component_scaffolding_for_crate(
         udl_file,
         crate_name,
         Some(out_dir.as_ref()),
         false,
-    )
+    )?;
+    rerun_if_changed(&included_files);
+    Ok(())
+}
+
+// Run the build script again when a file included by the UDL file changes, like the UDL file
+fn rerun_if_changed(included_files: &[Utf8PathBuf]) {
+    for included_file in included_files {
+        println!("cargo:rerun-if-changed={included_file}");
+    }
 }
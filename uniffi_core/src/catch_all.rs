@@ -0,0 +1,109 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Support for functions exported with `catch_all`
+//!
+//! These return `anyhow::Result<T>` or `Result<T, Box<dyn Error + Send + Sync>>`, rather than a
+//! `Result` with an error type that's exported to the foreign side.  The scaffolding converts the
+//! error into a [CatchAllError], which each crate exposes to the bindings as a flat error with a
+//! single variant.
+
+use std::{error::Error, fmt};
+
+use bytes::BufMut;
+
+use crate::{format_error_chain, FfiConverter, LowerError, RustBuffer};
+
+/// The error returned to the foreign side by functions exported with `catch_all`
+///
+/// This only keeps the message of the original error, including the messages of its sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatchAllError {
+    message: String,
+}
+
+impl CatchAllError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for CatchAllError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for CatchAllError {}
+
+// The `TypeId` impl is generated for each crate by `setup_scaffolding!`, since the error is
+// declared in the bindings of every crate which uses it.
+unsafe impl<UT> LowerError<UT> for CatchAllError {
+    fn lower_error(obj: Self) -> RustBuffer {
+        // Lowered like a flat error whose only variant is `Failed`
+        let mut buf = Vec::new();
+        buf.put_i32(1);
+        <String as FfiConverter<UT>>::write(obj.message, &mut buf);
+        RustBuffer::from_vec(buf)
+    }
+}
+
+/// Results which functions exported with `catch_all` can return
+pub trait CatchAllResult {
+    type Ok;
+
+    fn into_catch_all(self) -> Result<Self::Ok, CatchAllError>;
+}
+
+impl<T> CatchAllResult for Result<T, anyhow::Error> {
+    type Ok = T;
+
+    fn into_catch_all(self) -> Result<T, CatchAllError> {
+        // The alternate format includes the chain of sources, separated by `": "`
+        self.map_err(|e| CatchAllError::new(format!("{e:#}")))
+    }
+}
+
+impl<T> CatchAllResult for Result<T, Box<dyn Error + Send + Sync>> {
+    type Ok = T;
+
+    fn into_catch_all(self) -> Result<T, CatchAllError> {
+        self.map_err(|e| CatchAllError::new(format_error_chain(&*e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn anyhow_chain() {
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("disk full"))
+            .context("can't write")
+            .context("can't save");
+        assert_eq!(
+            result.into_catch_all(),
+            Err(CatchAllError::new("can't save: can't write: disk full"))
+        );
+    }
+
+    #[test]
+    fn boxed_chain() {
+        let err = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+        let result: Result<u8, Box<dyn Error + Send + Sync>> = Err(Box::new(err));
+        assert_eq!(
+            result.into_catch_all(),
+            Err(CatchAllError::new("disk full"))
+        );
+        let result: Result<u8, Box<dyn Error + Send + Sync>> = Ok(1);
+        assert_eq!(result.into_catch_all(), Ok(1));
+    }
+}
@@ -103,6 +103,7 @@
 //! UniFFI generates a struct that implements the trait by calling VTable methods, passing the handle as the first parameter.
 //! When the struct is dropped, the `uniffi_free` method is called.
 
+use crate::{FfiConverter, RustBuffer, UniFfiTag};
 use std::fmt;
 
 /// Used when internal/unexpected error happened when calling a foreign callback, for example when
@@ -112,23 +113,68 @@ use std::fmt;
 #[derive(Debug)]
 pub struct UnexpectedUniFFICallbackError {
     pub reason: String,
+    /// Class name of the foreign exception, when the bindings sent one.
+    pub class_name: Option<String>,
+    /// True when the foreign code was cancelled, for example by a Kotlin `CancellationException`
+    /// or a Swift `CancellationError`.
+    pub cancelled: bool,
 }
 
 impl UnexpectedUniFFICallbackError {
     pub fn new(reason: impl fmt::Display) -> Self {
         Self {
             reason: reason.to_string(),
+            class_name: None,
+            cancelled: false,
+        }
+    }
+
+    /// Lift the error that the foreign bindings sent for an exception the callback method didn't
+    /// declare.
+    ///
+    /// The buffer contains the exception message, optionally followed by its class name.  It may
+    /// also be empty, which older bindings send for cancelled calls.
+    pub(crate) fn from_foreign(buf: RustBuffer, cancelled: bool) -> Self {
+        let vec = buf.destroy_into_vec();
+        if vec.is_empty() {
+            return Self {
+                cancelled,
+                ..Self::new("")
+            };
+        }
+        let mut buf = vec.as_slice();
+        let lifted = <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).and_then(|reason| {
+            let class_name = if buf.is_empty() {
+                None
+            } else {
+                Some(<String as FfiConverter<UniFfiTag>>::try_read(&mut buf)?)
+            };
+            Ok((reason, class_name))
+        });
+        match lifted {
+            Ok((reason, class_name)) => Self {
+                reason,
+                class_name,
+                cancelled,
+            },
+            Err(e) => Self {
+                cancelled,
+                ..Self::new(format!("(Error lifting message: {e})"))
+            },
         }
     }
 }
 
 impl fmt::Display for UnexpectedUniFFICallbackError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "UnexpectedUniFFICallbackError(reason: {:?})",
-            self.reason
-        )
+        write!(f, "UnexpectedUniFFICallbackError(reason: {:?}", self.reason)?;
+        if let Some(class_name) = &self.class_name {
+            write!(f, ", class_name: {class_name:?}")?;
+        }
+        if self.cancelled {
+            write!(f, ", cancelled: true")?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -207,3 +253,58 @@ macro_rules! convert_unexpected_error {
             .try_convert_unexpected_callback_error($error)
     }};
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn foreign_buf(strings: &[&str]) -> RustBuffer {
+        let mut buf = vec![];
+        for s in strings {
+            <String as FfiConverter<UniFfiTag>>::write(s.to_string(), &mut buf);
+        }
+        RustBuffer::from_vec(buf)
+    }
+
+    #[test]
+    fn test_from_foreign() {
+        let e = UnexpectedUniFFICallbackError::from_foreign(
+            foreign_buf(&["boom", "java.lang.IllegalStateException"]),
+            false,
+        );
+        assert_eq!(e.reason, "boom");
+        assert_eq!(
+            e.class_name.as_deref(),
+            Some("java.lang.IllegalStateException")
+        );
+        assert!(!e.cancelled);
+        assert_eq!(
+            e.to_string(),
+            "UnexpectedUniFFICallbackError(reason: \"boom\", class_name: \"java.lang.IllegalStateException\")"
+        );
+    }
+
+    #[test]
+    fn test_from_foreign_message_only() {
+        let e = UnexpectedUniFFICallbackError::from_foreign(foreign_buf(&["boom"]), false);
+        assert_eq!(e.reason, "boom");
+        assert_eq!(e.class_name, None);
+        assert_eq!(
+            e.to_string(),
+            "UnexpectedUniFFICallbackError(reason: \"boom\")"
+        );
+    }
+
+    #[test]
+    fn test_from_foreign_cancelled() {
+        let e = UnexpectedUniFFICallbackError::from_foreign(RustBuffer::new(), true);
+        assert_eq!(e.reason, "");
+        assert!(e.cancelled);
+        let e = UnexpectedUniFFICallbackError::from_foreign(
+            foreign_buf(&["Job was cancelled", "CancellationError"]),
+            true,
+        );
+        assert_eq!(e.class_name.as_deref(), Some("CancellationError"));
+        assert!(e.cancelled);
+    }
+}
@@ -88,12 +88,19 @@ pub enum RustCallStatusCode {
     /// will contain the serialized error.
     Error = 1,
     /// Unexpected error.  [RustCallStatus::error_buf] will contain a serialized message string
+    ///
+    /// When foreign callback code returns this, the message string may be followed by a second
+    /// string with the class name of the foreign exception.
     UnexpectedError = 2,
     /// Async function cancelled.  [RustCallStatus::error_buf] will be empty and does not need to
     /// be freed.
     ///
     /// This is only returned for async functions and only if the bindings code uses the
     /// [rust_future_cancel] call.
+    ///
+    /// Foreign callback code also returns this when it was cancelled, for example by a Kotlin
+    /// `CancellationException`.  In that case [RustCallStatus::error_buf] is serialized the same
+    /// way as for [Self::UnexpectedError] from a callback.
     Cancelled = 3,
 }
 
@@ -3,10 +3,14 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 /// This module contains builtin `FFIConverter` implementations.  These cover:
-///   - Simple privitive types: u8, i32, String, Arc<T>, etc
+///   - Simple privitive types: u8, i32, u128, String, Arc<T>, etc
 ///   - Composite types: Vec<T>, Option<T>, etc.
 ///   - SystemTime and Duration, which maybe shouldn`t be built-in, but have been historically and
 ///     we want to continue to support them for now.
+///   - `chrono` dates and times, when the `chrono` feature is enabled.
+///   - `serde_json::Value`, when the `json` feature is enabled.
+///   - `url::Url`, when the `url` feature is enabled.
+///   - `PathBuf`, passed as the bytes of the path.
 ///
 /// As described in
 /// https://mozilla.github.io/uniffi-rs/internals/lifting_and_lowering.html#code-generation-and-the-fficonverter-trait,
@@ -34,6 +38,10 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     fmt::{Debug, Display},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU8,
+    },
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -82,6 +90,35 @@ impl_ffi_converter_for_num_primitive!(i64, metadata::codes::TYPE_I64);
 impl_ffi_converter_for_num_primitive!(f32, metadata::codes::TYPE_F32);
 impl_ffi_converter_for_num_primitive!(f64, metadata::codes::TYPE_F64);
 
+/// Support for passing 128-bit integers via the FFI.
+///
+/// There's no 128-bit integer type in the C ABI that all foreign languages can use, so these are
+/// passed by serializing them to a buffer, as 16 bytes in big-endian order.  The bindings check
+/// that the values they lower are in range, since they're usually represented by a wider type.
+macro_rules! impl_ffi_converter_for_128_bit_int {
+    ($T:ty, $type_code:expr) => {
+        paste! {
+            unsafe impl<UT> FfiConverter<UT> for $T {
+                ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+                fn write(obj: $T, buf: &mut Vec<u8>) {
+                    buf.[<put_ $T>](obj);
+                }
+
+                fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                    check_remaining(buf, 16)?;
+                    Ok(buf.[<get_ $T>]())
+                }
+
+                const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code($type_code);
+            }
+        }
+    };
+}
+
+impl_ffi_converter_for_128_bit_int!(u128, metadata::codes::TYPE_U128);
+impl_ffi_converter_for_128_bit_int!(i128, metadata::codes::TYPE_I128);
+
 /// Support for passing boolean values via the FFI.
 ///
 /// Booleans are passed as an `i8` in order to avoid problems with handling
@@ -113,6 +150,86 @@ unsafe impl<UT> FfiConverter<UT> for bool {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BOOL);
 }
 
+/// Support for passing chars via the FFI.
+///
+/// Chars are passed as their Unicode scalar value in a `u32`.  Not every `u32` is a valid scalar
+/// value, so lifting fails for surrogates and values above `0x10FFFF`.
+unsafe impl<UT> FfiConverter<UT> for char {
+    type FfiType = u32;
+
+    fn lower(obj: char) -> Self::FfiType {
+        u32::from(obj)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<char> {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!("invalid Unicode scalar value for char: {v:#x}"),
+        }
+    }
+
+    fn write(obj: char, buf: &mut Vec<u8>) {
+        buf.put_u32(<Self as FfiConverter<UT>>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<char> {
+        check_remaining(buf, 4)?;
+        <Self as FfiConverter<UT>>::try_lift(buf.get_u32())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_CHAR);
+}
+
+/// Support for passing the `NonZero*` integers via the FFI.
+///
+/// These are passed exactly like the underlying integer type, which is also the type the bindings
+/// use for them.  Lifting a zero fails, so foreign code which passes one gets the same error as
+/// for any other argument that can't be converted.
+macro_rules! impl_ffi_converter_for_non_zero_int {
+    ($T:ty, $Int:ty) => {
+        unsafe impl<UT> FfiConverter<UT> for $T {
+            type FfiType = <$Int as FfiConverter<UT>>::FfiType;
+
+            fn lower(obj: $T) -> Self::FfiType {
+                <$Int as FfiConverter<UT>>::lower(obj.get())
+            }
+
+            fn try_lift(v: Self::FfiType) -> Result<$T> {
+                non_zero(<$T>::new(<$Int as FfiConverter<UT>>::try_lift(v)?))
+            }
+
+            fn write(obj: $T, buf: &mut Vec<u8>) {
+                <$Int as FfiConverter<UT>>::write(obj.get(), buf);
+            }
+
+            fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                non_zero(<$T>::new(<$Int as FfiConverter<UT>>::try_read(buf)?))
+            }
+
+            const TYPE_ID_META: MetadataBuffer = <$Int as FfiConverter<UT>>::TYPE_ID_META;
+        }
+    };
+}
+
+// `NonZero*::new` returns `None` for zero
+fn non_zero<T>(v: Option<T>) -> Result<T> {
+    match v {
+        Some(v) => Ok(v),
+        None => bail!("value must be non-zero"),
+    }
+}
+
+impl_ffi_converter_for_non_zero_int!(NonZeroU8, u8);
+impl_ffi_converter_for_non_zero_int!(NonZeroI8, i8);
+impl_ffi_converter_for_non_zero_int!(NonZeroU16, u16);
+impl_ffi_converter_for_non_zero_int!(NonZeroI16, i16);
+impl_ffi_converter_for_non_zero_int!(NonZeroU32, u32);
+impl_ffi_converter_for_non_zero_int!(NonZeroI32, i32);
+impl_ffi_converter_for_non_zero_int!(NonZeroU64, u64);
+impl_ffi_converter_for_non_zero_int!(NonZeroI64, i64);
+impl_ffi_converter_for_non_zero_int!(NonZeroU128, u128);
+impl_ffi_converter_for_non_zero_int!(NonZeroI128, i128);
+
 /// Support for passing Strings via the FFI.
 ///
 /// Unlike many other implementations of `FfiConverter`, this passes a struct containing
@@ -416,11 +533,32 @@ derive_ffi_traits!(blanket u64);
 derive_ffi_traits!(blanket i64);
 derive_ffi_traits!(blanket f32);
 derive_ffi_traits!(blanket f64);
+derive_ffi_traits!(blanket u128);
+derive_ffi_traits!(blanket i128);
 derive_ffi_traits!(blanket bool);
+derive_ffi_traits!(blanket char);
+derive_ffi_traits!(blanket NonZeroU8);
+derive_ffi_traits!(blanket NonZeroI8);
+derive_ffi_traits!(blanket NonZeroU16);
+derive_ffi_traits!(blanket NonZeroI16);
+derive_ffi_traits!(blanket NonZeroU32);
+derive_ffi_traits!(blanket NonZeroI32);
+derive_ffi_traits!(blanket NonZeroU64);
+derive_ffi_traits!(blanket NonZeroI64);
+derive_ffi_traits!(blanket NonZeroU128);
+derive_ffi_traits!(blanket NonZeroI128);
 derive_ffi_traits!(blanket String);
 derive_ffi_traits!(blanket Duration);
 derive_ffi_traits!(blanket SystemTime);
 
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "json")]
+mod json;
+mod path;
+#[cfg(feature = "url")]
+mod url;
+
 // For composite types, derive LowerReturn, LiftReturn, etc, from Lift/Lower.
 //
 // Note that this means we don't get specialized return handling.  For example, if we could return
@@ -514,9 +652,9 @@ where
         match E::try_lift_from_rust_buffer(buf) {
             Ok(lifted_error) => Err(lifted_error),
             Err(anyhow_error) => {
-                Self::handle_callback_unexpected_error(UnexpectedUniFFICallbackError {
-                    reason: format!("Error lifting from rust buffer: {anyhow_error}"),
-                })
+                Self::handle_callback_unexpected_error(UnexpectedUniFFICallbackError::new(format!(
+                    "Error lifting from rust buffer: {anyhow_error}"
+                )))
             }
         }
     }
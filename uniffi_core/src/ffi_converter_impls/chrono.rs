@@ -0,0 +1,159 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` implementations for `chrono` types, enabled by the `chrono` feature.
+
+use crate::{
+    check_remaining, derive_ffi_traits, ffi_converter_rust_buffer_lift_and_lower, metadata,
+    FfiConverter, MetadataBuffer, Result,
+};
+use ::chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use anyhow::bail;
+use bytes::buf::{Buf, BufMut};
+use std::time::SystemTime;
+
+/// Days from 0001-01-01 (day 1 of the common era) to 1970-01-01
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Support for passing `DateTime<Utc>` values via the FFI.
+///
+/// These are passed exactly like `SystemTime` values and are a timestamp in the foreign bindings.
+/// Lifting fails for timestamps outside of the range that `DateTime` can represent.
+unsafe impl<UT> FfiConverter<UT> for DateTime<Utc> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: DateTime<Utc>, buf: &mut Vec<u8>) {
+        <SystemTime as FfiConverter<UT>>::write(obj.into(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<DateTime<Utc>> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= 1_000_000_000 {
+            bail!("invalid nanoseconds for timestamp: {nanos}");
+        }
+        // The offset is stored as a sign and a magnitude, while `DateTime` counts whole seconds
+        // towards negative infinity and then nanoseconds forwards from there.
+        let (seconds, nanos) = if seconds < 0 && nanos > 0 {
+            match seconds.checked_sub(1) {
+                Some(seconds) => (seconds, 1_000_000_000 - nanos),
+                None => bail!("timestamp out of range for DateTime<Utc>: {seconds}s"),
+            }
+        } else {
+            (seconds, nanos)
+        };
+        match Utc.timestamp_opt(seconds, nanos).single() {
+            Some(datetime) => Ok(datetime),
+            None => bail!("timestamp out of range for DateTime<Utc>: {seconds}s"),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
+}
+
+/// Support for passing `NaiveDate` values via the FFI.
+///
+/// Dates are passed as the number of days since 1970-01-01 in an `i32`, which covers every date
+/// that `NaiveDate` can represent.  Lifting fails for the days outside of that range.
+unsafe impl<UT> FfiConverter<UT> for NaiveDate {
+    type FfiType = i32;
+
+    fn lower(obj: NaiveDate) -> Self::FfiType {
+        obj.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<NaiveDate> {
+        match v
+            .checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+        {
+            Some(date) => Ok(date),
+            None => bail!("date out of range for NaiveDate: {v} days from 1970-01-01"),
+        }
+    }
+
+    fn write(obj: NaiveDate, buf: &mut Vec<u8>) {
+        buf.put_i32(<Self as FfiConverter<UT>>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<NaiveDate> {
+        check_remaining(buf, 4)?;
+        <Self as FfiConverter<UT>>::try_lift(buf.get_i32())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DATE);
+}
+
+derive_ffi_traits!(blanket DateTime<Utc>);
+derive_ffi_traits!(blanket NaiveDate);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower};
+
+    struct UniFfiTag;
+
+    #[test]
+    fn datetime_round_trip() {
+        for (seconds, nanos) in [(0, 0), (1_700_000_000, 123), (-1, 0), (-2, 500_000_000)] {
+            let datetime = Utc.timestamp_opt(seconds, nanos).unwrap();
+            let buf = <DateTime<Utc> as Lower<UniFfiTag>>::lower_into_rust_buffer(datetime);
+            let result = <DateTime<Utc> as Lift<UniFfiTag>>::try_lift_from_rust_buffer(buf)
+                .expect("Failed to lift!");
+            assert_eq!(datetime, result);
+        }
+    }
+
+    #[test]
+    fn datetime_lift_out_of_range() {
+        for (seconds, nanos) in [(i64::MAX, 0), (i64::MIN, 0), (i64::MIN, 1)] {
+            let mut buf = vec![];
+            buf.put_i64(seconds);
+            buf.put_u32(nanos);
+            let err = <DateTime<Utc> as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice())
+                .unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with("timestamp out of range for DateTime<Utc>"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn datetime_matches_system_time() {
+        // 1.5 seconds before the epoch
+        let system_time = SystemTime::UNIX_EPOCH - std::time::Duration::from_millis(1500);
+        let buf = <SystemTime as Lower<UniFfiTag>>::lower_into_rust_buffer(system_time);
+        let datetime = <DateTime<Utc> as Lift<UniFfiTag>>::try_lift_from_rust_buffer(buf).unwrap();
+        assert_eq!(datetime, Utc.timestamp_opt(-2, 500_000_000).unwrap());
+    }
+
+    #[test]
+    fn date_lift() {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        assert_eq!(<NaiveDate as FfiConverter<UniFfiTag>>::lower(epoch), 0);
+        for date in [
+            epoch,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            NaiveDate::from_ymd_opt(1, 1, 1).unwrap(),
+            NaiveDate::MIN,
+            NaiveDate::MAX,
+        ] {
+            let result =
+                <NaiveDate as FfiConverter<UniFfiTag>>::try_lift(<NaiveDate as FfiConverter<
+                    UniFfiTag,
+                >>::lower(date))
+                .expect("Failed to lift!");
+            assert_eq!(date, result);
+        }
+        // Most `i32` values are too far from the epoch for `NaiveDate`
+        for v in [i32::MIN, i32::MAX] {
+            <NaiveDate as FfiConverter<UniFfiTag>>::try_lift(v).unwrap_err();
+        }
+    }
+}
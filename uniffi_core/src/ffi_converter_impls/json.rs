@@ -0,0 +1,73 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` implementation for `serde_json::Value`, enabled by the `json` feature.
+
+use crate::{
+    derive_ffi_traits, ffi_converter_rust_buffer_lift_and_lower, metadata, FfiConverter,
+    MetadataBuffer, Result,
+};
+use anyhow::Context;
+use serde_json::Value;
+
+/// Support for passing JSON values via the FFI.
+///
+/// JSON values are always passed by serializing to a buffer, which holds the JSON text written
+/// like a `String`.  The text is compact, and objects have their keys in the order `Value` keeps
+/// them, which is sorted unless serde_json's `preserve_order` feature is enabled.  Lifting fails
+/// for text which isn't valid JSON, or which is nested more than 128 levels deep.
+unsafe impl<UT> FfiConverter<UT> for Value {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: Value, buf: &mut Vec<u8>) {
+        <String as FfiConverter<UT>>::write(obj.to_string(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Value> {
+        let text = <String as FfiConverter<UT>>::try_read(buf)?;
+        serde_json::from_str(&text).context("invalid JSON value")
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_JSON);
+}
+
+derive_ffi_traits!(blanket Value);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower};
+    use serde_json::json;
+
+    struct UniFfiTag;
+
+    fn round_trip(value: Value) -> Value {
+        let buf = <Value as Lower<UniFfiTag>>::lower_into_rust_buffer(value);
+        <Value as Lift<UniFfiTag>>::try_lift_from_rust_buffer(buf).expect("Failed to lift!")
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for value in [
+            Value::Null,
+            json!(true),
+            json!("h\u{e9}llo \u{1F600} \"quoted\"\n"),
+            json!(u64::MAX),
+            json!(i64::MIN),
+            json!(f64::MAX),
+            json!({"b": [1, 2.5, null], "a": {"nested": []}}),
+        ] {
+            assert_eq!(round_trip(value.clone()), value);
+        }
+    }
+
+    #[test]
+    fn json_lift_errors() {
+        for text in ["", "{", "[1,]", "NaN"] {
+            let mut buf = vec![];
+            <String as FfiConverter<UniFfiTag>>::write(text.to_string(), &mut buf);
+            <Value as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap_err();
+        }
+    }
+}
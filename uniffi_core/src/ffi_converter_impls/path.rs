@@ -0,0 +1,119 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` implementation for `std::path::PathBuf`.
+
+use crate::{
+    check_remaining, derive_ffi_traits, ffi_converter_rust_buffer_lift_and_lower, metadata,
+    FfiConverter, LiftRef, MetadataBuffer, Result,
+};
+use bytes::buf::{Buf, BufMut};
+use std::path::{Path, PathBuf};
+
+/// Support for passing file system paths via the FFI.
+///
+/// Paths are passed by serializing to a buffer, which holds the bytes of the path written like a
+/// `Vec<u8>`.  Paths which are valid Unicode are UTF-8.  On Unix, paths which aren't are passed as
+/// their raw bytes, so they survive the round trip through foreign code.  On other platforms,
+/// they're lowered as WTF-8, and lifting fails for bytes which aren't UTF-8.
+unsafe impl<UT> FfiConverter<UT> for PathBuf {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: PathBuf, buf: &mut Vec<u8>) {
+        let bytes = path_into_bytes(obj);
+        // TODO: it would be nice not to panic here.
+        let len = i32::try_from(bytes.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(&bytes);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<PathBuf> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        let bytes = buf.chunk()[..len].to_vec();
+        buf.advance(len);
+        path_from_bytes(bytes)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_PATH);
+}
+
+derive_ffi_traits!(blanket PathBuf);
+
+unsafe impl<UT> LiftRef<UT> for Path {
+    type LiftType = PathBuf;
+}
+
+#[cfg(unix)]
+fn path_into_bytes(path: PathBuf) -> Vec<u8> {
+    use std::os::unix::ffi::OsStringExt;
+    path.into_os_string().into_vec()
+}
+
+#[cfg(not(unix))]
+fn path_into_bytes(path: PathBuf) -> Vec<u8> {
+    path.into_os_string().into_encoded_bytes()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> Result<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(std::ffi::OsString::from_vec(bytes).into())
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> Result<PathBuf> {
+    use anyhow::Context;
+    Ok(String::from_utf8(bytes)
+        .context("path isn't valid UTF-8")?
+        .into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower};
+
+    struct UniFfiTag;
+
+    fn round_trip(path: PathBuf) -> PathBuf {
+        let buf = <PathBuf as Lower<UniFfiTag>>::lower_into_rust_buffer(path);
+        <PathBuf as Lift<UniFfiTag>>::try_lift_from_rust_buffer(buf).expect("Failed to lift!")
+    }
+
+    #[test]
+    fn path_round_trip() {
+        for path in ["", "/", "relative/file.txt", "/tmp/h\u{e9}llo \u{1F600}"] {
+            assert_eq!(round_trip(PathBuf::from(path)), PathBuf::from(path));
+        }
+    }
+
+    #[test]
+    fn path_is_written_as_utf8() {
+        let mut buf = vec![];
+        <PathBuf as FfiConverter<UniFfiTag>>::write(PathBuf::from("/caf\u{e9}"), &mut buf);
+        assert_eq!(buf, [0, 0, 0, 6, b'/', b'c', b'a', b'f', 0xc3, 0xa9]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path_round_trip() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+        let path = PathBuf::from(std::ffi::OsString::from_vec(b"/caf\xe9".to_vec()));
+        let mut buf = vec![];
+        <PathBuf as FfiConverter<UniFfiTag>>::write(path.clone(), &mut buf);
+        assert_eq!(buf, [0, 0, 0, 5, b'/', b'c', b'a', b'f', 0xe9]);
+        let lifted = round_trip(path);
+        assert_eq!(lifted.as_os_str().as_bytes(), b"/caf\xe9");
+    }
+
+    #[test]
+    fn path_read_errors() {
+        // Lengths which don't match the buffer
+        for buf in [vec![0, 0, 0], vec![0, 0, 0, 2, b'a']] {
+            <PathBuf as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap_err();
+        }
+    }
+}
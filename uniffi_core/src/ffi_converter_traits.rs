@@ -348,12 +348,17 @@ pub unsafe trait LiftReturn<UT>: Sized {
             RustCallStatusCode::Error => {
                 Self::lift_error(ManuallyDrop::into_inner(call_status.error_buf))
             }
-            _ => {
-                let e = <String as FfiConverter<crate::UniFfiTag>>::try_lift(
+            RustCallStatusCode::Cancelled => {
+                Self::handle_callback_unexpected_error(UnexpectedUniFFICallbackError::from_foreign(
                     ManuallyDrop::into_inner(call_status.error_buf),
-                )
-                .unwrap_or_else(|e| format!("(Error lifting message: {e}"));
-                Self::handle_callback_unexpected_error(UnexpectedUniFFICallbackError::new(e))
+                    true,
+                ))
+            }
+            RustCallStatusCode::UnexpectedError => {
+                Self::handle_callback_unexpected_error(UnexpectedUniFFICallbackError::from_foreign(
+                    ManuallyDrop::into_inner(call_status.error_buf),
+                    false,
+                ))
             }
         }
     }
@@ -38,12 +38,14 @@ use bytes::buf::Buf;
 // Make Result<> public to support external impls of FfiConverter
 pub use anyhow::Result;
 
+mod catch_all;
 pub mod ffi;
 mod ffi_converter_impls;
 mod ffi_converter_traits;
 pub mod metadata;
 mod oneshot;
 
+pub use catch_all::{CatchAllError, CatchAllResult};
 #[cfg(feature = "scaffolding-ffi-buffer-fns")]
 pub use ffi::ffiserialize::FfiBufferElement;
 pub use ffi::*;
@@ -60,10 +62,30 @@ pub mod deps {
     #[cfg(feature = "tokio")]
     pub use async_compat;
     pub use bytes;
+    #[cfg(feature = "chrono")]
+    pub use chrono;
     pub use log;
+    #[cfg(feature = "json")]
+    pub use serde_json;
     pub use static_assertions;
+    #[cfg(feature = "url")]
+    pub use url;
 }
 
+/// Implemented by the scaffolding for each constructor exported for a type.
+///
+/// `NAME_HASH` is a hash of the constructor's foreign name.  An object's constructors can be
+/// exported from several `impl` blocks, and two of them with the same name are a conflicting impl
+/// error which points at both, instead of a duplicate symbol error.
+#[doc(hidden)]
+pub trait ExportedConstructorName<const NAME_HASH: u64> {}
+
+/// Implemented by the scaffolding for each method exported for a type.
+///
+/// See [ExportedConstructorName].
+#[doc(hidden)]
+pub trait ExportedMethodName<const NAME_HASH: u64> {}
+
 const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // For the significance of this magic number 10 here, and the reason that
@@ -151,6 +173,31 @@ pub fn check_remaining(buf: &[u8], num_bytes: usize) -> Result<()> {
     Ok(())
 }
 
+/// The most errors [format_error_chain] includes from a chain of error sources
+pub const MAX_ERROR_CHAIN_DEPTH: usize = 32;
+
+/// Format an error followed by its chain of sources, separated by `": "`
+///
+/// This is for `#[uniffi(source)]` fields of type `String`, which stand in for errors that can't
+/// be passed across the FFI, like `std::io::Error`.  At most [MAX_ERROR_CHAIN_DEPTH] errors are
+/// included, so a chain that loops back on itself can't go on forever.
+pub fn format_error_chain(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    let mut depth = 1;
+    while let Some(err) = source {
+        if depth == MAX_ERROR_CHAIN_DEPTH {
+            message.push_str(": ...");
+            break;
+        }
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+        depth += 1;
+    }
+    message
+}
+
 /// Macro to implement lowering/lifting using a `RustBuffer`
 ///
 /// For complex types where it's too fiddly or too unsafe to convert them into a special-purpose
@@ -279,6 +326,93 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn error_chain() {
+        #[derive(Debug)]
+        struct Chained(&'static str, Option<Box<Chained>>);
+
+        impl std::fmt::Display for Chained {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+
+        impl std::error::Error for Chained {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                self.1.as_deref().map(|e| e as _)
+            }
+        }
+
+        let err = Chained(
+            "can't load",
+            Some(Box::new(Chained(
+                "can't read",
+                Some(Box::new(Chained("disk full", None))),
+            ))),
+        );
+        assert_eq!(
+            super::format_error_chain(&err),
+            "can't load: can't read: disk full"
+        );
+
+        // An error which is its own source is cut off
+        #[derive(Debug)]
+        struct Cycle;
+
+        impl std::fmt::Display for Cycle {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("cycle")
+            }
+        }
+
+        impl std::error::Error for Cycle {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(self)
+            }
+        }
+
+        let message = super::format_error_chain(&Cycle);
+        assert_eq!(
+            message.matches("cycle").count(),
+            super::MAX_ERROR_CHAIN_DEPTH
+        );
+        assert!(message.ends_with("cycle: ..."));
+    }
+
+    #[test]
+    fn char_lift() {
+        for c in ['a', '\0', '\u{10FFFF}', '😀'] {
+            let result = <char as FfiConverter<UniFfiTag>>::try_lift(<char as FfiConverter<
+                UniFfiTag,
+            >>::lower(c))
+            .expect("Failed to lift!");
+            assert_eq!(c, result);
+        }
+        // Surrogates and values past the end of the Unicode range aren't chars
+        for v in [0xD800, 0xDFFF, 0x110000, u32::MAX] {
+            <char as FfiConverter<UniFfiTag>>::try_lift(v).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn non_zero_lift() {
+        use std::num::{NonZeroI128, NonZeroU64};
+
+        let id = NonZeroU64::new(42).unwrap();
+        assert_eq!(<NonZeroU64 as FfiConverter<UniFfiTag>>::lower(id), 42);
+        assert_eq!(
+            <NonZeroU64 as FfiConverter<UniFfiTag>>::try_lift(42).unwrap(),
+            id
+        );
+        let err = <NonZeroU64 as FfiConverter<UniFfiTag>>::try_lift(0).unwrap_err();
+        assert_eq!(err.to_string(), "value must be non-zero");
+
+        // Values in a buffer are checked too
+        let mut buf = vec![];
+        <i128 as FfiConverter<UniFfiTag>>::write(0, &mut buf);
+        <NonZeroI128 as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap_err();
+    }
 }
 
 #[cfg(test)]
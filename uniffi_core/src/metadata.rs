@@ -40,6 +40,10 @@ pub mod codes {
     pub const UNIFFI_TRAIT: u8 = 11;
     pub const TRAIT_INTERFACE: u8 = 12;
     pub const CALLBACK_TRAIT_INTERFACE: u8 = 13;
+    pub const NAMESPACE_DOCSTRING: u8 = 14;
+    pub const CONTRACT_VERSION: u8 = 15;
+    pub const CONSTANT: u8 = 16;
+    pub const TRAIT_CONSTANT: u8 = 17;
     pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -69,6 +73,13 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     pub const TYPE_TRAIT_INTERFACE: u8 = 24;
     pub const TYPE_CALLBACK_TRAIT_INTERFACE: u8 = 25;
+    pub const TYPE_U128: u8 = 26;
+    pub const TYPE_I128: u8 = 27;
+    pub const TYPE_CHAR: u8 = 28;
+    pub const TYPE_DATE: u8 = 29;
+    pub const TYPE_JSON: u8 = 30;
+    pub const TYPE_PATH: u8 = 31;
+    pub const TYPE_URL: u8 = 32;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes for LiteralMetadata
@@ -79,6 +90,9 @@ pub mod codes {
     pub const LIT_NONE: u8 = 4;
     pub const LIT_SOME: u8 = 5;
     pub const LIT_EMPTY_SEQ: u8 = 6;
+    pub const LIT_EMPTY_MAP: u8 = 7;
+    pub const LIT_ENUM: u8 = 8;
+    pub const LIT_BYTES: u8 = 9;
 }
 
 // For large errors (e.g. enums) a buffer size of ~4k - ~8k
@@ -201,13 +215,22 @@ impl MetadataBuffer {
     //
     // This consumes self, which is convenient for the proc-macro code and also allows us to avoid
     // allocated an extra buffer.
-    pub const fn concat_long_str(mut self, string: &str) -> Self {
-        assert!(self.size + string.len() + 1 < BUF_SIZE);
-        let [lo, hi] = (string.len() as u16).to_le_bytes();
+    pub const fn concat_long_str(self, string: &str) -> Self {
+        self.concat_bytes(string.as_bytes())
+    }
+
+    // Concatenate a byte string to this buffer.
+    //
+    // Byte strings are encoded like long strings, as a `u16` length followed by the data.
+    //
+    // This consumes self, which is convenient for the proc-macro code and also allows us to avoid
+    // allocated an extra buffer.
+    pub const fn concat_bytes(mut self, bytes: &[u8]) -> Self {
+        assert!(self.size + bytes.len() + 1 < BUF_SIZE);
+        let [lo, hi] = (bytes.len() as u16).to_le_bytes();
         self.bytes[self.size] = lo;
         self.bytes[self.size + 1] = hi;
         self.size += 2;
-        let bytes = string.as_bytes();
         let mut i = 0;
         while i < bytes.len() {
             self.bytes[self.size] = bytes[i];
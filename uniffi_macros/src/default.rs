@@ -2,14 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::util::kw;
+use crate::util::{ident_to_string, kw};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{
-    bracketed, parenthesized,
+    braced, bracketed, parenthesized,
     parse::{Nothing, Parse, ParseStream},
-    token::{Bracket, Paren},
-    Lit,
+    token::{Brace, Bracket, Paren},
+    Lit, Path, Token,
 };
 
 /// Default value
@@ -23,6 +23,9 @@ pub enum DefaultValue {
         inner: Box<DefaultValue>,
     },
     EmptySeq(Bracket),
+    EmptyMap(Brace),
+    // An enum variant, like `Color::Red`
+    Enum(Path),
 }
 
 impl ToTokens for DefaultValue {
@@ -32,6 +35,8 @@ impl ToTokens for DefaultValue {
             DefaultValue::None(kw) => kw.to_tokens(tokens),
             DefaultValue::Some { inner, .. } => tokens.extend(quote! { Some(#inner) }),
             DefaultValue::EmptySeq(_) => tokens.extend(quote! { [] }),
+            DefaultValue::EmptyMap(_) => tokens.extend(quote! { {} }),
+            DefaultValue::Enum(path) => path.to_tokens(tokens),
         }
     }
 }
@@ -56,6 +61,13 @@ impl Parse for DefaultValue {
             let bracket = bracketed!(content in input);
             content.parse::<Nothing>()?;
             Ok(Self::EmptySeq(bracket))
+        } else if lookahead.peek(Brace) {
+            let content;
+            let brace = braced!(content in input);
+            content.parse::<Nothing>()?;
+            Ok(Self::EmptyMap(brace))
+        } else if input.peek(syn::Ident) && input.peek2(Token![::]) {
+            Ok(Self::Enum(input.parse()?))
         } else {
             Ok(Self::Literal(input.parse()?))
         }
@@ -94,6 +106,10 @@ impl DefaultValue {
                 .concat_value(::uniffi::metadata::codes::LIT_BOOL)
                 .concat_bool(#b)
             }),
+            DefaultValue::Literal(Lit::ByteStr(b)) => Ok(quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_BYTES)
+                .concat_bytes(#b)
+            }),
 
             DefaultValue::Literal(_) => Err(syn::Error::new_spanned(
                 self,
@@ -104,6 +120,19 @@ impl DefaultValue {
                 .concat_value(::uniffi::metadata::codes::LIT_EMPTY_SEQ)
             }),
 
+            DefaultValue::EmptyMap(_) => Ok(quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_EMPTY_MAP)
+            }),
+
+            DefaultValue::Enum(path) => {
+                // Only the variant is needed, the enum is the type of the field or argument.
+                let variant = ident_to_string(&path.segments.last().unwrap().ident);
+                Ok(quote! {
+                    .concat_value(::uniffi::metadata::codes::LIT_ENUM)
+                    .concat_str(#variant)
+                })
+            }
+
             DefaultValue::None(_) => Ok(quote! {
                 .concat_value(::uniffi::metadata::codes::LIT_NONE)
             }),
@@ -25,6 +25,21 @@ pub fn expand_derive(
     }
 }
 
+/// Expand `#[uniffi::remote(<kind>)]`
+///
+/// The input is a copy of the definition of a type from another crate.  It's only used to
+/// generate the FFI traits and metadata, the type itself must be in scope.
+pub fn expand_remote(kind: DeriveKind, input: DeriveInput) -> syn::Result<TokenStream> {
+    match kind {
+        DeriveKind::Record(_) | DeriveKind::Enum(_) => {
+            expand_derive(kind, input, DeriveOptions::remote())
+        }
+        DeriveKind::Error(kw::Error { span }) | DeriveKind::Object(kw::Object { span }) => Err(
+            syn::Error::new(span, "remote types must be a `Record` or an `Enum`"),
+        ),
+    }
+}
+
 pub enum DeriveKind {
     Record(kw::Record),
     Enum(kw::Enum),
@@ -75,6 +90,17 @@ impl DeriveOptions {
         }
     }
 
+    /// Construct DeriveOptions for `#[uniffi::remote]`
+    ///
+    /// The FFI traits are only implemented for the local tag, since the orphan rules forbid
+    /// blanket impls for a type from another crate.
+    pub fn remote() -> Self {
+        Self {
+            local_tag: true,
+            generate_metadata: true,
+        }
+    }
+
     /// Generate the impl header for a FFI trait
     ///
     /// This will output something like `impl<UT> FfiConverter<UT> for #type`.  The caller is
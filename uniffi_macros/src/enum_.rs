@@ -2,15 +2,17 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
     parse::ParseStream, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Expr, Index, Lit,
-    Variant,
+    LitStr, Variant,
 };
 
 use crate::{
+    default::default_value_metadata_calls,
     ffiops,
+    record::{check_field_types, is_skipped, FieldAttributeArguments},
     util::{
-        create_metadata_items, either_attribute_arg, extract_docstring, ident_to_string, kw,
-        mod_path, try_metadata_value_from_usize, try_read_field, AttributeSliceExt,
-        UniffiAttributeArgs,
+        check_unique_names, create_metadata_items, either_attribute_arg, extract_docstring,
+        ident_to_string, kw, mod_path, parse_tag, try_metadata_value_from_usize, try_read_field,
+        AttributeSliceExt, DeprecatedArg, UniffiAttributeArgs,
     },
     DeriveOptions,
 };
@@ -88,6 +90,12 @@ impl EnumItem {
                 "with_try_read not allowed for non-error enums",
             ));
         }
+        for field in self.enum_.variants.iter().flat_map(|v| &v.fields) {
+            field
+                .attrs
+                .parse_uniffi_attr_args::<FieldAttributeArguments>()?
+                .check_no_source()?;
+        }
         Ok(())
     }
 
@@ -107,6 +115,15 @@ impl EnumItem {
         self.docstring.as_str()
     }
 
+    /// The export tag, from `#[uniffi(tag = "...")]`, or empty if there isn't one
+    pub fn tag(&self) -> String {
+        self.attr
+            .tag
+            .as_ref()
+            .map(|tag| tag.value())
+            .unwrap_or_default()
+    }
+
     pub fn discr_type(&self) -> Option<&Ident> {
         self.discr_type.as_ref()
     }
@@ -115,6 +132,11 @@ impl EnumItem {
         ident_to_string(&self.ident)
     }
 
+    /// Check the types of the variant fields for types that UniFFI can't support
+    pub fn check_field_types(&self) -> syn::Result<()> {
+        check_field_types(self.enum_.variants.iter().flat_map(|v| &v.fields))
+    }
+
     pub fn is_flat_error(&self) -> bool {
         self.attr.flat_error.is_some()
     }
@@ -127,6 +149,7 @@ impl EnumItem {
 pub fn expand_enum(input: DeriveInput, options: DeriveOptions) -> syn::Result<TokenStream> {
     let item = EnumItem::new(input)?;
     item.check_attributes_valid_for_enum()?;
+    item.check_field_types()?;
     let ffi_converter_impl = enum_ffi_converter_impl(&item, &options);
 
     let meta_static_var = options
@@ -171,44 +194,51 @@ fn enum_or_error_ffi_converter_impl(
         Ok(p) => p,
         Err(e) => return e.into_compile_error(),
     };
-    let mut write_match_arms: Vec<_> = item
+    let mut write_match_arms = match item
         .enum_()
         .variants
         .iter()
         .enumerate()
         .map(|(i, v)| {
             let v_ident = &v.ident;
-            let field_idents = v
-                .fields
-                .iter()
-                .enumerate()
-                .map(|(i, f)| {
-                    f.ident
-                        .clone()
-                        .unwrap_or_else(|| Ident::new(&format!("e{i}"), f.span()))
-                })
-                .collect::<Vec<Ident>>();
-            let idx = Index::from(i + 1);
-            let write_fields =
-                std::iter::zip(v.fields.iter(), field_idents.iter()).map(|(f, ident)| {
+            let mut field_patterns = vec![];
+            let mut write_fields = vec![];
+            for (i, f) in v.fields.iter().enumerate() {
+                let ident = f
+                    .ident
+                    .clone()
+                    .unwrap_or_else(|| Ident::new(&format!("e{i}"), f.span()));
+                if is_skipped(f)? {
+                    field_patterns.push(match &f.ident {
+                        Some(ident) => quote! { #ident: _ },
+                        None => quote! { _ },
+                    });
+                } else {
                     let write = ffiops::write(&f.ty);
-                    quote! { #write(#ident, buf); }
-                });
+                    write_fields.push(quote! { #write(#ident, buf); });
+                    field_patterns.push(quote! { #ident });
+                }
+            }
+            let idx = Index::from(i + 1);
             let is_tuple = v.fields.iter().any(|f| f.ident.is_none());
             let fields = if is_tuple {
-                quote! { ( #(#field_idents),* ) }
+                quote! { ( #(#field_patterns),* ) }
             } else {
-                quote! { { #(#field_idents),* } }
+                quote! { { #(#field_patterns),* } }
             };
 
-            quote! {
+            Ok(quote! {
                 Self::#v_ident #fields => {
                     ::uniffi::deps::bytes::BufMut::put_i32(buf, #idx);
                     #(#write_fields)*
                 }
-            }
+            })
         })
-        .collect();
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(arms) => arms,
+        Err(e) => return e.into_compile_error(),
+    };
     if item.is_non_exhaustive() {
         write_match_arms.push(quote! {
             _ => ::std::panic!("Unexpected variant in non-exhaustive enum"),
@@ -222,7 +252,11 @@ fn enum_or_error_ffi_converter_impl(
         let idx = Index::from(i + 1);
         let v_ident = &v.ident;
         let is_tuple = v.fields.iter().any(|f| f.ident.is_none());
-        let try_read_fields = v.fields.iter().map(try_read_field);
+        // Invalid attributes were already reported when building the write arms
+        let try_read_fields = v
+            .fields
+            .iter()
+            .map(|f| try_read_field(f, is_skipped(f).unwrap_or_default()));
 
         if is_tuple {
             quote! {
@@ -271,6 +305,7 @@ pub(crate) fn enum_meta_static_var(item: &EnumItem) -> syn::Result<TokenStream>
     let module_path = mod_path()?;
     let non_exhaustive = item.is_non_exhaustive();
     let docstring = item.docstring();
+    let tag = item.tag();
     let shape = EnumShape::Enum.as_u8();
 
     let mut metadata_expr = quote! {
@@ -290,6 +325,7 @@ pub(crate) fn enum_meta_static_var(item: &EnumItem) -> syn::Result<TokenStream>
     metadata_expr.extend(quote! {
         .concat_bool(#non_exhaustive)
         .concat_long_str(#docstring)
+        .concat_str(#tag)
     });
     Ok(create_metadata_items("enum", &name, metadata_expr, None))
 }
@@ -356,26 +392,61 @@ pub fn variant_metadata(item: &EnumItem) -> syn::Result<Vec<TokenStream>> {
         try_metadata_value_from_usize(enum_.variants.len(), "UniFFI limits enums to 256 variants")?;
     std::iter::once(Ok(quote! { .concat_value(#variants_len) }))
         .chain(enum_.variants.iter().map(|v| {
+            let fields = v
+                .fields
+                .iter()
+                .map(|f| {
+                    let attrs = f
+                        .attrs
+                        .parse_uniffi_attr_args::<FieldAttributeArguments>()?;
+                    attrs.check_skip()?;
+                    if let (Some(default), None) = (&attrs.default, &f.ident) {
+                        return Err(syn::Error::new_spanned(
+                            default,
+                            "`default` can only be used on named fields",
+                        ));
+                    }
+                    Ok((f, attrs))
+                })
+                .filter(|field| !matches!(field, Ok((_, attrs)) if attrs.skip.is_some()))
+                .collect::<syn::Result<Vec<_>>>()?;
             let fields_len = try_metadata_value_from_usize(
-                v.fields.len(),
+                fields.len(),
                 "UniFFI limits enum variants to 256 fields",
             )?;
 
-            let field_names = v
-                .fields
+            let field_names = fields
                 .iter()
-                .map(|f| f.ident.as_ref().map(ident_to_string).unwrap_or_default())
-                .collect::<Vec<_>>();
+                .map(|(f, attrs)| attrs.field_name(f))
+                .collect::<syn::Result<Vec<_>>>()?;
+            check_unique_names("field", field_names.clone())?;
+            let field_names = field_names.into_iter().map(|(name, _)| name);
+
+            let mut sources = fields.iter().filter_map(|(_, attrs)| attrs.source.as_ref());
+            if let (Some(_), Some(source)) = (sources.next(), sources.next()) {
+                return Err(syn::Error::new(
+                    source.span,
+                    "a variant can only have one `source` field",
+                ));
+            }
+            let field_is_sources = fields.iter().map(|(_, attrs)| attrs.source.is_some());
 
             let name = ident_to_string(&v.ident);
             let value_tokens = variant_value(v)?;
             let docstring = extract_docstring(&v.attrs)?;
-            let field_docstrings = v
-                .fields
+            let deprecated = VariantAttributeArguments::deprecated_metadata_calls(v)?;
+            let field_docstrings = fields
+                .iter()
+                .map(|(f, _)| extract_docstring(&f.attrs))
+                .collect::<syn::Result<Vec<_>>>()?;
+            let field_deprecations = fields
+                .iter()
+                .map(|(_, attrs)| attrs.deprecated_metadata_calls());
+            let field_type_id_metas = fields.iter().map(|(f, _)| ffiops::type_id_meta(&f.ty));
+            let field_defaults = fields
                 .iter()
-                .map(|f| extract_docstring(&f.attrs))
+                .map(|(_, attrs)| default_value_metadata_calls(&attrs.default))
                 .collect::<syn::Result<Vec<_>>>()?;
-            let field_type_id_metas = v.fields.iter().map(|f| ffiops::type_id_meta(&f.ty));
 
             Ok(quote! {
                 .concat_str(#name)
@@ -384,16 +455,51 @@ pub fn variant_metadata(item: &EnumItem) -> syn::Result<Vec<TokenStream>> {
                     #(
                         .concat_str(#field_names)
                         .concat(#field_type_id_metas)
-                        // field defaults not yet supported for enums
-                        .concat_bool(false)
+                        #field_defaults
+                        .concat_bool(#field_is_sources)
                         .concat_long_str(#field_docstrings)
+                        #field_deprecations
                     )*
                 .concat_long_str(#docstring)
+                #deprecated
             })
         }))
         .collect()
 }
 
+/// Arguments of `#[uniffi(...)]` on an enum or error variant
+#[derive(Default)]
+pub struct VariantAttributeArguments {
+    deprecated: Option<DeprecatedArg>,
+}
+
+impl VariantAttributeArguments {
+    /// The metadata calls for the deprecation of a variant
+    pub fn deprecated_metadata_calls(v: &Variant) -> syn::Result<TokenStream> {
+        let args = v.attrs.parse_uniffi_attr_args::<Self>()?;
+        Ok(DeprecatedArg::metadata_calls(args.deprecated.as_ref()))
+    }
+}
+
+impl UniffiAttributeArgs for VariantAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::deprecated) {
+            Ok(Self {
+                deprecated: Some(input.parse()?),
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            deprecated: either_attribute_arg(self.deprecated, other.deprecated)?,
+        })
+    }
+}
+
 /// Handle #[uniffi(...)] attributes for enums
 #[derive(Clone, Default)]
 pub struct EnumAttr {
@@ -401,6 +507,7 @@ pub struct EnumAttr {
     // can reuse EnumItem for errors.
     pub flat_error: Option<kw::flat_error>,
     pub with_try_read: Option<kw::with_try_read>,
+    pub tag: Option<LitStr>,
 }
 
 impl UniffiAttributeArgs for EnumAttr {
@@ -416,6 +523,11 @@ impl UniffiAttributeArgs for EnumAttr {
                 with_try_read: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
+            })
         } else if lookahead.peek(kw::handle_unknown_callback_error) {
             // Not used anymore, but still allowed
             Ok(Self::default())
@@ -428,6 +540,7 @@ impl UniffiAttributeArgs for EnumAttr {
         Ok(Self {
             flat_error: either_attribute_arg(self.flat_error, other.flat_error)?,
             with_try_read: either_attribute_arg(self.with_try_read, other.with_try_read)?,
+            tag: either_attribute_arg(self.tag, other.tag)?,
         })
     }
 }
@@ -4,10 +4,10 @@ use syn::{DeriveInput, Index};
 use uniffi_meta::EnumShape;
 
 use crate::{
-    enum_::{rich_error_ffi_converter_impl, variant_metadata, EnumItem},
+    enum_::{rich_error_ffi_converter_impl, variant_metadata, EnumItem, VariantAttributeArguments},
     ffiops,
     util::{
-        chain, create_metadata_items, extract_docstring, ident_to_string, mod_path,
+        create_metadata_items, extract_docstring, ident_to_string, mod_path,
         try_metadata_value_from_usize, AttributeSliceExt,
     },
     DeriveOptions,
@@ -15,24 +15,25 @@ use crate::{
 
 pub fn expand_error(input: DeriveInput, options: DeriveOptions) -> syn::Result<TokenStream> {
     let enum_item = EnumItem::new(input)?;
+    // The fields of flat errors are never lowered, so they can have any type.
+    if !enum_item.is_flat_error() {
+        enum_item.check_field_types()?;
+    }
     let ffi_converter_impl = error_ffi_converter_impl(&enum_item, &options)?;
     let meta_static_var = options
         .generate_metadata
         .then(|| error_meta_static_var(&enum_item).unwrap_or_else(syn::Error::into_compile_error));
 
+    // The fields of flat errors aren't in the bindings, so they can't have attributes.  The
+    // attributes of other errors' fields are handled like those of enum fields.
+    let is_flat = enum_item.is_flat_error();
     let variant_errors: TokenStream = enum_item
         .enum_()
         .variants
         .iter()
-        .flat_map(|variant| {
-            chain(
-                variant.attrs.uniffi_attr_args_not_allowed_here(),
-                variant
-                    .fields
-                    .iter()
-                    .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here()),
-            )
-        })
+        .flat_map(|variant| &variant.fields)
+        .filter(|_| is_flat)
+        .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here())
         .map(syn::Error::into_compile_error)
         .collect();
 
@@ -182,6 +183,7 @@ pub(crate) fn error_meta_static_var(item: &EnumItem) -> syn::Result<TokenStream>
     let module_path = mod_path()?;
     let non_exhaustive = item.is_non_exhaustive();
     let docstring = item.docstring();
+    let tag = item.tag();
     let flat = item.is_flat_error();
     let shape = EnumShape::Error { flat }.as_u8();
     let mut metadata_expr = quote! {
@@ -199,6 +201,7 @@ pub(crate) fn error_meta_static_var(item: &EnumItem) -> syn::Result<TokenStream>
     metadata_expr.extend(quote! {
         .concat_bool(#non_exhaustive)
         .concat_long_str(#docstring)
+        .concat_str(#tag)
     });
     Ok(create_metadata_items("error", &name, metadata_expr, None))
 }
@@ -211,9 +214,11 @@ pub fn flat_error_variant_metadata(item: &EnumItem) -> syn::Result<Vec<TokenStre
         .chain(enum_.variants.iter().map(|v| {
             let name = ident_to_string(&v.ident);
             let docstring = extract_docstring(&v.attrs)?;
+            let deprecated = VariantAttributeArguments::deprecated_metadata_calls(v)?;
             Ok(quote! {
                 .concat_str(#name)
                 .concat_long_str(#docstring)
+                #deprecated
             })
         }))
         .collect()
@@ -2,24 +2,28 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{quote, quote_spanned};
-use syn::{visit_mut::VisitMut, Item, Type};
+use syn::{visit_mut::VisitMut, Attribute, Item, PatType, TraitItem, TraitItemConst, Type};
 
 mod attributes;
 mod callback_interface;
+mod constant;
 mod item;
 mod scaffolding;
 mod trait_interface;
 mod utrait;
 
 use self::{
-    item::{ExportItem, ImplItem},
+    item::{ExportItem, ImplItem, TraitConstant},
     scaffolding::{
         gen_constructor_scaffolding, gen_ffi_function, gen_fn_scaffolding, gen_method_scaffolding,
     },
 };
-use crate::util::{ident_to_string, mod_path};
+use crate::{
+    fnsig::FnSignature,
+    util::{apply_cfg_attrs, extract_cfg_attrs, ident_to_string, mod_path},
+};
 pub use attributes::{AsyncRuntime, DefaultMap, ExportFnArgs};
 pub use callback_interface::ffi_converter_callback_interface_impl;
 
@@ -38,9 +42,11 @@ pub(crate) fn expand_export(
     // new functions outside of the `impl`).
     rewrite_self_type(&mut item);
 
-    let metadata = ExportItem::new(item, all_args)?;
+    // Everything generated for a conditionally compiled item must be too.
+    let cfg_attrs = extract_cfg_attrs(item_attrs(&item));
+    let metadata = ExportItem::new(item, all_args, udl_mode)?;
 
-    match metadata {
+    let tokens = match metadata {
         ExportItem::Function { sig, args } => {
             gen_fn_scaffolding(sig, args.async_runtime.as_ref(), udl_mode)
         }
@@ -67,12 +73,20 @@ pub(crate) fn expand_export(
                     ImplItem::Constructor(sig) => {
                         let async_runtime =
                             sig.async_runtime.clone().or(args.async_runtime.clone());
-                        gen_constructor_scaffolding(sig, async_runtime.as_ref(), udl_mode)
+                        let cfg_attrs = sig.cfg_attrs.clone();
+                        apply_cfg_attrs(
+                            &cfg_attrs,
+                            gen_constructor_scaffolding(sig, async_runtime.as_ref(), udl_mode)?,
+                        )
                     }
                     ImplItem::Method(sig) => {
                         let async_runtime =
                             sig.async_runtime.clone().or(args.async_runtime.clone());
-                        gen_method_scaffolding(sig, async_runtime.as_ref(), udl_mode)
+                        let cfg_attrs = sig.cfg_attrs.clone();
+                        apply_cfg_attrs(
+                            &cfg_attrs,
+                            gen_method_scaffolding(sig, async_runtime.as_ref(), udl_mode)?,
+                        )
                     }
                 })
                 .collect::<syn::Result<_>>()?;
@@ -80,35 +94,60 @@ pub(crate) fn expand_export(
         }
         ExportItem::Trait {
             items,
+            constants,
             self_ident,
             with_foreign,
             callback_interface_only: false,
             docstring,
             args,
-        } => trait_interface::gen_trait_scaffolding(
-            &mod_path,
-            args,
-            self_ident,
-            items,
-            udl_mode,
-            with_foreign,
-            docstring,
-        ),
+        } => {
+            let constant_metadata = trait_constant_metadata(&self_ident, &constants, udl_mode)?;
+            let tokens = trait_interface::gen_trait_scaffolding(
+                &mod_path,
+                args,
+                self_ident,
+                items,
+                udl_mode,
+                with_foreign,
+                docstring,
+            )?;
+            Ok(quote! {
+                #tokens
+                #constant_metadata
+            })
+        }
         ExportItem::Trait {
             items,
+            constants,
             self_ident,
             callback_interface_only: true,
             docstring,
+            args,
             ..
         } => {
+            let constant_metadata = trait_constant_metadata(&self_ident, &constants, udl_mode)?;
             let trait_name = ident_to_string(&self_ident);
             let trait_impl_ident = callback_interface::trait_impl_ident(&trait_name);
             let trait_impl = callback_interface::trait_impl(&mod_path, &self_ident, &items)
                 .unwrap_or_else(|e| e.into_compile_error());
+            let default_method_tokens = (!udl_mode).then(|| {
+                callback_interface::default_method_scaffolding(
+                    &self_ident,
+                    &items,
+                    quote! { ::std::boxed::Box<dyn #self_ident> },
+                )
+                .unwrap_or_else(|e| e.into_compile_error())
+            });
             let metadata_items = (!udl_mode).then(|| {
-                let items =
-                    callback_interface::metadata_items(&self_ident, &items, &mod_path, docstring)
-                        .unwrap_or_else(|e| vec![e.into_compile_error()]);
+                let tag = args.tag.map(|tag| tag.value()).unwrap_or_default();
+                let items = callback_interface::metadata_items(
+                    &self_ident,
+                    &items,
+                    &mod_path,
+                    docstring,
+                    &tag,
+                )
+                .unwrap_or_else(|e| vec![e.into_compile_error()]);
                 quote! { #(#items)* }
             });
             let ffi_converter_tokens =
@@ -117,9 +156,13 @@ pub(crate) fn expand_export(
             Ok(quote! {
                 #trait_impl
 
+                #default_method_tokens
+
                 #ffi_converter_tokens
 
                 #metadata_items
+
+                #constant_metadata
             })
         }
         ExportItem::Struct {
@@ -130,6 +173,50 @@ pub(crate) fn expand_export(
             assert!(!udl_mode);
             utrait::expand_uniffi_trait_export(self_ident, uniffi_traits)
         }
+        ExportItem::Constant {
+            ident,
+            ty,
+            value_metadata_calls,
+            docstring,
+            args,
+        } => {
+            let tag = args.tag.map(|tag| tag.value()).unwrap_or_default();
+            constant::gen_constant_metadata(&ident, &ty, value_metadata_calls, &docstring, &tag)
+        }
+    }?;
+    apply_cfg_attrs(&cfg_attrs, tokens)
+}
+
+/// Generate the metadata for the associated constants of an exported trait
+///
+/// UDL doesn't have trait constants, so there's nothing to generate in UDL mode.
+fn trait_constant_metadata(
+    self_ident: &Ident,
+    constants: &[TraitConstant],
+    udl_mode: bool,
+) -> syn::Result<TokenStream> {
+    if udl_mode {
+        return Ok(TokenStream::new());
+    }
+    constants
+        .iter()
+        .map(|c| constant::gen_trait_constant_metadata(self_ident, c))
+        .collect()
+}
+
+/// Generate the scaffolding and metadata for the getter of an object's field
+pub(crate) fn gen_getter_scaffolding(sig: FnSignature) -> syn::Result<TokenStream> {
+    gen_method_scaffolding(sig, None, false)
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Fn(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Const(i) => &i.attrs,
+        _ => &[],
     }
 }
 
@@ -184,3 +271,63 @@ pub fn rewrite_self_type(item: &mut Item) {
         visitor.visit_impl_item_mut(item);
     }
 }
+
+/// Remove the `#[uniffi(...)]` attributes from the arguments of the functions in an item
+///
+/// Attribute macros can't declare helper attributes like derives can, so the item `export`
+/// outputs mustn't contain them.  Returns whether any were removed.
+pub fn strip_arg_attributes(item: &mut Item) -> bool {
+    struct StripArgAttributesVisitor(bool);
+
+    impl VisitMut for StripArgAttributesVisitor {
+        fn visit_pat_type_mut(&mut self, i: &mut PatType) {
+            let len = i.attrs.len();
+            i.attrs.retain(|attr| !attr.path().is_ident("uniffi"));
+            self.0 |= i.attrs.len() != len;
+        }
+    }
+
+    let mut visitor = StripArgAttributesVisitor(false);
+    visitor.visit_item_mut(item);
+    visitor.0
+}
+
+/// Move the associated constants of an exported trait to an inherent `impl dyn Trait` block
+///
+/// Traits with associated constants can't be made into objects, which exported traits need to
+/// be.  Rust code can still use the constants as `<dyn Trait>::NAME`.  Constants without a value
+/// or with an unsupported type stay in the trait, `ExportItem::new` reports an error for them.
+pub fn move_trait_constants(item: &mut Item) -> Option<TokenStream> {
+    let Item::Trait(item) = item else {
+        return None;
+    };
+    let (constants, items): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut item.items).into_iter().partition(|i| {
+            matches!(
+                i,
+                TraitItem::Const(c) if c.default.is_some() && constant::constant_type(&c.ty).is_ok()
+            )
+        });
+    item.items = items;
+    if constants.is_empty() {
+        return None;
+    }
+
+    let vis = &item.vis;
+    let trait_ident = &item.ident;
+    let constants = constants.into_iter().filter_map(|c| match c {
+        TraitItem::Const(TraitItemConst {
+            attrs,
+            ident,
+            ty,
+            default: Some((_, expr)),
+            ..
+        }) => Some(quote! { #(#attrs)* #vis const #ident: #ty = #expr; }),
+        _ => None,
+    });
+    Some(quote! {
+        impl dyn #trait_ident {
+            #(#constants)*
+        }
+    })
+}
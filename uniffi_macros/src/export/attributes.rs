@@ -2,7 +2,10 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     default::DefaultValue,
-    util::{either_attribute_arg, kw, parse_comma_separated, UniffiAttributeArgs},
+    util::{
+        either_attribute_arg, kw, parse_comma_separated, parse_foreign_name, parse_tag,
+        DeprecatedArg, UniffiAttributeArgs,
+    },
 };
 
 use proc_macro2::TokenStream;
@@ -20,6 +23,7 @@ pub struct ExportTraitArgs {
     pub(crate) async_runtime: Option<AsyncRuntime>,
     pub(crate) callback_interface: Option<kw::callback_interface>,
     pub(crate) with_foreign: Option<kw::with_foreign>,
+    pub(crate) tag: Option<LitStr>,
 }
 
 impl Parse for ExportTraitArgs {
@@ -48,6 +52,11 @@ impl UniffiAttributeArgs for ExportTraitArgs {
                 with_foreign: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
+            })
         } else {
             Ok(Self::default())
         }
@@ -61,6 +70,7 @@ impl UniffiAttributeArgs for ExportTraitArgs {
                 other.callback_interface,
             )?,
             with_foreign: either_attribute_arg(self.with_foreign, other.with_foreign)?,
+            tag: either_attribute_arg(self.tag, other.tag)?,
         };
         if merged.callback_interface.is_some() && merged.with_foreign.is_some() {
             return Err(syn::Error::new(
@@ -78,8 +88,12 @@ impl UniffiAttributeArgs for ExportTraitArgs {
 #[derive(Clone, Default)]
 pub struct ExportFnArgs {
     pub(crate) async_runtime: Option<AsyncRuntime>,
-    pub(crate) name: Option<String>,
+    pub(crate) name: Option<LitStr>,
     pub(crate) defaults: DefaultMap,
+    pub(crate) consumes: Option<kw::consumes>,
+    pub(crate) catch_all: Option<kw::catch_all>,
+    pub(crate) tag: Option<LitStr>,
+    pub(crate) deprecated: Option<DeprecatedArg>,
 }
 
 impl Parse for ExportFnArgs {
@@ -101,9 +115,8 @@ impl UniffiAttributeArgs for ExportFnArgs {
         } else if lookahead.peek(kw::name) {
             let _: kw::name = input.parse()?;
             let _: Token![=] = input.parse()?;
-            let name = Some(input.parse::<LitStr>()?.value());
             Ok(Self {
-                name,
+                name: Some(parse_foreign_name(input)?),
                 ..Self::default()
             })
         } else if lookahead.peek(kw::default) {
@@ -111,6 +124,26 @@ impl UniffiAttributeArgs for ExportFnArgs {
                 defaults: DefaultMap::parse(input)?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::consumes) {
+            Ok(Self {
+                consumes: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::catch_all) {
+            Ok(Self {
+                catch_all: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::deprecated) {
+            Ok(Self {
+                deprecated: Some(input.parse()?),
+                ..Self::default()
+            })
         } else {
             Err(syn::Error::new(
                 input.span(),
@@ -124,6 +157,10 @@ impl UniffiAttributeArgs for ExportFnArgs {
             async_runtime: either_attribute_arg(self.async_runtime, other.async_runtime)?,
             name: either_attribute_arg(self.name, other.name)?,
             defaults: self.defaults.merge(other.defaults),
+            consumes: either_attribute_arg(self.consumes, other.consumes)?,
+            catch_all: either_attribute_arg(self.catch_all, other.catch_all)?,
+            tag: either_attribute_arg(self.tag, other.tag)?,
+            deprecated: either_attribute_arg(self.deprecated, other.deprecated)?,
         })
     }
 }
@@ -131,6 +168,8 @@ impl UniffiAttributeArgs for ExportFnArgs {
 #[derive(Default)]
 pub struct ExportImplArgs {
     pub(crate) async_runtime: Option<AsyncRuntime>,
+    // The tag of the constructors and methods which don't have their own
+    pub(crate) tag: Option<LitStr>,
 }
 
 impl Parse for ExportImplArgs {
@@ -147,6 +186,12 @@ impl UniffiAttributeArgs for ExportImplArgs {
             let _: Token![=] = input.parse()?;
             Ok(Self {
                 async_runtime: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
             })
         } else {
             Err(syn::Error::new(
@@ -159,6 +204,40 @@ impl UniffiAttributeArgs for ExportImplArgs {
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             async_runtime: either_attribute_arg(self.async_runtime, other.async_runtime)?,
+            tag: either_attribute_arg(self.tag, other.tag)?,
+        })
+    }
+}
+
+/// Attribute arguments for constants
+#[derive(Default)]
+pub struct ExportConstArgs {
+    pub(crate) tag: Option<LitStr>,
+}
+
+impl Parse for ExportConstArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        parse_comma_separated(input)
+    }
+}
+
+impl UniffiAttributeArgs for ExportConstArgs {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+            })
+        } else {
+            Err(syn::Error::new(
+                input.span(),
+                format!("uniffi::export attribute `{input}` is not supported here."),
+            ))
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            tag: either_attribute_arg(self.tag, other.tag)?,
         })
     }
 }
@@ -197,6 +276,11 @@ impl UniffiAttributeArgs for ExportStructArgs {
             Ok(Self {
                 traits: HashSet::from([UniffiTraitDiscriminants::Eq]),
             })
+        } else if lookahead.peek(kw::Ord) {
+            input.parse::<Option<kw::Ord>>()?;
+            Ok(Self {
+                traits: HashSet::from([UniffiTraitDiscriminants::Ord]),
+            })
         } else {
             Err(syn::Error::new(
                 input.span(),
@@ -119,6 +119,101 @@ pub(super) fn trait_impl(
     })
 }
 
+/// Generate the scaffolding for the trait methods with default implementations
+///
+/// For each of those methods, this generates:
+///    * A type which implements the trait by forwarding the other methods to a foreign
+///      implementation, so that calling the method on it runs the default implementation.
+///    * A FFI function which calls the method on that type.  Foreign implementations of the trait
+///      call it instead of implementing the method.
+///
+/// `self_type` is the type which foreign implementations are lifted as.
+pub(super) fn default_method_scaffolding(
+    trait_ident: &Ident,
+    items: &[ImplItem],
+    self_type: TokenStream,
+) -> syn::Result<TokenStream> {
+    let trait_name = ident_to_string(trait_ident);
+    let methods = items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(sig) => Some(sig),
+            ImplItem::Constructor(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let has_async_method = methods.iter().any(|m| m.is_async);
+    let impl_attributes = has_async_method.then(|| quote! { #[::async_trait::async_trait] });
+
+    methods
+        .iter()
+        .filter(|sig| sig.has_default())
+        .map(|default_sig| {
+            let default_impl_ident =
+                format_ident!("UniFFIDefault{trait_name}_{}", default_sig.ident);
+            let forwarded_methods = methods
+                .iter()
+                .filter(|sig| sig.ident != default_sig.ident)
+                .map(|sig| gen_forwarded_method(trait_ident, sig))
+                .collect::<syn::Result<Vec<_>>>()?;
+            let ffi_function = super::scaffolding::gen_default_method_ffi_function(
+                default_sig,
+                &self_type,
+                &default_impl_ident,
+            );
+            Ok(quote! {
+                #[allow(non_camel_case_types)]
+                struct #default_impl_ident(::std::sync::Arc<dyn #trait_ident>);
+
+                #impl_attributes
+                impl #trait_ident for #default_impl_ident {
+                    #(#forwarded_methods)*
+                }
+
+                #ffi_function
+            })
+        })
+        .collect()
+}
+
+/// Generate a method for [default_method_scaffolding], which calls the method on the wrapped
+/// foreign implementation.
+fn gen_forwarded_method(trait_ident: &Ident, sig: &FnSignature) -> syn::Result<TokenStream> {
+    let FnSignature {
+        ident,
+        return_ty,
+        receiver,
+        ..
+    } = sig;
+    let (self_param, self_arg) = match receiver {
+        Some(ReceiverArg::Ref) => (quote! { &self }, quote! { &*self.0 }),
+        Some(ReceiverArg::Arc) => (
+            quote! { self: ::std::sync::Arc<Self> },
+            quote! { ::std::sync::Arc::clone(&self.0) },
+        ),
+        None => {
+            return Err(syn::Error::new(
+                sig.span,
+                "callback interface methods must take &self as their first argument",
+            ));
+        }
+    };
+    let params = sig.params();
+    let arg_idents = sig.args.iter().map(|a| &a.ident);
+    Ok(if sig.is_async {
+        quote! {
+            async fn #ident(#self_param, #(#params),*) -> #return_ty {
+                #trait_ident::#ident(#self_arg, #(#arg_idents),*).await
+            }
+        }
+    } else {
+        quote! {
+            fn #ident(#self_param, #(#params),*) -> #return_ty {
+                #trait_ident::#ident(#self_arg, #(#arg_idents),*)
+            }
+        }
+    })
+}
+
 pub fn trait_impl_ident(trait_name: &str) -> Ident {
     Ident::new(
         &format!("UniFFICallbackHandler{trait_name}"),
@@ -247,6 +342,7 @@ pub(super) fn metadata_items(
     items: &[ImplItem],
     module_path: &str,
     docstring: String,
+    tag: &str,
 ) -> syn::Result<Vec<TokenStream>> {
     let trait_name = ident_to_string(self_ident);
     let callback_interface_items = create_metadata_items(
@@ -257,6 +353,7 @@ pub(super) fn metadata_items(
                 .concat_str(#module_path)
                 .concat_str(#trait_name)
                 .concat_long_str(#docstring)
+                .concat_str(#tag)
         },
         None,
     );
@@ -0,0 +1,158 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Expr, ExprLit, ExprUnary, Lit, UnOp};
+
+use super::item::TraitConstant;
+use crate::{
+    ffiops,
+    util::{create_metadata_items, ident_to_string, mod_path},
+};
+
+/// Check the type of an exported constant
+///
+/// Only numbers, booleans and strings can be exported, since those are the types that foreign
+/// languages can declare constants of.  Returns the type to use for the metadata, which is
+/// `String` for `&str` constants.
+pub(super) fn constant_type(ty: &syn::Type) -> syn::Result<syn::Type> {
+    const SUPPORTED: &[&str] = &[
+        "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "f32", "f64", "bool",
+    ];
+    match ty {
+        syn::Type::Path(p)
+            if p.qself.is_none()
+                && p.path
+                    .get_ident()
+                    .is_some_and(|id| SUPPORTED.contains(&id.to_string().as_str())) =>
+        {
+            Ok(ty.clone())
+        }
+        syn::Type::Reference(r)
+            if r.mutability.is_none()
+                && matches!(&*r.elem, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("str")) =>
+        {
+            Ok(syn::parse_quote! { ::std::string::String })
+        }
+        _ => Err(syn::Error::new_spanned(
+            ty,
+            "only constants of number, `bool` and `&str` types can be exported",
+        )),
+    }
+}
+
+/// Metadata calls for the value of an exported constant
+///
+/// The value must be a literal, optionally negated, since the bindings declare the constant
+/// with the same value rather than calling into Rust for it.
+pub(super) fn constant_value_metadata_calls(expr: &Expr) -> syn::Result<TokenStream> {
+    let (lit, negated) = match expr {
+        Expr::Lit(ExprLit { lit, .. }) => (lit, false),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) if matches!(lit, Lit::Int(_) | Lit::Float(_)) => {
+                (lit, true)
+            }
+            _ => return Err(not_a_literal(expr)),
+        },
+        _ => return Err(not_a_literal(expr)),
+    };
+    let sign = if negated { "-" } else { "" };
+    match lit {
+        Lit::Int(i) if !i.suffix().is_empty() => Err(syn::Error::new_spanned(
+            i,
+            "integer literals with suffix not supported here",
+        )),
+        Lit::Float(f) if !f.suffix().is_empty() => Err(syn::Error::new_spanned(
+            f,
+            "float literals with suffix not supported here",
+        )),
+        Lit::Int(i) => {
+            let digits = format!("{sign}{}", i.base10_digits());
+            Ok(quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_INT)
+                .concat_str(#digits)
+            })
+        }
+        Lit::Float(f) => {
+            let digits = format!("{sign}{}", f.base10_digits());
+            Ok(quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_FLOAT)
+                .concat_str(#digits)
+            })
+        }
+        Lit::Str(s) => Ok(quote! {
+            .concat_value(::uniffi::metadata::codes::LIT_STR)
+            .concat_str(#s)
+        }),
+        Lit::Bool(b) => Ok(quote! {
+            .concat_value(::uniffi::metadata::codes::LIT_BOOL)
+            .concat_bool(#b)
+        }),
+        _ => Err(not_a_literal(expr)),
+    }
+}
+
+fn not_a_literal(expr: &Expr) -> syn::Error {
+    syn::Error::new_spanned(
+        expr,
+        "exported constants must be a number, `bool` or string literal",
+    )
+}
+
+pub(super) fn gen_constant_metadata(
+    ident: &Ident,
+    ty: &syn::Type,
+    value_metadata_calls: TokenStream,
+    docstring: &str,
+    tag: &str,
+) -> syn::Result<TokenStream> {
+    let mod_path = mod_path()?;
+    let name = ident_to_string(ident);
+    let type_id_meta = ffiops::type_id_meta(ty);
+    Ok(create_metadata_items(
+        "constant",
+        &name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONSTANT)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat(#type_id_meta)
+                #value_metadata_calls
+                .concat_long_str(#docstring)
+                .concat_str(#tag)
+        },
+        None,
+    ))
+}
+
+pub(super) fn gen_trait_constant_metadata(
+    trait_ident: &Ident,
+    constant: &TraitConstant,
+) -> syn::Result<TokenStream> {
+    let mod_path = mod_path()?;
+    let trait_name = ident_to_string(trait_ident);
+    let name = ident_to_string(&constant.ident);
+    let type_id_meta = ffiops::type_id_meta(&constant.ty);
+    let value_metadata_calls = &constant.value_metadata_calls;
+    let docstring = &constant.docstring;
+    Ok(create_metadata_items(
+        "trait_constant",
+        &format!("{trait_name}_{name}"),
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_CONSTANT)
+                .concat_str(#mod_path)
+                .concat_str(#trait_name)
+                .concat_str(#name)
+                .concat(#type_id_meta)
+                #value_metadata_calls
+                .concat_long_str(#docstring)
+        },
+        None,
+    ))
+}
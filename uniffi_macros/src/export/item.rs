@@ -8,9 +8,11 @@ use proc_macro2::{Ident, Span};
 use quote::ToTokens;
 
 use super::attributes::{
-    ExportFnArgs, ExportImplArgs, ExportStructArgs, ExportTraitArgs, ExportedImplFnAttributes,
+    ExportConstArgs, ExportFnArgs, ExportImplArgs, ExportStructArgs, ExportTraitArgs,
+    ExportedImplFnAttributes,
 };
-use crate::util::extract_docstring;
+use super::constant::{constant_type, constant_value_metadata_calls};
+use crate::util::{check_unique_names, extract_cfg_attrs, extract_docstring};
 use uniffi_meta::UniffiTraitDiscriminants;
 
 pub(super) enum ExportItem {
@@ -26,6 +28,7 @@ pub(super) enum ExportItem {
     Trait {
         self_ident: Ident,
         items: Vec<ImplItem>,
+        constants: Vec<TraitConstant>,
         with_foreign: bool,
         callback_interface_only: bool,
         docstring: String,
@@ -35,10 +38,17 @@ pub(super) enum ExportItem {
         self_ident: Ident,
         uniffi_traits: Vec<UniffiTraitDiscriminants>,
     },
+    Constant {
+        ident: Ident,
+        ty: syn::Type,
+        value_metadata_calls: proc_macro2::TokenStream,
+        docstring: String,
+        args: ExportConstArgs,
+    },
 }
 
 impl ExportItem {
-    pub fn new(item: syn::Item, attr_args: TokenStream) -> syn::Result<Self> {
+    pub fn new(item: syn::Item, attr_args: TokenStream, udl_mode: bool) -> syn::Result<Self> {
         match item {
             syn::Item::Fn(item) => {
                 let args: ExportFnArgs = syn::parse(attr_args)?;
@@ -47,17 +57,28 @@ impl ExportItem {
                 Ok(Self::Function { sig, args })
             }
             syn::Item::Impl(item) => Self::from_impl(item, attr_args),
-            syn::Item::Trait(item) => Self::from_trait(item, attr_args),
+            syn::Item::Trait(item) => Self::from_trait(item, attr_args, udl_mode),
             syn::Item::Struct(item) => Self::from_struct(item, attr_args),
-            // FIXME: Support const / static?
+            syn::Item::Const(item) => Self::from_const(item, attr_args),
             _ => Err(syn::Error::new(
                 Span::call_site(),
-                "unsupported item: only functions and impl \
+                "unsupported item: only functions, constants and impl \
                  blocks may be annotated with this attribute",
             )),
         }
     }
 
+    fn from_const(item: syn::ItemConst, attr_args: TokenStream) -> syn::Result<Self> {
+        let args: ExportConstArgs = syn::parse(attr_args)?;
+        Ok(Self::Constant {
+            ty: constant_type(&item.ty)?,
+            value_metadata_calls: constant_value_metadata_calls(&item.expr)?,
+            docstring: extract_docstring(&item.attrs)?,
+            ident: item.ident,
+            args,
+        })
+    }
+
     pub fn from_impl(item: syn::ItemImpl, attr_args: TokenStream) -> syn::Result<Self> {
         let args: ExportImplArgs = syn::parse(attr_args)?;
         if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
@@ -101,26 +122,49 @@ impl ExportItem {
                 };
 
                 let docstring = extract_docstring(&impl_fn.attrs)?;
-                let attrs = ExportedImplFnAttributes::new(&impl_fn.attrs)?;
+                let cfg_attrs = extract_cfg_attrs(&impl_fn.attrs);
+                let mut attrs = ExportedImplFnAttributes::new(&impl_fn.attrs)?;
+                if attrs.args.tag.is_none() {
+                    attrs.args.tag = args.tag.clone();
+                }
                 let item = if attrs.constructor {
-                    ImplItem::Constructor(FnSignature::new_constructor(
+                    let mut sig = FnSignature::new_constructor(
                         self_ident.clone(),
                         impl_fn.sig,
                         attrs.args,
                         docstring,
-                    )?)
+                    )?;
+                    sig.cfg_attrs = cfg_attrs;
+                    ImplItem::Constructor(sig)
                 } else {
-                    ImplItem::Method(FnSignature::new_method(
+                    let mut sig = FnSignature::new_method(
                         self_ident.clone(),
                         impl_fn.sig,
                         attrs.args,
                         docstring,
-                    )?)
+                    )?;
+                    sig.cfg_attrs = cfg_attrs;
+                    ImplItem::Method(sig)
                 };
 
                 Ok(item)
             })
-            .collect::<syn::Result<_>>()?;
+            .collect::<syn::Result<Vec<_>>>()?;
+        // Renames can make two constructors or methods share a foreign name.  Items with `cfg`
+        // attributes are left out, since they may never be compiled together.
+        let names = |constructors: bool| {
+            items.iter().filter_map(move |item| match item {
+                ImplItem::Constructor(sig) | ImplItem::Method(sig)
+                    if matches!(item, ImplItem::Constructor(_)) == constructors
+                        && sig.cfg_attrs.is_empty() =>
+                {
+                    Some((sig.name.clone(), sig.name_span))
+                }
+                _ => None,
+            })
+        };
+        check_unique_names("constructor", names(true))?;
+        check_unique_names("method", names(false))?;
 
         Ok(Self::Impl {
             items,
@@ -129,7 +173,11 @@ impl ExportItem {
         })
     }
 
-    fn from_trait(item: syn::ItemTrait, attr_args: TokenStream) -> syn::Result<Self> {
+    fn from_trait(
+        item: syn::ItemTrait,
+        attr_args: TokenStream,
+        udl_mode: bool,
+    ) -> syn::Result<Self> {
         let args: ExportTraitArgs = syn::parse(attr_args)?;
         let with_foreign = args.callback_interface.is_some() || args.with_foreign.is_some();
         let callback_interface_only = args.callback_interface.is_some();
@@ -141,36 +189,85 @@ impl ExportItem {
             ));
         }
 
+        // `dyn Trait` only works with async methods when the trait is wrapped with `async_trait`,
+        // which must be expanded after us.  In UDL mode the trait is copied from the UDL, without
+        // the attributes of the Rust trait.
+        let has_async_trait = item.attrs.iter().any(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "async_trait")
+        });
+        if !udl_mode && !has_async_trait {
+            if let Some(asyncness) = item.items.iter().find_map(|item| match item {
+                syn::TraitItem::Fn(tim) => tim.sig.asyncness,
+                _ => None,
+            }) {
+                return Err(syn::Error::new_spanned(
+                    asyncness,
+                    "exported traits with async methods must also be annotated with \
+                     `#[async_trait::async_trait]`, below `#[uniffi::export]`",
+                ));
+            }
+        }
+
         let self_ident = item.ident.to_owned();
         let docstring = extract_docstring(&item.attrs)?;
-        let items = item
-            .items
+        let mut constants = vec![];
+        let mut tims = vec![];
+        for item in item.items {
+            match item {
+                syn::TraitItem::Fn(tim) => tims.push(tim),
+                syn::TraitItem::Const(tic) => constants.push(TraitConstant::new(tic)?),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "only fn's and constants are supported in traits annotated with \
+                         uniffi::export",
+                    ));
+                }
+            }
+        }
+        let items = tims
             .into_iter()
             .enumerate()
-            .map(|(i, item)| {
-                let tim = match item {
-                    syn::TraitItem::Fn(tim) => tim,
-                    _ => {
-                        return Err(syn::Error::new_spanned(
-                            item,
-                            "only fn's are supported in traits annotated with uniffi::export",
-                        ));
-                    }
-                };
-
+            .map(|(i, tim)| {
+                // Each method is also part of the generated vtable and trait impls, and its index
+                // is in the metadata, so only the whole trait can be conditionally compiled.
+                if let Some(cfg_attr) = extract_cfg_attrs(&tim.attrs).first() {
+                    return Err(syn::Error::new_spanned(
+                        cfg_attr,
+                        "`cfg` attributes are not supported on the methods of exported traits",
+                    ));
+                }
                 let docstring = extract_docstring(&tim.attrs)?;
                 let attrs = ExportedImplFnAttributes::new(&tim.attrs)?;
+                // Like `cfg`, a tag which left a method out would change the vtable.
+                if let Some(tag) = &attrs.args.tag {
+                    return Err(syn::Error::new_spanned(
+                        tag,
+                        "tags are not supported on the methods of exported traits, tag the \
+                         trait instead",
+                    ));
+                }
                 let item = if attrs.constructor {
                     return Err(syn::Error::new_spanned(
                         tim,
-                        "exported traits can not have constructors",
+                        format!(
+                            "exported traits can not have constructors, export a function \
+                             returning `Arc<dyn {self_ident}>` instead"
+                        ),
                     ));
                 } else {
+                    // Foreign implementations can only use the default implementations of sync
+                    // methods, async ones must still be implemented.
+                    let has_default = tim.default.is_some() && tim.sig.asyncness.is_none();
                     ImplItem::Method(FnSignature::new_trait_method(
                         self_ident.clone(),
                         tim.sig,
                         ExportFnArgs::default(),
                         i as u32,
+                        has_default,
                         docstring,
                     )?)
                 };
@@ -181,6 +278,7 @@ impl ExportItem {
 
         Ok(Self::Trait {
             items,
+            constants,
             self_ident,
             with_foreign,
             callback_interface_only,
@@ -205,6 +303,39 @@ impl ExportItem {
     }
 }
 
+/// An associated constant of an exported trait
+///
+/// The constant needs a default value, which the bindings declare on the generated interface.
+pub(super) struct TraitConstant {
+    pub ident: Ident,
+    pub ty: syn::Type,
+    pub value_metadata_calls: proc_macro2::TokenStream,
+    pub docstring: String,
+}
+
+impl TraitConstant {
+    fn new(item: syn::TraitItemConst) -> syn::Result<Self> {
+        if let Some(cfg_attr) = extract_cfg_attrs(&item.attrs).first() {
+            return Err(syn::Error::new_spanned(
+                cfg_attr,
+                "`cfg` attributes are not supported on the constants of exported traits",
+            ));
+        }
+        let Some((_, expr)) = &item.default else {
+            return Err(syn::Error::new_spanned(
+                &item,
+                "exported trait constants need a default value, which is what the bindings declare",
+            ));
+        };
+        Ok(Self {
+            ty: constant_type(&item.ty)?,
+            value_metadata_calls: constant_value_metadata_calls(expr)?,
+            docstring: extract_docstring(&item.attrs)?,
+            ident: item.ident,
+        })
+    }
+}
+
 pub(super) enum ImplItem {
     Constructor(FnSignature),
     Method(FnSignature),
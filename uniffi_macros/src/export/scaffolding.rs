@@ -2,8 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
 use std::iter;
 
 use super::attributes::AsyncRuntime;
@@ -57,10 +57,66 @@ pub(super) fn gen_constructor_scaffolding(
         sig.metadata_items()
             .unwrap_or_else(syn::Error::into_compile_error)
     });
+    let udl_constructors_check = (!udl_mode && sig.is_async).then(|| udl_constructors_check(&sig));
+    let unique_name_check = (!udl_mode).then(|| unique_name_check(&sig)).flatten();
     let scaffolding_func = gen_ffi_function(&sig, ar, udl_mode)?;
     Ok(quote! {
         #scaffolding_func
         #metadata_items
+        #udl_constructors_check
+        #unique_name_check
+    })
+}
+
+// Fail to compile if another constructor or method of the type has the same foreign name, which
+// can happen when they're exported from different impl blocks.  Their scaffolding functions would
+// have the same symbol, so we make that a conflicting impl error which points at both of them.
+//
+// UDL checks its own interfaces for duplicate names, and its scaffolding can export methods for
+// types from other crates, which we can't implement a uniffi trait for.
+fn unique_name_check(sig: &FnSignature) -> Option<TokenStream> {
+    let (trait_name, self_ident) = match &sig.kind {
+        FnKind::Constructor { self_ident } => ("ExportedConstructorName", self_ident),
+        FnKind::Method { self_ident } => ("ExportedMethodName", self_ident),
+        _ => return None,
+    };
+    let trait_ident = Ident::new(trait_name, sig.name_span);
+    let name_hash = name_hash(&sig.name);
+    Some(quote_spanned! { sig.name_span =>
+        impl ::uniffi::#trait_ident<#name_hash> for #self_ident {}
+    })
+}
+
+// FNV-1a hash of a foreign name, as the const parameter of the `unique_name_check` traits
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Fail to compile if the object also has constructors declared in UDL, since it's ambiguous which
+// one the bindings should use.
+//
+// The UDL scaffolding defines the `UNIFFI_HAS_UDL_CONSTRUCTORS` associated const for objects with
+// constructors, which takes precedence over the one from the blanket trait impl.
+fn udl_constructors_check(sig: &FnSignature) -> Option<TokenStream> {
+    let FnKind::Constructor { self_ident } = &sig.kind else {
+        return None;
+    };
+    let message = format!(
+        "async constructor `{}` can't be exported for `{self_ident}`, which has constructors declared in UDL",
+        sig.ident
+    );
+    Some(quote_spanned! { sig.span =>
+        const _: () = {
+            trait UniffiNoUdlConstructors {
+                const UNIFFI_HAS_UDL_CONSTRUCTORS: bool = false;
+            }
+            impl<T: ?Sized> UniffiNoUdlConstructors for T {}
+            if <#self_ident>::UNIFFI_HAS_UDL_CONSTRUCTORS {
+                ::std::panic!(#message);
+            }
+        };
     })
 }
 
@@ -69,22 +125,24 @@ pub(super) fn gen_method_scaffolding(
     ar: Option<&AsyncRuntime>,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
-    let scaffolding_func = if sig.receiver.is_none() {
+    // Methods of exported impl blocks can be static, but trait methods always need a receiver.
+    if sig.receiver.is_none() && matches!(sig.kind, FnKind::TraitMethod { .. }) {
         return Err(syn::Error::new(
             sig.span,
             "associated functions are not currently supported",
         ));
-    } else {
-        gen_ffi_function(&sig, ar, udl_mode)?
-    };
+    }
+    let scaffolding_func = gen_ffi_function(&sig, ar, udl_mode)?;
 
     let metadata_items = (!udl_mode).then(|| {
         sig.metadata_items()
             .unwrap_or_else(syn::Error::into_compile_error)
     });
+    let unique_name_check = (!udl_mode).then(|| unique_name_check(&sig)).flatten();
     Ok(quote! {
         #scaffolding_func
         #metadata_items
+        #unique_name_check
     })
 }
 
@@ -131,10 +189,12 @@ impl ScaffoldingBits {
         udl_mode: bool,
     ) -> Self {
         let ident = &sig.ident;
+        // Objects are lifted as `Arc<Self>`, records and enums as `Self`, since they're passed by
+        // value.
         let self_type = if is_trait {
             quote! { ::std::sync::Arc<dyn #self_ident> }
         } else {
-            quote! { ::std::sync::Arc<#self_ident> }
+            ffiops::lift_ref_type(self_ident)
         };
         let lift_type = ffiops::lift_type(&self_type);
         let try_lift = ffiops::try_lift(&self_type);
@@ -187,6 +247,61 @@ impl ScaffoldingBits {
         }
     }
 
+    fn new_for_static_method(sig: &FnSignature, self_ident: &Ident, udl_mode: bool) -> Self {
+        let ident = &sig.ident;
+        let call_params = sig.rust_call_params(false);
+        let rust_fn_call = quote! { <#self_ident>::#ident(#call_params) };
+        // UDL mode adds an extra conversion (#1749)
+        let convert_result = if udl_mode && sig.looks_like_result {
+            quote! { uniffi_result.map_err(::std::convert::Into::into) }
+        } else {
+            quote! { uniffi_result }
+        };
+
+        Self {
+            param_names: sig.scaffolding_param_names().collect(),
+            param_types: sig.scaffolding_param_types().collect(),
+            lift_closure: sig.lift_closure(None),
+            rust_fn_call,
+            convert_result,
+        }
+    }
+
+    fn new_for_default_method(
+        sig: &FnSignature,
+        self_type: &TokenStream,
+        default_impl_ident: &Ident,
+    ) -> Self {
+        let ident = &sig.ident;
+        let lift_type = ffiops::lift_type(self_type);
+        let try_lift = ffiops::try_lift(self_type);
+        // Wrap the foreign implementation in the type which uses the default implementation of
+        // this method.  It's in an `Arc` so that methods which take `self: Arc<Self>` work too.
+        let lift_closure = sig.lift_closure(Some(quote! {
+            match #try_lift(uniffi_self_lowered) {
+                ::std::result::Result::Ok(v) => ::std::sync::Arc::new(
+                    #default_impl_ident(::std::convert::Into::into(v)),
+                ),
+                ::std::result::Result::Err(e) => {
+                    return ::std::result::Result::Err(("self", e));
+                }
+            }
+        }));
+        let call_params = sig.rust_call_params(true);
+
+        Self {
+            param_names: iter::once(quote! { uniffi_self_lowered })
+                .chain(sig.scaffolding_param_names())
+                .collect(),
+            param_types: iter::once(quote! { #lift_type })
+                .chain(sig.scaffolding_param_types())
+                .collect(),
+            lift_closure,
+            rust_fn_call: quote! { uniffi_args.0.#ident(#call_params) },
+            convert_result: quote! { uniffi_result },
+        }
+    }
+
     fn new_for_constructor(sig: &FnSignature, self_ident: &Ident, udl_mode: bool) -> Self {
         let ident = &sig.ident;
         let call_params = sig.rust_call_params(false);
@@ -220,14 +335,11 @@ pub(super) fn gen_ffi_function(
     ar: Option<&AsyncRuntime>,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
-    let ScaffoldingBits {
-        param_names,
-        param_types,
-        lift_closure,
-        rust_fn_call,
-        convert_result,
-    } = match &sig.kind {
+    let mut bits = match &sig.kind {
         FnKind::Function => ScaffoldingBits::new_for_function(sig, udl_mode),
+        FnKind::Method { self_ident } if sig.receiver.is_none() => {
+            ScaffoldingBits::new_for_static_method(sig, self_ident, udl_mode)
+        }
         FnKind::Method { self_ident } => {
             ScaffoldingBits::new_for_method(sig, self_ident, false, udl_mode)
         }
@@ -238,6 +350,48 @@ pub(super) fn gen_ffi_function(
             ScaffoldingBits::new_for_constructor(sig, self_ident, udl_mode)
         }
     };
+    if sig.catch_all {
+        let convert_result = &bits.convert_result;
+        bits.convert_result = quote! { ::uniffi::CatchAllResult::into_catch_all(#convert_result) };
+    }
+    Ok(ffi_function(
+        sig,
+        &sig.scaffolding_fn_ident()?,
+        bits,
+        ar,
+        udl_mode,
+    ))
+}
+
+/// Generate the scaffolding function which runs the default implementation of a trait method
+///
+/// Foreign implementations of the trait call this instead of implementing the method.  `self_type`
+/// is the type foreign implementations are lifted as and `default_impl_ident` is a tuple struct
+/// wrapping an `Arc<dyn Trait>`, which implements the trait without overriding the method.
+pub(super) fn gen_default_method_ffi_function(
+    sig: &FnSignature,
+    self_type: &TokenStream,
+    default_impl_ident: &Ident,
+) -> TokenStream {
+    let ffi_ident = Ident::new(&sig.default_fn_symbol_name(), Span::call_site());
+    let bits = ScaffoldingBits::new_for_default_method(sig, self_type, default_impl_ident);
+    ffi_function(sig, &ffi_ident, bits, None, false)
+}
+
+fn ffi_function(
+    sig: &FnSignature,
+    ffi_ident: &Ident,
+    bits: ScaffoldingBits,
+    ar: Option<&AsyncRuntime>,
+    udl_mode: bool,
+) -> TokenStream {
+    let ScaffoldingBits {
+        param_names,
+        param_types,
+        lift_closure,
+        rust_fn_call,
+        convert_result,
+    } = bits;
     // Scaffolding functions are logically `pub`, but we don't use that in UDL mode since UDL has
     // historically not required types to be `pub`
     let vis = match udl_mode {
@@ -245,16 +399,15 @@ pub(super) fn gen_ffi_function(
         true => quote! {},
     };
 
-    let ffi_ident = sig.scaffolding_fn_ident()?;
     let name = &sig.name;
     let return_ty = &sig.return_ty;
     let ffi_return_ty = ffiops::lower_return_type(return_ty);
     let lower_return = ffiops::lower_return(return_ty);
     let handle_failed_lift = ffiops::lower_return_handle_failed_lift(return_ty);
 
-    Ok(if !sig.is_async {
+    if !sig.is_async {
         let scaffolding_fn_ffi_buffer_version =
-            ffi_buffer_scaffolding_fn(&ffi_ident, &ffi_return_ty, &param_types, true);
+            ffi_buffer_scaffolding_fn(ffi_ident, &ffi_return_ty, &param_types, true);
         quote! {
             #[doc(hidden)]
             #[no_mangle]
@@ -285,7 +438,7 @@ pub(super) fn gen_ffi_function(
             future_expr = quote! { ::uniffi::deps::async_compat::Compat::new(#future_expr) }
         }
         let scaffolding_fn_ffi_buffer_version =
-            ffi_buffer_scaffolding_fn(&ffi_ident, &quote! { ::uniffi::Handle}, &param_types, false);
+            ffi_buffer_scaffolding_fn(ffi_ident, &quote! { ::uniffi::Handle}, &param_types, false);
 
         quote! {
             #[doc(hidden)]
@@ -311,7 +464,7 @@ pub(super) fn gen_ffi_function(
 
             #scaffolding_fn_ffi_buffer_version
         }
-    })
+    }
 }
 
 #[cfg(feature = "scaffolding-ffi-buffer-fns")]
@@ -323,7 +476,7 @@ fn ffi_buffer_scaffolding_fn(
 ) -> TokenStream {
     let fn_name = fn_ident.to_string();
     let ffi_buffer_fn_name = uniffi_meta::ffi_buffer_symbol_name(&fn_name);
-    let ident = Ident::new(&ffi_buffer_fn_name, proc_macro2::Span::call_site());
+    let ident = Ident::new(&ffi_buffer_fn_name, Span::call_site());
     let type_list: Vec<_> = param_types.iter().map(|ty| quote! { #ty }).collect();
     if has_rust_call_status {
         quote! {
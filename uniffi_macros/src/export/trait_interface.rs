@@ -28,11 +28,20 @@ pub(super) fn gen_trait_scaffolding(
     if let Some(rt) = args.async_runtime {
         return Err(syn::Error::new_spanned(rt, "not supported for traits"));
     }
+    let tag = args.tag.map(|tag| tag.value()).unwrap_or_default();
     let trait_name = ident_to_string(&self_ident);
     let trait_impl = with_foreign.then(|| {
         callback_interface::trait_impl(mod_path, &self_ident, &items)
             .unwrap_or_else(|e| e.into_compile_error())
     });
+    let default_method_tokens = (with_foreign && !udl_mode).then(|| {
+        callback_interface::default_method_scaffolding(
+            &self_ident,
+            &items,
+            quote! { ::std::sync::Arc<dyn #self_ident> },
+        )
+        .unwrap_or_else(|e| e.into_compile_error())
+    });
 
     let clone_fn_ident = Ident::new(
         &uniffi_meta::clone_fn_symbol_name(mod_path, &trait_name),
@@ -104,7 +113,7 @@ pub(super) fn gen_trait_scaffolding(
         } else {
             ObjectImpl::Trait
         };
-        interface_meta_static_var(&self_ident, imp, mod_path, docstring.as_str())
+        interface_meta_static_var(&self_ident, imp, mod_path, docstring.as_str(), &tag)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
     let ffi_converter_tokens = ffi_converter(mod_path, &self_ident, udl_mode, with_foreign);
@@ -113,6 +122,7 @@ pub(super) fn gen_trait_scaffolding(
         #meta_static_var
         #helper_fn_tokens
         #trait_impl
+        #default_method_tokens
         #impl_tokens
         #ffi_converter_tokens
     })
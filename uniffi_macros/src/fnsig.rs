@@ -6,11 +6,19 @@ use crate::{
     default::{default_value_metadata_calls, DefaultValue},
     export::{AsyncRuntime, DefaultMap, ExportFnArgs},
     ffiops,
-    util::{create_metadata_items, ident_to_string, mod_path, try_metadata_value_from_usize},
+    type_check::{check_types, TypePosition},
+    util::{
+        check_unique_names, crate_catch_all, create_callable_metadata_items, create_metadata_items,
+        either_attribute_arg, ident_to_string, kw, mod_path, parse_foreign_name,
+        try_metadata_value_from_usize, AttributeSliceExt, DeprecatedArg, UniffiAttributeArgs,
+    },
 };
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{spanned::Spanned, FnArg, Ident, Pat, Receiver, ReturnType, Type};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::ParseStream, spanned::Spanned, Attribute, FnArg, GenericArgument, Ident, LitStr, Pat,
+    PathArguments, Receiver, ReturnType, Token, Type, TypeParamBound,
+};
 
 pub(crate) struct FnSignature {
     pub kind: FnKind,
@@ -20,9 +28,18 @@ pub(crate) struct FnSignature {
     pub ident: Ident,
     // The foreign name for this function, usually == ident.
     pub name: String,
+    // The span of the `name` argument, or of the ident if the function isn't renamed.
+    pub name_span: Span,
     pub is_async: bool,
     pub async_runtime: Option<AsyncRuntime>,
     pub receiver: Option<ReceiverArg>,
+    // Does this method consume the object, invalidating the foreign handle to it?
+    pub consumes: bool,
+    // Is this the getter of a public field, generated by `#[derive(uniffi::Object)]`?
+    pub is_getter: bool,
+    // Is the error converted into the crate's `CatchAllError`?  If so, `return_ty` is the
+    // converted return type.
+    pub catch_all: bool,
     pub args: Vec<NamedArg>,
     pub return_ty: TokenStream,
     // Does this the return type look like a result?
@@ -30,6 +47,13 @@ pub(crate) struct FnSignature {
     // In general, it's not reliable because it fails for type aliases.
     pub looks_like_result: bool,
     pub docstring: String,
+    // The export tag, from `tag = "..."`, or empty if there isn't one.
+    pub tag: String,
+    // The `deprecated` or `deprecated = "..."` argument
+    pub deprecated: Option<DeprecatedArg>,
+    // The `#[cfg(...)]` attributes of a method in an exported impl block, which also apply to
+    // its scaffolding and metadata.
+    pub cfg_attrs: Vec<Attribute>,
 }
 
 impl FnSignature {
@@ -50,6 +74,24 @@ impl FnSignature {
         Self::new(FnKind::Method { self_ident }, sig, args, docstring)
     }
 
+    /// The getter of a public field of an object, which the bindings expose as a property named
+    /// after the field
+    pub(crate) fn new_getter(
+        self_ident: Ident,
+        sig: syn::Signature,
+        field_name: LitStr,
+        docstring: String,
+    ) -> syn::Result<Self> {
+        let args = ExportFnArgs {
+            name: Some(field_name),
+            ..ExportFnArgs::default()
+        };
+        Ok(Self {
+            is_getter: true,
+            ..Self::new_method(self_ident, sig, args, docstring)?
+        })
+    }
+
     pub(crate) fn new_constructor(
         self_ident: Ident,
         sig: syn::Signature,
@@ -64,10 +106,15 @@ impl FnSignature {
         sig: syn::Signature,
         args: ExportFnArgs,
         index: u32,
+        has_default: bool,
         docstring: String,
     ) -> syn::Result<Self> {
         Self::new(
-            FnKind::TraitMethod { self_ident, index },
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            },
             sig,
             args,
             docstring,
@@ -81,10 +128,46 @@ impl FnSignature {
         docstring: String,
     ) -> syn::Result<Self> {
         let span = sig.span();
+        check_types(
+            sig.inputs
+                .iter()
+                .filter_map(|input| match input {
+                    FnArg::Typed(p) => Some((&*p.ty, TypePosition::Argument)),
+                    FnArg::Receiver(_) => None,
+                })
+                .chain(match &sig.output {
+                    ReturnType::Type(_, ty) => Some((&**ty, TypePosition::Return)),
+                    ReturnType::Default => None,
+                }),
+        )?;
         let ident = sig.ident;
         let looks_like_result = looks_like_result(&sig.output);
+        let catch_all = match (&export_fn_args.catch_all, &sig.output) {
+            (Some(catch_all), ReturnType::Default) => {
+                return Err(syn::Error::new(
+                    catch_all.span,
+                    "`catch_all` functions must return `anyhow::Result<T>` or \
+                     `Result<T, Box<dyn Error + Send + Sync>>`",
+                ));
+            }
+            (Some(_), ReturnType::Type(..)) => true,
+            // Trait methods can be implemented on the foreign side, which can't return an
+            // `anyhow::Error`.
+            (None, ReturnType::Type(_, ty)) => {
+                !matches!(kind, FnKind::TraitMethod { .. })
+                    && crate_catch_all()
+                    && looks_like_catch_all_result(ty)
+            }
+            (None, ReturnType::Default) => false,
+        };
         let output = match sig.output {
             ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) if catch_all => quote_spanned! { ty.span() =>
+                ::std::result::Result<
+                    <#ty as ::uniffi::CatchAllResult>::Ok,
+                    ::uniffi::CatchAllError,
+                >
+            },
             ReturnType::Type(_, ty) => quote! { #ty },
         };
         let is_async = sig.asyncness.is_some();
@@ -114,6 +197,10 @@ impl FnSignature {
                 })
             })
             .collect::<syn::Result<Vec<_>>>()?;
+        check_unique_names(
+            "argument",
+            args.iter().map(|a| (a.name.clone(), a.name_span)),
+        )?;
 
         if let Some(ident) = export_fn_args.defaults.idents().first() {
             return Err(syn::Error::new(
@@ -121,6 +208,20 @@ impl FnSignature {
                 format!("Unknown default argument: {}", ident),
             ));
         }
+        // Not every language can have required parameters after optional ones.
+        if let Some(arg) = args
+            .iter()
+            .skip_while(|a| a.default.is_none())
+            .find(|a| a.default.is_none())
+        {
+            return Err(syn::Error::new(
+                arg.ident.span(),
+                format!(
+                    "argument `{}` needs a default, since it follows an argument with a default",
+                    arg.ident
+                ),
+            ));
+        }
 
         if !is_async && export_fn_args.async_runtime.is_some() {
             return Err(syn::Error::new(
@@ -129,21 +230,44 @@ impl FnSignature {
             ));
         }
 
+        if let Some(consumes) = &export_fn_args.consumes {
+            if !matches!(kind, FnKind::Method { .. }) || !matches!(receiver, Some(ReceiverArg::Arc))
+            {
+                return Err(syn::Error::new(
+                    consumes.span,
+                    "only methods taking `self: Arc<Self>` can consume the object",
+                ));
+            }
+        }
+
+        let (name, name_span) = match &export_fn_args.name {
+            Some(name) => (name.value(), name.span()),
+            None => (ident_to_string(&ident), ident.span()),
+        };
+
         Ok(Self {
             kind,
             span,
             mod_path: mod_path()?,
-            name: export_fn_args
-                .name
-                .unwrap_or_else(|| ident_to_string(&ident)),
+            name,
+            name_span,
             ident,
             is_async,
             async_runtime: export_fn_args.async_runtime,
             receiver,
+            consumes: export_fn_args.consumes.is_some(),
+            is_getter: false,
+            catch_all,
             args,
             return_ty: output,
             looks_like_result,
             docstring,
+            tag: export_fn_args
+                .tag
+                .map(|tag| tag.value())
+                .unwrap_or_default(),
+            deprecated: export_fn_args.deprecated,
+            cfg_attrs: Vec::new(),
         })
     }
 
@@ -215,6 +339,30 @@ impl FnSignature {
         Ok(Ident::new(&name, Span::call_site()))
     }
 
+    /// Does this trait method have a default implementation that foreign implementations can use?
+    pub fn has_default(&self) -> bool {
+        matches!(
+            self.kind,
+            FnKind::TraitMethod {
+                has_default: true,
+                ..
+            }
+        )
+    }
+
+    /// Name of the scaffolding function which runs the default implementation of this trait method
+    pub fn default_fn_symbol_name(&self) -> String {
+        let self_ident = match &self.kind {
+            FnKind::TraitMethod { self_ident, .. } => self_ident,
+            _ => unreachable!("only trait methods have default implementations"),
+        };
+        uniffi_meta::default_method_symbol_name(
+            &self.mod_path,
+            &ident_to_string(self_ident),
+            &self.name,
+        )
+    }
+
     /// Scaffolding parameters expressions for each of our arguments
     pub fn scaffolding_param_names(&self) -> impl Iterator<Item = TokenStream> + '_ {
         self.args.iter().map(|a| {
@@ -229,12 +377,21 @@ impl FnSignature {
 
     /// Generate metadata items for this function
     pub(crate) fn metadata_expr(&self) -> syn::Result<TokenStream> {
+        let checksum_expr = self.checksum_metadata_expr()?;
+        let deprecated = self.deprecated_metadata_calls();
+        Ok(quote! { #checksum_expr #deprecated })
+    }
+
+    /// The metadata for this function which its checksum is calculated from, which is everything
+    /// but its deprecation
+    fn checksum_metadata_expr(&self) -> syn::Result<TokenStream> {
         let Self {
             name,
             return_ty,
             is_async,
             mod_path,
             docstring,
+            tag,
             ..
         } = &self;
         let args_len = try_metadata_value_from_usize(
@@ -261,24 +418,36 @@ impl FnSignature {
                     #(#arg_metadata_calls)*
                     .concat(#type_id_meta)
                     .concat_long_str(#docstring)
+                    .concat_str(#tag)
             }),
 
             FnKind::Method { self_ident } => {
                 let object_name = ident_to_string(self_ident);
+                let consumes = self.consumes;
+                let is_static = self.receiver.is_none();
+                let is_getter = self.is_getter;
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::METHOD)
                         .concat_str(#mod_path)
                         .concat_str(#object_name)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#consumes)
+                        .concat_bool(#is_static)
+                        .concat_bool(#is_getter)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(#type_id_meta)
                         .concat_long_str(#docstring)
+                        .concat_str(#tag)
                 })
             }
 
-            FnKind::TraitMethod { self_ident, index } => {
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            } => {
                 let object_name = ident_to_string(self_ident);
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_METHOD)
@@ -287,6 +456,7 @@ impl FnSignature {
                         .concat_u32(#index)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#has_default)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(#type_id_meta)
@@ -306,51 +476,102 @@ impl FnSignature {
                         #(#arg_metadata_calls)*
                         .concat(#type_id_meta)
                         .concat_long_str(#docstring)
+                        .concat_str(#tag)
                 })
             }
         }
     }
 
+    fn deprecated_metadata_calls(&self) -> TokenStream {
+        DeprecatedArg::metadata_calls(self.deprecated.as_ref())
+    }
+
     pub(crate) fn metadata_items(&self) -> syn::Result<TokenStream> {
         let Self { name, .. } = &self;
-        match &self.kind {
-            FnKind::Function => Ok(create_metadata_items(
+        let catch_all_error = self
+            .catch_all
+            .then(|| self.catch_all_error_metadata_items())
+            .transpose()?;
+        let items = match &self.kind {
+            FnKind::Function => create_callable_metadata_items(
                 "func",
                 name,
-                self.metadata_expr()?,
-                Some(self.checksum_symbol_name()),
-            )),
+                self.checksum_metadata_expr()?,
+                self.deprecated_metadata_calls(),
+                self.checksum_symbol_name(),
+            ),
 
             FnKind::Method { self_ident } => {
                 let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
+                create_callable_metadata_items(
                     "method",
                     &format!("{object_name}_{name}"),
-                    self.metadata_expr()?,
-                    Some(self.checksum_symbol_name()),
-                ))
+                    self.checksum_metadata_expr()?,
+                    self.deprecated_metadata_calls(),
+                    self.checksum_symbol_name(),
+                )
             }
 
             FnKind::TraitMethod { self_ident, .. } => {
                 let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
+                create_callable_metadata_items(
                     "method",
                     &format!("{object_name}_{name}"),
-                    self.metadata_expr()?,
-                    Some(self.checksum_symbol_name()),
-                ))
+                    self.checksum_metadata_expr()?,
+                    self.deprecated_metadata_calls(),
+                    self.checksum_symbol_name(),
+                )
             }
 
             FnKind::Constructor { self_ident } => {
                 let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
+                create_callable_metadata_items(
                     "constructor",
                     &format!("{object_name}_{name}"),
-                    self.metadata_expr()?,
-                    Some(self.checksum_symbol_name()),
-                ))
+                    self.checksum_metadata_expr()?,
+                    self.deprecated_metadata_calls(),
+                    self.checksum_symbol_name(),
+                )
             }
-        }
+        };
+        Ok(quote! {
+            #items
+            #catch_all_error
+        })
+    }
+
+    /// Generate the metadata for the crate's `CatchAllError`
+    ///
+    /// Each `catch_all` function declares the error, rather than it being declared once for the
+    /// crate, and the copies are merged when the metadata is grouped.  The symbol is named after
+    /// the scaffolding function, so that it's unique.
+    fn catch_all_error_metadata_items(&self) -> syn::Result<TokenStream> {
+        let mod_path = &self.mod_path;
+        let name = uniffi_meta::CATCH_ALL_ERROR_NAME;
+        let variant = uniffi_meta::CATCH_ALL_ERROR_VARIANT;
+        let shape = uniffi_meta::EnumShape::Error { flat: true }.as_u8();
+        let docstring = "The error thrown by functions which are exported with `catch_all`.\n\n\
+                         Its message describes the original error and the errors which caused it.";
+        let metadata_expr = quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::ENUM)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat_value(#shape)
+                .concat_bool(false)
+                .concat_value(1)
+                .concat_str(#variant)
+                .concat_long_str("")
+                .concat_bool(false) // deprecated
+                .concat_bool(false) // non_exhaustive
+                .concat_long_str(#docstring)
+                .concat_str("")
+        };
+        Ok(create_metadata_items(
+            "catch_all_error",
+            &self.scaffolding_fn_ident()?.to_string(),
+            metadata_expr,
+            None,
+        ))
     }
 
     pub(crate) fn checksum_symbol_name(&self) -> String {
@@ -387,11 +608,41 @@ impl Arg {
     fn new(syn_arg: FnArg, defaults: &mut DefaultMap) -> syn::Result<Self> {
         let span = syn_arg.span();
         let kind = match syn_arg {
-            FnArg::Typed(p) => match *p.pat {
-                Pat::Ident(i) => Ok(ArgKind::Named(NamedArg::new(i.ident, &p.ty, defaults)?)),
-                _ => Err(syn::Error::new_spanned(p, "Argument name missing")),
-            },
-            FnArg::Receiver(receiver) => Ok(ArgKind::Receiver(ReceiverArg::from(receiver))),
+            FnArg::Typed(p) => {
+                let attrs = p.attrs.parse_uniffi_attr_args::<ArgAttributeArguments>()?;
+                match *p.pat {
+                    Pat::Ident(i) => {
+                        let mut arg = NamedArg::new(i.ident, &p.ty, defaults)?;
+                        if let Some(default) = attrs.default {
+                            if arg.default.is_some() {
+                                return Err(syn::Error::new_spanned(
+                                    default,
+                                    "default given in both the export attribute and `#[uniffi(default = ...)]`",
+                                ));
+                            }
+                            arg.default = Some(default);
+                        }
+                        if let Some(name) = attrs.name {
+                            arg.name = name.value();
+                            arg.name_span = name.span();
+                        }
+                        Ok(ArgKind::Named(arg))
+                    }
+                    _ => Err(syn::Error::new_spanned(p, "Argument name missing")),
+                }
+            }
+            FnArg::Receiver(receiver) => {
+                if let (Some((and, _)), Some(mutability)) =
+                    (&receiver.reference, &receiver.mutability)
+                {
+                    let self_token = &receiver.self_token;
+                    return Err(syn::Error::new_spanned(
+                        quote! { #and #mutability #self_token },
+                        "`&mut self` methods can't be exported, use `&self` or `self` instead",
+                    ));
+                }
+                Ok(ArgKind::Receiver(ReceiverArg::from(receiver)))
+            }
         }?;
 
         Ok(Self { span, kind })
@@ -423,9 +674,49 @@ impl From<Receiver> for ReceiverArg {
     }
 }
 
+/// Handle #[uniffi(...)] attributes for function arguments
+#[derive(Default)]
+struct ArgAttributeArguments {
+    default: Option<DefaultValue>,
+    // The foreign name of the argument, if it's different from the Rust name
+    name: Option<LitStr>,
+}
+
+impl UniffiAttributeArgs for ArgAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                default: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::name) {
+            let _: kw::name = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                name: Some(parse_foreign_name(input)?),
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            default: either_attribute_arg(self.default, other.default)?,
+            name: either_attribute_arg(self.name, other.name)?,
+        })
+    }
+}
+
 pub(crate) struct NamedArg {
     pub(crate) ident: Ident,
+    // The foreign name for this argument, usually == ident.
     pub(crate) name: String,
+    pub(crate) name_span: Span,
     pub(crate) ty: TokenStream,
     pub(crate) ref_type: Option<Type>,
     pub(crate) default: Option<DefaultValue>,
@@ -438,6 +729,7 @@ impl NamedArg {
                 let inner = &r.elem;
                 Self {
                     name: ident_to_string(&ident),
+                    name_span: ident.span(),
                     ty: ffiops::lift_ref_type(inner),
                     ref_type: Some(*inner.clone()),
                     default: defaults.remove(&ident),
@@ -446,6 +738,7 @@ impl NamedArg {
             }
             _ => Self {
                 name: ident_to_string(&ident),
+                name_span: ident.span(),
                 ty: quote! { #ty },
                 ref_type: None,
                 default: defaults.remove(&ident),
@@ -487,10 +780,78 @@ fn looks_like_result(return_type: &ReturnType) -> bool {
     false
 }
 
+// Does the return type look like `anyhow::Result<T>`, `Result<T, anyhow::Error>` or
+// `Result<T, Box<dyn Error + Send + Sync>>`?
+//
+// This is used for crates which turn on `catch_all` for all their functions.  Like
+// `looks_like_result`, it fails for type aliases, which need an explicit `catch_all` attribute.
+fn looks_like_catch_all_result(return_type: &Type) -> bool {
+    let Type::Path(p) = return_type else {
+        return false;
+    };
+    let segments: Vec<_> = p.path.segments.iter().collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return false;
+    };
+    if last.ident != "Result" {
+        return false;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return false;
+    };
+    let type_args: Vec<_> = args
+        .args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect();
+    match type_args.as_slice() {
+        [_] => matches!(parents.last(), Some(seg) if seg.ident == "anyhow"),
+        [_, Type::Path(err)] => is_catch_all_error_path(&err.path),
+        _ => false,
+    }
+}
+
+// Is this `anyhow::Error` or `Box<dyn Error + ...>`?
+fn is_catch_all_error_path(path: &syn::Path) -> bool {
+    let segments: Vec<_> = path.segments.iter().collect();
+    match segments.as_slice() {
+        [.., parent, last] if parent.ident == "anyhow" && last.ident == "Error" => true,
+        [.., last] if last.ident == "Box" => {
+            let PathArguments::AngleBracketed(args) = &last.arguments else {
+                return false;
+            };
+            let Some(GenericArgument::Type(Type::TraitObject(dyn_type))) = args.args.first() else {
+                return false;
+            };
+            dyn_type.bounds.iter().any(|bound| match bound {
+                TypeParamBound::Trait(t) => t
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|seg| seg.ident == "Error"),
+                _ => false,
+            })
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum FnKind {
     Function,
-    Constructor { self_ident: Ident },
-    Method { self_ident: Ident },
-    TraitMethod { self_ident: Ident, index: u32 },
+    Constructor {
+        self_ident: Ident,
+    },
+    Method {
+        self_ident: Ident,
+    },
+    TraitMethod {
+        self_ident: Ident,
+        index: u32,
+        // Does the method have a default implementation that foreign implementations can use?
+        has_default: bool,
+    },
 }
@@ -27,6 +27,7 @@ mod object;
 mod record;
 mod setup_scaffolding;
 mod test;
+mod type_check;
 mod util;
 
 use self::{
@@ -85,13 +86,54 @@ pub fn setup_scaffolding(tokens: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Re-export the scaffolding of UniFFI component crates from a wrapper crate
+///
+/// Invoke this once in a crate that bundles other UniFFI components into a single library,
+/// passing the crate names of the dependencies whose scaffolding should be re-exported:
+///
+/// ```rs
+/// uniffi::reexport_scaffolding!(crate_one, crate_two);
+/// ```
+///
+/// This works around [rust-lang#50007](https://github.com/rust-lang/rust/issues/50007), which
+/// can drop the scaffolding functions and metadata of the dependencies from the final library.
+#[proc_macro]
+pub fn reexport_scaffolding(tokens: TokenStream) -> TokenStream {
+    let crates = parse_macro_input!(
+        tokens with syn::punctuated::Punctuated::<Ident, Token![,]>::parse_terminated
+    );
+    let calls = crates.iter().map(|krate| {
+        quote! { #krate::uniffi_reexport_scaffolding!(); }
+    });
+    quote! { #(#calls)* }.into()
+}
+
 #[proc_macro_attribute]
 pub fn export(attr_args: TokenStream, input: TokenStream) -> TokenStream {
     do_export(attr_args, input, false)
 }
 
 fn do_export(attr_args: TokenStream, input: TokenStream, udl_mode: bool) -> TokenStream {
-    let copied_input = (!udl_mode).then(|| proc_macro2::TokenStream::from(input.clone()));
+    let copied_input = (!udl_mode).then(|| {
+        // Argument attributes are removed from the output and trait constants are moved out of
+        // the trait, but the input is copied as it is otherwise, so it's still output if it
+        // doesn't parse.
+        match syn::parse::<syn::Item>(input.clone()) {
+            Ok(mut item) => {
+                let stripped = export::strip_arg_attributes(&mut item);
+                let trait_constants = export::move_trait_constants(&mut item);
+                if stripped || trait_constants.is_some() {
+                    quote! {
+                        #item
+                        #trait_constants
+                    }
+                } else {
+                    proc_macro2::TokenStream::from(input.clone())
+                }
+            }
+            Err(_) => proc_macro2::TokenStream::from(input.clone()),
+        }
+    });
 
     let gen_output = || {
         let item = syn::parse(input)?;
@@ -113,14 +155,14 @@ pub fn derive_record(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(Enum)]
+#[proc_macro_derive(Enum, attributes(uniffi))]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
     expand_enum(parse_macro_input!(input), DeriveOptions::default())
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
 
-#[proc_macro_derive(Object)]
+#[proc_macro_derive(Object, attributes(uniffi))]
 pub fn derive_object(input: TokenStream) -> TokenStream {
     expand_object(parse_macro_input!(input), DeriveOptions::default())
         .unwrap_or_else(syn::Error::into_compile_error)
@@ -134,6 +176,18 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Generate the FFI traits and metadata for a type defined in another crate
+///
+/// The input is a copy of the type's definition, with the kind of type as the argument, like
+/// `#[uniffi::remote(Record)]`. The copy isn't part of the output: the type from the other crate
+/// must be in scope, and it's an error if the fields or variants don't match it.
+#[proc_macro_attribute]
+pub fn remote(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    derive::expand_remote(parse_macro_input!(attrs), parse_macro_input!(input))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Generate the `FfiConverter` implementation for a Custom Type - ie,
 /// for a `<T>` which implements `UniffiCustomTypeConverter`.
 #[proc_macro]
@@ -182,7 +236,8 @@ pub fn custom_newtype(tokens: TokenStream) -> TokenStream {
 // define the external type in the UDL file.
 //
 // With proc-macros this system isn't so natural.  Instead, we create a blanket implementation
-// for all UT and support for remote types is still TODO.
+// for all UT, except for remote types declared with `#[uniffi::remote]`, which only implement the
+// traits for the local tag like UDL-mode does.
 #[doc(hidden)]
 #[proc_macro_attribute]
 pub fn udl_derive(attrs: TokenStream, input: TokenStream) -> TokenStream {
@@ -242,10 +297,9 @@ pub fn include_scaffolding(udl_stem: TokenStream) -> TokenStream {
             // See https://bugzilla.mozilla.org/show_bug.cgi?id=1846223
             // In the future we should handle that by using the `track_path::path` API,
             // see https://github.com/rust-lang/rust/pull/84029
-            #[allow(dead_code)]
-            mod __unused {
-                const _: &[u8] = include_bytes!(#toml_path);
-            }
+            // This is an anonymous const so that crates with several UDL files can include the
+            // scaffolding for each of them.
+            const _: &[u8] = include_bytes!(#toml_path);
 
             include!(concat!(env!("OUT_DIR"), "/", #udl_stem, ".uniffi.rs"));
         }
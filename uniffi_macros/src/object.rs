@@ -1,10 +1,17 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
-use syn::DeriveInput;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::ParseStream, parse_quote_spanned, spanned::Spanned, Data, DeriveInput, Field, LitStr,
+};
 
 use crate::{
+    export::gen_getter_scaffolding,
     ffiops,
-    util::{create_metadata_items, extract_docstring, ident_to_string, mod_path},
+    fnsig::FnSignature,
+    util::{
+        create_metadata_items, either_attribute_arg, extract_docstring, ident_to_string, kw,
+        mod_path, parse_tag, AttributeSliceExt, UniffiAttributeArgs,
+    },
     DeriveOptions,
 };
 use uniffi_meta::ObjectImpl;
@@ -13,13 +20,34 @@ use uniffi_meta::ObjectImpl;
 struct ObjectItem {
     ident: Ident,
     docstring: String,
+    // The public fields which get a getter, from `#[uniffi(getters)]` or `#[uniffi(get)]`
+    getter_fields: Vec<Field>,
+    // The export tag, from `#[uniffi(tag = "...")]`, or empty
+    tag: String,
 }
 
 impl ObjectItem {
     fn new(input: DeriveInput) -> syn::Result<Self> {
+        let args = input
+            .attrs
+            .parse_uniffi_attr_args::<ObjectAttributeArguments>()?;
+        let getter_fields = match input.data {
+            Data::Struct(s) => getter_fields(s.fields, args.getters)?,
+            _ => match args.getters {
+                Some(getters) => {
+                    return Err(syn::Error::new(
+                        getters.span,
+                        "`getters` can only be used on structs",
+                    ))
+                }
+                None => Vec::new(),
+            },
+        };
         Ok(Self {
             ident: input.ident,
             docstring: extract_docstring(&input.attrs)?,
+            getter_fields,
+            tag: args.tag.map(|tag| tag.value()).unwrap_or_default(),
         })
     }
 
@@ -36,6 +64,98 @@ impl ObjectItem {
     }
 }
 
+/// The fields to generate getters for
+///
+/// With `getters`, that's every public named field.  Otherwise it's the fields with `get`, which
+/// must be public and named.
+fn getter_fields(fields: syn::Fields, getters: Option<kw::getters>) -> syn::Result<Vec<Field>> {
+    let mut getter_fields = Vec::new();
+    for field in fields {
+        let args = field
+            .attrs
+            .parse_uniffi_attr_args::<ObjectFieldAttributeArguments>()?;
+        let is_public = matches!(field.vis, syn::Visibility::Public(_));
+        match args.get {
+            Some(get) if field.ident.is_none() => {
+                return Err(syn::Error::new(
+                    get.span,
+                    "`get` can only be used on named fields",
+                ))
+            }
+            Some(get) if !is_public => {
+                return Err(syn::Error::new(
+                    get.span,
+                    "`get` can only be used on `pub` fields",
+                ))
+            }
+            Some(_) => getter_fields.push(field),
+            None if getters.is_some() && is_public && field.ident.is_some() => {
+                getter_fields.push(field)
+            }
+            None => (),
+        }
+    }
+    match getters {
+        Some(getters) if getter_fields.is_empty() => Err(syn::Error::new(
+            getters.span,
+            "`getters` needs at least one `pub` named field",
+        )),
+        _ => Ok(getter_fields),
+    }
+}
+
+/// Arguments of `#[uniffi(...)]` on an object
+#[derive(Default)]
+struct ObjectAttributeArguments {
+    // Generate getters for all the public fields
+    getters: Option<kw::getters>,
+    tag: Option<LitStr>,
+}
+
+impl UniffiAttributeArgs for ObjectAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(kw::tag) {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
+            })
+        } else {
+            Ok(Self {
+                getters: Some(input.parse()?),
+                ..Self::default()
+            })
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            getters: either_attribute_arg(self.getters, other.getters)?,
+            tag: either_attribute_arg(self.tag, other.tag)?,
+        })
+    }
+}
+
+/// Arguments of `#[uniffi(...)]` on the field of an object
+#[derive(Default)]
+struct ObjectFieldAttributeArguments {
+    // Generate a getter for the field
+    get: Option<kw::get>,
+}
+
+impl UniffiAttributeArgs for ObjectFieldAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        Ok(Self {
+            get: Some(input.parse()?),
+        })
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            get: either_attribute_arg(self.get, other.get)?,
+        })
+    }
+}
+
 pub fn expand_object(input: DeriveInput, options: DeriveOptions) -> syn::Result<TokenStream> {
     let module_path = mod_path()?;
     let object = ObjectItem::new(input)?;
@@ -55,10 +175,21 @@ pub fn expand_object(input: DeriveInput, options: DeriveOptions) -> syn::Result<
             ObjectImpl::Struct,
             &module_path,
             object.docstring(),
+            &object.tag,
         )
         .unwrap_or_else(syn::Error::into_compile_error)
     });
     let interface_impl = interface_impl(&object, &options);
+    let getters = if options.generate_metadata {
+        getters_impl(&object)?
+    } else if let Some(field) = object.getter_fields.first() {
+        return Err(syn::Error::new(
+            field.span(),
+            "getters can't be generated for objects declared in UDL",
+        ));
+    } else {
+        TokenStream::new()
+    };
 
     Ok(quote! {
         #[doc(hidden)]
@@ -91,6 +222,63 @@ pub fn expand_object(input: DeriveInput, options: DeriveOptions) -> syn::Result<
 
         #interface_impl
         #meta_static_var
+        #getters
+    })
+}
+
+/// Generate the getters of the object's public fields, with their scaffolding and metadata
+///
+/// The getters are methods of a trait which is private to the generated code, so they can't clash
+/// with the object's own methods.
+fn getters_impl(object: &ObjectItem) -> syn::Result<TokenStream> {
+    if object.getter_fields.is_empty() {
+        return Ok(TokenStream::new());
+    }
+    let ident = object.ident();
+    let mut getter_fns = Vec::new();
+    let mut scaffolding = Vec::new();
+    for field in &object.getter_fields {
+        let field_ident = field.ident.as_ref().expect("getter fields are named");
+        let field_name = ident_to_string(field_ident);
+        let getter_ident = format_ident!("uniffi_get_{field_name}");
+        let ty = &field.ty;
+        let sig: syn::Signature =
+            parse_quote_spanned! { field.span() => fn #getter_ident(&self) -> #ty };
+        scaffolding.push(gen_getter_scaffolding(FnSignature::new_getter(
+            ident.clone(),
+            sig.clone(),
+            LitStr::new(&field_name, field_ident.span()),
+            extract_docstring(&field.attrs)?,
+        )?)?);
+        getter_fns.push((sig, field));
+    }
+    let trait_fns = getter_fns.iter().map(|(sig, _)| quote! { #sig; });
+    // The field is cloned, with the span of its type so that rustc reports fields which aren't
+    // `Clone` there.
+    let impl_fns = getter_fns.iter().map(|(sig, field)| {
+        let field_ident = &field.ident;
+        let clone = quote_spanned! { field.ty.span() =>
+            ::std::clone::Clone::clone(&self.#field_ident)
+        };
+        quote! {
+            #sig {
+                #clone
+            }
+        }
+    });
+
+    Ok(quote! {
+        const _: () = {
+            trait UniffiGetters {
+                #(#trait_fns)*
+            }
+
+            impl UniffiGetters for #ident {
+                #(#impl_fns)*
+            }
+
+            #(#scaffolding)*
+        };
     })
 }
 
@@ -211,6 +399,7 @@ pub(crate) fn interface_meta_static_var(
     imp: ObjectImpl,
     module_path: &str,
     docstring: &str,
+    tag: &str,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
     let code = match imp {
@@ -227,6 +416,7 @@ pub(crate) fn interface_meta_static_var(
                 .concat_str(#module_path)
                 .concat_str(#name)
                 .concat_long_str(#docstring)
+                .concat_str(#tag)
         },
         None,
     ))
@@ -1,13 +1,18 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{parse::ParseStream, Data, DataStruct, DeriveInput, Field, Token};
+use syn::{
+    parse::ParseStream, spanned::Spanned, Data, DataStruct, DeriveInput, Field, Fields, Index,
+    LitStr, Token,
+};
 
 use crate::{
     default::{default_value_metadata_calls, DefaultValue},
     ffiops,
+    type_check::{check_types, TypePosition},
     util::{
-        create_metadata_items, either_attribute_arg, extract_docstring, ident_to_string, kw,
-        mod_path, try_metadata_value_from_usize, try_read_field, AttributeSliceExt,
+        check_unique_names, create_metadata_items, either_attribute_arg, extract_docstring,
+        ident_to_string, kw, mod_path, parse_foreign_name, parse_tag,
+        try_metadata_value_from_usize, try_read_field, AttributeSliceExt, DeprecatedArg,
         UniffiAttributeArgs,
     },
     DeriveOptions,
@@ -18,6 +23,9 @@ struct RecordItem {
     ident: Ident,
     record: DataStruct,
     docstring: String,
+    // The export tag, from `#[uniffi(tag = "...")]`, or empty
+    tag: String,
+    deprecated: Option<DeprecatedArg>,
 }
 
 impl RecordItem {
@@ -31,10 +39,15 @@ impl RecordItem {
                 ));
             }
         };
+        let args = input
+            .attrs
+            .parse_uniffi_attr_args::<RecordAttributeArguments>()?;
         Ok(Self {
             ident: input.ident,
             record,
             docstring: extract_docstring(&input.attrs)?,
+            tag: args.tag.map(|tag| tag.value()).unwrap_or_default(),
+            deprecated: args.deprecated,
         })
     }
 
@@ -55,11 +68,39 @@ impl RecordItem {
     }
 }
 
-pub fn expand_record(input: DeriveInput, options: DeriveOptions) -> syn::Result<TokenStream> {
-    if let Some(e) = input.attrs.uniffi_attr_args_not_allowed_here() {
-        return Err(e);
+/// Arguments of `#[uniffi(...)]` on a record
+#[derive(Default)]
+struct RecordAttributeArguments {
+    tag: Option<LitStr>,
+    deprecated: Option<DeprecatedArg>,
+}
+
+impl UniffiAttributeArgs for RecordAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(kw::deprecated) {
+            Ok(Self {
+                deprecated: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else {
+            Ok(Self {
+                tag: Some(parse_tag(input)?),
+                ..Self::default()
+            })
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            tag: either_attribute_arg(self.tag, other.tag)?,
+            deprecated: either_attribute_arg(self.deprecated, other.deprecated)?,
+        })
     }
+}
+
+pub fn expand_record(input: DeriveInput, options: DeriveOptions) -> syn::Result<TokenStream> {
     let record = RecordItem::new(input)?;
+    check_field_types(&record.struct_().fields)?;
     let ffi_converter =
         record_ffi_converter_impl(&record, &options).unwrap_or_else(syn::Error::into_compile_error);
     let meta_static_var = options
@@ -81,8 +122,26 @@ fn record_ffi_converter_impl(
     let derive_ffi_traits = options.derive_all_ffi_traits(ident);
     let name = ident_to_string(ident);
     let mod_path = mod_path()?;
-    let write_impl: TokenStream = record.struct_().fields.iter().map(write_field).collect();
-    let try_read_fields: TokenStream = record.struct_().fields.iter().map(try_read_field).collect();
+    let fields = record
+        .struct_()
+        .fields
+        .iter()
+        .map(|f| Ok((f, is_skipped(f)?)))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let write_impl: TokenStream = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, skip))| !skip)
+        .map(|(i, (f, _))| write_field(f, i))
+        .collect();
+    let try_read_fields: TokenStream = fields
+        .iter()
+        .map(|(f, skip)| try_read_field(f, *skip))
+        .collect();
+    let try_read_impl = match record.struct_().fields {
+        Fields::Unnamed(_) => quote! { Self ( #try_read_fields ) },
+        _ => quote! { Self { #try_read_fields } },
+    };
 
     Ok(quote! {
         #[automatically_derived]
@@ -94,7 +153,7 @@ fn record_ffi_converter_impl(
             }
 
             fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
-                ::std::result::Result::Ok(Self { #try_read_fields })
+                ::std::result::Result::Ok(#try_read_impl)
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_RECORD)
@@ -106,32 +165,159 @@ fn record_ffi_converter_impl(
     })
 }
 
-fn write_field(f: &Field) -> TokenStream {
-    let ident = &f.ident;
+fn write_field(f: &Field, index: usize) -> TokenStream {
     let write = ffiops::write(&f.ty);
-    quote! {
-        #write(obj.#ident, buf);
+    match &f.ident {
+        Some(ident) => quote! {
+            #write(obj.#ident, buf);
+        },
+        None => {
+            let index = Index::from(index);
+            quote! {
+                #write(obj.#index, buf);
+            }
+        }
     }
 }
 
 #[derive(Default)]
 pub struct FieldAttributeArguments {
     pub(crate) default: Option<DefaultValue>,
+    // The foreign name of the field, if it's different from the Rust name
+    pub(crate) name: Option<LitStr>,
+    // Leave the field out of the bindings, using its `Default` value when it's lifted
+    pub(crate) skip: Option<kw::skip>,
+    // The field is the cause of an error variant
+    pub(crate) source: Option<kw::source>,
+    pub(crate) deprecated: Option<DeprecatedArg>,
+}
+
+/// Check the types of the fields which aren't skipped for types that UniFFI can't support
+pub(crate) fn check_field_types<'a>(
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Result<()> {
+    check_types(
+        fields
+            .into_iter()
+            .filter(|f| !matches!(is_skipped(f), Ok(true)))
+            .map(|f| (&f.ty, TypePosition::Field)),
+    )
+}
+
+/// Whether the field has `#[uniffi(skip)]`
+pub(crate) fn is_skipped(field: &Field) -> syn::Result<bool> {
+    Ok(field
+        .attrs
+        .parse_uniffi_attr_args::<FieldAttributeArguments>()?
+        .skip
+        .is_some())
+}
+
+impl FieldAttributeArguments {
+    /// The metadata calls for the field's deprecation
+    pub(crate) fn deprecated_metadata_calls(&self) -> TokenStream {
+        DeprecatedArg::metadata_calls(self.deprecated.as_ref())
+    }
+
+    /// The name of the field in the metadata, with its span
+    pub(crate) fn field_name(&self, field: &Field) -> syn::Result<(String, Span)> {
+        match (&self.name, &field.ident) {
+            (Some(name), Some(_)) => Ok((name.value(), name.span())),
+            (Some(name), None) => Err(syn::Error::new(
+                name.span(),
+                "`name` can only be used on named fields",
+            )),
+            (None, Some(ident)) => Ok((ident_to_string(ident), ident.span())),
+            (None, None) => Ok((String::new(), field.span())),
+        }
+    }
+
+    /// The name of a record field in the metadata, with its span
+    ///
+    /// Fields of tuple structs are named after their position, like `v0`, unless they have a
+    /// `name`.
+    pub(crate) fn record_field_name(
+        &self,
+        field: &Field,
+        index: usize,
+    ) -> syn::Result<(String, Span)> {
+        match (&self.name, &field.ident) {
+            (Some(name), None) => Ok((name.value(), name.span())),
+            (None, None) => Ok((format!("v{index}"), field.span())),
+            _ => self.field_name(field),
+        }
+    }
+
+    /// Fail if the field is skipped but has other attributes, which would have no effect
+    pub(crate) fn check_skip(&self) -> syn::Result<()> {
+        match (&self.skip, &self.default, &self.name, &self.source) {
+            (Some(skip), Some(_), _, _)
+            | (Some(skip), _, Some(_), _)
+            | (Some(skip), _, _, Some(_)) => Err(syn::Error::new(
+                skip.span,
+                "`skip` can't be combined with `default`, `name` or `source`",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if the field has `source`, which is only allowed on the fields of error variants
+    pub(crate) fn check_no_source(&self) -> syn::Result<()> {
+        match &self.source {
+            Some(source) => Err(syn::Error::new(
+                source.span,
+                "`source` can only be used on the fields of error variants",
+            )),
+            None => Ok(()),
+        }
+    }
 }
 
 impl UniffiAttributeArgs for FieldAttributeArguments {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
-        let _: kw::default = input.parse()?;
-        let _: Token![=] = input.parse()?;
-        let default = input.parse()?;
-        Ok(Self {
-            default: Some(default),
-        })
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let default = input.parse()?;
+            Ok(Self {
+                default: Some(default),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::name) {
+            let _: kw::name = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                name: Some(parse_foreign_name(input)?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::skip) {
+            Ok(Self {
+                skip: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::source) {
+            Ok(Self {
+                source: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::deprecated) {
+            Ok(Self {
+                deprecated: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
     }
 
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             default: either_attribute_arg(self.default, other.default)?,
+            name: either_attribute_arg(self.name, other.name)?,
+            skip: either_attribute_arg(self.skip, other.skip)?,
+            source: either_attribute_arg(self.source, other.source)?,
+            deprecated: either_attribute_arg(self.deprecated, other.deprecated)?,
         })
     }
 }
@@ -139,25 +325,37 @@ impl UniffiAttributeArgs for FieldAttributeArguments {
 fn record_meta_static_var(record: &RecordItem) -> syn::Result<TokenStream> {
     let name = record.name();
     let docstring = record.docstring();
+    let tag = &record.tag;
+    let deprecated = DeprecatedArg::metadata_calls(record.deprecated.as_ref());
     let module_path = mod_path()?;
-    let fields_len = try_metadata_value_from_usize(
-        record.struct_().fields.len(),
-        "UniFFI limits structs to 256 fields",
-    )?;
-
-    let concat_fields: TokenStream = record
+    let fields = record
         .struct_()
         .fields
         .iter()
-        .map(|f| {
+        .enumerate()
+        .map(|(i, f)| {
             let attrs = f
                 .attrs
                 .parse_uniffi_attr_args::<FieldAttributeArguments>()?;
+            attrs.check_skip()?;
+            attrs.check_no_source()?;
+            Ok((i, f, attrs))
+        })
+        .filter(|field| !matches!(field, Ok((_, _, attrs)) if attrs.skip.is_some()))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let fields_len =
+        try_metadata_value_from_usize(fields.len(), "UniFFI limits structs to 256 fields")?;
 
-            let name = ident_to_string(f.ident.as_ref().unwrap());
+    let mut field_names = vec![];
+    let concat_fields: TokenStream = fields
+        .into_iter()
+        .map(|(i, f, attrs)| {
+            let (name, span) = attrs.record_field_name(f, i)?;
+            field_names.push((name.clone(), span));
             let docstring = extract_docstring(&f.attrs)?;
             let default = default_value_metadata_calls(&attrs.default)?;
             let type_id_meta = ffiops::type_id_meta(&f.ty);
+            let deprecated = attrs.deprecated_metadata_calls();
 
             // Note: fields need to implement both `Lower` and `Lift` to be used in a record.  The
             // TYPE_ID_META should be the same for both traits.
@@ -165,10 +363,13 @@ fn record_meta_static_var(record: &RecordItem) -> syn::Result<TokenStream> {
                 .concat_str(#name)
                 .concat(#type_id_meta)
                 #default
+                .concat_bool(false) // is_source
                 .concat_long_str(#docstring)
+                #deprecated
             })
         })
         .collect::<syn::Result<_>>()?;
+    check_unique_names("field", field_names)?;
 
     Ok(create_metadata_items(
         "record",
@@ -180,6 +381,8 @@ fn record_meta_static_var(record: &RecordItem) -> syn::Result<TokenStream> {
                 .concat_value(#fields_len)
                 #concat_fields
                 .concat_long_str(#docstring)
+                .concat_str(#tag)
+                #deprecated
         },
         None,
     ))
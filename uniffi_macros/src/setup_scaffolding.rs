@@ -2,11 +2,13 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use fs_err as fs;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::Result;
+use serde::Deserialize;
+use syn::{Expr, ExprLit, Lit, Meta, MetaNameValue, Result};
 
-use crate::util::mod_path;
+use crate::util::{extract_docstring, manifest_path, mod_path};
 use uniffi_meta::UNIFFI_CONTRACT_VERSION;
 
 pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
@@ -15,12 +17,32 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let namespace_upper = namespace.to_ascii_uppercase();
     let namespace_const_ident = format_ident!("UNIFFI_META_CONST_NAMESPACE_{namespace_upper}");
     let namespace_static_ident = format_ident!("UNIFFI_META_NAMESPACE_{namespace_upper}");
+    let contract_version_const_ident =
+        format_ident!("UNIFFI_META_CONST_CONTRACT_VERSION_{namespace_upper}");
+    let contract_version_static_ident =
+        format_ident!("UNIFFI_META_CONTRACT_VERSION_{namespace_upper}");
     let ffi_rustbuffer_alloc_ident = format_ident!("ffi_{module_path}_rustbuffer_alloc");
     let ffi_rustbuffer_from_bytes_ident = format_ident!("ffi_{module_path}_rustbuffer_from_bytes");
     let ffi_rustbuffer_free_ident = format_ident!("ffi_{module_path}_rustbuffer_free");
     let ffi_rustbuffer_reserve_ident = format_ident!("ffi_{module_path}_rustbuffer_reserve");
     let reexport_hack_ident = format_ident!("{module_path}_uniffi_reexport_hack");
+    let catch_all_error_name = uniffi_meta::CATCH_ALL_ERROR_NAME;
     let ffi_rust_future_scaffolding_fns = rust_future_scaffolding_fns(&module_path);
+    let namespace_docstring_metadata = crate_docstring().map(|docstring| {
+        let const_ident = format_ident!("UNIFFI_META_CONST_NAMESPACE_DOCSTRING_{namespace_upper}");
+        let static_ident = format_ident!("UNIFFI_META_NAMESPACE_DOCSTRING_{namespace_upper}");
+        quote! {
+            const #const_ident: ::uniffi::MetadataBuffer =
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::NAMESPACE_DOCSTRING)
+                    .concat_str(#module_path)
+                    .concat_long_str(#docstring);
+
+            #[doc(hidden)]
+            #[no_mangle]
+            pub static #static_ident: [::std::primitive::u8; #const_ident.size] =
+                #const_ident.into_array();
+        }
+    });
 
     Ok(quote! {
         // Unit struct to parameterize the FfiConverter trait.
@@ -33,6 +55,17 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
         #[doc(hidden)]
         pub struct UniFfiTag;
 
+        // Functions exported with `catch_all` return a `CatchAllError`, which is declared in the
+        // bindings for this crate.  See `FnSignature::catch_all_error_metadata_items` for its
+        // metadata.
+        #[doc(hidden)]
+        impl ::uniffi::TypeId<UniFfiTag> for ::uniffi::CatchAllError {
+            const TYPE_ID_META: ::uniffi::MetadataBuffer =
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_ENUM)
+                    .concat_str(#module_path)
+                    .concat_str(#catch_all_error_name);
+        }
+
         #[allow(clippy::missing_safety_doc, missing_docs)]
         #[doc(hidden)]
         #[no_mangle]
@@ -55,6 +88,20 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
         pub static #namespace_static_ident: [::std::primitive::u8; #namespace_const_ident.size] =
             #namespace_const_ident.into_array();
 
+        #namespace_docstring_metadata
+
+        // The contract version is also exported as metadata, so that `uniffi-bindgen` can check
+        // it when generating bindings rather than the foreign code failing at runtime.
+        const #contract_version_const_ident: ::uniffi::MetadataBuffer =
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONTRACT_VERSION)
+                .concat_str(#module_path)
+                .concat_u32(#UNIFFI_CONTRACT_VERSION);
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static #contract_version_static_ident: [::std::primitive::u8; #contract_version_const_ident.size] =
+            #contract_version_const_ident.into_array();
+
         // Everybody gets basic buffer support, since it's needed for passing complex types over the FFI.
         //
         // See `uniffi/src/ffi/rustbuffer.rs` for documentation on these functions
@@ -144,6 +191,53 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     })
 }
 
+/// Read the crate-level docs (`//!` comments) from the library's root source file.
+///
+/// Proc-macros can't see the inner attributes of the crate they're called from, so we parse the
+/// crate root ourselves.  If anything goes wrong, we just don't have a namespace docstring.
+fn crate_docstring() -> Option<String> {
+    #[derive(Deserialize)]
+    struct CargoToml {
+        #[serde(default)]
+        lib: Lib,
+    }
+
+    #[derive(Default, Deserialize)]
+    struct Lib {
+        path: Option<String>,
+    }
+
+    let manifest_path = manifest_path().ok()?;
+    let cargo_toml = toml::from_slice::<CargoToml>(&fs::read(&manifest_path).ok()?).ok()?;
+    let lib_path = cargo_toml
+        .lib
+        .path
+        .unwrap_or_else(|| "src/lib.rs".to_owned());
+    let source = fs::read_to_string(manifest_path.parent()?.join(lib_path)).ok()?;
+    let file = syn::parse_file(&source).ok()?;
+    // Skip doc attributes which aren't plain strings, like `#![doc = include_str!(...)]` or
+    // `#![doc(html_root_url = ...)]`.
+    let doc_attrs: Vec<_> = file
+        .attrs
+        .into_iter()
+        .filter(|attr| {
+            matches!(
+                &attr.meta,
+                Meta::NameValue(MetaNameValue {
+                    value: Expr::Lit(ExprLit {
+                        lit: Lit::Str(_),
+                        ..
+                    }),
+                    ..
+                })
+            )
+        })
+        .collect();
+    extract_docstring(&doc_attrs)
+        .ok()
+        .filter(|docstring| !docstring.is_empty())
+}
+
 /// Generates the rust_future_* functions
 ///
 /// The foreign side uses a type-erased `Handle` to interact with futures, which presents
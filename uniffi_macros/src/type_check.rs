@@ -0,0 +1,184 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Check the types used in exported items for types that UniFFI can never support
+//!
+//! Without this, an unsupported type shows up as a missing `Lift`/`Lower` impl, which rustc
+//! reports on the whole macro invocation and buries in trait resolution notes.  These checks are
+//! purely syntactic, so types that they can't identify, like user-defined types or type aliases,
+//! are left for rustc to check.
+
+use quote::{quote, ToTokens};
+use syn::{GenericArgument, PathArguments, Type, TypePath, TypeReference};
+
+/// Where a type is used, since that determines whether references are allowed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TypePosition {
+    /// The type of a record field or of an enum or error variant field
+    Field,
+    /// The type of a function argument, which may be a shared reference
+    Argument,
+    /// The return type of a function
+    Return,
+}
+
+/// Check several types, combining the errors for all the unsupported ones
+pub(crate) fn check_types<'a>(
+    types: impl IntoIterator<Item = (&'a Type, TypePosition)>,
+) -> syn::Result<()> {
+    types
+        .into_iter()
+        .filter_map(|(ty, position)| check_type(ty, position).err())
+        .reduce(|mut combined, e| {
+            combined.combine(e);
+            combined
+        })
+        .map_or(Ok(()), Err)
+}
+
+/// Check a type, returning an error spanned on the first unsupported part of it
+pub(crate) fn check_type(ty: &Type, position: TypePosition) -> syn::Result<()> {
+    match ty {
+        Type::Reference(r) if position == TypePosition::Argument => {
+            if r.mutability.is_some() {
+                let elem = type_to_string(&r.elem);
+                return Err(unsupported(
+                    ty,
+                    &format!("`&{elem}`, since exported functions can't mutate their arguments"),
+                ));
+            }
+            match &*r.elem {
+                // `&[T]` arguments are lifted from a `Vec<T>`
+                Type::Slice(s) => check_type(&s.elem, TypePosition::Field),
+                elem => check_type(elem, TypePosition::Field),
+            }
+        }
+        Type::Reference(r) => Err(unsupported(ty, &owned_suggestion(r))),
+        Type::Paren(p) => check_type(&p.elem, position),
+        Type::Group(g) => check_type(&g.elem, position),
+        Type::Path(p) => check_path(ty, p),
+        Type::Ptr(_) => Err(unsupported(
+            ty,
+            "an object (`Arc<T>`) which owns the data, since raw pointers can't be passed across \
+             the FFI",
+        )),
+        Type::Array(a) => Err(unsupported(
+            ty,
+            &format!("`Vec<{}>`", type_to_string(&a.elem)),
+        )),
+        Type::Slice(s) => Err(unsupported(
+            ty,
+            &format!("`Vec<{}>`", type_to_string(&s.elem)),
+        )),
+        Type::Tuple(t) if !t.elems.is_empty() => Err(unsupported(ty, "a record")),
+        Type::BareFn(_) => Err(unsupported(ty, "a callback interface or a foreign trait")),
+        Type::ImplTrait(i) => {
+            let bounds = &i.bounds;
+            Err(unsupported(
+                ty,
+                &format!("`Arc<dyn {}>`", tokens_to_string(quote! { #bounds })),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_path(ty: &Type, p: &TypePath) -> syn::Result<()> {
+    let Some(segment) = p.path.segments.last() else {
+        return Ok(());
+    };
+    let generic_args: Vec<&Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let first_arg = generic_args.first().map(|ty| type_to_string(ty));
+    // Only check for `usize` and friends when the path is a single identifier, a path like
+    // `my_crate::usize` is probably a user-defined type.
+    let is_ident = p.qself.is_none() && p.path.segments.len() == 1;
+    let suggestion = match (segment.ident.to_string().as_str(), first_arg) {
+        ("usize", _) if is_ident => Some("`u64`".to_string()),
+        ("isize", _) if is_ident => Some("`i64`".to_string()),
+        ("NonZeroUsize", _) => Some("`NonZeroU64`".to_string()),
+        ("NonZeroIsize", _) => Some("`NonZeroI64`".to_string()),
+        ("Cow", Some(arg)) if arg == "str" => Some("`String`".to_string()),
+        ("Cow", Some(arg)) => Some(match generic_args[0] {
+            Type::Slice(s) => format!("`Vec<{}>`", type_to_string(&s.elem)),
+            _ => format!("`{arg}`"),
+        }),
+        // `Box<dyn CallbackInterface>` is how callback interfaces are passed
+        ("Box", Some(arg)) if arg.starts_with("dyn ") => None,
+        ("Box", Some(arg)) => Some(format!("`{arg}`, or `Arc<{arg}>` if it's an object")),
+        ("Rc", Some(arg)) => Some(format!("`Arc<{arg}>`")),
+        // Only UTC times can be passed, since timestamps don't carry a time zone
+        ("DateTime", Some(arg)) if arg != "Utc" && !arg.ends_with("::Utc") => Some(
+            "`DateTime<Utc>` and converting with `.with_timezone(&Utc)`, since timestamps are \
+             passed without a time zone"
+                .to_string(),
+        ),
+        _ => None,
+    };
+    if let Some(suggestion) = suggestion {
+        return Err(unsupported(ty, &suggestion));
+    }
+    // Generic arguments are checked like fields
+    generic_args
+        .into_iter()
+        .try_for_each(|arg| check_type(arg, TypePosition::Field))
+}
+
+fn owned_suggestion(r: &TypeReference) -> String {
+    match &*r.elem {
+        Type::Path(p) if p.path.is_ident("str") => "`String`".to_string(),
+        Type::Slice(s) => format!("`Vec<{}>`", type_to_string(&s.elem)),
+        elem => {
+            let elem = type_to_string(elem);
+            format!("`{elem}`, or `Arc<{elem}>` if it's an object")
+        }
+    }
+}
+
+fn unsupported(ty: &Type, suggestion: &str) -> syn::Error {
+    syn::Error::new_spanned(
+        ty,
+        format!(
+            "`{}` is not supported by UniFFI; consider {suggestion}",
+            type_to_string(ty)
+        ),
+    )
+}
+
+fn type_to_string(ty: &Type) -> String {
+    tokens_to_string(ty.to_token_stream())
+}
+
+// `TokenStream::to_string()` puts spaces between all the tokens, remove the ones that rustfmt
+// wouldn't have.
+fn tokens_to_string(tokens: proc_macro2::TokenStream) -> String {
+    let mut s = tokens.to_string();
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        (" :: ", "::"),
+        (":: ", "::"),
+        ("& ", "&"),
+        ("* ", "*"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        s = s.replace(from, to);
+    }
+    s
+}
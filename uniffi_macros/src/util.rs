@@ -4,12 +4,16 @@
 
 use crate::ffiops;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
-use std::path::{Path as StdPath, PathBuf};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::{
+    collections::HashMap,
+    path::{Path as StdPath, PathBuf},
+};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
-    Attribute, Expr, Lit, Token,
+    spanned::Spanned,
+    Attribute, Expr, Lit, LitStr, Token,
 };
 
 pub fn manifest_path() -> Result<PathBuf, String> {
@@ -76,16 +80,68 @@ pub fn mod_path() -> syn::Result<String> {
     Ok(syn::parse::<syn::LitStr>(expanded_module_path)?.value())
 }
 
-pub fn try_read_field(f: &syn::Field) -> TokenStream {
+/// Does the crate export all functions returning `anyhow::Result` or boxed errors with `catch_all`?
+///
+/// Crates opt into this with `catch-all = true` in the `[package.metadata.uniffi]` table of their
+/// `Cargo.toml`.  If the manifest can't be read, we just fall back to the per-function attribute.
+pub fn crate_catch_all() -> bool {
+    use fs_err as fs;
+    use once_cell::sync::Lazy;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct CargoToml {
+        package: Package,
+    }
+
+    #[derive(Deserialize)]
+    struct Package {
+        #[serde(default)]
+        metadata: PackageMetadata,
+    }
+
+    #[derive(Default, Deserialize)]
+    struct PackageMetadata {
+        #[serde(default)]
+        uniffi: UniffiMetadata,
+    }
+
+    #[derive(Default, Deserialize)]
+    struct UniffiMetadata {
+        #[serde(default, rename = "catch-all")]
+        catch_all: bool,
+    }
+
+    static CATCH_ALL: Lazy<bool> = Lazy::new(|| {
+        let Some(bytes) = manifest_path().ok().and_then(|path| fs::read(path).ok()) else {
+            return false;
+        };
+        toml::from_slice::<CargoToml>(&bytes)
+            .map(|cargo_toml| cargo_toml.package.metadata.uniffi.catch_all)
+            .unwrap_or(false)
+    });
+
+    *CATCH_ALL
+}
+
+/// Read a field in `try_read`, or use its default value if it's skipped
+pub fn try_read_field(f: &syn::Field, skip: bool) -> TokenStream {
     let ident = &f.ident;
-    let try_read = ffiops::try_read(&f.ty);
+    let ty = &f.ty;
+    let try_read = if skip {
+        // Spanned so that a missing `Default` impl is reported on the field's type
+        quote_spanned! { ty.span() => <#ty as ::std::default::Default>::default() }
+    } else {
+        let try_read = ffiops::try_read(ty);
+        quote! { #try_read(buf)? }
+    };
 
     match ident {
         Some(ident) => quote! {
-            #ident: #try_read(buf)?,
+            #ident: #try_read,
         },
         None => quote! {
-            #try_read(buf)?,
+            #try_read,
         },
     }
 }
@@ -103,6 +159,37 @@ pub fn create_metadata_items(
     name: &str,
     metadata_expr: TokenStream,
     checksum_fn_name: Option<String>,
+) -> TokenStream {
+    let checksum = checksum_fn_name.map(|name| (name, metadata_expr.clone()));
+    metadata_items(kind, name, metadata_expr, checksum)
+}
+
+/// Like [create_metadata_items], for a callable whose metadata is `checksum_expr` followed by
+/// `unchecked_calls`.
+///
+/// The checksum is only calculated from `checksum_expr`, so the parts of the metadata which
+/// don't affect the FFI, like the deprecation message, can change without breaking the bindings.
+pub fn create_callable_metadata_items(
+    kind: &str,
+    name: &str,
+    checksum_expr: TokenStream,
+    unchecked_calls: TokenStream,
+    checksum_fn_name: String,
+) -> TokenStream {
+    let metadata_expr = quote! { #checksum_expr #unchecked_calls };
+    metadata_items(
+        kind,
+        name,
+        metadata_expr,
+        Some((checksum_fn_name, checksum_expr)),
+    )
+}
+
+fn metadata_items(
+    kind: &str,
+    name: &str,
+    metadata_expr: TokenStream,
+    checksum: Option<(String, TokenStream)>,
 ) -> TokenStream {
     let crate_name = crate_name();
     let crate_name_upper = crate_name.to_uppercase();
@@ -111,7 +198,7 @@ pub fn create_metadata_items(
     let const_ident =
         format_ident!("UNIFFI_META_CONST_{crate_name_upper}_{kind_upper}_{name_upper}");
     let static_ident = format_ident!("UNIFFI_META_{crate_name_upper}_{kind_upper}_{name_upper}");
-    let checksum_fn = checksum_fn_name.map(|name| {
+    let checksum_fn = checksum.map(|(name, checksum_expr)| {
         let ident = Ident::new(&name, Span::call_site());
         quote! {
             #[doc(hidden)]
@@ -120,7 +207,8 @@ pub fn create_metadata_items(
                 // Force constant evaluation to ensure:
                 // 1. The checksum is computed at compile time; and
                 // 2. The metadata buffer is not embedded into the binary.
-                const CHECKSUM: u16 = #const_ident.checksum();
+                const CHECKSUM_METADATA: ::uniffi::MetadataBuffer = #checksum_expr;
+                const CHECKSUM: u16 = CHECKSUM_METADATA.checksum();
                 CHECKSUM
             }
         }
@@ -142,13 +230,6 @@ pub fn try_metadata_value_from_usize(value: usize, error_message: &str) -> syn::
         .map_err(|_| syn::Error::new(Span::call_site(), error_message))
 }
 
-pub fn chain<T>(
-    a: impl IntoIterator<Item = T>,
-    b: impl IntoIterator<Item = T>,
-) -> impl Iterator<Item = T> {
-    a.into_iter().chain(b)
-}
-
 pub trait UniffiAttributeArgs: Default {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self>;
     fn merge(self, other: Self) -> syn::Result<Self>;
@@ -194,6 +275,107 @@ impl AttributeSliceExt for [Attribute] {
     }
 }
 
+/// Parse the string for a `name = "..."` attribute argument, which must not be empty
+pub fn parse_foreign_name(input: ParseStream<'_>) -> syn::Result<LitStr> {
+    let name: LitStr = input.parse()?;
+    if name.value().is_empty() {
+        return Err(syn::Error::new(name.span(), "the name can't be empty"));
+    }
+    Ok(name)
+}
+
+/// Parse a `tag = "..."` attribute argument
+///
+/// Tags are matched against the `include_tags` that the bindings are generated with, which are
+/// passed to `uniffi-bindgen` as a comma separated list, so they're limited to ASCII letters,
+/// digits, `-` and `_`.
+pub fn parse_tag(input: ParseStream<'_>) -> syn::Result<LitStr> {
+    let _: kw::tag = input.parse()?;
+    let _: Token![=] = input.parse()?;
+    let tag: LitStr = input.parse()?;
+    let value = tag.value();
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(syn::Error::new(
+            tag.span(),
+            "tags must be ASCII letters, digits, `-` and `_`",
+        ));
+    }
+    Ok(tag)
+}
+
+/// Fail if two items have the same name, which can happen when they're renamed
+///
+/// `kind` is what the items are, like `field`.  Empty names, for tuple fields, are skipped.
+pub fn check_unique_names(
+    kind: &str,
+    names: impl IntoIterator<Item = (String, Span)>,
+) -> syn::Result<()> {
+    let mut seen: HashMap<String, Span> = HashMap::new();
+    for (name, span) in names {
+        if name.is_empty() {
+            continue;
+        }
+        if let Some(first) = seen.get(&name) {
+            let mut error = syn::Error::new(span, format!("duplicate {kind} name `{name}`"));
+            error.combine(syn::Error::new(*first, "note: first one here"));
+            return Err(error);
+        }
+        seen.insert(name, span);
+    }
+    Ok(())
+}
+
+/// A `deprecated` or `deprecated = "..."` attribute argument
+///
+/// The bindings mark the item as deprecated, with the message if there is one.
+#[derive(Clone)]
+pub struct DeprecatedArg {
+    kw: kw::deprecated,
+    message: Option<LitStr>,
+}
+
+impl DeprecatedArg {
+    /// The metadata calls for an item's deprecation, which is written as a bool followed by the
+    /// message.
+    pub(crate) fn metadata_calls(deprecated: Option<&Self>) -> TokenStream {
+        match deprecated {
+            None => quote! { .concat_bool(false) },
+            Some(deprecated) => {
+                let message = deprecated
+                    .message
+                    .as_ref()
+                    .map(LitStr::value)
+                    .unwrap_or_default();
+                quote! { .concat_bool(true).concat_long_str(#message) }
+            }
+        }
+    }
+}
+
+impl Parse for DeprecatedArg {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let kw = input.parse()?;
+        let message = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { kw, message })
+    }
+}
+
+impl ToTokens for DeprecatedArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        self.message.to_tokens(tokens);
+    }
+}
+
 pub fn either_attribute_arg<T: ToTokens>(a: Option<T>, b: Option<T>) -> syn::Result<Option<T>> {
     match (a, b) {
         (None, None) => Ok(None),
@@ -253,6 +435,8 @@ pub(crate) fn derive_ffi_traits(
 /// Custom keywords
 pub mod kw {
     syn::custom_keyword!(async_runtime);
+    syn::custom_keyword!(consumes);
+    syn::custom_keyword!(catch_all);
     syn::custom_keyword!(callback_interface);
     syn::custom_keyword!(with_foreign);
     syn::custom_keyword!(default);
@@ -261,6 +445,12 @@ pub mod kw {
     syn::custom_keyword!(Some);
     syn::custom_keyword!(with_try_read);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(skip);
+    syn::custom_keyword!(get);
+    syn::custom_keyword!(getters);
+    syn::custom_keyword!(source);
+    syn::custom_keyword!(tag);
+    syn::custom_keyword!(deprecated);
     syn::custom_keyword!(non_exhaustive);
     syn::custom_keyword!(Record);
     syn::custom_keyword!(Enum);
@@ -270,6 +460,7 @@ pub mod kw {
     syn::custom_keyword!(Display);
     syn::custom_keyword!(Eq);
     syn::custom_keyword!(Hash);
+    syn::custom_keyword!(Ord);
     // Not used anymore
     syn::custom_keyword!(handle_unknown_callback_error);
 }
@@ -307,3 +498,31 @@ pub(crate) fn extract_docstring(attrs: &[Attribute]) -> syn::Result<String> {
         .collect::<syn::Result<Vec<_>>>()
         .map(|lines| lines.join("\n"));
 }
+
+/// Get the `#[cfg(...)]` attributes of an item
+pub(crate) fn extract_cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Add `cfg` attributes to each of the items in `tokens`
+///
+/// This is used for the code generated for an exported item, so that the scaffolding functions
+/// and metadata exist exactly when the item itself does.
+pub(crate) fn apply_cfg_attrs(
+    cfg_attrs: &[Attribute],
+    tokens: TokenStream,
+) -> syn::Result<TokenStream> {
+    if cfg_attrs.is_empty() {
+        return Ok(tokens);
+    }
+    let file: syn::File = syn::parse2(tokens)?;
+    let items = file
+        .items
+        .iter()
+        .map(|item| quote! { #(#cfg_attrs)* #item });
+    Ok(quote! { #(#items)* })
+}
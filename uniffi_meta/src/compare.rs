@@ -0,0 +1,702 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Compare two definitions of the same item, for example the one from a crate's UDL file and
+//! the one exported by its Rust code.
+//!
+//! Only the parts of an item which affect the bindings are compared, so docstrings, deprecation
+//! messages, checksums and the module within a crate that an item or type is defined in are
+//! ignored.
+
+use crate::*;
+
+/// A difference between two definitions of an item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The part of the item which differs, for example "signature" or "field `name`"
+    pub part: String,
+    /// The part as it's defined by the first definition, or "missing"
+    pub left: String,
+    /// The part as it's defined by the second definition, or "missing"
+    pub right: String,
+}
+
+impl Mismatch {
+    fn new(part: impl Into<String>, left: impl Into<String>, right: impl Into<String>) -> Self {
+        Self {
+            part: part.into(),
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+}
+
+/// The differences between two definitions of the same item
+///
+/// Functions, methods and constructors are compared by their signatures, including parameter
+/// defaults.  Records are compared by their fields and field defaults, enums and errors by their
+/// shape and variants, objects by whether they're a struct or a trait, custom types by their
+/// builtin type and constants by their type and value.  Other items, and items of different kinds,
+/// have nothing to compare.
+pub fn item_mismatches(left: &Metadata, right: &Metadata) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    match (left, right) {
+        (Metadata::Func(left), Metadata::Func(right)) => compare_callables(
+            &mut mismatches,
+            Callable::new(
+                left.is_async,
+                &left.inputs,
+                left.return_type.as_ref(),
+                left.throws.as_ref(),
+            ),
+            Callable::new(
+                right.is_async,
+                &right.inputs,
+                right.return_type.as_ref(),
+                right.throws.as_ref(),
+            ),
+        ),
+        (Metadata::Constructor(left), Metadata::Constructor(right)) => compare_callables(
+            &mut mismatches,
+            Callable::new(left.is_async, &left.inputs, None, left.throws.as_ref()),
+            Callable::new(right.is_async, &right.inputs, None, right.throws.as_ref()),
+        ),
+        (Metadata::Method(left), Metadata::Method(right)) => compare_callables(
+            &mut mismatches,
+            Callable::new(
+                left.is_async,
+                &left.inputs,
+                left.return_type.as_ref(),
+                left.throws.as_ref(),
+            ),
+            Callable::new(
+                right.is_async,
+                &right.inputs,
+                right.return_type.as_ref(),
+                right.throws.as_ref(),
+            ),
+        ),
+        (Metadata::TraitMethod(left), Metadata::TraitMethod(right)) => compare_callables(
+            &mut mismatches,
+            Callable::new(
+                left.is_async,
+                &left.inputs,
+                left.return_type.as_ref(),
+                left.throws.as_ref(),
+            ),
+            Callable::new(
+                right.is_async,
+                &right.inputs,
+                right.return_type.as_ref(),
+                right.throws.as_ref(),
+            ),
+        ),
+        (Metadata::Record(left), Metadata::Record(right)) => {
+            compare_fields(&mut mismatches, "", &left.fields, &right.fields)
+        }
+        (Metadata::Enum(left), Metadata::Enum(right)) => {
+            if left.shape != right.shape {
+                mismatches.push(Mismatch::new(
+                    "shape",
+                    describe_shape(&left.shape),
+                    describe_shape(&right.shape),
+                ));
+            }
+            for left_variant in &left.variants {
+                let part = format!("variant `{}`", left_variant.name);
+                match right.variants.iter().find(|v| v.name == left_variant.name) {
+                    Some(right_variant) => compare_fields(
+                        &mut mismatches,
+                        &format!("{part} "),
+                        &left_variant.fields,
+                        &right_variant.fields,
+                    ),
+                    None => mismatches.push(Mismatch::new(part, "defined", "missing")),
+                }
+            }
+            for right_variant in &right.variants {
+                if left.variants.iter().all(|v| v.name != right_variant.name) {
+                    mismatches.push(Mismatch::new(
+                        format!("variant `{}`", right_variant.name),
+                        "missing",
+                        "defined",
+                    ));
+                }
+            }
+        }
+        (Metadata::Object(left), Metadata::Object(right)) if left.imp != right.imp => mismatches
+            .push(Mismatch::new(
+                "implementation",
+                describe_object_impl(left.imp),
+                describe_object_impl(right.imp),
+            )),
+        (Metadata::CustomType(left), Metadata::CustomType(right))
+            if !same_type(&left.builtin, &right.builtin) =>
+        {
+            mismatches.push(Mismatch::new(
+                "builtin type",
+                type_name(&left.builtin),
+                type_name(&right.builtin),
+            ))
+        }
+        (Metadata::Constant(left), Metadata::Constant(right)) => compare_constants(
+            &mut mismatches,
+            (&left.ty, &left.value),
+            (&right.ty, &right.value),
+        ),
+        (Metadata::TraitConstant(left), Metadata::TraitConstant(right)) => compare_constants(
+            &mut mismatches,
+            (&left.ty, &left.value),
+            (&right.ty, &right.value),
+        ),
+        _ => (),
+    }
+    mismatches
+}
+
+fn compare_constants(
+    mismatches: &mut Vec<Mismatch>,
+    (left_ty, left_value): (&Type, &LiteralMetadata),
+    (right_ty, right_value): (&Type, &LiteralMetadata),
+) {
+    if !same_type(left_ty, right_ty) {
+        mismatches.push(Mismatch::new(
+            "type",
+            type_name(left_ty),
+            type_name(right_ty),
+        ));
+    } else if left_value != right_value {
+        mismatches.push(Mismatch::new(
+            "value",
+            literal_name(left_value),
+            literal_name(right_value),
+        ));
+    }
+}
+
+/// Compare two types
+///
+/// The modules within a crate that types are defined in are ignored, since they don't affect the
+/// bindings.
+pub fn same_type(left: &Type, right: &Type) -> bool {
+    normalize_type(left) == normalize_type(right)
+}
+
+/// A Rust-like name for a type, for use in messages
+pub fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::UInt8 => "u8".to_string(),
+        Type::Int8 => "i8".to_string(),
+        Type::UInt16 => "u16".to_string(),
+        Type::Int16 => "i16".to_string(),
+        Type::UInt32 => "u32".to_string(),
+        Type::Int32 => "i32".to_string(),
+        Type::UInt64 => "u64".to_string(),
+        Type::Int64 => "i64".to_string(),
+        Type::UInt128 => "u128".to_string(),
+        Type::Int128 => "i128".to_string(),
+        Type::Char => "char".to_string(),
+        Type::Float32 => "f32".to_string(),
+        Type::Float64 => "f64".to_string(),
+        Type::Boolean => "bool".to_string(),
+        Type::String => "String".to_string(),
+        Type::Bytes => "Vec<u8>".to_string(),
+        Type::Timestamp => "SystemTime".to_string(),
+        Type::Date => "NaiveDate".to_string(),
+        Type::Json => "serde_json::Value".to_string(),
+        Type::Path => "PathBuf".to_string(),
+        Type::Url => "url::Url".to_string(),
+        Type::Duration => "Duration".to_string(),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::CallbackInterface { name, .. }
+        | Type::External { name, .. }
+        | Type::Custom { name, .. } => name.clone(),
+        Type::Optional { inner_type } => format!("Option<{}>", type_name(inner_type)),
+        Type::Sequence { inner_type } => format!("Vec<{}>", type_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "HashMap<{}, {}>",
+            type_name(key_type),
+            type_name(value_type)
+        ),
+    }
+}
+
+/// The UDL name of a type, for use in messages about UDL files
+///
+/// Types which can't be written in UDL, like `char`, have their Rust-like [type_name].
+pub fn udl_type_name(ty: &Type) -> String {
+    match ty {
+        Type::Float32 => "f32".to_string(),
+        Type::Float64 => "f64".to_string(),
+        Type::Boolean => "boolean".to_string(),
+        Type::String => "string".to_string(),
+        Type::Bytes => "bytes".to_string(),
+        Type::Timestamp => "timestamp".to_string(),
+        Type::Duration => "duration".to_string(),
+        Type::Optional { inner_type } => format!("{}?", udl_type_name(inner_type)),
+        Type::Sequence { inner_type } => format!("sequence<{}>", udl_type_name(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "record<{}, {}>",
+            udl_type_name(key_type),
+            udl_type_name(value_type)
+        ),
+        _ => type_name(ty),
+    }
+}
+
+/// A Rust-like representation of a default value, for use in messages
+pub fn literal_name(literal: &LiteralMetadata) -> String {
+    match literal {
+        LiteralMetadata::Boolean(value) => value.to_string(),
+        LiteralMetadata::String(value) => format!("{value:?}"),
+        LiteralMetadata::UInt(value, ..) => value.to_string(),
+        LiteralMetadata::Int(value, ..) => value.to_string(),
+        LiteralMetadata::Float(value, _) => value.clone(),
+        LiteralMetadata::Enum(variant, ty) => format!("{}::{variant}", type_name(ty)),
+        LiteralMetadata::Bytes(bytes) => format!("b\"{}\"", bytes.escape_ascii()),
+        LiteralMetadata::EmptySequence => "[]".to_string(),
+        LiteralMetadata::EmptyMap => "{}".to_string(),
+        LiteralMetadata::None => "None".to_string(),
+        LiteralMetadata::Some { inner } => format!("Some({})", literal_name(inner)),
+    }
+}
+
+// The parts of a function, method or constructor which affect how it's called
+struct Callable<'a> {
+    is_async: bool,
+    inputs: &'a [FnParamMetadata],
+    return_type: Option<&'a Type>,
+    throws: Option<&'a Type>,
+}
+
+impl<'a> Callable<'a> {
+    fn new(
+        is_async: bool,
+        inputs: &'a [FnParamMetadata],
+        return_type: Option<&'a Type>,
+        throws: Option<&'a Type>,
+    ) -> Self {
+        Self {
+            is_async,
+            inputs,
+            return_type,
+            throws,
+        }
+    }
+
+    fn signature(&self) -> String {
+        let params = self
+            .inputs
+            .iter()
+            .map(|p| match &p.default {
+                Some(default) => {
+                    format!(
+                        "{}: {} = {}",
+                        p.name,
+                        type_name(&p.ty),
+                        literal_name(default)
+                    )
+                }
+                None => format!("{}: {}", p.name, type_name(&p.ty)),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let return_type = self.return_type.map(type_name);
+        let return_type = match (return_type, self.throws) {
+            (None, None) => String::new(),
+            (Some(ty), None) => format!(" -> {ty}"),
+            (ty, Some(throws)) => format!(
+                " -> Result<{}, {}>",
+                ty.unwrap_or_else(|| "()".to_string()),
+                type_name(throws)
+            ),
+        };
+        let prefix = if self.is_async { "async fn" } else { "fn" };
+        format!("{prefix}({params}){return_type}")
+    }
+}
+
+fn compare_callables(mismatches: &mut Vec<Mismatch>, left: Callable<'_>, right: Callable<'_>) {
+    let same_optional_type = |left: Option<&Type>, right: Option<&Type>| match (left, right) {
+        (Some(left), Some(right)) => same_type(left, right),
+        (left, right) => left.is_none() && right.is_none(),
+    };
+    let same_param = |left: &FnParamMetadata, right: &FnParamMetadata| {
+        left.name == right.name
+            && same_type(&left.ty, &right.ty)
+            && left.default.as_ref().map(literal_name) == right.default.as_ref().map(literal_name)
+    };
+    let same = left.is_async == right.is_async
+        && same_optional_type(left.return_type, right.return_type)
+        && same_optional_type(left.throws, right.throws)
+        && left.inputs.len() == right.inputs.len()
+        && left
+            .inputs
+            .iter()
+            .zip(right.inputs)
+            .all(|(l, r)| same_param(l, r));
+    if !same {
+        mismatches.push(Mismatch::new(
+            "signature",
+            left.signature(),
+            right.signature(),
+        ));
+    }
+}
+
+// Compare the fields of a record or enum variant, matching them by name
+//
+// `prefix` describes where the fields are, for example "variant `Foo` ".
+fn compare_fields(
+    mismatches: &mut Vec<Mismatch>,
+    prefix: &str,
+    left: &[FieldMetadata],
+    right: &[FieldMetadata],
+) {
+    for left_field in left {
+        let part = format!("{prefix}field `{}`", left_field.name);
+        let Some(right_field) = right.iter().find(|f| f.name == left_field.name) else {
+            mismatches.push(Mismatch::new(part, describe_field(left_field), "missing"));
+            continue;
+        };
+        let same_default = left_field.default.as_ref().map(literal_name)
+            == right_field.default.as_ref().map(literal_name);
+        if !same_type(&left_field.ty, &right_field.ty) || !same_default {
+            mismatches.push(Mismatch::new(
+                part,
+                describe_field(left_field),
+                describe_field(right_field),
+            ));
+        }
+    }
+    for right_field in right {
+        if left.iter().all(|f| f.name != right_field.name) {
+            mismatches.push(Mismatch::new(
+                format!("{prefix}field `{}`", right_field.name),
+                "missing",
+                describe_field(right_field),
+            ));
+        }
+    }
+}
+
+fn describe_field(field: &FieldMetadata) -> String {
+    match &field.default {
+        Some(default) => format!(
+            "{}: {} = {}",
+            field.name,
+            type_name(&field.ty),
+            literal_name(default)
+        ),
+        None => format!("{}: {}", field.name, type_name(&field.ty)),
+    }
+}
+
+fn describe_shape(shape: &EnumShape) -> &'static str {
+    match shape {
+        EnumShape::Enum => "an enum",
+        EnumShape::Error { flat: false } => "an error",
+        EnumShape::Error { flat: true } => "a flat error",
+    }
+}
+
+fn describe_object_impl(imp: ObjectImpl) -> &'static str {
+    match imp {
+        ObjectImpl::Struct => "a struct",
+        ObjectImpl::Trait => "a trait",
+        ObjectImpl::CallbackTrait => "a foreign-implementable trait",
+    }
+}
+
+// A copy of `ty` with the parts which `same_type` ignores removed
+fn normalize_type(ty: &Type) -> Type {
+    let crate_path = |module_path: String| match module_path.split_once("::") {
+        Some((crate_name, _)) => crate_name.to_string(),
+        None => module_path,
+    };
+    match ty.clone() {
+        Type::Object {
+            module_path,
+            name,
+            imp,
+        } => Type::Object {
+            module_path: crate_path(module_path),
+            name,
+            imp,
+        },
+        Type::Record { module_path, name } => Type::Record {
+            module_path: crate_path(module_path),
+            name,
+        },
+        Type::Enum { module_path, name } => Type::Enum {
+            module_path: crate_path(module_path),
+            name,
+        },
+        Type::CallbackInterface { module_path, name } => Type::CallbackInterface {
+            module_path: crate_path(module_path),
+            name,
+        },
+        Type::External {
+            module_path,
+            name,
+            namespace,
+            kind,
+            tagged,
+        } => Type::External {
+            module_path: crate_path(module_path),
+            name,
+            namespace,
+            kind,
+            tagged,
+        },
+        Type::Optional { inner_type } => Type::Optional {
+            inner_type: Box::new(normalize_type(&inner_type)),
+        },
+        Type::Sequence { inner_type } => Type::Sequence {
+            inner_type: Box::new(normalize_type(&inner_type)),
+        },
+        Type::Map {
+            key_type,
+            value_type,
+        } => Type::Map {
+            key_type: Box::new(normalize_type(&key_type)),
+            value_type: Box::new(normalize_type(&value_type)),
+        },
+        Type::Custom {
+            module_path,
+            name,
+            builtin,
+        } => Type::Custom {
+            module_path: crate_path(module_path),
+            name,
+            builtin: Box::new(normalize_type(&builtin)),
+        },
+        ty => ty,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn func(inputs: Vec<FnParamMetadata>, return_type: Option<Type>) -> Metadata {
+        Metadata::Func(FnMetadata {
+            module_path: "crate_a".to_string(),
+            name: "get_value".to_string(),
+            is_async: false,
+            inputs,
+            return_type,
+            throws: None,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        })
+    }
+
+    fn field(name: &str, ty: Type, default: Option<LiteralMetadata>) -> FieldMetadata {
+        FieldMetadata {
+            name: name.to_string(),
+            ty,
+            default,
+            is_source: false,
+            docstring: None,
+            deprecated: None,
+        }
+    }
+
+    fn record(module_path: &str, fields: Vec<FieldMetadata>) -> Metadata {
+        Metadata::Record(RecordMetadata {
+            module_path: module_path.to_string(),
+            name: "Value".to_string(),
+            fields,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        })
+    }
+
+    #[test]
+    fn test_same_function() {
+        let record_type = |module_path: &str| Type::Record {
+            module_path: module_path.to_string(),
+            name: "Value".to_string(),
+        };
+        // The module within the crate doesn't matter
+        let left = func(
+            vec![FnParamMetadata::simple("value", record_type("crate_a"))],
+            Some(Type::UInt32),
+        );
+        let right = func(
+            vec![FnParamMetadata::simple(
+                "value",
+                record_type("crate_a::submodule"),
+            )],
+            Some(Type::UInt32),
+        );
+        assert_eq!(item_mismatches(&left, &right), vec![]);
+    }
+
+    #[test]
+    fn test_function_signature_mismatch() {
+        let left = func(
+            vec![FnParamMetadata::simple("value", Type::UInt32)],
+            Some(Type::String),
+        );
+        let mut param = FnParamMetadata::simple("value", Type::Int32);
+        param.default = Some(LiteralMetadata::Int(0, Radix::Decimal, Type::Int32));
+        let right = func(vec![param], Some(Type::String));
+        assert_eq!(
+            item_mismatches(&left, &right),
+            vec![Mismatch::new(
+                "signature",
+                "fn(value: u32) -> String",
+                "fn(value: i32 = 0) -> String"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_record_field_mismatches() {
+        let left = record(
+            "crate_a",
+            vec![
+                field("same", Type::String, None),
+                field("retyped", Type::UInt8, None),
+                field(
+                    "defaulted",
+                    Type::Boolean,
+                    Some(LiteralMetadata::Boolean(true)),
+                ),
+                field("removed", Type::String, None),
+            ],
+        );
+        let right = record(
+            "crate_a::submodule",
+            vec![
+                field("same", Type::String, None),
+                field("retyped", Type::UInt16, None),
+                field(
+                    "defaulted",
+                    Type::Boolean,
+                    Some(LiteralMetadata::Boolean(false)),
+                ),
+                field("added", Type::String, None),
+            ],
+        );
+        assert_eq!(
+            item_mismatches(&left, &right),
+            vec![
+                Mismatch::new("field `retyped`", "retyped: u8", "retyped: u16"),
+                Mismatch::new(
+                    "field `defaulted`",
+                    "defaulted: bool = true",
+                    "defaulted: bool = false"
+                ),
+                Mismatch::new("field `removed`", "removed: String", "missing"),
+                Mismatch::new("field `added`", "missing", "added: String"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_variant_mismatches() {
+        let error = |flat, variants: &[&str]| {
+            Metadata::Enum(EnumMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Failure".to_string(),
+                shape: EnumShape::Error { flat },
+                variants: variants
+                    .iter()
+                    .map(|name| VariantMetadata {
+                        name: name.to_string(),
+                        discr: None,
+                        fields: vec![],
+                        docstring: None,
+                        deprecated: None,
+                    })
+                    .collect(),
+                discr_type: None,
+                non_exhaustive: false,
+                docstring: None,
+                tag: None,
+            })
+        };
+        assert_eq!(
+            item_mismatches(
+                &error(true, &["NotFound", "Timeout"]),
+                &error(false, &["NotFound", "Denied"])
+            ),
+            vec![
+                Mismatch::new("shape", "a flat error", "an error"),
+                Mismatch::new("variant `Timeout`", "defined", "missing"),
+                Mismatch::new("variant `Denied`", "missing", "defined"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constant_mismatches() {
+        let constant = |ty: Type, value| {
+            Metadata::Constant(ConstantMetadata {
+                module_path: "crate_a".to_string(),
+                name: "MAX_SIZE".to_string(),
+                ty,
+                value,
+                docstring: None,
+                tag: None,
+            })
+        };
+        let left = constant(
+            Type::UInt32,
+            LiteralMetadata::UInt(1024, Radix::Decimal, Type::UInt32),
+        );
+        assert_eq!(
+            item_mismatches(
+                &left,
+                &constant(
+                    Type::UInt32,
+                    LiteralMetadata::UInt(2048, Radix::Decimal, Type::UInt32)
+                )
+            ),
+            vec![Mismatch::new("value", "1024", "2048")]
+        );
+        assert_eq!(
+            item_mismatches(
+                &left,
+                &constant(
+                    Type::UInt64,
+                    LiteralMetadata::UInt(1024, Radix::Decimal, Type::UInt64)
+                )
+            ),
+            vec![Mismatch::new("type", "u32", "u64")]
+        );
+    }
+
+    #[test]
+    fn test_udl_type_name() {
+        assert_eq!(udl_type_name(&Type::Float32), "f32");
+        assert_eq!(udl_type_name(&Type::Bytes), "bytes");
+        assert_eq!(udl_type_name(&Type::Char), "char");
+        assert_eq!(
+            udl_type_name(&Type::Map {
+                key_type: Box::new(Type::String),
+                value_type: Box::new(Type::Optional {
+                    inner_type: Box::new(Type::Sequence {
+                        inner_type: Box::new(Type::Timestamp)
+                    })
+                }),
+            }),
+            "record<string, sequence<timestamp>?>"
+        );
+    }
+}
@@ -33,6 +33,13 @@ pub fn method_symbol_name(namespace: &str, object_name: &str, name: &str) -> Str
     format!("uniffi_{namespace}_fn_method_{object_name}_{name}")
 }
 
+/// FFI symbol name for the default implementation of a trait method
+pub fn default_method_symbol_name(namespace: &str, trait_name: &str, name: &str) -> String {
+    let trait_name = trait_name.to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_default_{trait_name}_{name}")
+}
+
 /// FFI symbol name for the `clone` function for an object.
 pub fn clone_fn_symbol_name(namespace: &str, object_name: &str) -> String {
     let object_name = object_name.to_ascii_lowercase();
@@ -2,13 +2,17 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::*;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 
 type MetadataGroupMap = HashMap<String, MetadataGroup>;
 
+/// The docstrings of the types defined by each crate, keyed by crate name and type name
+pub type TypeDocstringMap = HashMap<(String, String), String>;
+
 // Create empty metadata groups based on the metadata items.
 pub fn create_metadata_groups(items: &[Metadata]) -> MetadataGroupMap {
     // Map crate names to MetadataGroup instances
@@ -42,27 +46,193 @@ pub fn create_metadata_groups(items: &[Metadata]) -> MetadataGroupMap {
 
 /// Consume the items into the previously created metadata groups.
 pub fn group_metadata(group_map: &mut MetadataGroupMap, items: Vec<Metadata>) -> Result<()> {
+    check_namespace_names(group_map)?;
+    // Items which have been added so far, keyed by crate name and description.  Comparing
+    // against these lets us say something useful when the same item shows up twice.
+    let mut seen: HashMap<(String, String), Metadata> = group_map
+        .iter()
+        .flat_map(|(crate_name, group)| {
+            group
+                .items
+                .iter()
+                .map(|item| ((crate_name.clone(), describe_item(item)), item.clone()))
+        })
+        .collect();
+    // Items from crates without a namespace, keyed by crate name.  These are reported together
+    // once we've seen all the items.
+    let mut unknown_namespaces: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
     for item in items {
-        if matches!(&item, Metadata::Namespace(_)) {
+        if matches!(&item, Metadata::Namespace(_) | Metadata::ContractVersion(_)) {
             continue;
         }
-
         let crate_name = calc_crate_name(item.module_path()).to_owned(); // XXX - kill clone?
+        if !group_map.contains_key(&crate_name) {
+            unknown_namespaces
+                .entry(crate_name)
+                .or_default()
+                .push(describe_item(&item));
+            continue;
+        }
+        if let Metadata::NamespaceDocstring(meta) = item {
+            group_map.get_mut(&crate_name).unwrap().namespace_docstring = Some(meta.docstring);
+            continue;
+        }
 
-        let item = fixup_external_type(item, group_map);
-        let group = match group_map.get_mut(&crate_name) {
-            Some(ns) => ns,
-            None => bail!("Unknown namespace for {item:?} ({crate_name})"),
-        };
-        if group.items.contains(&item) {
-            bail!("Duplicate metadata item: {item:?}");
+        let item = fixup_external_type(item, group_map)?;
+        let group = group_map.get_mut(&crate_name).unwrap();
+        let key = (crate_name, describe_item(&item));
+        if let Some(existing) = seen.get(&key) {
+            // Every function exported with `catch_all` declares its crate's `CatchAllError`
+            if *existing == item && is_catch_all_error(&item) {
+                continue;
+            }
+            bail!(duplicate_item_error(&key.1, &key.0, existing, &item));
         }
+        seen.insert(key, item.clone());
         group.add_item(item);
     }
+    if !unknown_namespaces.is_empty() {
+        bail!(unknown_namespaces_error(&unknown_namespaces));
+    }
     Ok(())
 }
 
-#[derive(Debug)]
+// The bindings for a crate are named after its namespace, so two crates with the same namespace
+// name would write the same files.
+fn check_namespace_names(group_map: &MetadataGroupMap) -> Result<()> {
+    let mut crates_by_namespace: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (crate_name, group) in group_map {
+        crates_by_namespace
+            .entry(&group.namespace.name)
+            .or_default()
+            .insert(crate_name);
+    }
+    let mut collisions = String::new();
+    for (namespace, crate_names) in crates_by_namespace {
+        if crate_names.len() > 1 {
+            let crate_names = crate_names
+                .into_iter()
+                .map(|c| format!("`{c}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            collisions.push_str(&format!(
+                "\n  namespace `{namespace}`: crates {crate_names}"
+            ));
+        }
+    }
+    if !collisions.is_empty() {
+        bail!(
+            "More than one crate uses the same UniFFI namespace:{collisions}\n\
+             The bindings for each crate are named after its namespace, so they would overwrite \
+             each other. Give each crate a different namespace."
+        );
+    }
+    Ok(())
+}
+
+fn unknown_namespaces_error(unknown_namespaces: &BTreeMap<String, Vec<String>>) -> String {
+    let mut message = String::from("Unknown namespace for UniFFI items:");
+    for (crate_name, descriptions) in unknown_namespaces {
+        message.push_str(&format!("\n  crate `{crate_name}`: {}", descriptions[0]));
+        match descriptions.len() {
+            1 => (),
+            2 => message.push_str(" and 1 other item"),
+            n => message.push_str(&format!(" and {} other items", n - 1)),
+        }
+    }
+    message.push_str(
+        "\nEach crate with UniFFI items needs a namespace. Make sure the crate calls \
+         `uniffi::setup_scaffolding!()`, or `uniffi::include_scaffolding!()` if it uses a UDL \
+         file, and that the call isn't disabled by a feature flag.",
+    );
+    message
+}
+
+/// Describe a metadata item for error messages, for example "method `Object.get_inner`"
+///
+/// This is unique for each item in a crate, so it can also be used as a key when comparing items.
+pub fn describe_item(item: &Metadata) -> String {
+    match item {
+        Metadata::Namespace(meta) => format!("namespace `{}`", meta.name),
+        Metadata::NamespaceDocstring(meta) => format!("docstring for `{}`", meta.module_path),
+        Metadata::ContractVersion(meta) => format!("contract version for `{}`", meta.module_path),
+        Metadata::UdlFile(meta) => format!("UDL file `{}`", meta.file_stub),
+        Metadata::Func(meta) => format!("function `{}`", meta.name),
+        Metadata::Object(meta) => format!("object `{}`", meta.name),
+        Metadata::CallbackInterface(meta) => format!("callback interface `{}`", meta.name),
+        Metadata::Record(meta) => format!("record `{}`", meta.name),
+        Metadata::Enum(meta) => format!("enum `{}`", meta.name),
+        Metadata::Constructor(meta) => format!("constructor `{}.{}`", meta.self_name, meta.name),
+        Metadata::Method(meta) => format!("method `{}.{}`", meta.self_name, meta.name),
+        Metadata::TraitMethod(meta) => {
+            format!("trait method `{}.{}`", meta.trait_name, meta.name)
+        }
+        Metadata::CustomType(meta) => format!("custom type `{}`", meta.name),
+        Metadata::Constant(meta) => format!("constant `{}`", meta.name),
+        Metadata::TraitConstant(meta) => {
+            format!("trait constant `{}.{}`", meta.trait_name, meta.name)
+        }
+        Metadata::UniffiTrait(meta) => {
+            let trait_name = match meta {
+                UniffiTraitMetadata::Debug { .. } => "Debug",
+                UniffiTraitMetadata::Display { .. } => "Display",
+                UniffiTraitMetadata::Eq { .. } => "Eq",
+                UniffiTraitMetadata::Hash { .. } => "Hash",
+                UniffiTraitMetadata::Ord { .. } => "Ord",
+            };
+            format!("`{trait_name}` implementation for `{}`", meta.self_name())
+        }
+    }
+}
+
+fn item_checksum(item: &Metadata) -> Option<u16> {
+    match item {
+        Metadata::Func(meta) => meta.checksum,
+        Metadata::Constructor(meta) => meta.checksum,
+        Metadata::Method(meta) => meta.checksum,
+        Metadata::TraitMethod(meta) => meta.checksum,
+        _ => None,
+    }
+}
+
+fn is_catch_all_error(item: &Metadata) -> bool {
+    matches!(item, Metadata::Enum(meta) if meta.name == CATCH_ALL_ERROR_NAME)
+}
+
+fn duplicate_item_error(
+    description: &str,
+    crate_name: &str,
+    existing: &Metadata,
+    new: &Metadata,
+) -> String {
+    let module_paths = if existing.module_path() == new.module_path() {
+        format!("module path `{}`", new.module_path())
+    } else {
+        format!(
+            "module paths `{}` and `{}`",
+            existing.module_path(),
+            new.module_path()
+        )
+    };
+    if existing == new {
+        return format!(
+            "Duplicate metadata item: {description} in crate `{crate_name}` ({module_paths}). \
+             This usually means the crate was compiled into the library more than once."
+        );
+    }
+    let difference = match (item_checksum(existing), item_checksum(new)) {
+        (Some(a), Some(b)) if a != b => format!("the checksums differ ({a} and {b})"),
+        _ => "the definitions differ".to_string(),
+    };
+    format!(
+        "Conflicting metadata for {description} in crate `{crate_name}` ({module_paths}): \
+         {difference}. This usually means two versions of `{crate_name}` are linked into the \
+         library."
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MetadataGroup {
     pub namespace: NamespaceMetadata,
     pub namespace_docstring: Option<String>,
@@ -75,13 +245,244 @@ impl MetadataGroup {
     }
 }
 
-pub fn fixup_external_type(item: Metadata, group_map: &MetadataGroupMap) -> Metadata {
+/// Collect the docstrings of the records, enums, objects and callback interfaces in `items`
+///
+/// These aren't part of [Type::External], since they don't affect the type's identity.  Instead
+/// they're looked up by the crate and name of the external type when it's rendered.
+pub fn type_docstrings<'a>(items: impl IntoIterator<Item = &'a Metadata>) -> TypeDocstringMap {
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let (module_path, name, docstring) = match item {
+                Metadata::Record(meta) => (&meta.module_path, &meta.name, &meta.docstring),
+                Metadata::Enum(meta) => (&meta.module_path, &meta.name, &meta.docstring),
+                Metadata::Object(meta) => (&meta.module_path, &meta.name, &meta.docstring),
+                Metadata::CallbackInterface(meta) => {
+                    (&meta.module_path, &meta.name, &meta.docstring)
+                }
+                _ => return None,
+            };
+            let key = (calc_crate_name(module_path).to_owned(), name.clone());
+            docstring.clone().map(|docstring| (key, docstring))
+        })
+        .collect()
+}
+
+pub fn fixup_external_type(item: Metadata, group_map: &MetadataGroupMap) -> Result<Metadata> {
     let crate_name = calc_crate_name(item.module_path()).to_owned();
     let converter = ExternalTypeConverter {
         crate_name: &crate_name,
         crate_to_namespace: group_map,
     };
-    converter.convert_item(item)
+    // Flatten the context chain so the whole path to the problem ends up in a single message.
+    converter
+        .convert_item(item)
+        .map_err(|e| anyhow::anyhow!("{e:#}"))
+}
+
+/// The crates whose bindings are needed by the bindings for `root`
+///
+/// This is `root` plus every crate reachable from it by following the [Type::External] references
+/// in the items of each crate's group, so the groups must have been passed through
+/// [group_metadata].  Crates in `group_map` which aren't reachable from `root` aren't included.
+pub fn crate_dependencies(group_map: &MetadataGroupMap, root: &str) -> Result<BTreeSet<String>> {
+    if !group_map.contains_key(root) {
+        bail!("Crate `{root}` has no UniFFI metadata");
+    }
+    Ok(reachable_crates(root, |crate_name| {
+        group_map
+            .get(crate_name)
+            .map(|group| {
+                group
+                    .items
+                    .iter()
+                    .flat_map(item_types)
+                    .flat_map(Type::iter_types)
+                    .filter_map(|ty| match ty {
+                        Type::External { module_path, .. } => {
+                            Some(calc_crate_name(module_path).to_owned())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    }))
+}
+
+/// Remove the items with a tag which isn't included from the groups
+///
+/// `include_tags` maps crate names to the tags of the items to keep, see `#[uniffi::export(tag =
+/// "...")]`.  Untagged items are always kept and the groups of crates which aren't in the map
+/// aren't changed.  The constructors, methods and trait implementations of a removed type are
+/// removed with it.
+///
+/// Fails if a kept item uses a removed type, in its own crate or another, since its bindings would
+/// refer to a type which isn't generated.
+pub fn retain_included_tags(
+    group_map: &mut MetadataGroupMap,
+    include_tags: &HashMap<String, BTreeSet<String>>,
+) -> Result<()> {
+    // The removed types, keyed by crate name and type name, with their tags
+    let mut removed_types: BTreeMap<(String, String), String> = BTreeMap::new();
+    for (crate_name, group) in group_map.iter_mut() {
+        let Some(tags) = include_tags.get(crate_name) else {
+            continue;
+        };
+        let is_excluded = |item: &Metadata| item_tag(item).is_some_and(|tag| !tags.contains(tag));
+        for item in group.items.iter().filter(|item| is_excluded(item)) {
+            if let Some(name) = defined_type_name(item) {
+                let tag = item_tag(item).unwrap_or_default().to_owned();
+                removed_types.insert((crate_name.clone(), name.to_owned()), tag);
+            }
+        }
+        group.items.retain(|item| {
+            !is_excluded(item)
+                && member_of(item).map_or(true, |owner| {
+                    !removed_types.contains_key(&(crate_name.clone(), owner.to_owned()))
+                })
+        });
+    }
+    if removed_types.is_empty() {
+        return Ok(());
+    }
+
+    let mut uses = BTreeSet::new();
+    for (crate_name, group) in group_map.iter() {
+        for item in &group.items {
+            for ty in item_types(item).into_iter().flat_map(Type::iter_types) {
+                let key = match ty {
+                    Type::Record { name, .. }
+                    | Type::Enum { name, .. }
+                    | Type::Object { name, .. }
+                    | Type::CallbackInterface { name, .. } => (crate_name.clone(), name.clone()),
+                    Type::External {
+                        module_path, name, ..
+                    } => (calc_crate_name(module_path).to_owned(), name.clone()),
+                    _ => continue,
+                };
+                if let Some(tag) = removed_types.get(&key) {
+                    uses.insert(format!(
+                        "\n  crate `{crate_name}`: {} uses `{}`, which has the tag `{tag}`",
+                        describe_item(item),
+                        key.1,
+                    ));
+                }
+            }
+        }
+    }
+    if !uses.is_empty() {
+        bail!(
+            "Types left out by `include_tags` are used by items which are kept:{}\n\
+             Either include the tags of the types or tag the items which use them.",
+            uses.into_iter().collect::<String>()
+        );
+    }
+    Ok(())
+}
+
+// The export tag of an item, if it has one
+fn item_tag(item: &Metadata) -> Option<&str> {
+    match item {
+        Metadata::Func(meta) => meta.tag.as_deref(),
+        Metadata::Constructor(meta) => meta.tag.as_deref(),
+        Metadata::Method(meta) => meta.tag.as_deref(),
+        Metadata::Record(meta) => meta.tag.as_deref(),
+        Metadata::Enum(meta) => meta.tag.as_deref(),
+        Metadata::Object(meta) => meta.tag.as_deref(),
+        Metadata::CallbackInterface(meta) => meta.tag.as_deref(),
+        Metadata::Constant(meta) => meta.tag.as_deref(),
+        _ => None,
+    }
+}
+
+// The name of the type an item defines, if it defines one which can be tagged
+fn defined_type_name(item: &Metadata) -> Option<&str> {
+    match item {
+        Metadata::Record(meta) => Some(&meta.name),
+        Metadata::Enum(meta) => Some(&meta.name),
+        Metadata::Object(meta) => Some(&meta.name),
+        Metadata::CallbackInterface(meta) => Some(&meta.name),
+        _ => None,
+    }
+}
+
+// The name of the type an item is a member of, if it's a member of one
+fn member_of(item: &Metadata) -> Option<&str> {
+    match item {
+        Metadata::Constructor(meta) => Some(&meta.self_name),
+        Metadata::Method(meta) => Some(&meta.self_name),
+        Metadata::TraitMethod(meta) => Some(&meta.trait_name),
+        Metadata::TraitConstant(meta) => Some(&meta.trait_name),
+        Metadata::UniffiTrait(meta) => Some(meta.self_name()),
+        _ => None,
+    }
+}
+
+/// The crates reachable from `root`, including `root` itself
+///
+/// `dependencies` returns the crates which a crate refers to directly.  Each crate is only
+/// visited once, so cycles between crates are fine.
+pub fn reachable_crates<I>(root: &str, mut dependencies: impl FnMut(&str) -> I) -> BTreeSet<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut reached = BTreeSet::from([root.to_owned()]);
+    let mut to_visit = vec![root.to_owned()];
+    while let Some(crate_name) = to_visit.pop() {
+        for dependency in dependencies(&crate_name) {
+            if reached.insert(dependency.clone()) {
+                to_visit.push(dependency);
+            }
+        }
+    }
+    reached
+}
+
+// The types used directly by an item
+fn item_types(item: &Metadata) -> Vec<&Type> {
+    fn signature<'a>(
+        inputs: &'a [FnParamMetadata],
+        return_type: Option<&'a Type>,
+        throws: Option<&'a Type>,
+    ) -> Vec<&'a Type> {
+        inputs
+            .iter()
+            .map(|param| &param.ty)
+            .chain(return_type)
+            .chain(throws)
+            .collect()
+    }
+
+    match item {
+        Metadata::Func(meta) => signature(
+            &meta.inputs,
+            meta.return_type.as_ref(),
+            meta.throws.as_ref(),
+        ),
+        Metadata::Constructor(meta) => signature(&meta.inputs, None, meta.throws.as_ref()),
+        Metadata::Method(meta) => signature(
+            &meta.inputs,
+            meta.return_type.as_ref(),
+            meta.throws.as_ref(),
+        ),
+        Metadata::TraitMethod(meta) => signature(
+            &meta.inputs,
+            meta.return_type.as_ref(),
+            meta.throws.as_ref(),
+        ),
+        Metadata::Record(meta) => meta.fields.iter().map(|field| &field.ty).collect(),
+        Metadata::Enum(meta) => meta
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .map(|field| &field.ty)
+            .collect(),
+        Metadata::CustomType(meta) => vec![&meta.builtin],
+        Metadata::Constant(meta) => vec![&meta.ty],
+        Metadata::TraitConstant(meta) => vec![&meta.ty],
+        _ => vec![],
+    }
 }
 
 /// Convert metadata items by replacing types from external crates with Type::External
@@ -91,95 +492,143 @@ struct ExternalTypeConverter<'a> {
 }
 
 impl<'a> ExternalTypeConverter<'a> {
-    fn crate_to_namespace(&self, crate_name: &str) -> String {
-        self.crate_to_namespace
-            .get(crate_name)
-            .unwrap_or_else(|| panic!("Can't find namespace for module {crate_name}"))
-            .namespace
-            .name
-            .clone()
-    }
-
-    fn convert_item(&self, item: Metadata) -> Metadata {
-        match item {
-            Metadata::Func(meta) => Metadata::Func(FnMetadata {
-                inputs: self.convert_params(meta.inputs),
-                return_type: self.convert_optional(meta.return_type),
-                throws: self.convert_optional(meta.throws),
-                ..meta
-            }),
-            Metadata::Method(meta) => Metadata::Method(MethodMetadata {
-                inputs: self.convert_params(meta.inputs),
-                return_type: self.convert_optional(meta.return_type),
-                throws: self.convert_optional(meta.throws),
-                ..meta
-            }),
-            Metadata::TraitMethod(meta) => Metadata::TraitMethod(TraitMethodMetadata {
-                inputs: self.convert_params(meta.inputs),
-                return_type: self.convert_optional(meta.return_type),
-                throws: self.convert_optional(meta.throws),
-                ..meta
-            }),
-            Metadata::Constructor(meta) => Metadata::Constructor(ConstructorMetadata {
-                inputs: self.convert_params(meta.inputs),
-                throws: self.convert_optional(meta.throws),
-                ..meta
-            }),
-            Metadata::Record(meta) => Metadata::Record(RecordMetadata {
-                fields: self.convert_fields(meta.fields),
-                ..meta
-            }),
-            Metadata::Enum(meta) => Metadata::Enum(self.convert_enum(meta)),
-            _ => item,
+    fn crate_to_namespace(&self, module_path: &str, name: &str) -> Result<String> {
+        let crate_name = calc_crate_name(module_path);
+        match self.crate_to_namespace.get(crate_name) {
+            Some(group) => Ok(group.namespace.name.clone()),
+            None => bail!(
+                "type `{name}` is from crate `{crate_name}`, which has no UniFFI metadata in \
+                 this library. Does `{crate_name}` call `uniffi::setup_scaffolding!()` or \
+                 `uniffi::include_scaffolding!()`? If the library is built from a wrapper crate, \
+                 it may need to call `uniffi::reexport_scaffolding!({crate_name})`."
+            ),
         }
     }
 
-    fn convert_params(&self, params: Vec<FnParamMetadata>) -> Vec<FnParamMetadata> {
+    fn convert_item(&self, item: Metadata) -> Result<Metadata> {
+        Ok(match item {
+            Metadata::Func(meta) => {
+                let context = || format!("function `{}`", meta.name);
+                Metadata::Func(FnMetadata {
+                    inputs: self.convert_params(meta.inputs).with_context(context)?,
+                    return_type: self
+                        .convert_return_type(meta.return_type)
+                        .with_context(context)?,
+                    throws: self.convert_throws(meta.throws).with_context(context)?,
+                    ..meta
+                })
+            }
+            Metadata::Method(meta) => {
+                let context = || format!("method `{}.{}`", meta.self_name, meta.name);
+                Metadata::Method(MethodMetadata {
+                    inputs: self.convert_params(meta.inputs).with_context(context)?,
+                    return_type: self
+                        .convert_return_type(meta.return_type)
+                        .with_context(context)?,
+                    throws: self.convert_throws(meta.throws).with_context(context)?,
+                    ..meta
+                })
+            }
+            Metadata::TraitMethod(meta) => {
+                let context = || format!("method `{}.{}`", meta.trait_name, meta.name);
+                Metadata::TraitMethod(TraitMethodMetadata {
+                    inputs: self.convert_params(meta.inputs).with_context(context)?,
+                    return_type: self
+                        .convert_return_type(meta.return_type)
+                        .with_context(context)?,
+                    throws: self.convert_throws(meta.throws).with_context(context)?,
+                    ..meta
+                })
+            }
+            Metadata::Constructor(meta) => {
+                let context = || format!("constructor `{}.{}`", meta.self_name, meta.name);
+                Metadata::Constructor(ConstructorMetadata {
+                    inputs: self.convert_params(meta.inputs).with_context(context)?,
+                    throws: self.convert_throws(meta.throws).with_context(context)?,
+                    ..meta
+                })
+            }
+            Metadata::Record(meta) => {
+                let context = || format!("record `{}`", meta.name);
+                Metadata::Record(RecordMetadata {
+                    fields: self.convert_fields(meta.fields).with_context(context)?,
+                    ..meta
+                })
+            }
+            Metadata::Enum(meta) => {
+                let context = format!("enum `{}`", meta.name);
+                Metadata::Enum(self.convert_enum(meta).context(context)?)
+            }
+            _ => item,
+        })
+    }
+
+    fn convert_params(&self, params: Vec<FnParamMetadata>) -> Result<Vec<FnParamMetadata>> {
         params
             .into_iter()
-            .map(|param| FnParamMetadata {
-                ty: self.convert_type(param.ty),
-                ..param
+            .map(|param| {
+                Ok(FnParamMetadata {
+                    ty: self
+                        .convert_type(param.ty)
+                        .with_context(|| format!("parameter `{}`", param.name))?,
+                    ..param
+                })
             })
             .collect()
     }
 
-    fn convert_fields(&self, fields: Vec<FieldMetadata>) -> Vec<FieldMetadata> {
+    fn convert_fields(&self, fields: Vec<FieldMetadata>) -> Result<Vec<FieldMetadata>> {
         fields
             .into_iter()
-            .map(|field| FieldMetadata {
-                ty: self.convert_type(field.ty),
-                ..field
+            .map(|field| {
+                Ok(FieldMetadata {
+                    ty: self
+                        .convert_type(field.ty)
+                        .with_context(|| format!("field `{}`", field.name))?,
+                    ..field
+                })
             })
             .collect()
     }
 
-    fn convert_enum(&self, enum_: EnumMetadata) -> EnumMetadata {
-        EnumMetadata {
+    fn convert_enum(&self, enum_: EnumMetadata) -> Result<EnumMetadata> {
+        Ok(EnumMetadata {
             variants: enum_
                 .variants
                 .into_iter()
-                .map(|variant| VariantMetadata {
-                    fields: self.convert_fields(variant.fields),
-                    ..variant
+                .map(|variant| {
+                    Ok(VariantMetadata {
+                        fields: self
+                            .convert_fields(variant.fields)
+                            .with_context(|| format!("variant `{}`", variant.name))?,
+                        ..variant
+                    })
                 })
-                .collect(),
+                .collect::<Result<_>>()?,
             ..enum_
-        }
+        })
+    }
+
+    fn convert_return_type(&self, ty: Option<Type>) -> Result<Option<Type>> {
+        self.convert_optional(ty).context("return type")
     }
 
-    fn convert_optional(&self, ty: Option<Type>) -> Option<Type> {
-        ty.map(|ty| self.convert_type(ty))
+    fn convert_throws(&self, ty: Option<Type>) -> Result<Option<Type>> {
+        self.convert_optional(ty).context("error type")
     }
 
-    fn convert_type(&self, ty: Type) -> Type {
-        match ty {
+    fn convert_optional(&self, ty: Option<Type>) -> Result<Option<Type>> {
+        ty.map(|ty| self.convert_type(ty)).transpose()
+    }
+
+    fn convert_type(&self, ty: Type) -> Result<Type> {
+        Ok(match ty {
             // Convert `ty` if it's external
             Type::Enum { module_path, name } | Type::Record { module_path, name }
                 if self.is_module_path_external(&module_path) =>
             {
                 Type::External {
-                    namespace: self.crate_to_namespace(&module_path),
+                    namespace: self.crate_to_namespace(&module_path, &name)?,
                     module_path,
                     name,
                     kind: ExternalKind::DataClass,
@@ -192,7 +641,7 @@ impl<'a> ExternalTypeConverter<'a> {
                 // For now, it's safe to assume that all custom types are data classes.
                 // There's no reason to use a custom type with an interface.
                 Type::External {
-                    namespace: self.crate_to_namespace(&module_path),
+                    namespace: self.crate_to_namespace(&module_path, &name)?,
                     module_path,
                     name,
                     kind: ExternalKind::DataClass,
@@ -202,7 +651,7 @@ impl<'a> ExternalTypeConverter<'a> {
             Type::Object {
                 module_path, name, ..
             } if self.is_module_path_external(&module_path) => Type::External {
-                namespace: self.crate_to_namespace(&module_path),
+                namespace: self.crate_to_namespace(&module_path, &name)?,
                 module_path,
                 name,
                 kind: ExternalKind::Interface,
@@ -211,7 +660,13 @@ impl<'a> ExternalTypeConverter<'a> {
             Type::CallbackInterface { module_path, name }
                 if self.is_module_path_external(&module_path) =>
             {
-                panic!("External callback interfaces not supported ({name})")
+                Type::External {
+                    namespace: self.crate_to_namespace(&module_path, &name)?,
+                    module_path,
+                    name,
+                    kind: ExternalKind::CallbackInterface,
+                    tagged: false,
+                }
             }
             // Convert child types
             Type::Custom {
@@ -222,20 +677,20 @@ impl<'a> ExternalTypeConverter<'a> {
             } => Type::Custom {
                 module_path,
                 name,
-                builtin: Box::new(self.convert_type(*builtin)),
+                builtin: Box::new(self.convert_type(*builtin)?),
             },
             Type::Optional { inner_type } => Type::Optional {
-                inner_type: Box::new(self.convert_type(*inner_type)),
+                inner_type: Box::new(self.convert_type(*inner_type)?),
             },
             Type::Sequence { inner_type } => Type::Sequence {
-                inner_type: Box::new(self.convert_type(*inner_type)),
+                inner_type: Box::new(self.convert_type(*inner_type)?),
             },
             Type::Map {
                 key_type,
                 value_type,
             } => Type::Map {
-                key_type: Box::new(self.convert_type(*key_type)),
-                value_type: Box::new(self.convert_type(*value_type)),
+                key_type: Box::new(self.convert_type(*key_type)?),
+                value_type: Box::new(self.convert_type(*value_type)?),
             },
             // Existing External types probably need namespace fixed.
             Type::External {
@@ -247,7 +702,7 @@ impl<'a> ExternalTypeConverter<'a> {
             } => {
                 assert!(namespace.is_empty());
                 Type::External {
-                    namespace: self.crate_to_namespace(&module_path),
+                    namespace: self.crate_to_namespace(&module_path, &name)?,
                     module_path,
                     name,
                     kind,
@@ -257,7 +712,7 @@ impl<'a> ExternalTypeConverter<'a> {
 
             // Otherwise, just return the type unchanged
             _ => ty,
-        }
+        })
     }
 
     fn is_module_path_external(&self, module_path: &str) -> bool {
@@ -268,3 +723,569 @@ impl<'a> ExternalTypeConverter<'a> {
 fn calc_crate_name(module_path: &str) -> &str {
     module_path.split("::").next().unwrap()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn namespace(crate_name: &str) -> Metadata {
+        Metadata::Namespace(NamespaceMetadata {
+            crate_name: crate_name.to_string(),
+            name: crate_name.to_string(),
+        })
+    }
+
+    fn func(module_path: &str, name: &str, checksum: u16) -> Metadata {
+        Metadata::Func(FnMetadata {
+            module_path: module_path.to_string(),
+            name: name.to_string(),
+            is_async: false,
+            inputs: vec![],
+            return_type: None,
+            throws: None,
+            checksum: Some(checksum),
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        })
+    }
+
+    #[test]
+    fn test_unknown_namespace() {
+        let items = vec![
+            namespace("crate_a"),
+            func("crate_a", "get_foo", 1),
+            func("crate_b", "get_bar", 2),
+            func("crate_b::module", "get_baz", 3),
+            func("crate_b", "get_qux", 4),
+            func("crate_c", "get_quux", 5),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown namespace for UniFFI items:\n  \
+             crate `crate_b`: function `get_bar` and 2 other items\n  \
+             crate `crate_c`: function `get_quux`\n\
+             Each crate with UniFFI items needs a namespace. Make sure the crate calls \
+             `uniffi::setup_scaffolding!()`, or `uniffi::include_scaffolding!()` if it uses a UDL \
+             file, and that the call isn't disabled by a feature flag."
+        );
+    }
+
+    #[test]
+    fn test_duplicate_item() {
+        let items = vec![
+            namespace("crate_a"),
+            func("crate_a", "get_foo", 1),
+            func("crate_a", "get_foo", 1),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate metadata item: function `get_foo` in crate `crate_a` (module path \
+             `crate_a`). This usually means the crate was compiled into the library more than \
+             once."
+        );
+    }
+
+    #[test]
+    fn test_conflicting_items() {
+        let items = vec![
+            namespace("crate_a"),
+            func("crate_a", "get_foo", 1),
+            func("crate_a::submodule", "get_foo", 2),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Conflicting metadata for function `get_foo` in crate `crate_a` (module paths \
+             `crate_a` and `crate_a::submodule`): the checksums differ (1 and 2). This usually \
+             means two versions of `crate_a` are linked into the library."
+        );
+    }
+
+    #[test]
+    fn test_duplicate_catch_all_error() {
+        let catch_all_error = |module_path: &str| {
+            Metadata::Enum(EnumMetadata {
+                module_path: module_path.to_string(),
+                name: CATCH_ALL_ERROR_NAME.to_string(),
+                shape: EnumShape::Error { flat: true },
+                variants: vec![VariantMetadata {
+                    name: CATCH_ALL_ERROR_VARIANT.to_string(),
+                    discr: None,
+                    fields: vec![],
+                    docstring: None,
+                    deprecated: None,
+                }],
+                discr_type: None,
+                non_exhaustive: false,
+                docstring: None,
+                tag: None,
+            })
+        };
+        // Each function exported with `catch_all` declares the error, so identical copies are
+        // merged.
+        let items = vec![
+            namespace("crate_a"),
+            catch_all_error("crate_a"),
+            catch_all_error("crate_a"),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items).unwrap();
+        assert_eq!(group_map["crate_a"].items.len(), 1);
+
+        // Copies from different versions of the crate still conflict
+        let items = vec![
+            namespace("crate_a"),
+            catch_all_error("crate_a"),
+            catch_all_error("crate_a::submodule"),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Conflicting metadata for enum `CatchAllError` in crate `crate_a`"));
+    }
+
+    #[test]
+    fn test_same_name_different_kinds() {
+        let items = vec![
+            namespace("crate_a"),
+            func("crate_a", "foo", 1),
+            Metadata::Record(RecordMetadata {
+                module_path: "crate_a".to_string(),
+                name: "foo".to_string(),
+                fields: vec![],
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items).unwrap();
+        assert_eq!(group_map["crate_a"].items.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_external_crate() {
+        let items = vec![
+            namespace("crate_a"),
+            Metadata::Record(RecordMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Outer".to_string(),
+                fields: vec![FieldMetadata {
+                    name: "inner".to_string(),
+                    ty: Type::Record {
+                        module_path: "crate_b::submodule".to_string(),
+                        name: "Inner".to_string(),
+                    },
+                    default: None,
+                    is_source: false,
+                    docstring: None,
+                    deprecated: None,
+                }],
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "record `Outer`: field `inner`: type `Inner` is from crate `crate_b`, which has no \
+             UniFFI metadata in this library. Does `crate_b` call \
+             `uniffi::setup_scaffolding!()` or `uniffi::include_scaffolding!()`? If the library \
+             is built from a wrapper crate, it may need to call \
+             `uniffi::reexport_scaffolding!(crate_b)`."
+        );
+    }
+
+    #[test]
+    fn test_type_docstrings() {
+        let external_record = |name: &str| Type::Record {
+            module_path: "crate_b::submodule".to_string(),
+            name: name.to_string(),
+        };
+        let items = vec![
+            namespace("crate_a"),
+            Metadata::Func(FnMetadata {
+                module_path: "crate_a".to_string(),
+                name: "func".to_string(),
+                is_async: false,
+                inputs: vec![FnParamMetadata::simple(
+                    "arg",
+                    external_record("Documented"),
+                )],
+                return_type: Some(external_record("Undocumented")),
+                throws: None,
+                checksum: None,
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+            namespace("crate_b"),
+            Metadata::Record(RecordMetadata {
+                module_path: "crate_b::submodule".to_string(),
+                name: "Documented".to_string(),
+                fields: vec![],
+                docstring: Some("A record from crate_b".to_string()),
+                tag: None,
+                deprecated: None,
+            }),
+            Metadata::Record(RecordMetadata {
+                module_path: "crate_b::submodule".to_string(),
+                name: "Undocumented".to_string(),
+                fields: vec![],
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items).unwrap();
+        let docstrings = type_docstrings(group_map.values().flat_map(|group| &group.items));
+        assert_eq!(
+            docstrings,
+            HashMap::from([(
+                ("crate_b".to_string(), "Documented".to_string()),
+                "A record from crate_b".to_string()
+            )])
+        );
+        // The docstrings aren't part of the external types, so documenting a type doesn't
+        // change the types which refer to it.
+        let Some(Metadata::Func(func)) = group_map["crate_a"].items.first() else {
+            panic!("missing function");
+        };
+        assert_eq!(
+            func.inputs[0].ty,
+            Type::External {
+                module_path: "crate_b::submodule".to_string(),
+                name: "Documented".to_string(),
+                namespace: "crate_b".to_string(),
+                kind: ExternalKind::DataClass,
+                tagged: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_external_crate_in_function_param() {
+        let items = vec![
+            namespace("crate_a"),
+            Metadata::Func(FnMetadata {
+                module_path: "crate_a".to_string(),
+                name: "func".to_string(),
+                is_async: false,
+                inputs: vec![FnParamMetadata::simple(
+                    "arg",
+                    Type::Optional {
+                        inner_type: Box::new(Type::Object {
+                            module_path: "crate_b".to_string(),
+                            name: "Obj".to_string(),
+                            imp: ObjectImpl::Struct,
+                        }),
+                    },
+                )],
+                return_type: None,
+                throws: None,
+                checksum: None,
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("function `func`: parameter `arg`: type `Obj` is from crate `crate_b`"));
+    }
+
+    #[test]
+    fn test_item_order() {
+        fn enum_(name: &str, shape: EnumShape) -> Metadata {
+            Metadata::Enum(EnumMetadata {
+                module_path: "crate_a".to_string(),
+                name: name.to_string(),
+                shape,
+                variants: vec![],
+                discr_type: None,
+                non_exhaustive: false,
+                docstring: None,
+                tag: None,
+            })
+        }
+        fn method(self_name: &str, name: &str) -> Metadata {
+            Metadata::Method(MethodMetadata {
+                module_path: "crate_a".to_string(),
+                self_name: self_name.to_string(),
+                name: name.to_string(),
+                is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: None,
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            })
+        }
+
+        let items = vec![
+            namespace("crate_a"),
+            func("crate_a", "make_b", 1),
+            method("Zebra", "run"),
+            Metadata::Object(ObjectMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Zebra".to_string(),
+                imp: ObjectImpl::Struct,
+                docstring: None,
+                tag: None,
+            }),
+            enum_("Failure", EnumShape::Error { flat: true }),
+            method("Apple", "eat"),
+            Metadata::CallbackInterface(CallbackInterfaceMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Listener".to_string(),
+                docstring: None,
+                tag: None,
+            }),
+            enum_("Color", EnumShape::Enum),
+            Metadata::Record(RecordMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Point".to_string(),
+                fields: vec![],
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            }),
+            func("crate_a::submodule", "make_a", 2),
+            Metadata::Object(ObjectMetadata {
+                module_path: "crate_a".to_string(),
+                name: "Apple".to_string(),
+                imp: ObjectImpl::Struct,
+                docstring: None,
+                tag: None,
+            }),
+        ];
+        let group_items = |items: Vec<Metadata>| {
+            let mut group_map = create_metadata_groups(&items);
+            group_metadata(&mut group_map, items).unwrap();
+            group_map.remove("crate_a").unwrap().items
+        };
+        let first = group_items(items.clone());
+        let second = group_items(items.into_iter().rev().collect());
+        assert_eq!(first, second);
+        assert_eq!(
+            first.iter().map(describe_item).collect::<Vec<_>>(),
+            [
+                "record `Point`",
+                "enum `Color`",
+                "enum `Failure`",
+                "object `Apple`",
+                "object `Zebra`",
+                "callback interface `Listener`",
+                "function `make_a`",
+                "function `make_b`",
+                "method `Apple.eat`",
+                "method `Zebra.run`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_namespace_collision() {
+        let items = vec![
+            namespace("crate_a"),
+            Metadata::Namespace(NamespaceMetadata {
+                crate_name: "crate_a_ext".to_string(),
+                name: "crate_a".to_string(),
+            }),
+            Metadata::UdlFile(UdlFile {
+                module_path: "crate_b".to_string(),
+                namespace: "crate_a".to_string(),
+                file_stub: "crate_b".to_string(),
+                file_path: None,
+            }),
+            namespace("crate_c"),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        let err = group_metadata(&mut group_map, items).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "More than one crate uses the same UniFFI namespace:\n  \
+             namespace `crate_a`: crates `crate_a`, `crate_a_ext`, `crate_b`\n\
+             The bindings for each crate are named after its namespace, so they would overwrite \
+             each other. Give each crate a different namespace."
+        );
+    }
+
+    #[test]
+    fn test_crate_dependencies() {
+        // A record in `crate_name` with a field of type `ty`
+        fn record(crate_name: &str, ty: Type) -> Metadata {
+            Metadata::Record(RecordMetadata {
+                module_path: crate_name.to_string(),
+                name: format!("{crate_name}_record"),
+                fields: vec![FieldMetadata {
+                    name: "field".to_string(),
+                    ty,
+                    default: None,
+                    is_source: false,
+                    docstring: None,
+                    deprecated: None,
+                }],
+                docstring: None,
+                tag: None,
+                deprecated: None,
+            })
+        }
+        fn other_record(crate_name: &str) -> Type {
+            Type::Record {
+                module_path: format!("{crate_name}::submodule"),
+                name: format!("{crate_name}_record"),
+            }
+        }
+        // `crate_a` uses `crate_b`, which is in a cycle with `crate_c`.  `crate_d` uses `crate_a`
+        // but nothing uses `crate_d`.
+        let items = vec![
+            namespace("crate_a"),
+            namespace("crate_b"),
+            namespace("crate_c"),
+            namespace("crate_d"),
+            record(
+                "crate_a",
+                Type::Sequence {
+                    inner_type: Box::new(other_record("crate_b")),
+                },
+            ),
+            record("crate_b", other_record("crate_c")),
+            record("crate_c", other_record("crate_b")),
+            record("crate_d", other_record("crate_a")),
+        ];
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items).unwrap();
+        assert_eq!(
+            crate_dependencies(&group_map, "crate_a").unwrap(),
+            BTreeSet::from(["crate_a".into(), "crate_b".into(), "crate_c".into()])
+        );
+        assert_eq!(
+            crate_dependencies(&group_map, "crate_c").unwrap(),
+            BTreeSet::from(["crate_b".into(), "crate_c".into()])
+        );
+        assert_eq!(
+            crate_dependencies(&group_map, "crate_e")
+                .unwrap_err()
+                .to_string(),
+            "Crate `crate_e` has no UniFFI metadata"
+        );
+    }
+
+    #[test]
+    fn test_retain_included_tags() {
+        fn tagged_func(name: &str, tag: Option<&str>, return_type: Option<Type>) -> Metadata {
+            Metadata::Func(FnMetadata {
+                module_path: "crate_a".to_string(),
+                name: name.to_string(),
+                is_async: false,
+                inputs: vec![],
+                return_type,
+                throws: None,
+                checksum: None,
+                docstring: None,
+                tag: tag.map(str::to_string),
+                deprecated: None,
+            })
+        }
+        let object = Metadata::Object(ObjectMetadata {
+            module_path: "crate_a".to_string(),
+            name: "ProClient".to_string(),
+            imp: ObjectImpl::Struct,
+            docstring: None,
+            tag: Some("pro".to_string()),
+        });
+        let method = Metadata::Method(MethodMetadata {
+            module_path: "crate_a".to_string(),
+            self_name: "ProClient".to_string(),
+            name: "fetch".to_string(),
+            is_async: false,
+            consumes: false,
+            is_static: false,
+            is_getter: false,
+            inputs: vec![],
+            return_type: None,
+            throws: None,
+            takes_self_by_arc: false,
+            checksum: None,
+            docstring: None,
+            tag: None,
+            deprecated: None,
+        });
+        let pro_client = Type::Object {
+            module_path: "crate_a".to_string(),
+            name: "ProClient".to_string(),
+            imp: ObjectImpl::Struct,
+        };
+        let items = vec![
+            namespace("crate_a"),
+            object.clone(),
+            method,
+            tagged_func("basic", None, None),
+            tagged_func("beta", Some("beta"), None),
+            tagged_func("new_client", Some("pro"), Some(pro_client.clone())),
+        ];
+
+        // With no tags included, only the untagged items are kept.  The method is removed along
+        // with its object.
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items.clone()).unwrap();
+        let include_tags = HashMap::from([("crate_a".to_string(), BTreeSet::new())]);
+        retain_included_tags(&mut group_map, &include_tags).unwrap();
+        assert_eq!(
+            group_map["crate_a"].items,
+            BTreeSet::from([tagged_func("basic", None, None)])
+        );
+
+        // Crates which aren't in the map keep all their items.
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items.clone()).unwrap();
+        retain_included_tags(&mut group_map, &HashMap::new()).unwrap();
+        assert_eq!(group_map["crate_a"].items.len(), 5);
+
+        let include_tags =
+            HashMap::from([("crate_a".to_string(), BTreeSet::from(["pro".to_string()]))]);
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items.clone()).unwrap();
+        retain_included_tags(&mut group_map, &include_tags).unwrap();
+        assert!(group_map["crate_a"].items.contains(&object));
+        assert!(!group_map["crate_a"]
+            .items
+            .contains(&tagged_func("beta", Some("beta"), None)));
+        assert_eq!(group_map["crate_a"].items.len(), 4);
+
+        // An untagged item which uses a removed type is an error.
+        let mut items = items;
+        items.push(tagged_func("get_client", None, Some(pro_client)));
+        let mut group_map = create_metadata_groups(&items);
+        group_metadata(&mut group_map, items).unwrap();
+        let include_tags =
+            HashMap::from([("crate_a".to_string(), BTreeSet::from(["beta".to_string()]))]);
+        assert_eq!(
+            retain_included_tags(&mut group_map, &include_tags)
+                .unwrap_err()
+                .to_string(),
+            "Types left out by `include_tags` are used by items which are kept:\n  \
+             crate `crate_a`: function `get_client` uses `ProClient`, which has the tag `pro`\n\
+             Either include the tags of the types or tag the items which use them."
+        );
+    }
+}
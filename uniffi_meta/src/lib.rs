@@ -3,13 +3,21 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::{collections::BTreeMap, hash::Hasher};
+
+use serde::{Deserialize, Serialize};
 pub use uniffi_checksum_derive::Checksum;
 
+mod compare;
+pub use compare::{item_mismatches, literal_name, same_type, type_name, udl_type_name, Mismatch};
+
 mod ffi_names;
 pub use ffi_names::*;
 
 mod group;
-pub use group::{create_metadata_groups, fixup_external_type, group_metadata, MetadataGroup};
+pub use group::{
+    crate_dependencies, create_metadata_groups, describe_item, fixup_external_type, group_metadata,
+    reachable_crates, retain_included_tags, type_docstrings, MetadataGroup, TypeDocstringMap,
+};
 
 mod reader;
 pub use reader::{read_metadata, read_metadata_type};
@@ -25,6 +33,15 @@ mod metadata;
 // Once we get to 1.0, then we'll need to update the scheme to something like 100 + major_version
 pub const UNIFFI_CONTRACT_VERSION: u32 = 26;
 
+/// Name of the error returned by functions exported with `catch_all`
+///
+/// Each crate which has such functions declares this error, as a flat error with a single
+/// [CATCH_ALL_ERROR_VARIANT] variant.
+pub const CATCH_ALL_ERROR_NAME: &str = "CatchAllError";
+
+/// Name of the only variant of the [CATCH_ALL_ERROR_NAME] error
+pub const CATCH_ALL_ERROR_VARIANT: &str = "Failed";
+
 /// Similar to std::hash::Hash.
 ///
 /// Implementations of this trait are expected to update the hasher state in
@@ -39,6 +56,12 @@ impl Checksum for bool {
     }
 }
 
+impl Checksum for u8 {
+    fn checksum<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(*self);
+    }
+}
+
 impl Checksum for u64 {
     fn checksum<H: Hasher>(&self, state: &mut H) {
         state.write(&self.to_le_bytes());
@@ -116,25 +139,42 @@ impl Checksum for &str {
 // The namespace of a Component interface.
 //
 // This is used to match up the macro metadata with the UDL items.
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NamespaceMetadata {
     pub crate_name: String,
     pub name: String,
 }
 
+// Docstring for a namespace, taken from the crate-level (`//!`) docs.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NamespaceDocstringMetadata {
+    pub module_path: String,
+    pub docstring: String,
+}
+
+// The `UNIFFI_CONTRACT_VERSION` a crate's scaffolding was built with.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ContractVersionMetadata {
+    pub module_path: String,
+    pub version: u32,
+}
+
 // UDL file included with `include_scaffolding!()`
 //
 // This is to find the UDL files in library mode generation
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UdlFile {
     // The module path specified when the UDL file was parsed.
     pub module_path: String,
     pub namespace: String,
     // the base filename of the udl file - no path, no extension.
     pub file_stub: String,
+    // The path of the udl file relative to the crate root, if it was known when the scaffolding
+    // was generated.
+    pub file_path: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FnMetadata {
     pub module_path: String,
     pub name: String,
@@ -144,6 +184,15 @@ pub struct FnMetadata {
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    /// The export tag, from `tag = "..."`.  Generating the bindings with `include_tags` leaves
+    /// out the items whose tag isn't included.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// The deprecation message, from `deprecated = "..."`.  It's empty if the function was
+    /// deprecated without a message.  It isn't part of the checksum, so deprecating a function
+    /// doesn't break the bindings generated before.
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 impl FnMetadata {
@@ -156,7 +205,7 @@ impl FnMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ConstructorMetadata {
     pub module_path: String,
     pub self_name: String,
@@ -166,6 +215,10 @@ pub struct ConstructorMetadata {
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 impl ConstructorMetadata {
@@ -182,18 +235,34 @@ impl ConstructorMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct MethodMetadata {
     pub module_path: String,
     pub self_name: String,
     pub name: String,
     pub is_async: bool,
+    /// Does the method consume the object?  Foreign bindings invalidate their handle to the
+    /// object once the method has been called.
+    #[serde(default)]
+    pub consumes: bool,
+    /// Is this a static method, which doesn't take `self` at all?  Foreign bindings make it a
+    /// static member of the object's class.
+    #[serde(default)]
+    pub is_static: bool,
+    /// Is this a getter generated for a public field of the object?  Foreign bindings make it a
+    /// read-only property named after the field.
+    #[serde(default)]
+    pub is_getter: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 impl MethodMetadata {
@@ -206,21 +275,27 @@ impl MethodMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TraitMethodMetadata {
     pub module_path: String,
     pub trait_name: String,
     // Note: the position of `index` is important since it causes callback interface methods to be
-    // ordered correctly in MetadataGroup.items
+    // ordered correctly in MetadataGroup.items (see `Metadata::sort_key`)
     pub index: u32,
     pub name: String,
     pub is_async: bool,
+    /// Does the method have a default implementation in Rust, which foreign implementations of
+    /// the trait can use instead of implementing it?
+    #[serde(default)]
+    pub has_default: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 impl TraitMethodMetadata {
@@ -228,12 +303,18 @@ impl TraitMethodMetadata {
         method_symbol_name(&self.module_path, &self.trait_name, &self.name)
     }
 
+    /// The function which runs the Rust default implementation of the method, for foreign
+    /// implementations of the trait
+    pub fn default_ffi_symbol_name(&self) -> String {
+        default_method_symbol_name(&self.module_path, &self.trait_name, &self.name)
+    }
+
     pub fn checksum_symbol_name(&self) -> String {
         method_checksum_symbol_name(&self.module_path, &self.trait_name, &self.name)
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FnParamMetadata {
     pub name: String,
     pub ty: Type,
@@ -254,7 +335,7 @@ impl FnParamMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize, Deserialize)]
 pub enum LiteralMetadata {
     Boolean(bool),
     String(String),
@@ -269,6 +350,7 @@ pub enum LiteralMetadata {
     // will have to do extra work.
     Float(String, Type),
     Enum(String, Type),
+    Bytes(Vec<u8>),
     EmptySequence,
     EmptyMap,
     None,
@@ -286,30 +368,40 @@ impl LiteralMetadata {
 
 // Represent the radix of integer literal values.
 // We preserve the radix into the generated bindings for readability reasons.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize, Deserialize)]
 pub enum Radix {
     Decimal = 10,
     Octal = 8,
     Hexadecimal = 16,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct RecordMetadata {
     pub module_path: String,
     pub name: String,
     pub fields: Vec<FieldMetadata>,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FieldMetadata {
     pub name: String,
     pub ty: Type,
     pub default: Option<LiteralMetadata>,
+    /// Is this error variant field the cause of the error, marked with `#[uniffi(source)]`?
+    /// Its type is another error or a string.
+    #[serde(default)]
+    pub is_source: bool,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize, Deserialize)]
 pub enum EnumShape {
     Enum,
     Error { flat: bool },
@@ -334,7 +426,7 @@ impl EnumShape {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct EnumMetadata {
     pub module_path: String,
     pub name: String,
@@ -343,29 +435,37 @@ pub struct EnumMetadata {
     pub discr_type: Option<Type>,
     pub non_exhaustive: bool,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct VariantMetadata {
     pub name: String,
     pub discr: Option<LiteralMetadata>,
     pub fields: Vec<FieldMetadata>,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ObjectMetadata {
     pub module_path: String,
     pub name: String,
     pub imp: types::ObjectImpl,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct CallbackInterfaceMetadata {
     pub module_path: String,
     pub name: String,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 impl ObjectMetadata {
@@ -386,7 +486,7 @@ impl ObjectMetadata {
 }
 
 /// The list of traits we support generating helper methods for.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum UniffiTraitMetadata {
     Debug {
         fmt: MethodMetadata,
@@ -401,6 +501,9 @@ pub enum UniffiTraitMetadata {
     Hash {
         hash: MethodMetadata,
     },
+    Ord {
+        cmp: MethodMetadata,
+    },
 }
 
 impl UniffiTraitMetadata {
@@ -410,6 +513,7 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
         }
         .module_path
     }
@@ -420,6 +524,7 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
         }
         .self_name
     }
@@ -432,6 +537,7 @@ pub enum UniffiTraitDiscriminants {
     Display,
     Eq,
     Hash,
+    Ord,
 }
 
 impl UniffiTraitDiscriminants {
@@ -441,18 +547,69 @@ impl UniffiTraitDiscriminants {
             1 => UniffiTraitDiscriminants::Display,
             2 => UniffiTraitDiscriminants::Eq,
             3 => UniffiTraitDiscriminants::Hash,
+            4 => UniffiTraitDiscriminants::Ord,
             _ => anyhow::bail!("invalid trait discriminant {v}"),
         })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct CustomTypeMetadata {
     pub module_path: String,
     pub name: String,
     pub builtin: Type,
 }
 
+/// A constant exported with `#[uniffi::export]` or declared in the UDL namespace
+///
+/// Only numbers, booleans and strings can be exported, so `value` is always one of the literals
+/// for those types.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ConstantMetadata {
+    pub module_path: String,
+    pub name: String,
+    pub ty: Type,
+    pub value: LiteralMetadata,
+    pub docstring: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl ConstantMetadata {
+    /// Can a constant of this type be exported?
+    pub fn is_supported_type(ty: &Type) -> bool {
+        matches!(
+            ty,
+            Type::UInt8
+                | Type::Int8
+                | Type::UInt16
+                | Type::Int16
+                | Type::UInt32
+                | Type::Int32
+                | Type::UInt64
+                | Type::Int64
+                | Type::Float32
+                | Type::Float64
+                | Type::Boolean
+                | Type::String
+        )
+    }
+}
+
+/// An associated constant of an exported trait
+///
+/// These are informational: foreign implementations of the trait can't override them, the
+/// bindings declare them on the generated interface with the value from Rust.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TraitConstantMetadata {
+    pub module_path: String,
+    pub trait_name: String,
+    pub name: String,
+    pub ty: Type,
+    pub value: LiteralMetadata,
+    pub docstring: Option<String>,
+}
+
 /// Returns the last 16 bits of the value's hash as computed with [`SipHasher13`].
 ///
 /// This is used as a safeguard against different UniFFI versions being used for scaffolding and
@@ -464,9 +621,14 @@ pub fn checksum<T: Checksum>(val: &T) -> u16 {
 }
 
 /// Enum covering all the possible metadata types
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Items are ordered by kind and then by name, see [Metadata::sort_key].  This is the order of
+/// the items in a [MetadataGroup] and so the order they're generated in the bindings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Metadata {
     Namespace(NamespaceMetadata),
+    NamespaceDocstring(NamespaceDocstringMetadata),
+    ContractVersion(ContractVersionMetadata),
     UdlFile(UdlFile),
     Func(FnMetadata),
     Object(ObjectMetadata),
@@ -478,6 +640,8 @@ pub enum Metadata {
     TraitMethod(TraitMethodMetadata),
     CustomType(CustomTypeMetadata),
     UniffiTrait(UniffiTraitMetadata),
+    Constant(ConstantMetadata),
+    TraitConstant(TraitConstantMetadata),
 }
 
 impl Metadata {
@@ -488,6 +652,8 @@ impl Metadata {
     pub(crate) fn module_path(&self) -> &String {
         match self {
             Metadata::Namespace(meta) => &meta.crate_name,
+            Metadata::NamespaceDocstring(meta) => &meta.module_path,
+            Metadata::ContractVersion(meta) => &meta.module_path,
             Metadata::UdlFile(meta) => &meta.module_path,
             Metadata::Func(meta) => &meta.module_path,
             Metadata::Constructor(meta) => &meta.module_path,
@@ -499,16 +665,100 @@ impl Metadata {
             Metadata::TraitMethod(meta) => &meta.module_path,
             Metadata::CustomType(meta) => &meta.module_path,
             Metadata::UniffiTrait(meta) => meta.module_path(),
+            Metadata::Constant(meta) => &meta.module_path,
+            Metadata::TraitConstant(meta) => &meta.module_path,
+        }
+    }
+
+    /// The key that items are sorted by
+    ///
+    /// Items are grouped by kind: records, enums, errors, objects, callback interfaces, custom
+    /// types, then functions.  Constructors, methods and trait methods come last, since they're
+    /// added to the objects and callback interfaces they belong to.  Within each kind, items are
+    /// sorted by name, with members sorted by the name of their type first.
+    ///
+    /// The position of a variant in this enum doesn't affect the order, so adding a new kind of
+    /// item doesn't reorder the existing ones.
+    pub fn sort_key(&self) -> (u8, &str, &str) {
+        match self {
+            Metadata::Namespace(meta) => (0, &meta.crate_name, ""),
+            Metadata::NamespaceDocstring(meta) => (1, &meta.module_path, ""),
+            Metadata::ContractVersion(meta) => (2, &meta.module_path, ""),
+            Metadata::UdlFile(meta) => (3, &meta.module_path, ""),
+            Metadata::Record(meta) => (4, &meta.name, ""),
+            Metadata::Enum(meta) => match meta.shape {
+                EnumShape::Enum => (5, &meta.name, ""),
+                EnumShape::Error { .. } => (6, &meta.name, ""),
+            },
+            Metadata::Object(meta) => (7, &meta.name, ""),
+            Metadata::CallbackInterface(meta) => (8, &meta.name, ""),
+            Metadata::CustomType(meta) => (9, &meta.name, ""),
+            Metadata::Func(meta) => (10, &meta.name, ""),
+            Metadata::Constructor(meta) => (11, &meta.self_name, &meta.name),
+            Metadata::Method(meta) => (12, &meta.self_name, &meta.name),
+            // Trait methods stay in the order they're declared in, since that's the order of the
+            // vtable.  They're sorted by `index` when the sort keys are equal.
+            Metadata::TraitMethod(meta) => (13, &meta.trait_name, ""),
+            Metadata::UniffiTrait(meta) => (14, meta.self_name(), ""),
+            Metadata::Constant(meta) => (15, &meta.name, ""),
+            Metadata::TraitConstant(meta) => (16, &meta.trait_name, &meta.name),
         }
     }
 }
 
+impl Ord for Metadata {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key()
+            .cmp(&other.sort_key())
+            // Break ties by comparing the items themselves, which is needed for things like
+            // functions with the same name in different modules.
+            .then_with(|| match (self, other) {
+                (Metadata::Namespace(a), Metadata::Namespace(b)) => a.cmp(b),
+                (Metadata::NamespaceDocstring(a), Metadata::NamespaceDocstring(b)) => a.cmp(b),
+                (Metadata::ContractVersion(a), Metadata::ContractVersion(b)) => a.cmp(b),
+                (Metadata::UdlFile(a), Metadata::UdlFile(b)) => a.cmp(b),
+                (Metadata::Func(a), Metadata::Func(b)) => a.cmp(b),
+                (Metadata::Object(a), Metadata::Object(b)) => a.cmp(b),
+                (Metadata::CallbackInterface(a), Metadata::CallbackInterface(b)) => a.cmp(b),
+                (Metadata::Record(a), Metadata::Record(b)) => a.cmp(b),
+                (Metadata::Enum(a), Metadata::Enum(b)) => a.cmp(b),
+                (Metadata::Constructor(a), Metadata::Constructor(b)) => a.cmp(b),
+                (Metadata::Method(a), Metadata::Method(b)) => a.cmp(b),
+                (Metadata::TraitMethod(a), Metadata::TraitMethod(b)) => a.cmp(b),
+                (Metadata::CustomType(a), Metadata::CustomType(b)) => a.cmp(b),
+                (Metadata::UniffiTrait(a), Metadata::UniffiTrait(b)) => a.cmp(b),
+                (Metadata::Constant(a), Metadata::Constant(b)) => a.cmp(b),
+                (Metadata::TraitConstant(a), Metadata::TraitConstant(b)) => a.cmp(b),
+                // Each kind has its own position in the sort key
+                _ => unreachable!("items of different kinds with the same sort key"),
+            })
+    }
+}
+
+impl PartialOrd for Metadata {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl From<NamespaceMetadata> for Metadata {
     fn from(value: NamespaceMetadata) -> Metadata {
         Self::Namespace(value)
     }
 }
 
+impl From<NamespaceDocstringMetadata> for Metadata {
+    fn from(value: NamespaceDocstringMetadata) -> Metadata {
+        Self::NamespaceDocstring(value)
+    }
+}
+
+impl From<ContractVersionMetadata> for Metadata {
+    fn from(value: ContractVersionMetadata) -> Metadata {
+        Self::ContractVersion(value)
+    }
+}
+
 impl From<UdlFile> for Metadata {
     fn from(value: UdlFile) -> Metadata {
         Self::UdlFile(value)
@@ -569,6 +819,18 @@ impl From<CustomTypeMetadata> for Metadata {
     }
 }
 
+impl From<ConstantMetadata> for Metadata {
+    fn from(v: ConstantMetadata) -> Self {
+        Self::Constant(v)
+    }
+}
+
+impl From<TraitConstantMetadata> for Metadata {
+    fn from(v: TraitConstantMetadata) -> Self {
+        Self::TraitConstant(v)
+    }
+}
+
 impl From<UniffiTraitMetadata> for Metadata {
     fn from(v: UniffiTraitMetadata) -> Self {
         Self::UniffiTrait(v)
@@ -23,6 +23,10 @@ pub mod codes {
     pub const UNIFFI_TRAIT: u8 = 11;
     pub const TRAIT_INTERFACE: u8 = 12;
     pub const CALLBACK_TRAIT_INTERFACE: u8 = 13;
+    pub const NAMESPACE_DOCSTRING: u8 = 14;
+    pub const CONTRACT_VERSION: u8 = 15;
+    pub const CONSTANT: u8 = 16;
+    pub const TRAIT_CONSTANT: u8 = 17;
     //pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -52,6 +56,13 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     pub const TYPE_TRAIT_INTERFACE: u8 = 24;
     pub const TYPE_CALLBACK_TRAIT_INTERFACE: u8 = 25;
+    pub const TYPE_U128: u8 = 26;
+    pub const TYPE_I128: u8 = 27;
+    pub const TYPE_CHAR: u8 = 28;
+    pub const TYPE_DATE: u8 = 29;
+    pub const TYPE_JSON: u8 = 30;
+    pub const TYPE_PATH: u8 = 31;
+    pub const TYPE_URL: u8 = 32;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes
@@ -62,6 +73,9 @@ pub mod codes {
     pub const LIT_NONE: u8 = 4;
     pub const LIT_SOME: u8 = 5;
     pub const LIT_EMPTY_SEQ: u8 = 6;
+    pub const LIT_EMPTY_MAP: u8 = 7;
+    pub const LIT_ENUM: u8 = 8;
+    pub const LIT_BYTES: u8 = 9;
 }
 
 // Create a checksum for a MetadataBuffer
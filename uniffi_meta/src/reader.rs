@@ -42,10 +42,21 @@ impl<'a> MetadataReader<'a> {
                 name: self.read_string()?,
             }
             .into(),
+            codes::NAMESPACE_DOCSTRING => NamespaceDocstringMetadata {
+                module_path: self.read_string()?,
+                docstring: self.read_long_string()?,
+            }
+            .into(),
+            codes::CONTRACT_VERSION => ContractVersionMetadata {
+                module_path: self.read_string()?,
+                version: self.read_u32()?,
+            }
+            .into(),
             codes::UDL_FILE => UdlFile {
                 module_path: self.read_string()?,
                 namespace: self.read_string()?,
                 file_stub: self.read_string()?,
+                file_path: self.read_optional_long_string()?,
             }
             .into(),
             codes::FUNC => self.read_func()?.into(),
@@ -59,6 +70,8 @@ impl<'a> MetadataReader<'a> {
             codes::CALLBACK_INTERFACE => self.read_callback_interface()?.into(),
             codes::TRAIT_METHOD => self.read_trait_method()?.into(),
             codes::UNIFFI_TRAIT => self.read_uniffi_trait()?.into(),
+            codes::CONSTANT => self.read_constant()?.into(),
+            codes::TRAIT_CONSTANT => self.read_trait_constant()?.into(),
             _ => bail!("Unexpected metadata code: {value:?}"),
         })
     }
@@ -124,10 +137,21 @@ impl<'a> MetadataReader<'a> {
         String::from_utf8(slice.into()).context("Invalid string data")
     }
 
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let size = self.read_u16()? as usize;
+        let slice;
+        (slice, self.buf) = self.buf.split_at(size);
+        Ok(slice.into())
+    }
+
     fn read_optional_long_string(&mut self) -> Result<Option<String>> {
         Ok(Some(self.read_long_string()?).filter(|str| !str.is_empty()))
     }
 
+    fn read_optional_string(&mut self) -> Result<Option<String>> {
+        Ok(Some(self.read_string()?).filter(|str| !str.is_empty()))
+    }
+
     fn read_type(&mut self) -> Result<Type> {
         let value = self.read_u8()?;
         Ok(match value {
@@ -139,6 +163,13 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_I32 => Type::Int32,
             codes::TYPE_U64 => Type::UInt64,
             codes::TYPE_I64 => Type::Int64,
+            codes::TYPE_U128 => Type::UInt128,
+            codes::TYPE_I128 => Type::Int128,
+            codes::TYPE_CHAR => Type::Char,
+            codes::TYPE_DATE => Type::Date,
+            codes::TYPE_JSON => Type::Json,
+            codes::TYPE_PATH => Type::Path,
+            codes::TYPE_URL => Type::Url,
             codes::TYPE_F32 => Type::Float32,
             codes::TYPE_F64 => Type::Float64,
             codes::TYPE_BOOL => Type::Boolean,
@@ -200,6 +231,17 @@ impl<'a> MetadataReader<'a> {
         })
     }
 
+    // Read the deprecation message of an item, which is empty if it was deprecated without one.
+    //
+    // Callables write it after the bytes their checksum is calculated from.
+    fn read_deprecated(&mut self) -> Result<Option<String>> {
+        Ok(if self.read_bool()? {
+            Some(self.read_long_string()?)
+        } else {
+            None
+        })
+    }
+
     fn read_optional_type(&mut self) -> Result<Option<Type>> {
         Ok(match self.peek_u8()? {
             codes::TYPE_UNIT => {
@@ -231,6 +273,8 @@ impl<'a> MetadataReader<'a> {
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
+        let tag = self.read_optional_string()?;
+        let checksum = self.calc_checksum();
         Ok(FnMetadata {
             module_path,
             name,
@@ -239,7 +283,51 @@ impl<'a> MetadataReader<'a> {
             return_type,
             throws,
             docstring,
-            checksum: self.calc_checksum(),
+            tag,
+            checksum,
+            deprecated: self.read_deprecated()?,
+        })
+    }
+
+    fn read_constant(&mut self) -> Result<ConstantMetadata> {
+        let module_path = self.read_string()?;
+        let name = self.read_string()?;
+        let ty = self.read_type()?;
+        ensure!(
+            ConstantMetadata::is_supported_type(&ty),
+            "constant {name} of type {ty:?} can't be exported, only numbers, booleans and strings can"
+        );
+        let value = self.read_default(&name, &ty)?;
+        let docstring = self.read_optional_long_string()?;
+        let tag = self.read_optional_string()?;
+        Ok(ConstantMetadata {
+            module_path,
+            name,
+            ty,
+            value,
+            docstring,
+            tag,
+        })
+    }
+
+    fn read_trait_constant(&mut self) -> Result<TraitConstantMetadata> {
+        let module_path = self.read_string()?;
+        let trait_name = self.read_string()?;
+        let name = self.read_string()?;
+        let ty = self.read_type()?;
+        ensure!(
+            ConstantMetadata::is_supported_type(&ty),
+            "constant {trait_name}::{name} of type {ty:?} can't be exported, only numbers, booleans and strings can"
+        );
+        let value = self.read_default(&name, &ty)?;
+        let docstring = self.read_optional_long_string()?;
+        Ok(TraitConstantMetadata {
+            module_path,
+            trait_name,
+            name,
+            ty,
+            value,
+            docstring,
         })
     }
 
@@ -251,6 +339,7 @@ impl<'a> MetadataReader<'a> {
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
+        let tag = self.read_optional_string()?;
 
         return_type
             .filter(|t| {
@@ -261,6 +350,7 @@ impl<'a> MetadataReader<'a> {
             })
             .context("Constructor return type must be Self or Arc<Self>")?;
 
+        let checksum = self.calc_checksum();
         Ok(ConstructorMetadata {
             module_path,
             self_name,
@@ -268,8 +358,10 @@ impl<'a> MetadataReader<'a> {
             name,
             inputs,
             throws,
-            checksum: self.calc_checksum(),
+            checksum,
             docstring,
+            tag,
+            deprecated: self.read_deprecated()?,
         })
     }
 
@@ -278,20 +370,30 @@ impl<'a> MetadataReader<'a> {
         let self_name = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let consumes = self.read_bool()?;
+        let is_static = self.read_bool()?;
+        let is_getter = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
+        let tag = self.read_optional_string()?;
+        let checksum = self.calc_checksum();
         Ok(MethodMetadata {
             module_path,
             self_name,
             name,
             is_async,
+            consumes,
+            is_static,
+            is_getter,
             inputs,
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
-            checksum: self.calc_checksum(),
+            checksum,
             docstring,
+            tag,
+            deprecated: self.read_deprecated()?,
         })
     }
 
@@ -301,6 +403,8 @@ impl<'a> MetadataReader<'a> {
             name: self.read_string()?,
             fields: self.read_fields()?,
             docstring: self.read_optional_long_string()?,
+            tag: self.read_optional_string()?,
+            deprecated: self.read_deprecated()?,
         })
     }
 
@@ -326,6 +430,7 @@ impl<'a> MetadataReader<'a> {
             variants,
             non_exhaustive: self.read_bool()?,
             docstring: self.read_optional_long_string()?,
+            tag: self.read_optional_string()?,
         })
     }
 
@@ -335,6 +440,7 @@ impl<'a> MetadataReader<'a> {
             name: self.read_string()?,
             imp,
             docstring: self.read_optional_long_string()?,
+            tag: self.read_optional_string()?,
         })
     }
 
@@ -360,6 +466,9 @@ impl<'a> MetadataReader<'a> {
             UniffiTraitDiscriminants::Hash => UniffiTraitMetadata::Hash {
                 hash: read_metadata_method()?,
             },
+            UniffiTraitDiscriminants::Ord => UniffiTraitMetadata::Ord {
+                cmp: read_metadata_method()?,
+            },
         })
     }
 
@@ -368,6 +477,7 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             docstring: self.read_optional_long_string()?,
+            tag: self.read_optional_string()?,
         })
     }
 
@@ -377,21 +487,25 @@ impl<'a> MetadataReader<'a> {
         let index = self.read_u32()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let has_default = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
+        let checksum = self.calc_checksum();
         Ok(TraitMethodMetadata {
             module_path,
             trait_name,
             index,
             name,
             is_async,
+            has_default,
             inputs,
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
-            checksum: self.calc_checksum(),
+            checksum,
             docstring,
+            deprecated: self.read_deprecated()?,
         })
     }
 
@@ -406,7 +520,9 @@ impl<'a> MetadataReader<'a> {
                     name,
                     ty,
                     default,
+                    is_source: self.read_bool()?,
                     docstring: self.read_optional_long_string()?,
+                    deprecated: self.read_deprecated()?,
                 })
             })
             .collect()
@@ -421,6 +537,7 @@ impl<'a> MetadataReader<'a> {
                     discr: self.read_optional_default("<variant-value>", &Type::UInt64)?,
                     fields: self.read_fields()?,
                     docstring: self.read_optional_long_string()?,
+                    deprecated: self.read_deprecated()?,
                 })
             })
             .collect()
@@ -435,6 +552,7 @@ impl<'a> MetadataReader<'a> {
                     discr: None,
                     fields: vec![],
                     docstring: self.read_optional_long_string()?,
+                    deprecated: self.read_deprecated()?,
                 })
             })
             .collect()
@@ -544,7 +662,21 @@ impl<'a> MetadataReader<'a> {
                 },
                 _ => bail!("field {name} of type {ty:?} can't have a default value of None"),
             },
+            codes::LIT_BYTES => match ty {
+                Type::Bytes => LiteralMetadata::Bytes(self.read_bytes()?),
+                _ => bail!("field {name} of type {ty:?} can't have a default value of type bytes"),
+            },
             codes::LIT_EMPTY_SEQ => LiteralMetadata::EmptySequence,
+            codes::LIT_EMPTY_MAP => match ty {
+                Type::Map { .. } => LiteralMetadata::EmptyMap,
+                _ => bail!("field {name} of type {ty:?} can't have a default value of {{}}"),
+            },
+            codes::LIT_ENUM => match ty {
+                Type::Enum { .. } => LiteralMetadata::Enum(self.read_string()?, ty.to_owned()),
+                _ => bail!(
+                    "field {name} of type {ty:?} can't have a default value which is an enum variant"
+                ),
+            },
             _ => bail!("Unexpected literal kind code: {literal_kind:?}"),
         })
     }
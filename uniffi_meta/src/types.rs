@@ -18,8 +18,9 @@
 //! by the [`ffi::FfiType`](super::ffi::FfiType) enum, but that's a detail that is invisible to end users.
 
 use crate::Checksum;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ObjectImpl {
     // A single Rust type
     Struct,
@@ -51,18 +52,20 @@ impl ObjectImpl {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ExternalKind {
     Interface,
     Trait,
     // Either a record or enum
     DataClass,
+    // A callback interface, passed as a handle
+    CallbackInterface,
 }
 
 /// Represents all the different high-level types that can be used in a component interface.
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
-#[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum Type {
     // Primitive types.
     UInt8,
@@ -73,12 +76,19 @@ pub enum Type {
     Int32,
     UInt64,
     Int64,
+    UInt128,
+    Int128,
     Float32,
     Float64,
     Boolean,
+    Char,
     String,
     Bytes,
     Timestamp,
+    Date,
+    Json,
+    Path,
+    Url,
     Duration,
     Object {
         // The module path to the object
@@ -139,6 +149,7 @@ impl Type {
                 key_type,
                 value_type,
             } => Box::new(key_type.iter_types().chain(value_type.iter_types())),
+            Type::Custom { builtin, .. } => builtin.iter_types(),
             _ => Box::new(std::iter::empty()),
         };
         Box::new(std::iter::once(self).chain(nested_types))
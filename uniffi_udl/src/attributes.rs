@@ -48,6 +48,8 @@ pub(super) enum Attribute {
     WithForeign,
     Async,
     NonExhaustive,
+    // `[Deprecated]` or `[Deprecated="message"]`, the message is empty if there isn't one.
+    Deprecated(String),
 }
 
 // A type defined in Rust via procmacros but which should be available
@@ -89,6 +91,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 "WithForeign" => Ok(Attribute::WithForeign),
                 "Async" => Ok(Attribute::Async),
                 "NonExhaustive" => Ok(Attribute::NonExhaustive),
+                "Deprecated" => Ok(Attribute::Deprecated(String::new())),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
@@ -96,6 +99,9 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 match identity.lhs_identifier.0 {
                     "Name" => Ok(Attribute::Name(name_from_id_or_string(&identity.rhs))),
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
+                    "Deprecated" => {
+                        Ok(Attribute::Deprecated(name_from_id_or_string(&identity.rhs)))
+                    }
                     "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
                     "External" => Ok(Attribute::External {
                         crate_name: name_from_id_or_string(&identity.rhs),
@@ -185,6 +191,14 @@ fn rust_kind_from_id_or_string(nm: &weedle::attribute::IdentifierOrString<'_>) -
     })
 }
 
+// The deprecation message from a `[Deprecated]` attribute, if there is one
+fn get_deprecated(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match attr {
+        Attribute::Deprecated(message) => Some(message.clone()),
+        _ => None,
+    })
+}
+
 /// Parse a weedle `ExtendedAttributeList` into a list of `Attribute`s,
 /// erroring out on duplicates.
 fn parse_attributes<F>(
@@ -263,6 +277,7 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
 /// This supports:
 ///   * `[Throws=ErrorName]` attribute for functions that can produce an error.
 ///   * `[Async] for async functions
+///   * `[Deprecated="message"]` for deprecated functions
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct FunctionAttributes(Vec<Attribute>);
 
@@ -279,6 +294,10 @@ impl FunctionAttributes {
     pub(super) fn is_async(&self) -> bool {
         self.0.iter().any(|attr| matches!(attr, Attribute::Async))
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<String> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl FromIterator<Attribute> for FunctionAttributes {
@@ -293,7 +312,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::Throws(_) | Attribute::Async | Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{attr:?} not supported for functions")),
         })?;
         Ok(Self(attrs))
@@ -405,12 +424,24 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
             Attribute::Trait => Ok(()),
             Attribute::WithForeign => Ok(()),
             Attribute::Traits(_) => Ok(()),
+            Attribute::NonExhaustive => Ok(()),
             _ => bail!(format!("{attr:?} not supported for interface definition")),
         })?;
-        if attrs.iter().any(|a| matches!(a, Attribute::Enum)) && attrs.len() != 1 {
-            // If `[Enum]` is specified it must be the only attribute.
+        let is_enum = attrs.iter().any(Attribute::is_enum);
+        if is_enum
+            && attrs
+                .iter()
+                .any(|a| !matches!(a, Attribute::Enum | Attribute::NonExhaustive))
+        {
+            // If `[Enum]` is specified it can only be non-exhaustive.
             bail!("conflicting attributes on interface definition");
         }
+        if !is_enum
+            && !attrs.iter().any(Attribute::is_error)
+            && attrs.iter().any(|a| matches!(a, Attribute::NonExhaustive))
+        {
+            bail!("NonExhaustive is only supported for `[Enum]` and `[Error]` interfaces");
+        }
         Ok(Self(attrs))
     }
 }
@@ -460,6 +491,10 @@ impl ConstructorAttributes {
     pub(super) fn is_async(&self) -> bool {
         self.0.iter().any(|attr| matches!(attr, Attribute::Async))
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<String> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttributes {
@@ -471,6 +506,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
             Attribute::Throws(_) => Ok(()),
             Attribute::Name(_) => Ok(()),
             Attribute::Async => Ok(()),
+            Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{attr:?} not supported for constructors")),
         })?;
         Ok(Self(attrs))
@@ -503,6 +539,10 @@ impl MethodAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByArc)))
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<String> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl FromIterator<Attribute> for MethodAttributes {
@@ -517,7 +557,10 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::SelfType(_) | Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::SelfType(_)
+            | Attribute::Throws(_)
+            | Attribute::Async
+            | Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{attr:?} not supported for methods")),
         })?;
         Ok(Self(attrs))
@@ -534,6 +577,47 @@ impl<T: TryInto<MethodAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for
     }
 }
 
+/// Represents UDL attributes that might appear on a dictionary, a dictionary member or a
+/// variant of an `[Enum]` or `[Error]` interface.
+///
+/// This only supports `[Deprecated="message"]`, since the other attributes of records and
+/// variants are given by the type's definition in Rust.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct DeprecatableAttributes(Vec<Attribute>);
+
+impl DeprecatableAttributes {
+    pub(super) fn get_deprecated(&self) -> Option<String> {
+        get_deprecated(&self.0)
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for DeprecatableAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Deprecated(_) => Ok(()),
+            _ => bail!(format!(
+                "{attr:?} not supported for dictionaries, dictionary members and variants"
+            )),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<DeprecatableAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for DeprecatableAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents the different possible types of method call receiver.
 ///
 /// Actually we only support one of these right now, `[Self=ByArc]`.
@@ -713,6 +797,21 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_deprecated() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Deprecated").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::Deprecated(message) if message.is_empty()));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttribute::parse("Deprecated=\"use fetch() instead\"")
+                .unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::Deprecated(message) if message == "use fetch() instead"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_unsupported() {
         let (_, node) =
@@ -852,6 +951,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_deprecated_callables() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Throws=Error, Deprecated]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_deprecated().as_deref(), Some(""));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Deprecated=\"use new()\"]").unwrap();
+        let attrs = ConstructorAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_deprecated().as_deref(), Some("use new()"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Self=ByArc]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_deprecated().is_none());
+    }
+
+    #[test]
+    fn test_deprecatable_attributes() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Deprecated=\"use Request\"]")
+                .unwrap();
+        let attrs = DeprecatableAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_deprecated().as_deref(), Some("use Request"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = DeprecatableAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ByRef not supported for dictionaries, dictionary members and variants"
+        );
+    }
+
     #[test]
     fn test_byref_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
@@ -958,6 +1090,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_non_exhaustive_interfaces() {
+        for attrs in ["[Enum, NonExhaustive]", "[Error, NonExhaustive]"] {
+            let (_, node) = weedle::attribute::ExtendedAttributeList::parse(attrs).unwrap();
+            assert!(InterfaceAttributes::try_from(&node).is_ok(), "{attrs}");
+        }
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Trait, NonExhaustive]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "NonExhaustive is only supported for `[Enum]` and `[Error]` interfaces"
+        );
+    }
+
     #[test]
     fn test_typedef_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
@@ -8,9 +8,10 @@ use crate::attributes;
 use crate::converters::{convert_docstring, APIConverter};
 use crate::finder;
 use crate::resolver::TypeResolver;
-use anyhow::{bail, Result};
-use std::collections::{hash_map, BTreeSet, HashMap};
-use uniffi_meta::Type;
+use crate::{ParseError, ValidationError};
+use anyhow::{anyhow, bail, Result};
+use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use uniffi_meta::{ConstantMetadata, Type};
 
 /// The implementation of this crate - we collect weedle definitions from UDL and convert
 /// them into `uniffi_meta` metadata.
@@ -22,6 +23,9 @@ pub(crate) struct InterfaceCollector {
     pub types: TypeCollector,
     /// The output we collect and supply to our consumer.
     pub items: BTreeSet<uniffi_meta::Metadata>,
+    /// Was the namespace declared with `partial namespace`, meaning this UDL file extends a
+    /// namespace declared in another file?
+    pub partial_namespace: bool,
 }
 
 impl InterfaceCollector {
@@ -36,9 +40,7 @@ impl InterfaceCollector {
         use weedle::Parse; // this trait must be in scope for parse to work.
         let (remaining, defns) = weedle::Definitions::parse(idl.trim()).unwrap();
         if !remaining.is_empty() {
-            println!("Error parsing the IDL. Text remaining to be parsed is:");
-            println!("{remaining}");
-            bail!("parse error");
+            bail!(ParseError::new(idl, remaining));
         }
         // We process the WebIDL definitions in 3 passes.
         // First, find the namespace.
@@ -47,9 +49,11 @@ impl InterfaceCollector {
         ci.types.crate_name = crate_name.to_string();
         // Next, go through and look for all the named types.
         ci.types.add_type_definitions_from(defns.as_slice())?;
+        // Constants are checked up front, so problems can be reported with their position.
+        ci.check_constants(idl, &defns)?;
 
         // With those names resolved, we can build a complete representation of the API.
-        APIBuilder::process(&defns, &mut ci)?;
+        APIBuilder::process(&defns, &mut ci).map_err(|err| ValidationError::locate(idl, err))?;
         // Any misc items we need to add to the set.
         for t in ci.types.type_definitions.values() {
             if let Type::Custom {
@@ -73,13 +77,46 @@ impl InterfaceCollector {
 
     fn find_namespace(&mut self, defns: &Vec<weedle::Definition<'_>>) -> Result<String> {
         for defn in defns {
-            if let weedle::Definition::Namespace(n) = defn {
-                return Ok(n.identifier.0.to_string());
+            match defn {
+                weedle::Definition::Namespace(n) => return Ok(n.identifier.0.to_string()),
+                weedle::Definition::PartialNamespace(n) => {
+                    self.partial_namespace = true;
+                    return Ok(n.identifier.0.to_string());
+                }
+                _ => (),
             }
         }
         bail!("Failed to find the namespace");
     }
 
+    // Check the constants declared in the namespace are unique and that their values fit their
+    // types, reporting any problem at the constant's name.
+    fn check_constants(&mut self, idl: &str, defns: &[weedle::Definition<'_>]) -> Result<()> {
+        let mut names = HashSet::new();
+        for defn in defns {
+            let members = match defn {
+                weedle::Definition::Namespace(n) => &n.members.body,
+                weedle::Definition::PartialNamespace(n) => &n.members.body,
+                _ => continue,
+            };
+            for member in members {
+                let weedle::namespace::NamespaceMember::Const(c) = member else {
+                    continue;
+                };
+                let name = c.identifier.0;
+                let checked = if names.insert(name) {
+                    APIConverter::<ConstantMetadata>::convert(c, self).map(|_| ())
+                } else {
+                    Err(anyhow!("duplicate constant `{name}`"))
+                };
+                if let Err(e) = checked {
+                    bail!(ValidationError::new(idl, name, e.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// The module path which should be used by all items in this namespace.
     pub fn module_path(&self) -> String {
         self.types.module_path()
@@ -171,6 +208,7 @@ impl APIBuilder for weedle::Definition<'_> {
     fn process(&self, ci: &mut InterfaceCollector) -> Result<()> {
         match self {
             weedle::Definition::Namespace(d) => d.process(ci)?,
+            weedle::Definition::PartialNamespace(d) => d.process(ci)?,
             weedle::Definition::Enum(d) => {
                 let e: uniffi_meta::EnumMetadata = d.convert(ci)?;
                 ci.add_definition(e.into())?;
@@ -206,12 +244,29 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         if self.attributes.is_some() {
             bail!("namespace attributes are not supported yet");
         }
-        if self.identifier.0 != ci.types.namespace {
+        if self.identifier.0 != ci.types.namespace || ci.partial_namespace {
             bail!("duplicate namespace definition");
         }
         ci.types.namespace_docstring = self.docstring.as_ref().map(|v| convert_docstring(&v.0));
-        for func in self.members.body.convert(ci)? {
-            ci.add_definition(func.into())?;
+        let items: Vec<uniffi_meta::Metadata> = self.members.body.convert(ci)?;
+        for item in items {
+            ci.add_definition(item)?;
+        }
+        Ok(())
+    }
+}
+
+impl APIBuilder for weedle::PartialNamespaceDefinition<'_> {
+    fn process(&self, ci: &mut InterfaceCollector) -> Result<()> {
+        if self.attributes.is_some() {
+            bail!("namespace attributes are not supported yet");
+        }
+        if self.identifier.0 != ci.types.namespace || !ci.partial_namespace {
+            bail!("duplicate namespace definition");
+        }
+        let items: Vec<uniffi_meta::Metadata> = self.members.body.convert(ci)?;
+        for item in items {
+            ci.add_definition(item)?;
         }
         Ok(())
     }
@@ -227,6 +282,9 @@ pub(crate) struct TypeCollector {
 
     // Named type definitions (including aliases).
     pub type_definitions: HashMap<String, Type>,
+
+    // Enums which can't be used as map keys, since they have fields or are errors.
+    pub non_key_enums: HashSet<String>,
 }
 
 impl TypeCollector {
@@ -245,6 +303,28 @@ impl TypeCollector {
     /// Add the definition of a named [Type].
     ///
     /// This will fail if you try to add a name for which an existing type definition exists.
+    /// Can `key_type` be used as the key of a `record<K, V>`?
+    ///
+    /// Keys must be hashable in every language, and compared by value.  Enums from other crates
+    /// can't be checked here, so are assumed to be fine.
+    pub fn is_valid_map_key(&self, key_type: &Type) -> bool {
+        match key_type {
+            Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Boolean
+            | Type::String => true,
+            Type::Enum { name, .. } => !self.non_key_enums.contains(name),
+            Type::Custom { builtin, .. } => self.is_valid_map_key(builtin),
+            _ => false,
+        }
+    }
+
     pub fn add_type_definition(&mut self, name: &str, type_: Type) -> Result<()> {
         match self.type_definitions.entry(name.to_string()) {
             hash_map::Entry::Occupied(o) => {
@@ -3,16 +3,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use super::APIConverter;
-use crate::attributes::ArgumentAttributes;
+use crate::attributes::{ArgumentAttributes, DeprecatableAttributes};
 use crate::attributes::{ConstructorAttributes, FunctionAttributes, MethodAttributes};
 use crate::converters::convert_docstring;
 use crate::literal::convert_default_value;
-use crate::InterfaceCollector;
+use crate::{InterfaceCollector, UnlocatedError};
 use anyhow::{bail, Result};
 
 use uniffi_meta::{
-    ConstructorMetadata, FieldMetadata, FnMetadata, FnParamMetadata, MethodMetadata,
-    TraitMethodMetadata, Type,
+    ConstantMetadata, ConstructorMetadata, FieldMetadata, FnMetadata, FnParamMetadata, Metadata,
+    MethodMetadata, TraitMethodMetadata, Type,
 };
 
 impl APIConverter<FieldMetadata> for weedle::argument::Argument<'_> {
@@ -33,16 +33,16 @@ impl APIConverter<FieldMetadata> for weedle::argument::SingleArgument<'_> {
         if self.default.is_some() {
             bail!("enum interface variant fields must not have default values");
         }
-        if self.attributes.is_some() {
-            bail!("enum interface variant fields must not have attributes");
-        }
+        let attributes = DeprecatableAttributes::try_from(self.attributes.as_ref())?;
         // TODO: maybe we should use our own `Field` type here with just name and type,
         // rather than appropriating record::Field..?
         Ok(FieldMetadata {
             name: self.identifier.0.to_string(),
             ty: type_,
             default: None,
-            docstring: None,
+            is_source: false,
+            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
@@ -58,6 +58,16 @@ impl APIConverter<FnParamMetadata> for weedle::argument::Argument<'_> {
 
 impl APIConverter<FnParamMetadata> for weedle::argument::SingleArgument<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<FnParamMetadata> {
+        if self.docstring.is_some() {
+            bail!(UnlocatedError::new(
+                self.identifier.0,
+                format!(
+                    "argument `{}` has a docstring, but only the fields of enum variants can \
+                     have one",
+                    self.identifier.0
+                )
+            ));
+        }
         let type_ = ci.resolve_type_expression(&self.type_)?;
         let default = match self.default {
             None => None,
@@ -74,10 +84,15 @@ impl APIConverter<FnParamMetadata> for weedle::argument::SingleArgument<'_> {
     }
 }
 
-impl APIConverter<FnMetadata> for weedle::namespace::NamespaceMember<'_> {
-    fn convert(&self, ci: &mut InterfaceCollector) -> Result<FnMetadata> {
+impl APIConverter<Metadata> for weedle::namespace::NamespaceMember<'_> {
+    fn convert(&self, ci: &mut InterfaceCollector) -> Result<Metadata> {
         match self {
-            weedle::namespace::NamespaceMember::Operation(f) => f.convert(ci),
+            weedle::namespace::NamespaceMember::Operation(f) => {
+                APIConverter::<FnMetadata>::convert(f, ci).map(Into::into)
+            }
+            weedle::namespace::NamespaceMember::Const(c) => {
+                APIConverter::<ConstantMetadata>::convert(c, ci).map(Into::into)
+            }
             _ => bail!("no support for namespace member type {:?} yet", self),
         }
     }
@@ -108,6 +123,8 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
             throws,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
             checksum: None,
+            tag: None,
+            deprecated: attrs.get_deprecated(),
         })
     }
 }
@@ -132,6 +149,8 @@ impl APIConverter<ConstructorMetadata> for weedle::interface::ConstructorInterfa
             throws,
             checksum: None,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
@@ -172,12 +191,17 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
             // We don't know the name of the containing `Object` at this point, fill it in later.
             self_name: Default::default(),
             is_async,
+            consumes: false,
+            is_static: false,
+            is_getter: false,
             inputs: self.args.body.list.convert(ci)?,
             return_type,
             throws,
             takes_self_by_arc,
             checksum: None,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
@@ -218,12 +242,14 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
                 }
             },
             is_async,
+            has_default: false,
             inputs: self.args.body.list.convert(ci)?,
             return_type,
             throws,
             takes_self_by_arc,
             checksum: None,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
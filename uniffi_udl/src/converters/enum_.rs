@@ -3,10 +3,14 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use super::APIConverter;
-use crate::{attributes::EnumAttributes, converters::convert_docstring, InterfaceCollector};
+use crate::{
+    attributes::EnumAttributes, converters::convert_docstring, literal::convert_default_value,
+    InterfaceCollector, UnlocatedError,
+};
 use anyhow::{bail, Result};
+use std::collections::HashMap;
 
-use uniffi_meta::{EnumMetadata, EnumShape, VariantMetadata};
+use uniffi_meta::{EnumMetadata, EnumShape, LiteralMetadata, Type, VariantMetadata};
 
 // Note that we have 2 `APIConverter` impls here - one for the `enum` case
 // (including an enum with `[Error]`), and one for the `[Error] interface` cas
@@ -19,31 +23,99 @@ impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
         } else {
             EnumShape::Enum
         };
+        let discrs = convert_discriminants(self)?;
+        if attributes.contains_error_attr() && discrs.iter().any(Option::is_some) {
+            bail!(UnlocatedError::new(
+                self.identifier.0,
+                format!(
+                    "the variants of `{}` can't be given values because it's an error",
+                    self.identifier.0
+                ),
+            ));
+        }
         Ok(EnumMetadata {
             module_path: ci.module_path(),
             name: self.identifier.0.to_string(),
             shape,
-            discr_type: None,
+            // Explicit values are exposed to the foreign bindings as 64 bit integers.
+            discr_type: discrs.iter().any(Option::is_some).then_some(Type::Int64),
             variants: self
                 .values
                 .body
                 .list
                 .iter()
-                .map::<Result<_>, _>(|v| {
-                    Ok(VariantMetadata {
-                        name: v.value.0.to_string(),
-                        discr: None,
-                        fields: vec![],
-                        docstring: v.docstring.as_ref().map(|v| convert_docstring(&v.0)),
-                    })
+                .zip(discrs)
+                .map(|(v, discr)| VariantMetadata {
+                    name: v.value.0.to_string(),
+                    discr,
+                    fields: vec![],
+                    docstring: v.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+                    deprecated: None,
                 })
-                .collect::<Result<Vec<_>>>()?,
+                .collect(),
             non_exhaustive: attributes.contains_non_exhaustive_attr(),
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
         })
     }
 }
 
+// Convert the values given to the variants, eg `"Gone" = 4`.  Like Rust, a variant without a value
+// is one more than the variant before it, and no two variants can have the same value.
+fn convert_discriminants(
+    enum_: &weedle::EnumDefinition<'_>,
+) -> Result<Vec<Option<LiteralMetadata>>> {
+    let mut discrs = vec![];
+    let mut seen = HashMap::new();
+    let mut next = Some(0_i64);
+    for variant in enum_.values.body.list.iter() {
+        let name = variant.value.0;
+        let (value, position) = match &variant.discriminant {
+            Some(discriminant) => {
+                let position = match discriminant.value {
+                    weedle::literal::IntegerLit::Dec(v) => v.0,
+                    weedle::literal::IntegerLit::Hex(v) => v.0,
+                    weedle::literal::IntegerLit::Oct(v) => v.0,
+                };
+                let literal = convert_default_value(
+                    &weedle::literal::DefaultValue::Integer(discriminant.value),
+                    &Type::Int64,
+                )
+                .map_err(|err| {
+                    UnlocatedError::new(position, format!("invalid value for `{name}`: {err}"))
+                })?;
+                let LiteralMetadata::Int(value, ..) = literal else {
+                    unreachable!("integers converted as i64 are always `Int` literals");
+                };
+                // Store them the way the proc-macros do, so bindings don't see a difference.
+                discrs.push(Some(match u64::try_from(value) {
+                    Ok(value) => LiteralMetadata::new_uint(value),
+                    Err(_) => LiteralMetadata::new_int(value),
+                }));
+                (value, position)
+            }
+            None => {
+                let Some(value) = next else {
+                    bail!(UnlocatedError::new(
+                        name,
+                        format!("the value of `{name}` doesn't fit in i64")
+                    ));
+                };
+                discrs.push(None);
+                (value, name)
+            }
+        };
+        if let Some(other) = seen.insert(value, name) {
+            bail!(UnlocatedError::new(
+                position,
+                format!("`{name}` has the value {value}, which is already used by `{other}`")
+            ));
+        }
+        next = value.checked_add(1);
+    }
+    Ok(discrs)
+}
+
 impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<EnumMetadata> {
         if self.inheritance.is_some() {
@@ -76,6 +148,7 @@ impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
             // Enums declared using the `[Enum] interface` syntax might have variants with fields.
             //flat: false,
+            tag: None,
         })
     }
 }
@@ -103,4 +176,112 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_discriminants() {
+        const UDL: &str = r#"
+            namespace test{};
+            enum Status { "Ok" = 0, "Gone" = 4, "Moved", "Invalid" = -0x2 };
+            enum Plain { "A", "B" };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap();
+        let enums: Vec<_> = ci
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                Metadata::Enum(e) => Some(e),
+                _ => None,
+            })
+            .collect();
+        let status = enums.iter().find(|e| e.name == "Status").unwrap();
+        assert_eq!(status.discr_type, Some(Type::Int64));
+        let discrs: Vec<_> = status.variants.iter().map(|v| v.discr.clone()).collect();
+        assert_eq!(
+            discrs,
+            vec![
+                Some(LiteralMetadata::new_uint(0)),
+                Some(LiteralMetadata::new_uint(4)),
+                None,
+                Some(LiteralMetadata::new_int(-2)),
+            ]
+        );
+        let plain = enums.iter().find(|e| e.name == "Plain").unwrap();
+        assert_eq!(plain.discr_type, None);
+        assert!(plain.variants.iter().all(|v| v.discr.is_none()));
+    }
+
+    #[test]
+    fn test_invalid_discriminants() {
+        let error = |enum_: &str| {
+            InterfaceCollector::from_webidl(&format!("namespace test{{}};\n{enum_}"), "crate_name")
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            error(r#"enum E { "A" = 1, "B" = 0, "C" };"#),
+            "error at line 2, column 29: `C` has the value 1, which is already used by `A`"
+        );
+        assert_eq!(
+            error(r#"enum E { "A" = 3, "B" = 0x3 };"#),
+            "error at line 2, column 25: `B` has the value 3, which is already used by `A`"
+        );
+        assert_eq!(
+            error(r#"enum E { "A" = 0x8000000000000000 };"#),
+            "error at line 2, column 16: invalid value for `A`: literal 0x8000000000000000 \
+             doesn't fit in i64"
+        );
+        assert_eq!(
+            error(r#"enum E { "A" = 0x7fffffffffffffff, "B" };"#),
+            "error at line 2, column 37: the value of `B` doesn't fit in i64"
+        );
+        assert_eq!(
+            error(r#"[Error] enum E { "A" = 1 };"#),
+            "error at line 2, column 14: the variants of `E` can't be given values because it's \
+             an error"
+        );
+    }
+
+    #[test]
+    fn test_variant_field_docstrings() {
+        const UDL: &str = r#"
+            namespace test {
+                void send(
+                    /// The message
+                    string message
+                );
+            };
+            [Enum]
+            interface Shape {
+                Circle(
+                    /// The radius
+                    f64 radius,
+                    f64 scale
+                );
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 5, column 28: argument `message` has a docstring, but only the fields \
+             of enum variants can have one"
+        );
+
+        let udl = UDL.replace("/// The message", "");
+        let ci = InterfaceCollector::from_webidl(&udl, "crate_name").unwrap();
+        let shape = ci
+            .items
+            .into_iter()
+            .find_map(|item| match item {
+                Metadata::Enum(e) => Some(e),
+                _ => None,
+            })
+            .unwrap();
+        let docstrings: Vec<_> = shape.variants[0]
+            .fields
+            .iter()
+            .map(|f| f.docstring.as_deref())
+            .collect();
+        assert_eq!(docstrings, vec![Some("The radius"), None]);
+    }
 }
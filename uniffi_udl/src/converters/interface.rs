@@ -8,8 +8,8 @@ use crate::{converters::convert_docstring, InterfaceCollector};
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 use uniffi_meta::{
-    ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectImpl, ObjectMetadata, Type,
-    UniffiTraitMetadata,
+    ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectMetadata, TraitMethodMetadata,
+    Type, UniffiTraitMetadata,
 };
 
 impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
@@ -27,11 +27,12 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
         // Convert each member into a constructor or method, guarding against duplicate names.
         // They get added to the ci and aren't carried in ObjectMetadata.
         let mut member_names = HashSet::new();
+        let mut trait_method_count = 0;
         for member in &self.members.body {
             match member {
                 weedle::interface::InterfaceMember::Constructor(t) => {
                     let mut cons: ConstructorMetadata = t.convert(ci)?;
-                    if object_impl == ObjectImpl::Trait {
+                    if object_impl.is_trait_interface() {
                         bail!(
                             "Trait interfaces can not have constructors: \"{}\"",
                             cons.name
@@ -43,6 +44,20 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                     cons.self_name = object_name.to_string();
                     ci.items.insert(cons.into());
                 }
+                // Methods of trait interfaces are trait methods, like the ones of traits exported
+                // with proc-macros, which keep the order they're declared in.
+                weedle::interface::InterfaceMember::Operation(t)
+                    if object_impl.is_trait_interface() =>
+                {
+                    let mut method: TraitMethodMetadata = t.convert(ci)?;
+                    if !member_names.insert(method.name.clone()) {
+                        bail!("Duplicate interface member name: \"{}\"", method.name)
+                    }
+                    method.trait_name = object_name.to_string();
+                    method.index = trait_method_count;
+                    trait_method_count += 1;
+                    ci.items.insert(method.into());
+                }
                 weedle::interface::InterfaceMember::Operation(t) => {
                     let mut method: MethodMetadata = t.convert(ci)?;
                     if !member_names.insert(method.name.clone()) {
@@ -65,12 +80,17 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 name: name.to_string(),
                 self_name: object_name.to_string(),
                 is_async: false,
+                consumes: false,
+                is_static: false,
+                is_getter: false,
                 inputs,
                 return_type,
                 throws: None,
                 takes_self_by_arc: false,
                 checksum: None,
                 docstring: None,
+                tag: None,
+                deprecated: None,
             })
         };
         // Trait methods are in the Metadata.
@@ -120,6 +140,23 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                     "Hash" => UniffiTraitMetadata::Hash {
                         hash: make_trait_method("uniffi_trait_hash", vec![], Some(Type::UInt64))?,
                     },
+                    "Ord" => UniffiTraitMetadata::Ord {
+                        cmp: make_trait_method(
+                            "uniffi_trait_ord_cmp",
+                            vec![FnParamMetadata {
+                                name: "other".to_string(),
+                                ty: Type::Object {
+                                    module_path: ci.module_path(),
+                                    name: object_name.to_string(),
+                                    imp: object_impl,
+                                },
+                                by_ref: true,
+                                default: None,
+                                optional: false,
+                            }],
+                            Some(Type::Int8),
+                        )?,
+                    },
                     _ => bail!("Invalid trait name: {}", trait_name),
                 })
             })
@@ -132,6 +169,7 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
             name: object_name.to_string(),
             imp: object_impl,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
         })
     }
 }
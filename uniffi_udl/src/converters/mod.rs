@@ -2,12 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use crate::attributes::DeprecatableAttributes;
 use crate::literal::convert_default_value;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 
 use uniffi_meta::{
-    CallbackInterfaceMetadata, FieldMetadata, RecordMetadata, TraitMethodMetadata, VariantMetadata,
+    CallbackInterfaceMetadata, ConstantMetadata, FieldMetadata, RecordMetadata,
+    TraitMethodMetadata, VariantMetadata,
 };
 
 mod callables;
@@ -34,6 +36,28 @@ pub(crate) fn convert_docstring(docstring: &str) -> String {
     textwrap::dedent(docstring)
 }
 
+impl APIConverter<ConstantMetadata> for weedle::namespace::ConstNamespaceMember<'_> {
+    fn convert(&self, ci: &mut InterfaceCollector) -> Result<ConstantMetadata> {
+        if self.attributes.is_some() {
+            bail!("constant attributes are not supported yet");
+        }
+        let name = self.identifier.0.to_string();
+        let ty = ci.resolve_type_expression(&self.type_)?;
+        if !ConstantMetadata::is_supported_type(&ty) {
+            bail!("constant `{name}` must be a number, boolean or string");
+        }
+        let value = convert_default_value(&self.value, &ty)?;
+        Ok(ConstantMetadata {
+            module_path: ci.module_path(),
+            name,
+            ty,
+            value,
+            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
+        })
+    }
+}
+
 /// Convert a list of weedle items into a list of `InterfaceCollector` items,
 /// by doing a direct item-by-item mapping.
 impl<U, T: APIConverter<U>> APIConverter<Vec<U>> for Vec<T> {
@@ -75,6 +99,7 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
                 _ => bail!("enum interface members must have plain identifiers as names"),
             }
         };
+        let attributes = DeprecatableAttributes::try_from(self.attributes.as_ref())?;
         Ok(VariantMetadata {
             name,
             discr: None,
@@ -86,15 +111,14 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
                 .map(|arg| arg.convert(ci))
                 .collect::<Result<Vec<_>>>()?,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
 
 impl APIConverter<RecordMetadata> for weedle::DictionaryDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<RecordMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary attributes are not supported yet");
-        }
+        let attributes = DeprecatableAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("dictionary inheritance is not supported");
         }
@@ -103,15 +127,15 @@ impl APIConverter<RecordMetadata> for weedle::DictionaryDefinition<'_> {
             name: self.identifier.0.to_string(),
             fields: self.members.body.convert(ci)?,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
 
 impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<FieldMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary member attributes are not supported yet");
-        }
+        let attributes = DeprecatableAttributes::try_from(self.attributes.as_ref())?;
         let type_ = ci.resolve_type_expression(&self.type_)?;
         let default = match self.default {
             None => None,
@@ -121,7 +145,9 @@ impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
             name: self.identifier.0.to_string(),
             ty: type_,
             default,
+            is_source: false,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            deprecated: attributes.get_deprecated(),
         })
     }
 }
@@ -160,6 +186,7 @@ impl APIConverter<CallbackInterfaceMetadata> for weedle::CallbackInterfaceDefini
             module_path: ci.module_path(),
             name: object_name.to_string(),
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            tag: None,
         })
     }
 }
@@ -229,4 +256,39 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_trait_interface_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Greeter {
+                string name();
+                string greet(string greeting);
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap();
+        // Like the methods of traits exported with proc-macros, these are trait methods which keep
+        // the order they're declared in.
+        let methods: Vec<_> = ci
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Metadata::TraitMethod(method) => Some(method),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(methods.len(), 2);
+        assert_eq!(
+            (methods[0].trait_name.as_str(), methods[0].index),
+            ("Greeter", 0)
+        );
+        assert_eq!(methods[0].name, "name");
+        assert_eq!(methods[1].index, 1);
+        assert_eq!(methods[1].name, "greet");
+        assert!(!ci
+            .items
+            .iter()
+            .any(|item| matches!(item, Metadata::Method(_))));
+    }
 }
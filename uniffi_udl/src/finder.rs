@@ -22,7 +22,7 @@ use std::convert::TryFrom;
 use anyhow::{bail, Result};
 
 use super::TypeCollector;
-use crate::attributes::{InterfaceAttributes, RustKind, TypedefAttributes};
+use crate::attributes::{EnumAttributes, InterfaceAttributes, RustKind, TypedefAttributes};
 use uniffi_meta::{ObjectImpl, Type};
 
 /// Trait to help with an early "type discovery" phase when processing the UDL.
@@ -62,6 +62,13 @@ impl TypeFinder for weedle::InterfaceDefinition<'_> {
         let attrs = InterfaceAttributes::try_from(self.attributes.as_ref())?;
         // Some enum types are defined using an `interface` with a special attribute.
         if attrs.contains_enum_attr() || attrs.contains_error_attr() {
+            let has_fields = self.members.body.iter().any(|member| {
+                matches!(member, weedle::interface::InterfaceMember::Operation(op)
+                    if !op.args.body.list.is_empty())
+            });
+            if has_fields || attrs.contains_error_attr() {
+                types.non_key_enums.insert(name.clone());
+            }
             types.add_type_definition(
                 self.identifier.0,
                 Type::Enum {
@@ -99,6 +106,9 @@ impl TypeFinder for weedle::EnumDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeCollector) -> Result<()> {
         let name = self.identifier.0.to_string();
         // Our error types are defined using an `enum` with a special attribute.
+        if EnumAttributes::try_from(self.attributes.as_ref())?.contains_error_attr() {
+            types.non_key_enums.insert(name.clone());
+        }
         types.add_type_definition(
             self.identifier.0,
             Type::Enum {
@@ -0,0 +1,297 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Expands `// #include "<path>"` directives in UDL.
+//!
+//! The directive is a comment, so it's invisible to weedle.  Each one is replaced by the lines of
+//! the included file, and we remember where every line of the expanded UDL came from so errors
+//! can be reported against the file they're in.
+
+use crate::{ParseError, ValidationError};
+use anyhow::{bail, Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+const DIRECTIVE: &str = "// #include";
+
+/// UDL with all of its includes expanded
+pub(crate) struct ExpandedUdl {
+    pub text: String,
+    /// The paths of the included files, in the order they were first included
+    pub included_files: Vec<PathBuf>,
+    /// For each line of `text`, the index into `included_files` of the file it came from, or
+    /// `None` for the UDL being parsed, and the line number in that file
+    lines: Vec<(Option<usize>, usize)>,
+}
+
+impl ExpandedUdl {
+    /// Expand the includes of `udl`, which was read from `udl_path`
+    ///
+    /// Included files are read with `read_include`.  Without a `udl_path`, includes are an error,
+    /// since there's nothing to resolve them relative to.
+    pub fn new(
+        udl: &str,
+        udl_path: Option<&Path>,
+        read_include: &mut dyn FnMut(&Path) -> Result<String>,
+    ) -> Result<Self> {
+        let mut expanded = Self {
+            text: String::with_capacity(udl.len()),
+            included_files: vec![],
+            lines: vec![],
+        };
+        let mut chain = udl_path.map(normalize).into_iter().collect();
+        expanded.expand(udl, udl_path, None, &mut chain, read_include)?;
+        Ok(expanded)
+    }
+
+    fn expand(
+        &mut self,
+        udl: &str,
+        udl_path: Option<&Path>,
+        file_index: Option<usize>,
+        chain: &mut Vec<PathBuf>,
+        read_include: &mut dyn FnMut(&Path) -> Result<String>,
+    ) -> Result<()> {
+        for (line_index, line) in udl.lines().enumerate() {
+            let Some(include) = line.trim().strip_prefix(DIRECTIVE) else {
+                self.text.push_str(line);
+                self.text.push('\n');
+                self.lines.push((file_index, line_index + 1));
+                continue;
+            };
+            let describe_position = || match udl_path {
+                Some(path) => format!("{}, line {}", path.display(), line_index + 1),
+                None => format!("line {}", line_index + 1),
+            };
+            let Some(relative_path) = include
+                .trim()
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+            else {
+                bail!(
+                    "invalid include at {}, expected `{DIRECTIVE} \"<path>\"`",
+                    describe_position()
+                );
+            };
+            let Some(udl_path) = udl_path else {
+                bail!(
+                    "UDL includes \"{relative_path}\", but the path of the UDL file isn't known so \
+                     it can't be found"
+                );
+            };
+            let path = normalize(
+                &udl_path
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(relative_path),
+            );
+            if chain.contains(&path) {
+                let chain: Vec<_> = chain
+                    .iter()
+                    .chain([&path])
+                    .map(|p| p.display().to_string())
+                    .collect();
+                bail!("circular UDL include: {}", chain.join(" -> "));
+            }
+            let included = read_include(&path).with_context(|| {
+                format!(
+                    "failed to read {}, included at {}",
+                    path.display(),
+                    describe_position()
+                )
+            })?;
+            check_no_namespace(&included, &path)?;
+            let index = match self.included_files.iter().position(|p| *p == path) {
+                Some(index) => index,
+                None => {
+                    self.included_files.push(path.clone());
+                    self.included_files.len() - 1
+                }
+            };
+            chain.push(path.clone());
+            self.expand(&included, Some(&path), Some(index), chain, read_include)?;
+            chain.pop();
+        }
+        Ok(())
+    }
+
+    /// Update the position of a parse or validation error to be in the file it came from
+    pub fn locate_error(&self, mut err: anyhow::Error) -> anyhow::Error {
+        if let Some(parse_error) = err.downcast_mut::<ParseError>() {
+            (parse_error.file, parse_error.line) = self.locate_line(parse_error.line);
+        } else if let Some(validation_error) = err.downcast_mut::<ValidationError>() {
+            (validation_error.file, validation_error.line) =
+                self.locate_line(validation_error.line);
+        }
+        err
+    }
+
+    fn locate_line(&self, line: usize) -> (Option<PathBuf>, usize) {
+        match self.lines.get(line.wrapping_sub(1)) {
+            Some((file_index, file_line)) => (
+                file_index.map(|i| self.included_files[i].clone()),
+                *file_line,
+            ),
+            // The position is past the end of the UDL, so it's in the UDL being parsed.
+            None => (None, line),
+        }
+    }
+}
+
+/// `udl` with its include directives blanked out, for when only the UDL itself is needed
+///
+/// weedle can't parse a comment after the last definition, so the directives can't be left in.
+pub(crate) fn without_includes(udl: &str) -> String {
+    udl.lines()
+        .map(|line| {
+            if line.trim().starts_with(DIRECTIVE) {
+                ""
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Included files extend the namespace of the file which includes them, so they can't declare one.
+fn check_no_namespace(udl: &str, path: &Path) -> Result<()> {
+    use weedle::Parse;
+    // Syntax errors are reported when the expanded UDL is parsed, with their position.
+    let Ok((_, definitions)) = weedle::Definitions::parse(udl.trim()) else {
+        return Ok(());
+    };
+    if definitions.iter().any(|d| {
+        matches!(
+            d,
+            weedle::Definition::Namespace(_) | weedle::Definition::PartialNamespace(_)
+        )
+    }) {
+        bail!(
+            "{} declares a namespace, but included UDL files use the namespace of the file \
+             which includes them",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+// Remove `.` and `..` components, so the same file is always given the same path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn expand(udl: &str, files: &[(&str, &str)]) -> Result<ExpandedUdl> {
+        let files: HashMap<PathBuf, String> = files
+            .iter()
+            .map(|(path, udl)| (PathBuf::from(path), udl.to_string()))
+            .collect();
+        ExpandedUdl::new(udl, Some(Path::new("src/main.udl")), &mut |path| {
+            files
+                .get(path)
+                .cloned()
+                .with_context(|| format!("{} doesn't exist", path.display()))
+        })
+    }
+
+    #[test]
+    fn test_expand() {
+        let expanded = expand(
+            "namespace test {};\n// #include \"errors.udl\"\n  // #include \"../shared/types.udl\"\n",
+            &[
+                ("src/errors.udl", "// #include \"nested/more.udl\"\nenum E {\"A\"};"),
+                ("src/nested/more.udl", "dictionary D {};"),
+                ("shared/types.udl", "dictionary T {};"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.text,
+            "namespace test {};\ndictionary D {};\nenum E {\"A\"};\ndictionary T {};\n"
+        );
+        assert_eq!(
+            expanded.included_files,
+            vec![
+                PathBuf::from("src/errors.udl"),
+                PathBuf::from("src/nested/more.udl"),
+                PathBuf::from("shared/types.udl"),
+            ]
+        );
+        assert_eq!(expanded.locate_line(1), (None, 1));
+        assert_eq!(
+            expanded.locate_line(3),
+            (Some(PathBuf::from("src/errors.udl")), 2)
+        );
+        assert_eq!(
+            expanded.locate_line(4),
+            (Some(PathBuf::from("shared/types.udl")), 1)
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_expand_errors() {
+        let err = expand(
+            "namespace test {};\n// #include \"a.udl\"",
+            &[
+                ("src/a.udl", "// #include \"b.udl\""),
+                ("src/b.udl", "// #include \"./a.udl\""),
+            ],
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "circular UDL include: src/main.udl -> src/a.udl -> src/b.udl -> src/a.udl"
+        );
+
+        let err = expand(
+            "namespace test {};\n// #include \"a.udl\"",
+            &[("src/a.udl", "namespace other {};")],
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "src/a.udl declares a namespace, but included UDL files use the namespace of the \
+             file which includes them"
+        );
+
+        let err = expand("namespace test {};\n// #include \"missing.udl\"", &[])
+            .err()
+            .unwrap();
+        assert_eq!(
+            format!("{err:#}"),
+            "failed to read src/missing.udl, included at src/main.udl, line 2: src/missing.udl \
+             doesn't exist"
+        );
+
+        let err = expand("namespace test {};\n// #include errors.udl", &[])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "invalid include at src/main.udl, line 2, expected `// #include \"<path>\"`"
+        );
+    }
+}
@@ -14,16 +14,210 @@ mod attributes;
 mod collectors;
 mod converters;
 mod finder;
+mod include;
 mod literal;
 mod resolver;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use collectors::{InterfaceCollector, TypeCollector};
+use include::ExpandedUdl;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 use uniffi_meta::Type;
 
 /// The single entry-point to this module.
+///
+/// The UDL can't include other files, since there's no path to find them relative to, use
+/// [parse_udl_file] for that.
 pub fn parse_udl(udl: &str, crate_name: &str) -> Result<uniffi_meta::MetadataGroup> {
-    Ok(InterfaceCollector::from_webidl(udl, crate_name)?.into())
+    let expanded = ExpandedUdl::new(udl, None, &mut |path| {
+        bail!(
+            "can't include {} without the path of the UDL",
+            path.display()
+        )
+    })?;
+    Ok(InterfaceCollector::from_webidl(&expanded.text, crate_name)?.into())
+}
+
+/// Parse UDL which was read from `udl_path`, along with the files it includes.
+///
+/// A line like `// #include "errors.udl"` is replaced with the contents of that file, found
+/// relative to the file which includes it.  Included files are read with `read_include`, and
+/// can't declare a namespace.  Errors in an included file have its path.
+///
+/// Returns the paths of all the included files along with the metadata, since anything tracking
+/// the files the metadata came from needs them.
+pub fn parse_udl_file(
+    udl: &str,
+    udl_path: &Path,
+    crate_name: &str,
+    mut read_include: impl FnMut(&Path) -> Result<String>,
+) -> Result<(uniffi_meta::MetadataGroup, Vec<PathBuf>)> {
+    let expanded = ExpandedUdl::new(udl, Some(udl_path), &mut read_include)?;
+    let collector = InterfaceCollector::from_webidl(&expanded.text, crate_name)
+        .map_err(|err| expanded.locate_error(err))?;
+    Ok((collector.into(), expanded.included_files))
+}
+
+/// Does the UDL extend a namespace declared in another UDL file?
+///
+/// A crate can split its interface across several UDL files.  One of them declares the namespace
+/// with `namespace`, and the others extend it with `partial namespace`.
+pub fn is_partial_namespace(udl: &str) -> Result<bool> {
+    use weedle::Parse;
+    // Only the namespace is looked at, since the UDL may use types from files it includes.
+    let udl = include::without_includes(udl);
+    let (remaining, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+    if !remaining.is_empty() {
+        bail!(ParseError::new(&udl, remaining));
+    }
+    Ok(defns
+        .iter()
+        .any(|d| matches!(d, weedle::Definition::PartialNamespace(_))))
+}
+
+/// The error for UDL which couldn't be parsed, with the position where parsing stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The file included by the UDL which parsing stopped in, or `None` if it stopped in the UDL
+    /// itself
+    pub file: Option<PathBuf>,
+    /// The line parsing stopped on, starting from 1
+    pub line: usize,
+    /// The column parsing stopped at, in characters and starting from 1
+    pub column: usize,
+    /// The rest of the line, from where parsing stopped
+    pub text: String,
+}
+
+impl ParseError {
+    // `remaining` is the unparsed end of the trimmed `udl`
+    fn new(udl: &str, remaining: &str) -> Self {
+        let (line, column) = line_and_column(udl, udl.trim_end().len() - remaining.len());
+        Self {
+            file: None,
+            line,
+            column,
+            text: remaining.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error ")?;
+        if let Some(file) = &self.file {
+            write!(f, "in {} ", file.display())?;
+        }
+        write!(
+            f,
+            "at line {}, column {}: `{}`",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The error for UDL which parsed but declares something invalid, with the position of the
+/// offending item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The file included by the UDL which the item is in, or `None` if it's in the UDL itself
+    pub file: Option<PathBuf>,
+    /// The line of the item, starting from 1
+    pub line: usize,
+    /// The column of the item, in characters and starting from 1
+    pub column: usize,
+    /// What's wrong with the item
+    pub message: String,
+}
+
+impl ValidationError {
+    // `item` must be a slice of `udl`, as weedle hands out for identifiers
+    fn new(udl: &str, item: &str, message: String) -> Self {
+        let (line, column) = line_and_column(udl, item.as_ptr() as usize - udl.as_ptr() as usize);
+        Self {
+            file: None,
+            line,
+            column,
+            message,
+        }
+    }
+
+    // Give an [UnlocatedError] from processing `udl` its position.  Other errors are unchanged.
+    fn locate(udl: &str, err: anyhow::Error) -> anyhow::Error {
+        let Some(unlocated) = err.downcast_ref::<UnlocatedError>() else {
+            return err;
+        };
+        match unlocated.address.checked_sub(udl.as_ptr() as usize) {
+            Some(offset) if offset <= udl.len() => {
+                let (line, column) = line_and_column(udl, offset);
+                anyhow::Error::new(Self {
+                    file: None,
+                    line,
+                    column,
+                    message: unlocated.message.clone(),
+                })
+            }
+            _ => anyhow::Error::msg(unlocated.message.clone()),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error ")?;
+        if let Some(file) = &self.file {
+            write!(f, "in {} ", file.display())?;
+        }
+        write!(
+            f,
+            "at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// An error about an item which is found deep inside the UDL, where the UDL itself isn't at hand
+///
+/// It keeps the address of the item, which must be a slice of the UDL like weedle's identifiers,
+/// and becomes a [ValidationError] with the item's position when it reaches
+/// [ValidationError::locate].
+#[derive(Debug)]
+pub(crate) struct UnlocatedError {
+    address: usize,
+    message: String,
+}
+
+impl UnlocatedError {
+    pub fn new(item: &str, message: String) -> Self {
+        Self {
+            address: item.as_ptr() as usize,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for UnlocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for UnlocatedError {}
+
+// Find the 1-based line and column of a byte offset into `udl`
+fn line_and_column(udl: &str, offset: usize) -> (usize, usize) {
+    let line_start = udl[..offset].rfind('\n').map_or(0, |i| i + 1);
+    (
+        udl[..offset].matches('\n').count() + 1,
+        udl[line_start..offset].chars().count() + 1,
+    )
 }
 
 #[cfg(test)]
@@ -44,4 +238,352 @@ mod test {
             uniffi_meta::Metadata::Record(r) if r.module_path == "crate_name" && r.name == "Empty" && r.fields.is_empty()
         ));
     }
+
+    #[test]
+    fn test_partial_namespace() {
+        const UDL: &str = r#"
+            partial namespace test {
+                u32 get_value();
+            };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        assert_eq!(group.namespace.name, "test");
+        assert!(matches!(
+            group.items.into_iter().next().unwrap(),
+            uniffi_meta::Metadata::Func(f) if f.name == "get_value"
+        ));
+        assert!(is_partial_namespace(UDL).unwrap());
+        assert!(!is_partial_namespace("namespace test {};").unwrap());
+        assert!(is_partial_namespace(
+            "partial namespace test {};\n// #include \"types.udl\"\ninterface Uses { Type get(); };"
+        )
+        .unwrap());
+        assert!(parse_udl(
+            "namespace test {}; partial namespace test {};",
+            "crate_name"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_constants() {
+        const UDL: &str = r#"
+            namespace test {
+                /// The largest payload we accept
+                const u32 MAX_PAYLOAD_SIZE = 65536;
+                const string GREETING = "hello";
+            };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        let constants: Vec<_> = group
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                uniffi_meta::Metadata::Constant(c) => Some(c),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(constants.len(), 2);
+        assert_eq!(constants[0].name, "GREETING");
+        assert_eq!(
+            constants[0].value,
+            uniffi_meta::LiteralMetadata::String("hello".into())
+        );
+        assert_eq!(constants[1].name, "MAX_PAYLOAD_SIZE");
+        assert_eq!(constants[1].ty, uniffi_meta::Type::UInt32);
+        assert_eq!(
+            constants[1].value,
+            uniffi_meta::LiteralMetadata::UInt(
+                65536,
+                uniffi_meta::Radix::Decimal,
+                uniffi_meta::Type::UInt32
+            )
+        );
+        assert_eq!(
+            constants[1].docstring.as_deref(),
+            Some("The largest payload we accept")
+        );
+
+        let err = parse_udl(
+            "namespace test { const sequence<u32> VALUES = []; };",
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 1, column 38: constant `VALUES` must be a number, boolean or string"
+        );
+    }
+
+    #[test]
+    fn test_invalid_constants() {
+        const UDL: &str = r#"
+            namespace test {
+                const u8 SMALL = 300;
+            };
+        "#;
+        let err = parse_udl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ValidationError>().unwrap(),
+            &ValidationError {
+                file: None,
+                line: 3,
+                column: 26,
+                message: "literal 300 doesn't fit in u8".into(),
+            }
+        );
+
+        const DUPLICATE_UDL: &str = r#"
+            namespace test {
+                const u32 LIMIT = 1;
+                const string LIMIT = "one";
+            };
+        "#;
+        let err = parse_udl(DUPLICATE_UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 4, column 30: duplicate constant `LIMIT`"
+        );
+
+        const PARTIAL_UDL: &str = r#"
+            partial namespace test {
+                const i8 OFFSET = -129;
+            };
+        "#;
+        let err = parse_udl(PARTIAL_UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 3, column 26: literal -129 doesn't fit in i8"
+        );
+    }
+
+    #[test]
+    fn test_includes() {
+        let read_include = |path: &Path| {
+            assert_eq!(path.parent(), Some(Path::new("src")));
+            Ok(match path.file_name().unwrap().to_str().unwrap() {
+                "errors.udl" => "[Error]\nenum ApiError { \"Failed\" };",
+                "broken.udl" => "dictionary Empty {};\n  u32 oops()",
+                _ => bail!("unexpected include"),
+            }
+            .to_string())
+        };
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=ApiError]
+                u32 get_value();
+            };
+            // #include "errors.udl"
+        "#;
+        let (group, included) =
+            parse_udl_file(UDL, Path::new("src/test.udl"), "crate_name", read_include).unwrap();
+        assert_eq!(included, vec![Path::new("src").join("errors.udl")]);
+        assert_eq!(group.items.len(), 2);
+
+        // Errors in an included file are reported in that file.
+        let err = parse_udl_file(
+            "namespace test {};\n// #include \"broken.udl\"",
+            Path::new("src/test.udl"),
+            "crate_name",
+            read_include,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>().unwrap(),
+            &ParseError {
+                file: Some(Path::new("src").join("broken.udl")),
+                line: 2,
+                column: 3,
+                text: "u32 oops()".into(),
+            }
+        );
+
+        // There's nothing to resolve includes relative to without a path.
+        let err = parse_udl(
+            "namespace test {};\n// #include \"errors.udl\"",
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "UDL includes \"errors.udl\", but the path of the UDL file isn't known so it can't be \
+             found"
+        );
+    }
+
+    #[test]
+    fn test_map_keys() {
+        const UDL: &str = r#"
+            namespace test {
+                record<KEY, string> get_map();
+            };
+            enum Level { "Debug", "Info" };
+            [Enum]
+            interface Direction { North(); South(); };
+            [Enum]
+            interface Shape { Circle(f64 radius); Point(); };
+            [Error]
+            enum ApiError { "Failed" };
+            dictionary Point { i32 x; i32 y; };
+            interface Counter { constructor(); };
+            [Custom]
+            typedef string Url;
+        "#;
+        let key_type = |key| {
+            let group = parse_udl(&UDL.replace("KEY", key), "crate_name")?;
+            Ok::<_, anyhow::Error>(group.items.into_iter().find_map(|item| match item {
+                uniffi_meta::Metadata::Func(f) => match f.return_type {
+                    Some(Type::Map { key_type, .. }) => Some(*key_type),
+                    _ => None,
+                },
+                _ => None,
+            }))
+        };
+        for (key, expected) in [
+            ("string", Type::String),
+            ("DOMString", Type::String),
+            ("u8", Type::UInt8),
+            ("i16", Type::Int16),
+            ("u32", Type::UInt32),
+            ("i64", Type::Int64),
+            ("boolean", Type::Boolean),
+        ] {
+            assert_eq!(key_type(key).unwrap(), Some(expected), "{key}");
+        }
+        for key in ["Level", "Direction"] {
+            assert!(
+                matches!(key_type(key).unwrap(), Some(Type::Enum { name, .. }) if name == key),
+                "{key}"
+            );
+        }
+        assert!(matches!(
+            key_type("Url").unwrap(),
+            Some(Type::Custom { name, .. }) if name == "Url"
+        ));
+
+        for (key, name) in [
+            ("Point", "Point"),
+            ("Counter", "Counter"),
+            ("Shape", "Shape"),
+            ("ApiError", "ApiError"),
+            ("f64", "f64"),
+            ("bytes", "bytes"),
+        ] {
+            let err = key_type(key).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<ValidationError>(),
+                Some(&ValidationError {
+                    file: None,
+                    line: 3,
+                    column: 24,
+                    message: format!(
+                        "`{name}` can't be used as a map key: keys must be integers, booleans, \
+                         strings or enums without fields"
+                    ),
+                }),
+                "{key}"
+            );
+        }
+        // Keys which aren't named types don't have a position.
+        let err = key_type("sequence<u32>").unwrap_err();
+        assert!(err.downcast_ref::<ValidationError>().is_none());
+        assert!(
+            err.to_string().contains("can't be used as a map key"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 get_value()
+            };
+        "#;
+        let err = parse_udl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>().unwrap(),
+            &ParseError {
+                file: None,
+                line: 2,
+                column: 13,
+                text: "namespace test {".into(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 13: `namespace test {`"
+        );
+    }
+
+    #[test]
+    fn test_collection_defaults() {
+        const UDL: &str = r#"
+            namespace test {
+                void send(optional record<string, string> headers = {}, optional string? note = null);
+            };
+            dictionary Message {
+                sequence<string> tags = [];
+                record<DOMString, string> headers = {};
+                string? note = null;
+            };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        let defaults: Vec<_> = group
+            .items
+            .into_iter()
+            .flat_map(|item| match item {
+                uniffi_meta::Metadata::Func(f) => {
+                    f.inputs.into_iter().map(|a| a.default).collect::<Vec<_>>()
+                }
+                uniffi_meta::Metadata::Record(r) => {
+                    r.fields.into_iter().map(|f| f.default).collect()
+                }
+                _ => vec![],
+            })
+            .collect();
+        assert_eq!(defaults.len(), 5);
+        for literal in [
+            uniffi_meta::LiteralMetadata::EmptySequence,
+            uniffi_meta::LiteralMetadata::EmptyMap,
+            uniffi_meta::LiteralMetadata::None,
+        ] {
+            assert!(defaults.contains(&Some(literal.clone())), "{literal:?}");
+        }
+
+        let err = parse_udl(
+            "namespace test {};\ndictionary D {\n  sequence<u32> ids = [1, 2];\n};",
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 3, column 23: `[1, 2]` isn't supported, only an empty sequence `[]` \
+             can be a default"
+        );
+        let err = parse_udl(
+            "namespace test {\n  void f(optional record<string, u8> m = {\"a\": 1});\n};",
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 2, column 42: `{\"a\": 1}` isn't supported, only an empty record \
+             `{}` can be a default"
+        );
+    }
+
+    #[test]
+    fn test_bytes_default_errors() {
+        let err = parse_udl(
+            "namespace test {};\ndictionary D {\n  bytes magic = h\"DEADBEE\";\n};",
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error at line 3, column 19: h\"DEADBEE\" has an odd number of hex digits, each byte \
+             needs two"
+        );
+    }
 }
@@ -2,7 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use anyhow::{bail, Result};
+use crate::UnlocatedError;
+use anyhow::{anyhow, bail, Result};
 use uniffi_meta::{LiteralMetadata, Radix, Type};
 
 // We are able to use LiteralMetadata directly.
@@ -14,7 +15,7 @@ pub(super) fn convert_default_value(
     type_: &Type,
 ) -> Result<LiteralMetadata> {
     fn convert_integer(literal: &weedle::literal::IntegerLit<'_>, type_: &Type) -> Result<Literal> {
-        let (string, radix) = match literal {
+        let (original, radix) = match literal {
             weedle::literal::IntegerLit::Dec(v) => (v.0, Radix::Decimal),
             weedle::literal::IntegerLit::Hex(v) => (v.0, Radix::Hexadecimal),
             weedle::literal::IntegerLit::Oct(v) => (v.0, Radix::Octal),
@@ -22,7 +23,7 @@ pub(super) fn convert_default_value(
         // This is the radix of the parsed number, passed to `from_str_radix`.
         let src_radix = radix as u32;
         // This radix tells the backends how to represent the number in the output languages.
-        let dest_radix = if string == "0" || string.starts_with('-') {
+        let dest_radix = if original == "0" || original.starts_with('-') {
             // 1. weedle parses "0" as an octal literal, but we most likely want to treat this as a decimal.
             // 2. Explicitly negatively signed hex numbers won't convert via i64 very well if they're not 64 bit.
             //    For ease of implementation, output will use decimal.
@@ -34,23 +35,47 @@ pub(super) fn convert_default_value(
         // Clippy seems to think we should be using `strip_prefix` here, but
         // it seems confused as to what this is actually doing.
         #[allow(clippy::manual_strip)]
-        let string = if string.starts_with('-') {
-            ("-".to_string() + string[1..].trim_start_matches("0x")).to_lowercase()
+        let string = if original.starts_with('-') {
+            ("-".to_string() + original[1..].trim_start_matches("0x")).to_lowercase()
         } else {
-            string.trim_start_matches("0x").to_lowercase()
+            original.trim_start_matches("0x").to_lowercase()
         };
 
+        // Literals must fit in the declared type, eg `const u8 X = 300;` is an error.
+        let out_of_range = || {
+            anyhow!(
+                "literal {} doesn't fit in {}",
+                original,
+                udl_type_name(type_)
+            )
+        };
         Ok(match type_ {
-            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => Literal::Int(
-                i64::from_str_radix(&string, src_radix)?,
-                dest_radix,
-                type_.clone(),
-            ),
-            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => Literal::UInt(
-                u64::from_str_radix(&string, src_radix)?,
-                dest_radix,
-                type_.clone(),
-            ),
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => {
+                let value = i64::from_str_radix(&string, src_radix).map_err(|_| out_of_range())?;
+                let fits = match type_ {
+                    Type::Int8 => i8::try_from(value).is_ok(),
+                    Type::Int16 => i16::try_from(value).is_ok(),
+                    Type::Int32 => i32::try_from(value).is_ok(),
+                    _ => true,
+                };
+                if !fits {
+                    return Err(out_of_range());
+                }
+                Literal::Int(value, dest_radix, type_.clone())
+            }
+            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => {
+                let value = u64::from_str_radix(&string, src_radix).map_err(|_| out_of_range())?;
+                let fits = match type_ {
+                    Type::UInt8 => u8::try_from(value).is_ok(),
+                    Type::UInt16 => u16::try_from(value).is_ok(),
+                    Type::UInt32 => u32::try_from(value).is_ok(),
+                    _ => true,
+                };
+                if !fits {
+                    return Err(out_of_range());
+                }
+                Literal::UInt(value, dest_radix, type_.clone())
+            }
 
             _ => bail!("Cannot coerce literal {} into a non-integer type", string),
         })
@@ -70,6 +95,22 @@ pub(super) fn convert_default_value(
     }
 
     Ok(match (default_value, type_) {
+        // Only empty collections are supported, but other collections are recognized so this
+        // error can point at them.
+        (weedle::literal::DefaultValue::NonEmptyArray(lit), _) => bail!(UnlocatedError::new(
+            lit.0,
+            format!(
+                "`{}` isn't supported, only an empty sequence `[]` can be a default",
+                lit.0
+            )
+        )),
+        (weedle::literal::DefaultValue::NonEmptyDictionary(lit), _) => bail!(UnlocatedError::new(
+            lit.0,
+            format!(
+                "`{}` isn't supported, only an empty record `{{}}` can be a default",
+                lit.0
+            )
+        )),
         (weedle::literal::DefaultValue::Boolean(b), Type::Boolean) => Literal::Boolean(b.0),
         (weedle::literal::DefaultValue::String(s), Type::String) => {
             // Note that weedle doesn't parse escaped double quotes.
@@ -78,16 +119,38 @@ pub(super) fn convert_default_value(
             // trying to break default values with weird escapes and quotes.
             Literal::String(s.0.to_string())
         }
+        (weedle::literal::DefaultValue::Bytes(b), Type::Bytes) => {
+            Literal::Bytes(parse_hex(b.0).map_err(|message| UnlocatedError::new(b.0, message))?)
+        }
         (weedle::literal::DefaultValue::EmptyArray(_), Type::Sequence { .. }) => {
             Literal::EmptySequence
         }
-        (weedle::literal::DefaultValue::String(s), Type::Enum { .. }) => {
-            Literal::Enum(s.0.to_string(), type_.clone())
+        (weedle::literal::DefaultValue::EmptyDictionary(_), Type::Map { .. }) => Literal::EmptyMap,
+        (weedle::literal::DefaultValue::String(s), Type::Enum { name, .. }) => {
+            // The variant can be qualified with the name of the enum, like `"LogLevel.Info"`.
+            let variant = match s.0.split_once('.') {
+                Some((enum_name, variant)) if enum_name == name => variant,
+                Some(_) => bail!("\"{}\" isn't a variant of {name}", s.0),
+                None => s.0,
+            };
+            Literal::Enum(variant.to_string(), type_.clone())
         }
         (weedle::literal::DefaultValue::Null(_), Type::Optional { .. }) => Literal::None,
         (_, Type::Optional { inner_type, .. }) => Literal::Some {
             inner: Box::new(convert_default_value(default_value, inner_type)?),
         },
+        (weedle::literal::DefaultValue::EmptyArray(_), _) => {
+            bail!("`[]` can only be the default of a sequence")
+        }
+        (weedle::literal::DefaultValue::EmptyDictionary(_), _) => {
+            bail!("`{{}}` can only be the default of a record")
+        }
+        (weedle::literal::DefaultValue::Null(_), _) => {
+            bail!("`null` can only be the default of an optional type")
+        }
+        (weedle::literal::DefaultValue::Bytes(b), _) => {
+            bail!("`h\"{}\"` can only be the default of bytes", b.0)
+        }
 
         // We'll ensure the type safety in the convert_* number methods.
         (weedle::literal::DefaultValue::Integer(i), _) => convert_integer(i, type_)?,
@@ -97,6 +160,37 @@ pub(super) fn convert_default_value(
     })
 }
 
+// Parse the hex digits of a `h"..."` bytes literal, two digits per byte.
+fn parse_hex(digits: &str) -> Result<Vec<u8>, String> {
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("`{c}` isn't a hex digit in h\"{digits}\""));
+    }
+    if digits.len() % 2 != 0 {
+        return Err(format!(
+            "h\"{digits}\" has an odd number of hex digits, each byte needs two"
+        ));
+    }
+    Ok((0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap())
+        .collect())
+}
+
+// The name of a numeric type as it's written in UDL, for error messages.
+fn udl_type_name(type_: &Type) -> &'static str {
+    match type_ {
+        Type::Int8 => "i8",
+        Type::Int16 => "i16",
+        Type::Int32 => "i32",
+        Type::Int64 => "i64",
+        Type::UInt8 => "u8",
+        Type::UInt16 => "u16",
+        Type::UInt32 => "u32",
+        Type::UInt64 => "u64",
+        _ => "a non-integer type",
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -130,6 +224,9 @@ mod test {
         assert!(
             matches!(parse_and_convert("\"one\"", Type::Enum { name: "E".into(), module_path: "".into() })?, Literal::Enum(v, Type::Enum { name, .. }) if v == "one" && name == "E")
         );
+        assert!(
+            matches!(parse_and_convert("\"E.one\"", Type::Enum { name: "E".into(), module_path: "".into() })?, Literal::Enum(v, Type::Enum { name, .. }) if v == "one" && name == "E")
+        );
         assert!(matches!(
             parse_and_convert(
                 "[]",
@@ -148,6 +245,27 @@ mod test {
             )?,
             Literal::None
         ));
+        assert!(matches!(
+            parse_and_convert(
+                "{}",
+                Type::Map {
+                    key_type: Box::new(Type::String),
+                    value_type: Box::new(Type::String)
+                }
+            )?,
+            Literal::EmptyMap
+        ));
+        assert!(matches!(
+            parse_and_convert(
+                "[]",
+                Type::Optional {
+                    inner_type: Box::new(Type::Sequence {
+                        inner_type: Box::new(Type::String)
+                    })
+                }
+            )?,
+            Literal::Some { inner } if *inner == Literal::EmptySequence
+        ));
         Ok(())
     }
     #[test]
@@ -158,9 +276,115 @@ mod test {
                 .to_string(),
             "Cannot coerce literal 0 into a non-integer type"
         );
-        assert!(parse_and_convert("{}", Type::Boolean)
+        for (expr, message) in [
+            ("[]", "`[]` can only be the default of a sequence"),
+            ("{}", "`{}` can only be the default of a record"),
+            ("null", "`null` can only be the default of an optional type"),
+            (
+                "[true]",
+                "`[true]` isn't supported, only an empty sequence `[]` can be a default",
+            ),
+        ] {
+            assert_eq!(
+                parse_and_convert(expr, Type::Boolean)
+                    .unwrap_err()
+                    .to_string(),
+                message
+            );
+        }
+        assert_eq!(
+            parse_and_convert(
+                "\"Other.one\"",
+                Type::Enum {
+                    name: "E".into(),
+                    module_path: "".into()
+                }
+            )
             .unwrap_err()
-            .to_string()
-            .starts_with("No support for"));
+            .to_string(),
+            "\"Other.one\" isn't a variant of E"
+        );
+    }
+
+    #[test]
+    fn test_bytes_defaults() {
+        assert_eq!(
+            parse_and_convert("h\"DEADbeef\"", Type::Bytes).unwrap(),
+            Literal::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            parse_and_convert("h\"\"", Type::Bytes).unwrap(),
+            Literal::Bytes(vec![])
+        );
+        assert_eq!(
+            parse_and_convert(
+                "h\"00\"",
+                Type::Optional {
+                    inner_type: Box::new(Type::Bytes)
+                }
+            )
+            .unwrap(),
+            Literal::Some {
+                inner: Box::new(Literal::Bytes(vec![0]))
+            }
+        );
+        for (expr, t, message) in [
+            (
+                "h\"ABC\"",
+                Type::Bytes,
+                "h\"ABC\" has an odd number of hex digits, each byte needs two",
+            ),
+            (
+                "h\"DE AD\"",
+                Type::Bytes,
+                "` ` isn't a hex digit in h\"DE AD\"",
+            ),
+            (
+                "h\"0x00\"",
+                Type::Bytes,
+                "`x` isn't a hex digit in h\"0x00\"",
+            ),
+            (
+                "h\"00\"",
+                Type::String,
+                "`h\"00\"` can only be the default of bytes",
+            ),
+        ] {
+            assert_eq!(parse_and_convert(expr, t).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_error_on_out_of_range() {
+        assert!(matches!(
+            parse_and_convert("255", Type::UInt8).unwrap(),
+            Literal::UInt(255, Radix::Decimal, Type::UInt8)
+        ));
+        assert!(matches!(
+            parse_and_convert("-128", Type::Int8).unwrap(),
+            Literal::Int(-128, Radix::Decimal, Type::Int8)
+        ));
+        for (expr, t, message) in [
+            ("300", Type::UInt8, "literal 300 doesn't fit in u8"),
+            (
+                "0x10000",
+                Type::UInt16,
+                "literal 0x10000 doesn't fit in u16",
+            ),
+            ("-1", Type::UInt32, "literal -1 doesn't fit in u32"),
+            ("-129", Type::Int8, "literal -129 doesn't fit in i8"),
+            (
+                "2147483648",
+                Type::Int32,
+                "literal 2147483648 doesn't fit in i32",
+            ),
+            (
+                "18446744073709551616",
+                Type::UInt64,
+                "literal 18446744073709551616 doesn't fit in u64",
+            ),
+        ] {
+            assert_eq!(parse_and_convert(expr, t).unwrap_err().to_string(), message);
+        }
     }
 }
@@ -15,6 +15,7 @@
 use anyhow::{bail, Result};
 
 use super::{Type, TypeCollector};
+use crate::UnlocatedError;
 
 /// Trait to help resolving an UDL type node to a [`Type`].
 ///
@@ -144,6 +145,21 @@ impl TypeResolver for weedle::types::RecordKeyType<'_> {
 impl TypeResolver for weedle::types::RecordType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
         let key_type = self.generics.body.0.resolve_type_expression(types)?;
+        if !types.is_valid_map_key(&key_type) {
+            let message = format!(
+                "`{}` can't be used as a map key: keys must be integers, booleans, strings or \
+                 enums without fields",
+                uniffi_meta::udl_type_name(&key_type)
+            );
+            // Named types are identifiers, which weedle gives us as a slice of the UDL, so the
+            // error can have their position.
+            match self.generics.body.0.as_ref() {
+                weedle::types::RecordKeyType::NonAny(weedle::types::NonAnyType::Identifier(id)) => {
+                    bail!(UnlocatedError::new(id.type_.0, message))
+                }
+                _ => bail!(message),
+            }
+        }
         let value_type = self.generics.body.2.resolve_type_expression(types)?;
         let map = Type::Map {
             key_type: Box::new(key_type),
@@ -205,6 +221,9 @@ pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "i32" => Some(Type::Int32),
         "u64" => Some(Type::UInt64),
         "i64" => Some(Type::Int64),
+        "u128" => Some(Type::UInt128),
+        "i128" => Some(Type::Int128),
+        "char" => Some(Type::Char),
         "f32" => Some(Type::Float32),
         "f64" => Some(Type::Float64),
         "timestamp" => Some(Type::Timestamp),
@@ -248,6 +267,34 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_128_bit_int_resolution() -> Result<()> {
+        let mut types = TypeCollector::default();
+        let (_, expr) = weedle::types::Type::parse("u128").unwrap();
+        assert_eq!(types.resolve_type_expression(expr)?, Type::UInt128);
+        let (_, expr) = weedle::types::Type::parse("sequence<i128>").unwrap();
+        assert_eq!(
+            types.resolve_type_expression(expr)?,
+            Type::Sequence {
+                inner_type: Box::new(Type::Int128)
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_char_resolution() -> Result<()> {
+        let mut types = TypeCollector::default();
+        let (_, expr) = weedle::types::Type::parse("char?").unwrap();
+        assert_eq!(
+            types.resolve_type_expression(expr)?,
+            Type::Optional {
+                inner_type: Box::new(Type::Char)
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_error_on_unknown_type() -> Result<()> {
         let mut types = TypeCollector::default();
@@ -1,5 +1,5 @@
 use crate::attribute::ExtendedAttributeList;
-use crate::common::{Default, Identifier, Punctuated};
+use crate::common::{Default, Docstring, Identifier, Punctuated};
 use crate::types::{AttributedType, Type};
 
 /// Parses a list of argument. Ex: `double v1, double v2, double v3, optional double alpha`
@@ -8,10 +8,11 @@ pub type ArgumentList<'a> = Punctuated<Argument<'a>, term!(,)>;
 ast_types! {
     /// Parses an argument. Ex: `double v1|double... v1s`
     enum Argument<'a> {
-        /// Parses `[attributes]? optional? attributedtype identifier ( = default )?`
+        /// Parses `docstring? [attributes]? optional? attributedtype identifier ( = default )?`
         ///
         /// Note: `= default` is only allowed if `optional` is present
         Single(struct SingleArgument<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             optional: Option<term!(optional)>,
             type_: AttributedType<'a>,
@@ -37,6 +38,13 @@ mod test {
     use crate::literal::{DecLit, DefaultValue, IntegerLit};
     use crate::Parse;
 
+    test!(should_parse_single_argument_with_docstring { "/// The code\n  short a" =>
+        "";
+        SingleArgument;
+        docstring == Some(Docstring(" The code".to_string()));
+        identifier.0 == "a";
+    });
+
     test!(should_parse_single_argument { "short a" =>
         "";
         SingleArgument;
@@ -26,7 +26,7 @@ use self::attribute::ExtendedAttributeList;
 use self::common::{Braced, Docstring, Identifier, Parenthesized, PunctuatedNonEmpty};
 use self::dictionary::DictionaryMembers;
 use self::interface::{Inheritance, InterfaceMembers};
-use self::literal::StringLit;
+use self::literal::{IntegerLit, StringLit};
 use self::mixin::MixinMembers;
 use self::namespace::NamespaceMembers;
 use self::types::{AttributedType, ReturnType};
@@ -233,6 +233,13 @@ ast_types! {
     struct EnumVariant<'a> {
         docstring: Option<Docstring>,
         value: StringLit<'a>,
+        discriminant: Option<EnumVariantDiscriminant<'a>>,
+    }
+
+    /// Parses the value given to an enum variant. Ex: `= 4`
+    struct EnumVariantDiscriminant<'a> {
+        assign: term!(=),
+        value: IntegerLit<'a>,
     }
 }
 
@@ -266,6 +273,34 @@ mod test {
         values.body.list.len() == 2;
     });
 
+    test!(should_parse_enum_with_discriminants { r#"enum name { "first" = 0, "second", "third" = -0x4 }; "# =>
+        "";
+        EnumDefinition;
+        values.body.list.len() == 3;
+    });
+
+    #[test]
+    fn should_parse_enum_discriminant_values() {
+        let (_, enum_) =
+            EnumDefinition::parse(r#"enum name { "first" = 0, "second", "third" = -0x4 };"#)
+                .unwrap();
+        let discriminants: Vec<_> = enum_
+            .values
+            .body
+            .list
+            .iter()
+            .map(|v| v.discriminant.as_ref().map(|d| d.value))
+            .collect();
+        assert_eq!(
+            discriminants,
+            vec![
+                Some(IntegerLit::Oct(literal::OctLit("0"))),
+                None,
+                Some(IntegerLit::Hex(literal::HexLit("-0x4"))),
+            ]
+        );
+    }
+
     test!(should_parse_dictionary { "dictionary A { long c; long g; };" =>
         "";
         DictionaryDefinition;
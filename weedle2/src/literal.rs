@@ -1,3 +1,5 @@
+use crate::IResult;
+
 ast_types! {
     /// Represents an integer value
     #[derive(Copy)]
@@ -60,10 +62,31 @@ ast_types! {
             open_brace: term!(OpenBrace),
             close_brace: term!(CloseBrace),
         }),
+        /// Represents a non-empty `[ ... ]`, which isn't valid WebIDL but is recognized so it can be
+        /// reported as unsupported
+        #[derive(Copy)]
+        NonEmptyArray(struct NonEmptyArrayLit<'a>(
+            &'a str = crate::whitespace::ws(bracketed('[', ']')),
+        )),
+        /// Represents a non-empty `{ ... }`, which isn't valid WebIDL but is recognized so it can
+        /// be reported as unsupported
+        #[derive(Copy)]
+        NonEmptyDictionary(struct NonEmptyDictionaryLit<'a>(
+            &'a str = crate::whitespace::ws(bracketed('{', '}')),
+        )),
         Float(FloatLit<'a>),
         Integer(IntegerLit<'a>),
         Null(term!(null)),
         String(StringLit<'a>),
+        /// Represents a byte string written in hex, `h"DEADBEEF"`, which isn't valid WebIDL
+        #[derive(Copy)]
+        Bytes(struct BytesLit<'a>(
+            &'a str = crate::whitespace::ws(nom::sequence::delimited(
+                nom::bytes::complete::tag("h\""),
+                nom::bytes::complete::take_while(|c| c != '"'),
+                nom::character::complete::char('"'),
+            )),
+        )),
     }
 
     /// Represents `true`, `false`, `34.23`, `null`, `56`, ...
@@ -130,6 +153,37 @@ ast_types! {
     }
 }
 
+/// Recognizes everything from `open` to the matching `close`, skipping over nested brackets and
+/// strings.
+fn bracketed<'a>(open: char, close: char) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    move |input: &'a str| {
+        let mut depth = 0;
+        let mut in_string = false;
+        for (index, c) in input.char_indices() {
+            if in_string {
+                in_string = c != '"';
+            } else if c == '"' {
+                in_string = true;
+            } else if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    let end = index + c.len_utf8();
+                    return Ok((&input[end..], &input[..end]));
+                }
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Char,
+        )))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -231,6 +285,20 @@ mod test {
         StringLit => StringLit("this is first")
     });
 
+    test!(should_parse_bytes { r#"h"DEADbeef" ;"# =>
+        ";";
+        BytesLit => BytesLit("DEADbeef")
+    });
+
+    test!(should_parse_empty_bytes { r#"h"""# =>
+        "";
+        BytesLit => BytesLit("")
+    });
+
+    test!(err should_not_parse_unquoted_bytes { "hDEADBEEF" =>
+        BytesLit
+    });
+
     test!(should_parse_string_with_spaces { r#"  "  this is a string  "  "# =>
         "";
         StringLit => StringLit("  this is a string  ")
@@ -257,6 +325,20 @@ mod test {
         EmptyArrayLit => Default::default()
     });
 
+    test!(should_parse_non_empty_array { r#"[1, ["]", 2]] ;"# =>
+        ";";
+        NonEmptyArrayLit => NonEmptyArrayLit(r#"[1, ["]", 2]]"#)
+    });
+
+    test!(should_parse_non_empty_dictionary { r#"{"a": 1}"# =>
+        "";
+        NonEmptyDictionaryLit => NonEmptyDictionaryLit(r#"{"a": 1}"#)
+    });
+
+    test!(err should_not_parse_unclosed_array { "[1, 2" =>
+        NonEmptyArrayLit
+    });
+
     test!(should_parse_bool_true { "true" =>
         "";
         BooleanLit => BooleanLit(true)
@@ -1,7 +1,8 @@
 use crate::argument::ArgumentList;
 use crate::attribute::ExtendedAttributeList;
 use crate::common::{Docstring, Identifier, Parenthesized};
-use crate::types::{AttributedType, ReturnType};
+use crate::literal::DefaultValue;
+use crate::types::{AttributedType, ReturnType, Type};
 
 /// Parses namespace members declaration
 pub type NamespaceMembers<'a> = Vec<NamespaceMember<'a>>;
@@ -30,6 +31,17 @@ ast_types! {
             identifier: Identifier<'a>,
             semi_colon: term!(;),
         }),
+        /// Parses `[attributes]? const type identifier = value;`
+        Const(struct ConstNamespaceMember<'a> {
+            docstring: Option<Docstring>,
+            attributes: Option<ExtendedAttributeList<'a>>,
+            const_: term!(const),
+            type_: Type<'a>,
+            identifier: Identifier<'a>,
+            assign: term!(=),
+            value: DefaultValue<'a>,
+            semi_colon: term!(;),
+        }),
     }
 }
 
@@ -45,6 +57,19 @@ mod test {
         identifier.0 == "name";
     });
 
+    test!(should_parse_const_namespace_member { "const u32 max_size = 65536;" =>
+        "";
+        ConstNamespaceMember;
+        attributes.is_none();
+        identifier.0 == "max_size";
+    });
+
+    test!(should_parse_string_const_namespace_member { "const string greeting = \"hello\";" =>
+        "";
+        ConstNamespaceMember;
+        identifier.0 == "greeting";
+    });
+
     test!(should_parse_operation_namespace_member { "short (long a, long b);" =>
         "";
         OperationNamespaceMember;